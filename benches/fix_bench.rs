@@ -196,6 +196,37 @@ fn bench_decode_and_find(c: &mut Criterion) {
         });
     });
 
+    // Full-depth snapshot: 200 MD entries (600+ fields), iterated twice as a
+    // book builder reading bids then offers would.
+    let snapshot = {
+        let mut body = String::from("35=W\x0155=AAPL\x01268=200\x01");
+        for i in 0..200 {
+            body.push_str(&format!(
+                "269={}\x01270=1{i:02}.25\x01271={}\x01",
+                i % 2,
+                100 + i
+            ));
+        }
+        body.into_bytes()
+    };
+    group.throughput(Throughput::Bytes(snapshot.len() as u64));
+    group.bench_function("md_snapshot_200_entries_two_passes", |b| {
+        use fix_codec_rs::group;
+        let mut dec = Decoder::new();
+        b.iter(|| {
+            let msg = dec.decode(black_box(&snapshot)).unwrap();
+            let mut count = 0usize;
+            for side in [b"0", b"1"] {
+                for g in msg.groups(&group::MD_ENTRIES) {
+                    if g.find(tag::MD_ENTRY_TYPE).is_some_and(|f| f.value == side) {
+                        count += 1;
+                    }
+                }
+            }
+            black_box(count)
+        });
+    });
+
     group.finish();
 }
 
@@ -0,0 +1,281 @@
+use std::sync::mpsc::Sender;
+
+use crate::message::Message;
+use crate::tag::{self, Tag};
+use crate::timestamp::UtcTimestamp;
+use crate::validate::{SessionRejectReason, Violation};
+
+/// Which way a message travelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+/// What happened to a message, as recorded for compliance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditKind {
+    /// An application or session message passed every check.
+    Accepted,
+    /// The message was rejected, either by our own validation or by a
+    /// session-level Reject (35=3) carrying these reason fields.
+    Rejected {
+        reason: SessionRejectReason,
+        /// RefTagID (371) of the offending field, if known.
+        ref_tag: Option<Tag>,
+    },
+    /// A retransmission (PossDupFlag 43=Y).
+    Resent,
+    /// SequenceReset-GapFill (35=4, 123=Y) moving the sequence to
+    /// `new_seq_no` (36).
+    GapFilled { new_seq_no: Option<u64> },
+    /// Logon (35=A).
+    Logon,
+    /// Logout (35=5).
+    Logout,
+}
+
+/// One audit event, borrowing the message it describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditEvent<'a> {
+    /// Caller-chosen session label.
+    pub session: &'a [u8],
+    pub direction: Direction,
+    pub kind: AuditKind,
+    /// MsgType (35), empty when absent.
+    pub msg_type: &'a [u8],
+    /// MsgSeqNum (34), if present and numeric.
+    pub seq_num: Option<u64>,
+    /// The message bytes.
+    pub raw: &'a [u8],
+    pub at: UtcTimestamp,
+}
+
+/// An owned copy of an [`AuditEvent`], e.g. to send across threads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    pub session: Vec<u8>,
+    pub direction: Direction,
+    pub kind: AuditKind,
+    pub msg_type: Vec<u8>,
+    pub seq_num: Option<u64>,
+    pub raw: Vec<u8>,
+    pub at: UtcTimestamp,
+}
+
+impl From<&AuditEvent<'_>> for AuditRecord {
+    fn from(e: &AuditEvent<'_>) -> Self {
+        Self {
+            session: e.session.to_vec(),
+            direction: e.direction,
+            kind: e.kind,
+            msg_type: e.msg_type.to_vec(),
+            seq_num: e.seq_num,
+            raw: e.raw.to_vec(),
+            at: e.at,
+        }
+    }
+}
+
+/// Consumer of the audit stream, independent of any logging setup.
+///
+/// Implemented for closures taking a `&AuditEvent`, for `Vec<AuditRecord>`
+/// and for `mpsc::Sender<AuditRecord>` (a disconnected receiver drops
+/// events silently).
+pub trait AuditSink {
+    fn record(&mut self, event: &AuditEvent<'_>);
+}
+
+impl<F> AuditSink for F
+where
+    F: FnMut(&AuditEvent<'_>),
+{
+    #[inline]
+    fn record(&mut self, event: &AuditEvent<'_>) {
+        self(event)
+    }
+}
+
+impl AuditSink for Vec<AuditRecord> {
+    fn record(&mut self, event: &AuditEvent<'_>) {
+        self.push(event.into());
+    }
+}
+
+impl AuditSink for Sender<AuditRecord> {
+    fn record(&mut self, event: &AuditEvent<'_>) {
+        let _ = self.send(event.into());
+    }
+}
+
+/// Classify a message that passed validation: Logon, Logout, GapFill,
+/// Resent, a Reject's own reason, or plain Accepted.
+pub fn classify(msg: &Message<'_>) -> AuditKind {
+    let msg_type = msg.find(tag::MSG_TYPE).map_or(&[][..], |f| f.value);
+    match msg_type {
+        b"A" => return AuditKind::Logon,
+        b"5" => return AuditKind::Logout,
+        b"3" => {
+            return AuditKind::Rejected {
+                reason: msg
+                    .find(tag::SESSION_REJECT_REASON)
+                    .and_then(|f| f.as_u64().ok())
+                    .and_then(|c| SessionRejectReason::from_code(c as u32))
+                    .unwrap_or(SessionRejectReason::Other),
+                ref_tag: msg
+                    .find(tag::REF_TAG_ID)
+                    .and_then(|f| f.as_u64().ok())
+                    .map(|t| t as Tag),
+            };
+        }
+        b"4" if msg
+            .find(tag::GAP_FILL_FLAG)
+            .is_some_and(|f| f.value == b"Y") =>
+        {
+            return AuditKind::GapFilled {
+                new_seq_no: msg.find(tag::NEW_SEQ_NO).and_then(|f| f.as_u64().ok()),
+            };
+        }
+        _ => {}
+    }
+    if msg
+        .find(tag::POSS_DUP_FLAG)
+        .is_some_and(|f| f.value == b"Y")
+    {
+        AuditKind::Resent
+    } else {
+        AuditKind::Accepted
+    }
+}
+
+/// Emits a typed audit event per message for one session.
+///
+/// # Example
+/// ```ignore
+/// let (tx, rx) = std::sync::mpsc::channel();
+/// let mut audit = Auditor::new(b"CLIENT->BROKER", tx);
+/// if validator.validate(&msg, &mut violations) {
+///     audit.message(Direction::Inbound, &msg);
+/// } else {
+///     audit.rejected(Direction::Inbound, &msg, &violations[0]);
+/// }
+/// // elsewhere: for record in rx { store(record) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Auditor<S> {
+    session: Vec<u8>,
+    sink: S,
+}
+
+impl<S: AuditSink> Auditor<S> {
+    pub fn new(session: &[u8], sink: S) -> Self {
+        Self {
+            session: session.to_vec(),
+            sink,
+        }
+    }
+
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+
+    /// Record `msg` with the kind chosen by [`classify`]. Returns the kind.
+    pub fn message(&mut self, direction: Direction, msg: &Message<'_>) -> AuditKind {
+        let kind = classify(msg);
+        self.emit(direction, kind, msg);
+        kind
+    }
+
+    /// Record `msg` as rejected for `violation`.
+    pub fn rejected(&mut self, direction: Direction, msg: &Message<'_>, violation: &Violation<'_>) {
+        let kind = AuditKind::Rejected {
+            reason: violation.reason,
+            ref_tag: Some(violation.tag),
+        };
+        self.emit(direction, kind, msg);
+    }
+
+    /// Record `msg` with an explicit `kind`.
+    pub fn emit(&mut self, direction: Direction, kind: AuditKind, msg: &Message<'_>) {
+        self.sink.record(&AuditEvent {
+            session: &self.session,
+            direction,
+            kind,
+            msg_type: msg.find(tag::MSG_TYPE).map_or(&[][..], |f| f.value),
+            seq_num: msg.find(tag::MSG_SEQ_NUM).and_then(|f| f.as_u64().ok()),
+            raw: msg.buf,
+            at: UtcTimestamp::now(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    fn kind_of(raw: &[u8]) -> AuditKind {
+        let mut dec = Decoder::new();
+        classify(&dec.decode(raw).unwrap())
+    }
+
+    #[test]
+    fn classify_session_events() {
+        assert_eq!(kind_of(b"35=A\x0134=1\x01"), AuditKind::Logon);
+        assert_eq!(kind_of(b"35=5\x01"), AuditKind::Logout);
+        assert_eq!(
+            kind_of(b"35=4\x0143=Y\x01123=Y\x0136=20\x01"),
+            AuditKind::GapFilled {
+                new_seq_no: Some(20)
+            }
+        );
+        assert_eq!(kind_of(b"35=4\x0136=20\x01"), AuditKind::Accepted);
+        assert_eq!(kind_of(b"35=D\x0143=Y\x01"), AuditKind::Resent);
+        assert_eq!(
+            kind_of(b"35=3\x0145=7\x01371=59\x01373=5\x01"),
+            AuditKind::Rejected {
+                reason: SessionRejectReason::ValueIsIncorrect,
+                ref_tag: Some(59)
+            }
+        );
+    }
+
+    #[test]
+    fn auditor_streams_over_channel() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut audit = Auditor::new(b"S->T", tx);
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=D\x0134=9\x0159=X\x01").unwrap();
+        audit.message(Direction::Outbound, &msg);
+        audit.rejected(
+            Direction::Inbound,
+            &msg,
+            &Violation {
+                tag: tag::TIME_IN_FORCE,
+                reason: SessionRejectReason::ValueIsIncorrect,
+                value: b"X",
+            },
+        );
+        drop(audit);
+
+        let records: Vec<AuditRecord> = rx.iter().collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, AuditKind::Accepted);
+        assert_eq!(records[0].direction, Direction::Outbound);
+        assert_eq!(records[0].seq_num, Some(9));
+        assert_eq!(records[0].msg_type, b"D");
+        assert_eq!(records[1].session, b"S->T");
+        assert_eq!(
+            records[1].kind,
+            AuditKind::Rejected {
+                reason: SessionRejectReason::ValueIsIncorrect,
+                ref_tag: Some(tag::TIME_IN_FORCE)
+            }
+        );
+    }
+}
@@ -0,0 +1,578 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::group::{self, Group, GroupIter};
+use crate::message::Message;
+use crate::response::mirror_routing;
+use crate::tag::{self, Tag};
+
+/// BidRequestTransType (374) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BidRequestTransType {
+    New,
+    Cancel,
+}
+
+impl BidRequestTransType {
+    /// The character carried in tag 374.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::New => b'N',
+            Self::Cancel => b'C',
+        }
+    }
+
+    /// The type for a tag 374 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"N" => Some(Self::New),
+            b"C" => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+fn push_opt(b: &mut MessageBuilder, tag: Tag, value: Option<&[u8]>) {
+    if let Some(v) = value {
+        b.push(tag, v);
+    }
+}
+
+/// One `NO_BID_DESCRIPTORS` instance: a sector, country or index bucket of
+/// the portfolio and its liquidity figures.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BidDescriptor<'a> {
+    /// BidDescriptorType (399): `1` sector, `2` country, `3` index.
+    pub descriptor_type: &'a [u8],
+    pub descriptor: Option<&'a [u8]>,
+    /// SideValueInd (401): `1` or `2`, referring to SideValue1/SideValue2.
+    pub side_value_ind: Option<&'a [u8]>,
+    pub liquidity_value: Option<&'a [u8]>,
+    pub liquidity_num_securities: Option<&'a [u8]>,
+    pub liquidity_pct_low: Option<&'a [u8]>,
+    pub liquidity_pct_high: Option<&'a [u8]>,
+    pub efp_tracking_error: Option<&'a [u8]>,
+    pub fair_value: Option<&'a [u8]>,
+    pub outside_index_pct: Option<&'a [u8]>,
+    pub value_of_futures: Option<&'a [u8]>,
+}
+
+/// One BidRequest `NO_BID_COMPONENTS` instance: a list the bid covers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BidComponent<'a> {
+    pub list_id: &'a [u8],
+    pub side: Option<&'a [u8]>,
+    pub trading_session_id: Option<&'a [u8]>,
+    pub net_gross_ind: Option<&'a [u8]>,
+    pub settl_type: Option<&'a [u8]>,
+    pub settl_date: Option<&'a [u8]>,
+    pub account: Option<&'a [u8]>,
+}
+
+/// One BidResponse `NO_BID_COMPONENTS` instance: the commission bid for a
+/// list or a country.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BidResponseComponent<'a> {
+    pub commission: &'a [u8],
+    /// CommType (13).
+    pub comm_type: &'a [u8],
+    pub list_id: Option<&'a [u8]>,
+    pub country: Option<&'a [u8]>,
+    pub side: Option<&'a [u8]>,
+    pub price: Option<&'a [u8]>,
+    pub price_type: Option<&'a [u8]>,
+    pub fair_value: Option<&'a [u8]>,
+    pub net_gross_ind: Option<&'a [u8]>,
+    pub text: Option<&'a [u8]>,
+}
+
+/// Read access to a BidRequest (35=k).
+///
+/// # Example
+/// ```ignore
+/// let req = BidRequest::new(&msg)?;
+/// for d in req.descriptors() {
+///     sectors.add(d.find(tag::BID_DESCRIPTOR), d.find(tag::LIQUIDITY_VALUE));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BidRequest<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> BidRequest<'a> {
+    /// `InvalidValue` if `msg` is not a BidRequest.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        match value(msg, tag::MSG_TYPE) {
+            Some(b"k") => Ok(Self { msg }),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+
+    /// BidID (390), assigned by the sell side; absent on a first request.
+    pub fn bid_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::BID_ID)
+    }
+
+    /// ClientBidID (391).
+    pub fn client_bid_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CLIENT_BID_ID)
+    }
+
+    /// BidRequestTransType (374), `None` if absent or not a known type.
+    pub fn trans_type(&self) -> Option<BidRequestTransType> {
+        value(self.msg, tag::BID_REQUEST_TRANS_TYPE).and_then(BidRequestTransType::from_code)
+    }
+
+    pub fn list_name(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::LIST_NAME)
+    }
+
+    /// TotalNumSecurities (393) across the whole portfolio.
+    pub fn total_num_securities(&self) -> Option<u64> {
+        self.msg
+            .find(tag::TOT_NO_RELATED_SYM)
+            .and_then(|f| f.as_u64().ok())
+    }
+
+    /// BidType (394): `1` non-disclosed, `2` disclosed, `3` no bidding
+    /// process.
+    pub fn bid_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::BID_TYPE)
+    }
+
+    pub fn currency(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CURRENCY)
+    }
+
+    /// `(SideValue1 (396), SideValue2 (397))`.
+    pub fn side_values(&self) -> (Option<&'a [u8]>, Option<&'a [u8]>) {
+        (
+            value(self.msg, tag::SIDE_VALUE1),
+            value(self.msg, tag::SIDE_VALUE2),
+        )
+    }
+
+    /// TradeType (418): `R` risk, `G` VWAP guarantee, `A` agency, `J`
+    /// guaranteed close.
+    pub fn trade_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRADE_TYPE)
+    }
+
+    /// BasisPxType (419).
+    pub fn basis_px_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::BASIS_PX_TYPE)
+    }
+
+    /// The `NO_BID_DESCRIPTORS` instances.
+    pub fn descriptors(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::BID_DESCRIPTORS)
+    }
+
+    /// The `NO_BID_COMPONENTS` instances.
+    pub fn components(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::BID_COMPONENTS)
+    }
+
+    /// ListID (66) of every component.
+    pub fn list_ids(&self) -> impl Iterator<Item = &'a [u8]> + use<'a> {
+        self.components()
+            .filter_map(|g| g.find(tag::LIST_ID).map(|f| f.value))
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of a BidRequest (35=k).
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// BidRequestBuilder::new(b"CB1", BidRequestTransType::New, 250, b"2", b"A", b"2")
+///     .list_name(b"EUROPE-Q3")
+///     .side_values(b"1500000", b"900000")
+///     .descriptor(BidDescriptor {
+///         descriptor_type: b"1",
+///         descriptor: Some(b"Banks"),
+///         side_value_ind: Some(b"1"),
+///         ..Default::default()
+///     })
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BidRequestBuilder<'a> {
+    client_bid_id: &'a [u8],
+    trans_type: BidRequestTransType,
+    total_num_securities: u32,
+    bid_type: &'a [u8],
+    trade_type: &'a [u8],
+    basis_px_type: &'a [u8],
+    bid_id: Option<&'a [u8]>,
+    list_name: Option<&'a [u8]>,
+    num_tickets: Option<u32>,
+    currency: Option<&'a [u8]>,
+    side_values: Option<(&'a [u8], &'a [u8])>,
+    strike_time: Option<&'a [u8]>,
+    text: Option<&'a [u8]>,
+    descriptors: Vec<BidDescriptor<'a>>,
+    components: Vec<BidComponent<'a>>,
+}
+
+impl<'a> BidRequestBuilder<'a> {
+    pub fn new(
+        client_bid_id: &'a [u8],
+        trans_type: BidRequestTransType,
+        total_num_securities: u32,
+        bid_type: &'a [u8],
+        trade_type: &'a [u8],
+        basis_px_type: &'a [u8],
+    ) -> Self {
+        Self {
+            client_bid_id,
+            trans_type,
+            total_num_securities,
+            bid_type,
+            trade_type,
+            basis_px_type,
+            bid_id: None,
+            list_name: None,
+            num_tickets: None,
+            currency: None,
+            side_values: None,
+            strike_time: None,
+            text: None,
+            descriptors: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    /// BidID (390), when following up on a bid the sell side already
+    /// answered.
+    pub fn bid_id(mut self, bid_id: &'a [u8]) -> Self {
+        self.bid_id = Some(bid_id);
+        self
+    }
+
+    pub fn list_name(mut self, list_name: &'a [u8]) -> Self {
+        self.list_name = Some(list_name);
+        self
+    }
+
+    pub fn num_tickets(mut self, num_tickets: u32) -> Self {
+        self.num_tickets = Some(num_tickets);
+        self
+    }
+
+    pub fn currency(mut self, currency: &'a [u8]) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    /// SideValue1 (396) and SideValue2 (397).
+    pub fn side_values(mut self, side_value1: &'a [u8], side_value2: &'a [u8]) -> Self {
+        self.side_values = Some((side_value1, side_value2));
+        self
+    }
+
+    pub fn strike_time(mut self, time: &'a [u8]) -> Self {
+        self.strike_time = Some(time);
+        self
+    }
+
+    pub fn text(mut self, text: &'a [u8]) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Add a `NO_BID_DESCRIPTORS` instance.
+    pub fn descriptor(mut self, descriptor: BidDescriptor<'a>) -> Self {
+        self.descriptors.push(descriptor);
+        self
+    }
+
+    /// Add a `NO_BID_COMPONENTS` instance, for a disclosed bid.
+    pub fn component(mut self, component: BidComponent<'a>) -> Self {
+        self.components.push(component);
+        self
+    }
+
+    /// Append MsgType `k` and the bid request fields to `b`. Header fields
+    /// other than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"k");
+        push_opt(b, tag::BID_ID, self.bid_id);
+        b.push(tag::CLIENT_BID_ID, self.client_bid_id)
+            .push(tag::BID_REQUEST_TRANS_TYPE, &[self.trans_type.code()]);
+        push_opt(b, tag::LIST_NAME, self.list_name);
+        b.push_u32(tag::TOT_NO_RELATED_SYM, self.total_num_securities)
+            .push(tag::BID_TYPE, self.bid_type);
+        if let Some(n) = self.num_tickets {
+            b.push_u32(tag::NUM_TICKETS, n);
+        }
+        push_opt(b, tag::CURRENCY, self.currency);
+        if let Some((v1, v2)) = self.side_values {
+            b.push(tag::SIDE_VALUE1, v1).push(tag::SIDE_VALUE2, v2);
+        }
+
+        let mut g = b.begin_group(&group::BID_DESCRIPTORS);
+        for d in &self.descriptors {
+            g.instance()
+                .push(tag::BID_DESCRIPTOR_TYPE, d.descriptor_type);
+            for (t, v) in [
+                (tag::BID_DESCRIPTOR, d.descriptor),
+                (tag::SIDE_VALUE_IND, d.side_value_ind),
+                (tag::LIQUIDITY_VALUE, d.liquidity_value),
+                (tag::LIQUIDITY_NUM_SECURITIES, d.liquidity_num_securities),
+                (tag::LIQUIDITY_PCT_LOW, d.liquidity_pct_low),
+                (tag::LIQUIDITY_PCT_HIGH, d.liquidity_pct_high),
+                (tag::EFP_TRACKING_ERROR, d.efp_tracking_error),
+                (tag::FAIR_VALUE, d.fair_value),
+                (tag::OUTSIDE_INDEX_PCT, d.outside_index_pct),
+                (tag::VALUE_OF_FUTURES, d.value_of_futures),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+        }
+        drop(g);
+
+        let mut g = b.begin_group(&group::BID_COMPONENTS);
+        for c in &self.components {
+            g.instance().push(tag::LIST_ID, c.list_id);
+            for (t, v) in [
+                (tag::SIDE, c.side),
+                (tag::TRADING_SESSION_ID, c.trading_session_id),
+                (tag::NET_GROSS_IND, c.net_gross_ind),
+                (tag::SETTL_TYPE, c.settl_type),
+                (tag::SETTL_DATE, c.settl_date),
+                (tag::ACCOUNT, c.account),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+        }
+        drop(g);
+
+        b.push(tag::TRADE_TYPE, self.trade_type)
+            .push(tag::BASIS_PX_TYPE, self.basis_px_type);
+        push_opt(b, tag::STRIKE_TIME, self.strike_time);
+        push_opt(b, tag::TEXT, self.text);
+    }
+}
+
+/// Read access to a BidResponse (35=l).
+#[derive(Debug, Clone, Copy)]
+pub struct BidResponse<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> BidResponse<'a> {
+    /// `InvalidValue` if `msg` is not a BidResponse.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        match value(msg, tag::MSG_TYPE) {
+            Some(b"l") => Ok(Self { msg }),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+
+    /// BidID (390).
+    pub fn bid_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::BID_ID)
+    }
+
+    /// ClientBidID (391).
+    pub fn client_bid_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CLIENT_BID_ID)
+    }
+
+    /// The `NO_BID_COMPONENTS` instances, read with
+    /// [`group::BID_COMPONENT_RESPONSES`].
+    pub fn components(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::BID_COMPONENT_RESPONSES)
+    }
+
+    /// The component bidding on `list_id`, if any.
+    pub fn component_for_list(&self, list_id: &[u8]) -> Option<Group<'a>> {
+        self.components()
+            .find(|g| g.find(tag::LIST_ID).is_some_and(|f| f.value == list_id))
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of a BidResponse (35=l).
+///
+/// # Example
+/// ```ignore
+/// let req = BidRequest::new(&msg)?;
+/// let mut b = MessageBuilder::new();
+/// BidResponseBuilder::from_request(&req, b"BID42")
+///     .component(BidResponseComponent {
+///         commission: b"0.0012",
+///         comm_type: b"1",
+///         list_id: Some(b"L1"),
+///         ..Default::default()
+///     })
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BidResponseBuilder<'a> {
+    request: Option<&'a Message<'a>>,
+    bid_id: Option<&'a [u8]>,
+    client_bid_id: Option<&'a [u8]>,
+    components: Vec<BidResponseComponent<'a>>,
+}
+
+impl<'a> BidResponseBuilder<'a> {
+    pub fn new(bid_id: &'a [u8]) -> Self {
+        Self {
+            request: None,
+            bid_id: Some(bid_id),
+            client_bid_id: None,
+            components: Vec::new(),
+        }
+    }
+
+    /// A response to `request` with BidID `bid_id`: ClientBidID is echoed
+    /// and the request's routing IDs are mirrored.
+    pub fn from_request(request: &BidRequest<'a>, bid_id: &'a [u8]) -> Self {
+        Self {
+            request: Some(request.msg),
+            bid_id: Some(bid_id),
+            client_bid_id: request.client_bid_id(),
+            components: Vec::new(),
+        }
+    }
+
+    /// ClientBidID (391).
+    pub fn client_bid_id(mut self, client_bid_id: &'a [u8]) -> Self {
+        self.client_bid_id = Some(client_bid_id);
+        self
+    }
+
+    /// Add a `NO_BID_COMPONENTS` instance.
+    pub fn component(mut self, component: BidResponseComponent<'a>) -> Self {
+        self.components.push(component);
+        self
+    }
+
+    /// Append MsgType `l`, the mirrored routing IDs when built from a
+    /// request, BidID, ClientBidID and the components to `b`.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"l");
+        if let Some(request) = self.request {
+            mirror_routing(request, b);
+        }
+        push_opt(b, tag::BID_ID, self.bid_id);
+        push_opt(b, tag::CLIENT_BID_ID, self.client_bid_id);
+        let mut g = b.begin_group(&group::BID_COMPONENT_RESPONSES);
+        for c in &self.components {
+            g.instance()
+                .push(tag::COMMISSION, c.commission)
+                .push(tag::COMM_TYPE, c.comm_type);
+            for (t, v) in [
+                (tag::LIST_ID, c.list_id),
+                (tag::COUNTRY, c.country),
+                (tag::SIDE, c.side),
+                (tag::PRICE, c.price),
+                (tag::PRICE_TYPE, c.price_type),
+                (tag::FAIR_VALUE, c.fair_value),
+                (tag::NET_GROSS_IND, c.net_gross_ind),
+                (tag::TEXT, c.text),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn bid_request_round_trip() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::SENDER_COMP_ID, b"BUY")
+            .push(tag::TARGET_COMP_ID, b"SELL");
+        BidRequestBuilder::new(b"CB1", BidRequestTransType::New, 250, b"2", b"A", b"2")
+            .list_name(b"EU")
+            .side_values(b"1500000", b"900000")
+            .descriptor(BidDescriptor {
+                descriptor_type: b"1",
+                descriptor: Some(b"Banks"),
+                side_value_ind: Some(b"1"),
+                liquidity_value: Some(b"400000"),
+                ..Default::default()
+            })
+            .descriptor(BidDescriptor {
+                descriptor_type: b"2",
+                descriptor: Some(b"FR"),
+                ..Default::default()
+            })
+            .component(BidComponent {
+                list_id: b"L1",
+                side: Some(b"1"),
+                ..Default::default()
+            })
+            .component(BidComponent {
+                list_id: b"L2",
+                side: Some(b"2"),
+                ..Default::default()
+            })
+            .write_to(&mut b);
+        let mut out = Vec::new();
+        let mut enc = Encoder::new();
+        enc.encode(&b.as_message(), &mut out).unwrap();
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let req = BidRequest::new(&msg).unwrap();
+        assert_eq!(req.trans_type(), Some(BidRequestTransType::New));
+        assert_eq!(req.total_num_securities(), Some(250));
+        assert_eq!(req.descriptors().count(), 2);
+        assert_eq!(req.list_ids().collect::<Vec<_>>(), [b"L1", b"L2"]);
+        assert_eq!(req.trade_type(), Some(&b"A"[..]));
+        assert!(BidResponse::new(&msg).is_err());
+
+        let mut b = MessageBuilder::new();
+        BidResponseBuilder::from_request(&req, b"BID42")
+            .component(BidResponseComponent {
+                commission: b"0.0012",
+                comm_type: b"1",
+                list_id: Some(b"L2"),
+                ..Default::default()
+            })
+            .write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 49, 56, 390, 391, 420, 12, 13, 66]);
+        let reply = b.as_message();
+        assert_eq!(reply.find(tag::TARGET_COMP_ID).unwrap().value, b"BUY");
+        let rsp = BidResponse::new(&reply).unwrap();
+        assert_eq!(rsp.client_bid_id(), Some(&b"CB1"[..]));
+        let c = rsp.component_for_list(b"L2").unwrap();
+        assert_eq!(c.find(tag::COMMISSION).unwrap().value, b"0.0012");
+        assert!(rsp.component_for_list(b"L1").is_none());
+    }
+}
@@ -0,0 +1,672 @@
+use std::collections::BTreeMap;
+
+use crate::decimal::FixDecimal;
+use crate::encoder::u32_to_ascii;
+use crate::error::FixError;
+use crate::field::{FIELD_KEY_VALUE_SEPARATOR, FIELD_SEPARATOR, Field};
+use crate::group::{Group, GroupSpec, group_end, instance_end, spec_for_count};
+use crate::message::{MAX_MESSAGE_LEN, Message};
+use crate::tag::Tag;
+
+/// An owned, append-only list of FIX fields used to construct outbound
+/// messages.
+///
+/// Fields are written in wire format (`tag=value\x01`) into an internal byte
+/// buffer, alongside the same `(tag, value_start, value_end)` offset index the
+/// decoder produces. [`as_message`] therefore exposes the builder as an
+/// ordinary zero-copy [`Message`], which can be passed straight to
+/// [`Encoder::encode`](crate::encoder::Encoder::encode) — tags 9 and 10 are
+/// computed there, so the builder never needs to contain them.
+///
+/// Like `Decoder` and `Encoder`, a builder is meant to be reused: [`clear`]
+/// keeps the allocated capacity.
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// b.push(tag::MSG_TYPE, b"D")
+///     .push(tag::CL_ORD_ID, b"ORD001")
+///     .push(tag::SYMBOL, b"AAPL");
+/// enc.encode(&b.as_message(), &mut out)?;
+/// ```
+///
+/// [`as_message`]: MessageBuilder::as_message
+/// [`clear`]: MessageBuilder::clear
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuilder {
+    /// Wire-format bytes of every field pushed so far.
+    buf: Vec<u8>,
+    /// `(tag, value_start, value_end)` into `buf`, same layout as the decoder.
+    offsets: Vec<(Tag, u32, u32)>,
+}
+
+impl MessageBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty builder pre-allocated for `fields` fields and `bytes`
+    /// bytes of wire data.
+    pub fn with_capacity(fields: usize, bytes: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(bytes),
+            offsets: Vec::with_capacity(fields),
+        }
+    }
+
+    /// Remove every field, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.offsets.clear();
+    }
+
+    /// Number of fields pushed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if no fields have been pushed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Append `tag=value`.
+    ///
+    /// # Panics
+    /// If the message would exceed [`MAX_MESSAGE_LEN`] bytes.
+    pub fn push(&mut self, tag: Tag, value: &[u8]) -> &mut Self {
+        let (digits, pos) = u32_to_ascii(tag);
+        self.buf.extend_from_slice(&digits[pos..]);
+        self.buf.push(FIELD_KEY_VALUE_SEPARATOR);
+        let start = self.buf.len() as u32;
+        self.buf.extend_from_slice(value);
+        self.check_len();
+        let end = self.buf.len() as u32;
+        self.buf.push(FIELD_SEPARATOR);
+        self.offsets.push((tag, start, end));
+        self
+    }
+
+    /// Append `tag=n` with `n` formatted as ASCII decimal, without allocating.
+    pub fn push_u32(&mut self, tag: Tag, n: u32) -> &mut Self {
+        let (digits, pos) = u32_to_ascii(n);
+        self.push(tag, &digits[pos..])
+    }
+
+    /// Append `tag=d` with `d` written exactly as it is scaled
+    /// (`149.50`), without allocating.
+    pub fn push_decimal(&mut self, tag: Tag, d: FixDecimal) -> &mut Self {
+        use std::io::Write;
+        // Sign, 39 digits of i128, '.' and up to MAX_SCALE leading zeros.
+        let mut digits = [0u8; 64];
+        let mut rest = &mut digits[..];
+        let _ = write!(rest, "{d}");
+        let len = 64 - rest.len();
+        self.push(tag, &digits[..len])
+    }
+
+    /// Append `tag=n` for values that may exceed `u32`, such as sequence
+    /// numbers, without allocating.
+    pub fn push_u64(&mut self, tag: Tag, n: u64) -> &mut Self {
+        let mut digits = [0u8; 20];
+        let mut pos = digits.len();
+        let mut v = n;
+        loop {
+            pos -= 1;
+            digits[pos] = b'0' + (v % 10) as u8;
+            v /= 10;
+            if v == 0 {
+                break;
+            }
+        }
+        self.push(tag, &digits[pos..])
+    }
+
+    /// Start writing a repeating group described by `spec`.
+    ///
+    /// Call [`GroupWriter::instance`] before the fields of each instance.
+    /// Members are reordered into `spec` order when an instance is closed,
+    /// and the `NO_*` count tag is written in front of the first instance
+    /// when the group ends — so the count always agrees with the instances.
+    /// A group with no instances writes nothing.
+    ///
+    /// # Example
+    /// ```ignore
+    /// b.push(tag::MSG_TYPE, b"W");
+    /// let mut g = b.begin_group(&group::MD_ENTRIES);
+    /// g.instance().push(tag::MD_ENTRY_TYPE, b"0").push(tag::MD_ENTRY_PX, b"1.5");
+    /// g.instance().push(tag::MD_ENTRY_PX, b"1.6").push(tag::MD_ENTRY_TYPE, b"1");
+    /// g.end_group()?; // writes 268=2 before the first 269
+    /// ```
+    pub fn begin_group<'a>(&'a mut self, spec: &'a GroupSpec) -> GroupWriter<'a> {
+        GroupWriter::new(self, spec)
+    }
+
+    /// Offsets are `u32`; refuse to grow past what they can address.
+    #[inline]
+    fn check_len(&self) {
+        assert!(
+            self.buf.len() <= MAX_MESSAGE_LEN,
+            "message exceeds MAX_MESSAGE_LEN bytes"
+        );
+    }
+
+    /// Remove every field from index `len` onwards.
+    fn truncate(&mut self, len: usize) {
+        let at = self.field_start(len);
+        self.buf.truncate(at);
+        self.offsets.truncate(len);
+    }
+
+    /// Byte offset in `buf` where field `index` (its tag) begins.
+    fn field_start(&self, index: usize) -> usize {
+        match index {
+            0 => 0,
+            i => self.offsets[i - 1].2 as usize + 1,
+        }
+    }
+
+    /// Shift the offsets of every field from `from` onwards by `delta` bytes.
+    fn shift_offsets(&mut self, from: usize, delta: isize) {
+        for (_, start, end) in &mut self.offsets[from..] {
+            *start = (*start as isize + delta) as u32;
+            *end = (*end as isize + delta) as u32;
+        }
+    }
+
+    /// Insert `tag=value` so it becomes field `index`. Panics if
+    /// `index > self.len()`.
+    pub(crate) fn insert_at(&mut self, index: usize, tag: Tag, value: &[u8]) {
+        let at = self.field_start(index);
+        let (digits, pos) = u32_to_ascii(tag);
+        let tag_len = digits.len() - pos;
+        let bytes = digits[pos..]
+            .iter()
+            .copied()
+            .chain(std::iter::once(FIELD_KEY_VALUE_SEPARATOR))
+            .chain(value.iter().copied())
+            .chain(std::iter::once(FIELD_SEPARATOR));
+        self.buf.splice(at..at, bytes);
+        self.check_len();
+        self.shift_offsets(index, (tag_len + value.len() + 2) as isize);
+        let start = (at + tag_len + 1) as u32;
+        self.offsets
+            .insert(index, (tag, start, start + value.len() as u32));
+    }
+
+    /// Replace the value of field `index`. Panics if `index >= self.len()`.
+    pub(crate) fn replace_at(&mut self, index: usize, value: &[u8]) {
+        let (_, start, end) = self.offsets[index];
+        self.buf
+            .splice(start as usize..end as usize, value.iter().copied());
+        self.check_len();
+        let delta = value.len() as isize - (end - start) as isize;
+        self.offsets[index].2 = start + value.len() as u32;
+        self.shift_offsets(index + 1, delta);
+    }
+
+    /// Remove field `index`. Panics if `index >= self.len()`.
+    pub(crate) fn remove_at(&mut self, index: usize) {
+        let from = self.field_start(index);
+        let to = self.offsets[index].2 as usize + 1;
+        self.buf.drain(from..to);
+        self.offsets.remove(index);
+        self.shift_offsets(index, -((to - from) as isize));
+    }
+
+    /// View the fields pushed so far as a zero-copy [`Message`].
+    #[inline]
+    pub fn as_message(&self) -> Message<'_> {
+        Message::new(&self.buf, &self.offsets)
+    }
+}
+
+/// Append every `(tag, value)` pair in iteration order, e.g. from a
+/// config-driven or computed field set.
+///
+/// ```ignore
+/// b.extend([(tag::MSG_TYPE, "D"), (tag::SYMBOL, "AAPL")]);
+/// b.extend(extra_fields.iter().map(|(t, v)| (*t, v)));
+/// ```
+impl<V: AsRef<[u8]>> Extend<(Tag, V)> for MessageBuilder {
+    fn extend<I: IntoIterator<Item = (Tag, V)>>(&mut self, iter: I) {
+        for (tag, value) in iter {
+            self.push(tag, value.as_ref());
+        }
+    }
+}
+
+/// Append decoded fields in iteration order, e.g. a block copied from an
+/// inbound message.
+///
+/// ```ignore
+/// order.extend(quote.group_fields(&group::PARTY_IDS));
+/// order.extend(quote.fields().filter(|f| INSTRUMENT_TAGS.contains(&f.tag)));
+/// ```
+impl<'f> Extend<Field<'f>> for MessageBuilder {
+    fn extend<I: IntoIterator<Item = Field<'f>>>(&mut self, iter: I) {
+        for f in iter {
+            self.push(f.tag, f.value);
+        }
+    }
+}
+
+impl<V: AsRef<[u8]>> FromIterator<(Tag, V)> for MessageBuilder {
+    fn from_iter<I: IntoIterator<Item = (Tag, V)>>(iter: I) -> Self {
+        let mut b = Self::new();
+        b.extend(iter);
+        b
+    }
+}
+
+/// Fields are written in ascending tag order. Header tags are not moved
+/// first; push MsgType (35) and friends separately if the order matters.
+impl<V: AsRef<[u8]>> From<BTreeMap<Tag, V>> for MessageBuilder {
+    fn from(map: BTreeMap<Tag, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+/// Writes one repeating group into a [`MessageBuilder`]; created by
+/// [`MessageBuilder::begin_group`].
+///
+/// Dropping the writer ends the group like [`end_group`](Self::end_group),
+/// discarding any error.
+pub struct GroupWriter<'a> {
+    builder: &'a mut MessageBuilder,
+    spec: &'a GroupSpec,
+    /// Field index where the count tag is inserted when the group ends.
+    count_index: usize,
+    count: u32,
+    /// Field index of the first field of the open instance.
+    instance_start: usize,
+    /// `(start, end)` field ranges of nested groups in the open instance.
+    /// `end` is `usize::MAX` until the next write to this group.
+    nested: Vec<(usize, usize)>,
+    invalid: bool,
+    finished: bool,
+}
+
+impl<'a> GroupWriter<'a> {
+    fn new(builder: &'a mut MessageBuilder, spec: &'a GroupSpec) -> Self {
+        let count_index = builder.len();
+        Self {
+            builder,
+            spec,
+            count_index,
+            count: 0,
+            instance_start: count_index,
+            nested: Vec::new(),
+            invalid: false,
+            finished: false,
+        }
+    }
+
+    /// Number of instances started so far.
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Close the current instance, if any, and start a new one.
+    pub fn instance(&mut self) -> &mut Self {
+        self.close_instance();
+        self.count += 1;
+        self.instance_start = self.builder.len();
+        self
+    }
+
+    /// Append `tag=value` to the current instance.
+    pub fn push(&mut self, tag: Tag, value: &[u8]) -> &mut Self {
+        self.close_nested();
+        self.invalid |= self.count == 0;
+        self.builder.push(tag, value);
+        self
+    }
+
+    /// Append `tag=n` to the current instance, as with
+    /// [`MessageBuilder::push_u32`].
+    pub fn push_u32(&mut self, tag: Tag, n: u32) -> &mut Self {
+        let (digits, pos) = u32_to_ascii(n);
+        self.push(tag, &digits[pos..])
+    }
+
+    /// Start a new instance holding a copy of `instance`, which must be an
+    /// instance of this writer's spec (e.g. from [`Message::groups`]).
+    /// Fields after the instance's last member are not copied; nested
+    /// groups whose count tag is a member of the spec are copied whole.
+    ///
+    /// More fields may be pushed to the copied instance before the next
+    /// call to [`instance`](Self::instance).
+    pub fn copy_instance(&mut self, instance: &Group<'_>) -> &mut Self {
+        self.instance();
+        let (buf, offsets) = (instance.buf, instance.offsets);
+        let end = instance_end(offsets, buf, 0, self.spec).min(offsets.len());
+        let mut i = 0;
+        while i < end {
+            let block_end = match spec_for_count(offsets[i].0) {
+                Some(nested) if i > 0 => group_end(offsets, buf, i, nested),
+                _ => i + 1,
+            };
+            self.close_nested();
+            if block_end > i + 1 {
+                self.nested.push((self.builder.len(), usize::MAX));
+            }
+            for &(tag, start, stop) in &offsets[i..block_end] {
+                self.builder.push(tag, &buf[start as usize..stop as usize]);
+            }
+            i = block_end;
+        }
+        self
+    }
+
+    /// Start a group nested in the current instance. It is kept after the
+    /// member preceding it, or at its count tag's place if that tag is a
+    /// member of this group's spec.
+    pub fn begin_group<'b>(&'b mut self, spec: &'b GroupSpec) -> GroupWriter<'b> {
+        self.close_nested();
+        self.invalid |= self.count == 0;
+        self.nested.push((self.builder.len(), usize::MAX));
+        GroupWriter::new(self.builder, spec)
+    }
+
+    /// Close the last instance and write the count tag.
+    ///
+    /// # Errors
+    /// Returns `FixError::EncodeError` if a field was written outside an
+    /// instance, an instance lacks the delimiter tag, or a field is not a
+    /// member of the spec. The fields are still written.
+    pub fn end_group(mut self) -> Result<(), FixError> {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Result<(), FixError> {
+        self.finished = true;
+        self.close_instance();
+        if self.count > 0 {
+            let (digits, pos) = u32_to_ascii(self.count);
+            self.builder
+                .insert_at(self.count_index, self.spec.count_tag, &digits[pos..]);
+        }
+        if self.invalid {
+            Err(FixError::EncodeError)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn close_nested(&mut self) {
+        if let Some((_, end)) = self.nested.last_mut()
+            && *end == usize::MAX
+        {
+            *end = self.builder.len();
+        }
+    }
+
+    /// Sort position of `tag` within an instance: the delimiter first, then
+    /// the other members in spec order.
+    fn rank(&self, tag: Tag) -> Option<usize> {
+        if tag == self.spec.delimiter_tag {
+            return Some(0);
+        }
+        self.spec
+            .member_tags
+            .iter()
+            .position(|&t| t == tag)
+            .map(|p| p + 1)
+    }
+
+    /// Reorder the open instance into spec order.
+    fn close_instance(&mut self) {
+        self.close_nested();
+        if self.count == 0 {
+            return;
+        }
+        let msg = self.builder.as_message();
+        let end = msg.len();
+
+        // `(rank, first_field, end_field)` blocks; a nested group is one block.
+        let mut blocks: Vec<(usize, usize, usize)> = Vec::new();
+        let mut nested = self.nested.iter().peekable();
+        let mut i = self.instance_start;
+        while i < end {
+            let tag = msg.field(i).tag;
+            let block_end = match nested.peek() {
+                Some(&&(start, stop)) if start == i => {
+                    nested.next();
+                    stop
+                }
+                _ => i + 1,
+            };
+            let rank = match (self.rank(tag), blocks.last()) {
+                (Some(r), _) => r,
+                (None, Some(&(prev, _, _))) if block_end > i + 1 => prev,
+                (None, None) if block_end > i + 1 => usize::MAX,
+                (None, _) => {
+                    self.invalid = true;
+                    usize::MAX
+                }
+            };
+            blocks.push((rank, i, block_end));
+            i = block_end;
+        }
+        self.nested.clear();
+
+        if !blocks.iter().any(|&(rank, _, _)| rank == 0) {
+            self.invalid = true;
+        }
+        if blocks.is_sorted_by_key(|&(rank, _, _)| rank) {
+            return;
+        }
+        blocks.sort_by_key(|&(rank, _, _)| rank);
+        let fields: Vec<(Tag, Vec<u8>)> = blocks
+            .iter()
+            .flat_map(|&(_, from, to)| from..to)
+            .map(|i| {
+                let f = msg.field(i);
+                (f.tag, f.value.to_vec())
+            })
+            .collect();
+        self.builder.truncate(self.instance_start);
+        for (tag, value) in &fields {
+            self.builder.push(*tag, value);
+        }
+    }
+}
+
+impl Drop for GroupWriter<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::group;
+    use crate::tag;
+
+    #[test]
+    fn push_and_view_as_message() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"D").push_u32(tag::ORDER_QTY, 100);
+
+        let msg = b.as_message();
+        assert_eq!(msg.len(), 2);
+        assert_eq!(msg.field(0).tag, tag::MSG_TYPE);
+        assert_eq!(msg.field(0).value, b"D");
+        assert_eq!(msg.find(tag::ORDER_QTY).unwrap().value, b"100");
+
+        b.push_u64(tag::MSG_SEQ_NUM, u64::MAX)
+            .push_u64(tag::NEW_SEQ_NO, 0);
+        let msg = b.as_message();
+        assert_eq!(
+            msg.find(tag::MSG_SEQ_NUM).unwrap().value,
+            b"18446744073709551615"
+        );
+        assert_eq!(msg.find(tag::NEW_SEQ_NO).unwrap().value, b"0");
+
+        b.push_decimal(tag::PRICE, FixDecimal::new(14950, 2))
+            .push_decimal(tag::STOP_PX, FixDecimal::new(-5, 3))
+            .push_decimal(tag::LAST_PX, FixDecimal::new(i128::MIN, 18));
+        let msg = b.as_message();
+        assert_eq!(msg.find(tag::PRICE).unwrap().value, b"149.50");
+        assert_eq!(msg.find(tag::STOP_PX).unwrap().value, b"-0.005");
+        let widest = FixDecimal::new(i128::MIN, 18).to_string();
+        assert_eq!(msg.find(tag::LAST_PX).unwrap().value, widest.as_bytes());
+    }
+
+    #[test]
+    fn encode_builder_output_validates() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::BEGIN_STRING, b"FIX.4.2")
+            .push(tag::MSG_TYPE, b"D")
+            .push(tag::SENDER_COMP_ID, b"SENDER")
+            .push(tag::TARGET_COMP_ID, b"TARGET");
+
+        let mut enc = Encoder::new();
+        let mut out = Vec::new();
+        enc.encode(&b.as_message(), &mut out).unwrap();
+        assert!(out.starts_with(b"8=FIX.4.2\x019=25\x0135=D\x0149=SENDER\x0156=TARGET\x01"));
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        assert!(msg.validate_body_length().is_ok());
+        assert!(msg.validate_checksum().is_ok());
+    }
+
+    #[test]
+    fn clear_resets_fields() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"D");
+        b.clear();
+        assert!(b.is_empty());
+        b.push(tag::MSG_TYPE, b"0");
+        assert_eq!(b.as_message().field(0).value, b"0");
+    }
+    #[test]
+    fn group_writer_fills_count_and_orders_members() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"W");
+        let mut g = b.begin_group(&group::MD_ENTRIES);
+        g.instance()
+            .push(tag::MD_ENTRY_PX, b"1.5")
+            .push(tag::MD_ENTRY_TYPE, b"0");
+        g.instance()
+            .push(tag::MD_ENTRY_TYPE, b"1")
+            .push(tag::MD_ENTRY_PX, b"1.6");
+        assert_eq!(g.count(), 2);
+        g.end_group().unwrap();
+        b.push(tag::MD_REQ_ID, b"R1");
+
+        let mut enc = Encoder::new();
+        let mut out = Vec::new();
+        enc.encode(&b.as_message(), &mut out).unwrap();
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let tags: Vec<Tag> = msg.fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [8, 9, 35, 268, 269, 270, 269, 270, 262, 10]);
+        let prices: Vec<&[u8]> = msg
+            .groups(&group::MD_ENTRIES)
+            .map(|e| e.find(tag::MD_ENTRY_PX).unwrap().value)
+            .collect();
+        assert_eq!(prices, [&b"1.5"[..], b"1.6"]);
+    }
+
+    #[test]
+    fn group_writer_nested_groups_and_errors() {
+        let mut b = MessageBuilder::new();
+        let mut parties = b.begin_group(&group::PARTY_IDS);
+        parties.instance().push(tag::PARTY_ROLE, b"1");
+        let mut nested = parties.begin_group(&group::NESTED_PARTY_IDS);
+        nested.instance().push(tag::NESTED_PARTY_ID, b"TRADER1");
+        drop(nested);
+        parties.push(tag::PARTY_ID, b"FIRM_A");
+        parties.end_group().unwrap();
+        let values: Vec<(Tag, &[u8])> = b.as_message().fields().map(|f| (f.tag, f.value)).collect();
+        assert_eq!(
+            values,
+            [
+                (tag::NO_PARTY_IDS, &b"1"[..]),
+                (tag::PARTY_ID, b"FIRM_A"),
+                (tag::PARTY_ROLE, b"1"),
+                (tag::NO_NESTED_PARTY_IDS, b"1"),
+                (tag::NESTED_PARTY_ID, b"TRADER1"),
+            ]
+        );
+
+        let mut b = MessageBuilder::new();
+        let mut g = b.begin_group(&group::MD_ENTRIES);
+        g.instance().push(tag::MD_ENTRY_PX, b"1.5");
+        assert!(matches!(g.end_group(), Err(FixError::EncodeError)));
+
+        let mut b = MessageBuilder::new();
+        b.begin_group(&group::MD_ENTRIES).end_group().unwrap();
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn copy_instance_and_extend_from_decoded_group() {
+        let mut dec = Decoder::new();
+        let quote = dec
+            .decode(b"35=S\x01453=2\x01448=A\x01452=1\x01448=B\x01452=3\x0155=IBM\x01")
+            .unwrap();
+
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"D");
+        let mut g = b.begin_group(&group::PARTY_IDS);
+        for party in quote.groups(&group::PARTY_IDS) {
+            g.copy_instance(&party);
+        }
+        g.copy_instance(&quote.groups(&group::PARTY_IDS).next().unwrap())
+            .push(tag::PARTY_ID_SOURCE, b"D");
+        g.end_group().unwrap();
+        b.extend(quote.fields().filter(|f| f.tag == tag::SYMBOL));
+        let values: Vec<(Tag, &[u8])> = b.as_message().fields().map(|f| (f.tag, f.value)).collect();
+        assert_eq!(
+            values,
+            [
+                (tag::MSG_TYPE, &b"D"[..]),
+                (tag::NO_PARTY_IDS, b"3"),
+                (tag::PARTY_ID, b"A"),
+                (tag::PARTY_ROLE, b"1"),
+                (tag::PARTY_ID, b"B"),
+                (tag::PARTY_ROLE, b"3"),
+                (tag::PARTY_ID, b"A"),
+                (tag::PARTY_ID_SOURCE, b"D"),
+                (tag::PARTY_ROLE, b"1"),
+                (tag::SYMBOL, b"IBM"),
+            ]
+        );
+
+        let mut order = MessageBuilder::new();
+        order.extend(quote.group_fields(&group::PARTY_IDS));
+        assert_eq!(order.len(), 5);
+        assert!(order.as_message().find(tag::SYMBOL).is_none());
+    }
+
+    #[test]
+    fn extend_and_collect_from_pairs() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"D");
+        b.extend([(tag::CL_ORD_ID, "A1"), (tag::SYMBOL, "IBM")]);
+        b.extend(vec![(tag::ORDER_QTY, b"100".to_vec())]);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 11, 55, 38]);
+
+        let map = BTreeMap::from([(tag::SYMBOL, "IBM"), (tag::MSG_TYPE, "D")]);
+        let b = MessageBuilder::from(map);
+        assert_eq!(b.as_message().field(0).value, b"D");
+        assert_eq!(b.as_message().find(tag::SYMBOL).unwrap().value, b"IBM");
+
+        let b: MessageBuilder = [(tag::TEXT, String::from("hi"))].into_iter().collect();
+        assert_eq!(b.len(), 1);
+    }
+}
@@ -0,0 +1,525 @@
+//! Raw capture of every inbound and outbound message of a session, for
+//! regulatory retention and incident replay.
+//!
+//! A capture file is a sequence of records:
+//!
+//! ```text
+//! <UTCTimestamp> <I|O> <len> <len raw bytes>\n
+//! ```
+//!
+//! The byte length makes records unambiguous even when a message carries
+//! newlines in a data field. [`CaptureWriter`] rotates to a new file by size
+//! or age, optionally gzip-compressed (`gzip` feature); [`CaptureReader`]
+//! reads the records back, transparently decompressing gzip (`gzip`
+//! feature) and zstd (`zstd` feature) files.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::audit::Direction;
+use crate::encoder::u32_to_ascii;
+use crate::error::FixError;
+use crate::message::MAX_MESSAGE_LEN;
+use crate::timestamp::{TimestampPrecision, UtcTimestamp};
+
+/// Extension of uncompressed capture files.
+pub const CAPTURE_EXTENSION: &str = "fixlog";
+
+/// When a [`CaptureWriter`] starts a new file. Both limits may be set; the
+/// first one reached wins. With neither set the writer never rotates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rotation {
+    /// Rotate once the current file holds at least this many record bytes.
+    pub max_bytes: Option<u64>,
+    /// Rotate once the current file was opened this long ago.
+    pub max_age: Option<Duration>,
+}
+
+impl Rotation {
+    /// Rotate by size only.
+    pub fn by_size(max_bytes: u64) -> Self {
+        Self {
+            max_bytes: Some(max_bytes),
+            max_age: None,
+        }
+    }
+
+    /// Rotate by age only, e.g. hourly or daily slices.
+    pub fn by_age(max_age: Duration) -> Self {
+        Self {
+            max_bytes: None,
+            max_age: Some(max_age),
+        }
+    }
+}
+
+enum Output {
+    Plain(BufWriter<File>),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<BufWriter<File>>),
+}
+
+impl Output {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Self::Plain(w) => w,
+            #[cfg(feature = "gzip")]
+            Self::Gzip(w) => w,
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Self::Plain(mut w) => w.flush(),
+            #[cfg(feature = "gzip")]
+            Self::Gzip(w) => w.finish()?.flush(),
+        }
+    }
+}
+
+/// Appends raw message bytes of one session to rotating capture files.
+///
+/// Files are created in `dir` as
+/// `<session>.<YYYYMMDD-HHMMSS>.<n>.fixlog[.gz]`, named after the timestamp
+/// of their first record; `n` keeps names unique within one second.
+///
+/// # Example
+/// ```ignore
+/// let mut capture = CaptureWriter::new("/var/fix/capture", "CLIENT-BROKER")
+///     .rotation(Rotation::by_age(Duration::from_secs(3600)));
+/// capture.record(Direction::Inbound, &read_buf[..n])?;
+/// capture.record(Direction::Outbound, &out)?;
+/// ```
+pub struct CaptureWriter {
+    dir: PathBuf,
+    session: String,
+    rotation: Rotation,
+    #[cfg(feature = "gzip")]
+    gzip: bool,
+    output: Option<Output>,
+    path: Option<PathBuf>,
+    written: u64,
+    opened_at: UtcTimestamp,
+    counter: u32,
+}
+
+impl CaptureWriter {
+    /// Capture into `dir` (created on first write) under the `session` name.
+    /// No file is opened until the first record.
+    pub fn new(dir: impl Into<PathBuf>, session: &str) -> Self {
+        Self {
+            dir: dir.into(),
+            session: session.to_owned(),
+            rotation: Rotation::default(),
+            #[cfg(feature = "gzip")]
+            gzip: false,
+            output: None,
+            path: None,
+            written: 0,
+            opened_at: UtcTimestamp::new(0, 0),
+            counter: 0,
+        }
+    }
+
+    /// Set when to rotate. Defaults to never.
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Gzip-compress new capture files (`.fixlog.gz`).
+    #[cfg(feature = "gzip")]
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Path of the file currently being written, if any.
+    #[inline]
+    pub fn current_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Record `raw` as seen now.
+    pub fn record(&mut self, direction: Direction, raw: &[u8]) -> io::Result<()> {
+        self.record_at(direction, raw, UtcTimestamp::now())
+    }
+
+    /// Record `raw` with an explicit timestamp, which also drives age-based
+    /// rotation.
+    ///
+    /// # Errors
+    /// An I/O error, or `FixError::MessageTooLarge` wrapped as
+    /// `io::ErrorKind::InvalidData` if `raw` is longer than
+    /// [`MAX_MESSAGE_LEN`].
+    pub fn record_at(
+        &mut self,
+        direction: Direction,
+        raw: &[u8],
+        at: UtcTimestamp,
+    ) -> io::Result<()> {
+        if raw.len() > MAX_MESSAGE_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                FixError::MessageTooLarge { len: raw.len() },
+            ));
+        }
+        if self.output.is_none() || self.should_rotate(at) {
+            self.open(at)?;
+        }
+        let (ts, ts_len) = at.format(TimestampPrecision::Micros);
+        let dir = match direction {
+            Direction::Inbound => b'I',
+            Direction::Outbound => b'O',
+        };
+        // Fits: checked against MAX_MESSAGE_LEN (u32::MAX) above.
+        let (len_buf, len_pos) = u32_to_ascii(raw.len() as u32);
+        let len = &len_buf[len_pos..];
+
+        let w = self.output.as_mut().expect("opened above").writer();
+        w.write_all(&ts[..ts_len])?;
+        w.write_all(&[b' ', dir, b' '])?;
+        w.write_all(len)?;
+        w.write_all(b" ")?;
+        w.write_all(raw)?;
+        w.write_all(b"\n")?;
+        self.written += (ts_len + 3 + len.len() + 1 + raw.len() + 1) as u64;
+        Ok(())
+    }
+
+    /// Flush buffered records to the current file. Compressed files are only
+    /// complete once rotated or [`close`](Self::close)d.
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.output {
+            Some(o) => o.writer().flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Finish the current file. The next record opens a new one.
+    pub fn close(&mut self) -> io::Result<()> {
+        self.path = None;
+        match self.output.take() {
+            Some(o) => o.finish(),
+            None => Ok(()),
+        }
+    }
+
+    fn should_rotate(&self, at: UtcTimestamp) -> bool {
+        self.rotation
+            .max_bytes
+            .is_some_and(|max| self.written >= max)
+            || self
+                .rotation
+                .max_age
+                .is_some_and(|max| at > self.opened_at && at.abs_diff(&self.opened_at) >= max)
+    }
+
+    fn open(&mut self, at: UtcTimestamp) -> io::Result<()> {
+        self.close()?;
+        fs::create_dir_all(&self.dir)?;
+
+        let (ts, ts_len) = at.format(TimestampPrecision::Seconds);
+        let stamp: String = ts[..ts_len]
+            .iter()
+            .filter(|&&b| b != b':')
+            .map(|&b| b as char)
+            .collect();
+        #[cfg(feature = "gzip")]
+        let ext = if self.gzip { ".gz" } else { "" };
+        #[cfg(not(feature = "gzip"))]
+        let ext = "";
+        let path = loop {
+            let path = self.dir.join(format!(
+                "{}.{stamp}.{}.{CAPTURE_EXTENSION}{ext}",
+                self.session, self.counter
+            ));
+            self.counter += 1;
+            if !path.exists() {
+                break path;
+            }
+        };
+
+        let file = BufWriter::new(File::create(&path)?);
+        #[cfg(feature = "gzip")]
+        let output = if self.gzip {
+            Output::Gzip(flate2::write::GzEncoder::new(
+                file,
+                flate2::Compression::default(),
+            ))
+        } else {
+            Output::Plain(file)
+        };
+        #[cfg(not(feature = "gzip"))]
+        let output = Output::Plain(file);
+
+        self.output = Some(output);
+        self.path = Some(path);
+        self.written = 0;
+        self.opened_at = at;
+        Ok(())
+    }
+}
+
+impl Drop for CaptureWriter {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+/// One record read back from a capture file, borrowing the reader's buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureRecord<'a> {
+    pub at: UtcTimestamp,
+    pub direction: Direction,
+    pub raw: &'a [u8],
+}
+
+/// Reads the records of a capture file in order.
+///
+/// # Example
+/// ```ignore
+/// let mut reader = CaptureReader::open(path)?;
+/// while let Some(rec) = reader.next_record()? {
+///     let msg = decoder.decode(rec.raw)?;
+///     replay(rec.direction, &msg);
+/// }
+/// ```
+pub struct CaptureReader<R> {
+    inner: R,
+    header: Vec<u8>,
+    raw: Vec<u8>,
+}
+
+/// Leading bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Leading bytes of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Open `path` for buffered reading, decompressing it on the fly if it is
+/// gzip or zstd — detected from the file's magic bytes, not its extension.
+/// Works for any capture or log file.
+///
+/// # Errors
+/// `io::ErrorKind::Unsupported` for a compressed file whose codec feature
+/// (`gzip` / `zstd`) is not enabled.
+pub fn open_maybe_compressed(path: impl AsRef<Path>) -> io::Result<Box<dyn BufRead + Send>> {
+    let mut file = BufReader::new(File::open(path)?);
+    let head = file.fill_buf()?;
+    if head.starts_with(&GZIP_MAGIC) {
+        #[cfg(feature = "gzip")]
+        return Ok(Box::new(BufReader::new(
+            flate2::bufread::MultiGzDecoder::new(file),
+        )));
+        #[cfg(not(feature = "gzip"))]
+        return Err(unsupported("gzip"));
+    }
+    if head.starts_with(&ZSTD_MAGIC) {
+        #[cfg(feature = "zstd")]
+        return Ok(Box::new(BufReader::new(
+            zstd::stream::read::Decoder::with_buffer(file)?,
+        )));
+        #[cfg(not(feature = "zstd"))]
+        return Err(unsupported("zstd"));
+    }
+    Ok(Box::new(file))
+}
+
+#[cfg(not(all(feature = "gzip", feature = "zstd")))]
+fn unsupported(codec: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{codec}-compressed file requires the `{codec}` feature"),
+    )
+}
+
+impl CaptureReader<Box<dyn BufRead + Send>> {
+    /// Open a capture file, plain or compressed (see
+    /// [`open_maybe_compressed`]).
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(open_maybe_compressed(path)?))
+    }
+}
+
+impl<R: BufRead> CaptureReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            header: Vec::new(),
+            raw: Vec::new(),
+        }
+    }
+
+    /// Read the next record, or `None` at end of file.
+    ///
+    /// # Errors
+    /// `io::ErrorKind::InvalidData` if the file is not a well-formed capture;
+    /// `UnexpectedEof` if it ends inside a record.
+    pub fn next_record(&mut self) -> io::Result<Option<CaptureRecord<'_>>> {
+        let Some(ts) = self.field()? else {
+            return Ok(None);
+        };
+        let (at, _) = UtcTimestamp::parse(ts).map_err(|_| invalid("bad timestamp"))?;
+        let direction = match self.field()?.ok_or_else(eof)? {
+            b"I" => Direction::Inbound,
+            b"O" => Direction::Outbound,
+            _ => return Err(invalid("bad direction")),
+        };
+        let len: usize = std::str::from_utf8(self.field()?.ok_or_else(eof)?)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| invalid("bad length"))?;
+
+        self.raw.resize(len + 1, 0);
+        self.inner.read_exact(&mut self.raw)?;
+        if self.raw.pop() != Some(b'\n') {
+            return Err(invalid("missing record terminator"));
+        }
+        Ok(Some(CaptureRecord {
+            at,
+            direction,
+            raw: &self.raw,
+        }))
+    }
+
+    /// Read up to the next space. `None` only at a clean end of file.
+    fn field(&mut self) -> io::Result<Option<&[u8]>> {
+        self.header.clear();
+        if self.inner.read_until(b' ', &mut self.header)? == 0 {
+            return Ok(None);
+        }
+        match self.header.pop() {
+            Some(b' ') => Ok(Some(&self.header)),
+            _ => Err(eof()),
+        }
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn eof() -> io::Error {
+    io::Error::from(io::ErrorKind::UnexpectedEof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("fix-codec-capture-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn files(dir: &Path) -> Vec<PathBuf> {
+        let mut v: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn round_trip_with_newline_in_value() {
+        let dir = temp_dir("roundtrip");
+        let mut w = CaptureWriter::new(&dir, "S");
+        let at = UtcTimestamp::new(1_709_214_306, 123_456_000);
+        w.record_at(Direction::Inbound, b"8=FIX.4.4\x0158=a\nb\x01", at)
+            .unwrap();
+        w.record_at(Direction::Outbound, b"35=0\x01", at).unwrap();
+        let path = w.current_path().unwrap().to_path_buf();
+        assert!(path.ends_with("S.20240229-134506.0.fixlog"));
+        w.close().unwrap();
+
+        let mut r = CaptureReader::open(&path).unwrap();
+        let rec = r.next_record().unwrap().unwrap();
+        assert_eq!(rec.at, at);
+        assert_eq!(rec.direction, Direction::Inbound);
+        assert_eq!(rec.raw, b"8=FIX.4.4\x0158=a\nb\x01");
+        let rec = r.next_record().unwrap().unwrap();
+        assert_eq!(rec.direction, Direction::Outbound);
+        assert_eq!(rec.raw, b"35=0\x01");
+        assert!(r.next_record().unwrap().is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rotates_by_size_and_age() {
+        let dir = temp_dir("rotate");
+        let t0 = UtcTimestamp::new(1_709_214_306, 0);
+        let mut w = CaptureWriter::new(&dir, "S").rotation(Rotation {
+            max_bytes: Some(40),
+            max_age: Some(Duration::from_secs(60)),
+        });
+        // 35-byte records: the second takes the file past 40 bytes, the third rotates.
+        w.record_at(Direction::Inbound, b"35=0\x01", t0).unwrap();
+        w.record_at(Direction::Inbound, b"35=0\x01", t0).unwrap();
+        w.record_at(Direction::Inbound, b"35=0\x01", t0).unwrap();
+        let t1 = UtcTimestamp::new(t0.secs + 60, 0);
+        w.record_at(Direction::Inbound, b"35=0\x01", t1).unwrap();
+        w.close().unwrap();
+        assert_eq!(files(&dir).len(), 3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn truncated_record_is_an_error() {
+        let data = b"20240229-13:45:06.000000 I 10 35=0\x01".to_vec();
+        let mut r = CaptureReader::new(&data[..]);
+        let err = r.next_record().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzip_capture_is_compressed() {
+        let dir = temp_dir("gzip");
+        let mut w = CaptureWriter::new(&dir, "S").gzip(true);
+        w.record_at(Direction::Inbound, b"35=0\x01", UtcTimestamp::new(0, 0))
+            .unwrap();
+        let path = w.current_path().unwrap().to_path_buf();
+        drop(w);
+        assert!(path.to_string_lossy().ends_with(".fixlog.gz"));
+        let bytes = fs::read(&path).unwrap();
+        assert_eq!(&bytes[..2], &[0x1f, 0x8b]);
+
+        let mut r = CaptureReader::open(&path).unwrap();
+        assert_eq!(r.next_record().unwrap().unwrap().raw, b"35=0\x01");
+        assert!(r.next_record().unwrap().is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_capture_is_read_transparently() {
+        let dir = temp_dir("zstd");
+        fs::create_dir_all(&dir).unwrap();
+        let plain = b"20240229-13:45:06.000000 O 5 35=0\x01\n";
+        let path = dir.join("S.fixlog.zst");
+        fs::write(&path, zstd::encode_all(&plain[..], 0).unwrap()).unwrap();
+
+        let mut r = CaptureReader::open(&path).unwrap();
+        let rec = r.next_record().unwrap().unwrap();
+        assert_eq!(rec.direction, Direction::Outbound);
+        assert_eq!(rec.raw, b"35=0\x01");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn gzip_without_feature_is_unsupported() {
+        let dir = temp_dir("nogzip");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("S.fixlog.gz");
+        fs::write(&path, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+        let err = CaptureReader::open(&path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,11 +1,172 @@
+use std::sync::OnceLock;
+
+/// Implementation used by [`compute_checksum`], chosen from the CPU features
+/// detected at runtime the first time a checksum is computed.
+///
+/// Field scanning needs no equivalent: it goes through `memchr`, which does
+/// its own runtime dispatch (AVX2/SSE2 on x86-64, NEON on aarch64).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Avx2,
+    Sse2,
+    Neon,
+    Scalar,
+}
+
+impl Backend {
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Avx2 => "avx2",
+            Backend::Sse2 => "sse2",
+            Backend::Neon => "neon",
+            Backend::Scalar => "scalar",
+        }
+    }
+
+    fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if std::arch::is_x86_feature_detected!("avx2") {
+                return Backend::Avx2;
+            }
+            // SSE2 is part of the x86-64 baseline.
+            Backend::Sse2
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            // NEON is part of the aarch64 baseline.
+            Backend::Neon
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+        {
+            Backend::Scalar
+        }
+    }
+
+    fn checksum_fn(self) -> ChecksumFn {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => x86::checksum_avx2,
+            #[cfg(target_arch = "x86_64")]
+            Backend::Sse2 => x86::checksum_sse2,
+            #[cfg(target_arch = "aarch64")]
+            Backend::Neon => neon::checksum_neon,
+            _ => checksum_scalar,
+        }
+    }
+}
+
+type ChecksumFn = fn(&[u8]) -> u8;
+
+static CHECKSUM: OnceLock<(Backend, ChecksumFn)> = OnceLock::new();
+
+fn selected() -> &'static (Backend, ChecksumFn) {
+    CHECKSUM.get_or_init(|| {
+        let backend = Backend::detect();
+        (backend, backend.checksum_fn())
+    })
+}
+
+/// The checksum implementation selected for this CPU.
+pub fn backend() -> Backend {
+    selected().0
+}
+
+/// Inputs shorter than this are summed inline by [`checksum_scalar`]
+/// without touching the dispatch table: the SIMD loops would run only a few
+/// iterations, and the `OnceLock` load plus indirect call that selects them
+/// costs more than they save.
+const DISPATCH_MIN_LEN: usize = 64;
+
 /// Compute the FIX checksum over `bytes`: sum of every byte value, mod 256.
 ///
 /// The result fits in a `u8` because wrapping arithmetic is used throughout.
 #[inline]
 pub(crate) fn compute_checksum(bytes: &[u8]) -> u8 {
+    if bytes.len() < DISPATCH_MIN_LEN {
+        return checksum_scalar(bytes);
+    }
+    (selected().1)(bytes)
+}
+
+#[inline]
+fn checksum_scalar(bytes: &[u8]) -> u8 {
     bytes.iter().fold(0u8, |acc, &b| acc.wrapping_add(b))
 }
 
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    use super::checksum_scalar;
+
+    pub(super) fn checksum_avx2(bytes: &[u8]) -> u8 {
+        // SAFETY: only selected after `is_x86_feature_detected!("avx2")`.
+        unsafe { sum_avx2(bytes) }
+    }
+
+    pub(super) fn checksum_sse2(bytes: &[u8]) -> u8 {
+        // SAFETY: SSE2 is always available on x86-64.
+        unsafe { sum_sse2(bytes) }
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn sum_avx2(bytes: &[u8]) -> u8 {
+        let chunks = bytes.chunks_exact(32);
+        let rest = chunks.remainder();
+        let zero = _mm256_setzero_si256();
+        let mut acc = _mm256_setzero_si256();
+        for chunk in chunks {
+            // SAFETY: `chunk` is exactly 32 readable bytes; loadu has no
+            // alignment requirement.
+            let v = unsafe { _mm256_loadu_si256(chunk.as_ptr().cast()) };
+            // Sum of absolute differences against zero: four u64 partial sums.
+            acc = _mm256_add_epi64(acc, _mm256_sad_epu8(v, zero));
+        }
+        let mut lanes = [0u64; 4];
+        // SAFETY: `lanes` is 32 writable bytes.
+        unsafe { _mm256_storeu_si256(lanes.as_mut_ptr().cast(), acc) };
+        let sum = lanes.iter().fold(0u64, |a, &l| a.wrapping_add(l));
+        (sum as u8).wrapping_add(checksum_scalar(rest))
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn sum_sse2(bytes: &[u8]) -> u8 {
+        let chunks = bytes.chunks_exact(16);
+        let rest = chunks.remainder();
+        let zero = _mm_setzero_si128();
+        let mut acc = _mm_setzero_si128();
+        for chunk in chunks {
+            // SAFETY: `chunk` is exactly 16 readable bytes.
+            let v = unsafe { _mm_loadu_si128(chunk.as_ptr().cast()) };
+            acc = _mm_add_epi64(acc, _mm_sad_epu8(v, zero));
+        }
+        let mut lanes = [0u64; 2];
+        // SAFETY: `lanes` is 16 writable bytes.
+        unsafe { _mm_storeu_si128(lanes.as_mut_ptr().cast(), acc) };
+        (lanes[0].wrapping_add(lanes[1]) as u8).wrapping_add(checksum_scalar(rest))
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+
+    use super::checksum_scalar;
+
+    pub(super) fn checksum_neon(bytes: &[u8]) -> u8 {
+        let chunks = bytes.chunks_exact(16);
+        let rest = chunks.remainder();
+        let mut sum = 0u64;
+        for chunk in chunks {
+            // SAFETY: NEON is always available on aarch64, and `chunk` is
+            // exactly 16 readable bytes.
+            sum += u64::from(unsafe { vaddlvq_u8(vld1q_u8(chunk.as_ptr())) });
+        }
+        (sum as u8).wrapping_add(checksum_scalar(rest))
+    }
+}
+
 /// Parse the ASCII decimal checksum value stored in a FIX tag-10 field value.
 ///
 /// The value must be a decimal integer in 0–255. Returns `None` if the bytes
@@ -40,6 +201,42 @@ mod tests {
         assert_eq!(compute_checksum(b""), 0);
     }
 
+    #[test]
+    fn backends_agree_with_scalar() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 37 + i / 7) as u8).collect();
+        let mut backends = vec![backend()];
+        #[cfg(target_arch = "x86_64")]
+        backends.push(Backend::Sse2);
+        for b in backends {
+            for len in [0, 1, 15, 16, 17, 31, 32, 33, 100, 1000] {
+                let bytes = &data[..len];
+                assert_eq!(
+                    b.checksum_fn()(bytes),
+                    checksum_scalar(bytes),
+                    "{} len {len}",
+                    b.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn compute_agrees_across_dispatch_threshold() {
+        let data: Vec<u8> = (0..200u32).map(|i| (i * 53 + 11) as u8).collect();
+        for len in [
+            DISPATCH_MIN_LEN - 1,
+            DISPATCH_MIN_LEN,
+            DISPATCH_MIN_LEN + 1,
+            200,
+        ] {
+            assert_eq!(
+                compute_checksum(&data[..len]),
+                checksum_scalar(&data[..len]),
+                "len {len}"
+            );
+        }
+    }
+
     #[test]
     fn parse_valid() {
         assert_eq!(parse_checksum(b"000"), Some(0));
@@ -0,0 +1,286 @@
+use std::fmt;
+use std::io;
+
+use bytes::{Bytes, BytesMut};
+use smallvec::SmallVec;
+
+use crate::decoder::{DEFAULT_CAPACITY, ScanOptions, check_begin_string, frame_end, scan_fields};
+use crate::encoder::Encoder;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::tag::Tag;
+
+/// Error returned by [`FixCodec`]: I/O from the underlying stream, or a
+/// framing/encoding error from this crate.
+#[derive(Debug)]
+pub enum CodecError {
+    Io(io::Error),
+    Fix(FixError),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::Io(e) => write!(f, "I/O error: {e}"),
+            CodecError::Fix(e) => write!(f, "FIX error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CodecError::Io(e) => Some(e),
+            CodecError::Fix(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for CodecError {
+    fn from(e: io::Error) -> Self {
+        CodecError::Io(e)
+    }
+}
+
+impl From<FixError> for CodecError {
+    fn from(e: FixError) -> Self {
+        CodecError::Fix(e)
+    }
+}
+
+/// Default [`FixCodec::max_frame_len`]: 1 MiB, far above any ordinary
+/// message, so a BodyLength (9) claiming gigabytes fails fast instead of
+/// buffering.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 1 << 20;
+
+/// One complete message read by [`FixCodec`].
+///
+/// The bytes are split off the read buffer without copying; only the field
+/// offsets are owned. Use [`as_message`](Self::as_message) for the usual
+/// zero-copy [`Message`] API.
+#[derive(Debug, Clone)]
+pub struct FixFrame {
+    bytes: Bytes,
+    offsets: SmallVec<[(Tag, u32, u32); DEFAULT_CAPACITY]>,
+}
+
+impl FixFrame {
+    /// View the frame as a zero-copy [`Message`].
+    #[inline]
+    pub fn as_message(&self) -> Message<'_> {
+        Message::new(&self.bytes, &self.offsets)
+    }
+
+    /// The raw message bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Take the raw message bytes, e.g. to forward them unchanged.
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+}
+
+/// `tokio_util` codec framing FIX messages on BodyLength (9) and CheckSum
+/// (10), for use with `Framed<TcpStream, FixCodec>`.
+///
+/// Reading yields one [`FixFrame`] per complete message; partial messages
+/// stay buffered until the rest arrives. Writing encodes a [`Message`]
+/// with the wrapped [`Encoder`], so 9 and 10 are computed and any
+/// configured pipeline stages run.
+///
+/// The stream must start at a message boundary: bytes that do not begin
+/// with `8=` fail with `FixError::DecodeError`.
+///
+/// # Example
+/// ```ignore
+/// let mut framed = Framed::new(stream, FixCodec::new());
+/// while let Some(frame) = framed.next().await {
+///     let frame = frame?;
+///     let msg = frame.as_message();
+///     framed.send(&reply.as_message()).await?;
+/// }
+/// ```
+pub struct FixCodec {
+    encoder: Encoder,
+    max_frame_len: Option<usize>,
+    begin_strings: Vec<Box<[u8]>>,
+}
+
+impl Default for FixCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FixCodec {
+    pub fn new() -> Self {
+        Self::with_encoder(Encoder::new())
+    }
+
+    /// Use a preconfigured `encoder` (timestamp policy, stages, ...) for
+    /// writes.
+    pub fn with_encoder(encoder: Encoder) -> Self {
+        Self {
+            encoder,
+            max_frame_len: Some(DEFAULT_MAX_FRAME_LEN),
+            begin_strings: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn encoder_mut(&mut self) -> &mut Encoder {
+        &mut self.encoder
+    }
+
+    /// Fail with `FixError::MessageTooLarge` once more than `max` bytes are
+    /// buffered without completing a message, or a frame is longer than
+    /// `max`. Defaults to [`DEFAULT_MAX_FRAME_LEN`]; `None` buffers without
+    /// limit.
+    pub fn max_frame_len(&mut self, max: Option<usize>) -> &mut Self {
+        self.max_frame_len = max;
+        self
+    }
+
+    /// Fail with `FixError::UnexpectedBeginString` on frames whose
+    /// BeginString (8) is not one of `allowed`; see
+    /// [`Decoder::begin_strings`](crate::decoder::Decoder::begin_strings).
+    /// An empty list (the default) accepts any.
+    pub fn begin_strings<I, B>(&mut self, allowed: I) -> &mut Self
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        self.begin_strings = allowed.into_iter().map(|b| b.as_ref().into()).collect();
+        self
+    }
+}
+
+impl tokio_util::codec::Decoder for FixCodec {
+    type Item = FixFrame;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<FixFrame>, CodecError> {
+        let too_long = |len: usize| self.max_frame_len.is_some_and(|max| len > max);
+        let Some(len) = frame_end(src)? else {
+            if too_long(src.len()) {
+                return Err(FixError::MessageTooLarge { len: src.len() }.into());
+            }
+            return Ok(None);
+        };
+        if too_long(len) {
+            return Err(FixError::MessageTooLarge { len }.into());
+        }
+        let bytes = src.split_to(len).freeze();
+        let mut offsets = SmallVec::new();
+        scan_fields(&bytes, 0, &mut offsets, ScanOptions::default())?;
+        check_begin_string(&bytes, &offsets, &self.begin_strings)?;
+        Ok(Some(FixFrame { bytes, offsets }))
+    }
+}
+
+impl tokio_util::codec::Encoder<&Message<'_>> for FixCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, msg: &Message<'_>, dst: &mut BytesMut) -> Result<(), CodecError> {
+        self.encoder.encode_to_buf(msg, dst)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+    use crate::tag;
+    use tokio_util::codec::{Decoder as _, Encoder as _};
+
+    const HEARTBEAT: &[u8] = b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x01";
+
+    #[test]
+    fn decodes_frames_as_bytes_arrive() {
+        let mut codec = FixCodec::new();
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(HEARTBEAT);
+        buf.extend_from_slice(&HEARTBEAT[..10]);
+
+        let frame = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(frame.as_bytes(), HEARTBEAT);
+        assert_eq!(frame.as_message().find(tag::MSG_TYPE).unwrap().value, b"0");
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+
+        buf.extend_from_slice(&HEARTBEAT[10..]);
+        assert!(codec.decode(&mut buf).unwrap().is_some());
+        assert!(buf.is_empty());
+
+        buf.extend_from_slice(b"garbage");
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::Fix(FixError::DecodeError))
+        ));
+    }
+
+    #[test]
+    fn max_frame_len_bounds_buffering() {
+        let mut codec = FixCodec::new();
+        codec.max_frame_len(Some(8));
+        let mut buf = BytesMut::from(&HEARTBEAT[..12]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::Fix(FixError::MessageTooLarge { len: 12 }))
+        ));
+
+        // A complete frame over the limit is too large, not incomplete.
+        codec.max_frame_len(Some(HEARTBEAT.len() - 1));
+        let mut buf = BytesMut::from(HEARTBEAT);
+        match codec.decode(&mut buf) {
+            Err(CodecError::Fix(FixError::MessageTooLarge { len })) => {
+                assert_eq!(len, HEARTBEAT.len());
+            }
+            other => panic!("expected MessageTooLarge, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn default_max_frame_len_bounds_huge_body_length() {
+        let mut codec = FixCodec::new();
+        let mut buf = BytesMut::from(&b"8=FIX.4.2\x019=4000000000\x0135=0\x01"[..]);
+        buf.resize(DEFAULT_MAX_FRAME_LEN + 1, b'x');
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::Fix(FixError::MessageTooLarge { len })) if len == DEFAULT_MAX_FRAME_LEN + 1
+        ));
+
+        let mut buf = BytesMut::from(&b"8=FIX.4.2\x019=18446744073709551615\x0135=0\x01"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::Fix(FixError::InvalidBodyLength { offset: 10 }))
+        ));
+    }
+
+    #[test]
+    fn begin_strings_rejects_other_versions() {
+        let mut codec = FixCodec::new();
+        codec.begin_strings([b"FIX.4.4"]);
+        let mut buf = BytesMut::from(&b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x01"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::Fix(FixError::UnexpectedBeginString))
+        ));
+    }
+
+    #[test]
+    fn encode_round_trips() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::BEGIN_STRING, b"FIX.4.2")
+            .push(tag::MSG_TYPE, b"0");
+        let mut codec = FixCodec::new();
+        let mut buf = BytesMut::new();
+        codec.encode(&b.as_message(), &mut buf).unwrap();
+        assert_eq!(&buf[..], HEARTBEAT);
+        let frame = codec.decode(&mut buf).unwrap().unwrap();
+        assert!(frame.as_message().validate_checksum().is_ok());
+    }
+}
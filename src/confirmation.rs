@@ -0,0 +1,837 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::fees::{FeeBreakdown, fee_breakdown};
+use crate::group::{self, GroupIter};
+use crate::message::Message;
+use crate::response::{self, respond_with};
+use crate::settlement::Settlement;
+use crate::tag::{self, Tag};
+
+/// ConfirmTransType (666) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfirmTransType {
+    New,
+    Replace,
+    Cancel,
+}
+
+impl ConfirmTransType {
+    /// The character carried in tag 666.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::New => b'0',
+            Self::Replace => b'1',
+            Self::Cancel => b'2',
+        }
+    }
+
+    /// The type for a tag 666 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"0" => Some(Self::New),
+            b"1" => Some(Self::Replace),
+            b"2" => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
+
+/// ConfirmType (773) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfirmType {
+    /// Status of the allocation, without confirming the trade.
+    Status,
+    Confirmation,
+    /// The ConfirmationRequest (35=BH) is rejected.
+    RequestRejected,
+}
+
+impl ConfirmType {
+    /// The character carried in tag 773.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Status => b'1',
+            Self::Confirmation => b'2',
+            Self::RequestRejected => b'3',
+        }
+    }
+
+    /// The type for a tag 773 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"1" => Some(Self::Status),
+            b"2" => Some(Self::Confirmation),
+            b"3" => Some(Self::RequestRejected),
+            _ => None,
+        }
+    }
+}
+
+/// ConfirmStatus (665) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfirmStatus {
+    Received,
+    MismatchedAccount,
+    MissingSettlementInstructions,
+    Confirmed,
+    RequestRejected,
+}
+
+impl ConfirmStatus {
+    /// The character carried in tag 665.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Received => b'1',
+            Self::MismatchedAccount => b'2',
+            Self::MissingSettlementInstructions => b'3',
+            Self::Confirmed => b'4',
+            Self::RequestRejected => b'5',
+        }
+    }
+
+    /// The status for a tag 665 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"1" => Some(Self::Received),
+            b"2" => Some(Self::MismatchedAccount),
+            b"3" => Some(Self::MissingSettlementInstructions),
+            b"4" => Some(Self::Confirmed),
+            b"5" => Some(Self::RequestRejected),
+            _ => None,
+        }
+    }
+}
+
+/// AffirmStatus (940) values, as sent in a ConfirmationAck (35=AU).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AffirmStatus {
+    Received,
+    ConfirmRejected,
+    Affirmed,
+}
+
+impl AffirmStatus {
+    /// The character carried in tag 940.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Received => b'1',
+            Self::ConfirmRejected => b'2',
+            Self::Affirmed => b'3',
+        }
+    }
+
+    /// The status for a tag 940 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"1" => Some(Self::Received),
+            b"2" => Some(Self::ConfirmRejected),
+            b"3" => Some(Self::Affirmed),
+            _ => None,
+        }
+    }
+}
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+fn expect_type(msg: &Message<'_>, msg_type: &[u8]) -> Result<(), FixError> {
+    match value(msg, tag::MSG_TYPE) {
+        Some(t) if t == msg_type => Ok(()),
+        _ => Err(FixError::InvalidValue),
+    }
+}
+
+/// One `NO_PARTY_IDS` instance, with its nested `NO_PARTY_SUB_IDS`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmParty<'a> {
+    pub id: &'a [u8],
+    /// PartyIDSource (447), e.g. `D` proprietary, `B` BIC.
+    pub id_source: Option<&'a [u8]>,
+    /// PartyRole (452), e.g. `1` executing firm, `24` customer account.
+    pub role: Option<&'a [u8]>,
+    /// `(PartySubID (523), PartySubIDType (803))` pairs.
+    pub sub_ids: Vec<(&'a [u8], &'a [u8])>,
+}
+
+/// One `NO_CAPACITIES` instance: the quantity executed in one capacity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfirmCapacity<'a> {
+    /// OrderCapacity (528), e.g. `A` agency, `P` principal.
+    pub capacity: &'a [u8],
+    /// OrderRestrictions (529).
+    pub restrictions: Option<&'a [u8]>,
+    /// OrderCapacityQty (863).
+    pub qty: &'a [u8],
+}
+
+/// Read access to a Confirmation (35=AK).
+///
+/// # Example
+/// ```ignore
+/// let confirm = Confirmation::new(&msg)?;
+/// let fees = confirm.fees()?;
+/// if let Some(settl) = confirm.settlement()? {
+///     book(confirm.alloc_account(), settl.currency, settl.amount);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Confirmation<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> Confirmation<'a> {
+    /// `InvalidValue` if `msg` is not a Confirmation.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"AK")?;
+        Ok(Self { msg })
+    }
+
+    /// ConfirmID (664).
+    pub fn confirm_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CONFIRM_ID)
+    }
+
+    /// ConfirmRefID (772): the confirmation replaced or cancelled.
+    pub fn ref_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CONFIRM_REF_ID)
+    }
+
+    /// ConfirmReqID (859) of the ConfirmationRequest answered, if any.
+    pub fn confirm_req_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CONFIRM_REQ_ID)
+    }
+
+    /// ConfirmTransType (666), `None` if absent or not a known type.
+    pub fn trans_type(&self) -> Option<ConfirmTransType> {
+        value(self.msg, tag::CONFIRM_TRANS_TYPE).and_then(ConfirmTransType::from_code)
+    }
+
+    /// ConfirmType (773), `None` if absent or not a known type.
+    pub fn confirm_type(&self) -> Option<ConfirmType> {
+        value(self.msg, tag::CONFIRM_TYPE).and_then(ConfirmType::from_code)
+    }
+
+    /// ConfirmStatus (665), `None` if absent or not a known status.
+    pub fn status(&self) -> Option<ConfirmStatus> {
+        value(self.msg, tag::CONFIRM_STATUS).and_then(ConfirmStatus::from_code)
+    }
+
+    /// AllocID (70) of the allocation confirmed.
+    pub fn alloc_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ALLOC_ID)
+    }
+
+    /// IndividualAllocID (467).
+    pub fn individual_alloc_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::INDIVIDUAL_ALLOC_ID)
+    }
+
+    pub fn trade_date(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRADE_DATE)
+    }
+
+    pub fn transact_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRANSACT_TIME)
+    }
+
+    pub fn symbol(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SYMBOL)
+    }
+
+    pub fn side(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SIDE)
+    }
+
+    /// AllocQty (80).
+    pub fn alloc_qty(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ALLOC_QTY)
+    }
+
+    /// AllocAccount (79).
+    pub fn alloc_account(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ALLOC_ACCOUNT)
+    }
+
+    pub fn avg_px(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::AVG_PX)
+    }
+
+    pub fn currency(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CURRENCY)
+    }
+
+    pub fn gross_trade_amt(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::GROSS_TRADE_AMT)
+    }
+
+    pub fn accrued_interest_amt(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ACCRUED_INTEREST_AMT)
+    }
+
+    pub fn net_money(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::NET_MONEY)
+    }
+
+    /// SettlType (63).
+    pub fn settl_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SETTL_TYPE)
+    }
+
+    /// SettlDate (64).
+    pub fn settl_date(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SETTL_DATE)
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// The `NO_PARTY_IDS` instances; sub-IDs are read with
+    /// `party.groups(&group::PARTY_SUB_IDS)`.
+    pub fn parties(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::PARTY_IDS)
+    }
+
+    /// The `NO_CAPACITIES` instances.
+    pub fn capacities(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::CAPACITIES)
+    }
+
+    /// The `NO_MISC_FEES` instances.
+    pub fn misc_fees(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::MISC_FEES)
+    }
+
+    /// CommissionData and misc fees aggregated per currency; see
+    /// [`fee_breakdown`].
+    pub fn fees(&self) -> Result<FeeBreakdown, FixError> {
+        fee_breakdown(self.msg)
+    }
+
+    /// The settlement currency fields; see [`Settlement::from_message`].
+    pub fn settlement(&self) -> Result<Option<Settlement<'a>>, FixError> {
+        Settlement::from_message(self.msg)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of a Confirmation (35=AK).
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// ConfirmationBuilder::new(b"CF1", ConfirmTransType::New, ConfirmType::Confirmation, ConfirmStatus::Confirmed)
+///     .alloc_id(b"AL1")
+///     .trade(b"20240229", b"20240229-15:00:00")
+///     .allocation(b"ACCT-7", b"1", b"IBM", b"500", b"120.25")
+///     .capacity(ConfirmCapacity { capacity: b"A", qty: b"500", ..Default::default() })
+///     .amounts(b"60125", b"60137.50")
+///     .commission(b"12.50", b"3", Some(b"USD"))
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfirmationBuilder<'a> {
+    confirm_id: &'a [u8],
+    trans_type: ConfirmTransType,
+    confirm_type: ConfirmType,
+    status: ConfirmStatus,
+    ref_id: Option<&'a [u8]>,
+    confirm_req_id: Option<&'a [u8]>,
+    alloc_id: Option<&'a [u8]>,
+    individual_alloc_id: Option<&'a [u8]>,
+    trade_date: Option<&'a [u8]>,
+    transact_time: Option<&'a [u8]>,
+    alloc_account: Option<&'a [u8]>,
+    side: Option<&'a [u8]>,
+    symbol: Option<&'a [u8]>,
+    alloc_qty: Option<&'a [u8]>,
+    avg_px: Option<&'a [u8]>,
+    currency: Option<&'a [u8]>,
+    gross_trade_amt: Option<&'a [u8]>,
+    accrued_interest_amt: Option<&'a [u8]>,
+    net_money: Option<&'a [u8]>,
+    settl_curr_amt: Option<&'a [u8]>,
+    settl_currency: Option<&'a [u8]>,
+    settl_curr_fx_rate: Option<&'a [u8]>,
+    settl_type: Option<&'a [u8]>,
+    settl_date: Option<&'a [u8]>,
+    commission: Option<&'a [u8]>,
+    comm_type: Option<&'a [u8]>,
+    comm_currency: Option<&'a [u8]>,
+    text: Option<&'a [u8]>,
+    parties: Vec<ConfirmParty<'a>>,
+    capacities: Vec<ConfirmCapacity<'a>>,
+    misc_fees: Vec<(&'a [u8], &'a [u8], &'a [u8])>,
+}
+
+impl<'a> ConfirmationBuilder<'a> {
+    pub fn new(
+        confirm_id: &'a [u8],
+        trans_type: ConfirmTransType,
+        confirm_type: ConfirmType,
+        status: ConfirmStatus,
+    ) -> Self {
+        Self {
+            confirm_id,
+            trans_type,
+            confirm_type,
+            status,
+            ref_id: None,
+            confirm_req_id: None,
+            alloc_id: None,
+            individual_alloc_id: None,
+            trade_date: None,
+            transact_time: None,
+            alloc_account: None,
+            side: None,
+            symbol: None,
+            alloc_qty: None,
+            avg_px: None,
+            currency: None,
+            gross_trade_amt: None,
+            accrued_interest_amt: None,
+            net_money: None,
+            settl_curr_amt: None,
+            settl_currency: None,
+            settl_curr_fx_rate: None,
+            settl_type: None,
+            settl_date: None,
+            commission: None,
+            comm_type: None,
+            comm_currency: None,
+            text: None,
+            parties: Vec::new(),
+            capacities: Vec::new(),
+            misc_fees: Vec::new(),
+        }
+    }
+
+    /// ConfirmRefID (772), for a replace or cancel.
+    pub fn ref_id(mut self, ref_id: &'a [u8]) -> Self {
+        self.ref_id = Some(ref_id);
+        self
+    }
+
+    /// ConfirmReqID (859), when answering a ConfirmationRequest.
+    pub fn confirm_req_id(mut self, confirm_req_id: &'a [u8]) -> Self {
+        self.confirm_req_id = Some(confirm_req_id);
+        self
+    }
+
+    /// AllocID (70) of the allocation confirmed.
+    pub fn alloc_id(mut self, alloc_id: &'a [u8]) -> Self {
+        self.alloc_id = Some(alloc_id);
+        self
+    }
+
+    /// IndividualAllocID (467).
+    pub fn individual_alloc_id(mut self, individual_alloc_id: &'a [u8]) -> Self {
+        self.individual_alloc_id = Some(individual_alloc_id);
+        self
+    }
+
+    /// TradeDate (75) and TransactTime (60).
+    pub fn trade(mut self, trade_date: &'a [u8], transact_time: &'a [u8]) -> Self {
+        self.trade_date = Some(trade_date);
+        self.transact_time = Some(transact_time);
+        self
+    }
+
+    /// AllocAccount (79), Side (54), Symbol (55), AllocQty (80) and AvgPx
+    /// (6) of the confirmed allocation.
+    pub fn allocation(
+        mut self,
+        account: &'a [u8],
+        side: &'a [u8],
+        symbol: &'a [u8],
+        qty: &'a [u8],
+        avg_px: &'a [u8],
+    ) -> Self {
+        self.alloc_account = Some(account);
+        self.side = Some(side);
+        self.symbol = Some(symbol);
+        self.alloc_qty = Some(qty);
+        self.avg_px = Some(avg_px);
+        self
+    }
+
+    pub fn currency(mut self, currency: &'a [u8]) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    /// GrossTradeAmt (381) and NetMoney (118).
+    pub fn amounts(mut self, gross_trade_amt: &'a [u8], net_money: &'a [u8]) -> Self {
+        self.gross_trade_amt = Some(gross_trade_amt);
+        self.net_money = Some(net_money);
+        self
+    }
+
+    pub fn accrued_interest_amt(mut self, amt: &'a [u8]) -> Self {
+        self.accrued_interest_amt = Some(amt);
+        self
+    }
+
+    /// SettlCurrency (120), SettlCurrAmt (119) and SettlCurrFxRate (155).
+    pub fn settl_currency(
+        mut self,
+        currency: &'a [u8],
+        amount: &'a [u8],
+        fx_rate: Option<&'a [u8]>,
+    ) -> Self {
+        self.settl_currency = Some(currency);
+        self.settl_curr_amt = Some(amount);
+        self.settl_curr_fx_rate = fx_rate;
+        self
+    }
+
+    /// SettlType (63) and SettlDate (64); the date is omitted when empty.
+    pub fn settlement(mut self, settl_type: &'a [u8], settl_date: &'a [u8]) -> Self {
+        self.settl_type = Some(settl_type);
+        self.settl_date = (!settl_date.is_empty()).then_some(settl_date);
+        self
+    }
+
+    /// CommissionData: Commission (12), CommType (13) and CommCurrency
+    /// (479).
+    pub fn commission(
+        mut self,
+        amount: &'a [u8],
+        comm_type: &'a [u8],
+        currency: Option<&'a [u8]>,
+    ) -> Self {
+        self.commission = Some(amount);
+        self.comm_type = Some(comm_type);
+        self.comm_currency = currency;
+        self
+    }
+
+    pub fn text(mut self, text: &'a [u8]) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Add a `NO_PARTY_IDS` instance.
+    pub fn party(mut self, party: ConfirmParty<'a>) -> Self {
+        self.parties.push(party);
+        self
+    }
+
+    /// Add a `NO_CAPACITIES` instance.
+    pub fn capacity(mut self, capacity: ConfirmCapacity<'a>) -> Self {
+        self.capacities.push(capacity);
+        self
+    }
+
+    /// Add a `NO_MISC_FEES` instance: MiscFeeAmt (137), MiscFeeCurr (138)
+    /// and MiscFeeType (139).
+    pub fn misc_fee(mut self, amt: &'a [u8], curr: &'a [u8], fee_type: &'a [u8]) -> Self {
+        self.misc_fees.push((amt, curr, fee_type));
+        self
+    }
+
+    /// Append MsgType `AK` and the confirmation fields to `b`. Header
+    /// fields other than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"AK")
+            .push(tag::CONFIRM_ID, self.confirm_id);
+        for (t, v) in [
+            (tag::CONFIRM_REF_ID, self.ref_id),
+            (tag::CONFIRM_REQ_ID, self.confirm_req_id),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+        b.push(tag::CONFIRM_TRANS_TYPE, &[self.trans_type.code()])
+            .push(tag::CONFIRM_TYPE, &[self.confirm_type.code()])
+            .push(tag::CONFIRM_STATUS, &[self.status.code()]);
+
+        let mut g = b.begin_group(&group::PARTY_IDS);
+        for p in &self.parties {
+            g.instance().push(tag::PARTY_ID, p.id);
+            for (t, v) in [
+                (tag::PARTY_ID_SOURCE, p.id_source),
+                (tag::PARTY_ROLE, p.role),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+            if !p.sub_ids.is_empty() {
+                let mut sub = g.begin_group(&group::PARTY_SUB_IDS);
+                for (id, id_type) in &p.sub_ids {
+                    sub.instance()
+                        .push(tag::PARTY_SUB_ID, id)
+                        .push(tag::PARTY_SUB_ID_TYPE, id_type);
+                }
+            }
+        }
+        drop(g);
+
+        for (t, v) in [
+            (tag::ALLOC_ID, self.alloc_id),
+            (tag::INDIVIDUAL_ALLOC_ID, self.individual_alloc_id),
+            (tag::TRANSACT_TIME, self.transact_time),
+            (tag::TRADE_DATE, self.trade_date),
+            (tag::SYMBOL, self.symbol),
+            (tag::ALLOC_QTY, self.alloc_qty),
+            (tag::SIDE, self.side),
+            (tag::CURRENCY, self.currency),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+
+        let mut g = b.begin_group(&group::CAPACITIES);
+        for c in &self.capacities {
+            g.instance().push(tag::ORDER_CAPACITY, c.capacity);
+            if let Some(v) = c.restrictions {
+                g.push(tag::ORDER_RESTRICTIONS, v);
+            }
+            g.push(tag::ORDER_CAPACITY_QTY, c.qty);
+        }
+        drop(g);
+
+        for (t, v) in [
+            (tag::ALLOC_ACCOUNT, self.alloc_account),
+            (tag::AVG_PX, self.avg_px),
+            (tag::TEXT, self.text),
+            (tag::GROSS_TRADE_AMT, self.gross_trade_amt),
+            (tag::ACCRUED_INTEREST_AMT, self.accrued_interest_amt),
+            (tag::NET_MONEY, self.net_money),
+            (tag::SETTL_CURR_AMT, self.settl_curr_amt),
+            (tag::SETTL_CURRENCY, self.settl_currency),
+            (tag::SETTL_CURR_FX_RATE, self.settl_curr_fx_rate),
+            (tag::SETTL_TYPE, self.settl_type),
+            (tag::SETTL_DATE, self.settl_date),
+            (tag::COMMISSION, self.commission),
+            (tag::COMM_TYPE, self.comm_type),
+            (tag::COMM_CURRENCY, self.comm_currency),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+
+        let mut g = b.begin_group(&group::MISC_FEES);
+        for (amt, curr, fee_type) in &self.misc_fees {
+            g.instance()
+                .push(tag::MISC_FEE_AMT, amt)
+                .push(tag::MISC_FEE_CURR, curr)
+                .push(tag::MISC_FEE_TYPE, fee_type);
+        }
+    }
+}
+
+/// Read access to a ConfirmationAck (35=AU).
+#[derive(Debug, Clone, Copy)]
+pub struct ConfirmationAck<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> ConfirmationAck<'a> {
+    /// `InvalidValue` if `msg` is not a ConfirmationAck.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"AU")?;
+        Ok(Self { msg })
+    }
+
+    /// ConfirmID (664) of the confirmation answered.
+    pub fn confirm_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CONFIRM_ID)
+    }
+
+    pub fn trade_date(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRADE_DATE)
+    }
+
+    pub fn transact_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRANSACT_TIME)
+    }
+
+    /// AffirmStatus (940), `None` if absent or not a known status.
+    pub fn affirm_status(&self) -> Option<AffirmStatus> {
+        value(self.msg, tag::AFFIRM_STATUS).and_then(AffirmStatus::from_code)
+    }
+
+    /// ConfirmRejReason (774): `1` mismatched account, `2` missing
+    /// settlement instructions, `99` other.
+    pub fn rej_reason(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CONFIRM_REJ_REASON)
+    }
+
+    /// MatchStatus (573): `0` compared, matched or affirmed, `1`
+    /// uncompared, `2` advisory.
+    pub fn match_status(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::MATCH_STATUS)
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes a ConfirmationAck (35=AU) answering a received Confirmation.
+///
+/// # Example
+/// ```ignore
+/// let confirm = Confirmation::new(&msg)?;
+/// let mut b = MessageBuilder::new();
+/// ConfirmationAckBuilder::new(&confirm, AffirmStatus::ConfirmRejected)
+///     .reject_reason(b"1", b"Unknown account")
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfirmationAckBuilder<'a> {
+    confirmation: &'a Message<'a>,
+    status: AffirmStatus,
+    rej_reason: Option<&'a [u8]>,
+    match_status: Option<&'a [u8]>,
+    text: Option<&'a [u8]>,
+}
+
+impl<'a> ConfirmationAckBuilder<'a> {
+    pub fn new(confirmation: &Confirmation<'a>, status: AffirmStatus) -> Self {
+        Self {
+            confirmation: confirmation.msg,
+            status,
+            rej_reason: None,
+            match_status: None,
+            text: None,
+        }
+    }
+
+    /// ConfirmRejReason (774) and Text (58); the text is omitted when
+    /// empty.
+    pub fn reject_reason(mut self, code: &'a [u8], text: &'a [u8]) -> Self {
+        self.rej_reason = Some(code);
+        self.text = (!text.is_empty()).then_some(text);
+        self
+    }
+
+    /// MatchStatus (573).
+    pub fn match_status(mut self, match_status: &'a [u8]) -> Self {
+        self.match_status = Some(match_status);
+        self
+    }
+
+    /// Append MsgType `AU`, the confirmation's routing IDs mirrored, the
+    /// echoed ConfirmID, TradeDate and TransactTime, then AffirmStatus,
+    /// the reject reason and MatchStatus to `b`.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        respond_with(self.confirmation, &response::CONFIRMATION, b);
+        b.push(tag::AFFIRM_STATUS, &[self.status.code()]);
+        for (t, v) in [
+            (tag::CONFIRM_REJ_REASON, self.rej_reason),
+            (tag::MATCH_STATUS, self.match_status),
+            (tag::TEXT, self.text),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::FixDecimal;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::fees::MiscFeeType;
+
+    #[test]
+    fn confirmation_round_trip_and_ack() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::BEGIN_STRING, b"FIX.4.4")
+            .push(tag::SENDER_COMP_ID, b"BROKER")
+            .push(tag::TARGET_COMP_ID, b"FUND");
+        ConfirmationBuilder::new(
+            b"CF1",
+            ConfirmTransType::New,
+            ConfirmType::Confirmation,
+            ConfirmStatus::Confirmed,
+        )
+        .alloc_id(b"AL1")
+        .trade(b"20240229", b"20240229-15:00:00")
+        .allocation(b"ACCT-7", b"1", b"IBM", b"500", b"120.25")
+        .currency(b"USD")
+        .party(ConfirmParty {
+            id: b"BRKR",
+            id_source: Some(b"D"),
+            role: Some(b"1"),
+            sub_ids: vec![(b"DESK-9", b"4")],
+        })
+        .capacity(ConfirmCapacity {
+            capacity: b"A",
+            qty: b"300",
+            ..Default::default()
+        })
+        .capacity(ConfirmCapacity {
+            capacity: b"P",
+            restrictions: Some(b"1"),
+            qty: b"200",
+        })
+        .amounts(b"60125", b"60142.50")
+        .settl_currency(b"EUR", b"55331.10", Some(b"0.92"))
+        .settlement(b"0", b"20240304")
+        .commission(b"12.50", b"3", None)
+        .misc_fee(b"5", b"USD", b"4")
+        .write_to(&mut b);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let confirm = Confirmation::new(&msg).unwrap();
+        assert_eq!(confirm.status(), Some(ConfirmStatus::Confirmed));
+        assert_eq!(confirm.confirm_type(), Some(ConfirmType::Confirmation));
+        assert_eq!(confirm.alloc_account(), Some(&b"ACCT-7"[..]));
+        assert_eq!(confirm.settl_date(), Some(&b"20240304"[..]));
+
+        let party = confirm.parties().next().unwrap();
+        let sub = party.groups(&group::PARTY_SUB_IDS).next().unwrap();
+        assert_eq!(sub.find(tag::PARTY_SUB_ID).unwrap().value, b"DESK-9");
+        let qty: Vec<_> = confirm
+            .capacities()
+            .map(|c| c.find(tag::ORDER_CAPACITY_QTY).unwrap().value)
+            .collect();
+        assert_eq!(qty, [&b"300"[..], b"200"]);
+
+        let fees = confirm.fees().unwrap();
+        let usd = |s: &[u8]| Some(FixDecimal::parse(s).unwrap());
+        assert_eq!(fees.misc_fee(b"USD", MiscFeeType::ExchangeFees), usd(b"5"));
+        assert_eq!(fees.total(b"USD"), usd(b"17.50"));
+        let settl = confirm.settlement().unwrap().unwrap();
+        assert_eq!(settl.currency, b"EUR");
+        assert!(ConfirmationAck::new(&msg).is_err());
+
+        let mut b = MessageBuilder::new();
+        ConfirmationAckBuilder::new(&confirm, AffirmStatus::ConfirmRejected)
+            .reject_reason(b"1", b"")
+            .write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 49, 56, 664, 75, 60, 940, 774]);
+        let reply = b.as_message();
+        let ack = ConfirmationAck::new(&reply).unwrap();
+        assert_eq!(ack.confirm_id(), Some(&b"CF1"[..]));
+        assert_eq!(ack.affirm_status(), Some(AffirmStatus::ConfirmRejected));
+        assert_eq!(ack.rej_reason(), Some(&b"1"[..]));
+        assert_eq!(ack.text(), None);
+    }
+}
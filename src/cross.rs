@@ -0,0 +1,453 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::group::{self, Group, GroupIter};
+use crate::message::Message;
+use crate::msg_type::MsgType;
+use crate::tag::{self, Tag};
+
+/// CrossType (549) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossType {
+    /// Both sides fully executed or the cross is rejected.
+    AllOrNone,
+    /// One side fully executed, the other side's remainder cancelled.
+    ImmediateOrCancel,
+    /// One side fully executed, the other side's remainder left working.
+    OneSide,
+    /// Both sides executed as far as possible at the same price.
+    SamePrice,
+}
+
+impl CrossType {
+    /// The character carried in tag 549.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::AllOrNone => b'1',
+            Self::ImmediateOrCancel => b'2',
+            Self::OneSide => b'3',
+            Self::SamePrice => b'4',
+        }
+    }
+
+    /// The type for a tag 549 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"1" => Some(Self::AllOrNone),
+            b"2" => Some(Self::ImmediateOrCancel),
+            b"3" => Some(Self::OneSide),
+            b"4" => Some(Self::SamePrice),
+            _ => None,
+        }
+    }
+}
+
+/// CrossPrioritization (550) values: which side, if any, is executed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossPrioritization {
+    None,
+    BuySide,
+    SellSide,
+}
+
+impl CrossPrioritization {
+    /// The character carried in tag 550.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::None => b'0',
+            Self::BuySide => b'1',
+            Self::SellSide => b'2',
+        }
+    }
+
+    /// The prioritization for a tag 550 value, or `None` if `code` is not
+    /// defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"0" => Some(Self::None),
+            b"1" => Some(Self::BuySide),
+            b"2" => Some(Self::SellSide),
+            _ => None,
+        }
+    }
+}
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+/// One `NO_SIDES` instance of a cross order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrossSide<'a> {
+    pub side: &'a [u8],
+    pub cl_ord_id: &'a [u8],
+    /// OrigClOrdID (41), on a cancel or replace.
+    pub orig_cl_ord_id: Option<&'a [u8]>,
+    pub account: Option<&'a [u8]>,
+    pub order_qty: Option<&'a [u8]>,
+    /// OrderCapacity (528): `A` agency, `P` principal, …
+    pub order_capacity: Option<&'a [u8]>,
+    pub text: Option<&'a [u8]>,
+}
+
+/// Read access to a NewOrderCross (35=s), CrossOrderCancelReplaceRequest
+/// (35=t) or CrossOrderCancelRequest (35=u).
+///
+/// # Example
+/// ```ignore
+/// let cross = CrossOrder::new(&msg)?;
+/// let buy = cross.side(b"1").ok_or(FixError::InvalidValue)?;
+/// let sell = cross.side(b"2").ok_or(FixError::InvalidValue)?;
+/// engine.internalize(cross.cross_id(), cross.cross_type(), buy, sell);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CrossOrder<'a> {
+    msg: &'a Message<'a>,
+    msg_type: MsgType,
+}
+
+impl<'a> CrossOrder<'a> {
+    /// `InvalidValue` if `msg` is not one of the three cross order
+    /// messages.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        match msg.msg_type() {
+            Some(
+                t @ (MsgType::NewOrderCross
+                | MsgType::CrossOrderCancelReplaceRequest
+                | MsgType::CrossOrderCancelRequest),
+            ) => Ok(Self { msg, msg_type: t }),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+
+    /// Which of the three messages this is.
+    #[inline]
+    pub fn msg_type(&self) -> MsgType {
+        self.msg_type
+    }
+
+    /// CrossID (548).
+    pub fn cross_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CROSS_ID)
+    }
+
+    /// OrigCrossID (551): the cross cancelled or replaced.
+    pub fn orig_cross_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ORIG_CROSS_ID)
+    }
+
+    /// OrderID (37) the sell side assigned to the cross.
+    pub fn order_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ORDER_ID)
+    }
+
+    /// CrossType (549), `None` if absent or not a known type.
+    pub fn cross_type(&self) -> Option<CrossType> {
+        value(self.msg, tag::CROSS_TYPE).and_then(CrossType::from_code)
+    }
+
+    /// CrossPrioritization (550), `None` if absent or not a known value.
+    pub fn prioritization(&self) -> Option<CrossPrioritization> {
+        value(self.msg, tag::CROSS_PRIORITIZATION).and_then(CrossPrioritization::from_code)
+    }
+
+    /// The `NO_SIDES` instances.
+    pub fn sides(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::SIDES)
+    }
+
+    /// The side instance whose Side (54) is `side`, e.g. `b"1"` for buy.
+    pub fn side(&self, side: &[u8]) -> Option<Group<'a>> {
+        self.sides()
+            .find(|g| g.find(tag::SIDE).is_some_and(|f| f.value == side))
+    }
+
+    pub fn symbol(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SYMBOL)
+    }
+
+    pub fn ord_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ORD_TYPE)
+    }
+
+    pub fn price(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::PRICE)
+    }
+
+    pub fn transact_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRANSACT_TIME)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of a NewOrderCross (35=s), CrossOrderCancelReplaceRequest
+/// (35=t) or CrossOrderCancelRequest (35=u).
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// CrossOrderBuilder::new_order(b"X1", CrossType::AllOrNone, CrossPrioritization::None, b"IBM")
+///     .side(CrossSide { side: b"1", cl_ord_id: b"B1", order_qty: Some(b"500"), ..Default::default() })
+///     .side(CrossSide { side: b"2", cl_ord_id: b"S1", order_qty: Some(b"500"), ..Default::default() })
+///     .ord_type(b"2")
+///     .price(b"150.25")
+///     .transact_time(b"20240229-13:45:06")
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CrossOrderBuilder<'a> {
+    msg_type: MsgType,
+    cross_id: &'a [u8],
+    orig_cross_id: Option<&'a [u8]>,
+    order_id: Option<&'a [u8]>,
+    cross_type: CrossType,
+    prioritization: CrossPrioritization,
+    symbol: &'a [u8],
+    sides: Vec<CrossSide<'a>>,
+    transact_time: Option<&'a [u8]>,
+    ord_type: Option<&'a [u8]>,
+    price: Option<&'a [u8]>,
+    time_in_force: Option<&'a [u8]>,
+}
+
+impl<'a> CrossOrderBuilder<'a> {
+    fn with_type(
+        msg_type: MsgType,
+        cross_id: &'a [u8],
+        orig_cross_id: Option<&'a [u8]>,
+        cross_type: CrossType,
+        prioritization: CrossPrioritization,
+        symbol: &'a [u8],
+    ) -> Self {
+        Self {
+            msg_type,
+            cross_id,
+            orig_cross_id,
+            order_id: None,
+            cross_type,
+            prioritization,
+            symbol,
+            sides: Vec::new(),
+            transact_time: None,
+            ord_type: None,
+            price: None,
+            time_in_force: None,
+        }
+    }
+
+    /// A NewOrderCross (35=s).
+    pub fn new_order(
+        cross_id: &'a [u8],
+        cross_type: CrossType,
+        prioritization: CrossPrioritization,
+        symbol: &'a [u8],
+    ) -> Self {
+        Self::with_type(
+            MsgType::NewOrderCross,
+            cross_id,
+            None,
+            cross_type,
+            prioritization,
+            symbol,
+        )
+    }
+
+    /// A CrossOrderCancelReplaceRequest (35=t) replacing `orig_cross_id`.
+    pub fn cancel_replace(
+        cross_id: &'a [u8],
+        orig_cross_id: &'a [u8],
+        cross_type: CrossType,
+        prioritization: CrossPrioritization,
+        symbol: &'a [u8],
+    ) -> Self {
+        Self::with_type(
+            MsgType::CrossOrderCancelReplaceRequest,
+            cross_id,
+            Some(orig_cross_id),
+            cross_type,
+            prioritization,
+            symbol,
+        )
+    }
+
+    /// A CrossOrderCancelRequest (35=u) cancelling `orig_cross_id`.
+    pub fn cancel(
+        cross_id: &'a [u8],
+        orig_cross_id: &'a [u8],
+        cross_type: CrossType,
+        prioritization: CrossPrioritization,
+        symbol: &'a [u8],
+    ) -> Self {
+        Self::with_type(
+            MsgType::CrossOrderCancelRequest,
+            cross_id,
+            Some(orig_cross_id),
+            cross_type,
+            prioritization,
+            symbol,
+        )
+    }
+
+    /// OrderID (37) of the cross being cancelled or replaced.
+    pub fn order_id(mut self, order_id: &'a [u8]) -> Self {
+        self.order_id = Some(order_id);
+        self
+    }
+
+    /// Add a `NO_SIDES` instance. A cross has one or two sides.
+    pub fn side(mut self, side: CrossSide<'a>) -> Self {
+        self.sides.push(side);
+        self
+    }
+
+    pub fn transact_time(mut self, time: &'a [u8]) -> Self {
+        self.transact_time = Some(time);
+        self
+    }
+
+    pub fn ord_type(mut self, ord_type: &'a [u8]) -> Self {
+        self.ord_type = Some(ord_type);
+        self
+    }
+
+    pub fn price(mut self, price: &'a [u8]) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: &'a [u8]) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// Append the MsgType and the cross order fields to `b`. Header fields
+    /// other than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, self.msg_type.as_bytes());
+        if let Some(v) = self.order_id {
+            b.push(tag::ORDER_ID, v);
+        }
+        b.push(tag::CROSS_ID, self.cross_id);
+        if let Some(v) = self.orig_cross_id {
+            b.push(tag::ORIG_CROSS_ID, v);
+        }
+        b.push(tag::CROSS_TYPE, &[self.cross_type.code()])
+            .push(tag::CROSS_PRIORITIZATION, &[self.prioritization.code()]);
+
+        let mut g = b.begin_group(&group::SIDES);
+        for s in &self.sides {
+            g.instance()
+                .push(tag::SIDE, s.side)
+                .push(tag::CL_ORD_ID, s.cl_ord_id);
+            for (t, v) in [
+                (tag::ORIG_CL_ORD_ID, s.orig_cl_ord_id),
+                (tag::ACCOUNT, s.account),
+                (tag::ORDER_QTY, s.order_qty),
+                (tag::ORDER_CAPACITY, s.order_capacity),
+                (tag::TEXT, s.text),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+        }
+        drop(g);
+
+        b.push(tag::SYMBOL, self.symbol);
+        for (t, v) in [
+            (tag::TRANSACT_TIME, self.transact_time),
+            (tag::ORD_TYPE, self.ord_type),
+            (tag::PRICE, self.price),
+            (tag::TIME_IN_FORCE, self.time_in_force),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn new_order_cross_round_trip() {
+        let mut b = MessageBuilder::new();
+        CrossOrderBuilder::new_order(
+            b"X1",
+            CrossType::AllOrNone,
+            CrossPrioritization::BuySide,
+            b"IBM",
+        )
+        .side(CrossSide {
+            side: b"1",
+            cl_ord_id: b"B1",
+            order_qty: Some(b"500"),
+            ..Default::default()
+        })
+        .side(CrossSide {
+            side: b"2",
+            cl_ord_id: b"S1",
+            account: Some(b"ACC9"),
+            order_qty: Some(b"500"),
+            ..Default::default()
+        })
+        .ord_type(b"2")
+        .price(b"150.25")
+        .transact_time(b"20240229-13:45:06")
+        .write_to(&mut b);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let cross = CrossOrder::new(&msg).unwrap();
+        assert_eq!(cross.msg_type(), MsgType::NewOrderCross);
+        assert_eq!(cross.cross_type(), Some(CrossType::AllOrNone));
+        assert_eq!(cross.prioritization(), Some(CrossPrioritization::BuySide));
+        assert_eq!(cross.sides().count(), 2);
+        let sell = cross.side(b"2").unwrap();
+        assert_eq!(sell.find(tag::CL_ORD_ID).unwrap().value, b"S1");
+        assert_eq!(sell.find(tag::ACCOUNT).unwrap().value, b"ACC9");
+        assert_eq!(cross.symbol(), Some(&b"IBM"[..]));
+    }
+
+    #[test]
+    fn cancel_carries_orig_ids() {
+        let mut b = MessageBuilder::new();
+        CrossOrderBuilder::cancel(
+            b"X2",
+            b"X1",
+            CrossType::AllOrNone,
+            CrossPrioritization::None,
+            b"IBM",
+        )
+        .order_id(b"OID7")
+        .side(CrossSide {
+            side: b"1",
+            cl_ord_id: b"B2",
+            orig_cl_ord_id: Some(b"B1"),
+            ..Default::default()
+        })
+        .write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 37, 548, 551, 549, 550, 552, 54, 11, 41, 55]);
+
+        let msg = b.as_message();
+        let cross = CrossOrder::new(&msg).unwrap();
+        assert_eq!(cross.msg_type(), MsgType::CrossOrderCancelRequest);
+        assert_eq!(cross.orig_cross_id(), Some(&b"X1"[..]));
+        assert!(cross.side(b"2").is_none());
+
+        let mut dec = Decoder::new();
+        let other = dec.decode(b"35=D\x01").unwrap();
+        assert!(CrossOrder::new(&other).is_err());
+    }
+}
@@ -0,0 +1,338 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::error::FixError;
+
+/// Largest number of fractional digits a `FixDecimal` carries.
+pub const MAX_SCALE: u32 = 18;
+
+/// An exact decimal number as carried by FIX Price, Qty and Amt fields.
+///
+/// Stored as `mantissa × 10^-scale`, so `b"149.50"` is `(14950, 2)` and no
+/// binary floating point is involved anywhere. Arithmetic is checked: an
+/// overflowing result is `None` rather than a silently wrong price.
+///
+/// Equality, ordering and hashing are by numeric value (`1.50 == 1.5`).
+#[derive(Debug, Clone, Copy)]
+pub struct FixDecimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl FixDecimal {
+    pub const ZERO: FixDecimal = FixDecimal {
+        mantissa: 0,
+        scale: 0,
+    };
+
+    /// `mantissa × 10^-scale`. Panics if `scale > MAX_SCALE`.
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        assert!(scale <= MAX_SCALE, "FixDecimal scale {scale} > {MAX_SCALE}");
+        Self { mantissa, scale }
+    }
+
+    /// Parse a FIX float value: optional sign, digits, optional `.` and
+    /// fractional digits (`"100"`, `"-0.25"`, `"149.50"`, `".5"`).
+    pub fn parse(value: &[u8]) -> Result<Self, FixError> {
+        let (neg, digits) = match value {
+            [b'-', rest @ ..] => (true, rest),
+            [b'+', rest @ ..] => (false, rest),
+            _ => (false, value),
+        };
+        let mut mantissa: i128 = 0;
+        let mut scale: Option<u32> = None;
+        let mut any_digit = false;
+        for &b in digits {
+            match b {
+                b'0'..=b'9' => {
+                    any_digit = true;
+                    mantissa = mantissa
+                        .checked_mul(10)
+                        .and_then(|m| m.checked_add((b - b'0') as i128))
+                        .ok_or(FixError::InvalidValue)?;
+                    if let Some(s) = scale.as_mut() {
+                        *s += 1;
+                        if *s > MAX_SCALE {
+                            return Err(FixError::InvalidValue);
+                        }
+                    }
+                }
+                b'.' if scale.is_none() => scale = Some(0),
+                _ => return Err(FixError::InvalidValue),
+            }
+        }
+        if !any_digit {
+            return Err(FixError::InvalidValue);
+        }
+        Ok(Self {
+            mantissa: if neg { -mantissa } else { mantissa },
+            scale: scale.unwrap_or(0),
+        })
+    }
+
+    #[inline]
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    #[inline]
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// The same value with trailing fractional zeros removed.
+    pub fn normalized(&self) -> Self {
+        let mut d = *self;
+        while d.scale > 0 && d.mantissa % 10 == 0 {
+            d.mantissa /= 10;
+            d.scale -= 1;
+        }
+        d
+    }
+
+    /// The same value at `scale` digits, or `None` if that would drop
+    /// non-zero digits or overflow. Use [`round`](Self::round) to reduce
+    /// precision deliberately.
+    pub fn rescale(&self, scale: u32) -> Option<Self> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        if scale >= self.scale {
+            let m = self.mantissa.checked_mul(pow10(scale - self.scale))?;
+            return Some(Self { mantissa: m, scale });
+        }
+        let div = pow10(self.scale - scale);
+        (self.mantissa % div == 0).then(|| Self {
+            mantissa: self.mantissa / div,
+            scale,
+        })
+    }
+
+    /// Round half away from zero to `scale` fractional digits.
+    pub fn round(&self, scale: u32) -> Self {
+        if scale >= self.scale {
+            return self.rescale(scale).unwrap_or(*self);
+        }
+        let div = pow10(self.scale - scale);
+        let q = self.mantissa / div;
+        let r = self.mantissa % div;
+        let q = if r.abs() * 2 >= div {
+            q + self.mantissa.signum()
+        } else {
+            q
+        };
+        Self { mantissa: q, scale }
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+        let a = self.rescale(scale)?;
+        let b = other.rescale(scale)?;
+        Some(Self {
+            mantissa: a.mantissa.checked_add(b.mantissa)?,
+            scale,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&Self {
+            mantissa: other.mantissa.checked_neg()?,
+            scale: other.scale,
+        })
+    }
+
+    /// Exact product. The result's scale is the sum of the scales; `None` on
+    /// overflow or if that exceeds [`MAX_SCALE`] after normalizing.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let d = Self {
+            mantissa: self.mantissa.checked_mul(other.mantissa)?,
+            scale: self.scale + other.scale,
+        };
+        let d = if d.scale > MAX_SCALE {
+            d.normalized()
+        } else {
+            d
+        };
+        (d.scale <= MAX_SCALE).then_some(d)
+    }
+
+    /// Quotient rounded half away from zero to `scale` digits, or `None` on
+    /// division by zero or overflow.
+    pub fn checked_div(&self, other: &Self, scale: u32) -> Option<Self> {
+        if other.mantissa == 0 || scale > MAX_SCALE {
+            return None;
+        }
+        // self / other = (a × 10^-sa) / (b × 10^-sb); compute with one extra
+        // digit for rounding.
+        let exp = scale as i64 + 1 + other.scale as i64 - self.scale as i64;
+        let (num, den) = if exp >= 0 {
+            (
+                self.mantissa.checked_mul(pow10(exp as u32))?,
+                other.mantissa,
+            )
+        } else {
+            (
+                self.mantissa,
+                other.mantissa.checked_mul(pow10((-exp) as u32))?,
+            )
+        };
+        let q = num / den;
+        let rounded = (q + 5 * q.signum()) / 10;
+        Some(Self {
+            mantissa: rounded,
+            scale,
+        })
+    }
+
+    /// `|self − other|`, or `None` on overflow.
+    pub fn abs_diff(&self, other: &Self) -> Option<Self> {
+        let d = self.checked_sub(other)?;
+        Some(Self {
+            mantissa: d.mantissa.checked_abs()?,
+            scale: d.scale,
+        })
+    }
+
+    /// Write the value as FIX ASCII (`-149.50`) into `out`.
+    pub fn write_to(&self, out: &mut Vec<u8>) {
+        use std::io::Write;
+        let _ = write!(out, "{self}");
+    }
+}
+
+#[inline]
+fn pow10(n: u32) -> i128 {
+    10i128.pow(n)
+}
+
+impl PartialEq for FixDecimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FixDecimal {}
+
+impl PartialOrd for FixDecimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FixDecimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        match (self.rescale(scale), other.rescale(scale)) {
+            (Some(a), Some(b)) => a.mantissa.cmp(&b.mantissa),
+            // Aligning overflowed (magnitudes near i128::MAX): compare integer
+            // parts, then the fractional parts, which always fit.
+            _ => {
+                let (ia, fa) = (
+                    self.mantissa / pow10(self.scale),
+                    self.mantissa % pow10(self.scale),
+                );
+                let (ib, fb) = (
+                    other.mantissa / pow10(other.scale),
+                    other.mantissa % pow10(other.scale),
+                );
+                ia.cmp(&ib).then_with(|| {
+                    (fa * pow10(scale - self.scale)).cmp(&(fb * pow10(scale - other.scale)))
+                })
+            }
+        }
+    }
+}
+
+impl Hash for FixDecimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let n = self.normalized();
+        n.mantissa.hash(state);
+        n.scale.hash(state);
+    }
+}
+
+impl fmt::Display for FixDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.scale == 0 {
+            return write!(f, "{}", self.mantissa);
+        }
+        let div = pow10(self.scale);
+        let int = self.mantissa / div;
+        let frac = (self.mantissa % div).unsigned_abs();
+        let sign = if self.mantissa < 0 && int == 0 {
+            "-"
+        } else {
+            ""
+        };
+        write!(f, "{sign}{int}.{frac:0width$}", width = self.scale as usize)
+    }
+}
+
+impl From<i64> for FixDecimal {
+    fn from(n: i64) -> Self {
+        Self {
+            mantissa: n as i128,
+            scale: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> FixDecimal {
+        FixDecimal::parse(s.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(d("149.50").to_string(), "149.50");
+        assert_eq!(d("-0.25").to_string(), "-0.25");
+        assert_eq!(d(".5").to_string(), "0.5");
+        assert_eq!(d("100").to_string(), "100");
+        for bad in ["", "-", "1.2.3", "1e5", "abc", "."] {
+            assert!(FixDecimal::parse(bad.as_bytes()).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn value_equality_and_ordering() {
+        assert_eq!(d("1.50"), d("1.5"));
+        assert!(d("1.49") < d("1.5"));
+        assert!(d("-2") < d("-1.99"));
+        use std::collections::HashSet;
+        let set: HashSet<_> = [d("1.50"), d("1.5"), d("1.500")].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn exact_arithmetic() {
+        // The classic float failure: 0.1 + 0.2.
+        assert_eq!(d("0.1").checked_add(&d("0.2")).unwrap(), d("0.3"));
+        assert_eq!(d("100").checked_sub(&d("37.5")).unwrap(), d("62.5"));
+        assert_eq!(
+            d("149.50").checked_mul(&d("300")).unwrap().to_string(),
+            "44850.00"
+        );
+        assert_eq!(d("10").checked_div(&d("3"), 4).unwrap(), d("3.3333"));
+        assert_eq!(d("2").checked_div(&d("3"), 2).unwrap(), d("0.67"));
+        assert_eq!(d("-2").checked_div(&d("3"), 2).unwrap(), d("-0.67"));
+        assert!(d("1").checked_div(&FixDecimal::ZERO, 2).is_none());
+    }
+
+    #[test]
+    fn rescale_and_round() {
+        assert_eq!(d("1.5").rescale(3).unwrap().to_string(), "1.500");
+        assert!(d("1.25").rescale(1).is_none());
+        assert_eq!(d("1.25").round(1).to_string(), "1.3");
+        assert_eq!(d("-1.25").round(1).to_string(), "-1.3");
+        assert_eq!(d("1.24").round(1).to_string(), "1.2");
+    }
+}
@@ -1,13 +1,17 @@
-use memchr::memchr;
+use memchr::{memchr, memmem};
 use smallvec::SmallVec;
 
+use crate::body_length::parse_body_length;
+use crate::dictionary::data_tag_for_length;
 use crate::error::FixError;
 use crate::field::{FIELD_KEY_VALUE_SEPARATOR, FIELD_SEPARATOR};
-use crate::message::Message;
-use crate::tag::{Tag, parse_tag};
+use crate::group::specs_for_version;
+use crate::message::{MAX_MESSAGE_LEN, Message};
+use crate::tag::{self, Tag, parse_tag};
+use crate::validate::EmptyValuePolicy;
 
 /// Default inline capacity: covers ~95% of FIX messages without heap spill.
-const DEFAULT_CAPACITY: usize = 32;
+pub(crate) const DEFAULT_CAPACITY: usize = 32;
 
 /// A reusable FIX message decoder.
 ///
@@ -34,6 +38,40 @@ pub struct Decoder {
     /// clear() at the start of each decode call preserves allocated capacity —
     /// no free/malloc on the hot path.
     offsets: SmallVec<[(Tag, u32, u32); DEFAULT_CAPACITY]>,
+    /// Message boundaries found by `decode_all`, reused across calls.
+    frames: Vec<Frame>,
+    /// When true, every decoded message is checked by [`check_strict`].
+    strict: bool,
+    /// Reusable scratch list of non-group tags for the duplicate check.
+    seen: Vec<Tag>,
+    /// Tolerances for dirty input; see [`Decoder::lenient`].
+    scan: ScanOptions,
+    /// Accepted BeginString (8) values; empty accepts any.
+    begin_strings: Vec<Box<[u8]>>,
+    /// What to do with fields sent without a value.
+    empty_values: EmptyValuePolicy,
+}
+
+/// Input tolerances applied by [`scan_fields`]. The default accepts only
+/// well-formed fields.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ScanOptions {
+    /// Skip bytes before the first `8=` and accept a final field without
+    /// its SOH.
+    pub(crate) lenient: bool,
+    /// Ignore ASCII whitespace around tags and after the last field.
+    pub(crate) trim_whitespace: bool,
+}
+
+/// One complete message found by [`Decoder::decode_all`]: its byte range in
+/// the input and its range in the decoder's offset buffer. Offsets are
+/// relative to `start`, so each message sees its own bytes from position 0.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    start: usize,
+    end: usize,
+    first_field: usize,
+    last_field: usize,
 }
 
 impl Default for Decoder {
@@ -47,6 +85,12 @@ impl Decoder {
     pub fn new() -> Self {
         Self {
             offsets: SmallVec::new(),
+            frames: Vec::new(),
+            strict: false,
+            seen: Vec::new(),
+            scan: ScanOptions::default(),
+            begin_strings: Vec::new(),
+            empty_values: EmptyValuePolicy::Accept,
         }
     }
 
@@ -55,9 +99,89 @@ impl Decoder {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             offsets: SmallVec::with_capacity(capacity),
+            frames: Vec::new(),
+            strict: false,
+            seen: Vec::new(),
+            scan: ScanOptions::default(),
+            begin_strings: Vec::new(),
+            empty_values: EmptyValuePolicy::Accept,
         }
     }
 
+    /// When set to `true`, `decode` and `decode_all` reject messages that
+    /// break the structural rules of the standard header and trailer instead
+    /// of leaving them to later validation:
+    ///
+    /// - BeginString (8) must be the first field, BodyLength (9) the second
+    ///   and CheckSum (10) the last — `FixError::TagOutOfOrder`.
+    /// - A tag may appear only once unless it is a member of a repeating
+    ///   group of the message's FIX version (see
+    ///   [`specs_for_version`]) — `FixError::DuplicateTag`.
+    /// - No field may have an empty value — `FixError::EmptyValue`.
+    ///
+    /// When `false` (the default), any well-formed sequence of fields is
+    /// accepted.
+    pub fn strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+
+    /// When set to `true`, tolerate the damage typical of FIX samples copied
+    /// from logs or edited by hand:
+    ///
+    /// - Bytes before the first `8=` (a log timestamp, a direction marker)
+    ///   are skipped. Without an `8=` the whole buffer is decoded.
+    /// - The final field may lack its trailing SOH; its value runs to the
+    ///   end of the buffer.
+    ///
+    /// In `decode_all` only the leading bytes before each message are
+    /// skipped, since a missing SOH cannot be told apart from a partial
+    /// read there.
+    ///
+    /// When `false` (the default), both are errors.
+    pub fn lenient(&mut self, lenient: bool) -> &mut Self {
+        self.scan.lenient = lenient;
+        self
+    }
+
+    /// When set to `true`, ASCII whitespace around tags (`" 35 =D"`) and
+    /// after the last field (a trailing newline) is ignored. Values are
+    /// never trimmed.
+    ///
+    /// When `false` (the default), whitespace in a tag is `InvalidTag`.
+    pub fn trim_tag_whitespace(&mut self, trim: bool) -> &mut Self {
+        self.scan.trim_whitespace = trim;
+        self
+    }
+
+    /// Accept only messages whose BeginString (8) is one of `allowed`, e.g.
+    /// `["FIX.4.4"]` for a FIX.4.4 session. Anything else — including a
+    /// message without tag 8 — fails with `FixError::UnexpectedBeginString`
+    /// before it reaches code that picks group specs by version.
+    ///
+    /// An empty list (the default) accepts any BeginString.
+    pub fn begin_strings<I, B>(&mut self, allowed: I) -> &mut Self
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        self.begin_strings = allowed.into_iter().map(|b| b.as_ref().into()).collect();
+        self
+    }
+
+    /// What to do with fields sent without a value, such as `35=` followed
+    /// by SOH: accept them (the default), fail with `FixError::EmptyValue`
+    /// — which maps to SessionRejectReason 4 through
+    /// [`FixError::reject_reason`] — or accept them and call a warning
+    /// function with each tag.
+    ///
+    /// [`strict`](Self::strict) mode rejects empty values whatever the
+    /// policy.
+    pub fn empty_values(&mut self, policy: EmptyValuePolicy) -> &mut Self {
+        self.empty_values = policy;
+        self
+    }
+
     /// Decode a raw FIX byte buffer into a `Message`.
     ///
     /// Clears and reuses the internal offset buffer — zero allocation per call
@@ -72,39 +196,404 @@ impl Decoder {
     /// # Errors
     /// - `FixError::IncompleteMessage` — the buffer contains a partial field
     ///   (no `=` or no SOH delimiter found); buffer more bytes before retrying.
-    /// - `FixError::InvalidTag` — a tag contained non-digit bytes or overflowed `u32`.
+    /// - `FixError::InvalidTag` — a tag contained non-digit bytes or overflowed
+    ///   `u32`; carries the byte offset in `buf`.
+    /// - `FixError::DataLengthMismatch` — a length-prefixed data field (e.g.
+    ///   RawData 96 after RawDataLength 95) is not followed by SOH at its
+    ///   declared length.
+    /// - `FixError::MessageTooLarge` — `buf` is longer than
+    ///   [`MAX_MESSAGE_LEN`].
+    ///
+    /// - `FixError::UnexpectedBeginString` — BeginString is not in the
+    ///   [`begin_strings`](Self::begin_strings) whitelist.
+    /// - `FixError::EmptyValue` — a field has no value under
+    ///   [`EmptyValuePolicy::Reject`].
+    /// - `FixError::TagOutOfOrder`, `FixError::DuplicateTag`,
+    ///   `FixError::EmptyValue` — the message breaks a rule checked in
+    ///   [`strict`](Self::strict) mode.
+    ///
+    /// Length-prefixed data values are read by their declared length (see
+    /// [`data_tag_for_length`]), so they may contain SOH bytes.
     pub fn decode<'a>(&'a mut self, buf: &'a [u8]) -> Result<Message<'a>, FixError> {
         // clear() keeps existing capacity — no allocator call on hot path
         self.offsets.clear();
+        let start = if self.scan.lenient {
+            message_start(buf).unwrap_or(0)
+        } else {
+            0
+        };
+        scan_fields(buf, start, &mut self.offsets, self.scan)?;
+        check_begin_string(buf, &self.offsets, &self.begin_strings)?;
+        check_empty_values(&self.offsets, self.empty_values)?;
+        if self.strict {
+            check_strict(buf, &self.offsets, &mut self.seen)?;
+        }
+
+        // Both borrows are genuinely 'a: offsets from &'a mut self, buf from
+        // &'a [u8]. No transmutes, no unsafe.
+        Ok(Message::new(buf, self.offsets.as_slice()))
+    }
 
+    /// Decode `buf` like [`decode`](Self::decode), then check BodyLength (9)
+    /// and CheckSum (10) in the same call, for consumers that validate every
+    /// message on the hot path.
+    ///
+    /// # Errors
+    /// Everything `decode` returns, and:
+    /// - `FixError::InvalidBodyLength` / `FixError::InvalidCheckSum` — tag 9
+    ///   is not second, tag 10 is not last, or either value is unparseable.
+    /// - `FixError::BodyLengthMismatch` — the declared body length differs
+    ///   from the byte count; carries both values.
+    /// - `FixError::CheckSumMismatch` — the declared checksum differs from
+    ///   the computed one; carries both values.
+    ///
+    /// # Example
+    /// ```ignore
+    /// match decoder.decode_verified(buf) {
+    ///     Ok(msg) => process(msg),
+    ///     Err(FixError::CheckSumMismatch { declared, computed }) => {
+    ///         warn!("checksum {declared:03}, expected {computed:03}");
+    ///     }
+    ///     Err(e) => return Err(e),
+    /// }
+    /// ```
+    pub fn decode_verified<'a>(&'a mut self, buf: &'a [u8]) -> Result<Message<'a>, FixError> {
+        let msg = self.decode(buf)?;
+        let (declared, computed) = msg.body_length_values()?;
+        if declared != computed {
+            return Err(FixError::BodyLengthMismatch { declared, computed });
+        }
+        let (declared, computed) = msg.checksum_values()?;
+        if declared != computed {
+            return Err(FixError::CheckSumMismatch { declared, computed });
+        }
+        Ok(msg)
+    }
+
+    /// Decode every complete message in `buf`, for reads that return several
+    /// back-to-back FIX messages.
+    ///
+    /// Messages are framed by BeginString (8), BodyLength (9) and CheckSum
+    /// (10); a trailing partial message is not an error and is left in
+    /// [`DecodeAll::remainder`] to be prepended to the next read. Each
+    /// yielded `Message` covers only its own bytes, so
+    /// [`validate_body_length`](Message::validate_body_length) and
+    /// [`validate_checksum`](Message::validate_checksum) work per message.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let batch = decoder.decode_all(&buf)?;
+    /// let tail = batch.remainder();
+    /// for msg in batch {
+    ///     process(msg);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// - `FixError::DecodeError` — a message does not start with tag 8.
+    /// - `FixError::InvalidBodyLength` — tag 9 is missing, unparseable, or
+    ///   does not end where tag 10 starts.
+    /// - `FixError::InvalidTag` — a tag inside a message is malformed.
+    /// - `FixError::UnexpectedBeginString` — a message's BeginString is not
+    ///   in the [`begin_strings`](Self::begin_strings) whitelist.
+    /// - `FixError::DuplicateTag`, `FixError::EmptyValue` — a message breaks
+    ///   a rule checked in [`strict`](Self::strict) mode.
+    ///
+    /// Error offsets are byte positions in `buf`, not in the failing message.
+    pub fn decode_all<'a>(&'a mut self, buf: &'a [u8]) -> Result<DecodeAll<'a>, FixError> {
+        self.offsets.clear();
+        self.frames.clear();
+
+        let scan = ScanOptions {
+            lenient: false,
+            ..self.scan
+        };
         let mut pos = 0;
         while pos < buf.len() {
-            // SIMD scan for '=' — delimits tag from value
-            let eq_pos = memchr(FIELD_KEY_VALUE_SEPARATOR, &buf[pos..])
-                .ok_or(FixError::IncompleteMessage)?
-                + pos;
+            if self.scan.lenient {
+                match message_start(&buf[pos..]) {
+                    Some(skip) => pos += skip,
+                    None => break,
+                }
+            }
+            let Some(end) = frame_end(&buf[pos..]).map_err(|e| e.offset_by(pos))? else {
+                break;
+            };
+            let first_field = self.offsets.len();
+            self.scan_frame(&buf[pos..pos + end], first_field, scan)
+                .map_err(|e| e.offset_by(pos))?;
+            self.frames.push(Frame {
+                start: pos,
+                end: pos + end,
+                first_field,
+                last_field: self.offsets.len(),
+            });
+            pos += end;
+        }
 
-            let tag = parse_tag(&buf[pos..eq_pos])?;
+        Ok(DecodeAll {
+            buf,
+            offsets: self.offsets.as_slice(),
+            frames: self.frames.iter(),
+            consumed: pos,
+        })
+    }
 
-            // SIMD scan for SOH (0x01) — delimits end of value
-            let soh_pos = memchr(FIELD_SEPARATOR, &buf[eq_pos + 1..])
-                .ok_or(FixError::IncompleteMessage)?
-                + eq_pos
-                + 1;
+    /// Scan and check one framed message for [`decode_all`](Self::decode_all),
+    /// appending its fields from `first_field` on. Error offsets are
+    /// relative to `frame`.
+    fn scan_frame(
+        &mut self,
+        frame: &[u8],
+        first_field: usize,
+        scan: ScanOptions,
+    ) -> Result<(), FixError> {
+        scan_fields(frame, 0, &mut self.offsets, scan)?;
+        check_begin_string(frame, &self.offsets[first_field..], &self.begin_strings)?;
+        check_empty_values(&self.offsets[first_field..], self.empty_values)?;
+        if self.strict {
+            check_strict(frame, &self.offsets[first_field..], &mut self.seen)?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the messages found by [`Decoder::decode_all`].
+#[derive(Debug, Clone)]
+pub struct DecodeAll<'a> {
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+    frames: std::slice::Iter<'a, Frame>,
+    consumed: usize,
+}
+
+impl<'a> DecodeAll<'a> {
+    /// Bytes after the last complete message: a partial message to keep for
+    /// the next read, or empty if `buf` ended on a message boundary.
+    #[inline]
+    pub fn remainder(&self) -> &'a [u8] {
+        &self.buf[self.consumed..]
+    }
+
+    /// Number of bytes taken up by complete messages.
+    #[inline]
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+}
 
-            // Store byte offsets — plain integers, no lifetimes, no unsafe needed.
-            self.offsets
-                .push((tag, (eq_pos + 1) as u32, soh_pos as u32));
+impl<'a> Iterator for DecodeAll<'a> {
+    type Item = Message<'a>;
 
-            pos = soh_pos + 1;
+    #[inline]
+    fn next(&mut self) -> Option<Message<'a>> {
+        self.frames.next().map(|f| {
+            Message::new(
+                &self.buf[f.start..f.end],
+                &self.offsets[f.first_field..f.last_field],
+            )
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.frames.size_hint()
+    }
+}
+
+impl ExactSizeIterator for DecodeAll<'_> {}
+
+/// Parse every `tag=value\x01` field of `buf` from `pos` on, appending
+/// offsets relative to the start of `buf`.
+#[inline]
+pub(crate) fn scan_fields(
+    buf: &[u8],
+    mut pos: usize,
+    offsets: &mut SmallVec<[(Tag, u32, u32); DEFAULT_CAPACITY]>,
+    opts: ScanOptions,
+) -> Result<(), FixError> {
+    // Offsets are stored as u32.
+    if buf.len() > MAX_MESSAGE_LEN {
+        return Err(FixError::MessageTooLarge { len: buf.len() });
+    }
+    // (data tag, byte length) announced by the previous `*Len` field.
+    let mut pending_data: Option<(Tag, usize)> = None;
+
+    while pos < buf.len() {
+        if opts.trim_whitespace && buf[pos..].iter().all(u8::is_ascii_whitespace) {
+            break;
         }
 
-        // Both borrows are genuinely 'a: offsets from &'a mut self, buf from
-        // &'a [u8]. No transmutes, no unsafe.
-        Ok(Message::new(buf, self.offsets.as_slice()))
+        // SIMD scan for '=' — delimits tag from value
+        let eq_pos = memchr(FIELD_KEY_VALUE_SEPARATOR, &buf[pos..])
+            .ok_or(FixError::IncompleteMessage { offset: pos })?
+            + pos;
+
+        let mut tag_start = pos;
+        let mut tag_bytes = &buf[pos..eq_pos];
+        if opts.trim_whitespace {
+            tag_start += tag_bytes.len() - tag_bytes.trim_ascii_start().len();
+            tag_bytes = tag_bytes.trim_ascii();
+        }
+        let tag = parse_tag(tag_bytes).map_err(|e| e.offset_by(tag_start))?;
+
+        let soh_pos = match pending_data.take() {
+            // Length-prefixed data: the value may contain SOH, so take exactly
+            // the announced number of bytes.
+            Some((data_tag, len)) if data_tag == tag => {
+                // An overflowing length runs past any buffer.
+                let end = (eq_pos + 1).checked_add(len);
+                match end.and_then(|end| buf.get(end).map(|&b| (end, b))) {
+                    Some((end, FIELD_SEPARATOR)) => end,
+                    Some((end, _)) => {
+                        return Err(FixError::DataLengthMismatch { tag, offset: end });
+                    }
+                    None => return Err(FixError::IncompleteMessage { offset: pos }),
+                }
+            }
+            // SIMD scan for SOH (0x01) — delimits end of value
+            _ => match memchr(FIELD_SEPARATOR, &buf[eq_pos + 1..]) {
+                Some(i) => i + eq_pos + 1,
+                // Lenient: the last value runs to the end of the buffer.
+                None if opts.lenient => buf.len(),
+                None => return Err(FixError::IncompleteMessage { offset: pos }),
+            },
+        };
+
+        if let Some(data_tag) = data_tag_for_length(tag) {
+            pending_data = parse_body_length(&buf[eq_pos + 1..soh_pos]).map(|len| (data_tag, len));
+        }
+
+        // Store byte offsets — plain integers, no lifetimes, no unsafe needed.
+        offsets.push((tag, (eq_pos + 1) as u32, soh_pos as u32));
+
+        pos = soh_pos + 1;
+    }
+
+    Ok(())
+}
+
+/// Offset of the first `8=` in `buf` that starts a field (at the start of
+/// `buf` or after a non-digit byte, so `58=` does not match).
+fn message_start(buf: &[u8]) -> Option<usize> {
+    memmem::find_iter(buf, b"8=").find(|&i| i == 0 || !buf[i - 1].is_ascii_digit())
+}
+
+/// Check BeginString (8) against `allowed`; an empty list accepts any.
+pub(crate) fn check_begin_string(
+    buf: &[u8],
+    offsets: &[(Tag, u32, u32)],
+    allowed: &[Box<[u8]>],
+) -> Result<(), FixError> {
+    if allowed.is_empty() {
+        return Ok(());
+    }
+    let begin_string = offsets
+        .iter()
+        .find(|f| f.0 == tag::BEGIN_STRING)
+        .map(|&(_, start, end)| &buf[start as usize..end as usize]);
+    match begin_string {
+        Some(v) if allowed.iter().any(|a| **a == *v) => Ok(()),
+        _ => Err(FixError::UnexpectedBeginString),
     }
 }
 
+/// Apply [`Decoder::empty_values`] to the fields of one message.
+fn check_empty_values(
+    offsets: &[(Tag, u32, u32)],
+    policy: EmptyValuePolicy,
+) -> Result<(), FixError> {
+    let empty = offsets.iter().filter(|f| f.1 == f.2).map(|f| f.0);
+    policy.apply(empty).map_err(FixError::EmptyValue)
+}
+
+/// The structural checks of [`Decoder::strict`] over the fields of one
+/// message. `seen` is scratch space, cleared on entry.
+fn check_strict(
+    buf: &[u8],
+    offsets: &[(Tag, u32, u32)],
+    seen: &mut Vec<Tag>,
+) -> Result<(), FixError> {
+    let value = |&(_, start, end): &(Tag, u32, u32)| &buf[start as usize..end as usize];
+
+    let tag_at = |i: usize| offsets.get(i).map(|f| f.0);
+    if tag_at(0) != Some(tag::BEGIN_STRING) {
+        return Err(FixError::TagOutOfOrder(tag::BEGIN_STRING));
+    }
+    if tag_at(1) != Some(tag::BODY_LENGTH) {
+        return Err(FixError::TagOutOfOrder(tag::BODY_LENGTH));
+    }
+    if offsets.last().map(|f| f.0) != Some(tag::CHECK_SUM) {
+        return Err(FixError::TagOutOfOrder(tag::CHECK_SUM));
+    }
+
+    if let Some(&(t, ..)) = offsets.iter().find(|f| f.1 == f.2) {
+        return Err(FixError::EmptyValue(t));
+    }
+
+    let appl_ver_id = offsets.iter().find(|f| f.0 == tag::APPL_VER_ID).map(value);
+    let specs = specs_for_version(Some(value(&offsets[0])), appl_ver_id);
+    let in_group = |t: Tag| {
+        specs
+            .iter()
+            .any(|s| s.count_tag != t && s.member_tags.contains(&t))
+    };
+
+    seen.clear();
+    seen.extend(offsets.iter().map(|f| f.0).filter(|&t| !in_group(t)));
+    seen.sort_unstable();
+    match seen.windows(2).find(|w| w[0] == w[1]) {
+        Some(w) => Err(FixError::DuplicateTag(w[0])),
+        None => Ok(()),
+    }
+}
+
+/// Length of the message at the start of `buf`, or `None` if `buf` holds
+/// only part of it.
+pub(crate) fn frame_end(buf: &[u8]) -> Result<Option<usize>, FixError> {
+    if buf.len() < 2 {
+        return Ok(None);
+    }
+    if &buf[..2] != b"8=" {
+        return Err(FixError::DecodeError);
+    }
+    let Some(soh8) = memchr(FIELD_SEPARATOR, buf) else {
+        return Ok(None);
+    };
+
+    // Tag 9 must follow immediately.
+    let rest = &buf[soh8 + 1..];
+    let Some(soh9) = memchr(FIELD_SEPARATOR, rest) else {
+        return Ok(None);
+    };
+    let field9 = &rest[..soh9];
+    let field9_start = soh8 + 1;
+    let invalid = || FixError::InvalidBodyLength {
+        offset: field9_start,
+    };
+    let eq = memchr(FIELD_KEY_VALUE_SEPARATOR, field9).ok_or_else(invalid)?;
+    if parse_tag(&field9[..eq]).map_err(|e| e.offset_by(field9_start))? != tag::BODY_LENGTH {
+        return Err(invalid());
+    }
+    let body_length = parse_body_length(&field9[eq + 1..]).ok_or_else(invalid)?;
+
+    if body_length > MAX_MESSAGE_LEN {
+        return Err(invalid());
+    }
+
+    // Tag 10 starts right after the body.
+    let checksum_start = (field9_start + soh9 + 1)
+        .checked_add(body_length)
+        .ok_or_else(invalid)?;
+    if buf.len().saturating_sub(checksum_start) < 3 {
+        return Ok(None);
+    }
+    if &buf[checksum_start..checksum_start + 3] != b"10=" {
+        return Err(invalid());
+    }
+    Ok(memchr(FIELD_SEPARATOR, &buf[checksum_start + 3..]).map(|i| checksum_start + 3 + i + 1))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,7 +754,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"8").unwrap_err(),
-            FixError::IncompleteMessage
+            FixError::IncompleteMessage { offset: 0 }
         ));
     }
 
@@ -274,7 +763,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"8=FIX.4.2").unwrap_err(),
-            FixError::IncompleteMessage
+            FixError::IncompleteMessage { offset: 0 }
         ));
     }
 
@@ -283,7 +772,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"8=FIX.4.2\x0135").unwrap_err(),
-            FixError::IncompleteMessage
+            FixError::IncompleteMessage { offset: 10 }
         ));
     }
 
@@ -292,7 +781,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"8=FIX.4.2\x0135=D").unwrap_err(),
-            FixError::IncompleteMessage
+            FixError::IncompleteMessage { offset: 10 }
         ));
     }
 
@@ -302,7 +791,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"\x01").unwrap_err(),
-            FixError::IncompleteMessage
+            FixError::IncompleteMessage { offset: 0 }
         ));
     }
 
@@ -316,7 +805,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"=val\x01").unwrap_err(),
-            FixError::InvalidTag
+            FixError::InvalidTag { .. }
         ));
     }
 
@@ -325,7 +814,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"8X=val\x01").unwrap_err(),
-            FixError::InvalidTag
+            FixError::InvalidTag { offset: 1 }
         ));
     }
 
@@ -335,7 +824,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"9999999999=val\x01").unwrap_err(),
-            FixError::InvalidTag
+            FixError::InvalidTag { .. }
         ));
     }
 
@@ -345,7 +834,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"4294967296=val\x01").unwrap_err(),
-            FixError::InvalidTag
+            FixError::InvalidTag { .. }
         ));
     }
 
@@ -354,7 +843,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b" 8=val\x01").unwrap_err(),
-            FixError::InvalidTag
+            FixError::InvalidTag { .. }
         ));
     }
 
@@ -363,7 +852,7 @@ mod tests {
         let mut dec = Decoder::new();
         assert!(matches!(
             dec.decode(b"8 =val\x01").unwrap_err(),
-            FixError::InvalidTag
+            FixError::InvalidTag { .. }
         ));
     }
 
@@ -384,7 +873,7 @@ mod tests {
         // so value = b"" and pos advances to 'v'. "val" then has no '=' → IncompleteMessage.
         let mut dec = Decoder::new();
         let err = dec.decode(b"8=\x01val\x01").unwrap_err();
-        assert!(matches!(err, FixError::IncompleteMessage));
+        assert!(matches!(err, FixError::IncompleteMessage { offset: 3 }));
     }
 
     #[test]
@@ -393,7 +882,7 @@ mod tests {
         // then "B\x01" has no '=' → IncompleteMessage.
         let mut dec = Decoder::new();
         let err = dec.decode(b"8=A\x01B\x01").unwrap_err();
-        assert!(matches!(err, FixError::IncompleteMessage));
+        assert!(matches!(err, FixError::IncompleteMessage { offset: 4 }));
     }
 
     #[test]
@@ -402,7 +891,7 @@ mod tests {
         // Second byte \x01 has no '=' → IncompleteMessage.
         let mut dec = Decoder::new();
         let err = dec.decode(b"8=\x01\x01").unwrap_err();
-        assert!(matches!(err, FixError::IncompleteMessage));
+        assert!(matches!(err, FixError::IncompleteMessage { offset: 3 }));
     }
 
     #[test]
@@ -732,6 +1221,46 @@ mod tests {
         assert_eq!(msg.all_groups().count(), 0);
     }
 
+    #[test]
+    fn all_groups_routes_fixt_by_appl_ver_id() {
+        // NO_ROOT_PARTY_IDS is a FIX 5.0 group; NO_PARTY_IDS arrived in 4.4.
+        let mut dec = Decoder::new();
+        let raw: &[u8] = b"8=FIXT.1.1\x0135=D\x011128=9\x011116=1\x011117=R\x01453=1\x01448=P\x01";
+        let msg = dec.decode(raw).unwrap();
+        assert_eq!(msg.group_specs().len(), crate::group::FIX50SP2_GROUPS.len());
+        let found: Vec<_> = msg.all_groups().map(|(spec, _)| spec.count_tag).collect();
+        assert_eq!(
+            found,
+            [crate::tag::NO_PARTY_IDS, crate::tag::NO_ROOT_PARTY_IDS]
+        );
+
+        let msg = dec.decode(b"8=FIXT.1.1\x0135=0\x01").unwrap();
+        assert_eq!(msg.group_specs().len(), crate::group::FIX50SP2_GROUPS.len());
+        let msg = dec.decode(b"8=FIXT.1.1\x011128=6\x01").unwrap();
+        assert_eq!(msg.group_specs().len(), crate::group::FIX44_GROUPS.len());
+        let msg = dec
+            .decode(b"8=FIXT.1.1\x011128=4\x01453=1\x01448=P\x01")
+            .unwrap();
+        assert_eq!(msg.all_groups().count(), 0);
+    }
+
+    #[test]
+    fn all_groups_uses_the_begin_string_table() {
+        // IOI qualifiers arrived in 4.1 and parties in 4.3.
+        let mut dec = Decoder::new();
+        let body = b"35=6\x01199=1\x01104=A\x01453=1\x01448=P\x01";
+        let count = |dec: &mut Decoder, begin: &[u8]| {
+            let raw = [begin, &body[..]].concat();
+            dec.decode(&raw).unwrap().all_groups().count()
+        };
+        assert_eq!(count(&mut dec, b"8=FIX.4.0\x01"), 0);
+        assert_eq!(count(&mut dec, b"8=FIX.4.1\x01"), 1);
+        assert_eq!(count(&mut dec, b"8=FIX.4.2\x01"), 1);
+        assert_eq!(count(&mut dec, b"8=FIX.4.3\x01"), 2);
+        assert_eq!(count(&mut dec, b"8=FIXT.1.1\x011128=3\x01"), 1);
+        assert_eq!(count(&mut dec, b"8=FIXT.1.1\x011128=5\x01"), 2);
+    }
+
     #[test]
     fn all_groups_instances_are_correct() {
         // Verify that instances returned through all_groups() have the right field values.
@@ -766,6 +1295,39 @@ mod tests {
         assert!(all.next().is_none());
     }
 
+    #[test]
+    fn cached_groups_match_scanned_groups() {
+        use crate::group::{MD_ENTRIES, QUOTE_REQUEST_RELATED_SYM, RELATED_SYM};
+
+        let raw =
+            b"35=W\x01268=3\x01269=0\x01270=1\x01269=1\x01270=2\x01271=5\x01269=2\x0110=000\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let scanned: Vec<Vec<Tag>> = msg
+            .groups(&MD_ENTRIES)
+            .map(|g| g.fields().map(|f| f.tag).collect())
+            .collect();
+
+        msg.cache_groups();
+        assert_eq!(msg.all_groups().count(), 1);
+        for _ in 0..2 {
+            let it = msg.groups(&MD_ENTRIES);
+            assert_eq!(it.size_hint(), (3, Some(3)));
+            let cached: Vec<Vec<Tag>> = it.map(|g| g.fields().map(|f| f.tag).collect()).collect();
+            assert_eq!(cached, scanned);
+        }
+
+        // Specs sharing a count tag are cached separately.
+        let raw = b"35=R\x01146=2\x0155=A\x0146=X\x0155=B\x01";
+        let msg = dec.decode(raw).unwrap();
+        msg.cache_groups();
+        let first_len = |spec| msg.groups(spec).next().unwrap().len();
+        assert_eq!(first_len(&QUOTE_REQUEST_RELATED_SYM), 2);
+        assert_eq!(first_len(&RELATED_SYM), 1);
+        assert_eq!(first_len(&QUOTE_REQUEST_RELATED_SYM), 2);
+        assert_eq!(msg.groups(&MD_ENTRIES).count(), 0);
+    }
+
     // -------------------------------------------------------------------------
     // Group 10 — validate_body_length() and validate_checksum()
     // -------------------------------------------------------------------------
@@ -793,7 +1355,7 @@ mod tests {
             .unwrap();
         assert!(matches!(
             msg.validate_body_length().unwrap_err(),
-            FixError::InvalidBodyLength
+            FixError::InvalidBodyLength { offset: 10 }
         ));
     }
 
@@ -815,7 +1377,7 @@ mod tests {
         let msg = dec.decode(b"8=FIX.4.2\x0135=D\x01").unwrap();
         assert!(matches!(
             msg.validate_body_length().unwrap_err(),
-            FixError::InvalidBodyLength
+            FixError::InvalidBodyLength { offset: 10 }
         ));
     }
 
@@ -828,7 +1390,7 @@ mod tests {
             .unwrap();
         assert!(matches!(
             msg.validate_body_length().unwrap_err(),
-            FixError::InvalidBodyLength
+            FixError::InvalidBodyLength { offset: 10 }
         ));
     }
 
@@ -841,7 +1403,7 @@ mod tests {
             .unwrap();
         assert!(matches!(
             msg.validate_body_length().unwrap_err(),
-            FixError::InvalidBodyLength
+            FixError::InvalidBodyLength { offset: 10 }
         ));
     }
 
@@ -865,7 +1427,7 @@ mod tests {
             .unwrap();
         assert!(matches!(
             msg.validate_checksum().unwrap_err(),
-            FixError::InvalidCheckSum
+            FixError::InvalidCheckSum { offset: 19 }
         ));
     }
 
@@ -887,7 +1449,7 @@ mod tests {
         let msg = dec.decode(b"8=FIX.4.2\x0135=D\x01").unwrap();
         assert!(matches!(
             msg.validate_checksum().unwrap_err(),
-            FixError::InvalidCheckSum
+            FixError::InvalidCheckSum { offset: 10 }
         ));
     }
 
@@ -898,7 +1460,7 @@ mod tests {
         let msg = dec.decode(b"8=FIX.4.2\x0110=181\x0135=D\x01").unwrap();
         assert!(matches!(
             msg.validate_checksum().unwrap_err(),
-            FixError::InvalidCheckSum
+            FixError::InvalidCheckSum { offset: 17 }
         ));
     }
 
@@ -912,4 +1474,569 @@ mod tests {
         assert!(msg.validate_body_length().is_ok());
         assert!(msg.validate_checksum().is_ok());
     }
+
+    #[test]
+    fn validate_with_dictionary() {
+        use crate::dictionary::Dictionary;
+
+        let mut dict = Dictionary::fix42();
+        dict.define_message(b"D", &[tag::CL_ORD_ID, tag::SIDE], &[tag::CL_ORD_ID]);
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"8=FIX.4.2\x019=25\x0135=D\x0149=SENDER\x0111=A\x0110=195\x01")
+            .unwrap();
+        assert!(msg.validate_with(&dict).is_ok());
+
+        let msg = dec
+            .decode(b"8=FIX.4.2\x019=25\x0135=D\x0159=9\x0110=195\x01")
+            .unwrap();
+        let violations = msg.validate_with(&dict).unwrap_err();
+        let found: Vec<_> = violations
+            .iter()
+            .map(|v| (v.tag, v.reason.code()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (tag::TIME_IN_FORCE, 2),
+                (tag::CL_ORD_ID, 1),
+                (tag::TIME_IN_FORCE, 5)
+            ]
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    // Group 11 — IntoIterator and Index<usize>
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn for_loop_and_indexing_over_message() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"8=FIX.4.2\x0135=D\x0149=SENDER\x01").unwrap();
+
+        let mut tags = Vec::new();
+        for field in &msg {
+            tags.push(field.tag);
+        }
+        assert_eq!(tags, vec![8, 35, 49]);
+        assert_eq!((&msg).into_iter().filter(|f| f.tag > 10).count(), 2);
+        assert_eq!(&msg[1], b"D");
+        assert_eq!(&msg[2], b"SENDER");
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds_panics() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=D\x01").unwrap();
+        let _ = &msg[1];
+    }
+
+    #[test]
+    fn fields_exact_size_and_reverse() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"8=FIX.4.2\x019=5\x0135=D\x0110=181\x01")
+            .unwrap();
+
+        let mut fields = msg.fields();
+        assert_eq!(fields.len(), 4);
+        fields.next();
+        assert_eq!(fields.len(), 3);
+
+        // Trailer access from the back.
+        let last = msg.fields().next_back().unwrap();
+        assert_eq!(last.tag, 10);
+        assert_eq!(last.value, b"181");
+        let rev: Vec<u32> = msg.fields().rev().map(|f| f.tag).collect();
+        assert_eq!(rev, vec![10, 35, 9, 8]);
+    }
+
+    #[test]
+    fn fields_compare_and_hash_by_tag_and_value() {
+        use std::collections::HashSet;
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=D\x0155=AAPL\x0155=AAPL\x01").unwrap();
+        let a = msg.field(1);
+        let b = a;
+        assert_eq!(a, b);
+        assert_eq!(msg.field(1), msg.field(2));
+        assert_ne!(msg.field(0), msg.field(1));
+
+        let distinct: HashSet<crate::field::Field<'_>> = msg.fields().collect();
+        assert_eq!(distinct.len(), 2);
+    }
+
+    #[test]
+    fn find_all_yields_every_occurrence_in_wire_order() {
+        let mut dec = Decoder::new();
+        let raw: Vec<u8> = (0..40)
+            .flat_map(|i| format!("55=S{i}\x0154=1\x01").into_bytes())
+            .collect();
+        let msg = dec.decode(&raw).unwrap();
+        let symbols: Vec<&[u8]> = msg.find_all(55).map(|f| f.value).collect();
+        assert_eq!(symbols.len(), 40);
+        assert_eq!(symbols[0], b"S0");
+        assert_eq!(symbols[39], b"S39");
+        assert_eq!(msg.find(55).unwrap().value, b"S0");
+        assert_eq!(msg.find_all(44).count(), 0);
+
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"136=2\x01137=1\x01139=A\x01137=2\x01139=B\x01")
+            .unwrap();
+        let g = msg.groups(&crate::group::MISC_FEES).next().unwrap();
+        assert_eq!(g.find_all(139).count(), 1);
+        assert_eq!(
+            msg.find_all(139).map(|f| f.value).collect::<Vec<_>>(),
+            [b"A", b"B"]
+        );
+        // Small messages are scanned without building the index.
+        assert!(!msg.has_sorted_index());
+    }
+
+    #[test]
+    fn find_from_resumes_in_wire_order() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"35=D\x01448=A\x01452=1\x01448=B\x01452=3\x0155=X\x01")
+            .unwrap();
+        let (i, f) = msg.find_from(0, 448).unwrap();
+        assert_eq!((i, f.value), (1, &b"A"[..]));
+        assert_eq!(msg.find_from(1, 448).unwrap().0, 1);
+        let (j, f) = msg.find_after(i, 448).unwrap();
+        assert_eq!((j, f.value), (3, &b"B"[..]));
+        assert!(msg.find_after(j, 448).is_none());
+        assert_eq!(msg.find_after(j, 452).unwrap().0, 4);
+        assert!(msg.find_from(99, 55).is_none());
+        assert!(msg.find_from(0, 44).is_none());
+    }
+
+    #[test]
+    fn raw_offsets_index_raw_buf() {
+        let raw: &[u8] = b"8=FIX.4.4\x0135=D\x0155=AAPL\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        assert_eq!(msg.raw_buf(), raw);
+        assert_eq!(
+            msg.raw_offsets(),
+            &[
+                (tag::BEGIN_STRING, 2, 9),
+                (tag::MSG_TYPE, 13, 14),
+                (tag::SYMBOL, 18, 22)
+            ]
+        );
+        for (i, &(t, start, end)) in msg.raw_offsets().iter().enumerate() {
+            assert_eq!(msg.field(i).tag, t);
+            assert_eq!(
+                msg.field(i).value,
+                &msg.raw_buf()[start as usize..end as usize]
+            );
+        }
+    }
+
+    #[test]
+    fn display_replaces_soh_and_names_tags() {
+        let raw: &[u8] = b"8=FIX.4.4\x0135=D\x0155=AAPL\x01268=1\x01269=0\x01270=1.5\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        assert_eq!(
+            msg.to_string(),
+            "8=FIX.4.4|35=D|55=AAPL|268=1|269=0|270=1.5|"
+        );
+        assert_eq!(
+            format!("{msg:#}"),
+            "BeginString=FIX.4.4|MsgType=D|Symbol=AAPL|NoMDEntries=1|MDEntryType=0|MDEntryPx=1.5|"
+        );
+        let entry = msg.groups(&group::MD_ENTRIES).next().unwrap();
+        assert_eq!(entry.to_string(), "269=0|270=1.5|");
+        assert_eq!(msg.to_owned().to_string(), msg.to_string());
+
+        // SOH inside a data value, invalid UTF-8 and unknown tags.
+        let raw: &[u8] = b"95=3\x0196=a\x01b\x0158=\xff\x019999=x\x01";
+        let msg = dec.decode(raw).unwrap();
+        assert_eq!(
+            format!("{msg:#}"),
+            "RawDataLength=3|RawData=a|b|Text=\u{fffd}|9999=x|"
+        );
+    }
+
+    #[test]
+    fn find_beyond_u16_field_positions() {
+        // 70,000 fields: positions past 65,535 must not wrap in the index.
+        let mut raw = Vec::new();
+        for t in 1..=70_000u32 {
+            raw.extend_from_slice(format!("{t}=v{t}\x01").as_bytes());
+        }
+        raw.extend_from_slice(b"1=again\x01");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        assert_eq!(msg.len(), 70_001);
+        for (pos, t) in [(65_534, 65_535), (65_535, 65_536), (69_999, 70_000)] {
+            assert_eq!(msg.find_from(0, t).unwrap().0, pos);
+            assert_eq!(msg.find(t).unwrap().value, format!("v{t}").as_bytes());
+        }
+        assert_eq!(msg.find_after(0, 1).unwrap().0, 70_000);
+        assert_eq!(
+            msg.find_all(1).map(|f| f.value).collect::<Vec<_>>(),
+            [&b"v1"[..], b"again"]
+        );
+    }
+
+    #[test]
+    fn find_agrees_on_small_and_large_messages() {
+        let mut dec = Decoder::new();
+        let small: &[u8] = b"35=D\x01448=A\x0155=X\x01448=B\x01";
+        let large: &[u8] = b"35=D\x01448=A\x0155=X\x01448=B\x011=a\x012=b\x013=c\x014=d\x015=e\x016=f\x017=g\x018=h\x01";
+        for raw in [small, large] {
+            let msg = dec.decode(raw).unwrap();
+            for tag in [35, 448, 55, 8, 999] {
+                let expected = msg.fields().position(|f| f.tag == tag);
+                assert_eq!(msg.find_from(0, tag).map(|(i, _)| i), expected);
+                assert_eq!(
+                    msg.find(tag).map(|f| f.value),
+                    expected.map(|i| msg.field(i).value)
+                );
+            }
+            assert_eq!(msg.find_after(1, 448).unwrap().0, 3);
+            assert!(msg.find_from(msg.len() + 1, 35).is_none());
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Group 12 — decode_all() over back-to-back messages
+    // -------------------------------------------------------------------------
+
+    const HEARTBEAT: &[u8] = b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x01";
+    const LOGOUT: &[u8] = b"8=FIX.4.2\x019=5\x0135=5\x0110=166\x01";
+
+    #[test]
+    fn decode_all_yields_each_message() {
+        let mut buf = HEARTBEAT.to_vec();
+        buf.extend_from_slice(LOGOUT);
+
+        let mut dec = Decoder::new();
+        let batch = dec.decode_all(&buf).unwrap();
+        assert!(batch.remainder().is_empty());
+        assert_eq!(batch.consumed(), buf.len());
+        let msgs: Vec<_> = batch.collect();
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs[0].find(crate::tag::MSG_TYPE).unwrap().value, b"0");
+        assert_eq!(msgs[1].find(crate::tag::MSG_TYPE).unwrap().value, b"5");
+        for m in &msgs {
+            assert!(m.validate_body_length().is_ok());
+            assert!(m.validate_checksum().is_ok());
+        }
+    }
+
+    #[test]
+    fn decode_all_leaves_partial_tail() {
+        let mut buf = HEARTBEAT.to_vec();
+        for cut in 1..LOGOUT.len() {
+            buf.truncate(HEARTBEAT.len());
+            buf.extend_from_slice(&LOGOUT[..cut]);
+            let mut dec = Decoder::new();
+            let batch = dec.decode_all(&buf).unwrap();
+            assert_eq!(batch.len(), 1);
+            assert_eq!(batch.remainder(), &LOGOUT[..cut]);
+        }
+    }
+
+    #[test]
+    fn decode_all_empty_buffer() {
+        let mut dec = Decoder::new();
+        let batch = dec.decode_all(b"").unwrap();
+        assert_eq!(batch.len(), 0);
+        assert!(batch.remainder().is_empty());
+    }
+
+    #[test]
+    fn decode_all_rejects_bad_framing() {
+        let mut dec = Decoder::new();
+        assert!(matches!(
+            dec.decode_all(b"35=0\x01"),
+            Err(FixError::DecodeError)
+        ));
+        assert!(matches!(
+            dec.decode_all(b"8=FIX.4.2\x0135=0\x0110=000\x01"),
+            Err(FixError::InvalidBodyLength { offset: 10 })
+        ));
+        // BodyLength too short: tag 10 is not where 9 says it is.
+        assert!(matches!(
+            dec.decode_all(b"8=FIX.4.2\x019=3\x0135=0\x0110=161\x01"),
+            Err(FixError::InvalidBodyLength { offset: 10 })
+        ));
+    }
+
+    #[test]
+    fn decode_all_error_offsets_are_in_buf() {
+        let mut dec = Decoder::new();
+        let batch = b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x018=FIX.4.2\x019=5\x013x=0\x0110=000\x01";
+        assert!(matches!(
+            dec.decode_all(batch),
+            Err(FixError::InvalidTag { offset: 41 })
+        ));
+        assert!(matches!(
+            dec.decode_all(b"8=FIX.4.2\x019x=5\x0135=0\x0110=161\x01"),
+            Err(FixError::InvalidTag { offset: 11 })
+        ));
+        let batch = b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x018=FIX.4.2\x019=3\x0135=0\x0110=161\x01";
+        assert!(matches!(
+            dec.decode_all(batch),
+            Err(FixError::InvalidBodyLength { offset: 36 })
+        ));
+    }
+
+    #[test]
+    fn decode_all_rejects_overflowing_body_length() {
+        let mut dec = Decoder::new();
+        assert!(matches!(
+            dec.decode_all(b"8=FIX.4.2\x019=18446744073709551615\x0135=0\x0110=161\x01"),
+            Err(FixError::InvalidBodyLength { offset: 10 })
+        ));
+        assert!(matches!(
+            dec.decode_all(b"8=FIX.4.2\x019=4294967296\x0135=0\x0110=161\x01"),
+            Err(FixError::InvalidBodyLength { offset: 10 })
+        ));
+    }
+
+    // -------------------------------------------------------------------------
+    // Group 13 — Length-prefixed data fields
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn data_field_may_contain_soh() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"35=B\x0195=5\x0196=a\x01b=c\x0158=x\x01")
+            .unwrap();
+        assert_eq!(msg.len(), 4);
+        assert_eq!(msg.find(crate::tag::RAW_DATA).unwrap().value, b"a\x01b=c");
+        assert_eq!(msg.find(crate::tag::TEXT).unwrap().value, b"x");
+    }
+
+    #[test]
+    fn data_field_length_mismatch() {
+        let mut dec = Decoder::new();
+        assert!(matches!(
+            dec.decode(b"354=2\x01355=abc\x01"),
+            Err(FixError::DataLengthMismatch {
+                tag: 355,
+                offset: 12
+            })
+        ));
+        assert!(matches!(
+            dec.decode(b"354=9\x01355=abc\x01"),
+            Err(FixError::IncompleteMessage { offset: 6 })
+        ));
+    }
+
+    #[test]
+    fn data_length_overflow_is_incomplete() {
+        let mut dec = Decoder::new();
+        assert!(matches!(
+            dec.decode(b"35=B\x0195=18446744073709551615\x0196=a\x01"),
+            Err(FixError::IncompleteMessage { offset: 29 })
+        ));
+    }
+
+    #[test]
+    fn data_length_without_data_tag_scans_normally() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"95=100\x0158=x\x01").unwrap();
+        assert_eq!(msg.field(1).value, b"x");
+    }
+
+    // -------------------------------------------------------------------------
+    // Strict mode
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn strict_accepts_well_formed_message_with_group() {
+        let mut dec = Decoder::new();
+        dec.strict(true);
+        let msg = dec
+            .decode(b"8=FIX.4.2\x019=5\x0135=D\x01136=2\x01137=1\x01138=USD\x01137=2\x0110=000\x01")
+            .unwrap();
+        assert_eq!(msg.len(), 8);
+    }
+
+    #[test]
+    fn strict_rejects_misplaced_header_and_trailer() {
+        let mut dec = Decoder::new();
+        dec.strict(true);
+        let cases: [(&[u8], Tag); 4] = [
+            (b"35=D\x018=FIX.4.2\x019=5\x0110=000\x01", tag::BEGIN_STRING),
+            (b"8=FIX.4.2\x0135=D\x019=5\x0110=000\x01", tag::BODY_LENGTH),
+            (b"8=FIX.4.2\x019=5\x0110=000\x0135=D\x01", tag::CHECK_SUM),
+            (b"", tag::BEGIN_STRING),
+        ];
+        for (raw, expected) in cases {
+            match dec.decode(raw) {
+                Err(FixError::TagOutOfOrder(t)) => assert_eq!(t, expected),
+                other => panic!("expected TagOutOfOrder({expected}), got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn strict_rejects_duplicates_outside_groups_and_empty_values() {
+        let mut dec = Decoder::new();
+        dec.strict(true);
+        assert!(matches!(
+            dec.decode(b"8=FIX.4.2\x019=5\x0135=D\x0135=G\x0110=000\x01"),
+            Err(FixError::DuplicateTag(35))
+        ));
+        assert!(matches!(
+            dec.decode(b"8=FIX.4.2\x019=5\x0135=D\x0158=\x0110=000\x01"),
+            Err(FixError::EmptyValue(58))
+        ));
+
+        // The same input decodes when strict mode is off.
+        dec.strict(false);
+        assert!(
+            dec.decode(b"8=FIX.4.2\x019=5\x0135=D\x0158=\x0110=000\x01")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn strict_applies_to_each_message_of_decode_all() {
+        let mut dec = Decoder::new();
+        dec.strict(true);
+        let mut buf = b"8=FIX.4.2\x019=5\x0135=0\x0110=000\x01".to_vec();
+        assert_eq!(dec.decode_all(&buf).unwrap().len(), 1);
+        buf.extend_from_slice(b"8=FIX.4.2\x019=15\x0135=0\x0149=A\x0149=B\x0110=000\x01");
+        assert!(matches!(
+            dec.decode_all(&buf),
+            Err(FixError::DuplicateTag(49))
+        ));
+    }
+
+    #[test]
+    fn begin_strings_whitelist() {
+        let fix42 = b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x01";
+        let fix44 = b"8=FIX.4.4\x019=5\x0135=0\x0110=163\x01";
+        let mut dec = Decoder::new();
+        dec.begin_strings(["FIX.4.4"]);
+        assert!(dec.decode(fix44).is_ok());
+        assert!(matches!(
+            dec.decode(fix42),
+            Err(FixError::UnexpectedBeginString)
+        ));
+        assert!(matches!(
+            dec.decode(b"35=0\x0110=000\x01"),
+            Err(FixError::UnexpectedBeginString)
+        ));
+        let both = [&fix44[..], &fix42[..]].concat();
+        assert!(matches!(
+            dec.decode_all(&both),
+            Err(FixError::UnexpectedBeginString)
+        ));
+
+        dec.begin_strings(["FIX.4.4", "FIX.4.2"]);
+        assert_eq!(dec.decode_all(&both).unwrap().len(), 2);
+        dec.begin_strings::<_, &[u8]>([]);
+        assert!(dec.decode(b"35=0\x01").is_ok());
+    }
+
+    #[test]
+    fn empty_values_policy() {
+        let raw = b"8=FIX.4.2\x019=4\x0135=\x0110=000\x01";
+        let mut dec = Decoder::new();
+        assert!(dec.decode(raw).is_ok());
+        dec.empty_values(EmptyValuePolicy::Reject);
+        assert!(matches!(dec.decode(raw), Err(FixError::EmptyValue(35))));
+        assert!(matches!(dec.decode_all(raw), Err(FixError::EmptyValue(35))));
+        dec.empty_values(EmptyValuePolicy::Warn(|t| assert_eq!(t, 35)));
+        assert_eq!(dec.decode(raw).unwrap().find(35).unwrap().value, b"");
+    }
+
+    // -------------------------------------------------------------------------
+    // Lenient mode
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn lenient_skips_leading_bytes_and_accepts_missing_final_soh() {
+        let raw = b"2024-02-29 12:00:01 IN 8=FIX.4.2\x019=5\x0135=0\x0110=161";
+        let mut dec = Decoder::new();
+        assert!(dec.decode(raw).is_err());
+
+        dec.lenient(true);
+        let msg = dec.decode(raw).unwrap();
+        let tags: Vec<Tag> = msg.fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [8, 9, 35, 10]);
+        assert_eq!(msg.find(tag::CHECK_SUM).unwrap().value, b"161");
+
+        // "58=" is not mistaken for the start of the message.
+        let msg = dec.decode(b"58=x\x0135=0\x01").unwrap();
+        assert_eq!(msg.len(), 2);
+    }
+
+    #[test]
+    fn trim_tag_whitespace_ignores_spaces_around_tags_and_trailing_newline() {
+        let raw = b"8=FIX.4.2\x01 9 =5\x01\n35=0\x01 10=161\x01\r\n";
+        let mut dec = Decoder::new();
+        assert!(matches!(
+            dec.decode(raw),
+            Err(FixError::InvalidTag { offset: 10 })
+        ));
+
+        dec.trim_tag_whitespace(true);
+        let msg = dec.decode(raw).unwrap();
+        let tags: Vec<Tag> = msg.fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [8, 9, 35, 10]);
+        assert_eq!(msg.find(tag::BODY_LENGTH).unwrap().value, b"5");
+    }
+
+    #[test]
+    fn lenient_decode_all_skips_noise_between_messages() {
+        let one = b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x01";
+        let mut buf = b"<- ".to_vec();
+        buf.extend_from_slice(one);
+        buf.extend_from_slice(b"\n<- ");
+        buf.extend_from_slice(one);
+        buf.extend_from_slice(b"\n");
+
+        let mut dec = Decoder::new();
+        dec.lenient(true);
+        let batch = dec.decode_all(&buf).unwrap();
+        assert_eq!(batch.remainder(), b"\n");
+        assert_eq!(batch.count(), 2);
+    }
+
+    // -------------------------------------------------------------------------
+    // decode_verified
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn decode_verified_accepts_valid_message() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode_verified(b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x01")
+            .unwrap();
+        assert_eq!(msg.len(), 4);
+    }
+
+    #[test]
+    fn decode_verified_reports_declared_and_computed_values() {
+        let mut dec = Decoder::new();
+        match dec.decode_verified(b"8=FIX.4.2\x019=7\x0135=0\x0110=161\x01") {
+            Err(FixError::BodyLengthMismatch { declared, computed }) => {
+                assert_eq!((declared, computed), (7, 5));
+            }
+            other => panic!("expected BodyLengthMismatch, got {other:?}"),
+        }
+        match dec.decode_verified(b"8=FIX.4.2\x019=5\x0135=0\x0110=007\x01") {
+            Err(FixError::CheckSumMismatch { declared, computed }) => {
+                assert_eq!((declared, computed), (7, 161));
+            }
+            other => panic!("expected CheckSumMismatch, got {other:?}"),
+        }
+        assert!(matches!(
+            dec.decode_verified(b"8=FIX.4.2\x0135=0\x0110=161\x01"),
+            Err(FixError::InvalidBodyLength { offset: 10 })
+        ));
+    }
 }
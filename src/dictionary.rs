@@ -0,0 +1,786 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::group::{FIX42_GROUPS, FIX44_GROUPS, GroupSpec};
+use crate::tag::{self, Tag};
+
+/// Highest tag number defined by FIX 4.2.
+const FIX42_MAX_TAG: Tag = 446;
+/// Highest tag number defined by FIX 4.4.
+const FIX44_MAX_TAG: Tag = 956;
+/// Tag numbers inside the standard ranges that were never assigned.
+const UNASSIGNED_TAGS: &[Tag] = &[101, 261, 636, 809];
+
+/// Enumerated fields seeded into both built-in dictionaries.
+const COMMON_ENUMS: &[(Tag, &[&[u8]])] = &[
+    (tag::HANDL_INST, &[b"1", b"2", b"3"]),
+    (tag::POSS_DUP_FLAG, &[b"Y", b"N"]),
+    (tag::POSS_RESEND, &[b"Y", b"N"]),
+];
+/// Field types seeded into both built-in dictionaries. NumInGroup counts,
+/// data lengths and data fields are typed from the group and data-pair
+/// tables instead.
+const COMMON_TYPES: &[(Tag, FieldType)] = &[
+    (tag::ACCOUNT, FieldType::String),
+    (tag::AVG_PX, FieldType::Float),
+    (tag::BEGIN_SEQ_NO, FieldType::Int),
+    (tag::BODY_LENGTH, FieldType::Int),
+    (tag::CHECK_SUM, FieldType::Int),
+    (tag::CL_ORD_ID, FieldType::String),
+    (tag::CUM_QTY, FieldType::Float),
+    (tag::CURRENCY, FieldType::String),
+    (tag::END_SEQ_NO, FieldType::Int),
+    (tag::EXEC_ID, FieldType::String),
+    (tag::LAST_PX, FieldType::Float),
+    (tag::LAST_QTY, FieldType::Float),
+    (tag::MSG_SEQ_NUM, FieldType::Int),
+    (tag::MSG_TYPE, FieldType::String),
+    (tag::NEW_SEQ_NO, FieldType::Int),
+    (tag::ORDER_ID, FieldType::String),
+    (tag::ORDER_QTY, FieldType::Float),
+    (tag::ORD_STATUS, FieldType::Char),
+    (tag::ORD_TYPE, FieldType::Char),
+    (tag::PRICE, FieldType::Float),
+    (tag::REF_SEQ_NUM, FieldType::Int),
+    (tag::SENDER_COMP_ID, FieldType::String),
+    (tag::SIDE, FieldType::Char),
+    (tag::STOP_PX, FieldType::Float),
+    (tag::SYMBOL, FieldType::String),
+    (tag::TARGET_COMP_ID, FieldType::String),
+    (tag::TEST_REQ_ID, FieldType::String),
+    (tag::TEXT, FieldType::String),
+    (tag::TIME_IN_FORCE, FieldType::Char),
+    (tag::EXEC_TYPE, FieldType::Char),
+    (tag::HEART_BT_INT, FieldType::Int),
+    (tag::LEAVES_QTY, FieldType::Float),
+];
+/// FIX 4.2 TimeInForce (59) values.
+const FIX42_TIME_IN_FORCE: &[&[u8]] = &[b"0", b"1", b"2", b"3", b"4", b"5", b"6"];
+/// FIX 4.4 TimeInForce (59) values (adds 7 = At the Close).
+const FIX44_TIME_IN_FORCE: &[&[u8]] = &[b"0", b"1", b"2", b"3", b"4", b"5", b"6", b"7"];
+
+/// Standard header tags (FIX 4.4 StandardHeader component).
+pub const HEADER_TAGS: &[Tag] = &[
+    tag::BEGIN_STRING,
+    tag::BODY_LENGTH,
+    tag::MSG_TYPE,
+    tag::SENDER_COMP_ID,
+    tag::TARGET_COMP_ID,
+    tag::ON_BEHALF_OF_COMP_ID,
+    tag::DELIVER_TO_COMP_ID,
+    tag::SECURE_DATA_LEN,
+    tag::SECURE_DATA,
+    tag::MSG_SEQ_NUM,
+    tag::SENDER_SUB_ID,
+    tag::SENDER_LOCATION_ID,
+    tag::TARGET_SUB_ID,
+    tag::TARGET_LOCATION_ID,
+    tag::ON_BEHALF_OF_SUB_ID,
+    tag::ON_BEHALF_OF_LOCATION_ID,
+    tag::DELIVER_TO_SUB_ID,
+    tag::DELIVER_TO_LOCATION_ID,
+    tag::POSS_DUP_FLAG,
+    tag::POSS_RESEND,
+    tag::SENDING_TIME,
+    tag::ORIG_SENDING_TIME,
+    tag::XML_DATA_LEN,
+    tag::XML_DATA,
+    tag::MESSAGE_ENCODING,
+    tag::LAST_MSG_SEQ_NUM_PROCESSED,
+    tag::ON_BEHALF_OF_SENDING_TIME,
+    tag::NO_HOPS,
+    tag::HOP_COMP_ID,
+    tag::HOP_SENDING_TIME,
+    tag::HOP_REF_ID,
+];
+
+/// Standard trailer tags (FIX 4.4 StandardTrailer component).
+pub const TRAILER_TAGS: &[Tag] = &[tag::SIGNATURE_LENGTH, tag::SIGNATURE, tag::CHECK_SUM];
+
+/// For a length field of a length-prefixed data pair (RawDataLength 95,
+/// EncodedTextLen 354, XmlDataLen 212, …), the data tag whose byte count it
+/// carries. Data values may contain SOH, so the decoder reads exactly that
+/// many bytes instead of scanning for the delimiter.
+#[inline]
+pub fn data_tag_for_length(tag: Tag) -> Option<Tag> {
+    let data = match tag {
+        tag::SECURE_DATA_LEN => tag::SECURE_DATA,
+        tag::SIGNATURE_LENGTH => tag::SIGNATURE,
+        tag::RAW_DATA_LENGTH => tag::RAW_DATA,
+        tag::XML_DATA_LEN => tag::XML_DATA,
+        tag::ENCODED_ISSUER_LEN => tag::ENCODED_ISSUER,
+        tag::ENCODED_SECURITY_DESC_LEN => tag::ENCODED_SECURITY_DESC,
+        tag::ENCODED_LIST_EXEC_INST_LEN => tag::ENCODED_LIST_EXEC_INST,
+        tag::ENCODED_TEXT_LEN => tag::ENCODED_TEXT,
+        tag::ENCODED_SUBJECT_LEN => tag::ENCODED_SUBJECT,
+        tag::ENCODED_HEADLINE_LEN => tag::ENCODED_HEADLINE,
+        tag::ENCODED_ALLOC_TEXT_LEN => tag::ENCODED_ALLOC_TEXT,
+        tag::ENCODED_UNDERLYING_ISSUER_LEN => tag::ENCODED_UNDERLYING_ISSUER,
+        tag::ENCODED_UNDERLYING_SECURITY_DESC_LEN => tag::ENCODED_UNDERLYING_SECURITY_DESC,
+        tag::ENCODED_LIST_STATUS_TEXT_LEN => tag::ENCODED_LIST_STATUS_TEXT,
+        tag::ENCODED_LEG_ISSUER_LEN => tag::ENCODED_LEG_ISSUER,
+        tag::ENCODED_LEG_SECURITY_DESC_LEN => tag::ENCODED_LEG_SECURITY_DESC,
+        _ => return None,
+    };
+    Some(data)
+}
+
+/// Returns true if `tag` belongs to the standard header.
+#[inline]
+pub fn is_header_tag(tag: Tag) -> bool {
+    HEADER_TAGS.contains(&tag)
+}
+
+/// Returns true if `tag` belongs to the standard trailer.
+#[inline]
+pub fn is_trailer_tag(tag: Tag) -> bool {
+    TRAILER_TAGS.contains(&tag)
+}
+
+/// Returns true if `msg_type` is a session-level (administrative) message:
+/// Heartbeat, TestRequest, ResendRequest, Reject, SequenceReset, Logout or
+/// Logon.
+#[inline]
+pub fn is_admin_msg_type(msg_type: &[u8]) -> bool {
+    matches!(msg_type, b"0" | b"1" | b"2" | b"3" | b"4" | b"5" | b"A")
+}
+
+/// The character set a field's value is drawn from, coarsened from the FIX
+/// data types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// Optional `-` then digits: Int, SeqNum, Length, NumInGroup.
+    Int,
+    /// Optional sign, digits and at most one `.`: Float, Price, Qty, Amt.
+    Float,
+    /// Exactly one printable ASCII character.
+    Char,
+    /// Printable ASCII, space included: String, MultipleValueString,
+    /// UTCTimestamp, …
+    String,
+    /// Raw bytes of a length-prefixed data field; anything goes.
+    Data,
+}
+
+impl FieldType {
+    /// Position of the first byte of `value` this type does not allow, or
+    /// `value.len()` if the value stops short (`-` with no digits). `None`
+    /// if the value is well-formed. Empty values are `None`: whether they
+    /// are allowed is up to [`EmptyValuePolicy`](crate::validate::EmptyValuePolicy).
+    pub fn invalid_byte(self, value: &[u8]) -> Option<usize> {
+        let printable = |b: &u8| (0x20..=0x7E).contains(b);
+        if value.is_empty() {
+            return None;
+        }
+        match self {
+            FieldType::Int | FieldType::Float => {
+                let sign = match (self, value[0]) {
+                    (_, b'-') | (FieldType::Float, b'+') => 1,
+                    _ => 0,
+                };
+                let mut dot = self == FieldType::Int;
+                for (i, &b) in value.iter().enumerate().skip(sign) {
+                    match b {
+                        b'0'..=b'9' => {}
+                        b'.' if !dot => dot = true,
+                        _ => return Some(i),
+                    }
+                }
+                let digits = value[sign..].iter().any(u8::is_ascii_digit);
+                (!digits).then_some(value.len())
+            }
+            FieldType::Char if !printable(&value[0]) => Some(0),
+            FieldType::Char => (value.len() > 1).then_some(1),
+            FieldType::String => value.iter().position(|b| !printable(b)),
+            FieldType::Data => None,
+        }
+    }
+}
+
+/// The body layout of one message type.
+///
+/// - `fields`: every body tag the message may carry, in the order the
+///   specification lists them (header and trailer tags are not included).
+/// - `required`: the subset of `fields` that must be present.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageDef {
+    pub fields: Vec<Tag>,
+    pub required: BTreeSet<Tag>,
+}
+
+impl MessageDef {
+    /// Returns true if `tag` is a body field of this message type.
+    #[inline]
+    pub fn contains(&self, tag: Tag) -> bool {
+        self.fields.contains(&tag)
+    }
+
+    /// Position of `tag` in the specification order.
+    #[inline]
+    pub fn position(&self, tag: Tag) -> Option<usize> {
+        self.fields.iter().position(|&t| t == tag)
+    }
+}
+
+/// The layout of one repeating group, keyed in the dictionary by its count
+/// tag. Owned counterpart of [`GroupSpec`], so venues can extend it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupDef {
+    pub delimiter_tag: Tag,
+    /// All tags that may appear inside an instance (includes the delimiter).
+    pub member_tags: Vec<Tag>,
+}
+
+impl From<&GroupSpec> for GroupDef {
+    fn from(spec: &GroupSpec) -> Self {
+        Self {
+            delimiter_tag: spec.delimiter_tag,
+            member_tags: spec.member_tags.to_vec(),
+        }
+    }
+}
+
+/// A disagreement between a base dictionary and an overlay, reported by
+/// [`Dictionary::merge`]. The overlay always wins; conflicts exist so the
+/// venue customization can be reviewed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictionaryConflict {
+    /// The overlay describes a different FIX version than the base.
+    BeginString { base: Vec<u8>, overlay: Vec<u8> },
+    /// The overlay replaced the allowed values of an enumerated field.
+    Enum {
+        tag: Tag,
+        base: BTreeSet<Vec<u8>>,
+        overlay: BTreeSet<Vec<u8>>,
+    },
+    /// The overlay changed the delimiter tag of a repeating group.
+    GroupDelimiter {
+        count_tag: Tag,
+        base: Tag,
+        overlay: Tag,
+    },
+    /// The overlay replaced the definition of a message type.
+    Message { msg_type: Vec<u8> },
+    /// The overlay changed the type of a field.
+    FieldType {
+        tag: Tag,
+        base: FieldType,
+        overlay: FieldType,
+    },
+}
+
+/// The set of fields a session considers standard.
+///
+/// Built-in dictionaries cover every tag constant in [`crate::tag`] for the
+/// given FIX version. Venue-specific (custom / user-defined) tags can be
+/// registered with [`add_tag`](Self::add_tag) so they are no longer reported
+/// as unknown.
+///
+/// Message layouts (body fields per MsgType) are configured with
+/// [`define_message`](Self::define_message), enumerated values with
+/// [`set_enum`](Self::set_enum), and repeating groups with
+/// [`define_group`](Self::define_group). Venue dictionaries are usually
+/// small overlays [`merge`](Self::merge)d on top of a built-in one:
+///
+/// ```ignore
+/// let mut venue = Dictionary::empty(b"FIX.4.4");
+/// venue.add_tag(9001)
+///     .set_enum(tag::ORD_TYPE, &[b"1", b"2", b"P"])
+///     .extend_group(tag::NO_PARTY_IDS, &[9002]);
+/// let mut dict = Dictionary::fix44();
+/// for conflict in dict.merge(&venue) {
+///     log::info!("venue override: {conflict:?}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    /// BeginString (tag 8) value this dictionary describes, e.g. `FIX.4.4`.
+    begin_string: Vec<u8>,
+    /// Every tag the dictionary knows about.
+    fields: BTreeSet<Tag>,
+    /// Body layout per MsgType (35) value.
+    messages: BTreeMap<Vec<u8>, MessageDef>,
+    /// Allowed values of enumerated fields.
+    enums: BTreeMap<Tag, BTreeSet<Vec<u8>>>,
+    /// Repeating groups keyed by count tag.
+    groups: BTreeMap<Tag, GroupDef>,
+    /// Value character set of typed fields.
+    types: BTreeMap<Tag, FieldType>,
+}
+
+impl Dictionary {
+    /// An empty dictionary for `begin_string`.
+    pub fn empty(begin_string: &[u8]) -> Self {
+        Self {
+            begin_string: begin_string.to_vec(),
+            fields: BTreeSet::new(),
+            messages: BTreeMap::new(),
+            enums: BTreeMap::new(),
+            groups: BTreeMap::new(),
+            types: BTreeMap::new(),
+        }
+    }
+
+    /// The built-in FIX 4.2 dictionary.
+    pub fn fix42() -> Self {
+        let mut dict = Self::standard(b"FIX.4.2", FIX42_MAX_TAG, FIX42_GROUPS);
+        dict.set_enum(tag::TIME_IN_FORCE, FIX42_TIME_IN_FORCE);
+        dict
+    }
+
+    /// The built-in FIX 4.4 dictionary.
+    pub fn fix44() -> Self {
+        let mut dict = Self::standard(b"FIX.4.4", FIX44_MAX_TAG, FIX44_GROUPS);
+        dict.set_enum(tag::TIME_IN_FORCE, FIX44_TIME_IN_FORCE);
+        dict
+    }
+
+    /// The built-in dictionary for a BeginString value, or `None` if the
+    /// version has no built-in dictionary.
+    pub fn for_version(begin_string: &[u8]) -> Option<Self> {
+        match begin_string {
+            b"FIX.4.2" => Some(Self::fix42()),
+            b"FIX.4.4" => Some(Self::fix44()),
+            _ => None,
+        }
+    }
+
+    fn standard(begin_string: &[u8], max_tag: Tag, groups: &[&GroupSpec]) -> Self {
+        let mut dict = Self::empty(begin_string);
+        dict.fields = (1..=max_tag)
+            .filter(|t| !UNASSIGNED_TAGS.contains(t))
+            .collect();
+        dict.groups = groups.iter().map(|g| (g.count_tag, (*g).into())).collect();
+        for &(tag, values) in COMMON_ENUMS {
+            dict.set_enum(tag, values);
+        }
+        dict.types.extend(COMMON_TYPES.iter().copied());
+        for len_tag in 1..=max_tag {
+            if let Some(data_tag) = data_tag_for_length(len_tag) {
+                dict.types.insert(len_tag, FieldType::Int);
+                dict.types.insert(data_tag, FieldType::Data);
+            }
+        }
+        dict
+    }
+
+    /// BeginString (tag 8) value this dictionary describes.
+    #[inline]
+    pub fn begin_string(&self) -> &[u8] {
+        &self.begin_string
+    }
+
+    /// Returns true if `tag` is defined in this dictionary.
+    #[inline]
+    pub fn contains_tag(&self, tag: Tag) -> bool {
+        self.fields.contains(&tag)
+    }
+
+    /// Register an additional (e.g. venue-specific) tag.
+    pub fn add_tag(&mut self, tag: Tag) -> &mut Self {
+        self.fields.insert(tag);
+        self
+    }
+
+    /// Iterate over every defined tag in ascending order.
+    pub fn tags(&self) -> impl Iterator<Item = Tag> + '_ {
+        self.fields.iter().copied()
+    }
+
+    /// Define (or replace) the body layout of `msg_type`. Every tag in
+    /// `fields` is also registered as a known tag.
+    pub fn define_message(
+        &mut self,
+        msg_type: &[u8],
+        fields: &[Tag],
+        required: &[Tag],
+    ) -> &mut Self {
+        self.fields.extend(fields.iter().copied());
+        self.messages.insert(
+            msg_type.to_vec(),
+            MessageDef {
+                fields: fields.to_vec(),
+                required: required.iter().copied().collect(),
+            },
+        );
+        self
+    }
+
+    /// The body layout of `msg_type`, if defined.
+    #[inline]
+    pub fn message(&self, msg_type: &[u8]) -> Option<&MessageDef> {
+        self.messages.get(msg_type)
+    }
+
+    /// Iterate over every defined message type and its layout.
+    pub fn messages(&self) -> impl Iterator<Item = (&[u8], &MessageDef)> + '_ {
+        self.messages.iter().map(|(k, v)| (k.as_slice(), v))
+    }
+
+    /// Restrict `tag` to the given values, replacing any previous set.
+    pub fn set_enum(&mut self, tag: Tag, values: &[&[u8]]) -> &mut Self {
+        self.fields.insert(tag);
+        self.enums
+            .insert(tag, values.iter().map(|v| v.to_vec()).collect());
+        self
+    }
+
+    /// Register venue-specific values for `tag` in addition to the ones the
+    /// dictionary already allows. If `tag` was not enumerated before, it
+    /// becomes enumerated with exactly `values`.
+    pub fn add_enum_values(&mut self, tag: Tag, values: &[&[u8]]) -> &mut Self {
+        self.fields.insert(tag);
+        self.enums
+            .entry(tag)
+            .or_default()
+            .extend(values.iter().map(|v| v.to_vec()));
+        self
+    }
+
+    /// Returns true if `value` is acceptable for `tag`: either `tag` is not
+    /// enumerated, or `value` is one of its allowed values.
+    #[inline]
+    pub fn is_valid_enum(&self, tag: Tag, value: &[u8]) -> bool {
+        self.enums.get(&tag).is_none_or(|v| v.contains(value))
+    }
+
+    /// Allowed values of `tag`, if it is enumerated.
+    #[inline]
+    pub fn enum_values(&self, tag: Tag) -> Option<&BTreeSet<Vec<u8>>> {
+        self.enums.get(&tag)
+    }
+
+    /// Set the value type of `tag`, replacing any previous one.
+    pub fn set_field_type(&mut self, tag: Tag, ty: FieldType) -> &mut Self {
+        self.fields.insert(tag);
+        self.types.insert(tag, ty);
+        self
+    }
+
+    /// The value type of `tag`: as set, [`FieldType::Int`] for the count tag
+    /// of a defined group, otherwise `None` (not checked).
+    #[inline]
+    pub fn field_type(&self, tag: Tag) -> Option<FieldType> {
+        self.types
+            .get(&tag)
+            .copied()
+            .or_else(|| self.groups.contains_key(&tag).then_some(FieldType::Int))
+    }
+
+    /// Define (or replace) the repeating group introduced by `count_tag`.
+    pub fn define_group(
+        &mut self,
+        count_tag: Tag,
+        delimiter_tag: Tag,
+        member_tags: &[Tag],
+    ) -> &mut Self {
+        self.fields.insert(count_tag);
+        self.fields.extend(member_tags.iter().copied());
+        self.groups.insert(
+            count_tag,
+            GroupDef {
+                delimiter_tag,
+                member_tags: member_tags.to_vec(),
+            },
+        );
+        self
+    }
+
+    /// Add member tags to the group introduced by `count_tag`. In an overlay
+    /// the group need not be defined: it is created with an unspecified (`0`)
+    /// delimiter, and [`merge`](Self::merge) appends the extra members to the
+    /// base definition.
+    pub fn extend_group(&mut self, count_tag: Tag, extra: &[Tag]) -> &mut Self {
+        self.fields.insert(count_tag);
+        self.fields.extend(extra.iter().copied());
+        let group = self.groups.entry(count_tag).or_insert_with(|| GroupDef {
+            delimiter_tag: 0,
+            member_tags: Vec::new(),
+        });
+        for &t in extra {
+            if !group.member_tags.contains(&t) {
+                group.member_tags.push(t);
+            }
+        }
+        self
+    }
+
+    /// The repeating group introduced by `count_tag`, if defined.
+    #[inline]
+    pub fn group(&self, count_tag: Tag) -> Option<&GroupDef> {
+        self.groups.get(&count_tag)
+    }
+
+    /// Layer `overlay` on top of this dictionary.
+    ///
+    /// - tags are added;
+    /// - enum value sets, field types and message definitions are replaced;
+    /// - group members are appended to the existing group; a different
+    ///   delimiter replaces the base one unless the overlay left it
+    ///   unspecified (`0`). A new group is added as-is.
+    ///
+    /// Every place where the overlay contradicts the base, rather than just
+    /// adding to it, is returned as a [`DictionaryConflict`].
+    pub fn merge(&mut self, overlay: &Dictionary) -> Vec<DictionaryConflict> {
+        let mut conflicts = Vec::new();
+        if !overlay.begin_string.is_empty() && overlay.begin_string != self.begin_string {
+            conflicts.push(DictionaryConflict::BeginString {
+                base: self.begin_string.clone(),
+                overlay: overlay.begin_string.clone(),
+            });
+        }
+
+        self.fields.extend(overlay.fields.iter().copied());
+
+        for (&tag, values) in &overlay.enums {
+            if let Some(base) = self.enums.insert(tag, values.clone())
+                && base != *values
+            {
+                conflicts.push(DictionaryConflict::Enum {
+                    tag,
+                    base,
+                    overlay: values.clone(),
+                });
+            }
+        }
+
+        for (&tag, &ty) in &overlay.types {
+            if let Some(base) = self.types.insert(tag, ty)
+                && base != ty
+            {
+                conflicts.push(DictionaryConflict::FieldType {
+                    tag,
+                    base,
+                    overlay: ty,
+                });
+            }
+        }
+
+        for (&count_tag, def) in &overlay.groups {
+            let Some(base) = self.groups.get_mut(&count_tag) else {
+                let mut def = def.clone();
+                if def.delimiter_tag == 0 {
+                    def.delimiter_tag = def.member_tags.first().copied().unwrap_or(0);
+                }
+                self.groups.insert(count_tag, def);
+                continue;
+            };
+            if def.delimiter_tag != 0 && def.delimiter_tag != base.delimiter_tag {
+                conflicts.push(DictionaryConflict::GroupDelimiter {
+                    count_tag,
+                    base: base.delimiter_tag,
+                    overlay: def.delimiter_tag,
+                });
+                base.delimiter_tag = def.delimiter_tag;
+            }
+            for &t in &def.member_tags {
+                if !base.member_tags.contains(&t) {
+                    base.member_tags.push(t);
+                }
+            }
+        }
+
+        for (msg_type, def) in &overlay.messages {
+            if let Some(base) = self.messages.insert(msg_type.clone(), def.clone())
+                && base != *def
+            {
+                conflicts.push(DictionaryConflict::Message {
+                    msg_type: msg_type.clone(),
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// `base` with `overlay` merged on top; see [`merge`](Self::merge).
+    pub fn layered(
+        base: &Dictionary,
+        overlay: &Dictionary,
+    ) -> (Dictionary, Vec<DictionaryConflict>) {
+        let mut dict = base.clone();
+        let conflicts = dict.merge(overlay);
+        (dict, conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tag;
+
+    #[test]
+    fn builtin_versions() {
+        let d42 = Dictionary::fix42();
+        assert!(d42.contains_tag(tag::SYMBOL));
+        assert!(d42.contains_tag(tag::ENCODED_LIST_STATUS_TEXT));
+        assert!(!d42.contains_tag(tag::PARTY_ID));
+        assert!(!d42.contains_tag(101));
+
+        let d44 = Dictionary::fix44();
+        assert!(d44.contains_tag(tag::PARTY_ID));
+        assert!(d44.contains_tag(tag::LEG_INTEREST_ACCRUAL_DATE));
+        assert!(!d44.contains_tag(5000));
+
+        assert_eq!(Dictionary::for_version(b"FIX.4.4"), Some(d44));
+        assert!(Dictionary::for_version(b"FIX.4.0").is_none());
+    }
+
+    #[test]
+    fn message_definitions() {
+        let mut d = Dictionary::fix44();
+        d.define_message(
+            b"D",
+            &[tag::CL_ORD_ID, tag::SYMBOL, 9001],
+            &[tag::CL_ORD_ID],
+        );
+        let def = d.message(b"D").unwrap();
+        assert_eq!(def.position(tag::SYMBOL), Some(1));
+        assert!(def.required.contains(&tag::CL_ORD_ID));
+        assert!(d.contains_tag(9001));
+        assert!(d.message(b"8").is_none());
+        assert!(is_header_tag(tag::SENDING_TIME));
+        assert!(is_trailer_tag(tag::CHECK_SUM));
+        assert!(!is_header_tag(tag::SYMBOL));
+    }
+
+    #[test]
+    fn length_prefixed_data_pairs() {
+        assert_eq!(
+            data_tag_for_length(tag::RAW_DATA_LENGTH),
+            Some(tag::RAW_DATA)
+        );
+        assert_eq!(
+            data_tag_for_length(tag::ENCODED_TEXT_LEN),
+            Some(tag::ENCODED_TEXT)
+        );
+        assert_eq!(data_tag_for_length(tag::RAW_DATA), None);
+    }
+
+    #[test]
+    fn builtin_groups() {
+        let d = Dictionary::fix44();
+        let parties = d.group(tag::NO_PARTY_IDS).unwrap();
+        assert_eq!(parties.delimiter_tag, tag::PARTY_ID);
+        assert!(Dictionary::fix42().group(tag::NO_PARTY_IDS).is_none());
+    }
+
+    #[test]
+    fn merge_venue_overlay() {
+        let mut base = Dictionary::fix44();
+        base.set_enum(tag::SIDE, &[b"1", b"2"]);
+        base.define_message(b"D", &[tag::CL_ORD_ID], &[tag::CL_ORD_ID]);
+
+        let mut venue = Dictionary::empty(b"FIX.4.4");
+        venue
+            .add_tag(9001)
+            .set_enum(tag::SIDE, &[b"1", b"2", b"5"])
+            .set_enum(9001, &[b"A"])
+            .extend_group(tag::NO_PARTY_IDS, &[9002])
+            .define_message(b"D", &[tag::CL_ORD_ID], &[tag::CL_ORD_ID]);
+
+        let (dict, conflicts) = Dictionary::layered(&base, &venue);
+        assert!(dict.contains_tag(9001));
+        assert!(dict.contains_tag(9002));
+        assert_eq!(dict.enum_values(tag::SIDE).unwrap().len(), 3);
+        let parties = dict.group(tag::NO_PARTY_IDS).unwrap();
+        assert_eq!(parties.delimiter_tag, tag::PARTY_ID);
+        assert_eq!(parties.member_tags.last(), Some(&9002));
+
+        // Only the SIDE enum contradicts the base; identical message
+        // definitions and purely additive changes are not conflicts.
+        assert_eq!(conflicts.len(), 1);
+        assert!(matches!(
+            conflicts[0],
+            DictionaryConflict::Enum { tag: tag::SIDE, .. }
+        ));
+    }
+
+    #[test]
+    fn merge_reports_contradictions() {
+        let mut base = Dictionary::fix44();
+        base.define_message(b"D", &[tag::CL_ORD_ID], &[tag::CL_ORD_ID]);
+        let mut venue = Dictionary::empty(b"FIX.4.2");
+        venue
+            .define_group(tag::NO_PARTY_IDS, 9003, &[9003, tag::PARTY_ID])
+            .define_message(b"D", &[tag::CL_ORD_ID, 9001], &[tag::CL_ORD_ID]);
+
+        let conflicts = base.merge(&venue);
+        assert_eq!(conflicts.len(), 3);
+        assert!(matches!(
+            conflicts[0],
+            DictionaryConflict::BeginString { .. }
+        ));
+        assert_eq!(
+            conflicts[1],
+            DictionaryConflict::GroupDelimiter {
+                count_tag: tag::NO_PARTY_IDS,
+                base: tag::PARTY_ID,
+                overlay: 9003,
+            }
+        );
+        assert_eq!(
+            conflicts[2],
+            DictionaryConflict::Message {
+                msg_type: b"D".to_vec()
+            }
+        );
+        assert_eq!(base.message(b"D").unwrap().fields.len(), 2);
+    }
+
+    #[test]
+    fn field_types() {
+        let mut d = Dictionary::fix44();
+        assert_eq!(d.field_type(tag::MSG_SEQ_NUM), Some(FieldType::Int));
+        assert_eq!(d.field_type(tag::NO_PARTY_IDS), Some(FieldType::Int));
+        assert_eq!(d.field_type(tag::RAW_DATA_LENGTH), Some(FieldType::Int));
+        assert_eq!(d.field_type(tag::RAW_DATA), Some(FieldType::Data));
+        assert_eq!(d.field_type(tag::PRICE), Some(FieldType::Float));
+        assert_eq!(d.field_type(9001), None);
+
+        let mut venue = Dictionary::empty(b"FIX.4.4");
+        venue
+            .set_field_type(tag::ACCOUNT, FieldType::Int)
+            .set_field_type(9001, FieldType::String);
+        assert_eq!(
+            d.merge(&venue),
+            [DictionaryConflict::FieldType {
+                tag: tag::ACCOUNT,
+                base: FieldType::String,
+                overlay: FieldType::Int,
+            }]
+        );
+        assert_eq!(d.field_type(9001), Some(FieldType::String));
+
+        let bad = |ty: FieldType, v: &[u8]| ty.invalid_byte(v);
+        assert_eq!(bad(FieldType::Int, b"-42"), None);
+        assert_eq!(bad(FieldType::Int, b" 42"), Some(0));
+        assert_eq!(bad(FieldType::Int, b"4.2"), Some(1));
+        assert_eq!(bad(FieldType::Int, b"-"), Some(1));
+        assert_eq!(bad(FieldType::Float, b"+149.50"), None);
+        assert_eq!(bad(FieldType::Float, b"1.4.9"), Some(3));
+        assert_eq!(bad(FieldType::Char, b"1"), None);
+        assert_eq!(bad(FieldType::Char, b"12"), Some(1));
+        assert_eq!(bad(FieldType::String, "Caf\u{e9} ok".as_bytes()), Some(3));
+        assert_eq!(bad(FieldType::String, b"line\r\n"), Some(4));
+        assert_eq!(bad(FieldType::Data, b"\x00\xff"), None);
+    }
+
+    #[test]
+    fn custom_enum_values() {
+        let mut d = Dictionary::fix44();
+        assert!(d.is_valid_enum(tag::TIME_IN_FORCE, b"7"));
+        assert!(!Dictionary::fix42().is_valid_enum(tag::TIME_IN_FORCE, b"7"));
+        assert!(!d.is_valid_enum(tag::TIME_IN_FORCE, b"X"));
+        d.add_enum_values(tag::TIME_IN_FORCE, &[b"X"]);
+        assert!(d.is_valid_enum(tag::TIME_IN_FORCE, b"X"));
+        assert!(d.is_valid_enum(tag::TIME_IN_FORCE, b"0"));
+        assert!(d.is_valid_enum(tag::SYMBOL, b"anything"));
+    }
+
+    #[test]
+    fn custom_tags() {
+        let mut d = Dictionary::fix44();
+        d.add_tag(9001);
+        assert!(d.contains_tag(9001));
+        assert_eq!(d.tags().last(), Some(9001));
+    }
+}
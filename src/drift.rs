@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use crate::dictionary::{Dictionary, is_header_tag, is_trailer_tag};
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// Body structure observed for one MsgType over a capture.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Observed {
+    /// Number of messages of this type observed.
+    messages: u64,
+    /// Number of messages each body tag appeared in (at least once).
+    presence: BTreeMap<Tag, u64>,
+    /// Body tags in the order they were first seen across the capture.
+    order: Vec<Tag>,
+    /// Adjacent `(earlier, later)` wire pairs, used for ordering checks.
+    pairs: BTreeSet<(Tag, Tag)>,
+}
+
+/// Differences between the observed structure of one MsgType and its
+/// dictionary definition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MsgTypeDrift {
+    /// MsgType (35) value.
+    pub msg_type: Vec<u8>,
+    /// Number of messages of this type observed.
+    pub messages: u64,
+    /// True if the dictionary has no definition for this MsgType.
+    pub undefined: bool,
+    /// Required tags absent from some messages, with the number of messages
+    /// that lacked them.
+    pub missing: Vec<(Tag, u64)>,
+    /// Observed body tags the definition does not list, with the number of
+    /// messages that carried them.
+    pub extra: Vec<(Tag, u64)>,
+    /// `(a, b)` pairs where `a` was seen directly before `b` on the wire
+    /// although the definition places `b` first.
+    pub out_of_order: Vec<(Tag, Tag)>,
+}
+
+impl MsgTypeDrift {
+    /// Returns true if the observed structure matches the definition.
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        !self.undefined
+            && self.missing.is_empty()
+            && self.extra.is_empty()
+            && self.out_of_order.is_empty()
+    }
+}
+
+/// Result of [`DriftDetector::report`]: one entry per observed MsgType that
+/// deviates from the dictionary, in MsgType order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriftReport {
+    pub types: Vec<MsgTypeDrift>,
+}
+
+impl DriftReport {
+    /// Returns true if every observed MsgType matched its definition.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Render the differences as a dialect patch suggestion, one section per
+    /// MsgType:
+    ///
+    /// ```text
+    /// msgtype 8 (120 messages)
+    ///   + field 9001 optional      # seen in 80/120
+    ///   ~ field 60 optional        # missing in 3/120
+    ///   ^ field 55 before 11
+    /// ```
+    ///
+    /// `+` adds a field to the message definition (`required` if it was
+    /// present in every message), `~` relaxes a required field, `^` moves a
+    /// field. Undefined message types are emitted as `new msgtype` with every
+    /// observed field in first-seen order.
+    pub fn patch_suggestion(&self) -> String {
+        let mut out = String::new();
+        for t in &self.types {
+            let ty = String::from_utf8_lossy(&t.msg_type);
+            let kw = if t.undefined {
+                "new msgtype"
+            } else {
+                "msgtype"
+            };
+            let _ = writeln!(out, "{kw} {ty} ({} messages)", t.messages);
+            for &(tag, seen) in &t.extra {
+                let req = if seen == t.messages {
+                    "required"
+                } else {
+                    "optional"
+                };
+                let _ = writeln!(
+                    out,
+                    "  + field {tag} {req}\t# seen in {seen}/{}",
+                    t.messages
+                );
+            }
+            for &(tag, absent) in &t.missing {
+                let _ = writeln!(
+                    out,
+                    "  ~ field {tag} optional\t# missing in {absent}/{}",
+                    t.messages
+                );
+            }
+            for &(a, b) in &t.out_of_order {
+                let _ = writeln!(out, "  ^ field {a} before {b}");
+            }
+        }
+        out
+    }
+}
+
+/// Compares the structure of captured messages against a [`Dictionary`].
+///
+/// Feed every message of a capture to [`observe`](Self::observe); the
+/// detector records, per MsgType, which body tags appeared and in which
+/// order. [`report`](Self::report) then lists required tags that were
+/// missing, tags the dictionary does not define for that message, and tag
+/// pairs seen in a different order than the definition — and
+/// [`DriftReport::patch_suggestion`] turns that into a dialect patch.
+///
+/// Header and trailer tags are ignored; they are not part of a message
+/// definition.
+///
+/// # Example
+/// ```ignore
+/// let mut drift = DriftDetector::new();
+/// for raw in capture {
+///     drift.observe(&decoder.decode(raw)?);
+/// }
+/// print!("{}", drift.report(&dict).patch_suggestion());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DriftDetector {
+    types: BTreeMap<Vec<u8>, Observed>,
+    /// Per-message scratch set of body tags, reused across calls.
+    seen: BTreeSet<Tag>,
+}
+
+impl DriftDetector {
+    /// Create an empty detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the body structure of `msg`. Messages without MsgType (35)
+    /// are ignored.
+    pub fn observe(&mut self, msg: &Message<'_>) {
+        let Some(msg_type) = msg.find(tag::MSG_TYPE) else {
+            return;
+        };
+        let obs = match self.types.get_mut(msg_type.value) {
+            Some(obs) => obs,
+            None => self.types.entry(msg_type.value.to_vec()).or_default(),
+        };
+        obs.messages += 1;
+
+        self.seen.clear();
+        let mut prev: Option<Tag> = None;
+        for field in msg.fields() {
+            if is_header_tag(field.tag) || is_trailer_tag(field.tag) {
+                continue;
+            }
+            if self.seen.insert(field.tag) {
+                *obs.presence.entry(field.tag).or_insert(0) += 1;
+                if !obs.order.contains(&field.tag) {
+                    obs.order.push(field.tag);
+                }
+            }
+            if let Some(p) = prev
+                && p != field.tag
+            {
+                obs.pairs.insert((p, field.tag));
+            }
+            prev = Some(field.tag);
+        }
+    }
+
+    /// Number of messages observed for `msg_type`.
+    pub fn count(&self, msg_type: &[u8]) -> u64 {
+        self.types.get(msg_type).map_or(0, |o| o.messages)
+    }
+
+    /// Compare everything observed so far against `dict`.
+    pub fn report(&self, dict: &Dictionary) -> DriftReport {
+        let mut report = DriftReport::default();
+        for (msg_type, obs) in &self.types {
+            let mut drift = MsgTypeDrift {
+                msg_type: msg_type.clone(),
+                messages: obs.messages,
+                ..MsgTypeDrift::default()
+            };
+            match dict.message(msg_type) {
+                None => {
+                    drift.undefined = true;
+                    drift.extra = obs.order.iter().map(|t| (*t, obs.presence[t])).collect();
+                }
+                Some(def) => {
+                    for &req in &def.required {
+                        let present = obs.presence.get(&req).copied().unwrap_or(0);
+                        if present < obs.messages {
+                            drift.missing.push((req, obs.messages - present));
+                        }
+                    }
+                    drift.extra = obs
+                        .order
+                        .iter()
+                        .filter(|t| !def.contains(**t))
+                        .map(|t| (*t, obs.presence[t]))
+                        .collect();
+                    for &(a, b) in &obs.pairs {
+                        if let (Some(pa), Some(pb)) = (def.position(a), def.position(b))
+                            && pa > pb
+                        {
+                            drift.out_of_order.push((a, b));
+                        }
+                    }
+                }
+            }
+            if !drift.is_clean() {
+                report.types.push(drift);
+            }
+        }
+        report
+    }
+
+    /// Forget everything observed so far.
+    pub fn clear(&mut self) {
+        self.types.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    fn observe_all(drift: &mut DriftDetector, msgs: &[&str]) {
+        let mut dec = Decoder::new();
+        for raw in msgs {
+            let raw = fix(raw);
+            drift.observe(&dec.decode(&raw).unwrap());
+        }
+    }
+
+    fn dict() -> Dictionary {
+        let mut d = Dictionary::fix44();
+        d.define_message(
+            b"D",
+            &[tag::CL_ORD_ID, tag::SYMBOL, tag::SIDE, tag::TRANSACT_TIME],
+            &[tag::CL_ORD_ID, tag::SYMBOL, tag::SIDE, tag::TRANSACT_TIME],
+        );
+        d
+    }
+
+    #[test]
+    fn matching_capture_reports_nothing() {
+        let mut drift = DriftDetector::new();
+        observe_all(&mut drift, &["35=D|49=A|56=B|11=1|55=X|54=1|60=T|10=000|"]);
+        assert_eq!(drift.count(b"D"), 1);
+        assert!(drift.report(&dict()).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_extra_and_ordering() {
+        let mut drift = DriftDetector::new();
+        observe_all(
+            &mut drift,
+            &[
+                "35=D|11=1|55=X|54=1|60=T|9001=A|",
+                "35=D|11=2|54=1|55=X|9001=B|",
+                "35=0|112=T|",
+            ],
+        );
+        let report = drift.report(&dict());
+        assert_eq!(report.types.len(), 2);
+
+        let hb = &report.types[0];
+        assert_eq!(hb.msg_type, b"0");
+        assert!(hb.undefined);
+        assert_eq!(hb.extra, vec![(tag::TEST_REQ_ID, 1)]);
+
+        let d = &report.types[1];
+        assert_eq!(d.messages, 2);
+        assert_eq!(d.missing, vec![(tag::TRANSACT_TIME, 1)]);
+        assert_eq!(d.extra, vec![(9001, 2)]);
+        assert_eq!(d.out_of_order, vec![(tag::SIDE, tag::SYMBOL)]);
+
+        let patch = report.patch_suggestion();
+        assert!(patch.contains("new msgtype 0 (1 messages)"));
+        assert!(patch.contains("+ field 9001 required"));
+        assert!(patch.contains("~ field 60 optional"));
+        assert!(patch.contains("^ field 54 before 55"));
+    }
+}
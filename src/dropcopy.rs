@@ -0,0 +1,241 @@
+use crate::dictionary::is_admin_msg_type;
+use crate::edit::MessageEdit;
+use crate::encoder::Encoder;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::session::SessionId;
+use crate::tag::{self, Tag};
+use crate::timestamp::TimestampPolicy;
+
+/// Header fields of the source session that must not leak into a copy.
+const SOURCE_HEADER: &[Tag] = &[
+    tag::SENDER_SUB_ID,
+    tag::SENDER_LOCATION_ID,
+    tag::TARGET_SUB_ID,
+    tag::TARGET_LOCATION_ID,
+    tag::POSS_DUP_FLAG,
+    tag::POSS_RESEND,
+    tag::ORIG_SENDING_TIME,
+];
+
+/// Destination for drop-copy wire messages, e.g. the writers of the
+/// drop-copy sessions.
+///
+/// Implemented for closures taking `(&SessionId, seq_num, wire)` and for
+/// `Vec<DropCopied>`.
+pub trait DropCopySink {
+    fn send(&mut self, session: &SessionId, seq_num: u32, wire: &[u8]);
+}
+
+impl<F> DropCopySink for F
+where
+    F: FnMut(&SessionId, u32, &[u8]),
+{
+    #[inline]
+    fn send(&mut self, session: &SessionId, seq_num: u32, wire: &[u8]) {
+        self(session, seq_num, wire)
+    }
+}
+
+/// An owned drop-copy message, as collected by `Vec<DropCopied>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropCopied {
+    pub session: SessionId,
+    pub seq_num: u32,
+    pub wire: Vec<u8>,
+}
+
+impl DropCopySink for Vec<DropCopied> {
+    fn send(&mut self, session: &SessionId, seq_num: u32, wire: &[u8]) {
+        self.push(DropCopied {
+            session: session.clone(),
+            seq_num,
+            wire: wire.to_vec(),
+        });
+    }
+}
+
+struct Target {
+    id: SessionId,
+    encoder: Encoder,
+    next_seq: u32,
+}
+
+/// Republishes the application messages of one session on one or more
+/// drop-copy sessions.
+///
+/// Each copy gets the drop-copy session's header (see
+/// [`SessionId::header_fields`]), that session's next MsgSeqNum (34) and a
+/// fresh SendingTime (52). The source session's Sub/LocationIDs and resend
+/// markers (43, 97, 122) are dropped. Session-level messages (Logon,
+/// Heartbeat, ResendRequest, ...) are never copied.
+///
+/// # Example
+/// ```ignore
+/// let mut dc = DropCopy::new(|id: &SessionId, seq: u32, wire: &[u8]| {
+///     writers[id].write_all(wire);
+/// });
+/// dc.add_target(SessionId::new(b"FIX.4.4", CompId::new(b"BROKER"), CompId::new(b"RISK")), 1);
+/// // for every message sent or received on the trading session:
+/// dc.publish(&msg)?;
+/// ```
+pub struct DropCopy<S> {
+    targets: Vec<Target>,
+    edit: MessageEdit,
+    wire: Vec<u8>,
+    sink: S,
+}
+
+impl<S: DropCopySink> DropCopy<S> {
+    pub fn new(sink: S) -> Self {
+        Self {
+            targets: Vec::new(),
+            edit: MessageEdit::default(),
+            wire: Vec::new(),
+            sink,
+        }
+    }
+
+    /// Add a drop-copy session whose next outbound MsgSeqNum is `next_seq`,
+    /// encoding with millisecond SendingTime.
+    pub fn add_target(&mut self, id: SessionId, next_seq: u32) -> &mut Self {
+        let mut encoder = Encoder::new();
+        encoder.timestamp_policy(Some(TimestampPolicy::default()));
+        self.add_target_with(id, next_seq, encoder)
+    }
+
+    /// Add a drop-copy session encoded by `encoder`, after any stages it
+    /// already has. The session's header stage is appended to it.
+    pub fn add_target_with(
+        &mut self,
+        id: SessionId,
+        next_seq: u32,
+        mut encoder: Encoder,
+    ) -> &mut Self {
+        encoder.push_stage(id.header_fields());
+        self.targets.push(Target {
+            id,
+            encoder,
+            next_seq,
+        });
+        self
+    }
+
+    /// Number of drop-copy sessions.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns true if there are no drop-copy sessions.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// The MsgSeqNum the next copy to `id` will carry.
+    pub fn next_seq(&self, id: &SessionId) -> Option<u32> {
+        self.targets
+            .iter()
+            .find(|t| t.id == *id)
+            .map(|t| t.next_seq)
+    }
+
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+
+    /// Copy `msg` to every drop-copy session. Returns the number of copies
+    /// sent: 0 for session-level messages.
+    ///
+    /// # Errors
+    /// Returns the first encoding error; sessions before it have already
+    /// received their copy, and the failing session's sequence number is not
+    /// consumed.
+    pub fn publish(&mut self, msg: &Message<'_>) -> Result<usize, FixError> {
+        let msg_type = msg.find(tag::MSG_TYPE).map_or(&[][..], |f| f.value);
+        if msg_type.is_empty() || is_admin_msg_type(msg_type) {
+            return Ok(0);
+        }
+        self.edit.reset(msg);
+        for &t in SOURCE_HEADER {
+            self.edit.remove(t);
+        }
+        for target in &mut self.targets {
+            self.edit.set_u32(tag::MSG_SEQ_NUM, target.next_seq);
+            target
+                .encoder
+                .encode(&self.edit.as_message(), &mut self.wire)?;
+            self.sink.send(&target.id, target.next_seq, &self.wire);
+            target.next_seq += 1;
+        }
+        Ok(self.targets.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::session::CompId;
+
+    fn session(target: &[u8]) -> SessionId {
+        SessionId::new(b"FIX.4.4", CompId::new(b"BROKER"), CompId::new(target))
+    }
+
+    #[test]
+    fn publish_restamps_each_copy() {
+        let mut dc = DropCopy::new(Vec::new());
+        dc.add_target(session(b"RISK"), 10)
+            .add_target(session(b"BACKOFFICE"), 1);
+
+        let mut dec = Decoder::new();
+        let er = dec
+            .decode(b"8=FIX.4.4\x0135=8\x0149=BROKER\x0150=DESK\x0156=CLIENT\x0134=77\x0143=Y\x0152=20240101-00:00:00\x0137=O1\x01")
+            .unwrap();
+        assert_eq!(dc.publish(&er).unwrap(), 2);
+        assert_eq!(dc.next_seq(&session(b"RISK")), Some(11));
+
+        let copies = dc.into_sink();
+        assert_eq!(copies[0].seq_num, 10);
+        assert_eq!(copies[1].session, session(b"BACKOFFICE"));
+        let copy = dec.decode(&copies[0].wire).unwrap();
+        assert!(copy.validate_checksum().is_ok());
+        assert_eq!(copy.find(tag::TARGET_COMP_ID).unwrap().value, b"RISK");
+        assert_eq!(copy.find(tag::MSG_SEQ_NUM).unwrap().value, b"10");
+        assert_eq!(copy.find(tag::ORDER_ID).unwrap().value, b"O1");
+        assert!(copy.find(tag::SENDER_SUB_ID).is_none());
+        assert!(copy.find(tag::POSS_DUP_FLAG).is_none());
+        assert_ne!(
+            copy.find(tag::SENDING_TIME).unwrap().value,
+            b"20240101-00:00:00"
+        );
+    }
+
+    #[test]
+    fn session_messages_are_not_copied() {
+        let mut sent = 0;
+        let mut dc = DropCopy::new(|_: &SessionId, _: u32, _: &[u8]| sent += 1);
+        dc.add_target(session(b"RISK"), 1);
+        let mut dec = Decoder::new();
+        assert_eq!(
+            dc.publish(&dec.decode(b"35=0\x0134=5\x01").unwrap())
+                .unwrap(),
+            0
+        );
+        assert_eq!(dc.publish(&dec.decode(b"35=A\x01").unwrap()).unwrap(), 0);
+        assert_eq!(dc.next_seq(&session(b"RISK")), Some(1));
+        drop(dc);
+        assert_eq!(sent, 0);
+    }
+}
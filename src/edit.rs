@@ -0,0 +1,240 @@
+use crate::builder::MessageBuilder;
+use crate::dictionary::is_header_tag;
+use crate::encoder::u32_to_ascii;
+use crate::error::FixError;
+use crate::group::{GroupSpec, group_end};
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// An editable copy of a message, for rewriting a few tags before
+/// forwarding it (translating CompIDs, bumping MsgSeqNum, ...).
+///
+/// The fields are copied once into an internal [`MessageBuilder`];
+/// [`as_message`](Self::as_message) views the result as a [`Message`] that
+/// can be passed to [`Encoder::encode`](crate::encoder::Encoder::encode),
+/// which recomputes BodyLength (9) and CheckSum (10). Stale 9 and 10 values
+/// copied from the source are left in place and ignored there.
+///
+/// All edits act on the first occurrence of a tag except
+/// [`remove`](Self::remove), so they are meant for header and top-level body
+/// fields rather than repeating-group members.
+///
+/// # Example
+/// ```ignore
+/// let mut edit = MessageEdit::new(&msg);
+/// edit.set(tag::SENDER_COMP_ID, b"GATEWAY")
+///     .set_u32(tag::MSG_SEQ_NUM, next_seq)
+///     .remove(tag::POSS_DUP_FLAG);
+/// enc.encode(&edit.as_message(), &mut out)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageEdit {
+    fields: MessageBuilder,
+}
+
+impl MessageEdit {
+    /// Copy the fields of `msg` for editing.
+    pub fn new(msg: &Message<'_>) -> Self {
+        let mut edit = Self::default();
+        edit.reset(msg);
+        edit
+    }
+
+    /// Replace the contents with the fields of `msg`, keeping the allocated
+    /// capacity.
+    pub fn reset(&mut self, msg: &Message<'_>) {
+        self.fields.clear();
+        for f in msg.fields() {
+            self.fields.push(f.tag, f.value);
+        }
+    }
+
+    /// Number of fields.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns true if there are no fields.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Set the value of `tag`, replacing its first occurrence.
+    ///
+    /// An absent header tag is inserted at the end of the header; any other
+    /// absent tag is inserted before CheckSum (10), or at the end.
+    pub fn set(&mut self, tag: Tag, value: &[u8]) -> &mut Self {
+        if let Some(i) = self.position(tag) {
+            self.fields.replace_at(i, value);
+            return self;
+        }
+        let msg = self.fields.as_message();
+        let index = if is_header_tag(tag) {
+            msg.fields().position(|f| !is_header_tag(f.tag))
+        } else {
+            msg.fields().position(|f| f.tag == tag::CHECK_SUM)
+        }
+        .unwrap_or(msg.len());
+        self.fields.insert_at(index, tag, value);
+        self
+    }
+
+    /// Set `tag` to `n` formatted as ASCII decimal, as with [`set`](Self::set).
+    pub fn set_u32(&mut self, tag: Tag, n: u32) -> &mut Self {
+        let (digits, pos) = u32_to_ascii(n);
+        self.set(tag, &digits[pos..])
+    }
+
+    /// Replace the repeating group described by `spec` with a copy of the
+    /// same group from `src`, instances and nested groups in wire order.
+    ///
+    /// An absent group is inserted before CheckSum (10), or at the end. If
+    /// `src` has no such group, the existing one is removed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Echo the quote's parties on the order being forwarded.
+    /// edit.set_group(&quote, &group::PARTY_IDS);
+    /// ```
+    pub fn set_group(&mut self, src: &Message<'_>, spec: &GroupSpec) -> &mut Self {
+        let msg = self.fields.as_message();
+        let (at, end) = match self.position(spec.count_tag) {
+            Some(i) => (i, group_end(msg.offsets, msg.buf, i, spec)),
+            None => {
+                let i = msg
+                    .fields()
+                    .position(|f| f.tag == tag::CHECK_SUM)
+                    .unwrap_or(msg.len());
+                (i, i)
+            }
+        };
+        for _ in at..end {
+            self.fields.remove_at(at);
+        }
+        for (i, f) in src.group_fields(spec).enumerate() {
+            self.fields.insert_at(at + i, f.tag, f.value);
+        }
+        self
+    }
+
+    /// Remove every occurrence of `tag`.
+    pub fn remove(&mut self, tag: Tag) -> &mut Self {
+        while let Some(i) = self.position(tag) {
+            self.fields.remove_at(i);
+        }
+        self
+    }
+
+    /// Insert `tag=value` directly after the first occurrence of `after`.
+    ///
+    /// # Errors
+    /// Returns `FixError::EncodeError` if `after` is not present.
+    pub fn insert_after(
+        &mut self,
+        after: Tag,
+        tag: Tag,
+        value: &[u8],
+    ) -> Result<&mut Self, FixError> {
+        let i = self.position(after).ok_or(FixError::EncodeError)?;
+        self.fields.insert_at(i + 1, tag, value);
+        Ok(self)
+    }
+
+    /// View the edited fields as a zero-copy [`Message`].
+    #[inline]
+    pub fn as_message(&self) -> Message<'_> {
+        self.fields.as_message()
+    }
+
+    fn position(&self, tag: Tag) -> Option<usize> {
+        self.fields.as_message().fields().position(|f| f.tag == tag)
+    }
+}
+
+impl From<&Message<'_>> for MessageEdit {
+    fn from(msg: &Message<'_>) -> Self {
+        Self::new(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::group;
+
+    const ORDER: &[u8] =
+        b"8=FIX.4.4\x019=40\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=7\x0111=A1\x0155=IBM\x0110=000\x01";
+
+    #[test]
+    fn set_replaces_and_inserts() {
+        let mut dec = Decoder::new();
+        let mut edit = MessageEdit::new(&dec.decode(ORDER).unwrap());
+        edit.set(tag::SENDER_COMP_ID, b"GW")
+            .set_u32(tag::MSG_SEQ_NUM, 1234)
+            .set(tag::ON_BEHALF_OF_COMP_ID, b"CLIENT")
+            .set(tag::ORDER_QTY, b"100");
+
+        let msg = edit.as_message();
+        let tags: Vec<Tag> = msg.fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [8, 9, 35, 49, 56, 34, 115, 11, 55, 38, 10]);
+        assert_eq!(msg.find(tag::SENDER_COMP_ID).unwrap().value, b"GW");
+        assert_eq!(msg.find(tag::MSG_SEQ_NUM).unwrap().value, b"1234");
+        assert_eq!(msg.find(tag::SYMBOL).unwrap().value, b"IBM");
+    }
+
+    #[test]
+    fn remove_and_insert_after() {
+        let mut dec = Decoder::new();
+        let mut edit = MessageEdit::new(&dec.decode(ORDER).unwrap());
+        edit.remove(tag::CL_ORD_ID)
+            .insert_after(tag::SYMBOL, tag::SIDE, b"1")
+            .unwrap();
+        assert!(edit.insert_after(tag::PRICE, tag::SIDE, b"1").is_err());
+
+        let msg = edit.as_message();
+        assert!(msg.find(tag::CL_ORD_ID).is_none());
+        assert_eq!(msg.field(msg.len() - 2).value, b"1");
+        assert_eq!(edit.len(), 9);
+    }
+
+    #[test]
+    fn set_group_replaces_or_inserts() {
+        let mut dec = Decoder::new();
+        let mut edit = MessageEdit::new(&dec.decode(ORDER).unwrap());
+        let quote = b"35=S\x01453=1\x01448=A\x01452=1\x0155=IBM\x01";
+        let mut qdec = Decoder::new();
+        let quote = qdec.decode(quote).unwrap();
+
+        edit.set_group(&quote, &group::PARTY_IDS);
+        let tags: Vec<Tag> = edit.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [8, 9, 35, 49, 56, 34, 11, 55, 453, 448, 452, 10]);
+
+        let two = qdec
+            .decode(b"453=2\x01448=B\x01448=C\x01452=3\x0158=x\x01")
+            .unwrap();
+        edit.set_group(&two, &group::PARTY_IDS);
+        let tags: Vec<Tag> = edit.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [8, 9, 35, 49, 56, 34, 11, 55, 453, 448, 448, 452, 10]);
+
+        edit.set_group(&dec.decode(b"35=D\x01").unwrap(), &group::PARTY_IDS);
+        assert_eq!(edit.len(), 9);
+    }
+
+    #[test]
+    fn encode_recomputes_length_and_checksum() {
+        let mut dec = Decoder::new();
+        let mut edit = MessageEdit::new(&dec.decode(ORDER).unwrap());
+        edit.set(tag::TARGET_COMP_ID, b"EXCHANGE-1");
+
+        let mut out = Vec::new();
+        Encoder::new().encode(&edit.as_message(), &mut out).unwrap();
+        let msg = dec.decode(&out).unwrap();
+        assert!(msg.validate_body_length().is_ok());
+        assert!(msg.validate_checksum().is_ok());
+        assert_eq!(msg.find(tag::TARGET_COMP_ID).unwrap().value, b"EXCHANGE-1");
+    }
+}
@@ -1,10 +1,18 @@
+use std::io;
+
 use smallvec::SmallVec;
 
+use crate::builder::MessageBuilder;
 use crate::checksum::compute_checksum;
+use crate::dictionary::is_header_tag;
 use crate::error::FixError;
 use crate::field::FIELD_SEPARATOR;
+use crate::fragment::{self, FragmentSpec, Splitter};
 use crate::message::Message;
+use crate::pipeline::{EncodeStage, run_stages};
+use crate::policy::{FieldPolicy, PolicyProfiles};
 use crate::tag;
+use crate::timestamp::{MonotonicTimestamper, TimestampPolicy, TimestampPrecision, UtcTimestamp};
 
 /// Default inline capacity for the body buffer (bytes).
 /// Covers the body of most FIX messages without spilling to the heap.
@@ -25,12 +33,33 @@ pub struct Encoder {
     /// Reusable scratch buffer for building the message body.
     /// Cleared (not dropped) at the start of each encode call so capacity is preserved.
     body: SmallVec<[u8; DEFAULT_CAPACITY]>,
+    /// BeginString (8) and BodyLength (9) of the last framed message.
+    head: SmallVec<[u8; 32]>,
+    /// CheckSum (10) of the last framed message.
+    tail: SmallVec<[u8; 8]>,
     /// When true, tag 9 (BodyLength) is not auto-computed; the value from the
     /// message is used as-is if present, otherwise the field is omitted.
     disable_auto_calculate_body_length: bool,
     /// When true, tag 10 (CheckSum) is not auto-computed; the value from the
     /// message is used as-is if present, otherwise the field is omitted.
     disable_auto_calculate_checksum: bool,
+    /// Maximum wire size enforced by `encode_split`; `None` means unlimited.
+    max_message_size: Option<usize>,
+    /// Reusable scratch list of fragments produced by `encode_split`.
+    fragments: Vec<MessageBuilder>,
+    /// When set, SendingTime (52) is stamped and TransactTime (60) is
+    /// normalized to the policy's outbound precision.
+    timestamp_policy: Option<TimestampPolicy>,
+    /// Source of increasing SendingTime values when
+    /// [`monotonic_sending_time`](Self::monotonic_sending_time) is on;
+    /// otherwise the wall clock is read directly.
+    clock: Option<MonotonicTimestamper>,
+    /// Per-counterparty optional-field rules, selected by TargetCompID (56).
+    field_policies: Option<PolicyProfiles>,
+    /// Middleware stages run, in order, before the message is framed.
+    stages: Vec<Box<dyn EncodeStage + Send>>,
+    /// Ping-pong scratch builders holding each stage's output.
+    stage_bufs: [MessageBuilder; 2],
 }
 
 impl Default for Encoder {
@@ -44,8 +73,17 @@ impl Encoder {
     pub fn new() -> Self {
         Self {
             body: SmallVec::new(),
+            head: SmallVec::new(),
+            tail: SmallVec::new(),
             disable_auto_calculate_body_length: false,
             disable_auto_calculate_checksum: false,
+            max_message_size: None,
+            fragments: Vec::new(),
+            timestamp_policy: None,
+            clock: None,
+            field_policies: None,
+            stages: Vec::new(),
+            stage_bufs: Default::default(),
         }
     }
 
@@ -53,8 +91,17 @@ impl Encoder {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             body: SmallVec::with_capacity(capacity),
+            head: SmallVec::new(),
+            tail: SmallVec::new(),
             disable_auto_calculate_body_length: false,
             disable_auto_calculate_checksum: false,
+            max_message_size: None,
+            fragments: Vec::new(),
+            timestamp_policy: None,
+            clock: None,
+            field_policies: None,
+            stages: Vec::new(),
+            stage_bufs: Default::default(),
         }
     }
 
@@ -78,6 +125,181 @@ impl Encoder {
         self
     }
 
+    /// Stamp outbound timestamps according to `policy`. `None` (the default)
+    /// writes every field as given.
+    ///
+    /// With a policy set, `encode` writes SendingTime (52) with the current
+    /// time at `policy.outbound` precision — replacing any 52 in the message,
+    /// or inserting it at the end of the header fields if absent — and
+    /// rewrites TransactTime (60) at the same precision. A TransactTime that
+    /// is not a valid UTCTimestamp is written unchanged.
+    ///
+    /// SendingTime is read from the wall clock; see
+    /// [`monotonic_sending_time`](Self::monotonic_sending_time) for strictly
+    /// increasing values.
+    pub fn timestamp_policy(&mut self, policy: Option<TimestampPolicy>) -> &mut Self {
+        self.timestamp_policy = policy;
+        if let (Some(p), Some(clock)) = (policy, &self.clock)
+            && p.outbound != clock.precision()
+        {
+            self.clock = Some(MonotonicTimestamper::new(p.outbound));
+        }
+        self
+    }
+
+    /// Make stamped SendingTime values strictly increasing across `encode`
+    /// calls, even when the wall clock repeats a value or steps back (see
+    /// [`MonotonicTimestamper`]). A burst faster than the outbound precision
+    /// runs ahead of the wall clock until it ends. Off by default.
+    pub fn monotonic_sending_time(&mut self, enabled: bool) -> &mut Self {
+        self.clock = enabled.then(|| {
+            let precision = self
+                .timestamp_policy
+                .map_or(TimestampPrecision::Millis, |p| p.outbound);
+            MonotonicTimestamper::new(precision)
+        });
+        self
+    }
+
+    /// Apply per-counterparty field presence rules. `None` (the default)
+    /// writes every field as given.
+    ///
+    /// The profile is chosen from the message's TargetCompID (56) with
+    /// [`PolicyProfiles::for_target`]. Stripped tags are dropped wherever they
+    /// occur; always-send tags missing from the message are added at the end
+    /// of the header (header tags) or the end of the body (body tags).
+    /// A SendingTime stamped by the [`timestamp_policy`](Self::timestamp_policy)
+    /// is always written.
+    pub fn field_policies(&mut self, profiles: Option<PolicyProfiles>) -> &mut Self {
+        self.field_policies = profiles;
+        self
+    }
+
+    /// Append a middleware stage to the encode pipeline.
+    ///
+    /// Stages run in the order they were added, each transforming the field
+    /// stream produced by the previous one (see [`EncodeStage`]). The
+    /// timestamp and field policies configured on the encoder are applied
+    /// to the last stage's output, followed by the 8/9/10 framing.
+    pub fn push_stage<S>(&mut self, stage: S) -> &mut Self
+    where
+        S: EncodeStage + Send + 'static,
+    {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Remove every pipeline stage.
+    pub fn clear_stages(&mut self) -> &mut Self {
+        self.stages.clear();
+        self
+    }
+
+    /// Set the maximum wire size (in bytes) of a single encoded message, used
+    /// by [`encode_split`](Self::encode_split). `None` (the default) disables
+    /// splitting.
+    pub fn max_message_size(&mut self, max: Option<usize>) -> &mut Self {
+        self.max_message_size = max;
+        self
+    }
+
+    /// Encode `msg`, splitting it into several wire messages if it exceeds
+    /// the configured [`max_message_size`](Self::max_message_size).
+    ///
+    /// `out` is cleared first and receives one encoded message per fragment.
+    /// The repeating group to split on is chosen from MsgType (35) via
+    /// [`fragment::spec_for_msg_type`] (SecurityList, NewOrderList,
+    /// SecurityTypes, MarketDataSnapshotFullRefresh, MassQuote). Every
+    /// fragment repeats the non-group fields and carries the `Tot*` total,
+    /// LastFragment (893) and a count matching its own instances. Fragments
+    /// are sized after the timestamp policy, field policies and pipeline
+    /// stages have been applied.
+    ///
+    /// Messages that already fit are encoded unchanged as a single entry.
+    /// Returns the number of messages written.
+    ///
+    /// # Errors
+    /// Returns `FixError::EncodeError` if the message is too large and its
+    /// MsgType has no built-in fragment spec, or if a single group instance
+    /// cannot fit within the limit.
+    pub fn encode_split(
+        &mut self,
+        msg: &Message<'_>,
+        out: &mut Vec<Vec<u8>>,
+    ) -> Result<usize, FixError> {
+        let spec = msg
+            .find(tag::MSG_TYPE)
+            .and_then(|f| fragment::spec_for_msg_type(f.value));
+        self.encode_split_inner(msg, spec, out)
+    }
+
+    /// Like [`encode_split`](Self::encode_split), but splitting on an explicit
+    /// `spec` instead of one chosen from MsgType — for venue-specific groups.
+    pub fn encode_split_with(
+        &mut self,
+        msg: &Message<'_>,
+        spec: &'static FragmentSpec,
+        out: &mut Vec<Vec<u8>>,
+    ) -> Result<usize, FixError> {
+        self.encode_split_inner(msg, Some(spec), out)
+    }
+
+    fn encode_split_inner(
+        &mut self,
+        msg: &Message<'_>,
+        spec: Option<&'static FragmentSpec>,
+        out: &mut Vec<Vec<u8>>,
+    ) -> Result<usize, FixError> {
+        out.clear();
+        let mut whole = Vec::new();
+        self.encode(msg, &mut whole)?;
+
+        let max = match self.max_message_size {
+            Some(max) if whole.len() > max => max,
+            _ => {
+                out.push(whole);
+                return Ok(1);
+            }
+        };
+        let spec = spec.ok_or(FixError::EncodeError)?;
+
+        let mut fragments = std::mem::take(&mut self.fragments);
+        let result = self.encode_fragments(msg, spec, max, &mut fragments, out);
+        self.fragments = fragments;
+        result.map(|()| out.len())
+    }
+
+    /// Split `msg` and encode the fragments into `out`. The splitter sizes
+    /// fragments from the plain layout, so the timestamp policy, field
+    /// policies and pipeline stages can make them longer; while any encoded
+    /// fragment is over `max`, split again with the budget reduced by the
+    /// largest overshoot.
+    fn encode_fragments(
+        &mut self,
+        msg: &Message<'_>,
+        spec: &'static FragmentSpec,
+        max: usize,
+        fragments: &mut Vec<MessageBuilder>,
+        out: &mut Vec<Vec<u8>>,
+    ) -> Result<(), FixError> {
+        let mut budget = max;
+        loop {
+            fragments.clear();
+            out.clear();
+            Splitter::new(spec, budget).split(msg, fragments)?;
+            for frag in fragments.iter() {
+                let mut wire = Vec::new();
+                self.encode(&frag.as_message(), &mut wire)?;
+                out.push(wire);
+            }
+            let over = out.iter().map(|w| w.len().saturating_sub(max)).max();
+            match over {
+                Some(0) | None => return Ok(()),
+                Some(over) => budget = budget.checked_sub(over).ok_or(FixError::EncodeError)?,
+            }
+        }
+    }
+
     /// Encode `msg` as a complete FIX wire message into `out`.
     ///
     /// `out` is cleared first. By default, tag 9 (BodyLength) and tag 10 (CheckSum)
@@ -85,13 +307,131 @@ impl Encoder {
     /// Use `disable_auto_calculate_body_length(true)` or
     /// `disable_auto_calculate_checksum(true)` to write the message's own values instead.
     /// If tag 8 (BeginString) is absent, `FIX.4.4` is used as the default version.
+    ///
+    /// When pipeline stages are configured (see [`push_stage`](Self::push_stage)),
+    /// `msg` is passed through them first and the last stage's output is encoded.
+    ///
+    /// # Errors
+    /// Returns the first error raised by a pipeline stage.
     pub fn encode(&mut self, msg: &Message<'_>, out: &mut Vec<u8>) -> Result<(), FixError> {
+        self.prepare(msg)?;
+        out.clear();
+        out.reserve(self.encoded_len());
+        for part in self.parts() {
+            out.extend_from_slice(part);
+        }
+        Ok(())
+    }
+
+    /// Encode `msg` straight into `w` — a socket, file or buffered writer —
+    /// without an intermediate `Vec`. Returns the number of bytes written.
+    ///
+    /// Framing follows [`encode`](Self::encode). Wrap unbuffered writers in a
+    /// [`BufWriter`](std::io::BufWriter): the message is written in three
+    /// slices.
+    ///
+    /// # Errors
+    /// I/O errors from `w`; a pipeline stage error is returned as
+    /// `io::ErrorKind::InvalidData`.
+    pub fn encode_to_writer<W: io::Write>(
+        &mut self,
+        msg: &Message<'_>,
+        mut w: W,
+    ) -> io::Result<usize> {
+        self.prepare(msg)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        for part in self.parts() {
+            w.write_all(part)?;
+        }
+        Ok(self.encoded_len())
+    }
+
+    /// Encode `msg` by appending it to `buf` — a `BytesMut`, a pre-registered
+    /// `&mut [u8]` region, or any other [`BufMut`](bytes::BufMut). Returns the
+    /// number of bytes written.
+    ///
+    /// # Errors
+    /// `FixError::EncodeError` if `buf` has less than the encoded length
+    /// remaining (nothing is written), or the first pipeline stage error.
+    #[cfg(feature = "bytes")]
+    pub fn encode_to_buf<B: bytes::BufMut>(
+        &mut self,
+        msg: &Message<'_>,
+        buf: &mut B,
+    ) -> Result<usize, FixError> {
+        self.prepare(msg)?;
+        let len = self.encoded_len();
+        if buf.remaining_mut() < len {
+            return Err(FixError::EncodeError);
+        }
+        for part in self.parts() {
+            buf.put_slice(part);
+        }
+        Ok(len)
+    }
+
+    /// Run the pipeline stages on `msg` and frame the result into `head`,
+    /// `body` and `tail`.
+    fn prepare(&mut self, msg: &Message<'_>) -> Result<(), FixError> {
+        if self.stages.is_empty() {
+            self.encode_fields(msg);
+            return Ok(());
+        }
+
+        // Taken out of `self` so the stage output can be borrowed while
+        // `encode_fields` mutates the body buffer; restored below.
+        let mut bufs = std::mem::take(&mut self.stage_bufs);
+        let result = run_stages(&mut self.stages, msg, &mut bufs, |s, m, out| {
+            s.apply(m, out)
+        })
+        .map(|last| {
+            let msg = bufs[last].as_message();
+            self.encode_fields(&msg);
+        });
+        self.stage_bufs = bufs;
+        result
+    }
+
+    /// The framed message left by `prepare`, in wire order.
+    #[inline]
+    fn parts(&self) -> [&[u8]; 3] {
+        [&self.head, &self.body, &self.tail]
+    }
+
+    #[inline]
+    fn encoded_len(&self) -> usize {
+        self.head.len() + self.body.len() + self.tail.len()
+    }
+
+    fn encode_fields(&mut self, msg: &Message<'_>) {
         const DEFAULT_VERSION: &[u8] = b"FIX.4.4";
         let version = msg
             .find(tag::BEGIN_STRING)
             .map(|f| f.value)
             .unwrap_or(DEFAULT_VERSION);
 
+        // Stamp SendingTime once per message when a timestamp policy is set.
+        let now = match self.timestamp_policy {
+            Some(p) => {
+                let now = match &mut self.clock {
+                    Some(clock) => clock.stamp(),
+                    None => UtcTimestamp::now(),
+                };
+                Some(now.format(p.outbound))
+            }
+            None => None,
+        };
+        let mut sending_time_written = now.is_none();
+
+        // Select the counterparty's field policy, if any. Taken out of `self`
+        // for the duration of the body loop and restored below.
+        let profiles = self.field_policies.take();
+        let policy = profiles
+            .as_ref()
+            .and_then(|p| p.for_target(msg.find(tag::TARGET_COMP_ID).map_or(&[][..], |f| f.value)));
+        let msg_type = msg.find(tag::MSG_TYPE).map_or(&[][..], |f| f.value);
+        let mut header_defaults_written = policy.is_none();
+
         // Build body bytes into reusable scratch buffer (all fields except 8, 9, 10).
         self.body.clear();
         for field in msg.fields() {
@@ -101,56 +441,117 @@ impl Encoder {
             {
                 continue;
             }
-            let (digits, pos) = u32_to_ascii(field.tag);
-            self.body.extend_from_slice(&digits[pos..]);
-            self.body.push(b'=');
-            self.body.extend_from_slice(field.value);
-            self.body.push(FIELD_SEPARATOR);
+            if let Some(policy) = policy {
+                if !header_defaults_written && !is_header_tag(field.tag) {
+                    self.push_policy_defaults(policy, msg, msg_type, true);
+                    header_defaults_written = true;
+                }
+                if policy.strips(msg_type, field.tag) && field.tag != tag::SENDING_TIME {
+                    continue;
+                }
+            }
+            if let Some((buf, len)) = &now {
+                if field.tag == tag::SENDING_TIME {
+                    if !sending_time_written {
+                        self.push_body_field(tag::SENDING_TIME, &buf[..*len]);
+                        sending_time_written = true;
+                    }
+                    continue;
+                }
+                if !sending_time_written && !is_header_tag(field.tag) {
+                    self.push_body_field(tag::SENDING_TIME, &buf[..*len]);
+                    sending_time_written = true;
+                }
+                if field.tag == tag::TRANSACT_TIME
+                    && let (Some(policy), Ok((ts, _))) =
+                        (self.timestamp_policy, UtcTimestamp::parse(field.value))
+                {
+                    let (ts_buf, ts_len) = ts.format(policy.outbound);
+                    self.push_body_field(tag::TRANSACT_TIME, &ts_buf[..ts_len]);
+                    continue;
+                }
+            }
+            self.push_body_field(field.tag, field.value);
         }
+        if let Some((buf, len)) = &now
+            && !sending_time_written
+        {
+            self.push_body_field(tag::SENDING_TIME, &buf[..*len]);
+        }
+        if let Some(policy) = policy {
+            if !header_defaults_written {
+                self.push_policy_defaults(policy, msg, msg_type, true);
+            }
+            self.push_policy_defaults(policy, msg, msg_type, false);
+        }
+        self.field_policies = profiles;
 
-        // Assemble output: tag 8, tag 9, body, tag 10.
-        out.clear();
-
-        out.extend_from_slice(b"8=");
-        out.extend_from_slice(version);
-        out.push(FIELD_SEPARATOR);
+        // Frame the body: tags 8 and 9 in `head`, tag 10 in `tail`.
+        let head = &mut self.head;
+        head.clear();
+        head.extend_from_slice(b"8=");
+        head.extend_from_slice(version);
+        head.push(FIELD_SEPARATOR);
 
         if self.disable_auto_calculate_body_length {
             if let Some(f) = msg.find(tag::BODY_LENGTH) {
-                out.extend_from_slice(b"9=");
-                out.extend_from_slice(f.value);
-                out.push(FIELD_SEPARATOR);
+                head.extend_from_slice(b"9=");
+                head.extend_from_slice(f.value);
+                head.push(FIELD_SEPARATOR);
             }
         } else {
-            out.extend_from_slice(b"9=");
+            head.extend_from_slice(b"9=");
             let (digits, pos) = u32_to_ascii(self.body.len() as u32);
-            out.extend_from_slice(&digits[pos..]);
-            out.push(FIELD_SEPARATOR);
+            head.extend_from_slice(&digits[pos..]);
+            head.push(FIELD_SEPARATOR);
         }
 
-        out.extend_from_slice(&self.body);
-
+        let tail = &mut self.tail;
+        tail.clear();
         if self.disable_auto_calculate_checksum {
             if let Some(f) = msg.find(tag::CHECK_SUM) {
-                out.extend_from_slice(b"10=");
-                out.extend_from_slice(f.value);
-                out.push(FIELD_SEPARATOR);
+                tail.extend_from_slice(b"10=");
+                tail.extend_from_slice(f.value);
+                tail.push(FIELD_SEPARATOR);
             }
         } else {
-            let checksum = compute_checksum(out);
-            out.extend_from_slice(b"10=");
-            out.extend_from_slice(&checksum_to_ascii(checksum));
-            out.push(FIELD_SEPARATOR);
+            let checksum = compute_checksum(head).wrapping_add(compute_checksum(&self.body));
+            tail.extend_from_slice(b"10=");
+            tail.extend_from_slice(&checksum_to_ascii(checksum));
+            tail.push(FIELD_SEPARATOR);
         }
+    }
 
-        Ok(())
+    /// Write the policy's always-send fields that `msg` lacks: the header
+    /// ones if `header`, otherwise the body ones.
+    fn push_policy_defaults(
+        &mut self,
+        policy: &FieldPolicy,
+        msg: &Message<'_>,
+        msg_type: &[u8],
+        header: bool,
+    ) {
+        for (tag, value) in policy.defaults(msg_type) {
+            if is_header_tag(tag) == header && msg.find(tag).is_none() {
+                self.push_body_field(tag, value);
+            }
+        }
+    }
+
+    #[inline]
+    fn push_body_field(&mut self, tag: u32, value: &[u8]) {
+        let (digits, pos) = u32_to_ascii(tag);
+        self.body.extend_from_slice(&digits[pos..]);
+        self.body.push(b'=');
+        self.body.extend_from_slice(value);
+        self.body.push(FIELD_SEPARATOR);
     }
 }
 
 /// Write the decimal digits of `n` (no leading zeros) into `buf` as ASCII bytes.
 /// Uses a stack buffer — no heap allocation.
 #[inline]
-fn u32_to_ascii(n: u32) -> ([u8; 10], usize) {
+pub(crate) fn u32_to_ascii(n: u32) -> ([u8; 10], usize) {
     let mut buf = [0u8; 10];
     let mut pos = 10usize;
     let mut v = n;
@@ -177,6 +578,17 @@ mod tests {
     use super::*;
     use crate::decoder::Decoder;
 
+    #[test]
+    fn ascii_formatting_matches_display() {
+        for n in [0, 7, 10, 99, 1000, 65535, u32::MAX] {
+            let (digits, pos) = u32_to_ascii(n);
+            assert_eq!(&digits[pos..], n.to_string().as_bytes());
+        }
+        for n in [0u8, 5, 42, 255] {
+            assert_eq!(&checksum_to_ascii(n), format!("{n:03}").as_bytes());
+        }
+    }
+
     #[test]
     fn encode_single_body_field() {
         let raw = b"8=FIX.4.2\x019=5\x0135=D\x0110=181\x01";
@@ -422,6 +834,49 @@ mod tests {
         assert!(msg2.validate_body_length().is_ok());
     }
 
+    #[test]
+    fn encode_to_writer_matches_encode() {
+        let raw = b"8=FIX.4.4\x019=5\x0135=D\x0110=181\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut enc = Encoder::new();
+        let mut out = Vec::new();
+        enc.encode(&msg, &mut out).unwrap();
+
+        let mut written = Vec::new();
+        let n = enc.encode_to_writer(&msg, &mut written).unwrap();
+        assert_eq!(n, out.len());
+        assert_eq!(written, out);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn encode_to_buf_checks_remaining_capacity() {
+        let raw = b"8=FIX.4.4\x019=5\x0135=D\x0110=181\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut enc = Encoder::new();
+        let mut out = Vec::new();
+        enc.encode(&msg, &mut out).unwrap();
+
+        let mut region = [0u8; 64];
+        let mut buf = &mut region[..];
+        assert_eq!(enc.encode_to_buf(&msg, &mut buf).unwrap(), out.len());
+        assert_eq!(&region[..out.len()], &out[..]);
+
+        let mut small = [0u8; 8];
+        let mut buf = &mut small[..];
+        assert!(matches!(
+            enc.encode_to_buf(&msg, &mut buf),
+            Err(FixError::EncodeError)
+        ));
+        assert_eq!(small, [0u8; 8]);
+
+        let mut bytes = bytes::BytesMut::new();
+        enc.encode_to_buf(&msg, &mut bytes).unwrap();
+        assert_eq!(&bytes[..], &out[..]);
+    }
+
     #[test]
     fn encode_auto_checksum_ignores_wrong_value_in_message() {
         // Auto-computation overwrites a wrong tag 10 in the message; output validates.
@@ -472,4 +927,271 @@ mod tests {
         let msg2 = dec2.decode(&out).unwrap();
         assert!(msg2.validate_body_length().is_ok());
     }
+
+    #[test]
+    fn encode_split_fits_in_one_message() {
+        let raw = b"8=FIX.4.4\x019=5\x0135=y\x01146=1\x0155=AAPL\x0110=000\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut enc = Encoder::new();
+        enc.max_message_size(Some(1024));
+        let mut out = Vec::new();
+        assert_eq!(enc.encode_split(&msg, &mut out).unwrap(), 1);
+        // Unsplit messages get no continuation fields.
+        assert!(!out[0].windows(4).any(|w| w == b"893="));
+    }
+
+    #[test]
+    fn encode_split_security_list() {
+        let mut raw = b"8=FIX.4.4\x0135=y\x01320=REQ1\x01146=20\x01".to_vec();
+        for i in 0..20 {
+            raw.extend_from_slice(format!("55=SYM{i:02}\x01").as_bytes());
+        }
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut enc = Encoder::new();
+        enc.max_message_size(Some(120));
+        let mut out = Vec::new();
+        let n = enc.encode_split(&msg, &mut out).unwrap();
+        assert!(n > 1);
+        assert_eq!(n, out.len());
+
+        let mut dec2 = Decoder::new();
+        let mut seen = 0;
+        for (i, wire) in out.iter().enumerate() {
+            assert!(wire.len() <= 120);
+            let m = dec2.decode(wire).unwrap();
+            assert!(m.validate_body_length().is_ok());
+            assert!(m.validate_checksum().is_ok());
+            assert_eq!(m.find(tag::SECURITY_REQ_ID).unwrap().value, b"REQ1");
+            assert_eq!(m.find(tag::TOT_NO_RELATED_SYM).unwrap().value, b"20");
+            let last: &[u8] = if i == n - 1 { b"Y" } else { b"N" };
+            assert_eq!(m.find(tag::LAST_FRAGMENT).unwrap().value, last);
+            let count = m.find(tag::NO_RELATED_SYM).unwrap().value;
+            let count: usize = std::str::from_utf8(count).unwrap().parse().unwrap();
+            assert_eq!(count, m.fields().filter(|f| f.tag == tag::SYMBOL).count());
+            seen += count;
+        }
+        assert_eq!(seen, 20);
+    }
+
+    #[test]
+    fn encode_split_counts_stamped_sending_time() {
+        let mut raw = b"8=FIX.4.4\x0135=y\x01320=REQ1\x01146=20\x01".to_vec();
+        for i in 0..20 {
+            raw.extend_from_slice(format!("55=SYM{i:02}\x01").as_bytes());
+        }
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut enc = Encoder::new();
+        enc.max_message_size(Some(120))
+            .timestamp_policy(Some(TimestampPolicy::with_precision(
+                TimestampPrecision::Nanos,
+            )));
+        let mut out = Vec::new();
+        let n = enc.encode_split(&msg, &mut out).unwrap();
+        assert!(n > 1);
+
+        let mut dec2 = Decoder::new();
+        let mut seen = 0;
+        for wire in &out {
+            assert!(wire.len() <= 120, "fragment is {} bytes", wire.len());
+            let m = dec2.decode(wire).unwrap();
+            assert!(m.validate_body_length().is_ok());
+            assert_eq!(m.find(tag::SENDING_TIME).unwrap().value.len(), 27);
+            seen += m.fields().filter(|f| f.tag == tag::SYMBOL).count();
+        }
+        assert_eq!(seen, 20);
+    }
+
+    #[test]
+    fn encode_split_unknown_msg_type_too_large_errors() {
+        let raw = b"35=D\x0158=a long free text field that will not fit\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut enc = Encoder::new();
+        enc.max_message_size(Some(40));
+        let mut out = Vec::new();
+        assert!(matches!(
+            enc.encode_split(&msg, &mut out),
+            Err(FixError::EncodeError)
+        ));
+    }
+
+    #[test]
+    fn timestamp_policy_stamps_sending_time_and_normalizes_transact_time() {
+        let raw = b"35=D\x0149=S\x0156=T\x0111=1\x0160=20240229-13:45:06.123456789\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut enc = Encoder::new();
+        enc.timestamp_policy(Some(TimestampPolicy::with_precision(
+            TimestampPrecision::Micros,
+        )));
+        let mut out = Vec::new();
+        enc.encode(&msg, &mut out).unwrap();
+
+        let mut dec2 = Decoder::new();
+        let m = dec2.decode(&out).unwrap();
+        let tags: Vec<_> = m.fields().map(|f| f.tag).collect();
+        assert_eq!(&tags[2..7], &[35, 49, 56, 52, 11]);
+        let sending = m.find(tag::SENDING_TIME).unwrap().value;
+        assert_eq!(
+            UtcTimestamp::parse(sending).unwrap().1,
+            TimestampPrecision::Micros
+        );
+        assert_eq!(
+            m.find(tag::TRANSACT_TIME).unwrap().value,
+            b"20240229-13:45:06.123456"
+        );
+        assert!(m.validate_body_length().is_ok());
+    }
+
+    #[test]
+    fn timestamp_policy_replaces_existing_sending_time() {
+        let raw = b"35=0\x0149=S\x0152=OLD\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut enc = Encoder::new();
+        enc.timestamp_policy(Some(TimestampPolicy::default()));
+        let mut out = Vec::new();
+        enc.encode(&msg, &mut out).unwrap();
+        let mut dec2 = Decoder::new();
+        let m = dec2.decode(&out).unwrap();
+        assert_eq!(m.fields().filter(|f| f.tag == tag::SENDING_TIME).count(), 1);
+        assert_eq!(m.find(tag::SENDING_TIME).unwrap().value.len(), 21);
+    }
+
+    #[test]
+    fn stamped_sending_times_strictly_increase() {
+        let raw = b"35=0\x0149=S\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut enc = Encoder::new();
+        enc.timestamp_policy(Some(TimestampPolicy::with_precision(
+            TimestampPrecision::Millis,
+        )))
+        .monotonic_sending_time(true);
+
+        let mut prev: Option<UtcTimestamp> = None;
+        let mut out = Vec::new();
+        for _ in 0..5 {
+            enc.encode(&msg, &mut out).unwrap();
+            let mut dec2 = Decoder::new();
+            let m = dec2.decode(&out).unwrap();
+            let (ts, _) = UtcTimestamp::parse(m.find(tag::SENDING_TIME).unwrap().value).unwrap();
+            assert!(prev.is_none_or(|p| ts > p));
+            prev = Some(ts);
+        }
+    }
+
+    #[test]
+    fn sending_time_burst_at_seconds_precision() {
+        let raw = b"35=0\x0149=S\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut dec2 = Decoder::new();
+        let mut out = Vec::new();
+        for monotonic in [false, true] {
+            let mut enc = Encoder::new();
+            enc.timestamp_policy(Some(TimestampPolicy::with_precision(
+                TimestampPrecision::Seconds,
+            )))
+            .monotonic_sending_time(monotonic);
+            let mut prev: Option<UtcTimestamp> = None;
+            for _ in 0..10_000 {
+                enc.encode(&msg, &mut out).unwrap();
+                let now = UtcTimestamp::now();
+                let m = dec2.decode(&out).unwrap();
+                let (ts, _) =
+                    UtcTimestamp::parse(m.find(tag::SENDING_TIME).unwrap().value).unwrap();
+                if monotonic {
+                    assert!(prev.is_none_or(|p| ts > p), "{ts:?} after {prev:?}");
+                } else {
+                    // The wall clock is never ahead of itself.
+                    assert!(ts <= now);
+                }
+                prev = Some(ts);
+            }
+        }
+    }
+
+    #[test]
+    fn field_policies_apply_per_target() {
+        use crate::policy::FieldPolicy;
+
+        let mut legacy = FieldPolicy::new();
+        legacy
+            .always_send(tag::HANDL_INST, b"1", &[b"D"])
+            .always_send(tag::ON_BEHALF_OF_COMP_ID, b"DESK", &[])
+            .strip(tag::TRANSACT_TIME, &[]);
+        let mut profiles = PolicyProfiles::new();
+        profiles.insert(b"LEGACY", legacy);
+        let mut enc = Encoder::new();
+        enc.field_policies(Some(profiles));
+
+        let raw = b"35=D\x0149=S\x0156=LEGACY\x0111=1\x0160=20240229-13:45:06\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut out = Vec::new();
+        enc.encode(&msg, &mut out).unwrap();
+        let mut dec2 = Decoder::new();
+        let m = dec2.decode(&out).unwrap();
+        let tags: Vec<_> = m.fields().map(|f| f.tag).collect();
+        assert_eq!(tags, vec![8, 9, 35, 49, 56, 115, 11, 21, 10]);
+        assert!(m.validate_body_length().is_ok());
+
+        // Other counterparties are untouched.
+        let raw = b"35=D\x0149=S\x0156=OTHER\x0111=1\x0160=20240229-13:45:06\x01";
+        let msg = dec.decode(raw).unwrap();
+        enc.encode(&msg, &mut out).unwrap();
+        let m = dec2.decode(&out).unwrap();
+        assert!(m.find(tag::TRANSACT_TIME).is_some());
+        assert!(m.find(tag::HANDL_INST).is_none());
+    }
+
+    #[test]
+    fn pipeline_stages_run_in_order() {
+        use crate::pipeline::{HeaderFields, RemapTags, copy_fields};
+
+        let mut enc = Encoder::new();
+        enc.push_stage(HeaderFields::new().set(tag::SENDER_COMP_ID, b"ME"))
+            .push_stage(RemapTags::new().remap(tag::EX_DESTINATION, 9100))
+            .push_stage(|msg: &Message<'_>, out: &mut MessageBuilder| {
+                // Sees the remapped tag written by the previous stage.
+                assert!(msg.find(9100).is_some());
+                copy_fields(msg, out);
+                out.push(tag::SIGNATURE, b"SIG");
+                Ok(())
+            });
+
+        let raw = b"35=D\x0156=T\x0111=1\x01100=XNAS\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut out = Vec::new();
+        enc.encode(&msg, &mut out).unwrap();
+        let mut dec2 = Decoder::new();
+        let m = dec2.decode(&out).unwrap();
+        let tags: Vec<_> = m.fields().map(|f| f.tag).collect();
+        assert_eq!(tags, vec![8, 9, 35, 56, 49, 11, 9100, 89, 10]);
+        assert!(m.validate_body_length().is_ok());
+        assert!(m.validate_checksum().is_ok());
+    }
+
+    #[test]
+    fn pipeline_stage_error_aborts_encode() {
+        let mut enc = Encoder::new();
+        enc.push_stage(|_: &Message<'_>, _: &mut MessageBuilder| Err(FixError::EncodeError));
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=0\x01").unwrap();
+        let mut out = Vec::new();
+        assert!(matches!(
+            enc.encode(&msg, &mut out),
+            Err(FixError::EncodeError)
+        ));
+
+        enc.clear_stages();
+        assert!(enc.encode(&msg, &mut out).is_ok());
+    }
 }
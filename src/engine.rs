@@ -0,0 +1,589 @@
+//! Async session engine on tokio: a [`Session`] driven over a socket.
+//!
+//! [`Initiator`] connects and logs on, [`Acceptor`] listens and waits for
+//! the counterparty's Logon. Both hand back an [`Engine`]: a spawned task
+//! that owns the connection, answers heartbeats, test requests and resend
+//! requests, journals every outbound message in a [`MessageStore`], and
+//! passes inbound application messages to an [`Application`]. Send
+//! application messages through the engine's [`EngineHandle`].
+//!
+//! Each connection runs three tasks — socket reader, one-second timer and
+//! the session driver — joined by one channel, so the session itself is
+//! only ever touched by the driver. Outbound messages go through an
+//! [`Outbox`]: each batch is journaled, and synced per the store's
+//! [`SyncPolicy`](crate::store::SyncPolicy), before it is written.
+//! With a [`FaultInjector`] the written bytes are then deliberately
+//! broken, for testing the counterparty.
+
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+use bytes::BytesMut;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::codec::Decoder as _;
+
+use crate::builder::MessageBuilder;
+use crate::codec::{FixCodec, FixFrame};
+use crate::error::FixError;
+use crate::fault::FaultInjector;
+use crate::message::Message;
+use crate::owned::OwnedMessage;
+use crate::session::{DisconnectReason, SeqNums, Session, SessionEvent, SessionId, SessionState};
+use crate::store::{MessageStore, Outbox};
+use crate::timestamp::UtcTimestamp;
+
+/// Receives what an [`Engine`] delivers. Called on the engine's driver
+/// task, so keep the work short or hand it off.
+pub trait Application: Send + 'static {
+    /// An in-sequence inbound application message, or a session Reject.
+    fn on_message(&mut self, msg: &Message<'_>);
+
+    /// The Logon handshake completed.
+    fn on_logon(&mut self, _id: &SessionId) {}
+
+    /// The session ended with a Logout, a timeout or a protocol error.
+    fn on_logout(&mut self, _id: &SessionId, _reason: DisconnectReason) {}
+}
+
+/// Deliver inbound application messages as owned copies over a channel.
+impl Application for mpsc::UnboundedSender<OwnedMessage> {
+    fn on_message(&mut self, msg: &Message<'_>) {
+        let _ = self.send(msg.to_owned());
+    }
+}
+
+enum Event {
+    Inbound(Box<FixFrame>),
+    /// The reader stopped: end of stream, or a read or framing error.
+    Closed(io::Error),
+    Tick,
+    Send(MessageBuilder),
+    Logout(Option<Vec<u8>>),
+}
+
+impl std::fmt::Debug for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Event::Inbound(_) => "Inbound",
+            Event::Closed(_) => "Closed",
+            Event::Tick => "Tick",
+            Event::Send(_) => "Send",
+            Event::Logout(_) => "Logout",
+        })
+    }
+}
+
+/// Sends application messages and commands to a running [`Engine`].
+/// Cheap to clone; every clone talks to the same engine.
+#[derive(Debug, Clone)]
+pub struct EngineHandle {
+    events: mpsc::UnboundedSender<Event>,
+}
+
+impl EngineHandle {
+    /// Queue an application message: MsgType (35) and body fields. The
+    /// engine fills in BeginString, CompIDs, MsgSeqNum and SendingTime, so
+    /// any of those in `msg` are ignored. Messages queued before Logon
+    /// completes are sent right after it.
+    ///
+    /// # Errors
+    /// `io::ErrorKind::NotConnected` if the engine has stopped.
+    pub fn send(&self, msg: MessageBuilder) -> io::Result<()> {
+        self.post(Event::Send(msg))
+    }
+
+    /// Start the Logout handshake with an optional Text (58).
+    ///
+    /// # Errors
+    /// `io::ErrorKind::NotConnected` if the engine has stopped.
+    pub fn logout(&self, text: Option<&[u8]>) -> io::Result<()> {
+        self.post(Event::Logout(text.map(<[u8]>::to_vec)))
+    }
+
+    fn post(&self, event: Event) -> io::Result<()> {
+        self.events
+            .send(event)
+            .map_err(|_| io::Error::from(io::ErrorKind::NotConnected))
+    }
+}
+
+/// A session running on its own task; see the [module documentation](self).
+#[derive(Debug)]
+pub struct Engine {
+    handle: EngineHandle,
+    task: JoinHandle<io::Result<DisconnectReason>>,
+}
+
+impl Engine {
+    /// Run `session` over an established `stream` — a TLS stream or any
+    /// other transport. Sequence numbers are restored from `store` first.
+    /// Must be called inside a tokio runtime.
+    pub fn spawn<T, M, A>(stream: T, session: Session, store: M, app: A) -> Self
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+        M: MessageStore + Send + 'static,
+        A: Application,
+    {
+        Self::start(stream, session, store, app, None)
+    }
+
+    /// [`spawn`](Self::spawn), passing every outbound message through
+    /// `faults` after it is journaled: the counterparty sees a buggy
+    /// session while the store keeps the real one.
+    pub fn spawn_with_faults<T, M, A>(
+        stream: T,
+        session: Session,
+        store: M,
+        app: A,
+        faults: FaultInjector,
+    ) -> Self
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+        M: MessageStore + Send + 'static,
+        A: Application,
+    {
+        Self::start(stream, session, store, app, Some(faults))
+    }
+
+    fn start<T, M, A>(
+        stream: T,
+        session: Session,
+        store: M,
+        app: A,
+        faults: Option<FaultInjector>,
+    ) -> Self
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+        M: MessageStore + Send + 'static,
+        A: Application,
+    {
+        let (events, rx) = mpsc::unbounded_channel();
+        let (reader, writer) = tokio::io::split(stream);
+        let readers = [
+            tokio::spawn(read_frames(reader, events.clone())),
+            tokio::spawn(tick(events.clone())),
+        ];
+        let driver = Driver {
+            session,
+            store,
+            app,
+            writer,
+            out: Outbox::default(),
+            faults,
+            faulted: Vec::new(),
+            waiting: VecDeque::new(),
+        };
+        let task = tokio::spawn(async move {
+            let result = driver.run(rx).await;
+            for task in readers {
+                task.abort();
+            }
+            result
+        });
+        Self {
+            handle: EngineHandle { events },
+            task,
+        }
+    }
+
+    pub fn handle(&self) -> EngineHandle {
+        self.handle.clone()
+    }
+
+    /// Wait for the session to end. Returns why it ended, or the I/O error
+    /// that ended it (`UnexpectedEof` if the peer closed without Logout).
+    pub async fn wait(self) -> io::Result<DisconnectReason> {
+        self.task.await.map_err(io::Error::other)?
+    }
+}
+
+/// Connects to a counterparty and logs on.
+#[derive(Debug)]
+pub struct Initiator<M> {
+    session: Session,
+    store: M,
+    faults: Option<FaultInjector>,
+}
+
+impl<M: MessageStore + Send + 'static> Initiator<M> {
+    /// `session` should be built with [`Session::initiator`].
+    pub fn new(session: Session, store: M) -> Self {
+        Self {
+            session,
+            store,
+            faults: None,
+        }
+    }
+
+    /// Misbehave on purpose; see [`Engine::spawn_with_faults`].
+    pub fn faults(mut self, faults: FaultInjector) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    /// Connect to `addr`, send Logon and run the session.
+    ///
+    /// # Errors
+    /// The connection error.
+    pub async fn connect<A: Application>(
+        self,
+        addr: impl ToSocketAddrs,
+        app: A,
+    ) -> io::Result<Engine> {
+        let stream = TcpStream::connect(addr).await?;
+        stream.set_nodelay(true)?;
+        Ok(Engine::start(
+            stream,
+            self.session,
+            self.store,
+            app,
+            self.faults,
+        ))
+    }
+}
+
+/// Listens for counterparties that log on to us.
+#[derive(Debug)]
+pub struct Acceptor {
+    listener: TcpListener,
+    faults: Option<FaultInjector>,
+}
+
+impl Acceptor {
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr).await?,
+            faults: None,
+        })
+    }
+
+    /// Misbehave on purpose on every accepted connection, each with its own
+    /// fault counters; see [`Engine::spawn_with_faults`].
+    pub fn faults(mut self, faults: FaultInjector) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<std::net::SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept the next connection and run `session` (built with
+    /// [`Session::acceptor`]) on it.
+    ///
+    /// # Errors
+    /// The accept error.
+    pub async fn accept<M, A>(&self, session: Session, store: M, app: A) -> io::Result<Engine>
+    where
+        M: MessageStore + Send + 'static,
+        A: Application,
+    {
+        let (stream, _) = self.listener.accept().await?;
+        stream.set_nodelay(true)?;
+        Ok(Engine::start(
+            stream,
+            session,
+            store,
+            app,
+            self.faults.clone(),
+        ))
+    }
+}
+
+async fn read_frames<T: AsyncRead>(mut reader: ReadHalf<T>, events: mpsc::UnboundedSender<Event>) {
+    let mut codec = FixCodec::new();
+    let mut buf = BytesMut::with_capacity(8 * 1024);
+    let err = loop {
+        match codec.decode(&mut buf) {
+            Ok(Some(frame)) => {
+                if events.send(Event::Inbound(Box::new(frame))).is_err() {
+                    return;
+                }
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => break io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+        match reader.read_buf(&mut buf).await {
+            Ok(0) => break io::Error::from(io::ErrorKind::UnexpectedEof),
+            Ok(_) => {}
+            Err(e) => break e,
+        }
+    };
+    let _ = events.send(Event::Closed(err));
+}
+
+async fn tick(events: mpsc::UnboundedSender<Event>) {
+    let mut timer = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        timer.tick().await;
+        if events.send(Event::Tick).is_err() {
+            return;
+        }
+    }
+}
+
+struct Driver<W, M, A> {
+    session: Session,
+    store: M,
+    app: A,
+    writer: W,
+    out: Outbox,
+    faults: Option<FaultInjector>,
+    /// Outbound bytes out of `faults`.
+    faulted: Vec<u8>,
+    /// Application messages sent before Logon completed.
+    waiting: VecDeque<MessageBuilder>,
+}
+
+impl<W, M, A> Driver<W, M, A>
+where
+    W: AsyncWrite + Unpin,
+    M: MessageStore,
+    A: Application,
+{
+    async fn run(mut self, mut rx: mpsc::UnboundedReceiver<Event>) -> io::Result<DisconnectReason> {
+        *self.session.seq_nums_mut() = self.store.seq_nums();
+        let mut saved = self.store.seq_nums();
+        self.session
+            .on_connect(UtcTimestamp::now(), &mut |m| self.out.queue(m))
+            .map_err(invalid_data)?;
+        self.flush(&mut saved).await?;
+
+        while let Some(event) = rx.recv().await {
+            let now = UtcTimestamp::now();
+            let ended = match event {
+                Event::Inbound(frame) => self.on_inbound(&frame, now)?,
+                Event::Tick => self
+                    .session
+                    .poll(now, &mut |m| self.out.queue(m))
+                    .map_err(invalid_data)?,
+                Event::Send(msg) => {
+                    if self.session.state() == SessionState::Active {
+                        self.send_app(&msg, now)?;
+                    } else {
+                        self.waiting.push_back(msg);
+                    }
+                    None
+                }
+                Event::Logout(text) => {
+                    if self.session.state() == SessionState::Active {
+                        self.session
+                            .logout(text.as_deref(), now, &mut |m| self.out.queue(m))
+                            .map_err(invalid_data)?;
+                    }
+                    None
+                }
+                Event::Closed(err) => {
+                    self.session.on_disconnect();
+                    self.save(&mut saved)?;
+                    return Err(err);
+                }
+            };
+            self.flush(&mut saved).await?;
+            if let Some(reason) = ended {
+                self.app.on_logout(self.session.id(), reason);
+                let _ = self.writer.shutdown().await;
+                return Ok(reason);
+            }
+        }
+        Err(io::Error::from(io::ErrorKind::NotConnected))
+    }
+
+    fn on_inbound(
+        &mut self,
+        frame: &FixFrame,
+        now: UtcTimestamp,
+    ) -> io::Result<Option<DisconnectReason>> {
+        let msg = frame.as_message();
+        let event = self
+            .session
+            .on_message(&msg, now, &mut |m| self.out.queue(m))
+            .map_err(invalid_data)?;
+        match event {
+            SessionEvent::Deliver => self.app.on_message(&msg),
+            SessionEvent::LoggedOn => {
+                self.app.on_logon(self.session.id());
+                while let Some(waiting) = self.waiting.pop_front() {
+                    self.send_app(&waiting, now)?;
+                }
+            }
+            SessionEvent::Resend { begin, end } => {
+                self.session
+                    .resend_from(begin, end, &mut self.store, now, &mut |m| self.out.queue(m))?;
+            }
+            SessionEvent::Disconnect(reason) => return Ok(Some(reason)),
+            SessionEvent::Handled
+            | SessionEvent::Duplicate
+            | SessionEvent::Gap { .. }
+            | SessionEvent::Rejected => {}
+        }
+        Ok(None)
+    }
+
+    fn send_app(&mut self, msg: &MessageBuilder, now: UtcTimestamp) -> io::Result<()> {
+        self.session
+            .send_app(&msg.as_message(), now, &mut |m| self.out.queue(m))
+            .map_err(invalid_data)
+    }
+
+    /// Save sequence numbers if they moved, journal queued messages, then
+    /// write them.
+    async fn flush(&mut self, saved: &mut SeqNums) -> io::Result<()> {
+        self.save(saved)?;
+        let Some(faults) = &mut self.faults else {
+            if !self.out.is_empty() {
+                let wire = self.out.commit(&mut self.store)?;
+                self.writer.write_all(wire).await?;
+                self.writer.flush().await?;
+                self.out.clear();
+            }
+            return Ok(());
+        };
+        let now = UtcTimestamp::now();
+        self.faulted.clear();
+        faults.release(now, &mut self.faulted);
+        if !self.out.is_empty() {
+            let wire = self.out.commit(&mut self.store)?;
+            faults
+                .apply_all(wire, now, &mut self.faulted)
+                .map_err(invalid_data)?;
+            self.out.clear();
+        }
+        if !self.faulted.is_empty() {
+            self.writer.write_all(&self.faulted).await?;
+            self.writer.flush().await?;
+        }
+        Ok(())
+    }
+
+    fn save(&mut self, saved: &mut SeqNums) -> io::Result<()> {
+        let seq = *self.session.seq_nums();
+        if seq == *saved {
+            return Ok(());
+        }
+        // Sequence numbers going back means a reset: the journal is stale.
+        if seq.peek_outbound() < saved.peek_outbound() {
+            self.store.reset()?;
+        }
+        self.store.set_seq_nums(seq)?;
+        *saved = seq;
+        Ok(())
+    }
+}
+
+fn invalid_data(e: FixError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fault::Fault;
+    use crate::session::CompId;
+    use crate::store::MemoryStore;
+    use crate::tag;
+
+    fn id(sender: &[u8], target: &[u8]) -> SessionId {
+        SessionId::new(b"FIX.4.4", CompId::new(sender), CompId::new(target))
+    }
+
+    #[test]
+    fn initiator_and_acceptor_log_on_exchange_and_log_out() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let acceptor = Acceptor::bind("127.0.0.1:0").await.unwrap();
+            let addr = acceptor.local_addr().unwrap();
+            let (venue_tx, mut venue_rx) = mpsc::unbounded_channel();
+            let (broker_tx, _broker_rx) = mpsc::unbounded_channel();
+
+            let broker = Initiator::new(
+                Session::initiator(id(b"BROKER", b"VENUE"), Duration::from_secs(30)),
+                MemoryStore::new(),
+            )
+            .connect(addr, broker_tx)
+            .await
+            .unwrap();
+            let venue = acceptor
+                .accept(
+                    Session::acceptor(id(b"VENUE", b"BROKER")),
+                    MemoryStore::new(),
+                    venue_tx,
+                )
+                .await
+                .unwrap();
+
+            // Queued before Logon completes, sent right after it.
+            let mut order = MessageBuilder::new();
+            order
+                .push(tag::MSG_TYPE, b"D")
+                .push(tag::MSG_SEQ_NUM, b"999")
+                .push(tag::CL_ORD_ID, b"ORD-1");
+            broker.handle().send(order).unwrap();
+
+            let got = venue_rx.recv().await.unwrap();
+            assert_eq!(got.find(tag::MSG_TYPE).unwrap().value, b"D");
+            assert_eq!(got.find(tag::MSG_SEQ_NUM).unwrap().value, b"2");
+            assert_eq!(got.find(tag::SENDER_COMP_ID).unwrap().value, b"BROKER");
+            assert_eq!(got.find(tag::CL_ORD_ID).unwrap().value, b"ORD-1");
+
+            broker.handle().logout(Some(b"done")).unwrap();
+            assert_eq!(broker.wait().await.unwrap(), DisconnectReason::LoggedOut);
+            assert_eq!(venue.wait().await.unwrap(), DisconnectReason::LoggedOut);
+        });
+    }
+
+    #[test]
+    fn faulty_initiator_repeats_a_seq_num() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let acceptor = Acceptor::bind("127.0.0.1:0").await.unwrap();
+            let addr = acceptor.local_addr().unwrap();
+            let (venue_tx, _venue_rx) = mpsc::unbounded_channel();
+            let (broker_tx, _broker_rx) = mpsc::unbounded_channel();
+
+            let broker = Initiator::new(
+                Session::initiator(id(b"BROKER", b"VENUE"), Duration::from_secs(30)),
+                MemoryStore::new(),
+            )
+            .faults(FaultInjector::new().fault(Fault::DuplicateSeqNum(2)))
+            .connect(addr, broker_tx)
+            .await
+            .unwrap();
+            let venue = acceptor
+                .accept(
+                    Session::acceptor(id(b"VENUE", b"BROKER")),
+                    MemoryStore::new(),
+                    venue_tx,
+                )
+                .await
+                .unwrap();
+
+            // The order after Logon goes out as MsgSeqNum 1 again.
+            let mut order = MessageBuilder::new();
+            order
+                .push(tag::MSG_TYPE, b"D")
+                .push(tag::CL_ORD_ID, b"ORD-1");
+            broker.handle().send(order).unwrap();
+
+            assert_eq!(
+                venue.wait().await.unwrap(),
+                DisconnectReason::SeqNumTooLow {
+                    expected: 2,
+                    received: 1
+                }
+            );
+            let _ = broker.wait().await;
+        });
+    }
+}
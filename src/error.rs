@@ -1,19 +1,189 @@
+use std::fmt;
+
+use crate::tag::Tag;
+use crate::validate::SessionRejectReason;
+
 #[derive(Debug)]
 pub enum FixError {
-    /// A tag field contained non-digit bytes or was otherwise malformed.
-    InvalidTag,
+    /// A tag field was empty, contained a non-digit byte or overflowed
+    /// `u32`. `offset` is the position of the offending byte in the input.
+    InvalidTag { offset: usize },
     /// A value field contained bytes that are not valid UTF-8.
     InvalidUtf8,
     /// A numeric value field contained non-digit bytes.
     InvalidValue,
-    /// The buffer contains a partial FIX field; more bytes are needed (TCP framing).
-    IncompleteMessage,
+    /// The buffer contains a partial FIX field; more bytes are needed (TCP
+    /// framing). `offset` is where the partial field or frame starts.
+    IncompleteMessage { offset: usize },
     /// An error occurred during message encoding.
     EncodeError,
     /// An error occurred during message decoding.
     DecodeError,
     /// Tag 9 (BodyLength) is absent, unparseable, or does not match the computed byte count.
-    InvalidBodyLength,
+    /// `offset` is where the BodyLength field starts, or was expected.
+    InvalidBodyLength { offset: usize },
     /// Tag 10 (CheckSum) is absent, unparseable, or does not match the computed checksum.
-    InvalidCheckSum,
+    /// `offset` is where the CheckSum field starts, or was expected.
+    InvalidCheckSum { offset: usize },
+    /// [`Decoder::decode_verified`](crate::decoder::Decoder::decode_verified):
+    /// tag 9 declares `declared` body bytes but the message has `computed`.
+    BodyLengthMismatch { declared: usize, computed: usize },
+    /// [`Decoder::decode_verified`](crate::decoder::Decoder::decode_verified):
+    /// tag 10 declares `declared` but the bytes sum to `computed`.
+    CheckSumMismatch { declared: u8, computed: u8 },
+    /// Strict decode: BeginString (8) is not first, BodyLength (9) is not
+    /// second, or CheckSum (10) is not last. Carries the misplaced tag.
+    TagOutOfOrder(Tag),
+    /// A length-prefixed data field (e.g. RawData 96 after RawDataLength 95)
+    /// is not followed by SOH at its declared length; `offset` is where the
+    /// SOH was expected.
+    DataLengthMismatch { tag: Tag, offset: usize },
+    /// The input is longer than [`MAX_MESSAGE_LEN`](crate::message::MAX_MESSAGE_LEN)
+    /// bytes, or than a codec's `max_frame_len`; `len` is its length.
+    MessageTooLarge { len: usize },
+    /// Strict decode: the tag appears more than once outside a repeating group.
+    DuplicateTag(Tag),
+    /// The tag was sent without a value, in strict decode or under
+    /// [`EmptyValuePolicy::Reject`](crate::validate::EmptyValuePolicy::Reject).
+    EmptyValue(Tag),
+    /// BeginString (8) is missing or not one the decoder was configured to
+    /// accept (see [`Decoder::begin_strings`](crate::decoder::Decoder::begin_strings)).
+    UnexpectedBeginString,
+    /// An outbound message's header field conflicts with the session it is
+    /// sent on (see [`SessionId::header_guard`](crate::session::SessionId::header_guard)).
+    /// Carries the tag.
+    HeaderMismatch(Tag),
+}
+
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixError::InvalidTag { offset } => write!(f, "invalid tag at byte {offset}"),
+            FixError::InvalidUtf8 => f.write_str("value is not valid UTF-8"),
+            FixError::InvalidValue => f.write_str("invalid field value"),
+            FixError::IncompleteMessage { offset } => {
+                write!(f, "incomplete message at byte {offset}")
+            }
+            FixError::EncodeError => f.write_str("message cannot be encoded"),
+            FixError::DecodeError => f.write_str("message cannot be decoded"),
+            FixError::InvalidBodyLength { offset } => {
+                write!(f, "missing or invalid BodyLength (9) at byte {offset}")
+            }
+            FixError::InvalidCheckSum { offset } => {
+                write!(f, "missing or invalid CheckSum (10) at byte {offset}")
+            }
+            FixError::BodyLengthMismatch { declared, computed } => write!(
+                f,
+                "BodyLength (9) declares {declared} bytes, body has {computed}"
+            ),
+            FixError::CheckSumMismatch { declared, computed } => write!(
+                f,
+                "CheckSum (10) declares {declared:03}, computed {computed:03}"
+            ),
+            FixError::DataLengthMismatch { tag, offset } => write!(
+                f,
+                "data field {tag} does not end at its declared length (byte {offset})"
+            ),
+            FixError::MessageTooLarge { len } => {
+                write!(f, "message of {len} bytes exceeds the size limit")
+            }
+            FixError::TagOutOfOrder(tag) => write!(f, "tag {tag} out of order"),
+            FixError::DuplicateTag(tag) => write!(f, "duplicate tag {tag}"),
+            FixError::EmptyValue(tag) => write!(f, "tag {tag} has an empty value"),
+            FixError::UnexpectedBeginString => f.write_str("BeginString (8) not accepted"),
+            FixError::HeaderMismatch(tag) => {
+                write!(f, "header tag {tag} does not match the session")
+            }
+        }
+    }
+}
+
+impl FixError {
+    /// The SessionRejectReason (373) to send back for a message that failed
+    /// to decode with this error, for errors about one field of an otherwise
+    /// readable message. `None` for framing and encoding errors.
+    pub fn reject_reason(&self) -> Option<SessionRejectReason> {
+        Some(match self {
+            FixError::InvalidTag { .. } => SessionRejectReason::InvalidTagNumber,
+            FixError::EmptyValue(_) => SessionRejectReason::TagSpecifiedWithoutValue,
+            FixError::DuplicateTag(_) => SessionRejectReason::TagAppearsMoreThanOnce,
+            FixError::TagOutOfOrder(_) => SessionRejectReason::TagSpecifiedOutOfRequiredOrder,
+            FixError::InvalidValue => SessionRejectReason::IncorrectDataFormat,
+            _ => return None,
+        })
+    }
+
+    /// This error with its byte offset, if any, moved forward by `base`:
+    /// for errors found in a slice that starts `base` bytes into the input.
+    pub(crate) fn offset_by(self, base: usize) -> Self {
+        match self {
+            FixError::InvalidTag { offset } => FixError::InvalidTag {
+                offset: base + offset,
+            },
+            FixError::IncompleteMessage { offset } => FixError::IncompleteMessage {
+                offset: base + offset,
+            },
+            FixError::InvalidBodyLength { offset } => FixError::InvalidBodyLength {
+                offset: base + offset,
+            },
+            FixError::InvalidCheckSum { offset } => FixError::InvalidCheckSum {
+                offset: base + offset,
+            },
+            FixError::DataLengthMismatch { tag, offset } => FixError::DataLengthMismatch {
+                tag,
+                offset: base + offset,
+            },
+            e => e,
+        }
+    }
+}
+
+impl std::error::Error for FixError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_includes_context() {
+        assert_eq!(
+            FixError::InvalidTag { offset: 12 }.to_string(),
+            "invalid tag at byte 12"
+        );
+        assert_eq!(
+            FixError::CheckSumMismatch {
+                declared: 7,
+                computed: 161
+            }
+            .to_string(),
+            "CheckSum (10) declares 007, computed 161"
+        );
+        assert_eq!(
+            FixError::MessageTooLarge { len: 1 << 33 }.to_string(),
+            "message of 8589934592 bytes exceeds the size limit"
+        );
+        assert_eq!(
+            FixError::InvalidBodyLength { offset: 10 }.to_string(),
+            "missing or invalid BodyLength (9) at byte 10"
+        );
+        let boxed: Box<dyn std::error::Error + Send + Sync> = FixError::DuplicateTag(35).into();
+        assert_eq!(boxed.to_string(), "duplicate tag 35");
+    }
+
+    #[test]
+    fn reject_reason_for_field_errors() {
+        assert_eq!(
+            FixError::EmptyValue(58).reject_reason().map(|r| r.code()),
+            Some(4)
+        );
+        assert_eq!(
+            FixError::DuplicateTag(35).reject_reason().map(|r| r.code()),
+            Some(13)
+        );
+        assert!(
+            FixError::IncompleteMessage { offset: 0 }
+                .reject_reason()
+                .is_none()
+        );
+    }
 }
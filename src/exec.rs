@@ -0,0 +1,342 @@
+use crate::builder::MessageBuilder;
+use crate::decimal::FixDecimal;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::response::mirror_routing;
+use crate::tag::{self, Tag};
+
+/// Parse the first occurrence of `tag` as a [`FixDecimal`].
+///
+/// `InvalidValue` if the field is absent or not a decimal.
+fn decimal(msg: &Message<'_>, tag: Tag) -> Result<FixDecimal, FixError> {
+    let field = msg.find(tag).ok_or(FixError::InvalidValue)?;
+    FixDecimal::parse(field.value)
+}
+
+/// Remaining quantity of the order: `OrderQty (38) − CumQty (14)`.
+///
+/// Computed exactly, so it can be compared against LeavesQty (151) for
+/// orders that are still working.
+pub fn remaining_qty(msg: &Message<'_>) -> Result<FixDecimal, FixError> {
+    decimal(msg, tag::ORDER_QTY)?
+        .checked_sub(&decimal(msg, tag::CUM_QTY)?)
+        .ok_or(FixError::InvalidValue)
+}
+
+/// Notional of the fill carried by this report: `LastPx (31) × LastQty (32)`.
+pub fn last_notional(msg: &Message<'_>) -> Result<FixDecimal, FixError> {
+    decimal(msg, tag::LAST_PX)?
+        .checked_mul(&decimal(msg, tag::LAST_QTY)?)
+        .ok_or(FixError::InvalidValue)
+}
+
+/// Result of comparing a reported AvgPx with the fills seen so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AvgPxCheck {
+    /// `Σ(LastPx × LastQty) / Σ LastQty`, rounded to the reported AvgPx
+    /// precision.
+    pub expected: FixDecimal,
+    /// AvgPx (6) as reported by the counterparty.
+    pub reported: FixDecimal,
+    /// Sum of LastQty over the fills seen so far.
+    pub filled_qty: FixDecimal,
+    /// CumQty (14) as reported by the counterparty.
+    pub reported_cum_qty: FixDecimal,
+}
+
+impl AvgPxCheck {
+    /// True if AvgPx matches the fills (to the reported precision) and
+    /// CumQty matches the sum of LastQty.
+    #[inline]
+    pub fn is_consistent(&self) -> bool {
+        self.expected == self.reported && self.filled_qty == self.reported_cum_qty
+    }
+}
+
+/// Tracks the fills of one order to check the counterparty's AvgPx (6) and
+/// CumQty (14) arithmetic.
+///
+/// Feed every ExecutionReport of the order, in sequence, to
+/// [`apply`](Self::apply). Reports without a fill (LastQty absent or zero —
+/// new, replaced, cancelled, …) do not change the running totals but are
+/// still checked.
+///
+/// # Example
+/// ```ignore
+/// let mut fills = AvgPxTracker::new();
+/// for report in reports_for(cl_ord_id) {
+///     let check = fills.apply(&report)?;
+///     if !check.is_consistent() {
+///         warn!("AvgPx {} but fills imply {}", check.reported, check.expected);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AvgPxTracker {
+    notional: Option<FixDecimal>,
+    qty: Option<FixDecimal>,
+}
+
+impl AvgPxTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sum of LastQty applied so far.
+    pub fn filled_qty(&self) -> FixDecimal {
+        self.qty.unwrap_or(FixDecimal::ZERO)
+    }
+
+    /// Sum of LastPx × LastQty applied so far.
+    pub fn notional(&self) -> FixDecimal {
+        self.notional.unwrap_or(FixDecimal::ZERO)
+    }
+
+    /// Add this report's fill (if any) and compare the totals with its
+    /// AvgPx and CumQty.
+    ///
+    /// `InvalidValue` if AvgPx or CumQty is missing or malformed, if a fill
+    /// has no valid LastPx, or on arithmetic overflow.
+    pub fn apply(&mut self, msg: &Message<'_>) -> Result<AvgPxCheck, FixError> {
+        let reported = decimal(msg, tag::AVG_PX)?;
+        let reported_cum_qty = decimal(msg, tag::CUM_QTY)?;
+
+        if let Some(f) = msg.find(tag::LAST_QTY) {
+            let qty = FixDecimal::parse(f.value)?;
+            if !qty.is_zero() {
+                let notional = last_notional(msg)?;
+                self.notional = Some(
+                    self.notional()
+                        .checked_add(&notional)
+                        .ok_or(FixError::InvalidValue)?,
+                );
+                self.qty = Some(
+                    self.filled_qty()
+                        .checked_add(&qty)
+                        .ok_or(FixError::InvalidValue)?,
+                );
+            }
+        }
+
+        let filled_qty = self.filled_qty();
+        let expected = if filled_qty.is_zero() {
+            FixDecimal::ZERO
+        } else {
+            self.notional()
+                .checked_div(&filled_qty, reported.scale())
+                .ok_or(FixError::InvalidValue)?
+        };
+        Ok(AvgPxCheck {
+            expected,
+            reported,
+            filled_qty,
+            reported_cum_qty,
+        })
+    }
+}
+
+/// DKReason (127) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DkReason {
+    UnknownSymbol,
+    WrongSide,
+    QuantityExceedsOrder,
+    NoMatchingOrder,
+    PriceExceedsLimit,
+    CalculationDifference,
+    Other,
+}
+
+impl DkReason {
+    /// The character carried in tag 127.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::UnknownSymbol => b'A',
+            Self::WrongSide => b'B',
+            Self::QuantityExceedsOrder => b'C',
+            Self::NoMatchingOrder => b'D',
+            Self::PriceExceedsLimit => b'E',
+            Self::CalculationDifference => b'F',
+            Self::Other => b'Z',
+        }
+    }
+
+    /// The reason for a tag 127 value, or `None` if `code` is not defined.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            b'A' => Self::UnknownSymbol,
+            b'B' => Self::WrongSide,
+            b'C' => Self::QuantityExceedsOrder,
+            b'D' => Self::NoMatchingOrder,
+            b'E' => Self::PriceExceedsLimit,
+            b'F' => Self::CalculationDifference,
+            b'Z' => Self::Other,
+            _ => return None,
+        })
+    }
+}
+
+/// Fields copied from the ExecutionReport before DKReason (127)…
+const DK_IDS: [Tag; 3] = [tag::ORDER_ID, tag::SECONDARY_ORDER_ID, tag::EXEC_ID];
+/// …and after it.
+const DK_DETAILS: [Tag; 7] = [
+    tag::SYMBOL,
+    tag::SECURITY_ID,
+    tag::SECURITY_ID_SOURCE,
+    tag::SIDE,
+    tag::ORDER_QTY,
+    tag::LAST_QTY,
+    tag::LAST_PX,
+];
+
+/// A DontKnowTrade (35=Q) rejecting an ExecutionReport the buy side cannot
+/// match to an order.
+///
+/// # Example
+/// ```ignore
+/// if orders.get(cl_ord_id).is_none() {
+///     let dk = DontKnowTrade::from_report(&report, DkReason::NoMatchingOrder, b"")?;
+///     let mut b = MessageBuilder::new();
+///     dk.write_to(&mut b);
+///     enc.encode(&b.as_message(), &mut out)?;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DontKnowTrade<'a> {
+    report: &'a Message<'a>,
+    pub reason: DkReason,
+    /// Text (58); omitted when empty.
+    pub text: &'a [u8],
+}
+
+impl<'a> DontKnowTrade<'a> {
+    /// DK the execution in `report`.
+    ///
+    /// `InvalidValue` if `report` is not an ExecutionReport (35=8) or lacks
+    /// OrderID (37), ExecID (17) or Side (54), which a DK must carry.
+    pub fn from_report(
+        report: &'a Message<'a>,
+        reason: DkReason,
+        text: &'a [u8],
+    ) -> Result<Self, FixError> {
+        let is_report = report.find(tag::MSG_TYPE).is_some_and(|f| f.value == b"8");
+        let complete = [tag::ORDER_ID, tag::EXEC_ID, tag::SIDE]
+            .iter()
+            .all(|&t| report.find(t).is_some());
+        if !(is_report && complete) {
+            return Err(FixError::InvalidValue);
+        }
+        Ok(Self {
+            report,
+            reason,
+            text,
+        })
+    }
+
+    /// Append the DK to `b`: MsgType `Q`, the report's routing IDs mirrored
+    /// as with [`mirror_routing`], OrderID, SecondaryOrderID, ExecID,
+    /// DKReason, the instrument, Side, OrderQty, LastQty, LastPx and Text.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"Q");
+        mirror_routing(self.report, b);
+        self.copy(&DK_IDS, b);
+        b.push(tag::DK_REASON, &[self.reason.code()]);
+        self.copy(&DK_DETAILS, b);
+        if !self.text.is_empty() {
+            b.push(tag::TEXT, self.text);
+        }
+    }
+
+    fn copy(&self, tags: &[Tag], b: &mut MessageBuilder) {
+        for &t in tags {
+            if let Some(f) = self.report.find(t) {
+                b.push(t, f.value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    fn d(s: &str) -> FixDecimal {
+        FixDecimal::parse(s.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn remaining_and_notional() {
+        let raw = fix("35=8|38=1000|14=300|31=149.10|32=100|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        assert_eq!(remaining_qty(&msg).unwrap(), d("700"));
+        assert_eq!(last_notional(&msg).unwrap(), d("14910"));
+        // 0.1 × 3 is exactly 0.3, unlike f64.
+        let raw = fix("35=8|31=0.1|32=3|");
+        let msg = dec.decode(&raw).unwrap();
+        assert_eq!(last_notional(&msg).unwrap(), d("0.3"));
+        assert!(remaining_qty(&msg).is_err());
+    }
+
+    #[test]
+    fn avg_px_consistency_over_fills() {
+        let mut t = AvgPxTracker::new();
+        let mut dec = Decoder::new();
+        for (raw, consistent) in [
+            ("35=8|150=0|14=0|6=0|", true),
+            ("35=8|150=F|31=10.00|32=100|14=100|6=10.00|", true),
+            ("35=8|150=F|31=10.05|32=200|14=300|6=10.0333|", true),
+            // Wrong AvgPx reported by the counterparty.
+            ("35=8|150=F|31=10.10|32=100|14=400|6=10.06|", false),
+        ] {
+            let raw = fix(raw);
+            let msg = dec.decode(&raw).unwrap();
+            let check = t.apply(&msg).unwrap();
+            assert_eq!(check.is_consistent(), consistent, "{check:?}");
+        }
+        assert_eq!(t.filled_qty(), d("400"));
+        assert_eq!(t.notional(), d("4020"));
+    }
+
+    #[test]
+    fn cum_qty_mismatch_is_inconsistent() {
+        let mut t = AvgPxTracker::new();
+        let raw = fix("35=8|31=10|32=100|14=150|6=10|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let check = t.apply(&msg).unwrap();
+        assert_eq!(check.expected, check.reported);
+        assert!(!check.is_consistent());
+    }
+
+    #[test]
+    fn dk_trade_copies_execution_fields() {
+        let raw = fix(
+            "8=FIX.4.4|35=8|49=BROKER|56=CLIENT|34=40|37=O1|17=E1|150=F|39=2|55=IBM|54=1|38=100|32=100|31=150.25|14=100|6=150.25|",
+        );
+        let mut dec = Decoder::new();
+        let report = dec.decode(&raw).unwrap();
+        let dk =
+            DontKnowTrade::from_report(&report, DkReason::NoMatchingOrder, b"no order").unwrap();
+        let mut b = MessageBuilder::new();
+        dk.write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 49, 56, 37, 17, 127, 55, 54, 38, 32, 31, 58]);
+        let msg = b.as_message();
+        assert_eq!(msg.find(tag::DK_REASON).unwrap().value, b"D");
+        assert_eq!(msg.find(tag::SENDER_COMP_ID).unwrap().value, b"CLIENT");
+        assert_eq!(msg.find(tag::LAST_PX).unwrap().value, b"150.25");
+
+        let raw = fix("35=8|37=O1|54=1|");
+        let report = dec.decode(&raw).unwrap();
+        assert!(DontKnowTrade::from_report(&report, DkReason::Other, b"").is_err());
+        assert_eq!(DkReason::from_code(b'B'), Some(DkReason::WrongSide));
+    }
+}
@@ -0,0 +1,327 @@
+//! Fault injection: break a stream of encoded messages the way a buggy
+//! counterparty would, to test how a gateway copes.
+//!
+//! A [`FaultInjector`] sits between the encoder and the wire. Feed it each
+//! outgoing message with [`apply`](FaultInjector::apply) (or a batch with
+//! [`apply_all`](FaultInjector::apply_all)) and write what it hands back;
+//! call [`release`](FaultInjector::release) from the timer to let delayed
+//! messages go. The async engine takes one per connection
+//! (`Initiator::faults`, `Acceptor::faults`), and
+//! [`Simulator::peer_faults`](crate::sim::Simulator::peer_faults) runs the
+//! scripted counterparty through one.
+//!
+//! # Example
+//! ```ignore
+//! let mut faults = FaultInjector::new()
+//!     .fault(Fault::DropEvery(10))
+//!     .fault(Fault::DelayHeartbeat(Duration::from_secs(45)));
+//! let mut wire = Vec::new();
+//! faults.apply_all(outbox.commit(&mut store)?, UtcTimestamp::now(), &mut wire)?;
+//! stream.write_all(&wire)?;
+//! ```
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::builder::MessageBuilder;
+use crate::decoder::{Decoder, frame_end};
+use crate::encoder::Encoder;
+use crate::error::FixError;
+use crate::tag;
+use crate::timestamp::UtcTimestamp;
+
+/// One kind of misbehaviour. Counts start at the first message passed to
+/// the injector, so `DropEvery(3)` drops the 3rd, 6th, 9th, … message. A
+/// count of 0 never fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Every `n`th message carries a wrong CheckSum (10).
+    CorruptChecksum(u64),
+    /// Every `n`th message reuses the MsgSeqNum (34) of the message before
+    /// it.
+    DuplicateSeqNum(u64),
+    /// Heartbeats (35=0) leave this much later than they were sent.
+    DelayHeartbeat(Duration),
+    /// Every `n`th message never leaves.
+    DropEvery(u64),
+}
+
+impl Fault {
+    fn fires(every: u64, count: u64) -> bool {
+        every != 0 && count.is_multiple_of(every)
+    }
+}
+
+/// Applies a set of [`Fault`]s to encoded messages; see the
+/// [module documentation](self).
+#[derive(Default)]
+pub struct FaultInjector {
+    faults: Vec<Fault>,
+    /// Messages seen so far.
+    count: u64,
+    /// MsgSeqNum of the last message seen, as sent.
+    last_seq: Option<u64>,
+    /// Delayed messages and when they may leave.
+    held: VecDeque<(UtcTimestamp, Vec<u8>)>,
+    decoder: Decoder,
+    encoder: Encoder,
+    builder: MessageBuilder,
+}
+
+impl std::fmt::Debug for FaultInjector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FaultInjector")
+            .field("faults", &self.faults)
+            .field("count", &self.count)
+            .field("held", &self.held.len())
+            .finish()
+    }
+}
+
+/// A copy with the same faults and fresh counters, for a new connection.
+impl Clone for FaultInjector {
+    fn clone(&self) -> Self {
+        Self {
+            faults: self.faults.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `fault`. Faults combine: a message can be both renumbered and
+    /// corrupted; a dropped message is not counted as delayed.
+    pub fn fault(mut self, fault: Fault) -> Self {
+        self.faults.push(fault);
+        self
+    }
+
+    /// Messages passed through so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Whether delayed messages are waiting for [`release`](Self::release).
+    pub fn has_held(&self) -> bool {
+        !self.held.is_empty()
+    }
+
+    /// Pass one encoded message `raw`, sent at `now`, through the faults
+    /// and append whatever should go on the wire to `out`.
+    ///
+    /// # Errors
+    /// `raw`'s decode error, or `InvalidCheckSum` if it does not end with a
+    /// CheckSum field.
+    pub fn apply(
+        &mut self,
+        raw: &[u8],
+        now: UtcTimestamp,
+        out: &mut Vec<u8>,
+    ) -> Result<(), FixError> {
+        self.count += 1;
+        let count = self.count;
+        let (heartbeat, seq) = {
+            let msg = self.decoder.decode(raw)?;
+            let seq = msg.find(tag::MSG_SEQ_NUM).map(|f| f.as_u64()).transpose()?;
+            let heartbeat = msg.find(tag::MSG_TYPE).is_some_and(|f| f.value == b"0");
+            (heartbeat, seq)
+        };
+        let previous = self.last_seq;
+        if seq.is_some() {
+            self.last_seq = seq;
+        }
+        let mut faulted = raw.to_vec();
+        let mut delay = None;
+        for i in 0..self.faults.len() {
+            match self.faults[i] {
+                Fault::DropEvery(n) if Fault::fires(n, count) => return Ok(()),
+                Fault::DuplicateSeqNum(n) if Fault::fires(n, count) => {
+                    if let Some(previous) = previous {
+                        faulted = self.renumber(&faulted, previous)?;
+                    }
+                }
+                Fault::CorruptChecksum(n) if Fault::fires(n, count) => {
+                    corrupt_checksum(&mut faulted)?;
+                }
+                Fault::DelayHeartbeat(by) if heartbeat => delay = Some(by),
+                _ => {}
+            }
+        }
+        match delay {
+            Some(by) => self.held.push_back((later(now, by), faulted)),
+            None => out.extend_from_slice(&faulted),
+        }
+        Ok(())
+    }
+
+    /// [`apply`](Self::apply) to each message in `wire`, a run of whole
+    /// encoded messages such as [`Outbox::commit`](crate::store::Outbox::commit)
+    /// returns.
+    ///
+    /// # Errors
+    /// A framing or [`apply`](Self::apply) error; the messages before it
+    /// are already in `out`.
+    pub fn apply_all(
+        &mut self,
+        wire: &[u8],
+        now: UtcTimestamp,
+        out: &mut Vec<u8>,
+    ) -> Result<(), FixError> {
+        let mut rest = wire;
+        while !rest.is_empty() {
+            let offset = wire.len() - rest.len();
+            let len = frame_end(rest)
+                .map_err(|e| e.offset_by(offset))?
+                .ok_or(FixError::IncompleteMessage { offset })?;
+            self.apply(&rest[..len], now, out)?;
+            rest = &rest[len..];
+        }
+        Ok(())
+    }
+
+    /// Append the delayed messages due at `now` to `out`, in the order they
+    /// were held.
+    pub fn release(&mut self, now: UtcTimestamp, out: &mut Vec<u8>) {
+        while let Some((due, _)) = self.held.front() {
+            if *due > now {
+                break;
+            }
+            if let Some((_, raw)) = self.held.pop_front() {
+                out.extend_from_slice(&raw);
+            }
+        }
+    }
+
+    /// `raw` re-encoded with MsgSeqNum `seq`.
+    fn renumber(&mut self, raw: &[u8], seq: u64) -> Result<Vec<u8>, FixError> {
+        let msg = self.decoder.decode(raw)?;
+        self.builder.clear();
+        for f in msg.fields() {
+            match f.tag {
+                tag::BODY_LENGTH | tag::CHECK_SUM => {}
+                tag::MSG_SEQ_NUM => {
+                    self.builder.push_u64(f.tag, seq);
+                }
+                _ => {
+                    self.builder.push(f.tag, f.value);
+                }
+            }
+        }
+        let mut out = Vec::with_capacity(raw.len() + 4);
+        self.encoder.encode(&self.builder.as_message(), &mut out)?;
+        Ok(out)
+    }
+}
+
+/// Bump the three CheckSum digits at the end of `raw` by one.
+fn corrupt_checksum(raw: &mut [u8]) -> Result<(), FixError> {
+    let n = raw.len();
+    let invalid = || FixError::InvalidCheckSum {
+        offset: n.saturating_sub(7),
+    };
+    if n < 8 || &raw[n - 8..n - 4] != b"\x0110=" || raw[n - 1] != b'\x01' {
+        return Err(invalid());
+    }
+    let digits = &mut raw[n - 4..n - 1];
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return Err(invalid());
+    }
+    let value = digits
+        .iter()
+        .fold(0u32, |v, d| v * 10 + u32::from(d - b'0'));
+    let bumped = (value + 1) % 256;
+    digits.copy_from_slice(format!("{bumped:03}").as_bytes());
+    Ok(())
+}
+
+fn later(t: UtcTimestamp, by: Duration) -> UtcTimestamp {
+    let nanos = t.nanos + by.subsec_nanos();
+    UtcTimestamp::new(
+        t.secs + by.as_secs() as i64 + i64::from(nanos / 1_000_000_000),
+        nanos % 1_000_000_000,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(msg_type: &[u8], seq: u64) -> Vec<u8> {
+        let mut b = MessageBuilder::new();
+        b.push(tag::BEGIN_STRING, b"FIX.4.4")
+            .push(tag::MSG_TYPE, msg_type)
+            .push_u64(tag::MSG_SEQ_NUM, seq);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+        out
+    }
+
+    fn seqs(wire: &[u8]) -> Vec<u64> {
+        let mut dec = Decoder::new();
+        dec.decode_all(wire)
+            .unwrap()
+            .map(|m| m.find(tag::MSG_SEQ_NUM).unwrap().as_u64().unwrap())
+            .collect()
+    }
+
+    fn at(secs: i64) -> UtcTimestamp {
+        UtcTimestamp::new(1_709_214_306 + secs, 0)
+    }
+
+    #[test]
+    fn drops_and_renumbers_every_nth_message() {
+        let mut faults = FaultInjector::new()
+            .fault(Fault::DropEvery(3))
+            .fault(Fault::DuplicateSeqNum(2));
+        let wire: Vec<u8> = (1..=6).flat_map(|seq| encoded(b"D", seq)).collect();
+        let mut out = Vec::new();
+        faults.apply_all(&wire, at(0), &mut out).unwrap();
+        // 3 and 6 dropped; 2 and 4 repeat the number before them.
+        assert_eq!(seqs(&out), [1, 1, 3, 5]);
+        assert_eq!(faults.count(), 6);
+
+        let mut dec = Decoder::new();
+        for msg in dec.decode_all(&out).unwrap() {
+            msg.validate_body_length().unwrap();
+            msg.validate_checksum().unwrap();
+        }
+    }
+
+    #[test]
+    fn corrupts_checksum() {
+        let mut faults = FaultInjector::new().fault(Fault::CorruptChecksum(1));
+        let raw = encoded(b"D", 1);
+        let mut out = Vec::new();
+        faults.apply(&raw, at(0), &mut out).unwrap();
+        assert_eq!(out.len(), raw.len());
+        assert_ne!(out, raw);
+        let mut dec = Decoder::new();
+        assert!(matches!(
+            dec.decode_verified(&out),
+            Err(FixError::CheckSumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn delays_heartbeats_until_released() {
+        let mut faults = FaultInjector::new().fault(Fault::DelayHeartbeat(Duration::from_secs(5)));
+        let mut out = Vec::new();
+        faults.apply(&encoded(b"0", 1), at(0), &mut out).unwrap();
+        faults.apply(&encoded(b"D", 2), at(1), &mut out).unwrap();
+        assert_eq!(seqs(&out), [2]);
+        assert!(faults.has_held());
+
+        faults.release(at(4), &mut out);
+        assert_eq!(seqs(&out), [2]);
+        faults.release(at(5), &mut out);
+        assert_eq!(seqs(&out), [2, 1]);
+        assert!(!faults.has_held());
+
+        let fresh = faults.clone();
+        assert_eq!(fresh.count(), 0);
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+
+use crate::decimal::FixDecimal;
+use crate::error::FixError;
+use crate::group::MISC_FEES;
+use crate::message::Message;
+use crate::tag;
+
+/// MiscFeeType (139) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MiscFeeType {
+    Regulatory,
+    Tax,
+    LocalCommission,
+    ExchangeFees,
+    Stamp,
+    Levy,
+    Other,
+    Markup,
+    ConsumptionTax,
+    PerTransaction,
+    Conversion,
+    Agent,
+    /// A value outside the standard set (venue-specific), as sent.
+    Unknown(u32),
+}
+
+impl MiscFeeType {
+    /// Parse a MiscFeeType value. Non-numeric values are `InvalidValue`.
+    pub fn parse(value: &[u8]) -> Result<Self, FixError> {
+        if value.is_empty() || !value.iter().all(u8::is_ascii_digit) {
+            return Err(FixError::InvalidValue);
+        }
+        let n = value
+            .iter()
+            .try_fold(0u32, |acc, &b| {
+                acc.checked_mul(10)?.checked_add((b - b'0') as u32)
+            })
+            .ok_or(FixError::InvalidValue)?;
+        Ok(match n {
+            1 => Self::Regulatory,
+            2 => Self::Tax,
+            3 => Self::LocalCommission,
+            4 => Self::ExchangeFees,
+            5 => Self::Stamp,
+            6 => Self::Levy,
+            7 => Self::Other,
+            8 => Self::Markup,
+            9 => Self::ConsumptionTax,
+            10 => Self::PerTransaction,
+            11 => Self::Conversion,
+            12 => Self::Agent,
+            n => Self::Unknown(n),
+        })
+    }
+}
+
+/// CommType (13) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommType {
+    PerUnit,
+    Percent,
+    Absolute,
+    PercentWaivedCashDiscount,
+    PercentWaivedEnhancedUnits,
+    PointsPerBondOrContract,
+    Unknown(u8),
+}
+
+impl CommType {
+    fn parse(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            b"1" => Ok(Self::PerUnit),
+            b"2" => Ok(Self::Percent),
+            b"3" => Ok(Self::Absolute),
+            b"4" => Ok(Self::PercentWaivedCashDiscount),
+            b"5" => Ok(Self::PercentWaivedEnhancedUnits),
+            b"6" => Ok(Self::PointsPerBondOrContract),
+            [b] => Ok(Self::Unknown(*b)),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+/// CommissionData component: Commission (12), CommType (13) and
+/// CommCurrency (479, falling back to Currency 15).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commission {
+    pub amount: FixDecimal,
+    pub comm_type: Option<CommType>,
+    pub currency: Vec<u8>,
+    /// The commission as a money amount: `amount` itself for absolute
+    /// commissions (or when CommType is absent), `amount × LastQty` per unit,
+    /// `amount × LastQty × LastPx / 100` for percentages. `None` when the
+    /// fill fields needed for the conversion are absent or the type has no
+    /// defined conversion.
+    pub effective: Option<FixDecimal>,
+}
+
+/// Fee breakdown of one message, produced by [`fee_breakdown`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    pub commission: Option<Commission>,
+    /// MiscFeeAmt summed per (currency, MiscFeeType).
+    pub misc_fees: BTreeMap<(Vec<u8>, MiscFeeType), FixDecimal>,
+    /// Every fee (effective commission + misc fees) summed per currency.
+    pub total_by_currency: BTreeMap<Vec<u8>, FixDecimal>,
+}
+
+impl FeeBreakdown {
+    /// MiscFeeAmt total of `fee_type` in `currency`.
+    pub fn misc_fee(&self, currency: &[u8], fee_type: MiscFeeType) -> Option<FixDecimal> {
+        self.misc_fees.get(&(currency.to_vec(), fee_type)).copied()
+    }
+
+    /// Total of every fee in `currency`.
+    pub fn total(&self, currency: &[u8]) -> Option<FixDecimal> {
+        self.total_by_currency.get(currency).copied()
+    }
+
+    fn add_total(&mut self, currency: &[u8], amount: FixDecimal) -> Result<(), FixError> {
+        let total = self
+            .total_by_currency
+            .entry(currency.to_vec())
+            .or_insert(FixDecimal::ZERO);
+        *total = total.checked_add(&amount).ok_or(FixError::InvalidValue)?;
+        Ok(())
+    }
+}
+
+/// Aggregate CommissionData and the `NO_MISC_FEES` group of `msg`
+/// (ExecutionReport, Allocation, Confirmation, …) for post-trade
+/// reconciliation.
+///
+/// Fees without their own currency are attributed to Currency (15), or to
+/// the empty currency `b""` if that is absent too. Amounts are exact
+/// [`FixDecimal`] sums. `InvalidValue` if any amount or type is malformed.
+///
+/// # Example
+/// ```ignore
+/// let fees = fee_breakdown(&report)?;
+/// let usd_exchange = fees.misc_fee(b"USD", MiscFeeType::ExchangeFees);
+/// let usd_total = fees.total(b"USD");
+/// ```
+pub fn fee_breakdown(msg: &Message<'_>) -> Result<FeeBreakdown, FixError> {
+    let mut out = FeeBreakdown::default();
+    let default_ccy = msg.find(tag::CURRENCY).map_or(&b""[..], |f| f.value);
+
+    if let Some(c) = msg.find(tag::COMMISSION) {
+        let amount = FixDecimal::parse(c.value)?;
+        let comm_type = msg
+            .find(tag::COMM_TYPE)
+            .map(|f| CommType::parse(f.value))
+            .transpose()?;
+        let currency = msg
+            .find(tag::COMM_CURRENCY)
+            .map_or(default_ccy, |f| f.value);
+        let effective = effective_commission(msg, amount, comm_type)?;
+        if let Some(e) = effective {
+            out.add_total(currency, e)?;
+        }
+        out.commission = Some(Commission {
+            amount,
+            comm_type,
+            currency: currency.to_vec(),
+            effective,
+        });
+    }
+
+    for fee in msg.groups(&MISC_FEES) {
+        let Some(amt) = fee.find(tag::MISC_FEE_AMT) else {
+            continue;
+        };
+        let amount = FixDecimal::parse(amt.value)?;
+        let currency = fee
+            .find(tag::MISC_FEE_CURR)
+            .map_or(default_ccy, |f| f.value);
+        let fee_type = fee
+            .find(tag::MISC_FEE_TYPE)
+            .map_or(Ok(MiscFeeType::Other), |f| MiscFeeType::parse(f.value))?;
+        let sum = out
+            .misc_fees
+            .entry((currency.to_vec(), fee_type))
+            .or_insert(FixDecimal::ZERO);
+        *sum = sum.checked_add(&amount).ok_or(FixError::InvalidValue)?;
+        out.add_total(currency, amount)?;
+    }
+    Ok(out)
+}
+
+fn effective_commission(
+    msg: &Message<'_>,
+    amount: FixDecimal,
+    comm_type: Option<CommType>,
+) -> Result<Option<FixDecimal>, FixError> {
+    let field = |t| msg.find(t).map(|f| FixDecimal::parse(f.value)).transpose();
+    Ok(match comm_type {
+        None | Some(CommType::Absolute) => Some(amount),
+        Some(CommType::PerUnit) => field(tag::LAST_QTY)?.and_then(|q| amount.checked_mul(&q)),
+        Some(CommType::Percent) => match (field(tag::LAST_QTY)?, field(tag::LAST_PX)?) {
+            (Some(q), Some(px)) => amount
+                .checked_mul(&q)
+                .and_then(|n| n.checked_mul(&px))
+                .and_then(|n| n.checked_div(&FixDecimal::from(100), n.scale())),
+            _ => None,
+        },
+        Some(_) => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    fn d(s: &str) -> FixDecimal {
+        FixDecimal::parse(s.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn sums_per_currency_and_type() {
+        let raw = fix("35=8|15=USD|31=50|32=200|12=0.01|13=1|\
+136=4|137=1.25|139=4|137=0.75|139=4|137=3|138=GBP|139=5|137=0.10|139=1|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let fees = fee_breakdown(&msg).unwrap();
+
+        let comm = fees.commission.as_ref().unwrap();
+        assert_eq!(comm.comm_type, Some(CommType::PerUnit));
+        assert_eq!(comm.currency, b"USD");
+        assert_eq!(comm.effective, Some(d("2")));
+
+        assert_eq!(
+            fees.misc_fee(b"USD", MiscFeeType::ExchangeFees),
+            Some(d("2"))
+        );
+        assert_eq!(fees.misc_fee(b"GBP", MiscFeeType::Stamp), Some(d("3")));
+        assert_eq!(
+            fees.misc_fee(b"USD", MiscFeeType::Regulatory),
+            Some(d("0.10"))
+        );
+        assert_eq!(fees.total(b"USD"), Some(d("4.10")));
+        assert_eq!(fees.total(b"GBP"), Some(d("3")));
+    }
+
+    #[test]
+    fn percent_commission_and_unknown_fee_type() {
+        let raw = fix("35=8|15=EUR|31=20|32=100|12=0.5|13=2|479=CHF|136=1|137=1|139=42|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let fees = fee_breakdown(&msg).unwrap();
+        // 0.5% of 100 × 20 = 10
+        let comm = fees.commission.as_ref().unwrap();
+        assert_eq!(comm.effective, Some(d("10")));
+        assert_eq!(comm.currency, b"CHF");
+        assert_eq!(fees.total(b"CHF"), Some(d("10")));
+        assert_eq!(
+            fees.misc_fee(b"EUR", MiscFeeType::Unknown(42)),
+            Some(d("1"))
+        );
+    }
+
+    #[test]
+    fn malformed_amount_is_an_error() {
+        let raw = fix("35=8|136=1|137=abc|139=1|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        assert!(matches!(fee_breakdown(&msg), Err(FixError::InvalidValue)));
+    }
+}
@@ -1,12 +1,340 @@
-use crate::tag::Tag;
+use std::fmt::{self, Write};
+
+use crate::decimal::FixDecimal;
+use crate::error::FixError;
+use crate::tag::{self, Tag};
 
 pub const FIELD_SEPARATOR: u8 = 0x01;
 pub const FIELD_SEPARATOR_DISPLAY: char = '|'; // Only use to printing to UI or text-based debug
 // purpose
 pub const FIELD_KEY_VALUE_SEPARATOR: u8 = b'=';
 
-#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Field<'a> {
     pub tag: Tag,
     pub value: &'a [u8],
 }
+
+impl<'a> Field<'a> {
+    /// The value as UTF-8 text.
+    ///
+    /// # Errors
+    /// `FixError::InvalidUtf8` if the value is not valid UTF-8.
+    #[inline]
+    pub fn as_str(&self) -> Result<&'a str, FixError> {
+        std::str::from_utf8(self.value).map_err(|_| FixError::InvalidUtf8)
+    }
+
+    /// The value as an unsigned integer (SeqNum, Length, NumInGroup, …).
+    ///
+    /// # Errors
+    /// `FixError::InvalidValue` if the value is empty, contains a non-digit
+    /// byte, or overflows `u64`.
+    pub fn as_u64(&self) -> Result<u64, FixError> {
+        parse_digits(self.value)
+    }
+
+    /// The value as a signed integer: optional `-` followed by digits.
+    ///
+    /// # Errors
+    /// `FixError::InvalidValue` if the value is malformed or overflows `i64`.
+    pub fn as_i64(&self) -> Result<i64, FixError> {
+        match self.value {
+            [b'-', digits @ ..] => {
+                let n = parse_digits(digits)?;
+                0i64.checked_sub_unsigned(n).ok_or(FixError::InvalidValue)
+            }
+            digits => i64::try_from(parse_digits(digits)?).map_err(|_| FixError::InvalidValue),
+        }
+    }
+
+    /// The value as a float (Price, Qty, Amt, …): optional sign, digits and
+    /// at most one `.`. Exponents, `inf` and `NaN` are not FIX floats and
+    /// are rejected. Use [`FixDecimal`](crate::decimal::FixDecimal) when
+    /// exact decimal arithmetic matters.
+    ///
+    /// # Errors
+    /// `FixError::InvalidValue` if the value is not a FIX float.
+    pub fn as_f64(&self) -> Result<f64, FixError> {
+        let digits = match self.value {
+            [b'-' | b'+', rest @ ..] => rest,
+            v => v,
+        };
+        let mut dots = 0;
+        let mut any_digit = false;
+        for &b in digits {
+            match b {
+                b'0'..=b'9' => any_digit = true,
+                b'.' => dots += 1,
+                _ => return Err(FixError::InvalidValue),
+            }
+        }
+        if !any_digit || dots > 1 {
+            return Err(FixError::InvalidValue);
+        }
+        // Only ASCII digits, sign and '.' remain, so the value is valid UTF-8.
+        self.as_str()?.parse().map_err(|_| FixError::InvalidValue)
+    }
+
+    /// The value as an exact [`FixDecimal`] (Price, Qty, Amt, …), with the
+    /// same syntax as [`as_f64`](Self::as_f64) and at most
+    /// [`MAX_SCALE`](crate::decimal::MAX_SCALE) fractional digits.
+    ///
+    /// # Errors
+    /// `FixError::InvalidValue` if the value is not a FIX float or does not
+    /// fit.
+    #[inline]
+    pub fn as_decimal(&self) -> Result<FixDecimal, FixError> {
+        FixDecimal::parse(self.value)
+    }
+
+    /// The value as a single-character field (Side, OrdType, ExecType, …).
+    ///
+    /// # Errors
+    /// `FixError::InvalidValue` unless the value is exactly one ASCII byte.
+    #[inline]
+    pub fn as_char(&self) -> Result<char, FixError> {
+        match self.value {
+            [b] if b.is_ascii() => Ok(*b as char),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+
+    /// The value as a Boolean field: `Y` is true, `N` is false.
+    ///
+    /// # Errors
+    /// `FixError::InvalidValue` for anything else.
+    #[inline]
+    pub fn as_bool(&self) -> Result<bool, FixError> {
+        match self.value {
+            b"Y" => Ok(true),
+            b"N" => Ok(false),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+/// Parse a non-empty run of ASCII digits, checking for overflow.
+#[inline]
+fn parse_digits(digits: &[u8]) -> Result<u64, FixError> {
+    if digits.is_empty() {
+        return Err(FixError::InvalidValue);
+    }
+    let mut n: u64 = 0;
+    for &b in digits {
+        if !b.is_ascii_digit() {
+            return Err(FixError::InvalidValue);
+        }
+        n = n
+            .checked_mul(10)
+            .and_then(|n| n.checked_add((b - b'0') as u64))
+            .ok_or(FixError::InvalidValue)?;
+    }
+    Ok(n)
+}
+
+/// `55(Symbol)=AAPL`: the tag, its name when known, and the value as text —
+/// or as hex (`96(RawData)=0x01ff`) when it is not UTF-8.
+impl fmt::Display for Field<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_labelled(f, false)
+    }
+}
+
+/// Like `Display`, with control characters in the value escaped
+/// (`95(RawDataLength)=3`, `96(RawData)=a\u{1}b`).
+impl fmt::Debug for Field<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_labelled(f, true)
+    }
+}
+
+impl Field<'_> {
+    fn write_labelled(&self, f: &mut fmt::Formatter<'_>, escape: bool) -> fmt::Result {
+        write!(f, "{}", self.tag)?;
+        if let Some(name) = tag::name(self.tag) {
+            write!(f, "({name})")?;
+        }
+        f.write_char('=')?;
+        match std::str::from_utf8(self.value) {
+            Ok(text) if escape => write!(f, "{}", text.escape_debug()),
+            Ok(text) => f.write_str(text),
+            Err(_) => {
+                f.write_str("0x")?;
+                self.value.iter().try_for_each(|b| write!(f, "{b:02x}"))
+            }
+        }
+    }
+}
+
+/// Write `fields` as `tag=value|…` for logs, with
+/// [`FIELD_SEPARATOR_DISPLAY`] after each field. The alternate form (`{:#}`)
+/// writes tag names where known: `Symbol=AAPL|`.
+pub(crate) fn display_fields(fields: Fields<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for field in fields {
+        match tag::name(field.tag) {
+            Some(name) if f.alternate() => f.write_str(name)?,
+            _ => write!(f, "{}", field.tag)?,
+        }
+        f.write_char('=')?;
+        display_value(field.value, f)?;
+        f.write_char(FIELD_SEPARATOR_DISPLAY)?;
+    }
+    Ok(())
+}
+
+/// Write a value as text: SOH inside data values as
+/// [`FIELD_SEPARATOR_DISPLAY`], invalid UTF-8 as U+FFFD.
+fn display_value(value: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for chunk in value.utf8_chunks() {
+        for (i, part) in chunk.valid().split('\x01').enumerate() {
+            if i > 0 {
+                f.write_char(FIELD_SEPARATOR_DISPLAY)?;
+            }
+            f.write_str(part)?;
+        }
+        if !chunk.invalid().is_empty() {
+            f.write_char(char::REPLACEMENT_CHARACTER)?;
+        }
+    }
+    Ok(())
+}
+
+/// Iterator over the fields of a [`Message`](crate::message::Message) or
+/// [`Group`](crate::group::Group), reconstructing each `Field<'a>` zero-copy
+/// from the offset index.
+#[derive(Debug, Clone)]
+pub struct Fields<'a> {
+    pub(crate) buf: &'a [u8],
+    pub(crate) offsets: std::slice::Iter<'a, (Tag, u32, u32)>,
+}
+
+impl<'a> Fields<'a> {
+    #[inline]
+    pub(crate) fn new(buf: &'a [u8], offsets: &'a [(Tag, u32, u32)]) -> Self {
+        Self {
+            buf,
+            offsets: offsets.iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Field<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Field<'a>> {
+        self.offsets.next().map(|&(tag, start, end)| Field {
+            tag,
+            value: &self.buf[start as usize..end as usize],
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.offsets.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Fields<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Field<'a>> {
+        self.offsets.next_back().map(|&(tag, start, end)| Field {
+            tag,
+            value: &self.buf[start as usize..end as usize],
+        })
+    }
+}
+
+impl std::iter::FusedIterator for Fields<'_> {}
+
+impl ExactSizeIterator for Fields<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.offsets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f(value: &[u8]) -> Field<'_> {
+        Field { tag: 0, value }
+    }
+
+    #[test]
+    fn display_and_debug_name_the_tag() {
+        let symbol = Field {
+            tag: tag::SYMBOL,
+            value: b"AAPL",
+        };
+        assert_eq!(symbol.to_string(), "55(Symbol)=AAPL");
+        assert_eq!(format!("{symbol:?}"), "55(Symbol)=AAPL");
+        let custom = Field {
+            tag: 9999,
+            value: b"x",
+        };
+        assert_eq!(format!("{custom:?}"), "9999=x");
+
+        let data = Field {
+            tag: tag::RAW_DATA,
+            value: b"a\x01b",
+        };
+        assert_eq!(format!("{data:?}"), "96(RawData)=a\\u{1}b");
+        let binary = Field {
+            tag: tag::RAW_DATA,
+            value: b"\x01\xff",
+        };
+        assert_eq!(binary.to_string(), "96(RawData)=0x01ff");
+        assert_eq!(format!("{:?}", Some(binary)), "Some(96(RawData)=0x01ff)");
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(f(b"42").as_u64().unwrap(), 42);
+        assert_eq!(f(b"18446744073709551615").as_u64().unwrap(), u64::MAX);
+        assert!(f(b"18446744073709551616").as_u64().is_err());
+        assert!(f(b"").as_u64().is_err());
+        assert!(f(b"-1").as_u64().is_err());
+        assert!(f(b"1a").as_u64().is_err());
+
+        assert_eq!(f(b"-42").as_i64().unwrap(), -42);
+        assert_eq!(f(b"-9223372036854775808").as_i64().unwrap(), i64::MIN);
+        assert!(f(b"9223372036854775808").as_i64().is_err());
+        assert!(f(b"-").as_i64().is_err());
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(f(b"149.50").as_f64().unwrap(), 149.5);
+        assert_eq!(f(b"-0.25").as_f64().unwrap(), -0.25);
+        assert_eq!(f(b".5").as_f64().unwrap(), 0.5);
+        assert_eq!(f(b"100").as_f64().unwrap(), 100.0);
+        for bad in [&b""[..], b".", b"1.2.3", b"1e5", b"inf", b"NaN", b"1,5"] {
+            assert!(f(bad).as_f64().is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn decimals() {
+        assert_eq!(f(b"149.50").as_decimal().unwrap(), FixDecimal::new(1495, 1));
+        assert_eq!(f(b"-0.25").as_decimal().unwrap().scale(), 2);
+        assert_eq!(f(b"100").as_decimal().unwrap(), FixDecimal::from(100));
+        for bad in [&b""[..], b".", b"1.2.3", b"1e5", b"0.1234567890123456789"] {
+            assert!(f(bad).as_decimal().is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn chars_bools_and_text() {
+        assert_eq!(f(b"1").as_char().unwrap(), '1');
+        assert!(f(b"12").as_char().is_err());
+        assert!(f(b"").as_char().is_err());
+        assert!(f(b"Y").as_bool().unwrap());
+        assert!(!f(b"N").as_bool().unwrap());
+        assert!(f(b"y").as_bool().is_err());
+        assert_eq!(f(b"AAPL").as_str().unwrap(), "AAPL");
+        assert!(matches!(f(b"\xff").as_str(), Err(FixError::InvalidUtf8)));
+    }
+}
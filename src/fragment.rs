@@ -0,0 +1,627 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::group::{self, GroupSpec, parse_count};
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// Describes a repeating group that may be split across several messages.
+///
+/// - `group`: the repeating group carried in each fragment.
+/// - `total_tag`: the `Tot*` tag announcing the number of instances across
+///   *all* fragments (e.g. TotNoRelatedSym 393 for NO_RELATED_SYM), or `None`
+///   for messages that have no such tag.
+///
+/// The end of a fragmented response is marked by LastFragment (893) = `Y`.
+pub struct FragmentSpec {
+    pub group: &'static GroupSpec,
+    pub total_tag: Option<Tag>,
+}
+
+/// SecurityList (35=y) — Symbol-led NO_RELATED_SYM
+/// ([`group::SECURITY_LIST_RELATED_SYM`]) with TotNoRelatedSym (393).
+pub const SECURITY_LIST: FragmentSpec = FragmentSpec {
+    group: &group::SECURITY_LIST_RELATED_SYM,
+    total_tag: Some(tag::TOT_NO_RELATED_SYM),
+};
+
+/// NewOrderList (35=E) — NO_ORDERS with TotNoOrders (68).
+pub const NEW_ORDER_LIST: FragmentSpec = FragmentSpec {
+    group: &group::ORDERS,
+    total_tag: Some(tag::TOT_NO_ORDERS),
+};
+
+/// SecurityTypes (35=w) — NO_SECURITY_TYPES with TotNoSecurityTypes (557).
+pub const SECURITY_TYPE_LIST: FragmentSpec = FragmentSpec {
+    group: &group::SECURITY_TYPES,
+    total_tag: Some(tag::TOT_NO_SECURITY_TYPES),
+};
+
+/// DerivativeSecurityList-style strike lists — NO_STRIKES with TotNoStrikes (422).
+pub const STRIKE_LIST: FragmentSpec = FragmentSpec {
+    group: &group::STRIKES,
+    total_tag: Some(tag::TOT_NO_STRIKES),
+};
+
+/// MarketDataSnapshotFullRefresh (35=W) — NO_MD_ENTRIES, continued with
+/// LastFragment only.
+pub const MARKET_DATA_SNAPSHOT: FragmentSpec = FragmentSpec {
+    group: &group::MD_ENTRIES,
+    total_tag: None,
+};
+
+/// MassQuote (35=i) — NO_QUOTE_SETS, continued with LastFragment only. Each
+/// quote set keeps its own TotQuoteEntries.
+pub const MASS_QUOTE: FragmentSpec = FragmentSpec {
+    group: &group::QUOTE_SETS,
+    total_tag: None,
+};
+
+/// Return the built-in fragment spec for a MsgType (35) value, if the message
+/// type is one that is commonly split: SecurityList (`y`), NewOrderList (`E`),
+/// SecurityTypes (`w`), MarketDataSnapshotFullRefresh (`W`) and MassQuote (`i`).
+pub fn spec_for_msg_type(msg_type: &[u8]) -> Option<&'static FragmentSpec> {
+    match msg_type {
+        b"y" => Some(&SECURITY_LIST),
+        b"E" => Some(&NEW_ORDER_LIST),
+        b"w" => Some(&SECURITY_TYPE_LIST),
+        b"W" => Some(&MARKET_DATA_SNAPSHOT),
+        b"i" => Some(&MASS_QUOTE),
+        _ => None,
+    }
+}
+
+/// Outcome of feeding one message to a [`FragmentAccumulator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FragmentStatus {
+    /// More fragments are expected.
+    Incomplete,
+    /// The logical collection is complete.
+    Complete,
+}
+
+/// Stitches the group instances of a fragmented response back into one
+/// logical collection.
+///
+/// Each call to [`add`] copies the instances of the fragment's group into
+/// owned storage. The collection is complete when a fragment carries
+/// LastFragment (893) = `Y`, or — for counterparties that omit 893 — when the
+/// number of collected instances reaches the `Tot*` value. A message that
+/// carries neither is treated as an unfragmented, complete response.
+///
+/// # Example
+/// ```ignore
+/// let mut acc = FragmentAccumulator::new(&fragment::SECURITY_LIST);
+/// for raw in responses {
+///     let msg = decoder.decode(raw)?;
+///     if acc.add(&msg) == FragmentStatus::Complete {
+///         for instrument in acc.instances() {
+///             let sym = instrument.as_message().find(tag::SYMBOL);
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`add`]: FragmentAccumulator::add
+pub struct FragmentAccumulator {
+    spec: &'static FragmentSpec,
+    expected: Option<usize>,
+    instances: Vec<MessageBuilder>,
+    complete: bool,
+}
+
+impl FragmentAccumulator {
+    /// Create an empty accumulator for `spec`.
+    pub fn new(spec: &'static FragmentSpec) -> Self {
+        Self {
+            spec,
+            expected: None,
+            instances: Vec::new(),
+            complete: false,
+        }
+    }
+
+    /// Discard every collected instance so the accumulator can be reused.
+    pub fn reset(&mut self) {
+        self.expected = None;
+        self.instances.clear();
+        self.complete = false;
+    }
+
+    /// Add one fragment. Fragments received after completion start a new
+    /// collection.
+    pub fn add(&mut self, msg: &Message<'_>) -> FragmentStatus {
+        if self.complete {
+            self.reset();
+        }
+
+        if let Some(f) = self.spec.total_tag.and_then(|t| msg.find(t)) {
+            self.expected = Some(parse_count(f.value));
+        }
+
+        if let Some(span) = GroupSpan::locate(msg, self.spec.group) {
+            for range in span.instances(msg, self.spec.group) {
+                let mut b = MessageBuilder::with_capacity(range.len(), 0);
+                for i in range {
+                    let f = msg.field(i);
+                    b.push(f.tag, f.value);
+                }
+                self.instances.push(b);
+            }
+        }
+
+        self.complete = match msg.find(tag::LAST_FRAGMENT).map(|f| f.value) {
+            Some(b"Y") => true,
+            Some(_) => false,
+            None => self.expected.is_none_or(|n| self.instances.len() >= n),
+        };
+
+        if self.complete {
+            FragmentStatus::Complete
+        } else {
+            FragmentStatus::Incomplete
+        }
+    }
+
+    /// Returns true once the final fragment has been added.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// The `Tot*` value announced by the counterparty, if any.
+    #[inline]
+    pub fn expected_total(&self) -> Option<usize> {
+        self.expected
+    }
+
+    /// Number of instances collected so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Returns true if no instances have been collected.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// The collected group instances, in arrival order. Each one is viewable
+    /// as a `Message` via [`MessageBuilder::as_message`].
+    #[inline]
+    pub fn instances(&self) -> &[MessageBuilder] {
+        &self.instances
+    }
+}
+
+/// Splits a message whose repeating group is too large for one wire message
+/// into fragments no larger than `max_size` bytes once encoded.
+///
+/// The fields before and after the group are repeated in every fragment.
+/// Each fragment receives the `Tot*` tag if the spec has one (total instances
+/// across all fragments), LastFragment (893), and a `NO_*` count matching the
+/// instances it carries. Any `Tot*`, 893, 9, or 10 fields already present in the input
+/// are dropped and replaced.
+///
+/// Sizes are computed exactly as [`Encoder::encode`](crate::encoder::Encoder::encode)
+/// lays out a message with auto-calculated BodyLength and CheckSum.
+pub struct Splitter {
+    spec: &'static FragmentSpec,
+    max_size: usize,
+}
+
+impl Splitter {
+    /// Create a splitter producing fragments of at most `max_size` wire bytes.
+    pub fn new(spec: &'static FragmentSpec, max_size: usize) -> Self {
+        Self { spec, max_size }
+    }
+
+    /// Split `msg` into fragments appended to `out`.
+    ///
+    /// A message without the group (or with a zero count) produces a single
+    /// fragment.
+    ///
+    /// # Errors
+    /// Returns `FixError::EncodeError` if the repeated fields plus a single
+    /// group instance already exceed `max_size`.
+    pub fn split(&self, msg: &Message<'_>, out: &mut Vec<MessageBuilder>) -> Result<(), FixError> {
+        const DEFAULT_VERSION_LEN: usize = b"FIX.4.4".len();
+        let version_len = msg
+            .find(tag::BEGIN_STRING)
+            .map_or(DEFAULT_VERSION_LEN, |f| f.value.len());
+
+        let spec = self.spec;
+        let span = GroupSpan::locate(msg, spec.group);
+        let (prefix_end, suffix_start) = match &span {
+            Some(s) => (s.count_index, s.end),
+            None => (msg.len(), msg.len()),
+        };
+        let instances: Vec<_> = match &span {
+            Some(s) => s.instances(msg, spec.group).collect(),
+            None => Vec::new(),
+        };
+
+        let is_repeated = |t: Tag| {
+            t != tag::BODY_LENGTH
+                && t != tag::CHECK_SUM
+                && Some(t) != spec.total_tag
+                && t != tag::LAST_FRAGMENT
+        };
+        let repeated = || {
+            (0..prefix_end)
+                .chain(suffix_start..msg.len())
+                .filter(|&i| is_repeated(msg.field(i).tag))
+        };
+
+        // Body bytes shared by every fragment (tag 8 is not part of the body).
+        let mut fixed_body: usize = repeated()
+            .map(|i| msg.field(i))
+            .filter(|f| f.tag != tag::BEGIN_STRING)
+            .map(|f| field_len(f.tag, f.value.len()))
+            .sum();
+        if let Some(t) = spec.total_tag {
+            fixed_body += field_len(t, decimal_len(instances.len()));
+        }
+        fixed_body += field_len(tag::LAST_FRAGMENT, 1);
+
+        let instance_bytes: Vec<usize> = instances
+            .iter()
+            .map(|r| {
+                r.clone()
+                    .map(|i| {
+                        let f = msg.field(i);
+                        field_len(f.tag, f.value.len())
+                    })
+                    .sum()
+            })
+            .collect();
+
+        let wire_len = |count: usize, bytes: usize| {
+            let body = fixed_body + field_len(spec.group.count_tag, decimal_len(count)) + bytes;
+            // "8=" + version + SOH, "9=" + digits + SOH, body, "10=nnn" + SOH
+            2 + version_len + 1 + 2 + decimal_len(body) + 1 + body + 7
+        };
+
+        // Greedily pack instances into chunks.
+        let mut chunks: Vec<std::ops::Range<usize>> = Vec::new();
+        let mut start = 0;
+        let mut bytes = 0;
+        for (i, &n) in instance_bytes.iter().enumerate() {
+            if wire_len(i + 1 - start, bytes + n) > self.max_size {
+                if i == start {
+                    return Err(FixError::EncodeError);
+                }
+                chunks.push(start..i);
+                start = i;
+                bytes = 0;
+                if wire_len(1, n) > self.max_size {
+                    return Err(FixError::EncodeError);
+                }
+            }
+            bytes += n;
+        }
+        if start < instances.len() || chunks.is_empty() {
+            if wire_len(instances.len() - start, bytes) > self.max_size {
+                return Err(FixError::EncodeError);
+            }
+            chunks.push(start..instances.len());
+        }
+
+        let total = instances.len() as u32;
+        let last_chunk = chunks.len() - 1;
+        for (n, chunk) in chunks.into_iter().enumerate() {
+            let mut b = MessageBuilder::new();
+            for i in repeated().take_while(|&i| i < prefix_end) {
+                let f = msg.field(i);
+                b.push(f.tag, f.value);
+            }
+            if let Some(t) = spec.total_tag {
+                b.push_u32(t, total);
+            }
+            b.push(
+                tag::LAST_FRAGMENT,
+                if n == last_chunk { b"Y" } else { b"N" },
+            );
+            b.push_u32(spec.group.count_tag, chunk.len() as u32);
+            for inst in &instances[chunk] {
+                for i in inst.clone() {
+                    let f = msg.field(i);
+                    b.push(f.tag, f.value);
+                }
+            }
+            for i in repeated().skip_while(|&i| i < prefix_end) {
+                let f = msg.field(i);
+                b.push(f.tag, f.value);
+            }
+            out.push(b);
+        }
+        Ok(())
+    }
+}
+
+/// Position of one repeating group inside a message's field list.
+struct GroupSpan {
+    /// Index of the `NO_*` count field.
+    count_index: usize,
+    /// Index one past the last field of the last instance.
+    end: usize,
+}
+
+impl GroupSpan {
+    /// Find the group described by `spec`. Instances are walked with
+    /// [`group::group_end`], so nested groups (QuoteEntries inside a
+    /// QuoteSet) stay with their instance, and the group ends at the first
+    /// field that belongs to none of them: trailing fields (including tag
+    /// 10) are never swallowed into the last instance.
+    fn locate(msg: &Message<'_>, spec: &GroupSpec) -> Option<GroupSpan> {
+        let count_index = msg.fields().position(|f| f.tag == spec.count_tag)?;
+        let end = group::group_end(msg.raw_offsets(), msg.raw_buf(), count_index, spec);
+        Some(GroupSpan { count_index, end })
+    }
+
+    /// Field index ranges of each instance, nested groups included.
+    fn instances<'m>(
+        &self,
+        msg: &'m Message<'_>,
+        spec: &'m GroupSpec,
+    ) -> impl Iterator<Item = std::ops::Range<usize>> + 'm {
+        let end = self.end;
+        let mut next = self.count_index + 1;
+        std::iter::from_fn(move || {
+            let start = next;
+            if start >= end {
+                return None;
+            }
+            next = group::instance_end(msg.raw_offsets(), msg.raw_buf(), start, spec);
+            Some(start..next)
+        })
+    }
+}
+
+/// Wire length of `tag=value\x01` for a value of `value_len` bytes.
+#[inline]
+fn field_len(tag: Tag, value_len: usize) -> usize {
+    decimal_len(tag as usize) + 1 + value_len + 1
+}
+
+/// Number of ASCII digits needed to print `n`.
+#[inline]
+fn decimal_len(mut n: usize) -> usize {
+    let mut len = 1;
+    while n >= 10 {
+        n /= 10;
+        len += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    #[test]
+    fn accumulator_stitches_until_last_fragment() {
+        let mut acc = FragmentAccumulator::new(&SECURITY_LIST);
+        let mut dec = Decoder::new();
+
+        let raw1 = fix("35=y|320=REQ|393=3|893=N|146=2|55=AAPL|55=MSFT|10=000|");
+        let msg = dec.decode(&raw1).unwrap();
+        assert_eq!(acc.add(&msg), FragmentStatus::Incomplete);
+        assert_eq!(acc.len(), 2);
+
+        let raw2 = fix("35=y|320=REQ|393=3|893=Y|146=1|55=IBM|10=000|");
+        let msg = dec.decode(&raw2).unwrap();
+        assert_eq!(acc.add(&msg), FragmentStatus::Complete);
+        assert_eq!(acc.expected_total(), Some(3));
+
+        let syms: Vec<_> = acc
+            .instances()
+            .iter()
+            .map(|b| b.as_message().find(tag::SYMBOL).unwrap().value.to_vec())
+            .collect();
+        assert_eq!(
+            syms,
+            vec![b"AAPL".to_vec(), b"MSFT".to_vec(), b"IBM".to_vec()]
+        );
+        // The trailer never leaks into the last instance.
+        assert!(
+            acc.instances()[2]
+                .as_message()
+                .find(tag::CHECK_SUM)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn accumulator_falls_back_to_total_count() {
+        let mut acc = FragmentAccumulator::new(&NEW_ORDER_LIST);
+        let mut dec = Decoder::new();
+
+        let raw1 = fix("35=E|66=L1|68=2|73=1|11=A|");
+        let msg = dec.decode(&raw1).unwrap();
+        assert_eq!(acc.add(&msg), FragmentStatus::Incomplete);
+
+        let raw2 = fix("35=E|66=L1|68=2|73=1|11=B|");
+        let msg = dec.decode(&raw2).unwrap();
+        assert_eq!(acc.add(&msg), FragmentStatus::Complete);
+        assert_eq!(acc.len(), 2);
+
+        // A new fragment after completion starts a fresh collection.
+        let msg = dec.decode(&raw1).unwrap();
+        acc.add(&msg);
+        assert_eq!(acc.len(), 1);
+    }
+
+    #[test]
+    fn unfragmented_message_is_complete() {
+        let mut acc = FragmentAccumulator::new(&SECURITY_LIST);
+        let raw = fix("35=y|146=1|55=AAPL|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        assert_eq!(acc.add(&msg), FragmentStatus::Complete);
+    }
+
+    #[test]
+    fn splitter_respects_max_size_and_round_trips() {
+        let raw = fix("8=FIX.4.4|35=y|49=S|56=T|320=REQ|146=5|\
+             55=AAAA|107=first|55=BBBB|107=second|55=CCCC|107=third|\
+             55=DDDD|107=fourth|55=EEEE|107=fifth|1=tail|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let max = 110;
+        let mut frags = Vec::new();
+        Splitter::new(&SECURITY_LIST, max)
+            .split(&msg, &mut frags)
+            .unwrap();
+        assert!(frags.len() > 1);
+
+        let mut enc = Encoder::new();
+        let mut acc = FragmentAccumulator::new(&SECURITY_LIST);
+        let mut dec2 = Decoder::new();
+        let mut out = Vec::new();
+        for (i, frag) in frags.iter().enumerate() {
+            enc.encode(&frag.as_message(), &mut out).unwrap();
+            assert!(out.len() <= max, "fragment {i} is {} bytes", out.len());
+
+            let m = dec2.decode(&out).unwrap();
+            assert!(m.validate_body_length().is_ok());
+            assert_eq!(m.find(tag::TOT_NO_RELATED_SYM).unwrap().value, b"5");
+            assert_eq!(m.find(tag::ACCOUNT).unwrap().value, b"tail");
+            let status = acc.add(&m);
+            let last = i == frags.len() - 1;
+            assert_eq!(status == FragmentStatus::Complete, last);
+        }
+        assert_eq!(acc.len(), 5);
+        assert_eq!(
+            acc.instances()[4]
+                .as_message()
+                .find(tag::SECURITY_DESC)
+                .unwrap()
+                .value,
+            b"fifth"
+        );
+    }
+
+    #[test]
+    fn splitter_splits_symbol_led_security_list() {
+        let raw = fix("8=FIX.4.4|35=y|49=S|56=T|320=REQ|322=RSP|560=0|146=6|\
+             55=AAPL|48=037833100|22=1|55=MSFT|48=594918104|22=1|\
+             55=IBM|48=459200101|22=1|55=GOOG|48=38259P508|22=1|\
+             55=AMZN|48=023135106|22=1|55=ORCL|48=68389X105|22=1|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let max = 140;
+        let mut frags = Vec::new();
+        Splitter::new(&SECURITY_LIST, max)
+            .split(&msg, &mut frags)
+            .unwrap();
+        assert!(frags.len() > 1);
+
+        let mut enc = Encoder::new();
+        let mut acc = FragmentAccumulator::new(&SECURITY_LIST);
+        let mut out = Vec::new();
+        for frag in &frags {
+            enc.encode(&frag.as_message(), &mut out).unwrap();
+            assert!(out.len() <= max, "fragment is {} bytes", out.len());
+            acc.add(&dec.decode(&out).unwrap());
+        }
+        assert!(acc.is_complete());
+        let syms: Vec<_> = acc
+            .instances()
+            .iter()
+            .map(|b| b.as_message().find(tag::SYMBOL).unwrap().value.to_vec())
+            .collect();
+        assert_eq!(
+            syms,
+            [&b"AAPL"[..], b"MSFT", b"IBM", b"GOOG", b"AMZN", b"ORCL"]
+        );
+        let orcl = acc.instances()[5].as_message();
+        assert_eq!(orcl.find(tag::SECURITY_ID).unwrap().value, b"68389X105");
+    }
+
+    #[test]
+    fn splitter_exact_fit_is_single_fragment() {
+        let raw = fix("35=y|146=2|55=A|55=B|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut frags = Vec::new();
+        Splitter::new(&SECURITY_LIST, 4096)
+            .split(&msg, &mut frags)
+            .unwrap();
+        assert_eq!(frags.len(), 1);
+
+        let mut out = Vec::new();
+        Encoder::new()
+            .encode(&frags[0].as_message(), &mut out)
+            .unwrap();
+
+        // Re-splitting with the exact encoded size still fits in one message.
+        let mut again = Vec::new();
+        Splitter::new(&SECURITY_LIST, out.len())
+            .split(&msg, &mut again)
+            .unwrap();
+        assert_eq!(again.len(), 1);
+    }
+
+    #[test]
+    fn splitter_keeps_nested_quote_entries_with_their_set() {
+        let raw = fix("8=FIX.4.4|35=i|49=S|56=T|117=Q1|296=3|\
+             302=S1|311=AAPL|295=2|299=E1|132=1.50|299=E2|132=1.51|\
+             302=S2|311=MSFT|295=2|299=E3|132=2.50|299=E4|132=2.51|\
+             302=S3|311=IBMM|295=2|299=E5|132=3.50|299=E6|132=3.51|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let max = 160;
+        let mut frags = Vec::new();
+        Splitter::new(&MASS_QUOTE, max)
+            .split(&msg, &mut frags)
+            .unwrap();
+        assert!(frags.len() > 1);
+
+        let mut enc = Encoder::new();
+        let mut acc = FragmentAccumulator::new(&MASS_QUOTE);
+        let mut dec2 = Decoder::new();
+        let mut out = Vec::new();
+        for frag in &frags {
+            enc.encode(&frag.as_message(), &mut out).unwrap();
+            assert!(out.len() <= max, "fragment is {} bytes", out.len());
+            acc.add(&dec2.decode(&out).unwrap());
+        }
+        assert!(acc.is_complete());
+        assert_eq!(acc.len(), 3);
+        for (set, entries) in acc
+            .instances()
+            .iter()
+            .zip([["E1", "E2"], ["E3", "E4"], ["E5", "E6"]])
+        {
+            let set = set.as_message();
+            let ids: Vec<_> = set.find_all(tag::QUOTE_ENTRY_ID).map(|f| f.value).collect();
+            assert_eq!(ids, entries.map(str::as_bytes));
+            assert_eq!(set.find(tag::NO_QUOTE_ENTRIES).unwrap().value, b"2");
+        }
+    }
+
+    #[test]
+    fn splitter_errors_when_instance_cannot_fit() {
+        let raw = fix("35=y|146=1|55=AAPL|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let mut frags = Vec::new();
+        assert!(matches!(
+            Splitter::new(&SECURITY_LIST, 20).split(&msg, &mut frags),
+            Err(FixError::EncodeError)
+        ));
+    }
+}
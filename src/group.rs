@@ -1,4 +1,7 @@
-use crate::field::Field;
+use std::sync::Arc;
+
+use crate::field::{Field, Fields, display_fields};
+use crate::message::Message;
 use crate::tag::{self, Tag};
 
 /// Describes one repeating group in the FIX specification.
@@ -80,6 +83,13 @@ pub const ORDERS: GroupSpec = GroupSpec {
         tag::SECURITY_EXCHANGE,
         tag::ISSUER,
         tag::SECURITY_DESC,
+        // ListStatus (35=N) per-order status
+        tag::CUM_QTY,
+        tag::ORD_STATUS,
+        tag::LEAVES_QTY,
+        tag::CXL_QTY,
+        tag::AVG_PX,
+        tag::ORD_REJ_REASON,
         tag::TEXT,
     ],
 };
@@ -295,29 +305,146 @@ pub const BID_DESCRIPTORS: GroupSpec = GroupSpec {
     ],
 };
 
-/// NO_BID_COMPONENTS (420) — ClearingFirm is the delimiter tag.
+/// NO_BID_COMPONENTS (420) in a BidRequest (35=k) — ListID is the
+/// delimiter tag.
 pub const BID_COMPONENTS: GroupSpec = GroupSpec {
     count_tag: tag::NO_BID_COMPONENTS,
-    delimiter_tag: tag::CLEARING_FIRM,
+    delimiter_tag: tag::LIST_ID,
     member_tags: &[
-        tag::CLEARING_FIRM,
-        tag::CLEARING_ACCOUNT,
-        tag::LIQUIDITY_IND_TYPE,
-        tag::WT_AVERAGE_LIQUIDITY,
-        tag::EXCHANGE_FOR_PHYSICAL,
-        tag::OUT_MAIN_CNTRY_U_INDEX,
-        tag::CROSS_PERCENT,
-        tag::PROG_RPT_REQS,
-        tag::PROG_PERIOD_INTERVAL,
-        tag::INC_TAX_IND,
-        tag::NUM_BIDDERS,
-        tag::TRADE_TYPE,
-        tag::BASIS_PX_TYPE,
+        tag::LIST_ID,
+        tag::SIDE,
+        tag::TRADING_SESSION_ID,
+        tag::TRADING_SESSION_SUB_ID,
+        tag::NET_GROSS_IND,
+        tag::SETTLMNT_TYP,
+        tag::FUT_SETT_DATE,
+        tag::ACCOUNT,
+        tag::ACCT_ID_SOURCE,
+    ],
+};
+
+/// NO_BID_COMPONENTS (420) in a BidResponse (35=l) — Commission is the
+/// delimiter tag.
+///
+/// Shares its count tag with [`BID_COMPONENTS`], so it is not part of the
+/// version tables; pass it explicitly when reading a BidResponse.
+pub const BID_COMPONENT_RESPONSES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_BID_COMPONENTS,
+    delimiter_tag: tag::COMMISSION,
+    member_tags: &[
+        tag::COMMISSION,
+        tag::COMM_TYPE,
+        tag::COMM_CURRENCY,
+        tag::FUND_RENEW_WAIV,
+        tag::LIST_ID,
         tag::COUNTRY,
         tag::SIDE,
         tag::PRICE,
         tag::PRICE_TYPE,
         tag::FAIR_VALUE,
+        tag::NET_GROSS_IND,
+        tag::SETTLMNT_TYP,
+        tag::FUT_SETT_DATE,
+        tag::TRADING_SESSION_ID,
+        tag::TRADING_SESSION_SUB_ID,
+        tag::TEXT,
+        tag::ENCODED_TEXT_LEN,
+        tag::ENCODED_TEXT,
+    ],
+};
+
+/// NO_RELATED_SYM (146) in a QuoteRequest (35=R) — Symbol is the
+/// delimiter tag.
+///
+/// Shares its count tag with [`RELATED_SYM`], so it is not part of the
+/// version tables; pass it explicitly when reading a QuoteRequest.
+pub const QUOTE_REQUEST_RELATED_SYM: GroupSpec = GroupSpec {
+    count_tag: tag::NO_RELATED_SYM,
+    delimiter_tag: tag::SYMBOL,
+    member_tags: &[
+        tag::SYMBOL,
+        tag::SYMBOL_SFX,
+        tag::SECURITY_ID,
+        tag::SECURITY_ID_SOURCE,
+        tag::NO_SECURITY_ALT_ID,
+        tag::SECURITY_ALT_ID,
+        tag::SECURITY_ALT_ID_SOURCE,
+        tag::PRODUCT,
+        tag::CFI_CODE,
+        tag::SECURITY_TYPE,
+        tag::MATURITY_MONTH_YEAR,
+        tag::MATURITY_DATE,
+        tag::PUT_OR_CALL,
+        tag::STRIKE_PRICE,
+        tag::CONTRACT_MULTIPLIER,
+        tag::SECURITY_EXCHANGE,
+        tag::ISSUER,
+        tag::SECURITY_DESC,
+        tag::PREV_CLOSE_PX,
+        tag::QUOTE_REQUEST_TYPE,
+        tag::QUOTE_TYPE,
+        tag::TRADING_SESSION_ID,
+        tag::TRADING_SESSION_SUB_ID,
+        tag::TRADE_ORIGINATION_DATE,
+        tag::SIDE,
+        tag::QTY_TYPE,
+        tag::ORDER_QTY,
+        tag::CASH_ORDER_QTY,
+        tag::SETTL_TYPE,
+        tag::SETTL_DATE,
+        tag::ORD_TYPE,
+        tag::SETTL_DATE2,
+        tag::ORDER_QTY2,
+        tag::CURRENCY,
+        tag::ACCOUNT,
+        tag::ACCT_ID_SOURCE,
+        tag::ACCOUNT_TYPE,
+        tag::NO_QUOTE_QUALIFIERS,
+        tag::QUOTE_QUALIFIER,
+        tag::EXPIRE_TIME,
+        tag::TRANSACT_TIME,
+        tag::PRICE,
+        tag::PRICE2,
+        tag::NO_PARTY_IDS,
+    ],
+};
+
+/// NO_RELATED_SYM (146) in a FIX 4.4 SecurityList (35=y) — Symbol is the
+/// delimiter tag.
+///
+/// Shares its count tag with [`RELATED_SYM`], so it is not part of the
+/// version tables; pass it explicitly when reading a SecurityList.
+pub const SECURITY_LIST_RELATED_SYM: GroupSpec = GroupSpec {
+    count_tag: tag::NO_RELATED_SYM,
+    delimiter_tag: tag::SYMBOL,
+    member_tags: &[
+        tag::SYMBOL,
+        tag::SYMBOL_SFX,
+        tag::SECURITY_ID,
+        tag::SECURITY_ID_SOURCE,
+        tag::NO_SECURITY_ALT_ID,
+        tag::SECURITY_ALT_ID,
+        tag::SECURITY_ALT_ID_SOURCE,
+        tag::PRODUCT,
+        tag::CFI_CODE,
+        tag::SECURITY_TYPE,
+        tag::MATURITY_MONTH_YEAR,
+        tag::MATURITY_DATE,
+        tag::PUT_OR_CALL,
+        tag::STRIKE_PRICE,
+        tag::STRIKE_CURRENCY,
+        tag::CONTRACT_MULTIPLIER,
+        tag::COUPON_RATE,
+        tag::FACTOR,
+        tag::SECURITY_EXCHANGE,
+        tag::ISSUER,
+        tag::SECURITY_DESC,
+        tag::CURRENCY,
+        tag::ROUND_LOT,
+        tag::MIN_TRADE_VOL,
+        tag::TRADING_SESSION_ID,
+        tag::EXPIRATION_CYCLE,
+        tag::TEXT,
     ],
 };
 
@@ -358,6 +485,8 @@ pub const PARTY_IDS: GroupSpec = GroupSpec {
         tag::PARTY_ID_SOURCE,
         tag::PARTY_ROLE,
         tag::PARTY_SUB_ID,
+        tag::NO_PARTY_SUB_IDS,
+        tag::PARTY_SUB_ID_TYPE,
     ],
 };
 
@@ -378,28 +507,19 @@ pub const UNDERLYING_SECURITY_ALT_IDS: GroupSpec = GroupSpec {
     ],
 };
 
-/// NO_REGIST_DTLS (473) — MailingDtls is the delimiter tag.
+/// NO_REGIST_DTLS (473) — RegistDtls is the delimiter tag.
 pub const REGIST_DTLS: GroupSpec = GroupSpec {
     count_tag: tag::NO_REGIST_DTLS,
-    delimiter_tag: tag::MAILING_DTLS,
+    delimiter_tag: tag::REGIST_DTLS,
     member_tags: &[
-        tag::MAILING_DTLS,
-        tag::INVESTOR_COUNTRY_OF_RESIDENCE,
-        tag::MAILING_INST,
         tag::REGIST_DTLS,
         tag::REGIST_EMAIL,
-        tag::DISTRIB_PERCENTAGE,
-        tag::REGIST_ID,
-        tag::REGIST_TRANS_TYPE,
+        tag::MAILING_DTLS,
+        tag::MAILING_INST,
+        tag::NO_NESTED_PARTY_IDS,
         tag::OWNER_TYPE,
-        tag::NO_DISTRIB_INSTS,
-        tag::DISTRIB_PAYMENT_METHOD,
-        tag::CASH_DISTRIB_CURR,
-        tag::CASH_DISTRIB_AGENT_NAME,
-        tag::CASH_DISTRIB_AGENT_CODE,
-        tag::CASH_DISTRIB_AGENT_ACCT_NUMBER,
-        tag::CASH_DISTRIB_PAY_REF,
-        tag::CASH_DISTRIB_AGENT_ACCT_NAME,
+        tag::DATE_OF_BIRTH,
+        tag::INVESTOR_COUNTRY_OF_RESIDENCE,
     ],
 };
 
@@ -513,6 +633,16 @@ pub const SIDES: GroupSpec = GroupSpec {
         tag::ALLOC_SETTL_CURRENCY,
         tag::INDIVIDUAL_ALLOC_ID,
         tag::ALLOC_SHARES,
+        // NewOrderCross / CrossOrderCancel(Replace)Request (35=s/t/u) sides
+        tag::ORIG_CL_ORD_ID,
+        tag::CL_ORD_LINK_ID,
+        tag::ORIG_ORD_MOD_TIME,
+        tag::NO_PARTY_IDS,
+        tag::TRADE_ORIGINATION_DATE,
+        tag::TRADE_DATE,
+        tag::ORDER_QTY,
+        tag::CASH_ORDER_QTY,
+        tag::SIDE_COMPLIANCE_ID,
     ],
 };
 
@@ -581,6 +711,8 @@ pub const LEGS: GroupSpec = GroupSpec {
         tag::LEG_SETTL_DATE,
         tag::LEG_LAST_PX,
         tag::LEG_REF_ID,
+        tag::NO_NESTED_PARTY_IDS,
+        tag::NO_LEG_ALLOCS,
     ],
 };
 
@@ -755,7 +887,11 @@ pub const ALT_MD_SOURCES: GroupSpec = GroupSpec {
 pub const CAPACITIES: GroupSpec = GroupSpec {
     count_tag: tag::NO_CAPACITIES,
     delimiter_tag: tag::ORDER_CAPACITY,
-    member_tags: &[tag::ORDER_CAPACITY, tag::ORDER_CAPACITY_QTY],
+    member_tags: &[
+        tag::ORDER_CAPACITY,
+        tag::ORDER_RESTRICTIONS,
+        tag::ORDER_CAPACITY_QTY,
+    ],
 };
 
 /// NO_EVENTS (864) — EventType is the delimiter tag.
@@ -864,6 +1000,227 @@ pub const CLEARING_INSTRUCTIONS: GroupSpec = GroupSpec {
     member_tags: &[tag::CLEARING_INSTRUCTION],
 };
 
+// ---------------------------------------------------------------------------
+// FIX 5.0 SP2 built-in group specs
+// Source: https://www.onixs.biz/fix-dictionary/5.0.sp2/
+// ---------------------------------------------------------------------------
+
+/// NO_STRATEGY_PARAMETERS (957) — StrategyParameterName is the delimiter tag.
+pub const STRATEGY_PARAMETERS: GroupSpec = GroupSpec {
+    count_tag: tag::NO_STRATEGY_PARAMETERS,
+    delimiter_tag: tag::STRATEGY_PARAMETER_NAME,
+    member_tags: &[
+        tag::STRATEGY_PARAMETER_NAME,
+        tag::STRATEGY_PARAMETER_TYPE,
+        tag::STRATEGY_PARAMETER_VALUE,
+    ],
+};
+
+/// NO_INSTRUMENT_PARTIES (1018) — InstrumentPartyID is the delimiter tag.
+pub const INSTRUMENT_PARTIES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_INSTRUMENT_PARTIES,
+    delimiter_tag: tag::INSTRUMENT_PARTY_ID,
+    member_tags: &[
+        tag::INSTRUMENT_PARTY_ID,
+        tag::INSTRUMENT_PARTY_ID_SOURCE,
+        tag::INSTRUMENT_PARTY_ROLE,
+    ],
+};
+
+/// NO_INSTRUMENT_PARTY_SUB_IDS (1052) — InstrumentPartySubID is the delimiter tag.
+pub const INSTRUMENT_PARTY_SUB_IDS: GroupSpec = GroupSpec {
+    count_tag: tag::NO_INSTRUMENT_PARTY_SUB_IDS,
+    delimiter_tag: tag::INSTRUMENT_PARTY_SUB_ID,
+    member_tags: &[
+        tag::INSTRUMENT_PARTY_SUB_ID,
+        tag::INSTRUMENT_PARTY_SUB_ID_TYPE,
+    ],
+};
+
+/// NO_UNDLY_INSTRUMENT_PARTIES (1058) — UndlyInstrumentPartyID is the delimiter tag.
+pub const UNDLY_INSTRUMENT_PARTIES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_UNDLY_INSTRUMENT_PARTIES,
+    delimiter_tag: tag::UNDLY_INSTRUMENT_PARTY_ID,
+    member_tags: &[
+        tag::UNDLY_INSTRUMENT_PARTY_ID,
+        tag::UNDLY_INSTRUMENT_PARTY_ID_SOURCE,
+        tag::UNDLY_INSTRUMENT_PARTY_ROLE,
+    ],
+};
+
+/// NO_UNDLY_INSTRUMENT_PARTY_SUB_IDS (1062) — UndlyInstrumentPartySubID is the delimiter tag.
+pub const UNDLY_INSTRUMENT_PARTY_SUB_IDS: GroupSpec = GroupSpec {
+    count_tag: tag::NO_UNDLY_INSTRUMENT_PARTY_SUB_IDS,
+    delimiter_tag: tag::UNDLY_INSTRUMENT_PARTY_SUB_ID,
+    member_tags: &[
+        tag::UNDLY_INSTRUMENT_PARTY_SUB_ID,
+        tag::UNDLY_INSTRUMENT_PARTY_SUB_ID_TYPE,
+    ],
+};
+
+/// NO_ROOT_PARTY_IDS (1116) — RootPartyID is the delimiter tag.
+pub const ROOT_PARTY_IDS: GroupSpec = GroupSpec {
+    count_tag: tag::NO_ROOT_PARTY_IDS,
+    delimiter_tag: tag::ROOT_PARTY_ID,
+    member_tags: &[
+        tag::ROOT_PARTY_ID,
+        tag::ROOT_PARTY_ID_SOURCE,
+        tag::ROOT_PARTY_ROLE,
+    ],
+};
+
+/// NO_ROOT_PARTY_SUB_IDS (1120) — RootPartySubID is the delimiter tag.
+pub const ROOT_PARTY_SUB_IDS: GroupSpec = GroupSpec {
+    count_tag: tag::NO_ROOT_PARTY_SUB_IDS,
+    delimiter_tag: tag::ROOT_PARTY_SUB_ID,
+    member_tags: &[tag::ROOT_PARTY_SUB_ID, tag::ROOT_PARTY_SUB_ID_TYPE],
+};
+
+/// NO_MD_FEED_TYPES (1141) — MDFeedType is the delimiter tag.
+pub const MD_FEED_TYPES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_MD_FEED_TYPES,
+    delimiter_tag: tag::MD_FEED_TYPE,
+    member_tags: &[tag::MD_FEED_TYPE, tag::MARKET_DEPTH, tag::MD_BOOK_TYPE],
+};
+
+/// NO_TICK_RULES (1205) — StartTickPriceRange is the delimiter tag.
+pub const TICK_RULES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_TICK_RULES,
+    delimiter_tag: tag::START_TICK_PRICE_RANGE,
+    member_tags: &[
+        tag::START_TICK_PRICE_RANGE,
+        tag::END_TICK_PRICE_RANGE,
+        tag::TICK_INCREMENT,
+        tag::TICK_RULE_TYPE,
+    ],
+};
+
+/// NO_EXEC_INST_RULES (1232) — ExecInstValue is the delimiter tag.
+pub const EXEC_INST_RULES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_EXEC_INST_RULES,
+    delimiter_tag: tag::EXEC_INST_VALUE,
+    member_tags: &[tag::EXEC_INST_VALUE],
+};
+
+/// NO_LOT_TYPE_RULES (1234) — LotType is the delimiter tag.
+pub const LOT_TYPE_RULES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_LOT_TYPE_RULES,
+    delimiter_tag: tag::LOT_TYPE,
+    member_tags: &[tag::LOT_TYPE, tag::MIN_LOT_SIZE],
+};
+
+/// NO_MATCH_RULES (1235) — MatchAlgorithm is the delimiter tag.
+pub const MATCH_RULES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_MATCH_RULES,
+    delimiter_tag: tag::MATCH_ALGORITHM,
+    member_tags: &[tag::MATCH_ALGORITHM, tag::MATCH_TYPE],
+};
+
+/// NO_ORD_TYPE_RULES (1237) — OrdType is the delimiter tag.
+pub const ORD_TYPE_RULES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_ORD_TYPE_RULES,
+    delimiter_tag: tag::ORD_TYPE,
+    member_tags: &[tag::ORD_TYPE],
+};
+
+/// NO_TIME_IN_FORCE_RULES (1239) — TimeInForce is the delimiter tag.
+pub const TIME_IN_FORCE_RULES: GroupSpec = GroupSpec {
+    count_tag: tag::NO_TIME_IN_FORCE_RULES,
+    delimiter_tag: tag::TIME_IN_FORCE,
+    member_tags: &[tag::TIME_IN_FORCE],
+};
+
+/// NO_APPL_IDS (1351) — RefApplID is the delimiter tag.
+pub const APPL_IDS: GroupSpec = GroupSpec {
+    count_tag: tag::NO_APPL_IDS,
+    delimiter_tag: tag::REF_APPL_ID,
+    member_tags: &[
+        tag::REF_APPL_ID,
+        tag::APPL_BEG_SEQ_NUM,
+        tag::APPL_END_SEQ_NUM,
+    ],
+};
+
+/// All built-in FIX 5.0 SP2 group specs (superset of `FIX44_GROUPS`).
+///
+/// Used for FIXT.1.1 sessions whose ApplVerID (1128) is FIX 5.0 or later,
+/// or absent.
+pub const FIX50SP2_GROUPS: &[&GroupSpec] = &[
+    // -- FIX 4.2 groups (inherited) --
+    &ALLOCS,
+    &ORDERS,
+    &RPTS,
+    &DLVY_INST,
+    &EXECS,
+    &MISC_FEES,
+    &RELATED_SYM,
+    &IOI_QUALIFIERS,
+    &ROUTING_IDS,
+    &MD_ENTRY_TYPES,
+    &MD_ENTRIES,
+    &QUOTE_ENTRIES,
+    &QUOTE_SETS,
+    &CONTRA_BROKERS,
+    &MSG_TYPES,
+    &TRADING_SESSIONS,
+    &BID_DESCRIPTORS,
+    &BID_COMPONENTS,
+    &STRIKES,
+    // -- FIX 4.4 groups (inherited) --
+    &PARTY_IDS,
+    &SECURITY_ALT_IDS,
+    &UNDERLYING_SECURITY_ALT_IDS,
+    &REGIST_DTLS,
+    &DISTRIB_INSTS,
+    &CONT_AMTS,
+    &NESTED_PARTY_IDS,
+    &SIDES,
+    &SECURITY_TYPES,
+    &AFFECTED_ORDERS,
+    &LEGS,
+    &UNDERLYINGS,
+    &POSITIONS,
+    &QUOTE_QUALIFIERS,
+    &POS_AMTS,
+    &NESTED2_PARTY_IDS,
+    &TRD_REG_TIMESTAMPS,
+    &SETTL_INST,
+    &SETTL_PARTY_IDS,
+    &PARTY_SUB_IDS,
+    &NESTED_PARTY_SUB_IDS,
+    &NESTED2_PARTY_SUB_IDS,
+    &ALT_MD_SOURCES,
+    &CAPACITIES,
+    &EVENTS,
+    &INSTR_ATTRIB,
+    &UNDERLYING_STIPS,
+    &TRADES,
+    &COMP_IDS,
+    &COLL_INQUIRY_QUALIFIERS,
+    &NESTED3_PARTY_IDS,
+    &LEG_SECURITY_ALT_IDS,
+    &LEG_STIPULATIONS,
+    &LEG_ALLOCS,
+    &HOPS,
+    &CLEARING_INSTRUCTIONS,
+    // -- FIX 5.0 SP2 additions --
+    &STRATEGY_PARAMETERS,
+    &INSTRUMENT_PARTIES,
+    &INSTRUMENT_PARTY_SUB_IDS,
+    &UNDLY_INSTRUMENT_PARTIES,
+    &UNDLY_INSTRUMENT_PARTY_SUB_IDS,
+    &ROOT_PARTY_IDS,
+    &ROOT_PARTY_SUB_IDS,
+    &MD_FEED_TYPES,
+    &TICK_RULES,
+    &EXEC_INST_RULES,
+    &LOT_TYPE_RULES,
+    &MATCH_RULES,
+    &ORD_TYPE_RULES,
+    &TIME_IN_FORCE_RULES,
+    &APPL_IDS,
+];
+
 /// All built-in FIX 4.4 group specs (superset of `FIX42_GROUPS`).
 ///
 /// Includes all FIX 4.2 groups plus the groups introduced in FIX 4.4,
@@ -952,6 +1309,90 @@ pub const FIX42_GROUPS: &[&GroupSpec] = &[
     &STRIKES,
 ];
 
+/// All built-in FIX 4.3 group specs (superset of `FIX42_GROUPS`).
+pub const FIX43_GROUPS: &[&GroupSpec] = &[
+    // -- FIX 4.2 groups (inherited) --
+    &ALLOCS,
+    &ORDERS,
+    &RPTS,
+    &DLVY_INST,
+    &EXECS,
+    &MISC_FEES,
+    &RELATED_SYM,
+    &IOI_QUALIFIERS,
+    &ROUTING_IDS,
+    &MD_ENTRY_TYPES,
+    &MD_ENTRIES,
+    &QUOTE_ENTRIES,
+    &QUOTE_SETS,
+    &CONTRA_BROKERS,
+    &MSG_TYPES,
+    &TRADING_SESSIONS,
+    &BID_DESCRIPTORS,
+    &BID_COMPONENTS,
+    &STRIKES,
+    // -- FIX 4.3 additions --
+    &PARTY_IDS,
+    &SECURITY_ALT_IDS,
+    &UNDERLYING_SECURITY_ALT_IDS,
+    &REGIST_DTLS,
+    &DISTRIB_INSTS,
+    &CONT_AMTS,
+    &NESTED_PARTY_IDS,
+    &SIDES,
+    &SECURITY_TYPES,
+    &AFFECTED_ORDERS,
+    &LEGS,
+    &LEG_SECURITY_ALT_IDS,
+    &HOPS,
+    &CLEARING_INSTRUCTIONS,
+];
+
+/// All built-in FIX 4.1 group specs.
+pub const FIX41_GROUPS: &[&GroupSpec] = &[
+    &ALLOCS,
+    &ORDERS,
+    &RPTS,
+    &DLVY_INST,
+    &EXECS,
+    &MISC_FEES,
+    &RELATED_SYM,
+    &IOI_QUALIFIERS,
+];
+
+/// All built-in FIX 4.0 group specs.
+pub const FIX40_GROUPS: &[&GroupSpec] = &[&ALLOCS, &ORDERS, &RPTS, &DLVY_INST, &EXECS, &MISC_FEES];
+
+/// The built-in group specs for a message with BeginString (8)
+/// `begin_string` and ApplVerID (1128) `appl_ver_id`.
+///
+/// - `FIX.4.0` to `FIX.4.4` → the matching `FIX4x_GROUPS`.
+/// - `FIXT.1.1` → by ApplVerID: `2` to `6` (FIX 4.0 to 4.4) → the matching
+///   `FIX4x_GROUPS`, `7` and later (FIX 5.0 onwards) or absent →
+///   `FIX50SP2_GROUPS`. Session messages carry no ApplVerID and fall on 5.0.
+/// - Anything else → `FIX42_GROUPS`.
+pub fn specs_for_version(
+    begin_string: Option<&[u8]>,
+    appl_ver_id: Option<&[u8]>,
+) -> &'static [&'static GroupSpec] {
+    match (begin_string, appl_ver_id) {
+        (Some(b"FIX.4.0"), _) => FIX40_GROUPS,
+        (Some(b"FIX.4.1"), _) => FIX41_GROUPS,
+        (Some(b"FIX.4.3"), _) => FIX43_GROUPS,
+        (Some(b"FIX.4.4"), _) => FIX44_GROUPS,
+        (Some(b"FIXT.1.1"), None) => FIX50SP2_GROUPS,
+        (Some(b"FIXT.1.1"), Some(v)) => match parse_count(v) {
+            2 => FIX40_GROUPS,
+            3 => FIX41_GROUPS,
+            5 => FIX43_GROUPS,
+            6 => FIX44_GROUPS,
+            n if n >= 7 => FIX50SP2_GROUPS,
+            _ => FIX42_GROUPS,
+        },
+        _ => FIX42_GROUPS,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Group and GroupIter
 // ---------------------------------------------------------------------------
@@ -967,6 +1408,14 @@ pub struct Group<'a> {
     pub(crate) offsets: &'a [(Tag, u32, u32)],
 }
 
+/// The instance's fields as `tag=value|…`, like
+/// [`Message`](crate::message::Message)'s `Display`; `{:#}` writes tag names.
+impl std::fmt::Display for Group<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        display_fields(self.fields(), f)
+    }
+}
+
 impl<'a> Group<'a> {
     /// Number of fields in this group instance.
     #[inline]
@@ -992,11 +1441,15 @@ impl<'a> Group<'a> {
 
     /// Iterates over all fields in this group instance.
     #[inline]
-    pub fn fields(&self) -> impl Iterator<Item = Field<'a>> + '_ {
-        self.offsets.iter().map(move |&(tag, start, end)| Field {
-            tag,
-            value: &self.buf[start as usize..end as usize],
-        })
+    pub fn fields(&self) -> Fields<'a> {
+        Fields::new(self.buf, self.offsets)
+    }
+
+    /// Every field with the given tag in this instance, in wire order —
+    /// including those of nested group instances.
+    #[inline]
+    pub fn find_all(&self, tag: Tag) -> impl Iterator<Item = Field<'a>> + use<'a> {
+        self.fields().filter(move |f| f.tag == tag)
     }
 
     /// Returns the first field with the given tag, or `None`.
@@ -1011,6 +1464,18 @@ impl<'a> Group<'a> {
             })
     }
 
+    /// The fields of this instance alone: the delimiter, its members and any
+    /// nested groups, stopping at the first tag that is not a member of
+    /// `spec`.
+    ///
+    /// The last instance yielded by [`Message::groups`] runs on to the end
+    /// of the message; use this to copy an instance without the fields that
+    /// follow the group.
+    pub fn members(&self, spec: &GroupSpec) -> Fields<'a> {
+        let end = instance_end(self.offsets, self.buf, 0, spec).min(self.offsets.len());
+        Fields::new(self.buf, &self.offsets[..end])
+    }
+
     /// Return an iterator over the instances of a repeating group nested inside
     /// this group instance. Mirrors [`Message::groups`] exactly.
     ///
@@ -1020,10 +1485,7 @@ impl<'a> Group<'a> {
     /// Returns an empty iterator if the nested count tag is absent or zero.
     #[inline]
     pub fn groups(&self, spec: &GroupSpec) -> GroupIter<'a> {
-        let pos = self
-            .offsets
-            .iter()
-            .position(|&(t, _, _)| t == spec.count_tag);
+        let pos = position_of(self.offsets, spec.count_tag);
 
         let (count, remaining) = match pos {
             None => (0, &[][..]),
@@ -1041,14 +1503,67 @@ impl<'a> Group<'a> {
             delimiter_tag: spec.delimiter_tag,
             count,
             emitted: 0,
+            ends: None,
         }
     }
+
+    /// View this group instance as a [`Message`] over the same buffer and
+    /// offsets — zero copy, no allocation.
+    ///
+    /// Lets code written against `Message` (validators, printers, typed
+    /// accessors) run unchanged on a single group instance, including
+    /// `find` with the cached sorted index and nested `groups`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for party in msg.groups(&group::PARTY_IDS) {
+    ///     validator.validate(&party.as_message_view(), &mut violations);
+    /// }
+    /// ```
+    #[inline]
+    pub fn as_message_view(&self) -> Message<'a> {
+        Message::new(self.buf, self.offsets)
+    }
+}
+
+impl<'a> IntoIterator for &Group<'a> {
+    type Item = Field<'a>;
+    type IntoIter = Fields<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Fields<'a> {
+        Fields::new(self.buf, self.offsets)
+    }
+}
+
+/// `group[i]` is the value of the field at position `i`, with the same
+/// out-of-bounds panic as slice indexing. Use [`Group::find`] to look a field
+/// up by tag.
+impl std::ops::Index<usize> for Group<'_> {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &[u8] {
+        let (_, start, end) = self.offsets[index];
+        &self.buf[start as usize..end as usize]
+    }
+}
+
+impl<'a> From<Group<'a>> for Message<'a> {
+    #[inline]
+    fn from(group: Group<'a>) -> Self {
+        group.as_message_view()
+    }
 }
 
 /// Iterator over the instances of one repeating group.
 ///
 /// Produced by [`Message::groups`]. Each call to `next` returns the next
 /// `Group` instance as a zero-copy view into the parent message.
+///
+/// The iterator ends after `count` instances or when the message runs out of
+/// fields, whichever comes first, and is fused: it never yields again after
+/// returning `None`.
 pub struct GroupIter<'a> {
     pub(crate) buf: &'a [u8],
     /// Remaining flat offsets starting just after the NO_* count tag.
@@ -1056,6 +1571,9 @@ pub struct GroupIter<'a> {
     pub(crate) delimiter_tag: Tag,
     pub(crate) count: usize,
     pub(crate) emitted: usize,
+    /// Instance ends relative to the first instance, when served from
+    /// [`Message::cache_groups`]; `None` to scan for delimiters.
+    pub(crate) ends: Option<Arc<[u32]>>,
 }
 
 impl<'a> Iterator for GroupIter<'a> {
@@ -1063,20 +1581,33 @@ impl<'a> Iterator for GroupIter<'a> {
 
     fn next(&mut self) -> Option<Group<'a>> {
         if self.emitted >= self.count || self.remaining.is_empty() {
+            // Park in the exhausted state so every later call also
+            // returns `None` (see the `FusedIterator` impl).
+            self.remaining = &[];
+            self.emitted = self.count;
             return None;
         }
 
         let start = self.remaining;
 
-        // Find the end of this instance: the next occurrence of the delimiter tag
-        // after the first field, or the end of remaining.
-        let end_offset = start
-            .iter()
-            .enumerate()
-            .skip(1) // skip the delimiter tag that begins this instance
-            .find(|&(_, &(t, _, _))| t == self.delimiter_tag)
-            .map(|(i, _)| i)
-            .unwrap_or(start.len());
+        // Find the end of this instance: the recorded boundary if cached,
+        // otherwise the next occurrence of the delimiter tag after the first
+        // field (the delimiter that begins this instance), or the end of
+        // remaining.
+        let end_offset = match &self.ends {
+            Some(ends) => {
+                let prev = self.emitted.checked_sub(1).map_or(0, |i| ends[i]);
+                match ends.get(self.emitted) {
+                    Some(&end) => (end - prev) as usize,
+                    None => {
+                        self.remaining = &[];
+                        self.emitted = self.count;
+                        return None;
+                    }
+                }
+            }
+            None => position_of(&start[1..], self.delimiter_tag).map_or(start.len(), |i| i + 1),
+        };
 
         let instance_offsets = &start[..end_offset];
         self.remaining = &start[end_offset..];
@@ -1089,15 +1620,48 @@ impl<'a> Iterator for GroupIter<'a> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining.is_empty() {
+            return (0, Some(0));
+        }
         let left = self.count.saturating_sub(self.emitted);
         (left, Some(left))
     }
 }
 
+/// Once `next` has returned `None` the iterator stays exhausted, even if the
+/// declared count was larger than the number of instances present.
+impl std::iter::FusedIterator for GroupIter<'_> {}
+
 // ---------------------------------------------------------------------------
 // Helpers used by message.rs
 // ---------------------------------------------------------------------------
 
+/// Index of the first field with `tag` in `offsets`.
+///
+/// Compares eight entries per step and branches once per chunk, so the
+/// loop streams through the offsets array in order (friendly to the hardware
+/// prefetcher) and vectorizes; on 500+ field messages this matters for the
+/// repeated count and delimiter searches done by group iteration.
+#[inline]
+pub(crate) fn position_of(offsets: &[(Tag, u32, u32)], tag: Tag) -> Option<usize> {
+    const CHUNK: usize = 8;
+    let mut chunks = offsets.chunks_exact(CHUNK);
+    for (n, chunk) in chunks.by_ref().enumerate() {
+        let mut mask = 0u32;
+        for (i, &(t, _, _)) in chunk.iter().enumerate() {
+            mask |= u32::from(t == tag) << i;
+        }
+        if mask != 0 {
+            return Some(n * CHUNK + mask.trailing_zeros() as usize);
+        }
+    }
+    let rest = chunks.remainder();
+    let base = offsets.len() - rest.len();
+    rest.iter()
+        .position(|&(t, _, _)| t == tag)
+        .map(|i| base + i)
+}
+
 /// Parse a decimal ASCII count value from raw bytes. Returns 0 on failure.
 pub(crate) fn parse_count(bytes: &[u8]) -> usize {
     let mut n: usize = 0;
@@ -1110,6 +1674,114 @@ pub(crate) fn parse_count(bytes: &[u8]) -> usize {
     n
 }
 
+/// The built-in spec whose count tag is `tag`, if any.
+pub(crate) fn spec_for_count(tag: Tag) -> Option<&'static GroupSpec> {
+    FIX50SP2_GROUPS.iter().copied().find(|s| s.count_tag == tag)
+}
+
+/// Index just past the group whose count field is at `count_index`.
+///
+/// Unlike [`GroupIter`], which lets the last instance run to the end of the
+/// message, an instance ends at the first tag that is not a member of
+/// `spec`. A member that is itself a built-in count tag spans its nested
+/// group.
+pub(crate) fn group_end(
+    offsets: &[(Tag, u32, u32)],
+    buf: &[u8],
+    count_index: usize,
+    spec: &GroupSpec,
+) -> usize {
+    let (_, start, end) = offsets[count_index];
+    let count = parse_count(&buf[start as usize..end as usize]);
+    let mut i = count_index + 1;
+    for _ in 0..count {
+        if offsets
+            .get(i)
+            .is_none_or(|&(t, _, _)| t != spec.delimiter_tag)
+        {
+            break;
+        }
+        i = instance_end(offsets, buf, i, spec);
+    }
+    i
+}
+
+/// Index just past the instance whose delimiter is at `start`; see
+/// [`group_end`].
+pub(crate) fn instance_end(
+    offsets: &[(Tag, u32, u32)],
+    buf: &[u8],
+    start: usize,
+    spec: &GroupSpec,
+) -> usize {
+    let mut i = start + 1;
+    while let Some(&(t, _, _)) = offsets.get(i) {
+        if t == spec.delimiter_tag || !spec.member_tags.contains(&t) {
+            break;
+        }
+        i = match spec_for_count(t) {
+            Some(nested) => group_end(offsets, buf, i, nested),
+            None => i + 1,
+        };
+    }
+    i
+}
+
+/// One top-level entry of a field run: a single field, or a built-in group
+/// (its count field and every instance) spanning `start..end`.
+#[derive(Clone, Copy)]
+pub(crate) struct Item {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) spec: Option<&'static GroupSpec>,
+}
+
+/// Walks `offsets` as [`Item`]s, recognising groups whose count field is
+/// directly followed by their delimiter.
+pub(crate) fn items<'a>(
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+) -> impl Iterator<Item = Item> + 'a {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let &(tag, _, _) = offsets.get(pos)?;
+        let spec = spec_for_count(tag).filter(|spec| {
+            offsets
+                .get(pos + 1)
+                .is_some_and(|&(t, _, _)| t == spec.delimiter_tag)
+        });
+        let end = match spec {
+            Some(spec) => group_end(offsets, buf, pos, spec),
+            None => pos + 1,
+        };
+        let item = Item {
+            start: pos,
+            end,
+            spec,
+        };
+        pos = end;
+        Some(item)
+    })
+}
+
+/// The offset ranges of each instance of the group `item`.
+pub(crate) fn instance_ranges<'a>(
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+    item: Item,
+) -> impl Iterator<Item = std::ops::Range<usize>> + 'a {
+    let mut pos = item.start + 1;
+    std::iter::from_fn(move || {
+        let spec = item.spec?;
+        if pos >= item.end {
+            return None;
+        }
+        let start = pos;
+        pos = instance_end(offsets, buf, start, spec);
+        Some(start..pos)
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1127,6 +1799,25 @@ mod tests {
             .collect()
     }
 
+    // -----------------------------------------------------------------------
+    // position_of
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn position_of_matches_linear_scan() {
+        let offsets: Vec<(Tag, u32, u32)> = (0..37).map(|i| (100 + i % 11, 0, 0)).collect();
+        for len in [0, 1, 7, 8, 9, 16, 37] {
+            let slice = &offsets[..len];
+            for tag in [100, 103, 107, 110, 999] {
+                assert_eq!(
+                    position_of(slice, tag),
+                    slice.iter().position(|&(t, _, _)| t == tag),
+                    "len {len} tag {tag}"
+                );
+            }
+        }
+    }
+
     // -----------------------------------------------------------------------
     // parse_count
     // -----------------------------------------------------------------------
@@ -1481,4 +2172,74 @@ mod tests {
         assert_eq!(nested.size_hint(), (0, Some(0)));
         assert!(nested.next().is_none());
     }
+
+    // -----------------------------------------------------------------------
+    // Group::as_message_view
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn group_as_message_view() {
+        let raw = fix("552=2|54=1|37=ORD1|518=1|519=1|520=10.00|54=2|37=ORD2|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let side = msg.groups(&SIDES).next().unwrap();
+        let view = side.as_message_view();
+        assert_eq!(view.len(), side.len());
+        assert_eq!(view.find(tag::ORDER_ID).unwrap().value, b"ORD1");
+        assert_eq!(view.field(0).tag, tag::SIDE);
+        let amt = view.groups(&CONT_AMTS).next().unwrap();
+        assert_eq!(amt.find(tag::CONT_AMT_VALUE).unwrap().value, b"10.00");
+
+        let second: Message<'_> = msg.groups(&SIDES).nth(1).unwrap().into();
+        assert_eq!(second.find(tag::ORDER_ID).unwrap().value, b"ORD2");
+    }
+
+    #[test]
+    fn for_loop_and_indexing_over_group() {
+        let raw = fix("552=1|54=1|37=ORD1|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let side = msg.groups(&SIDES).next().unwrap();
+
+        let values: Vec<&[u8]> = (&side).into_iter().map(|f| f.value).collect();
+        assert_eq!(values, vec![&b"1"[..], b"ORD1"]);
+        assert_eq!(&side[1], b"ORD1");
+    }
+
+    #[test]
+    fn group_iter_is_fused_when_count_exceeds_instances() {
+        // Declares 3 instances but only carries 1.
+        let raw = fix("552=3|54=1|37=ORD1|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut it = msg.groups(&SIDES);
+        assert!(it.next().is_some());
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert!(it.next().is_none());
+        assert!(it.next().is_none());
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        let _: &dyn std::iter::FusedIterator<Item = Group<'_>> = &it;
+    }
+
+    // -----------------------------------------------------------------------
+    // Group::members / Message::group_fields
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn members_and_group_fields_stop_at_group_end() {
+        let raw = fix("35=W|268=2|269=0|270=1.5|269=1|270=1.6|262=R1|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let last = msg.groups(&MD_ENTRIES).nth(1).unwrap();
+        assert_eq!(last.len(), 3);
+        let tags: Vec<Tag> = last.members(&MD_ENTRIES).map(|f| f.tag).collect();
+        assert_eq!(tags, [269, 270]);
+
+        let tags: Vec<Tag> = msg.group_fields(&MD_ENTRIES).map(|f| f.tag).collect();
+        assert_eq!(tags, [268, 269, 270, 269, 270]);
+        assert_eq!(msg.group_fields(&PARTY_IDS).count(), 0);
+    }
 }
@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use crate::message::Message;
+use crate::skew::drift_nanos;
+use crate::tag;
+use crate::timestamp::UtcTimestamp;
+
+/// Heartbeat timing and liveness counters for one session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeartbeatStats {
+    /// Heartbeats (35=0) measured for jitter. Heartbeats answering a
+    /// TestRequest (112 present) are not timed.
+    pub heartbeats: u64,
+    /// Smallest, largest and most recent jitter in nanoseconds: the gap
+    /// before a heartbeat minus the agreed interval. Negative when early.
+    pub min_jitter: i64,
+    pub max_jitter: i64,
+    pub last_jitter: i64,
+    /// Sum of every jitter, for [`mean_jitter`](Self::mean_jitter).
+    pub sum_jitter: i128,
+    /// Intervals that elapsed without any inbound message, over the
+    /// session's lifetime.
+    pub missed: u64,
+    /// Intervals missed since the last inbound message.
+    pub consecutive_missed: u32,
+}
+
+impl HeartbeatStats {
+    /// Mean jitter, or `None` before the first timed heartbeat.
+    pub fn mean_jitter(&self) -> Option<i64> {
+        (self.heartbeats > 0).then(|| (self.sum_jitter / self.heartbeats as i128) as i64)
+    }
+
+    fn record_jitter(&mut self, jitter: i64) {
+        if self.heartbeats == 0 {
+            self.min_jitter = jitter;
+            self.max_jitter = jitter;
+        } else {
+            self.min_jitter = self.min_jitter.min(jitter);
+            self.max_jitter = self.max_jitter.max(jitter);
+        }
+        self.heartbeats += 1;
+        self.last_jitter = jitter;
+        self.sum_jitter += jitter as i128;
+    }
+}
+
+/// Raised by a [`HeartbeatMonitor`] for each interval that passes with no
+/// inbound traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissedHeartbeat {
+    /// Intervals missed in a row, starting at 1.
+    pub consecutive: u32,
+    /// When the last inbound message arrived.
+    pub last_received: UtcTimestamp,
+    pub now: UtcTimestamp,
+}
+
+/// Tracks heartbeat jitter and missed intervals on the inbound side of a
+/// session, so a degrading connection shows up before the disconnect.
+///
+/// Any inbound message counts as a sign of life. Call
+/// [`observe`](Self::observe) for each one and [`poll`](Self::poll) from the
+/// session timer; `on_missed` is called once per silent interval. A Logon
+/// (35=A) carrying HeartBtInt (108) updates the interval.
+///
+/// # Example
+/// ```ignore
+/// let mut hb = HeartbeatMonitor::new(Duration::from_secs(30), |m: &MissedHeartbeat| {
+///     if m.consecutive == 1 { send_test_request(); }
+/// });
+/// hb.observe(&msg, UtcTimestamp::now());
+/// // on every timer tick:
+/// hb.poll(UtcTimestamp::now());
+/// metrics.gauge("hb_jitter_ns", hb.stats().last_jitter);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeartbeatMonitor<F> {
+    interval: Duration,
+    on_missed: F,
+    stats: HeartbeatStats,
+    last_received: Option<UtcTimestamp>,
+}
+
+impl<F: FnMut(&MissedHeartbeat)> HeartbeatMonitor<F> {
+    pub fn new(interval: Duration, on_missed: F) -> Self {
+        Self {
+            interval,
+            on_missed,
+            stats: HeartbeatStats::default(),
+            last_received: None,
+        }
+    }
+
+    /// The heartbeat interval currently expected.
+    #[inline]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    #[inline]
+    pub fn stats(&self) -> &HeartbeatStats {
+        &self.stats
+    }
+
+    /// When the last inbound message arrived, if any.
+    #[inline]
+    pub fn last_received(&self) -> Option<UtcTimestamp> {
+        self.last_received
+    }
+
+    /// Forget every observation, e.g. on reconnect. The interval is kept.
+    pub fn reset(&mut self) {
+        self.stats = HeartbeatStats::default();
+        self.last_received = None;
+    }
+
+    /// Record inbound `msg` received at `received_at`. Returns the jitter if
+    /// `msg` is a timed heartbeat.
+    pub fn observe(&mut self, msg: &Message<'_>, received_at: UtcTimestamp) -> Option<i64> {
+        let msg_type = msg.find(tag::MSG_TYPE).map(|f| f.value);
+        if msg_type == Some(b"A")
+            && let Some(secs) = msg.find(tag::HEART_BT_INT).and_then(|f| f.as_u64().ok())
+        {
+            self.interval = Duration::from_secs(secs);
+        }
+
+        let previous = self.last_received.replace(received_at);
+        self.stats.consecutive_missed = 0;
+
+        if msg_type != Some(b"0") || msg.find(tag::TEST_REQ_ID).is_some() {
+            return None;
+        }
+        let gap = drift_nanos(received_at, previous?);
+        let jitter = gap.saturating_sub(self.interval.as_nanos() as i64);
+        self.stats.record_jitter(jitter);
+        Some(jitter)
+    }
+
+    /// Check for silence at `now`, calling `on_missed` for every interval
+    /// that has elapsed since the last inbound message and was not yet
+    /// reported. Returns the number of consecutive missed intervals.
+    pub fn poll(&mut self, now: UtcTimestamp) -> u32 {
+        let Some(last) = self.last_received else {
+            return 0;
+        };
+        let interval = self.interval.as_nanos();
+        if interval == 0 || now <= last {
+            return self.stats.consecutive_missed;
+        }
+        let silent = now.abs_diff(&last).as_nanos();
+        let due = (silent / interval).min(u32::MAX as u128) as u32;
+        while self.stats.consecutive_missed < due {
+            self.stats.consecutive_missed += 1;
+            self.stats.missed += 1;
+            (self.on_missed)(&MissedHeartbeat {
+                consecutive: self.stats.consecutive_missed,
+                last_received: last,
+                now,
+            });
+        }
+        self.stats.consecutive_missed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    const T0: i64 = 1_709_214_306;
+
+    #[test]
+    fn heartbeat_jitter_is_measured_against_interval() {
+        let mut hb = HeartbeatMonitor::new(Duration::from_secs(10), |_: &MissedHeartbeat| {});
+        let mut dec = Decoder::new();
+        let logon = dec.decode(b"35=A\x01108=30\x01").unwrap();
+        assert_eq!(hb.observe(&logon, UtcTimestamp::new(T0, 0)), None);
+        assert_eq!(hb.interval(), Duration::from_secs(30));
+
+        let beat = dec.decode(b"35=0\x01").unwrap();
+        assert_eq!(
+            hb.observe(&beat, UtcTimestamp::new(T0 + 30, 250_000_000)),
+            Some(250_000_000)
+        );
+        assert_eq!(
+            hb.observe(&beat, UtcTimestamp::new(T0 + 59, 250_000_000)),
+            Some(-1_000_000_000)
+        );
+        let answer = dec.decode(b"35=0\x01112=T1\x01").unwrap();
+        assert_eq!(hb.observe(&answer, UtcTimestamp::new(T0 + 60, 0)), None);
+
+        let s = hb.stats();
+        assert_eq!(s.heartbeats, 2);
+        assert_eq!((s.min_jitter, s.max_jitter), (-1_000_000_000, 250_000_000));
+        assert_eq!(s.mean_jitter(), Some(-375_000_000));
+    }
+
+    #[test]
+    fn missed_intervals_are_reported_once_each() {
+        let mut missed = Vec::new();
+        let mut hb = HeartbeatMonitor::new(Duration::from_secs(30), |m: &MissedHeartbeat| {
+            missed.push(m.consecutive)
+        });
+        assert_eq!(hb.poll(UtcTimestamp::new(T0 + 100, 0)), 0);
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=D\x01").unwrap();
+        hb.observe(&msg, UtcTimestamp::new(T0, 0));
+        assert_eq!(hb.poll(UtcTimestamp::new(T0 + 29, 0)), 0);
+        assert_eq!(hb.poll(UtcTimestamp::new(T0 + 31, 0)), 1);
+        assert_eq!(hb.poll(UtcTimestamp::new(T0 + 45, 0)), 1);
+        assert_eq!(hb.poll(UtcTimestamp::new(T0 + 95, 0)), 3);
+
+        hb.observe(&msg, UtcTimestamp::new(T0 + 96, 0));
+        assert_eq!(hb.stats().consecutive_missed, 0);
+        assert_eq!(hb.poll(UtcTimestamp::new(T0 + 127, 0)), 1);
+        assert_eq!(hb.stats().missed, 4);
+        assert_eq!(missed, vec![1, 2, 3, 1]);
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use crate::error::FixError;
+use crate::group::{self, GroupIter};
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// Instrument fields kept by [`InstrumentCache`], in the order they are
+/// stored.
+const INSTRUMENT_TAGS: [Tag; 19] = [
+    tag::SYMBOL,
+    tag::SYMBOL_SFX,
+    tag::SECURITY_ID,
+    tag::SECURITY_ID_SOURCE,
+    tag::PRODUCT,
+    tag::CFI_CODE,
+    tag::SECURITY_TYPE,
+    tag::MATURITY_MONTH_YEAR,
+    tag::MATURITY_DATE,
+    tag::PUT_OR_CALL,
+    tag::STRIKE_PRICE,
+    tag::CONTRACT_MULTIPLIER,
+    tag::SECURITY_EXCHANGE,
+    tag::ISSUER,
+    tag::SECURITY_DESC,
+    tag::CURRENCY,
+    tag::ROUND_LOT,
+    tag::MIN_TRADE_VOL,
+    tag::TRADING_SESSION_ID,
+];
+
+/// One instrument's reference data, copied out of a SecurityList entry or a
+/// SecurityDefinition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instrument {
+    fields: Vec<(Tag, Vec<u8>)>,
+    alt_ids: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Instrument {
+    fn read<'a>(find: impl Fn(Tag) -> Option<&'a [u8]>, alt_ids: GroupIter<'a>) -> Self {
+        // FIX 4.2 SecurityList entries carry the symbol in RelatdSym (46).
+        let symbol = find(tag::SYMBOL).or_else(|| find(tag::RELATD_SYM));
+        let fields = INSTRUMENT_TAGS
+            .iter()
+            .filter_map(|&t| {
+                let v = if t == tag::SYMBOL { symbol } else { find(t) };
+                v.map(|v| (t, v.to_vec()))
+            })
+            .collect();
+        let alt_ids = alt_ids
+            .filter_map(|g| {
+                let id = g.find(tag::SECURITY_ALT_ID)?.value;
+                let source = g
+                    .find(tag::SECURITY_ALT_ID_SOURCE)
+                    .map_or(&[][..], |f| f.value);
+                Some((id.to_vec(), source.to_vec()))
+            })
+            .collect();
+        Self { fields, alt_ids }
+    }
+
+    /// The stored value of `tag`, for any of the instrument fields kept by
+    /// the cache (Symbol, SecurityID, SecurityType, MaturityMonthYear,
+    /// Currency, RoundLot, …).
+    pub fn get(&self, tag: Tag) -> Option<&[u8]> {
+        self.fields
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    #[inline]
+    pub fn symbol(&self) -> Option<&[u8]> {
+        self.get(tag::SYMBOL)
+    }
+
+    #[inline]
+    pub fn security_id(&self) -> Option<&[u8]> {
+        self.get(tag::SECURITY_ID)
+    }
+
+    #[inline]
+    pub fn security_id_source(&self) -> Option<&[u8]> {
+        self.get(tag::SECURITY_ID_SOURCE)
+    }
+
+    /// `(SecurityAltID, SecurityAltIDSource)` pairs (455/456).
+    pub fn alt_ids(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.alt_ids
+            .iter()
+            .map(|(id, source)| (id.as_slice(), source.as_slice()))
+    }
+
+    /// `(SecurityID, SecurityIDSource)` if both are present.
+    fn id_key(&self) -> Option<(Vec<u8>, Vec<u8>)> {
+        Some((
+            self.security_id()?.to_vec(),
+            self.security_id_source()?.to_vec(),
+        ))
+    }
+}
+
+/// Reference data for instruments, built from SecurityList (35=y) and
+/// SecurityDefinition (35=d) messages and looked up by Symbol,
+/// SecurityID + SecurityIDSource, or SecurityAltID + SecurityAltIDSource.
+///
+/// An incoming instrument replaces the cached one with the same SecurityID
+/// and source, or, if it has none, the same Symbol.
+///
+/// # Example
+/// ```ignore
+/// let mut instruments = InstrumentCache::new();
+/// for msg in reference_data {
+///     instruments.ingest(&msg)?;
+/// }
+/// let ins = instruments.by_security_id(b"US0378331005", b"4").unwrap();
+/// let lot = ins.get(tag::ROUND_LOT);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentCache {
+    instruments: Vec<Instrument>,
+    by_symbol: HashMap<Vec<u8>, usize>,
+    by_id: HashMap<(Vec<u8>, Vec<u8>), usize>,
+    by_alt_id: HashMap<(Vec<u8>, Vec<u8>), usize>,
+}
+
+impl InstrumentCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the instruments of a SecurityList or SecurityDefinition. Returns
+    /// how many were added or replaced.
+    ///
+    /// A SecurityList's NO_RELATED_SYM entries are read with
+    /// [`group::SECURITY_LIST_RELATED_SYM`], or [`group::RELATED_SYM`] when
+    /// the entries start with RelatdSym (46) as in FIX 4.2. A
+    /// SecurityDefinition that rejects the request (SecurityResponseType
+    /// (323) `5` or `6`) adds nothing.
+    ///
+    /// `InvalidValue` if `msg` is neither message type.
+    pub fn ingest(&mut self, msg: &Message<'_>) -> Result<usize, FixError> {
+        match msg.find(tag::MSG_TYPE).map(|f| f.value) {
+            Some(b"y") => {
+                let spec = if msg.find(tag::RELATD_SYM).is_some() {
+                    &group::RELATED_SYM
+                } else {
+                    &group::SECURITY_LIST_RELATED_SYM
+                };
+                let mut n = 0;
+                for entry in msg.groups(spec) {
+                    let ins = Instrument::read(
+                        |t| entry.find(t).map(|f| f.value),
+                        entry.groups(&group::SECURITY_ALT_IDS),
+                    );
+                    n += usize::from(self.insert(ins));
+                }
+                Ok(n)
+            }
+            Some(b"d") => {
+                if matches!(
+                    msg.find(tag::SECURITY_RESPONSE_TYPE).map(|f| f.value),
+                    Some(b"5" | b"6")
+                ) {
+                    return Ok(0);
+                }
+                let ins = Instrument::read(
+                    |t| msg.find(t).map(|f| f.value),
+                    msg.groups(&group::SECURITY_ALT_IDS),
+                );
+                Ok(usize::from(self.insert(ins)))
+            }
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+
+    /// Cache `ins`, replacing any instrument with the same identity.
+    /// Returns false (and stores nothing) if it has neither Symbol nor
+    /// SecurityID.
+    fn insert(&mut self, ins: Instrument) -> bool {
+        let existing = match ins.id_key() {
+            Some(key) => self.by_id.get(&key).copied(),
+            None => match ins.symbol() {
+                Some(symbol) => self.by_symbol.get(symbol).copied(),
+                None => return false,
+            },
+        };
+        let i = match existing {
+            Some(i) => {
+                let old = std::mem::replace(&mut self.instruments[i], ins);
+                self.unindex(&old, i);
+                i
+            }
+            None => {
+                self.instruments.push(ins);
+                self.instruments.len() - 1
+            }
+        };
+        let ins = &self.instruments[i];
+        if let Some(symbol) = ins.symbol() {
+            self.by_symbol.insert(symbol.to_vec(), i);
+        }
+        if let Some(key) = ins.id_key() {
+            self.by_id.insert(key, i);
+        }
+        for (id, source) in ins.alt_ids() {
+            self.by_alt_id.insert((id.to_vec(), source.to_vec()), i);
+        }
+        true
+    }
+
+    /// Drop index entries of `old` that still point at slot `i`.
+    fn unindex(&mut self, old: &Instrument, i: usize) {
+        if let Some(symbol) = old.symbol()
+            && self.by_symbol.get(symbol) == Some(&i)
+        {
+            self.by_symbol.remove(symbol);
+        }
+        if let Some(key) = old.id_key()
+            && self.by_id.get(&key) == Some(&i)
+        {
+            self.by_id.remove(&key);
+        }
+        for (id, source) in old.alt_ids() {
+            let key = (id.to_vec(), source.to_vec());
+            if self.by_alt_id.get(&key) == Some(&i) {
+                self.by_alt_id.remove(&key);
+            }
+        }
+    }
+
+    /// The instrument most recently cached with Symbol (55) `symbol`.
+    pub fn by_symbol(&self, symbol: &[u8]) -> Option<&Instrument> {
+        self.by_symbol.get(symbol).map(|&i| &self.instruments[i])
+    }
+
+    /// The instrument with SecurityID (48) `id` from SecurityIDSource (22)
+    /// `source`.
+    pub fn by_security_id(&self, id: &[u8], source: &[u8]) -> Option<&Instrument> {
+        self.by_id
+            .get(&(id.to_vec(), source.to_vec()))
+            .map(|&i| &self.instruments[i])
+    }
+
+    /// The instrument with SecurityAltID (455) `id` from
+    /// SecurityAltIDSource (456) `source`.
+    pub fn by_alt_id(&self, id: &[u8], source: &[u8]) -> Option<&Instrument> {
+        self.by_alt_id
+            .get(&(id.to_vec(), source.to_vec()))
+            .map(|&i| &self.instruments[i])
+    }
+
+    /// Cached instruments, in first-seen order.
+    pub fn iter(&self) -> impl Iterator<Item = &Instrument> {
+        self.instruments.iter()
+    }
+
+    /// Number of cached instruments.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.instruments.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.instruments.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.instruments.clear();
+        self.by_symbol.clear();
+        self.by_id.clear();
+        self.by_alt_id.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+
+    #[test]
+    fn security_list_entries_are_indexed() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"y")
+            .push(tag::SECURITY_REQ_ID, b"R1");
+        let mut g = b.begin_group(&group::SECURITY_LIST_RELATED_SYM);
+        g.instance()
+            .push(tag::SYMBOL, b"AAPL")
+            .push(tag::SECURITY_ID, b"US0378331005")
+            .push(tag::SECURITY_ID_SOURCE, b"4")
+            .push(tag::ROUND_LOT, b"100");
+        let mut alt = g.begin_group(&group::SECURITY_ALT_IDS);
+        alt.instance()
+            .push(tag::SECURITY_ALT_ID, b"2046251")
+            .push(tag::SECURITY_ALT_ID_SOURCE, b"2");
+        drop(alt);
+        g.instance()
+            .push(tag::SYMBOL, b"MSFT")
+            .push(tag::CURRENCY, b"USD");
+        drop(g);
+
+        let mut cache = InstrumentCache::new();
+        assert_eq!(cache.ingest(&b.as_message()).unwrap(), 2);
+        let aapl = cache.by_security_id(b"US0378331005", b"4").unwrap();
+        assert_eq!(aapl.symbol(), Some(&b"AAPL"[..]));
+        assert_eq!(aapl.get(tag::ROUND_LOT), Some(&b"100"[..]));
+        assert_eq!(cache.by_alt_id(b"2046251", b"2"), Some(aapl));
+        assert_eq!(
+            cache.by_symbol(b"MSFT").unwrap().get(tag::CURRENCY),
+            Some(&b"USD"[..])
+        );
+        assert_eq!(cache.by_alt_id(b"2046251", b"4"), None);
+    }
+
+    #[test]
+    fn definition_replaces_by_security_id() {
+        let def = |symbol: &[u8], resp_type: &[u8]| {
+            let mut b = MessageBuilder::new();
+            b.push(tag::MSG_TYPE, b"d")
+                .push(tag::SECURITY_RESPONSE_TYPE, resp_type)
+                .push(tag::SYMBOL, symbol)
+                .push(tag::SECURITY_ID, b"123")
+                .push(tag::SECURITY_ID_SOURCE, b"8");
+            b
+        };
+        let mut cache = InstrumentCache::new();
+        assert_eq!(cache.ingest(&def(b"ESZ6", b"1").as_message()).unwrap(), 1);
+        assert_eq!(
+            cache.ingest(&def(b"ES DEC26", b"1").as_message()).unwrap(),
+            1
+        );
+        assert_eq!(cache.len(), 1);
+        assert!(cache.by_symbol(b"ESZ6").is_none());
+        assert!(cache.by_symbol(b"ES DEC26").is_some());
+        assert_eq!(cache.ingest(&def(b"X", b"5").as_message()).unwrap(), 0);
+
+        let mut other = MessageBuilder::new();
+        other.push(tag::MSG_TYPE, b"D");
+        assert!(matches!(
+            cache.ingest(&other.as_message()),
+            Err(FixError::InvalidValue)
+        ));
+    }
+
+    #[test]
+    fn fix42_security_list_uses_relatd_sym() {
+        let raw = b"8=FIX.4.2\x019=40\x0135=y\x01146=2\x0146=IBM\x0115=USD\x0146=HPQ\x0110=000\x01";
+        let mut dec = crate::decoder::Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut cache = InstrumentCache::new();
+        assert_eq!(cache.ingest(&msg).unwrap(), 2);
+        assert!(cache.by_symbol(b"HPQ").is_some());
+        assert_eq!(
+            cache.by_symbol(b"IBM").unwrap().get(tag::CURRENCY),
+            Some(&b"USD"[..])
+        );
+    }
+}
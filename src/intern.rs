@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use crate::message::Message;
+use crate::tag::Tag;
+
+/// A small integer standing for one distinct interned value.
+///
+/// Symbols are assigned densely from 0 in first-seen order, so they can index
+/// side tables directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    #[inline]
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    pub fn as_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Maps repeated field values to [`Symbol`]s for batch processing.
+///
+/// Symbols, CompIDs, currencies and similar values repeat millions of times
+/// in a capture; storing a `Symbol` per occurrence instead of a byte vector
+/// keeps extracted columns and indexes compact, and makes comparisons and
+/// grouping integer operations. Each distinct value is copied once.
+///
+/// # Example
+/// ```ignore
+/// let mut symbols = Interner::new();
+/// let mut column = Vec::new();
+/// for record in reader.records() {
+///     let msg = dec.decode(record?.raw)?;
+///     column.push(symbols.intern_field(&msg, tag::SYMBOL));
+/// }
+/// for (sym, n) in counts(&column) {
+///     println!("{} {n}", String::from_utf8_lossy(symbols.resolve(sym)));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    map: HashMap<Box<[u8]>, Symbol>,
+    /// Distinct values back to back; `spans[sym]` is each one's range.
+    bytes: Vec<u8>,
+    spans: Vec<(u32, u32)>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The symbol for `value`, assigning the next one if it is new.
+    ///
+    /// # Panics
+    /// If more than `u32::MAX` distinct values are interned.
+    pub fn intern(&mut self, value: &[u8]) -> Symbol {
+        if let Some(&sym) = self.map.get(value) {
+            return sym;
+        }
+        let sym = Symbol(u32::try_from(self.spans.len()).expect("interner symbol overflow"));
+        let start = self.bytes.len() as u32;
+        self.bytes.extend_from_slice(value);
+        self.spans.push((start, self.bytes.len() as u32));
+        self.map.insert(value.into(), sym);
+        sym
+    }
+
+    /// Intern the value of the first `tag` field in `msg`; `None` if absent.
+    pub fn intern_field(&mut self, msg: &Message<'_>, tag: Tag) -> Option<Symbol> {
+        msg.find(tag).map(|f| self.intern(f.value))
+    }
+
+    /// The symbol already assigned to `value`, without interning it.
+    pub fn get(&self, value: &[u8]) -> Option<Symbol> {
+        self.map.get(value).copied()
+    }
+
+    /// The value `sym` stands for.
+    ///
+    /// # Panics
+    /// If `sym` was not returned by this interner.
+    pub fn resolve(&self, sym: Symbol) -> &[u8] {
+        let (start, end) = self.spans[sym.as_usize()];
+        &self.bytes[start as usize..end as usize]
+    }
+
+    /// Number of distinct values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Distinct values with their symbols, in symbol order.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &[u8])> {
+        (0..self.spans.len() as u32).map(|i| (Symbol(i), self.resolve(Symbol(i))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+    use crate::tag;
+
+    #[test]
+    fn intern_is_dense_and_stable() {
+        let mut i = Interner::new();
+        let a = i.intern(b"AAPL");
+        let b = i.intern(b"MSFT");
+        assert_eq!(i.intern(b"AAPL"), a);
+        assert_eq!((a.as_u32(), b.as_u32()), (0, 1));
+        assert_eq!(i.resolve(b), b"MSFT");
+        assert_eq!(i.get(b"IBM"), None);
+        assert_eq!(i.len(), 2);
+        let empty = i.intern(b"");
+        assert_eq!(i.resolve(empty), b"");
+        assert_eq!(
+            i.iter().map(|(_, v)| v).collect::<Vec<_>>(),
+            [&b"AAPL"[..], b"MSFT", b""]
+        );
+    }
+
+    #[test]
+    fn intern_field_from_message() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"D").push(tag::SYMBOL, b"AAPL");
+        let mut i = Interner::new();
+        let sym = i.intern_field(&b.as_message(), tag::SYMBOL).unwrap();
+        assert_eq!(i.resolve(sym), b"AAPL");
+        assert_eq!(i.intern_field(&b.as_message(), tag::CURRENCY), None);
+    }
+}
@@ -0,0 +1,491 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::group;
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// IOITransType (28) and AdvTransType (5) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransType {
+    New,
+    Cancel,
+    Replace,
+}
+
+impl TransType {
+    /// The character carried in tag 28 or 5.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::New => b'N',
+            Self::Cancel => b'C',
+            Self::Replace => b'R',
+        }
+    }
+
+    /// The type for a tag 28 or 5 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"N" => Some(Self::New),
+            b"C" => Some(Self::Cancel),
+            b"R" => Some(Self::Replace),
+            _ => None,
+        }
+    }
+}
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+fn expect_type(msg: &Message<'_>, msg_type: &[u8]) -> Result<(), FixError> {
+    match value(msg, tag::MSG_TYPE) {
+        Some(t) if t == msg_type => Ok(()),
+        _ => Err(FixError::InvalidValue),
+    }
+}
+
+/// Read access to an IOI (35=6).
+///
+/// # Example
+/// ```ignore
+/// let ioi = Ioi::new(&msg)?;
+/// if ioi.trans_type() == Some(TransType::New) && ioi.qualifiers().any(|q| q == b"A") {
+///     book.add(ioi.ioi_id(), ioi.symbol(), ioi.side(), ioi.qty());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Ioi<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> Ioi<'a> {
+    /// `InvalidValue` if `msg` is not an IOI.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"6")?;
+        Ok(Self { msg })
+    }
+
+    /// IOIID (23).
+    pub fn ioi_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::IOI_ID)
+    }
+
+    /// IOITransType (28), `None` if absent or not a known type.
+    pub fn trans_type(&self) -> Option<TransType> {
+        value(self.msg, tag::IOI_TRANS_TYPE).and_then(TransType::from_code)
+    }
+
+    /// IOIRefID (26): the IOI cancelled or replaced.
+    pub fn ref_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::IOI_REF_ID)
+    }
+
+    pub fn symbol(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SYMBOL)
+    }
+
+    pub fn side(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SIDE)
+    }
+
+    /// IOIQty (27): a quantity, or `S`/`M`/`L` for small/medium/large.
+    pub fn qty(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::IOI_QTY)
+    }
+
+    pub fn price(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::PRICE)
+    }
+
+    /// IOIQltyInd (25): `L`, `M` or `H`.
+    pub fn quality(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::IOI_QLTY_IND)
+    }
+
+    /// IOINaturalFlag (130) is `Y`.
+    pub fn is_natural(&self) -> bool {
+        value(self.msg, tag::IOI_NATURAL_FLAG) == Some(b"Y")
+    }
+
+    pub fn valid_until(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::VALID_UNTIL_TIME)
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// IOIQualifier (104) of every `NO_IOI_QUALIFIERS` instance.
+    pub fn qualifiers(&self) -> impl Iterator<Item = &'a [u8]> + use<'a> {
+        self.msg
+            .groups(&group::IOI_QUALIFIERS)
+            .filter_map(|g| g.find(tag::IOI_QUALIFIER).map(|f| f.value))
+    }
+
+    /// `(RoutingType (216), RoutingID (217))` of every `NO_ROUTING_IDS`
+    /// instance.
+    pub fn routing_ids(&self) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + use<'a> {
+        self.msg.groups(&group::ROUTING_IDS).map(|g| {
+            (
+                g.find(tag::ROUTING_TYPE).map_or(&[][..], |f| f.value),
+                g.find(tag::ROUTING_ID).map_or(&[][..], |f| f.value),
+            )
+        })
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of an IOI (35=6).
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// IoiBuilder::new(b"IOI7", TransType::New, b"IBM", b"1", b"L")
+///     .price(b"150.25")
+///     .qualifier(b"A")
+///     .routing_id(b"3", b"BROKER_LIST_1")
+///     .write_to(&mut b);
+/// enc.encode(&b.as_message(), &mut out)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct IoiBuilder<'a> {
+    ioi_id: &'a [u8],
+    trans_type: TransType,
+    symbol: &'a [u8],
+    side: &'a [u8],
+    qty: &'a [u8],
+    ref_id: Option<&'a [u8]>,
+    price: Option<&'a [u8]>,
+    quality: Option<&'a [u8]>,
+    natural: bool,
+    valid_until: Option<&'a [u8]>,
+    text: Option<&'a [u8]>,
+    qualifiers: Vec<&'a [u8]>,
+    routing_ids: Vec<(&'a [u8], &'a [u8])>,
+}
+
+impl<'a> IoiBuilder<'a> {
+    pub fn new(
+        ioi_id: &'a [u8],
+        trans_type: TransType,
+        symbol: &'a [u8],
+        side: &'a [u8],
+        qty: &'a [u8],
+    ) -> Self {
+        Self {
+            ioi_id,
+            trans_type,
+            symbol,
+            side,
+            qty,
+            ref_id: None,
+            price: None,
+            quality: None,
+            natural: false,
+            valid_until: None,
+            text: None,
+            qualifiers: Vec::new(),
+            routing_ids: Vec::new(),
+        }
+    }
+
+    /// IOIRefID (26), for a cancel or replace.
+    pub fn ref_id(mut self, ref_id: &'a [u8]) -> Self {
+        self.ref_id = Some(ref_id);
+        self
+    }
+
+    pub fn price(mut self, price: &'a [u8]) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn quality(mut self, quality: &'a [u8]) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// Set IOINaturalFlag (130) to `Y`.
+    pub fn natural(mut self) -> Self {
+        self.natural = true;
+        self
+    }
+
+    pub fn valid_until(mut self, time: &'a [u8]) -> Self {
+        self.valid_until = Some(time);
+        self
+    }
+
+    pub fn text(mut self, text: &'a [u8]) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Add a `NO_IOI_QUALIFIERS` instance.
+    pub fn qualifier(mut self, qualifier: &'a [u8]) -> Self {
+        self.qualifiers.push(qualifier);
+        self
+    }
+
+    /// Add a `NO_ROUTING_IDS` instance.
+    pub fn routing_id(mut self, routing_type: &'a [u8], routing_id: &'a [u8]) -> Self {
+        self.routing_ids.push((routing_type, routing_id));
+        self
+    }
+
+    /// Append MsgType `6` and the IOI fields to `b`. Header fields other
+    /// than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"6")
+            .push(tag::IOI_ID, self.ioi_id)
+            .push(tag::IOI_TRANS_TYPE, &[self.trans_type.code()]);
+        if let Some(v) = self.ref_id {
+            b.push(tag::IOI_REF_ID, v);
+        }
+        b.push(tag::SYMBOL, self.symbol)
+            .push(tag::SIDE, self.side)
+            .push(tag::IOI_QTY, self.qty);
+        if let Some(v) = self.price {
+            b.push(tag::PRICE, v);
+        }
+        if let Some(v) = self.valid_until {
+            b.push(tag::VALID_UNTIL_TIME, v);
+        }
+        if let Some(v) = self.quality {
+            b.push(tag::IOI_QLTY_IND, v);
+        }
+        if self.natural {
+            b.push(tag::IOI_NATURAL_FLAG, b"Y");
+        }
+        let mut g = b.begin_group(&group::IOI_QUALIFIERS);
+        for q in &self.qualifiers {
+            g.instance().push(tag::IOI_QUALIFIER, q);
+        }
+        drop(g);
+        if let Some(v) = self.text {
+            b.push(tag::TEXT, v);
+        }
+        let mut g = b.begin_group(&group::ROUTING_IDS);
+        for (routing_type, id) in &self.routing_ids {
+            g.instance()
+                .push(tag::ROUTING_TYPE, routing_type)
+                .push(tag::ROUTING_ID, id);
+        }
+    }
+}
+
+/// Read access to an Advertisement (35=7).
+#[derive(Debug, Clone, Copy)]
+pub struct Advertisement<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> Advertisement<'a> {
+    /// `InvalidValue` if `msg` is not an Advertisement.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"7")?;
+        Ok(Self { msg })
+    }
+
+    /// AdvId (2).
+    pub fn adv_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ADV_ID)
+    }
+
+    /// AdvTransType (5), `None` if absent or not a known type.
+    pub fn trans_type(&self) -> Option<TransType> {
+        value(self.msg, tag::ADV_TRANS_TYPE).and_then(TransType::from_code)
+    }
+
+    /// AdvRefID (3): the advertisement cancelled or replaced.
+    pub fn ref_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ADV_REF_ID)
+    }
+
+    pub fn symbol(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SYMBOL)
+    }
+
+    /// AdvSide (4): `B`uy, `S`ell, `X` cross or `T`rade.
+    pub fn side(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ADV_SIDE)
+    }
+
+    /// Quantity (53).
+    pub fn quantity(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::QUANTITY)
+    }
+
+    pub fn price(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::PRICE)
+    }
+
+    pub fn currency(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CURRENCY)
+    }
+
+    pub fn transact_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRANSACT_TIME)
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of an Advertisement (35=7).
+#[derive(Debug, Clone)]
+pub struct AdvertisementBuilder<'a> {
+    adv_id: &'a [u8],
+    trans_type: TransType,
+    symbol: &'a [u8],
+    side: &'a [u8],
+    quantity: &'a [u8],
+    ref_id: Option<&'a [u8]>,
+    price: Option<&'a [u8]>,
+    currency: Option<&'a [u8]>,
+    transact_time: Option<&'a [u8]>,
+    text: Option<&'a [u8]>,
+}
+
+impl<'a> AdvertisementBuilder<'a> {
+    pub fn new(
+        adv_id: &'a [u8],
+        trans_type: TransType,
+        symbol: &'a [u8],
+        side: &'a [u8],
+        quantity: &'a [u8],
+    ) -> Self {
+        Self {
+            adv_id,
+            trans_type,
+            symbol,
+            side,
+            quantity,
+            ref_id: None,
+            price: None,
+            currency: None,
+            transact_time: None,
+            text: None,
+        }
+    }
+
+    /// AdvRefID (3), for a cancel or replace.
+    pub fn ref_id(mut self, ref_id: &'a [u8]) -> Self {
+        self.ref_id = Some(ref_id);
+        self
+    }
+
+    pub fn price(mut self, price: &'a [u8]) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn currency(mut self, currency: &'a [u8]) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    pub fn transact_time(mut self, time: &'a [u8]) -> Self {
+        self.transact_time = Some(time);
+        self
+    }
+
+    pub fn text(mut self, text: &'a [u8]) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Append MsgType `7` and the advertisement fields to `b`. Header
+    /// fields other than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"7")
+            .push(tag::ADV_ID, self.adv_id)
+            .push(tag::ADV_TRANS_TYPE, &[self.trans_type.code()]);
+        if let Some(v) = self.ref_id {
+            b.push(tag::ADV_REF_ID, v);
+        }
+        b.push(tag::SYMBOL, self.symbol)
+            .push(tag::ADV_SIDE, self.side)
+            .push(tag::QUANTITY, self.quantity);
+        if let Some(v) = self.price {
+            b.push(tag::PRICE, v);
+        }
+        if let Some(v) = self.currency {
+            b.push(tag::CURRENCY, v);
+        }
+        if let Some(v) = self.transact_time {
+            b.push(tag::TRANSACT_TIME, v);
+        }
+        if let Some(v) = self.text {
+            b.push(tag::TEXT, v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn ioi_round_trip() {
+        let mut b = MessageBuilder::new();
+        IoiBuilder::new(b"IOI7", TransType::Replace, b"IBM", b"1", b"L")
+            .ref_id(b"IOI6")
+            .price(b"150.25")
+            .natural()
+            .qualifier(b"A")
+            .qualifier(b"C")
+            .routing_id(b"3", b"LIST1")
+            .write_to(&mut b);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let ioi = Ioi::new(&msg).unwrap();
+        assert_eq!(ioi.ioi_id(), Some(&b"IOI7"[..]));
+        assert_eq!(ioi.trans_type(), Some(TransType::Replace));
+        assert_eq!(ioi.ref_id(), Some(&b"IOI6"[..]));
+        assert_eq!(ioi.qty(), Some(&b"L"[..]));
+        assert!(ioi.is_natural());
+        assert_eq!(ioi.qualifiers().collect::<Vec<_>>(), [b"A", b"C"]);
+        assert_eq!(
+            ioi.routing_ids().collect::<Vec<_>>(),
+            [(&b"3"[..], &b"LIST1"[..])]
+        );
+        assert!(Advertisement::new(&msg).is_err());
+    }
+
+    #[test]
+    fn advertisement_round_trip() {
+        let mut b = MessageBuilder::new();
+        AdvertisementBuilder::new(b"ADV1", TransType::New, b"MSFT", b"B", b"5000")
+            .price(b"410.5")
+            .currency(b"USD")
+            .write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 2, 5, 55, 4, 53, 44, 15]);
+
+        let msg = b.as_message();
+        let adv = Advertisement::new(&msg).unwrap();
+        assert_eq!(adv.trans_type(), Some(TransType::New));
+        assert_eq!(adv.side(), Some(&b"B"[..]));
+        assert_eq!(adv.quantity(), Some(&b"5000"[..]));
+        assert_eq!(adv.ref_id(), None);
+        assert!(Ioi::new(&msg).is_err());
+    }
+}
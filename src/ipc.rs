@@ -0,0 +1,225 @@
+use smallvec::SmallVec;
+
+use crate::error::FixError;
+use crate::message::Message;
+use crate::tag::Tag;
+
+/// Magic bytes at the start of every IPC frame.
+pub const IPC_MAGIC: [u8; 4] = *b"FXIP";
+/// Current frame format version. Readers reject frames with any other version.
+pub const IPC_VERSION: u16 = 1;
+/// Size of the fixed frame header in bytes.
+pub const IPC_HEADER_LEN: usize = 16;
+/// Size of one serialized `(tag, start, end)` offset entry in bytes.
+const OFFSET_ENTRY_LEN: usize = 12;
+
+/// Default inline capacity for the reader's offset index — matches the decoder.
+const DEFAULT_CAPACITY: usize = 32;
+
+/// Number of bytes [`write_frame`] produces for `msg`.
+#[inline]
+pub fn frame_len(msg: &Message<'_>) -> usize {
+    IPC_HEADER_LEN + msg.offsets.len() * OFFSET_ENTRY_LEN + msg.buf.len()
+}
+
+/// Append `msg` to `out` as one self-describing IPC frame.
+///
+/// Layout (all integers little-endian):
+///
+/// ```text
+/// 0   magic        "FXIP"
+/// 4   version      u16 (= IPC_VERSION)
+/// 6   reserved     u16 (= 0)
+/// 8   field_count  u32
+/// 12  buf_len      u32
+/// 16  offsets      field_count × (tag u32, start u32, end u32)
+/// ..  buf          buf_len raw message bytes
+/// ```
+///
+/// The offsets are the decoder's own index, so a consumer gets the message
+/// back with [`IpcReader::read`] without re-parsing the FIX bytes.
+pub fn write_frame(msg: &Message<'_>, out: &mut Vec<u8>) {
+    out.reserve(frame_len(msg));
+    out.extend_from_slice(&IPC_MAGIC);
+    out.extend_from_slice(&IPC_VERSION.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&(msg.offsets.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(msg.buf.len() as u32).to_le_bytes());
+    for &(tag, start, end) in msg.offsets {
+        out.extend_from_slice(&tag.to_le_bytes());
+        out.extend_from_slice(&start.to_le_bytes());
+        out.extend_from_slice(&end.to_le_bytes());
+    }
+    out.extend_from_slice(msg.buf);
+}
+
+/// Total size of the frame starting at `bytes`, read from its header.
+///
+/// Lets a transport find frame boundaries in a byte stream or ring buffer.
+/// Returns `IncompleteMessage` if fewer than [`IPC_HEADER_LEN`] bytes are
+/// available and `DecodeError` if the header is not a valid frame header.
+pub fn peek_frame_len(bytes: &[u8]) -> Result<usize, FixError> {
+    let (count, buf_len) = read_header(bytes)?;
+    Ok(IPC_HEADER_LEN + count * OFFSET_ENTRY_LEN + buf_len)
+}
+
+fn read_header(bytes: &[u8]) -> Result<(usize, usize), FixError> {
+    if bytes.len() < IPC_HEADER_LEN {
+        return Err(FixError::IncompleteMessage { offset: 0 });
+    }
+    if bytes[0..4] != IPC_MAGIC || read_u16(bytes, 4) != IPC_VERSION {
+        return Err(FixError::DecodeError);
+    }
+    Ok((read_u32(bytes, 8) as usize, read_u32(bytes, 12) as usize))
+}
+
+#[inline]
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+/// Reconstitutes messages from IPC frames written by [`write_frame`].
+///
+/// The message bytes are borrowed straight from the frame (e.g. a shared
+/// memory mapping) — no copy, no FIX re-parse. Only the offset index is
+/// copied into an internal buffer, which is reused across calls like the
+/// decoder's, so steady-state reads do not allocate.
+///
+/// Every offset is bounds-checked against the frame before the message is
+/// returned, so a corrupt or truncated frame yields an error rather than a
+/// panic on field access.
+///
+/// # Example
+/// ```ignore
+/// // producer
+/// let msg = decoder.decode(&wire)?;
+/// ipc::write_frame(&msg, &mut shm_slot);
+///
+/// // consumer
+/// let mut reader = IpcReader::new();
+/// let msg = reader.read(&shm_slot)?;
+/// let px = msg.find(tag::MD_ENTRY_PX);
+/// ```
+#[derive(Debug, Default)]
+pub struct IpcReader {
+    offsets: SmallVec<[(Tag, u32, u32); DEFAULT_CAPACITY]>,
+}
+
+impl IpcReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read one frame. `frame` may be longer than the frame itself; trailing
+    /// bytes are ignored (use [`peek_frame_len`] to step to the next frame).
+    pub fn read<'a>(&'a mut self, frame: &'a [u8]) -> Result<Message<'a>, FixError> {
+        let (count, buf_len) = read_header(frame)?;
+        let offsets_end = count
+            .checked_mul(OFFSET_ENTRY_LEN)
+            .and_then(|n| n.checked_add(IPC_HEADER_LEN))
+            .ok_or(FixError::DecodeError)?;
+        let buf_end = offsets_end
+            .checked_add(buf_len)
+            .ok_or(FixError::DecodeError)?;
+        if frame.len() < buf_end {
+            return Err(FixError::IncompleteMessage { offset: 0 });
+        }
+
+        self.offsets.clear();
+        for entry in frame[IPC_HEADER_LEN..offsets_end].chunks_exact(OFFSET_ENTRY_LEN) {
+            let (tag, start, end) = (read_u32(entry, 0), read_u32(entry, 4), read_u32(entry, 8));
+            if start > end || end as usize > buf_len {
+                return Err(FixError::DecodeError);
+            }
+            self.offsets.push((tag, start, end));
+        }
+
+        Ok(Message::new(&frame[offsets_end..buf_end], &self.offsets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::group::MD_ENTRIES;
+    use crate::tag;
+
+    const RAW: &[u8] = b"8=FIX.4.4\x019=40\x0135=W\x0155=AAPL\x01268=2\x01269=0\x01270=1.5\x01269=1\x01270=1.6\x0110=000\x01";
+
+    #[test]
+    fn round_trip_preserves_fields_and_groups() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(RAW).unwrap();
+        let mut frame = Vec::new();
+        write_frame(&msg, &mut frame);
+        assert_eq!(frame.len(), frame_len(&msg));
+        assert_eq!(peek_frame_len(&frame).unwrap(), frame.len());
+
+        let mut reader = IpcReader::new();
+        let back = reader.read(&frame).unwrap();
+        assert_eq!(back.len(), msg.len());
+        assert!(back.fields().eq(msg.fields()));
+        assert_eq!(back.find(tag::SYMBOL).unwrap().value, b"AAPL");
+        let px: Vec<_> = back
+            .groups(&MD_ENTRIES)
+            .map(|e| e.find(tag::MD_ENTRY_PX).unwrap().value)
+            .collect();
+        assert_eq!(px, vec![&b"1.5"[..], b"1.6"]);
+    }
+
+    #[test]
+    fn consecutive_frames_in_one_buffer() {
+        let mut dec = Decoder::new();
+        let mut stream = Vec::new();
+        {
+            let msg = dec.decode(RAW).unwrap();
+            write_frame(&msg, &mut stream);
+        }
+        let msg = dec.decode(b"35=0\x01").unwrap();
+        write_frame(&msg, &mut stream);
+
+        let first = peek_frame_len(&stream).unwrap();
+        let mut reader = IpcReader::new();
+        assert_eq!(reader.read(&stream[first..]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rejects_bad_frames() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(RAW).unwrap();
+        let mut frame = Vec::new();
+        write_frame(&msg, &mut frame);
+        let mut reader = IpcReader::new();
+
+        assert!(matches!(
+            reader.read(&frame[..frame.len() - 1]),
+            Err(FixError::IncompleteMessage { offset: 0 })
+        ));
+        assert!(matches!(
+            reader.read(&frame[..8]),
+            Err(FixError::IncompleteMessage { offset: 0 })
+        ));
+
+        let mut bad_version = frame.clone();
+        bad_version[4] = 9;
+        assert!(matches!(
+            reader.read(&bad_version),
+            Err(FixError::DecodeError)
+        ));
+
+        // Corrupt the first entry's end offset past the buffer.
+        let mut bad_offset = frame.clone();
+        bad_offset[IPC_HEADER_LEN + 8..IPC_HEADER_LEN + 12]
+            .copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            reader.read(&bad_offset),
+            Err(FixError::DecodeError)
+        ));
+    }
+}
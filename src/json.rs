@@ -0,0 +1,403 @@
+//! Conversion between tag=value messages and the FIX Trading Community JSON
+//! encoding, for REST gateways and log pipelines.
+//!
+//! ```text
+//! {"Header":{"BeginString":"FIX.4.4","MsgType":"D","SenderCompID":"CLIENT"},
+//!  "Body":{"ClOrdID":"A1","NoPartyIDs":[{"PartyID":"FIRM","PartyRole":"1"}]},
+//!  "Trailer":{}}
+//! ```
+//!
+//! Fields are named as in [`names`](crate::names); a tag without a known
+//! name is keyed by its number. Every value is a JSON string. Repeating
+//! groups of the built-in specs become arrays of instance objects under
+//! their `NO_*` field. BodyLength (9) and CheckSum (10) are framing and are
+//! left out; encode the decoded fields with
+//! [`Encoder`](crate::encoder::Encoder) to get them back.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::Serialize;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+
+use crate::builder::{GroupWriter, MessageBuilder};
+use crate::dictionary::{is_header_tag, is_trailer_tag};
+use crate::error::FixError;
+use crate::group::{GroupSpec, Item, instance_ranges, items, spec_for_count};
+use crate::message::Message;
+use crate::names::{tag_by_name, tag_name};
+use crate::tag::{self, Tag};
+
+/// Write `msg` as FIX JSON into `out`, which is cleared first.
+///
+/// # Errors
+/// Returns `FixError::InvalidUtf8` if a value is not UTF-8.
+pub fn to_json(msg: &Message<'_>, out: &mut Vec<u8>) -> Result<(), FixError> {
+    out.clear();
+    for f in msg.fields() {
+        std::str::from_utf8(f.value).map_err(|_| FixError::InvalidUtf8)?;
+    }
+    let doc = Document {
+        buf: msg.buf,
+        offsets: msg.offsets,
+    };
+    serde_json::to_writer(out, &doc).map_err(|_| FixError::EncodeError)
+}
+
+/// Read a FIX JSON message into `out`, which is cleared first: header
+/// fields, then body, then trailer, each in document order. Group members
+/// are written in spec order.
+///
+/// # Errors
+/// Returns `FixError::DecodeError` for malformed JSON, an unknown field
+/// name, or an array under a field that is not a built-in group count tag.
+pub fn from_json(json: &[u8], out: &mut MessageBuilder) -> Result<(), FixError> {
+    let mut sections: [MessageBuilder; 3] = Default::default();
+    let mut de = serde_json::Deserializer::from_slice(json);
+    de.deserialize_map(DocumentVisitor(&mut sections))
+        .and_then(|()| de.end())
+        .map_err(|_| FixError::DecodeError)?;
+
+    out.clear();
+    for section in &sections {
+        for f in section.as_message().fields() {
+            out.push(f.tag, f.value);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    Body,
+    Trailer,
+}
+
+impl Section {
+    fn of(tag: Tag) -> Self {
+        if is_header_tag(tag) {
+            Section::Header
+        } else if is_trailer_tag(tag) {
+            Section::Trailer
+        } else {
+            Section::Body
+        }
+    }
+}
+
+fn key(tag: Tag) -> Cow<'static, str> {
+    match tag_name(tag) {
+        Some(name) => Cow::Borrowed(name),
+        None => Cow::Owned(tag.to_string()),
+    }
+}
+
+struct Document<'a> {
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+}
+
+/// Top-level fields of one section, or every field of a group instance
+/// when `section` is `None`.
+struct Fields<'a> {
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+    section: Option<Section>,
+}
+
+struct Instances<'a> {
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+    item: Item,
+}
+
+impl Serialize for Document<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(Some(3))?;
+        for (name, section) in [
+            ("Header", Section::Header),
+            ("Body", Section::Body),
+            ("Trailer", Section::Trailer),
+        ] {
+            map.serialize_entry(
+                name,
+                &Fields {
+                    buf: self.buf,
+                    offsets: self.offsets,
+                    section: Some(section),
+                },
+            )?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Fields<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(None)?;
+        for item in items(self.buf, self.offsets) {
+            let (tag, start, end) = self.offsets[item.start];
+            if tag == tag::BODY_LENGTH || tag == tag::CHECK_SUM {
+                continue;
+            }
+            if self.section.is_some_and(|s| s != Section::of(tag)) {
+                continue;
+            }
+            match item.spec {
+                Some(_) => map.serialize_entry(
+                    &key(tag),
+                    &Instances {
+                        buf: self.buf,
+                        offsets: self.offsets,
+                        item,
+                    },
+                )?,
+                None => {
+                    // Checked to be UTF-8 by `to_json`.
+                    let value = std::str::from_utf8(&self.buf[start as usize..end as usize])
+                        .unwrap_or_default();
+                    map.serialize_entry(&key(tag), value)?
+                }
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Instances<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(None)?;
+        for r in instance_ranges(self.buf, self.offsets, self.item) {
+            seq.serialize_element(&Fields {
+                buf: self.buf,
+                offsets: &self.offsets[r],
+                section: None,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+/// Where decoded fields go: the message builder, or an open group.
+trait Sink {
+    fn push(&mut self, tag: Tag, value: &[u8]);
+    fn group<'s>(&'s mut self, spec: &'s GroupSpec) -> GroupWriter<'s>;
+}
+
+impl Sink for MessageBuilder {
+    fn push(&mut self, tag: Tag, value: &[u8]) {
+        MessageBuilder::push(self, tag, value);
+    }
+
+    fn group<'s>(&'s mut self, spec: &'s GroupSpec) -> GroupWriter<'s> {
+        self.begin_group(spec)
+    }
+}
+
+impl Sink for GroupWriter<'_> {
+    fn push(&mut self, tag: Tag, value: &[u8]) {
+        GroupWriter::push(self, tag, value);
+    }
+
+    fn group<'s>(&'s mut self, spec: &'s GroupSpec) -> GroupWriter<'s> {
+        self.begin_group(spec)
+    }
+}
+
+fn parse_key<E: de::Error>(key: &str) -> Result<Tag, E> {
+    tag_by_name(key)
+        .or_else(|| tag::parse_tag(key.as_bytes()).ok())
+        .ok_or_else(|| E::custom(format_args!("unknown field {key}")))
+}
+
+struct DocumentVisitor<'b>(&'b mut [MessageBuilder; 3]);
+
+impl<'de> Visitor<'de> for DocumentVisitor<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a FIX JSON message")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(name) = map.next_key::<Cow<'de, str>>()? {
+            let section = match &*name {
+                "Header" => &mut self.0[0],
+                "Body" => &mut self.0[1],
+                "Trailer" => &mut self.0[2],
+                other => {
+                    return Err(de::Error::unknown_field(
+                        other,
+                        &["Header", "Body", "Trailer"],
+                    ));
+                }
+            };
+            map.next_value_seed(FieldsSeed { sink: section })?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes every entry of an object into `sink`.
+struct FieldsSeed<'s, S> {
+    sink: &'s mut S,
+}
+
+impl<'de, S: Sink> DeserializeSeed<'de> for FieldsSeed<'_, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<(), D::Error> {
+        d.deserialize_map(self)
+    }
+}
+
+impl<'de, S: Sink> Visitor<'de> for FieldsSeed<'_, S> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an object of named FIX fields")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(name) = map.next_key::<Cow<'de, str>>()? {
+            let tag = parse_key(&name)?;
+            map.next_value_seed(ValueSeed {
+                tag,
+                sink: &mut *self.sink,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes one field, or a whole group for an array.
+struct ValueSeed<'s, S> {
+    tag: Tag,
+    sink: &'s mut S,
+}
+
+impl<'de, S: Sink> DeserializeSeed<'de> for ValueSeed<'_, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<(), D::Error> {
+        d.deserialize_any(self)
+    }
+}
+
+impl<'de, S: Sink> Visitor<'de> for ValueSeed<'_, S> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string or an array of group instances")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+        self.sink.push(self.tag, v.as_bytes());
+        Ok(())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<(), E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<(), E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<(), E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let spec = spec_for_count(self.tag)
+            .ok_or_else(|| de::Error::custom(format_args!("{} is not a group", self.tag)))?;
+        let mut group = self.sink.group(spec);
+        while seq
+            .next_element_seed(InstanceSeed { group: &mut group })?
+            .is_some()
+        {}
+        group
+            .end_group()
+            .map_err(|e| de::Error::custom(format_args!("{e:?}")))
+    }
+}
+
+/// Starts a group instance and writes the object's entries into it.
+struct InstanceSeed<'g, 'w> {
+    group: &'g mut GroupWriter<'w>,
+}
+
+impl<'de> DeserializeSeed<'de> for InstanceSeed<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<(), D::Error> {
+        self.group.instance();
+        FieldsSeed { sink: self.group }.deserialize(d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    const ORDER: &[u8] = b"8=FIX.4.4\x019=0\x0135=D\x0149=CLIENT\x0156=BROKER\x0111=A1\x01453=2\x01448=FIRM\x01447=D\x01452=1\x01448=TRADER\x01447=D\x01452=12\x015001=X\x0110=000\x01";
+
+    #[test]
+    fn to_json_uses_sections_and_names() {
+        let mut dec = Decoder::new();
+        let mut out = Vec::new();
+        to_json(&dec.decode(ORDER).unwrap(), &mut out).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            concat!(
+                r#"{"Header":{"BeginString":"FIX.4.4","MsgType":"D","SenderCompID":"CLIENT","TargetCompID":"BROKER"},"#,
+                r#""Body":{"ClOrdID":"A1","NoPartyIDs":[{"PartyID":"FIRM","PartyIDSource":"D","PartyRole":"1"},"#,
+                r#"{"PartyID":"TRADER","PartyIDSource":"D","PartyRole":"12"}],"5001":"X"},"Trailer":{}}"#
+            )
+        );
+        let bad = dec.decode(b"35=D\x0158=\xff\x01").unwrap();
+        assert!(matches!(
+            to_json(&bad, &mut out),
+            Err(FixError::InvalidUtf8)
+        ));
+    }
+
+    #[test]
+    fn from_json_round_trips_through_encoder() {
+        let mut dec = Decoder::new();
+        let mut enc = Encoder::new();
+        let mut expected = Vec::new();
+        enc.encode(&dec.decode(ORDER).unwrap(), &mut expected)
+            .unwrap();
+
+        let mut json = Vec::new();
+        to_json(&dec.decode(&expected).unwrap(), &mut json).unwrap();
+        let mut b = MessageBuilder::new();
+        from_json(&json, &mut b).unwrap();
+        let mut wire = Vec::new();
+        enc.encode(&b.as_message(), &mut wire).unwrap();
+        assert_eq!(wire, expected);
+    }
+
+    #[test]
+    fn from_json_orders_sections_and_group_members() {
+        let json = br#"{"Body":{"NoPartyIDs":[{"PartyRole":"1","PartyID":"FIRM"}],"OrderQty":100},"Header":{"MsgType":"D"}}"#;
+        let mut b = MessageBuilder::new();
+        from_json(json, &mut b).unwrap();
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 453, 448, 452, 38]);
+
+        assert!(matches!(
+            from_json(br#"{"Body":{"NoSuchField":"1"}}"#, &mut b),
+            Err(FixError::DecodeError)
+        ));
+        assert!(matches!(
+            from_json(br#"{"Body":{"ClOrdID":[{}]}}"#, &mut b),
+            Err(FixError::DecodeError)
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+use std::marker::PhantomData;
+
+use crate::decimal::FixDecimal;
+use crate::error::FixError;
+use crate::field::Field;
+use crate::message::Message;
+use crate::tag::Tag;
+
+pub(crate) use private::Parsed;
+
+mod private {
+    use super::*;
+
+    /// A value held in a message's parse cache.
+    #[derive(Debug, Clone, Copy)]
+    pub enum Parsed {
+        U64(u64),
+        I64(i64),
+        F64(f64),
+        Decimal(FixDecimal),
+    }
+
+    pub trait Sealed: Sized {
+        fn parse(field: &Field<'_>) -> Result<Self, FixError>;
+        fn wrap(self) -> Parsed;
+        fn unwrap(parsed: &Parsed) -> Option<Self>;
+    }
+}
+
+/// Types a [`LazyValue`] can parse into and cache: `u64`, `i64`, `f64` and
+/// [`FixDecimal`]. Sealed.
+pub trait LazyParse: Copy + private::Sealed {}
+
+macro_rules! lazy_parse {
+    ($ty:ty, $variant:ident, $parse:expr) => {
+        impl private::Sealed for $ty {
+            #[inline]
+            fn parse(field: &Field<'_>) -> Result<Self, FixError> {
+                $parse(field)
+            }
+
+            #[inline]
+            fn wrap(self) -> Parsed {
+                Parsed::$variant(self)
+            }
+
+            #[inline]
+            fn unwrap(parsed: &Parsed) -> Option<Self> {
+                match *parsed {
+                    Parsed::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+        }
+
+        impl LazyParse for $ty {}
+    };
+}
+
+lazy_parse!(u64, U64, Field::as_u64);
+lazy_parse!(i64, I64, Field::as_i64);
+lazy_parse!(f64, F64, Field::as_f64);
+lazy_parse!(FixDecimal, Decimal, Field::as_decimal);
+
+/// A numeric field whose parse is deferred until [`get`](Self::get) and
+/// then remembered by the message, as returned by [`Message::lazy`].
+///
+/// Several components reading the same Price or OrderQty from one message
+/// parse the ASCII once: the first `get` stores the result in a small
+/// per-message cache and later reads — through this value or a new one for
+/// the same tag and type — are a lookup. Failed parses are not cached.
+///
+/// # Example
+/// ```ignore
+/// let px = msg.lazy::<FixDecimal>(tag::PRICE);
+/// if risk.enabled() {
+///     risk.check(px.map(|p| p.get()).transpose()?);
+/// }
+/// let px = msg.lazy::<FixDecimal>(tag::PRICE).unwrap().get()?; // cached
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LazyValue<'m, 'a, T> {
+    msg: &'m Message<'a>,
+    field: Field<'a>,
+    _type: PhantomData<T>,
+}
+
+impl<'m, 'a, T: LazyParse> LazyValue<'m, 'a, T> {
+    #[inline]
+    pub(crate) fn new(msg: &'m Message<'a>, field: Field<'a>) -> Self {
+        Self {
+            msg,
+            field,
+            _type: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn tag(&self) -> Tag {
+        self.field.tag
+    }
+
+    /// The unparsed value bytes.
+    #[inline]
+    pub fn raw(&self) -> &'a [u8] {
+        self.field.value
+    }
+
+    /// The parsed value, from the message's cache when already parsed.
+    ///
+    /// # Errors
+    /// The parse error for `T`, typically `InvalidValue`.
+    #[inline]
+    pub fn get(&self) -> Result<T, FixError> {
+        self.msg.cached_value(&self.field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+    use crate::tag;
+
+    #[test]
+    fn parses_once_per_tag_and_type() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"D")
+            .push(tag::PRICE, b"101.25")
+            .push(tag::ORDER_QTY, b"100")
+            .push(tag::SYMBOL, b"AAPL");
+        let msg = b.as_message();
+
+        let px = msg.lazy::<FixDecimal>(tag::PRICE).unwrap();
+        assert_eq!(px.raw(), b"101.25");
+        assert_eq!(msg.cached_values(), 0);
+        assert_eq!(px.get().unwrap(), FixDecimal::new(10125, 2));
+        assert_eq!(msg.cached_values(), 1);
+        let again = msg.lazy::<FixDecimal>(tag::PRICE).unwrap();
+        assert_eq!(again.get().unwrap(), FixDecimal::new(10125, 2));
+        assert_eq!(msg.cached_values(), 1);
+
+        assert_eq!(msg.lazy::<f64>(tag::PRICE).unwrap().get().unwrap(), 101.25);
+        assert_eq!(msg.lazy::<u64>(tag::ORDER_QTY).unwrap().get().unwrap(), 100);
+        assert_eq!(msg.cached_values(), 3);
+
+        assert!(msg.lazy::<u64>(tag::PRICE).unwrap().get().is_err());
+        assert!(msg.lazy::<i64>(tag::SYMBOL).unwrap().get().is_err());
+        assert_eq!(msg.cached_values(), 3);
+        assert!(msg.lazy::<u64>(tag::STOP_PX).is_none());
+    }
+}
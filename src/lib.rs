@@ -1,9 +1,68 @@
+pub mod audit;
+pub mod bid;
 pub mod body_length;
+pub mod builder;
+pub mod capture;
 pub mod checksum;
+#[cfg(feature = "tokio")]
+pub mod codec;
+pub mod confirmation;
+pub mod cross;
+pub mod decimal;
 pub mod decoder;
+pub mod dictionary;
+pub mod drift;
+pub mod dropcopy;
+pub mod edit;
 pub mod encoder;
+#[cfg(feature = "tokio")]
+pub mod engine;
 pub mod error;
+pub mod exec;
+pub mod fault;
+pub mod fees;
 pub mod field;
+pub mod fragment;
 pub mod group;
+pub mod heartbeat;
+pub mod instrument;
+pub mod intern;
+pub mod ioi;
+pub mod ipc;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod lazy;
+pub mod list;
+pub mod map;
+pub mod md;
 pub mod message;
+pub mod msg_type;
+pub mod multileg;
+pub mod names;
+pub mod owned;
+pub mod pipeline;
+pub mod policy;
+pub mod predicate;
+pub mod quarantine;
+pub mod quote;
+pub mod registration;
+pub mod response;
+pub mod rfq;
+#[cfg(feature = "shm-ring")]
+pub mod ring;
+pub mod selftest;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod session;
+pub mod settlement;
+pub mod sim;
+pub mod skew;
+pub mod status;
+pub mod store;
 pub mod tag;
+pub mod timestamp;
+pub mod unknown_tags;
+pub mod validate;
+pub mod values;
+pub mod view;
+pub mod watcher;
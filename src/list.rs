@@ -0,0 +1,471 @@
+use std::ops::Range;
+
+use crate::builder::MessageBuilder;
+use crate::decimal::FixDecimal;
+use crate::error::FixError;
+use crate::fragment::FragmentStatus;
+use crate::group::{self, parse_count};
+use crate::message::Message;
+use crate::tag;
+
+/// A program-trading list of orders, written as NewOrderList (35=E) and
+/// executed with ListExecute (35=L).
+///
+/// Orders keep the order they were added in; each one gets ListSeqNo (67)
+/// set to its 1-based position.
+///
+/// # Example
+/// ```ignore
+/// let mut list = NewOrderList::new(b"LIST1");
+/// for (cl_ord_id, symbol, qty) in basket {
+///     order.clear();
+///     order.push(tag::CL_ORD_ID, cl_ord_id).push(tag::SYMBOL, symbol)
+///         .push(tag::SIDE, b"1").push(tag::ORDER_QTY, qty).push(tag::ORD_TYPE, b"1");
+///     list.push_order(&order.as_message());
+/// }
+/// for frag in list.fragments(50) {
+///     b.clear();
+///     frag.write_to(&mut b);
+///     enc.encode(&b.as_message(), &mut out)?;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NewOrderList {
+    list_id: Vec<u8>,
+    bid_type: u8,
+    orders: Vec<MessageBuilder>,
+}
+
+impl NewOrderList {
+    /// Create an empty list with BidType (394) `3` (no bidding process).
+    pub fn new(list_id: &[u8]) -> Self {
+        Self {
+            list_id: list_id.to_vec(),
+            bid_type: b'3',
+            orders: Vec::new(),
+        }
+    }
+
+    /// Set BidType (394): `1` non-disclosed, `2` disclosed, `3` no bidding
+    /// process.
+    pub fn bid_type(mut self, bid_type: u8) -> Self {
+        self.bid_type = bid_type;
+        self
+    }
+
+    #[inline]
+    pub fn list_id(&self) -> &[u8] {
+        &self.list_id
+    }
+
+    /// Number of orders.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Returns true if no orders have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Add an order built from the fields of `order` (ClOrdID, Symbol, Side,
+    /// OrderQty, …). Any ListSeqNo it carries is replaced. Returns the
+    /// ListSeqNo assigned.
+    pub fn push_order(&mut self, order: &Message<'_>) -> u32 {
+        let seq = self.orders.len() as u32 + 1;
+        let mut b = MessageBuilder::with_capacity(order.len() + 1, 0);
+        b.extend(order.fields().filter(|f| f.tag != tag::LIST_SEQ_NO));
+        b.push_u32(tag::LIST_SEQ_NO, seq);
+        self.orders.push(b);
+        seq
+    }
+
+    /// The orders added so far, in ListSeqNo order.
+    #[inline]
+    pub fn orders(&self) -> &[MessageBuilder] {
+        &self.orders
+    }
+
+    /// Split the list into messages of at most `max_orders` orders each
+    /// (`0` puts every order in one message). An empty list yields one
+    /// fragment with no orders.
+    pub fn fragments(&self, max_orders: usize) -> ListFragments<'_> {
+        let total = self.orders.len();
+        let per = if max_orders == 0 {
+            total.max(1)
+        } else {
+            max_orders
+        };
+        ListFragments {
+            list: self,
+            per,
+            next_order: 0,
+            index: 0,
+            total_fragments: total.div_ceil(per).max(1),
+        }
+    }
+
+    /// Append a ListExecute (35=L) for this list to `b`: MsgType, ListID
+    /// and TransactTime (60).
+    pub fn write_execute(&self, transact_time: &[u8], b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"L")
+            .push(tag::LIST_ID, &self.list_id)
+            .push(tag::TRANSACT_TIME, transact_time);
+    }
+}
+
+/// Iterator over the messages of a [`NewOrderList`], produced by
+/// [`NewOrderList::fragments`].
+pub struct ListFragments<'a> {
+    list: &'a NewOrderList,
+    per: usize,
+    next_order: usize,
+    index: usize,
+    total_fragments: usize,
+}
+
+impl<'a> Iterator for ListFragments<'a> {
+    type Item = ListFragment<'a>;
+
+    fn next(&mut self) -> Option<ListFragment<'a>> {
+        if self.index >= self.total_fragments {
+            return None;
+        }
+        let start = self.next_order;
+        let end = (start + self.per).min(self.list.len());
+        self.next_order = end;
+        self.index += 1;
+        Some(ListFragment {
+            list: self.list,
+            orders: start..end,
+            last: self.index == self.total_fragments,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.total_fragments - self.index;
+        (left, Some(left))
+    }
+}
+
+/// One NewOrderList message worth of orders.
+#[derive(Debug, Clone)]
+pub struct ListFragment<'a> {
+    list: &'a NewOrderList,
+    orders: Range<usize>,
+    last: bool,
+}
+
+impl ListFragment<'_> {
+    /// Number of orders in this fragment.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Returns true if this fragment carries no orders (empty list).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Returns true if this is the final fragment of the list.
+    #[inline]
+    pub fn is_last(&self) -> bool {
+        self.last
+    }
+
+    /// Append the NewOrderList to `b`: MsgType `E`, ListID, BidType,
+    /// TotNoOrders (68) for the whole list, LastFragment (893) and the
+    /// `NO_ORDERS` group with this fragment's orders.
+    ///
+    /// Header fields other than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"E")
+            .push(tag::LIST_ID, &self.list.list_id)
+            .push(tag::BID_TYPE, &[self.list.bid_type])
+            .push_u32(tag::TOT_NO_ORDERS, self.list.len() as u32)
+            .push(tag::LAST_FRAGMENT, if self.last { b"Y" } else { b"N" });
+        let mut g = b.begin_group(&group::ORDERS);
+        for order in &self.list.orders[self.orders.clone()] {
+            g.instance();
+            for f in order.as_message().fields() {
+                g.push(f.tag, f.value);
+            }
+        }
+    }
+}
+
+/// Latest status of one order of a list, as reported by ListStatus (35=N).
+#[derive(Debug, Clone)]
+pub struct ListOrderState {
+    pub cl_ord_id: Vec<u8>,
+    /// OrdStatus (39).
+    pub ord_status: Option<u8>,
+    pub cum_qty: Option<FixDecimal>,
+    pub leaves_qty: Option<FixDecimal>,
+    pub cxl_qty: Option<FixDecimal>,
+    pub avg_px: Option<FixDecimal>,
+}
+
+/// Folds the ListStatus (35=N) messages of one list into the latest
+/// status of each order.
+///
+/// A status report may be split across several messages; [`apply`]
+/// reports [`FragmentStatus::Complete`] once a report is whole —
+/// LastFragment (893) = `Y`, or as many orders as TotNoOrders (68) when
+/// 893 is absent.
+///
+/// # Example
+/// ```ignore
+/// let mut status = ListStatusAggregator::new(b"LIST1");
+/// if status.apply(&msg)? == FragmentStatus::Complete && status.is_all_done() {
+///     for order in status.orders() { report(order); }
+/// }
+/// ```
+///
+/// [`apply`]: ListStatusAggregator::apply
+#[derive(Debug, Clone)]
+pub struct ListStatusAggregator {
+    list_id: Vec<u8>,
+    orders: Vec<ListOrderState>,
+    list_status_type: Option<u8>,
+    list_order_status: Option<u8>,
+    expected: Option<usize>,
+    received: usize,
+    complete: bool,
+}
+
+impl ListStatusAggregator {
+    pub fn new(list_id: &[u8]) -> Self {
+        Self {
+            list_id: list_id.to_vec(),
+            orders: Vec::new(),
+            list_status_type: None,
+            list_order_status: None,
+            expected: None,
+            received: 0,
+            complete: false,
+        }
+    }
+
+    /// Add one ListStatus message. Order states are updated by ClOrdID; a
+    /// message received after a complete report starts the next report.
+    ///
+    /// `InvalidValue` if `msg` is not a ListStatus for this list, or a
+    /// quantity or price is malformed.
+    pub fn apply(&mut self, msg: &Message<'_>) -> Result<FragmentStatus, FixError> {
+        let is_status = msg.find(tag::MSG_TYPE).is_some_and(|f| f.value == b"N");
+        let same_list = msg
+            .find(tag::LIST_ID)
+            .is_some_and(|f| f.value == self.list_id);
+        if !(is_status && same_list) {
+            return Err(FixError::InvalidValue);
+        }
+        if self.complete {
+            self.received = 0;
+            self.complete = false;
+        }
+
+        let first_char = |t| msg.find(t).and_then(|f| f.value.first().copied());
+        self.list_status_type = first_char(tag::LIST_STATUS_TYPE).or(self.list_status_type);
+        self.list_order_status = first_char(tag::LIST_ORDER_STATUS).or(self.list_order_status);
+        if let Some(f) = msg.find(tag::TOT_NO_ORDERS) {
+            self.expected = Some(parse_count(f.value));
+        }
+
+        for order in msg.groups(&group::ORDERS) {
+            let decimal = |t| {
+                order
+                    .find(t)
+                    .map(|f| FixDecimal::parse(f.value))
+                    .transpose()
+            };
+            let cl_ord_id = order.find(tag::CL_ORD_ID).map_or(&[][..], |f| f.value);
+            let state = ListOrderState {
+                cl_ord_id: cl_ord_id.to_vec(),
+                ord_status: order
+                    .find(tag::ORD_STATUS)
+                    .and_then(|f| f.value.first().copied()),
+                cum_qty: decimal(tag::CUM_QTY)?,
+                leaves_qty: decimal(tag::LEAVES_QTY)?,
+                cxl_qty: decimal(tag::CXL_QTY)?,
+                avg_px: decimal(tag::AVG_PX)?,
+            };
+            match self.orders.iter_mut().find(|o| o.cl_ord_id == cl_ord_id) {
+                Some(o) => *o = state,
+                None => self.orders.push(state),
+            }
+            self.received += 1;
+        }
+
+        self.complete = match msg.find(tag::LAST_FRAGMENT).map(|f| f.value) {
+            Some(b"Y") => true,
+            Some(_) => false,
+            None => self.expected.is_none_or(|n| self.received >= n),
+        };
+        Ok(if self.complete {
+            FragmentStatus::Complete
+        } else {
+            FragmentStatus::Incomplete
+        })
+    }
+
+    #[inline]
+    pub fn list_id(&self) -> &[u8] {
+        &self.list_id
+    }
+
+    /// Every order reported so far, in first-reported order.
+    #[inline]
+    pub fn orders(&self) -> &[ListOrderState] {
+        &self.orders
+    }
+
+    /// The latest state of the order with ClOrdID `cl_ord_id`.
+    pub fn order(&self, cl_ord_id: &[u8]) -> Option<&ListOrderState> {
+        self.orders.iter().find(|o| o.cl_ord_id == cl_ord_id)
+    }
+
+    /// Latest ListStatusType (429).
+    #[inline]
+    pub fn list_status_type(&self) -> Option<u8> {
+        self.list_status_type
+    }
+
+    /// Latest ListOrderStatus (431).
+    #[inline]
+    pub fn list_order_status(&self) -> Option<u8> {
+        self.list_order_status
+    }
+
+    /// TotNoOrders (68) of the latest report.
+    #[inline]
+    pub fn expected_total(&self) -> Option<usize> {
+        self.expected
+    }
+
+    /// Returns true once the latest report is whole.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// ListOrderStatus is `6` (all done) or `7` (reject).
+    pub fn is_all_done(&self) -> bool {
+        matches!(self.list_order_status, Some(b'6' | b'7'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::tag::Tag;
+
+    fn list_of(n: usize) -> NewOrderList {
+        let mut list = NewOrderList::new(b"L1").bid_type(b'1');
+        let mut order = MessageBuilder::new();
+        for i in 0..n {
+            order.clear();
+            order
+                .push(tag::SYMBOL, b"IBM")
+                .push_u32(tag::ORDER_QTY, 100)
+                .push(tag::CL_ORD_ID, format!("C{i}").as_bytes())
+                .push(tag::SIDE, b"1");
+            assert_eq!(list.push_order(&order.as_message()), i as u32 + 1);
+        }
+        list
+    }
+
+    #[test]
+    fn new_order_list_fragments() {
+        let list = list_of(5);
+        let frags: Vec<_> = list.fragments(2).collect();
+        assert_eq!(frags.len(), 3);
+        assert_eq!(
+            frags
+                .iter()
+                .map(|f| (f.len(), f.is_last()))
+                .collect::<Vec<_>>(),
+            [(2, false), (2, false), (1, true)]
+        );
+
+        let mut b = MessageBuilder::new();
+        frags[2].write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 66, 394, 68, 893, 73, 11, 67, 38, 54, 55]);
+        let msg = b.as_message();
+        assert_eq!(msg.find(tag::TOT_NO_ORDERS).unwrap().value, b"5");
+        assert_eq!(msg.find(tag::NO_ORDERS).unwrap().value, b"1");
+        assert_eq!(msg.find(tag::LIST_SEQ_NO).unwrap().value, b"5");
+
+        let empty = NewOrderList::new(b"L2");
+        assert_eq!(empty.fragments(10).count(), 1);
+        b.clear();
+        list.write_execute(b"20240229-13:45:06", &mut b);
+        assert_eq!(b.as_message().find(tag::LIST_ID).unwrap().value, b"L1");
+    }
+
+    #[test]
+    fn list_status_aggregates_fragments() {
+        let mut agg = ListStatusAggregator::new(b"L1");
+        let mut dec = Decoder::new();
+        let mut enc = Encoder::new();
+        let mut out = Vec::new();
+        let mut b = MessageBuilder::new();
+        let status = |b: &mut MessageBuilder, last: &[u8], orders: &[(&[u8], &[u8], &[u8])]| {
+            b.clear();
+            b.push(tag::MSG_TYPE, b"N")
+                .push(tag::LIST_ID, b"L1")
+                .push(tag::LIST_STATUS_TYPE, b"3")
+                .push(
+                    tag::LIST_ORDER_STATUS,
+                    if last == b"Y" { b"6" } else { b"3" },
+                )
+                .push_u32(tag::TOT_NO_ORDERS, 2)
+                .push(tag::LAST_FRAGMENT, last);
+            let mut g = b.begin_group(&group::ORDERS);
+            for (id, st, cum) in orders {
+                g.instance()
+                    .push(tag::CL_ORD_ID, id)
+                    .push(tag::CUM_QTY, cum)
+                    .push(tag::ORD_STATUS, st)
+                    .push(tag::LEAVES_QTY, b"0")
+                    .push(tag::CXL_QTY, b"0")
+                    .push(tag::AVG_PX, b"10.5");
+            }
+        };
+
+        status(&mut b, b"N", &[(b"C0", b"1", b"40")]);
+        out.clear();
+        enc.encode(&b.as_message(), &mut out).unwrap();
+        assert_eq!(
+            agg.apply(&dec.decode(&out).unwrap()).unwrap(),
+            FragmentStatus::Incomplete
+        );
+
+        status(
+            &mut b,
+            b"Y",
+            &[(b"C1", b"2", b"100"), (b"C0", b"2", b"100")],
+        );
+        out.clear();
+        enc.encode(&b.as_message(), &mut out).unwrap();
+        assert_eq!(
+            agg.apply(&dec.decode(&out).unwrap()).unwrap(),
+            FragmentStatus::Complete
+        );
+        assert!(agg.is_all_done());
+        assert_eq!(agg.orders().len(), 2);
+        let c0 = agg.order(b"C0").unwrap();
+        assert_eq!(c0.ord_status, Some(b'2'));
+        assert_eq!(c0.cum_qty.unwrap().to_string(), "100");
+
+        let other = dec.decode(b"35=N\x0166=L9\x01").unwrap();
+        assert!(agg.apply(&other).is_err());
+    }
+}
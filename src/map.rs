@@ -0,0 +1,103 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::group::{instance_ranges, items};
+use crate::tag::Tag;
+
+/// Every value of a message keyed by tag, as returned by
+/// [`Message::to_map`](crate::message::Message::to_map).
+///
+/// Repeated tags (including group members across instances) keep their
+/// values in wire order.
+pub type FieldMap<'a> = BTreeMap<Tag, Vec<Cow<'a, [u8]>>>;
+
+/// A message keyed by tag with repeating groups nested, as returned by
+/// [`Message::to_nested_map`](crate::message::Message::to_nested_map).
+pub type NestedFieldMap<'a> = BTreeMap<Tag, Vec<MapValue<'a>>>;
+
+/// One entry of a [`NestedFieldMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapValue<'a> {
+    Value(Cow<'a, [u8]>),
+    /// The instances of a repeating group, under its `NO_*` count tag.
+    Group(Vec<NestedFieldMap<'a>>),
+}
+
+impl<'a> MapValue<'a> {
+    /// The field value, or `None` for a group.
+    pub fn as_value(&self) -> Option<&[u8]> {
+        match self {
+            MapValue::Value(v) => Some(v),
+            MapValue::Group(_) => None,
+        }
+    }
+
+    /// The group instances, or `None` for a plain value.
+    pub fn as_group(&self) -> Option<&[NestedFieldMap<'a>]> {
+        match self {
+            MapValue::Value(_) => None,
+            MapValue::Group(g) => Some(g),
+        }
+    }
+}
+
+pub(crate) fn flat<'a>(buf: &'a [u8], offsets: &[(Tag, u32, u32)]) -> FieldMap<'a> {
+    let mut map = FieldMap::new();
+    for &(tag, start, end) in offsets {
+        map.entry(tag)
+            .or_default()
+            .push(Cow::Borrowed(&buf[start as usize..end as usize]));
+    }
+    map
+}
+
+/// Groups are recognised from the built-in specs, with instance boundaries
+/// as in [`group_end`](crate::group::group_end).
+pub(crate) fn nested<'a>(buf: &'a [u8], offsets: &[(Tag, u32, u32)]) -> NestedFieldMap<'a> {
+    let mut map = NestedFieldMap::new();
+    for item in items(buf, offsets) {
+        let (tag, start, end) = offsets[item.start];
+        let value = match item.spec {
+            Some(_) => MapValue::Group(
+                instance_ranges(buf, offsets, item)
+                    .map(|r| nested(buf, &offsets[r]))
+                    .collect(),
+            ),
+            None => MapValue::Value(Cow::Borrowed(&buf[start as usize..end as usize])),
+        };
+        map.entry(tag).or_default().push(value);
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::Decoder;
+    use crate::tag;
+
+    const SNAPSHOT: &[u8] =
+        b"35=W\x0155=IBM\x01268=2\x01269=0\x01270=1.5\x01269=1\x01270=1.6\x01262=R1\x01";
+
+    #[test]
+    fn flat_map_collects_repeated_tags() {
+        let mut dec = Decoder::new();
+        let map = dec.decode(SNAPSHOT).unwrap().to_map();
+        assert_eq!(map[&tag::SYMBOL], [&b"IBM"[..]]);
+        assert_eq!(map[&tag::MD_ENTRY_PX], [&b"1.5"[..], b"1.6"]);
+        assert_eq!(map[&tag::NO_MD_ENTRIES], [&b"2"[..]]);
+    }
+
+    #[test]
+    fn nested_map_groups_instances() {
+        let mut dec = Decoder::new();
+        let map = dec.decode(SNAPSHOT).unwrap().to_nested_map();
+        let entries = map[&tag::NO_MD_ENTRIES][0].as_group().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[1][&tag::MD_ENTRY_PX][0].as_value(),
+            Some(&b"1.6"[..])
+        );
+        assert!(!map.contains_key(&tag::MD_ENTRY_TYPE));
+        assert_eq!(map[&tag::MD_REQ_ID][0].as_value(), Some(&b"R1"[..]));
+    }
+}
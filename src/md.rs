@@ -0,0 +1,319 @@
+use crate::builder::MessageBuilder;
+use crate::group::MD_ENTRIES;
+use crate::message::Message;
+use crate::tag;
+
+/// MDEntryType (269) value for a bid entry.
+pub const MD_ENTRY_TYPE_BID: &[u8] = b"0";
+/// MDEntryType (269) value for an offer entry.
+pub const MD_ENTRY_TYPE_OFFER: &[u8] = b"1";
+
+/// One price level of an order book.
+///
+/// Price and size are kept as the exact ASCII bytes seen on the wire, so a
+/// book assembled from FIX and republished over FIX is byte-for-byte
+/// faithful (no float round-tripping of prices).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookLevel {
+    pub price: Vec<u8>,
+    pub size: Vec<u8>,
+}
+
+impl BookLevel {
+    pub fn new(price: &[u8], size: &[u8]) -> Self {
+        Self {
+            price: price.to_vec(),
+            size: size.to_vec(),
+        }
+    }
+}
+
+/// An assembled order book for one instrument.
+///
+/// `bids` and `offers` are held in the order they should be published
+/// (best level first).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Book {
+    pub symbol: Vec<u8>,
+    pub bids: Vec<BookLevel>,
+    pub offers: Vec<BookLevel>,
+}
+
+impl Book {
+    /// Create an empty book for `symbol`.
+    pub fn new(symbol: &[u8]) -> Self {
+        Self {
+            symbol: symbol.to_vec(),
+            bids: Vec::new(),
+            offers: Vec::new(),
+        }
+    }
+
+    /// Total number of levels on both sides.
+    #[inline]
+    pub fn entry_count(&self) -> usize {
+        self.bids.len() + self.offers.len()
+    }
+
+    /// Assemble a book from a MarketDataSnapshotFullRefresh (35=W) message.
+    ///
+    /// Bid (269=0) and offer (269=1) entries of the `NO_MD_ENTRIES` group are
+    /// collected in wire order; other entry types (trades, imbalances, …) are
+    /// ignored. Entries missing a price or size are skipped.
+    pub fn from_snapshot(msg: &Message<'_>) -> Self {
+        let mut book = Book::new(msg.find(tag::SYMBOL).map(|f| f.value).unwrap_or(b""));
+        book.apply_snapshot(msg);
+        book
+    }
+
+    /// Append the bid/offer entries of a snapshot message (or one fragment of
+    /// a fragmented snapshot) to this book.
+    pub fn apply_snapshot(&mut self, msg: &Message<'_>) {
+        for entry in msg.groups(&MD_ENTRIES) {
+            let (Some(ty), Some(px), Some(size)) = (
+                entry.find(tag::MD_ENTRY_TYPE),
+                entry.find(tag::MD_ENTRY_PX),
+                entry.find(tag::MD_ENTRY_SIZE),
+            ) else {
+                continue;
+            };
+            let level = BookLevel::new(px.value, size.value);
+            match ty.value {
+                MD_ENTRY_TYPE_BID => self.bids.push(level),
+                MD_ENTRY_TYPE_OFFER => self.offers.push(level),
+                _ => {}
+            }
+        }
+    }
+
+    /// Split the book into snapshot fragments of at most `max_entries` group
+    /// instances each.
+    ///
+    /// An empty book yields one fragment with no entries, so a downstream
+    /// consumer is still told the book is empty. `max_entries` of `0` is
+    /// treated as "no limit".
+    ///
+    /// # Example
+    /// ```ignore
+    /// for frag in book.snapshot_fragments(100) {
+    ///     b.clear();
+    ///     b.push(tag::MSG_TYPE, b"W")
+    ///         .push(tag::SENDER_COMP_ID, b"MDSRC")
+    ///         .push(tag::TARGET_COMP_ID, b"CLIENT");
+    ///     frag.write_to(&mut b);
+    ///     enc.encode(&b.as_message(), &mut out)?;
+    /// }
+    /// ```
+    pub fn snapshot_fragments(&self, max_entries: usize) -> SnapshotFragments<'_> {
+        let total = self.entry_count();
+        let per = if max_entries == 0 {
+            total.max(1)
+        } else {
+            max_entries
+        };
+        SnapshotFragments {
+            book: self,
+            md_req_id: None,
+            per,
+            next_entry: 0,
+            index: 0,
+            total_fragments: total.div_ceil(per).max(1),
+        }
+    }
+}
+
+/// Iterator over the fragments of a book snapshot, produced by
+/// [`Book::snapshot_fragments`].
+pub struct SnapshotFragments<'a> {
+    book: &'a Book,
+    md_req_id: Option<&'a [u8]>,
+    per: usize,
+    next_entry: usize,
+    index: usize,
+    total_fragments: usize,
+}
+
+impl<'a> SnapshotFragments<'a> {
+    /// Echo `md_req_id` as MDReqID (262) in every fragment, for snapshots sent
+    /// in response to a MarketDataRequest.
+    pub fn md_req_id(mut self, md_req_id: &'a [u8]) -> Self {
+        self.md_req_id = Some(md_req_id);
+        self
+    }
+}
+
+impl<'a> Iterator for SnapshotFragments<'a> {
+    type Item = SnapshotFragment<'a>;
+
+    fn next(&mut self) -> Option<SnapshotFragment<'a>> {
+        if self.index >= self.total_fragments {
+            return None;
+        }
+        let start = self.next_entry;
+        let end = (start + self.per).min(self.book.entry_count());
+        self.next_entry = end;
+        self.index += 1;
+        Some(SnapshotFragment {
+            book: self.book,
+            md_req_id: self.md_req_id,
+            entries: start..end,
+            total_fragments: self.total_fragments,
+            last: self.index == self.total_fragments,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.total_fragments - self.index;
+        (left, Some(left))
+    }
+}
+
+/// One message worth of a book snapshot.
+///
+/// Entries are numbered across both sides: bids first, then offers.
+#[derive(Debug, Clone)]
+pub struct SnapshotFragment<'a> {
+    book: &'a Book,
+    md_req_id: Option<&'a [u8]>,
+    entries: std::ops::Range<usize>,
+    total_fragments: usize,
+    last: bool,
+}
+
+impl SnapshotFragment<'_> {
+    /// Number of group instances in this fragment.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this fragment carries no entries (empty book).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns true if this is the final fragment of the snapshot.
+    #[inline]
+    pub fn is_last(&self) -> bool {
+        self.last
+    }
+
+    /// Append the snapshot body to `b`: MDReqID (if set), Symbol,
+    /// TotNumReports (911), LastFragment (893), and the `NO_MD_ENTRIES`
+    /// group with its count.
+    ///
+    /// Header fields (35, 49, 56, 34, 52, …) are the caller's responsibility
+    /// and should be pushed before calling this.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        if let Some(id) = self.md_req_id {
+            b.push(tag::MD_REQ_ID, id);
+        }
+        b.push(tag::SYMBOL, &self.book.symbol);
+        b.push_u32(tag::TOT_NUM_REPORTS, self.total_fragments as u32);
+        b.push(tag::LAST_FRAGMENT, if self.last { b"Y" } else { b"N" });
+        b.push_u32(tag::NO_MD_ENTRIES, self.entries.len() as u32);
+
+        let bids = self.book.bids.len();
+        for i in self.entries.clone() {
+            let (ty, level) = if i < bids {
+                (MD_ENTRY_TYPE_BID, &self.book.bids[i])
+            } else {
+                (MD_ENTRY_TYPE_OFFER, &self.book.offers[i - bids])
+            };
+            b.push(tag::MD_ENTRY_TYPE, ty)
+                .push(tag::MD_ENTRY_PX, &level.price)
+                .push(tag::MD_ENTRY_SIZE, &level.size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    fn sample_book() -> Book {
+        let mut book = Book::new(b"AAPL");
+        book.bids.push(BookLevel::new(b"149.50", b"500"));
+        book.bids.push(BookLevel::new(b"149.45", b"200"));
+        book.offers.push(BookLevel::new(b"150.00", b"300"));
+        book
+    }
+
+    fn encode_fragment(frag: &SnapshotFragment<'_>) -> Vec<u8> {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, b"W");
+        frag.write_to(&mut b);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn round_trip_single_message() {
+        let book = sample_book();
+        let frags: Vec<_> = book.snapshot_fragments(0).collect();
+        assert_eq!(frags.len(), 1);
+        assert!(frags[0].is_last());
+
+        let wire = encode_fragment(&frags[0]);
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&wire).unwrap();
+        assert!(msg.validate_body_length().is_ok());
+        assert!(msg.validate_checksum().is_ok());
+        assert_eq!(msg.find(tag::NO_MD_ENTRIES).unwrap().value, b"3");
+        assert_eq!(msg.find(tag::TOT_NUM_REPORTS).unwrap().value, b"1");
+        assert_eq!(msg.find(tag::LAST_FRAGMENT).unwrap().value, b"Y");
+
+        assert_eq!(Book::from_snapshot(&msg), book);
+    }
+
+    #[test]
+    fn fragmented_snapshot_reassembles() {
+        let book = sample_book();
+        let frags: Vec<_> = book.snapshot_fragments(2).md_req_id(b"REQ1").collect();
+        assert_eq!(frags.len(), 2);
+        assert_eq!(frags[0].len(), 2);
+        assert_eq!(frags[1].len(), 1);
+        assert!(!frags[0].is_last());
+        assert!(frags[1].is_last());
+
+        let mut dec = Decoder::new();
+        let mut rebuilt = Book::new(b"AAPL");
+        for frag in &frags {
+            let wire = encode_fragment(frag);
+            let msg = dec.decode(&wire).unwrap();
+            assert_eq!(msg.find(tag::MD_REQ_ID).unwrap().value, b"REQ1");
+            assert_eq!(msg.find(tag::TOT_NUM_REPORTS).unwrap().value, b"2");
+            rebuilt.apply_snapshot(&msg);
+        }
+        assert_eq!(rebuilt, book);
+    }
+
+    #[test]
+    fn empty_book_yields_one_empty_fragment() {
+        let book = Book::new(b"MSFT");
+        let frags: Vec<_> = book.snapshot_fragments(10).collect();
+        assert_eq!(frags.len(), 1);
+        assert!(frags[0].is_empty());
+        assert!(frags[0].is_last());
+
+        let wire = encode_fragment(&frags[0]);
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&wire).unwrap();
+        assert_eq!(msg.find(tag::NO_MD_ENTRIES).unwrap().value, b"0");
+    }
+
+    #[test]
+    fn from_snapshot_ignores_other_entry_types() {
+        let raw = b"35=W\x0155=AAPL\x01268=3\x01269=0\x01270=1.0\x01271=5\x01\
+269=2\x01270=1.1\x01271=7\x01269=1\x01270=1.2\x01271=9\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let book = Book::from_snapshot(&msg);
+        assert_eq!(book.bids, vec![BookLevel::new(b"1.0", b"5")]);
+        assert_eq!(book.offers, vec![BookLevel::new(b"1.2", b"9")]);
+    }
+}
@@ -1,17 +1,42 @@
-use std::cell::OnceCell;
+use std::cell::{OnceCell, RefCell};
+use std::sync::Arc;
 
 use smallvec::SmallVec;
 
 use crate::body_length::parse_body_length;
 use crate::checksum::{compute_checksum, parse_checksum};
+use crate::dictionary::{Dictionary, is_header_tag, is_trailer_tag};
 use crate::error::FixError;
-use crate::field::Field;
-use crate::group::{FIX42_GROUPS, FIX44_GROUPS, GroupIter, GroupSpec, parse_count};
+use crate::field::{Field, Fields, display_fields};
+use crate::group::{GroupIter, GroupSpec, group_end, parse_count, position_of, specs_for_version};
+use crate::lazy::{LazyParse, LazyValue, Parsed};
+use crate::map::{self, FieldMap, NestedFieldMap};
+use crate::msg_type::MsgType;
+use crate::owned::OwnedMessage;
 use crate::tag::{self, Tag};
+use crate::validate::{Validator, Violation};
+use crate::view::Section;
+
+/// Largest message, in bytes, the crate can decode or build.
+///
+/// Field values are located by `u32` byte offsets, so a message is limited to
+/// 4 GiB. The number of fields is bounded only by this size: every field takes
+/// at least three bytes, so field positions always fit in `u32` too.
+pub const MAX_MESSAGE_LEN: usize = u32::MAX as usize;
 
 /// Default inline capacity for the sorted index — matches the decoder's field capacity.
 const SORTED_CAPACITY: usize = 32;
 
+/// Messages with fewer fields than this are searched linearly by [`find`]
+/// and [`find_from`] instead of building the sorted index. Below it the
+/// `sorted_vs_linear` benches show a scan beating sort-plus-search even for
+/// several lookups, so heartbeats, test requests and other small admin
+/// messages never pay for the sort.
+///
+/// [`find`]: Message::find
+/// [`find_from`]: Message::find_from
+const LINEAR_SCAN_MAX_FIELDS: usize = 12;
+
 /// A decoded FIX message.
 ///
 /// Zero-copy: field values are sub-slices of the original input buffer — no
@@ -20,6 +45,7 @@ const SORTED_CAPACITY: usize = 32;
 /// The sorted tag index for [`find`] is built lazily on the first call and
 /// cached for the lifetime of the message. This means `decode()` pays no sort
 /// cost when you never call `find()`, and pays it at most once when you do.
+/// Messages with fewer than 12 fields are scanned linearly and never build it.
 #[derive(Debug)]
 pub struct Message<'a> {
     /// The raw bytes of the complete FIX message as received (e.g. the network
@@ -45,7 +71,27 @@ pub struct Message<'a> {
     /// of the message via `OnceCell`. Never allocated if `find()` is never
     /// called, and built at most once regardless of how many times `find()` is
     /// called.
-    sorted: OnceCell<SmallVec<[(Tag, u16); SORTED_CAPACITY]>>,
+    sorted: OnceCell<SmallVec<[(Tag, u32); SORTED_CAPACITY]>>,
+
+    /// Group instance boundaries found by `groups()`, once enabled with
+    /// [`cache_groups`](Message::cache_groups).
+    groups_cache: OnceCell<RefCell<Vec<GroupBounds>>>,
+
+    /// Values parsed through [`lazy`](Message::lazy), keyed by tag. Empty
+    /// (and unallocated) until the first one is cached.
+    values: RefCell<Vec<(Tag, Parsed)>>,
+}
+
+/// Where the instances of one group end, for the group cache.
+#[derive(Debug)]
+struct GroupBounds {
+    count_tag: Tag,
+    delimiter_tag: Tag,
+    /// Index of the first field after the count tag.
+    first: usize,
+    count: usize,
+    /// End of each instance, relative to `first`.
+    ends: Arc<[u32]>,
 }
 
 impl<'a> Message<'a> {
@@ -56,9 +102,24 @@ impl<'a> Message<'a> {
             buf,
             offsets,
             sorted: OnceCell::new(),
+            groups_cache: OnceCell::new(),
+            values: RefCell::new(Vec::new()),
         }
     }
 
+    /// Remember group instance boundaries for the rest of this message's
+    /// life, so calling [`groups`](Self::groups) again for the same spec —
+    /// or after [`all_groups`](Self::all_groups) — reuses the first scan
+    /// instead of searching for delimiters again.
+    ///
+    /// Worth it when a large message (a full-depth MarketData snapshot, a
+    /// big SecurityList) is read group by group several times; the first
+    /// `groups()` call per spec scans the whole group up front. Cached
+    /// boundaries are keyed by count and delimiter tag.
+    pub fn cache_groups(&self) {
+        self.groups_cache.get_or_init(Default::default);
+    }
+
     /// Number of fields in the message.
     #[inline]
     pub fn len(&self) -> usize {
@@ -85,11 +146,101 @@ impl<'a> Message<'a> {
     /// Returns an iterator over all fields, reconstructing each `Field<'a>`
     /// zero-copy on demand.
     #[inline]
-    pub fn fields(&self) -> impl Iterator<Item = Field<'a>> + '_ {
-        self.offsets.iter().map(move |&(tag, start, end)| Field {
-            tag,
-            value: &self.buf[start as usize..end as usize],
-        })
+    pub fn fields(&self) -> Fields<'a> {
+        Fields::new(self.buf, self.offsets)
+    }
+
+    /// The decoder's field index: one `(tag, value_start, value_end)` entry
+    /// per field in wire order, with byte offsets into
+    /// [`raw_buf`](Self::raw_buf) (`value_end` is the SOH, exclusive).
+    ///
+    /// For consumers that build their own structures on top of the parse —
+    /// columnar extraction, shared-memory publishing, custom validators —
+    /// without re-scanning the bytes. `field(i)` is
+    /// `&raw_buf()[start as usize..end as usize]` of entry `i`.
+    #[inline]
+    pub fn raw_offsets(&self) -> &'a [(Tag, u32, u32)] {
+        self.offsets
+    }
+
+    /// The bytes the message was decoded from (or built into), which
+    /// [`raw_offsets`](Self::raw_offsets) index.
+    #[inline]
+    pub fn raw_buf(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// The standard header: the leading run of header fields (8, 9, 35,
+    /// CompIDs, MsgSeqNum, SendingTime, NO_HOPS, …).
+    ///
+    /// Lets a routing layer look at addressing and sequencing without touching
+    /// body fields. Header tags that appear after the first body field belong
+    /// to the body section, as they would fail layout validation anyway.
+    #[inline]
+    pub fn header(&self) -> Section<'a> {
+        self.section(0..self.header_end())
+    }
+
+    /// The application fields between the header and the trailer.
+    #[inline]
+    pub fn body(&self) -> Section<'a> {
+        self.section(self.header_end()..self.trailer_start())
+    }
+
+    /// The standard trailer: the closing run of SignatureLength (93),
+    /// Signature (89) and CheckSum (10).
+    #[inline]
+    pub fn trailer(&self) -> Section<'a> {
+        self.section(self.trailer_start()..self.offsets.len())
+    }
+
+    #[inline]
+    fn section(&self, range: std::ops::Range<usize>) -> Section<'a> {
+        Section {
+            buf: self.buf,
+            offsets: &self.offsets[range],
+        }
+    }
+
+    fn header_end(&self) -> usize {
+        self.offsets
+            .iter()
+            .position(|&(t, _, _)| !is_header_tag(t))
+            .unwrap_or(self.offsets.len())
+    }
+
+    fn trailer_start(&self) -> usize {
+        let header_end = self.header_end();
+        let trailer_len = self.offsets[header_end..]
+            .iter()
+            .rev()
+            .take_while(|&&(t, _, _)| is_trailer_tag(t))
+            .count();
+        self.offsets.len() - trailer_len
+    }
+
+    /// Copy this message into an [`OwnedMessage`] that does not borrow the
+    /// decoder or the input buffer, so it can be stored or sent to another
+    /// thread.
+    pub fn to_owned(&self) -> OwnedMessage {
+        OwnedMessage::new(self.buf, self.offsets)
+    }
+
+    /// Collect every field into a map from tag to its values in wire order.
+    /// Group members are flattened: a tag repeated across instances lists
+    /// each instance's value.
+    ///
+    /// Meant for scripts and tests; values stay borrowed from the buffer but
+    /// the map itself allocates.
+    pub fn to_map(&self) -> FieldMap<'a> {
+        map::flat(self.buf, self.offsets)
+    }
+
+    /// Like [`to_map`](Self::to_map), but with each repeating group of a
+    /// built-in spec nested under its `NO_*` count tag as a list of instance
+    /// maps.
+    pub fn to_nested_map(&self) -> NestedFieldMap<'a> {
+        map::nested(self.buf, self.offsets)
     }
 
     /// Return the value of tag 8 (`BEGIN_STRING`) as a byte slice, or `None`
@@ -101,23 +252,26 @@ impl<'a> Message<'a> {
         self.find(tag::BEGIN_STRING).map(|f| f.value)
     }
 
+    /// Return tag 35 (`MSG_TYPE`) as a [`MsgType`], or `None` if the field is
+    /// absent or not a standard value.
+    #[inline]
+    pub fn msg_type(&self) -> Option<MsgType> {
+        self.find(tag::MSG_TYPE)
+            .and_then(|f| MsgType::from_bytes(f.value))
+    }
+
     /// Find the first field with the given tag, or `None` if not present.
     ///
     /// The sorted index is built lazily on the first call (O(n log n)) and
     /// cached for subsequent calls (O(log n) binary search). If `find()` is
-    /// never called, the sort never happens.
+    /// never called, the sort never happens. Messages with fewer than 12
+    /// fields are scanned linearly instead, which is faster at that size.
     #[inline]
     pub fn find(&self, tag: Tag) -> Option<Field<'a>> {
-        let sorted = self.sorted.get_or_init(|| {
-            let mut v: SmallVec<[(Tag, u16); SORTED_CAPACITY]> =
-                SmallVec::with_capacity(self.offsets.len());
-            for (i, &(t, _, _)) in self.offsets.iter().enumerate() {
-                v.push((t, i as u16));
-            }
-            v.sort_unstable_by_key(|&(t, _)| t);
-            v
-        });
-
+        if self.scan_linearly() {
+            return position_of(self.offsets, tag).map(|i| self.field(i));
+        }
+        let sorted = self.sorted_index();
         let idx = sorted.partition_point(|&(t, _)| t < tag);
         let &(found_tag, offset_idx) = sorted.get(idx)?;
         if found_tag != tag {
@@ -130,6 +284,131 @@ impl<'a> Message<'a> {
         })
     }
 
+    /// The first `tag` field as a [`LazyValue`] of type `T` (`u64`, `i64`,
+    /// `f64` or [`FixDecimal`](crate::decimal::FixDecimal)), or `None` if
+    /// absent. Nothing is parsed until [`LazyValue::get`], and the result
+    /// is cached on this message for every later read of the same tag and
+    /// type.
+    #[inline]
+    pub fn lazy<T: LazyParse>(&self, tag: Tag) -> Option<LazyValue<'_, 'a, T>> {
+        self.find(tag).map(|f| LazyValue::new(self, f))
+    }
+
+    /// Parse `field` as `T` through the value cache.
+    pub(crate) fn cached_value<T: LazyParse>(&self, field: &Field<'a>) -> Result<T, FixError> {
+        let hit = self
+            .values
+            .borrow()
+            .iter()
+            .filter(|(t, _)| *t == field.tag)
+            .find_map(|(_, p)| T::unwrap(p));
+        if let Some(v) = hit {
+            return Ok(v);
+        }
+        let v = T::parse(field)?;
+        let mut values = self.values.borrow_mut();
+        if values.capacity() == 0 {
+            values.reserve_exact(4);
+        }
+        values.push((field.tag, v.wrap()));
+        Ok(v)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn cached_values(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn has_sorted_index(&self) -> bool {
+        self.sorted.get().is_some()
+    }
+
+    /// Every field with the given tag, in wire order — for tags that repeat
+    /// across group instances or legitimately appear more than once.
+    ///
+    /// Uses the same lazily built sorted index as [`find`](Self::find), and
+    /// like it scans small messages linearly instead.
+    #[inline]
+    pub fn find_all(&self, tag: Tag) -> impl Iterator<Item = Field<'a>> + '_ {
+        let (linear, indexed) = if self.scan_linearly() {
+            let positions = (0..self.offsets.len()).filter(move |&i| self.offsets[i].0 == tag);
+            (Some(positions), None)
+        } else {
+            let sorted = self.sorted_index();
+            let from = sorted.partition_point(|&(t, _)| t < tag);
+            let to = from + sorted[from..].partition_point(|&(t, _)| t == tag);
+            (
+                None,
+                Some(sorted[from..to].iter().map(|&(_, i)| i as usize)),
+            )
+        };
+        linear
+            .into_iter()
+            .flatten()
+            .chain(indexed.into_iter().flatten())
+            .map(move |i| self.field(i))
+    }
+
+    /// The first field with the given tag at position `start` or later,
+    /// with its position — for walking a message in wire order and resuming
+    /// the search where the last match left off.
+    ///
+    /// Positions are the indices used by [`field`](Self::field). Uses the
+    /// sorted index, so each call is O(log n), except on small messages,
+    /// which are scanned as in [`find`](Self::find).
+    pub fn find_from(&self, start: usize, tag: Tag) -> Option<(usize, Field<'a>)> {
+        if self.scan_linearly() {
+            let rest = self.offsets.get(start..)?;
+            return position_of(rest, tag).map(|i| (start + i, self.field(start + i)));
+        }
+        let sorted = self.sorted_index();
+        let from = sorted.partition_point(|&(t, i)| t < tag || (t == tag && (i as usize) < start));
+        let &(found_tag, i) = sorted.get(from)?;
+        if found_tag != tag {
+            return None;
+        }
+        Some((i as usize, self.field(i as usize)))
+    }
+
+    /// The first field with the given tag after position `index`; see
+    /// [`find_from`](Self::find_from).
+    #[inline]
+    pub fn find_after(&self, index: usize, tag: Tag) -> Option<(usize, Field<'a>)> {
+        self.find_from(index.saturating_add(1), tag)
+    }
+
+    /// Position of the first `count_tag`: a binary search if `find` has
+    /// already built the sorted index, otherwise a chunked linear scan.
+    #[inline]
+    fn count_position(&self, count_tag: Tag) -> Option<usize> {
+        match self.sorted.get() {
+            Some(_) => self.find_from(0, count_tag).map(|(i, _)| i),
+            None => position_of(self.offsets, count_tag),
+        }
+    }
+
+    /// Whether lookups should scan `offsets` rather than build the sorted
+    /// index: true for small messages unless the index already exists.
+    #[inline]
+    fn scan_linearly(&self) -> bool {
+        self.offsets.len() < LINEAR_SCAN_MAX_FIELDS && self.sorted.get().is_none()
+    }
+
+    /// `(tag, index)` pairs sorted by tag, then by position, so equal tags
+    /// stay in wire order.
+    fn sorted_index(&self) -> &[(Tag, u32)] {
+        self.sorted.get_or_init(|| {
+            let mut v: SmallVec<[(Tag, u32); SORTED_CAPACITY]> =
+                SmallVec::with_capacity(self.offsets.len());
+            for (i, &(t, _, _)) in self.offsets.iter().enumerate() {
+                v.push((t, i as u32));
+            }
+            v.sort_unstable();
+            v
+        })
+    }
+
     /// Return an iterator over the instances of the repeating group described
     /// by `spec`.
     ///
@@ -146,11 +425,12 @@ impl<'a> Message<'a> {
     /// ```
     #[inline]
     pub fn groups(&self, spec: &GroupSpec) -> GroupIter<'a> {
+        if let Some(cache) = self.groups_cache.get() {
+            return self.cached_groups(cache, spec);
+        }
+
         // Find the NO_* count tag position.
-        let pos = self
-            .offsets
-            .iter()
-            .position(|&(t, _, _)| t == spec.count_tag);
+        let pos = self.count_position(spec.count_tag);
 
         let (count, remaining) = match pos {
             None => (0, &[][..]),
@@ -168,29 +448,106 @@ impl<'a> Message<'a> {
             delimiter_tag: spec.delimiter_tag,
             count,
             emitted: 0,
+            ends: None,
         }
     }
 
-    /// Return an iterator over every repeating group present in this message.
+    /// `groups()` through the cache: scan the group once, then serve
+    /// instances from the recorded boundaries.
+    fn cached_groups(&self, cache: &RefCell<Vec<GroupBounds>>, spec: &GroupSpec) -> GroupIter<'a> {
+        let key = |b: &GroupBounds| {
+            b.count_tag == spec.count_tag && b.delimiter_tag == spec.delimiter_tag
+        };
+        if let Some(b) = cache.borrow().iter().find(|b| key(b)) {
+            return GroupIter {
+                buf: self.buf,
+                remaining: &self.offsets[b.first..],
+                delimiter_tag: spec.delimiter_tag,
+                count: b.count,
+                emitted: 0,
+                ends: Some(b.ends.clone()),
+            };
+        }
+
+        let (first, count) = match self.count_position(spec.count_tag) {
+            Some(i) => {
+                let (_, start, end) = self.offsets[i];
+                (i + 1, parse_count(&self.buf[start as usize..end as usize]))
+            }
+            None => (self.offsets.len(), 0),
+        };
+        let scan = GroupIter {
+            buf: self.buf,
+            remaining: &self.offsets[first..],
+            delimiter_tag: spec.delimiter_tag,
+            count,
+            emitted: 0,
+            ends: None,
+        };
+        let mut end = 0u32;
+        let ends: Arc<[u32]> = scan
+            .map(|g| {
+                end += g.offsets.len() as u32;
+                end
+            })
+            .collect();
+        cache.borrow_mut().push(GroupBounds {
+            count_tag: spec.count_tag,
+            delimiter_tag: spec.delimiter_tag,
+            first,
+            count,
+            ends: ends.clone(),
+        });
+        GroupIter {
+            buf: self.buf,
+            remaining: &self.offsets[first..],
+            delimiter_tag: spec.delimiter_tag,
+            count,
+            emitted: 0,
+            ends: Some(ends),
+        }
+    }
+
+    /// The whole repeating group described by `spec` in wire order: the
+    /// count tag followed by every instance, nested groups included.
+    /// Empty if the count tag is absent.
     ///
-    /// Scans the appropriate group spec array based on the FIX version detected
-    /// from tag 8 (`BEGIN_STRING`): `FIX42_GROUPS` for FIX 4.2 messages, and
-    /// both `FIX42_GROUPS` + `FIX44_GROUPS` for FIX 4.4 messages (which is a
-    /// superset). Yields `(&'static GroupSpec, GroupIter<'a>)` for each spec
-    /// whose count tag is found in the message with a non-zero count. Groups
-    /// whose count tag is absent or zero are skipped.
+    /// # Example
+    /// ```ignore
+    /// // Echo the quote's parties back on the order.
+    /// order.extend(quote.group_fields(&group::PARTY_IDS));
+    /// ```
+    pub fn group_fields(&self, spec: &GroupSpec) -> Fields<'a> {
+        let range = match self.count_position(spec.count_tag) {
+            Some(i) => i..group_end(self.offsets, self.buf, i, spec),
+            None => 0..0,
+        };
+        Fields::new(self.buf, &self.offsets[range])
+    }
+
+    /// Validate the message against `dict`: header and trailer placement,
+    /// required and legal fields per MsgType (see
+    /// [`Validator::check_layout`]) and enumerated values (see
+    /// [`Validator::check_enums`]).
     ///
-    /// The order follows the order of the spec arrays, not the order fields
-    /// appear in the message.
+    /// # Errors
+    /// Returns every violation found, structural ones first.
     ///
     /// # Example
     /// ```ignore
-    /// for (spec, instances) in msg.all_groups() {
-    ///     for g in instances {
-    ///         // process each group instance
-    ///     }
+    /// if let Err(violations) = msg.validate_with(&dict) {
+    ///     let v = violations[0];
+    ///     reject(v.tag, v.reason.code());
     /// }
     /// ```
+    pub fn validate_with(&self, dict: &Dictionary) -> Result<(), Vec<Violation<'a>>> {
+        let validator = Validator::new(dict);
+        let mut out = Vec::new();
+        validator.check_layout(self, &mut out);
+        validator.check_enums(self, &mut out);
+        if out.is_empty() { Ok(()) } else { Err(out) }
+    }
+
     /// Validate the BodyLength field (tag 9).
     ///
     /// A FIX message body spans from the first byte after the `9=…\x01` field
@@ -205,28 +562,44 @@ impl<'a> Message<'a> {
     /// - Tag 10 is not the last field.
     /// - The computed byte count does not match the declared value.
     pub fn validate_body_length(&self) -> Result<(), FixError> {
+        let (declared, computed) = self.body_length_values()?;
+        if computed == declared {
+            Ok(())
+        } else {
+            Err(FixError::InvalidBodyLength {
+                offset: self.field_start(1),
+            })
+        }
+    }
+
+    /// `(declared, computed)` BodyLength; `InvalidBodyLength` if the
+    /// message is too malformed to compute it.
+    pub(crate) fn body_length_values(&self) -> Result<(usize, usize), FixError> {
+        let invalid = || FixError::InvalidBodyLength {
+            offset: self.field_start(1),
+        };
         let n = self.offsets.len();
         if n < 3 {
-            return Err(FixError::InvalidBodyLength);
+            return Err(invalid());
         }
 
         // Tag 9 must be the second field.
         let (tag9, _, body_length_value_end) = self.offsets[1];
         if tag9 != tag::BODY_LENGTH {
-            return Err(FixError::InvalidBodyLength);
+            return Err(invalid());
         }
 
         // Tag 10 must be the last field.
         let (tag10, checksum_value_start, _) = self.offsets[n - 1];
         if tag10 != tag::CHECK_SUM {
-            return Err(FixError::InvalidBodyLength);
+            return Err(invalid());
         }
 
         // Parse the declared body length from the raw buffer.
         let declared = parse_body_length(
             &self.buf[self.offsets[1].1 as usize..body_length_value_end as usize],
         )
-        .ok_or(FixError::InvalidBodyLength)?;
+        .ok_or_else(invalid)?;
 
         // Body bytes: from (SOH of tag-9 field + 1) to (start of "10=" tag bytes).
         // "10=" is 3 bytes, so the tag-10 field starts at checksum_value_start - 3.
@@ -234,11 +607,7 @@ impl<'a> Message<'a> {
         let checksum_tag_start = checksum_value_start as usize - 3; // len("10=") == 3
         let computed = checksum_tag_start.saturating_sub(body_start);
 
-        if computed == declared {
-            Ok(())
-        } else {
-            Err(FixError::InvalidBodyLength)
-        }
+        Ok((declared, computed))
     }
 
     /// Validate the CheckSum field (tag 10).
@@ -254,41 +623,88 @@ impl<'a> Message<'a> {
     /// - Tag 10 is not the last field or its value cannot be parsed.
     /// - The computed checksum does not match the declared value.
     pub fn validate_checksum(&self) -> Result<(), FixError> {
+        let (declared, computed) = self.checksum_values()?;
+        if computed == declared {
+            Ok(())
+        } else {
+            Err(FixError::InvalidCheckSum {
+                offset: self.field_start(self.offsets.len() - 1),
+            })
+        }
+    }
+
+    /// `(declared, computed)` CheckSum; `InvalidCheckSum` if the message is
+    /// too malformed to compute it.
+    pub(crate) fn checksum_values(&self) -> Result<(u8, u8), FixError> {
         let n = self.offsets.len();
+        let invalid = || FixError::InvalidCheckSum {
+            offset: self.field_start(n.saturating_sub(1)),
+        };
         if n == 0 {
-            return Err(FixError::InvalidCheckSum);
+            return Err(invalid());
         }
 
         // Tag 10 must be the last field.
         let (tag10, checksum_value_start, checksum_value_end) = self.offsets[n - 1];
         if tag10 != tag::CHECK_SUM {
-            return Err(FixError::InvalidCheckSum);
+            return Err(invalid());
         }
 
         // Parse the declared checksum from the raw buffer.
         let declared =
             parse_checksum(&self.buf[checksum_value_start as usize..checksum_value_end as usize])
-                .ok_or(FixError::InvalidCheckSum)?;
+                .ok_or_else(invalid)?;
 
         // Checksum covers all bytes before the "10=" tag bytes.
         let checksum_tag_start = checksum_value_start as usize - 3; // len("10=") == 3
         let computed = compute_checksum(&self.buf[..checksum_tag_start]);
 
-        if computed == declared {
-            Ok(())
-        } else {
-            Err(FixError::InvalidCheckSum)
+        Ok((declared, computed))
+    }
+
+    /// Byte offset where field `index` starts: just after the SOH ending the
+    /// field before it, or the end of the buffer past the last field.
+    fn field_start(&self, index: usize) -> usize {
+        match index.checked_sub(1).and_then(|i| self.offsets.get(i)) {
+            Some(&(_, _, end)) => end as usize + 1,
+            None if index == 0 => 0,
+            None => self.buf.len(),
         }
     }
 
+    /// The built-in group specs for this message's FIX version, from
+    /// BeginString (8) and, on FIXT.1.1, ApplVerID (1128); see
+    /// [`group::specs_for_version`](crate::group::specs_for_version).
     #[inline]
-    pub fn all_groups(&self) -> impl Iterator<Item = (&'static GroupSpec, GroupIter<'a>)> + '_ {
-        let specs: &[&GroupSpec] = match self.fix_version() {
-            Some(b"FIX.4.4") => FIX44_GROUPS,
-            _ => FIX42_GROUPS,
-        };
+    pub fn group_specs(&self) -> &'static [&'static GroupSpec] {
+        specs_for_version(
+            self.fix_version(),
+            self.find(tag::APPL_VER_ID).map(|f| f.value),
+        )
+    }
 
-        specs.iter().copied().filter_map(|spec| {
+    /// Return an iterator over every repeating group present in this message.
+    ///
+    /// Scans the group spec array for the FIX version of the message, as
+    /// chosen by [`group_specs`](Self::group_specs). Yields
+    /// `(&'static GroupSpec, GroupIter<'a>)` for each spec whose count tag is
+    /// found in the message with a non-zero count. Groups whose count tag is
+    /// absent or zero are skipped.
+    ///
+    /// The order follows the order of the spec arrays, not the order fields
+    /// appear in the message.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for (spec, instances) in msg.all_groups() {
+    ///     for g in instances {
+    ///         // process each group instance
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn all_groups(&self) -> impl Iterator<Item = (&'static GroupSpec, GroupIter<'a>)> + '_ {
+        self.group_specs().iter().copied().filter_map(|spec| {
             // Check if the count tag is present with a non-zero count.
             let found = self.offsets.iter().find(|&&(t, _, _)| t == spec.count_tag);
             let &(_, start, end) = found?;
@@ -300,3 +716,34 @@ impl<'a> Message<'a> {
         })
     }
 }
+
+/// `8=FIX.4.4|9=5|35=0|10=163|`, for logs; `{:#}` writes tag names
+/// instead of numbers (`BeginString=FIX.4.4|…`).
+impl std::fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        display_fields(self.fields(), f)
+    }
+}
+
+impl<'a> IntoIterator for &Message<'a> {
+    type Item = Field<'a>;
+    type IntoIter = Fields<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Fields<'a> {
+        Fields::new(self.buf, self.offsets)
+    }
+}
+
+/// `msg[i]` is the value of the field at position `i`, with the same
+/// out-of-bounds panic as slice indexing. Use [`Message::find`] to look a field
+/// up by tag.
+impl std::ops::Index<usize> for Message<'_> {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &[u8] {
+        let (_, start, end) = self.offsets[index];
+        &self.buf[start as usize..end as usize]
+    }
+}
@@ -0,0 +1,462 @@
+use crate::error::FixError;
+
+/// Standard MsgType (35) values, FIX 4.0 through 4.4.
+///
+/// Values outside this set (user-defined `U*` types, FIX 5.0 additions)
+/// have no variant; [`MsgType::from_bytes`] returns `None` for them and the
+/// raw bytes remain available through [`Message::find`](crate::message::Message::find).
+///
+/// # Example
+/// ```ignore
+/// match msg.msg_type() {
+///     Some(MsgType::ExecutionReport) => orders.on_report(&msg),
+///     Some(t) if t.is_admin() => {}
+///     _ => reject_unsupported(&msg),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MsgType {
+    Heartbeat,
+    TestRequest,
+    ResendRequest,
+    Reject,
+    SequenceReset,
+    Logout,
+    IndicationOfInterest,
+    Advertisement,
+    ExecutionReport,
+    OrderCancelReject,
+    Logon,
+    News,
+    Email,
+    NewOrderSingle,
+    NewOrderList,
+    OrderCancelRequest,
+    OrderCancelReplaceRequest,
+    OrderStatusRequest,
+    AllocationInstruction,
+    ListCancelRequest,
+    ListExecute,
+    ListStatusRequest,
+    ListStatus,
+    AllocationInstructionAck,
+    DontKnowTrade,
+    QuoteRequest,
+    Quote,
+    SettlementInstructions,
+    MarketDataRequest,
+    MarketDataSnapshotFullRefresh,
+    MarketDataIncrementalRefresh,
+    MarketDataRequestReject,
+    QuoteCancel,
+    QuoteStatusRequest,
+    MassQuoteAcknowledgement,
+    SecurityDefinitionRequest,
+    SecurityDefinition,
+    SecurityStatusRequest,
+    SecurityStatus,
+    TradingSessionStatusRequest,
+    TradingSessionStatus,
+    MassQuote,
+    BusinessMessageReject,
+    BidRequest,
+    BidResponse,
+    ListStrikePrice,
+    XmlMessage,
+    RegistrationInstructions,
+    RegistrationInstructionsResponse,
+    OrderMassCancelRequest,
+    OrderMassCancelReport,
+    NewOrderCross,
+    CrossOrderCancelReplaceRequest,
+    CrossOrderCancelRequest,
+    SecurityTypeRequest,
+    SecurityTypes,
+    SecurityListRequest,
+    SecurityList,
+    DerivativeSecurityListRequest,
+    DerivativeSecurityList,
+    NewOrderMultileg,
+    MultilegOrderCancelReplace,
+    TradeCaptureReportRequest,
+    TradeCaptureReport,
+    OrderMassStatusRequest,
+    QuoteRequestReject,
+    RfqRequest,
+    QuoteStatusReport,
+    QuoteResponse,
+    Confirmation,
+    PositionMaintenanceRequest,
+    PositionMaintenanceReport,
+    RequestForPositions,
+    RequestForPositionsAck,
+    PositionReport,
+    TradeCaptureReportRequestAck,
+    TradeCaptureReportAck,
+    AllocationReport,
+    AllocationReportAck,
+    ConfirmationAck,
+    SettlementInstructionRequest,
+    AssignmentReport,
+    CollateralRequest,
+    CollateralAssignment,
+    CollateralResponse,
+    CollateralReport,
+    CollateralInquiry,
+    NetworkCounterpartySystemStatusRequest,
+    NetworkCounterpartySystemStatusResponse,
+    UserRequest,
+    UserResponse,
+    CollateralInquiryAck,
+    ConfirmationRequest,
+}
+
+impl MsgType {
+    /// Every variant, in wire-value order.
+    pub const ALL: [MsgType; 93] = [
+        Self::Heartbeat,
+        Self::TestRequest,
+        Self::ResendRequest,
+        Self::Reject,
+        Self::SequenceReset,
+        Self::Logout,
+        Self::IndicationOfInterest,
+        Self::Advertisement,
+        Self::ExecutionReport,
+        Self::OrderCancelReject,
+        Self::Logon,
+        Self::News,
+        Self::Email,
+        Self::NewOrderSingle,
+        Self::NewOrderList,
+        Self::OrderCancelRequest,
+        Self::OrderCancelReplaceRequest,
+        Self::OrderStatusRequest,
+        Self::AllocationInstruction,
+        Self::ListCancelRequest,
+        Self::ListExecute,
+        Self::ListStatusRequest,
+        Self::ListStatus,
+        Self::AllocationInstructionAck,
+        Self::DontKnowTrade,
+        Self::QuoteRequest,
+        Self::Quote,
+        Self::SettlementInstructions,
+        Self::MarketDataRequest,
+        Self::MarketDataSnapshotFullRefresh,
+        Self::MarketDataIncrementalRefresh,
+        Self::MarketDataRequestReject,
+        Self::QuoteCancel,
+        Self::QuoteStatusRequest,
+        Self::MassQuoteAcknowledgement,
+        Self::SecurityDefinitionRequest,
+        Self::SecurityDefinition,
+        Self::SecurityStatusRequest,
+        Self::SecurityStatus,
+        Self::TradingSessionStatusRequest,
+        Self::TradingSessionStatus,
+        Self::MassQuote,
+        Self::BusinessMessageReject,
+        Self::BidRequest,
+        Self::BidResponse,
+        Self::ListStrikePrice,
+        Self::XmlMessage,
+        Self::RegistrationInstructions,
+        Self::RegistrationInstructionsResponse,
+        Self::OrderMassCancelRequest,
+        Self::OrderMassCancelReport,
+        Self::NewOrderCross,
+        Self::CrossOrderCancelReplaceRequest,
+        Self::CrossOrderCancelRequest,
+        Self::SecurityTypeRequest,
+        Self::SecurityTypes,
+        Self::SecurityListRequest,
+        Self::SecurityList,
+        Self::DerivativeSecurityListRequest,
+        Self::DerivativeSecurityList,
+        Self::NewOrderMultileg,
+        Self::MultilegOrderCancelReplace,
+        Self::TradeCaptureReportRequest,
+        Self::TradeCaptureReport,
+        Self::OrderMassStatusRequest,
+        Self::QuoteRequestReject,
+        Self::RfqRequest,
+        Self::QuoteStatusReport,
+        Self::QuoteResponse,
+        Self::Confirmation,
+        Self::PositionMaintenanceRequest,
+        Self::PositionMaintenanceReport,
+        Self::RequestForPositions,
+        Self::RequestForPositionsAck,
+        Self::PositionReport,
+        Self::TradeCaptureReportRequestAck,
+        Self::TradeCaptureReportAck,
+        Self::AllocationReport,
+        Self::AllocationReportAck,
+        Self::ConfirmationAck,
+        Self::SettlementInstructionRequest,
+        Self::AssignmentReport,
+        Self::CollateralRequest,
+        Self::CollateralAssignment,
+        Self::CollateralResponse,
+        Self::CollateralReport,
+        Self::CollateralInquiry,
+        Self::NetworkCounterpartySystemStatusRequest,
+        Self::NetworkCounterpartySystemStatusResponse,
+        Self::UserRequest,
+        Self::UserResponse,
+        Self::CollateralInquiryAck,
+        Self::ConfirmationRequest,
+    ];
+
+    /// The MsgType for a tag 35 value, or `None` if `bytes` is not a
+    /// standard value.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(match bytes {
+            b"0" => Self::Heartbeat,
+            b"1" => Self::TestRequest,
+            b"2" => Self::ResendRequest,
+            b"3" => Self::Reject,
+            b"4" => Self::SequenceReset,
+            b"5" => Self::Logout,
+            b"6" => Self::IndicationOfInterest,
+            b"7" => Self::Advertisement,
+            b"8" => Self::ExecutionReport,
+            b"9" => Self::OrderCancelReject,
+            b"A" => Self::Logon,
+            b"B" => Self::News,
+            b"C" => Self::Email,
+            b"D" => Self::NewOrderSingle,
+            b"E" => Self::NewOrderList,
+            b"F" => Self::OrderCancelRequest,
+            b"G" => Self::OrderCancelReplaceRequest,
+            b"H" => Self::OrderStatusRequest,
+            b"J" => Self::AllocationInstruction,
+            b"K" => Self::ListCancelRequest,
+            b"L" => Self::ListExecute,
+            b"M" => Self::ListStatusRequest,
+            b"N" => Self::ListStatus,
+            b"P" => Self::AllocationInstructionAck,
+            b"Q" => Self::DontKnowTrade,
+            b"R" => Self::QuoteRequest,
+            b"S" => Self::Quote,
+            b"T" => Self::SettlementInstructions,
+            b"V" => Self::MarketDataRequest,
+            b"W" => Self::MarketDataSnapshotFullRefresh,
+            b"X" => Self::MarketDataIncrementalRefresh,
+            b"Y" => Self::MarketDataRequestReject,
+            b"Z" => Self::QuoteCancel,
+            b"a" => Self::QuoteStatusRequest,
+            b"b" => Self::MassQuoteAcknowledgement,
+            b"c" => Self::SecurityDefinitionRequest,
+            b"d" => Self::SecurityDefinition,
+            b"e" => Self::SecurityStatusRequest,
+            b"f" => Self::SecurityStatus,
+            b"g" => Self::TradingSessionStatusRequest,
+            b"h" => Self::TradingSessionStatus,
+            b"i" => Self::MassQuote,
+            b"j" => Self::BusinessMessageReject,
+            b"k" => Self::BidRequest,
+            b"l" => Self::BidResponse,
+            b"m" => Self::ListStrikePrice,
+            b"n" => Self::XmlMessage,
+            b"o" => Self::RegistrationInstructions,
+            b"p" => Self::RegistrationInstructionsResponse,
+            b"q" => Self::OrderMassCancelRequest,
+            b"r" => Self::OrderMassCancelReport,
+            b"s" => Self::NewOrderCross,
+            b"t" => Self::CrossOrderCancelReplaceRequest,
+            b"u" => Self::CrossOrderCancelRequest,
+            b"v" => Self::SecurityTypeRequest,
+            b"w" => Self::SecurityTypes,
+            b"x" => Self::SecurityListRequest,
+            b"y" => Self::SecurityList,
+            b"z" => Self::DerivativeSecurityListRequest,
+            b"AA" => Self::DerivativeSecurityList,
+            b"AB" => Self::NewOrderMultileg,
+            b"AC" => Self::MultilegOrderCancelReplace,
+            b"AD" => Self::TradeCaptureReportRequest,
+            b"AE" => Self::TradeCaptureReport,
+            b"AF" => Self::OrderMassStatusRequest,
+            b"AG" => Self::QuoteRequestReject,
+            b"AH" => Self::RfqRequest,
+            b"AI" => Self::QuoteStatusReport,
+            b"AJ" => Self::QuoteResponse,
+            b"AK" => Self::Confirmation,
+            b"AL" => Self::PositionMaintenanceRequest,
+            b"AM" => Self::PositionMaintenanceReport,
+            b"AN" => Self::RequestForPositions,
+            b"AO" => Self::RequestForPositionsAck,
+            b"AP" => Self::PositionReport,
+            b"AQ" => Self::TradeCaptureReportRequestAck,
+            b"AR" => Self::TradeCaptureReportAck,
+            b"AS" => Self::AllocationReport,
+            b"AT" => Self::AllocationReportAck,
+            b"AU" => Self::ConfirmationAck,
+            b"AV" => Self::SettlementInstructionRequest,
+            b"AW" => Self::AssignmentReport,
+            b"AX" => Self::CollateralRequest,
+            b"AY" => Self::CollateralAssignment,
+            b"AZ" => Self::CollateralResponse,
+            b"BA" => Self::CollateralReport,
+            b"BB" => Self::CollateralInquiry,
+            b"BC" => Self::NetworkCounterpartySystemStatusRequest,
+            b"BD" => Self::NetworkCounterpartySystemStatusResponse,
+            b"BE" => Self::UserRequest,
+            b"BF" => Self::UserResponse,
+            b"BG" => Self::CollateralInquiryAck,
+            b"BH" => Self::ConfirmationRequest,
+            _ => return None,
+        })
+    }
+
+    /// The tag 35 value.
+    pub fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Heartbeat => b"0",
+            Self::TestRequest => b"1",
+            Self::ResendRequest => b"2",
+            Self::Reject => b"3",
+            Self::SequenceReset => b"4",
+            Self::Logout => b"5",
+            Self::IndicationOfInterest => b"6",
+            Self::Advertisement => b"7",
+            Self::ExecutionReport => b"8",
+            Self::OrderCancelReject => b"9",
+            Self::Logon => b"A",
+            Self::News => b"B",
+            Self::Email => b"C",
+            Self::NewOrderSingle => b"D",
+            Self::NewOrderList => b"E",
+            Self::OrderCancelRequest => b"F",
+            Self::OrderCancelReplaceRequest => b"G",
+            Self::OrderStatusRequest => b"H",
+            Self::AllocationInstruction => b"J",
+            Self::ListCancelRequest => b"K",
+            Self::ListExecute => b"L",
+            Self::ListStatusRequest => b"M",
+            Self::ListStatus => b"N",
+            Self::AllocationInstructionAck => b"P",
+            Self::DontKnowTrade => b"Q",
+            Self::QuoteRequest => b"R",
+            Self::Quote => b"S",
+            Self::SettlementInstructions => b"T",
+            Self::MarketDataRequest => b"V",
+            Self::MarketDataSnapshotFullRefresh => b"W",
+            Self::MarketDataIncrementalRefresh => b"X",
+            Self::MarketDataRequestReject => b"Y",
+            Self::QuoteCancel => b"Z",
+            Self::QuoteStatusRequest => b"a",
+            Self::MassQuoteAcknowledgement => b"b",
+            Self::SecurityDefinitionRequest => b"c",
+            Self::SecurityDefinition => b"d",
+            Self::SecurityStatusRequest => b"e",
+            Self::SecurityStatus => b"f",
+            Self::TradingSessionStatusRequest => b"g",
+            Self::TradingSessionStatus => b"h",
+            Self::MassQuote => b"i",
+            Self::BusinessMessageReject => b"j",
+            Self::BidRequest => b"k",
+            Self::BidResponse => b"l",
+            Self::ListStrikePrice => b"m",
+            Self::XmlMessage => b"n",
+            Self::RegistrationInstructions => b"o",
+            Self::RegistrationInstructionsResponse => b"p",
+            Self::OrderMassCancelRequest => b"q",
+            Self::OrderMassCancelReport => b"r",
+            Self::NewOrderCross => b"s",
+            Self::CrossOrderCancelReplaceRequest => b"t",
+            Self::CrossOrderCancelRequest => b"u",
+            Self::SecurityTypeRequest => b"v",
+            Self::SecurityTypes => b"w",
+            Self::SecurityListRequest => b"x",
+            Self::SecurityList => b"y",
+            Self::DerivativeSecurityListRequest => b"z",
+            Self::DerivativeSecurityList => b"AA",
+            Self::NewOrderMultileg => b"AB",
+            Self::MultilegOrderCancelReplace => b"AC",
+            Self::TradeCaptureReportRequest => b"AD",
+            Self::TradeCaptureReport => b"AE",
+            Self::OrderMassStatusRequest => b"AF",
+            Self::QuoteRequestReject => b"AG",
+            Self::RfqRequest => b"AH",
+            Self::QuoteStatusReport => b"AI",
+            Self::QuoteResponse => b"AJ",
+            Self::Confirmation => b"AK",
+            Self::PositionMaintenanceRequest => b"AL",
+            Self::PositionMaintenanceReport => b"AM",
+            Self::RequestForPositions => b"AN",
+            Self::RequestForPositionsAck => b"AO",
+            Self::PositionReport => b"AP",
+            Self::TradeCaptureReportRequestAck => b"AQ",
+            Self::TradeCaptureReportAck => b"AR",
+            Self::AllocationReport => b"AS",
+            Self::AllocationReportAck => b"AT",
+            Self::ConfirmationAck => b"AU",
+            Self::SettlementInstructionRequest => b"AV",
+            Self::AssignmentReport => b"AW",
+            Self::CollateralRequest => b"AX",
+            Self::CollateralAssignment => b"AY",
+            Self::CollateralResponse => b"AZ",
+            Self::CollateralReport => b"BA",
+            Self::CollateralInquiry => b"BB",
+            Self::NetworkCounterpartySystemStatusRequest => b"BC",
+            Self::NetworkCounterpartySystemStatusResponse => b"BD",
+            Self::UserRequest => b"BE",
+            Self::UserResponse => b"BF",
+            Self::CollateralInquiryAck => b"BG",
+            Self::ConfirmationRequest => b"BH",
+        }
+    }
+
+    /// Returns true for the session-level (administrative) messages:
+    /// Heartbeat, TestRequest, ResendRequest, Reject, SequenceReset, Logout
+    /// and Logon.
+    pub fn is_admin(self) -> bool {
+        matches!(
+            self,
+            Self::Heartbeat
+                | Self::TestRequest
+                | Self::ResendRequest
+                | Self::Reject
+                | Self::SequenceReset
+                | Self::Logout
+                | Self::Logon
+        )
+    }
+}
+
+impl TryFrom<&[u8]> for MsgType {
+    type Error = FixError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes).ok_or(FixError::InvalidValue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::dictionary::is_admin_msg_type;
+
+    #[test]
+    fn bytes_roundtrip() {
+        for t in MsgType::ALL {
+            assert_eq!(MsgType::from_bytes(t.as_bytes()), Some(t));
+            assert_eq!(t.is_admin(), is_admin_msg_type(t.as_bytes()));
+        }
+        assert_eq!(MsgType::from_bytes(b"U1"), None);
+        assert_eq!(MsgType::from_bytes(b""), None);
+        assert!(MsgType::try_from(&b"AE"[..]).is_ok());
+    }
+
+    #[test]
+    fn message_msg_type() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=8\x0111=A\x01").unwrap();
+        assert_eq!(msg.msg_type(), Some(MsgType::ExecutionReport));
+        let msg = dec.decode(b"35=U7\x01").unwrap();
+        assert_eq!(msg.msg_type(), None);
+    }
+}
@@ -0,0 +1,435 @@
+use crate::builder::MessageBuilder;
+use crate::decimal::FixDecimal;
+use crate::error::FixError;
+use crate::group::{self, GroupIter};
+use crate::message::Message;
+use crate::msg_type::MsgType;
+use crate::tag::{self, Tag};
+use crate::validate::{SessionRejectReason, Violation};
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+/// One `NO_LEGS` instance of a multileg order.
+#[derive(Debug, Clone, Default)]
+pub struct MultilegLeg<'a> {
+    pub symbol: &'a [u8],
+    /// LegRatioQty (623): quantity of this leg per unit of the strategy.
+    pub ratio_qty: Option<&'a [u8]>,
+    /// LegSide (624).
+    pub side: Option<&'a [u8]>,
+    pub security_id: Option<&'a [u8]>,
+    pub security_id_source: Option<&'a [u8]>,
+    pub cfi_code: Option<&'a [u8]>,
+    pub maturity_month_year: Option<&'a [u8]>,
+    pub strike_price: Option<&'a [u8]>,
+    pub security_exchange: Option<&'a [u8]>,
+    pub position_effect: Option<&'a [u8]>,
+    pub price: Option<&'a [u8]>,
+    /// LegRefID (654), echoed on the leg's fills.
+    pub ref_id: Option<&'a [u8]>,
+    /// `(LegSecurityAltID (605), LegSecurityAltIDSource (606))` pairs.
+    pub alt_ids: Vec<(&'a [u8], &'a [u8])>,
+    /// `(LegStipulationType (688), LegStipulationValue (689))` pairs.
+    pub stipulations: Vec<(&'a [u8], &'a [u8])>,
+}
+
+/// Read access to a NewOrderMultileg (35=AB) or MultilegOrderCancelReplace
+/// (35=AC).
+///
+/// # Example
+/// ```ignore
+/// let order = MultilegOrder::new(&msg)?;
+/// let mut violations = Vec::new();
+/// order.check_legs(&mut violations);
+/// if let Some(v) = violations.first() {
+///     reject(v.tag, v.reason.code());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MultilegOrder<'a> {
+    msg: &'a Message<'a>,
+    msg_type: MsgType,
+}
+
+impl<'a> MultilegOrder<'a> {
+    /// `InvalidValue` if `msg` is not a NewOrderMultileg or
+    /// MultilegOrderCancelReplace.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        match msg.msg_type() {
+            Some(t @ (MsgType::NewOrderMultileg | MsgType::MultilegOrderCancelReplace)) => {
+                Ok(Self { msg, msg_type: t })
+            }
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+
+    /// Which of the two messages this is.
+    #[inline]
+    pub fn msg_type(&self) -> MsgType {
+        self.msg_type
+    }
+
+    pub fn cl_ord_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CL_ORD_ID)
+    }
+
+    /// OrigClOrdID (41), on a cancel/replace.
+    pub fn orig_cl_ord_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ORIG_CL_ORD_ID)
+    }
+
+    pub fn order_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ORDER_ID)
+    }
+
+    /// Side (54) of the strategy as a whole.
+    pub fn side(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SIDE)
+    }
+
+    pub fn symbol(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SYMBOL)
+    }
+
+    pub fn order_qty(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ORDER_QTY)
+    }
+
+    pub fn ord_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ORD_TYPE)
+    }
+
+    pub fn price(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::PRICE)
+    }
+
+    pub fn transact_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRANSACT_TIME)
+    }
+
+    /// MultiLegRptTypeReq (563): `0` report by strategy, `1` by leg, `2`
+    /// both.
+    pub fn rpt_type_req(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::MULTI_LEG_RPT_TYPE_REQ)
+    }
+
+    /// The `NO_LEGS` instances.
+    pub fn legs(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::LEGS)
+    }
+
+    /// Check that the order has legs and that every leg carries LegSide
+    /// (624) and a positive LegRatioQty (623). A missing field is reported
+    /// with [`SessionRejectReason::RequiredTagMissing`] (1), a ratio that
+    /// is not a positive number with
+    /// [`SessionRejectReason::ValueIsIncorrect`] (5).
+    pub fn check_legs(&self, out: &mut Vec<Violation<'a>>) {
+        let missing = |tag| Violation {
+            tag,
+            reason: SessionRejectReason::RequiredTagMissing,
+            value: &[],
+        };
+        let mut legs = 0;
+        for leg in self.legs() {
+            legs += 1;
+            match leg.find(tag::LEG_RATIO_QTY) {
+                None => out.push(missing(tag::LEG_RATIO_QTY)),
+                Some(f) => {
+                    let positive = FixDecimal::parse(f.value).is_ok_and(|d| d.mantissa() > 0);
+                    if !positive {
+                        out.push(Violation {
+                            tag: tag::LEG_RATIO_QTY,
+                            reason: SessionRejectReason::ValueIsIncorrect,
+                            value: f.value,
+                        });
+                    }
+                }
+            }
+            if leg.find(tag::LEG_SIDE).is_none() {
+                out.push(missing(tag::LEG_SIDE));
+            }
+        }
+        if legs == 0 {
+            out.push(missing(tag::NO_LEGS));
+        }
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of a NewOrderMultileg (35=AB) or
+/// MultilegOrderCancelReplace (35=AC).
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// MultilegOrderBuilder::new(b"ML1", b"1", b"ES-SPREAD", b"2", b"20240229-13:45:06")
+///     .order_qty(b"10")
+///     .price(b"-1.25")
+///     .leg(MultilegLeg { symbol: b"ESH4", ratio_qty: Some(b"1"), side: Some(b"1"), ..Default::default() })
+///     .leg(MultilegLeg { symbol: b"ESM4", ratio_qty: Some(b"1"), side: Some(b"2"), ..Default::default() })
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilegOrderBuilder<'a> {
+    cl_ord_id: &'a [u8],
+    side: &'a [u8],
+    symbol: &'a [u8],
+    ord_type: &'a [u8],
+    transact_time: &'a [u8],
+    orig_cl_ord_id: Option<&'a [u8]>,
+    order_id: Option<&'a [u8]>,
+    account: Option<&'a [u8]>,
+    order_qty: Option<&'a [u8]>,
+    price: Option<&'a [u8]>,
+    time_in_force: Option<&'a [u8]>,
+    rpt_type_req: Option<&'a [u8]>,
+    legs: Vec<MultilegLeg<'a>>,
+}
+
+impl<'a> MultilegOrderBuilder<'a> {
+    pub fn new(
+        cl_ord_id: &'a [u8],
+        side: &'a [u8],
+        symbol: &'a [u8],
+        ord_type: &'a [u8],
+        transact_time: &'a [u8],
+    ) -> Self {
+        Self {
+            cl_ord_id,
+            side,
+            symbol,
+            ord_type,
+            transact_time,
+            orig_cl_ord_id: None,
+            order_id: None,
+            account: None,
+            order_qty: None,
+            price: None,
+            time_in_force: None,
+            rpt_type_req: None,
+            legs: Vec::new(),
+        }
+    }
+
+    /// Make this a MultilegOrderCancelReplace (35=AC) of `orig_cl_ord_id`.
+    pub fn replaces(mut self, orig_cl_ord_id: &'a [u8]) -> Self {
+        self.orig_cl_ord_id = Some(orig_cl_ord_id);
+        self
+    }
+
+    /// OrderID (37) of the order being replaced.
+    pub fn order_id(mut self, order_id: &'a [u8]) -> Self {
+        self.order_id = Some(order_id);
+        self
+    }
+
+    pub fn account(mut self, account: &'a [u8]) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    pub fn order_qty(mut self, qty: &'a [u8]) -> Self {
+        self.order_qty = Some(qty);
+        self
+    }
+
+    pub fn price(mut self, price: &'a [u8]) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: &'a [u8]) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// MultiLegRptTypeReq (563).
+    pub fn rpt_type_req(mut self, rpt_type_req: &'a [u8]) -> Self {
+        self.rpt_type_req = Some(rpt_type_req);
+        self
+    }
+
+    /// Add a `NO_LEGS` instance.
+    pub fn leg(mut self, leg: MultilegLeg<'a>) -> Self {
+        self.legs.push(leg);
+        self
+    }
+
+    /// Append the MsgType (`AB`, or `AC` after [`replaces`](Self::replaces))
+    /// and the order fields to `b`. Header fields other than MsgType are
+    /// the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        let msg_type = match self.orig_cl_ord_id {
+            Some(_) => MsgType::MultilegOrderCancelReplace,
+            None => MsgType::NewOrderMultileg,
+        };
+        b.push(tag::MSG_TYPE, msg_type.as_bytes());
+        if let Some(v) = self.order_id {
+            b.push(tag::ORDER_ID, v);
+        }
+        if let Some(v) = self.orig_cl_ord_id {
+            b.push(tag::ORIG_CL_ORD_ID, v);
+        }
+        b.push(tag::CL_ORD_ID, self.cl_ord_id);
+        if let Some(v) = self.account {
+            b.push(tag::ACCOUNT, v);
+        }
+        b.push(tag::SIDE, self.side).push(tag::SYMBOL, self.symbol);
+
+        let mut g = b.begin_group(&group::LEGS);
+        for leg in &self.legs {
+            g.instance().push(tag::LEG_SYMBOL, leg.symbol);
+            for (t, v) in [
+                (tag::LEG_SECURITY_ID, leg.security_id),
+                (tag::LEG_SECURITY_ID_SOURCE, leg.security_id_source),
+                (tag::LEG_CFI_CODE, leg.cfi_code),
+                (tag::LEG_MATURITY_MONTH_YEAR, leg.maturity_month_year),
+                (tag::LEG_STRIKE_PRICE, leg.strike_price),
+                (tag::LEG_SECURITY_EXCHANGE, leg.security_exchange),
+                (tag::LEG_RATIO_QTY, leg.ratio_qty),
+                (tag::LEG_SIDE, leg.side),
+                (tag::LEG_POSITION_EFFECT, leg.position_effect),
+                (tag::LEG_PRICE, leg.price),
+                (tag::LEG_REF_ID, leg.ref_id),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+            if !leg.alt_ids.is_empty() {
+                let mut alt = g.begin_group(&group::LEG_SECURITY_ALT_IDS);
+                for (id, source) in &leg.alt_ids {
+                    alt.instance()
+                        .push(tag::LEG_SECURITY_ALT_ID, id)
+                        .push(tag::LEG_SECURITY_ALT_ID_SOURCE, source);
+                }
+            }
+            if !leg.stipulations.is_empty() {
+                let mut stip = g.begin_group(&group::LEG_STIPULATIONS);
+                for (stip_type, stip_value) in &leg.stipulations {
+                    stip.instance()
+                        .push(tag::LEG_STIPULATION_TYPE, stip_type)
+                        .push(tag::LEG_STIPULATION_VALUE, stip_value);
+                }
+            }
+        }
+        drop(g);
+
+        b.push(tag::TRANSACT_TIME, self.transact_time);
+        if let Some(v) = self.order_qty {
+            b.push(tag::ORDER_QTY, v);
+        }
+        b.push(tag::ORD_TYPE, self.ord_type);
+        for (t, v) in [
+            (tag::PRICE, self.price),
+            (tag::TIME_IN_FORCE, self.time_in_force),
+            (tag::MULTI_LEG_RPT_TYPE_REQ, self.rpt_type_req),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn multileg_round_trip_with_nested_leg_groups() {
+        let mut b = MessageBuilder::new();
+        MultilegOrderBuilder::new(b"ML1", b"1", b"ES-SPREAD", b"2", b"20240229-13:45:06")
+            .order_qty(b"10")
+            .price(b"-1.25")
+            .leg(MultilegLeg {
+                symbol: b"ESH4",
+                ratio_qty: Some(b"1"),
+                side: Some(b"1"),
+                alt_ids: vec![(b"ESH24", b"8"), (b"XS1", b"4")],
+                ..Default::default()
+            })
+            .leg(MultilegLeg {
+                symbol: b"ESM4",
+                ratio_qty: Some(b"1"),
+                side: Some(b"2"),
+                stipulations: vec![(b"MINQTY", b"5")],
+                ..Default::default()
+            })
+            .write_to(&mut b);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let order = MultilegOrder::new(&msg).unwrap();
+        assert_eq!(order.msg_type(), MsgType::NewOrderMultileg);
+        let legs: Vec<_> = order.legs().collect();
+        assert_eq!(legs.len(), 2);
+        assert_eq!(legs[0].groups(&group::LEG_SECURITY_ALT_IDS).count(), 2);
+        assert_eq!(legs[1].find(tag::LEG_SYMBOL).unwrap().value, b"ESM4");
+        let stip = legs[1].groups(&group::LEG_STIPULATIONS).next().unwrap();
+        assert_eq!(stip.find(tag::LEG_STIPULATION_VALUE).unwrap().value, b"5");
+        assert_eq!(order.ord_type(), Some(&b"2"[..]));
+
+        let mut violations = Vec::new();
+        order.check_legs(&mut violations);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn check_legs_reports_missing_ratio_and_side() {
+        let mut b = MessageBuilder::new();
+        MultilegOrderBuilder::new(b"ML2", b"1", b"SPRD", b"1", b"20240229-13:45:06")
+            .replaces(b"ML1")
+            .leg(MultilegLeg {
+                symbol: b"A",
+                side: Some(b"1"),
+                ..Default::default()
+            })
+            .leg(MultilegLeg {
+                symbol: b"B",
+                ratio_qty: Some(b"0"),
+                ..Default::default()
+            })
+            .write_to(&mut b);
+        let msg = b.as_message();
+        let order = MultilegOrder::new(&msg).unwrap();
+        assert_eq!(order.msg_type(), MsgType::MultilegOrderCancelReplace);
+        assert_eq!(order.orig_cl_ord_id(), Some(&b"ML1"[..]));
+
+        let mut violations = Vec::new();
+        order.check_legs(&mut violations);
+        let found: Vec<_> = violations
+            .iter()
+            .map(|v| (v.tag, v.reason.code()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (tag::LEG_RATIO_QTY, 1),
+                (tag::LEG_RATIO_QTY, 5),
+                (tag::LEG_SIDE, 1)
+            ]
+        );
+
+        b.clear();
+        MultilegOrderBuilder::new(b"ML3", b"1", b"SPRD", b"1", b"20240229-13:45:06")
+            .write_to(&mut b);
+        let msg = b.as_message();
+        let mut violations = Vec::new();
+        MultilegOrder::new(&msg)
+            .unwrap()
+            .check_legs(&mut violations);
+        assert_eq!(violations[0].tag, tag::NO_LEGS);
+    }
+}
@@ -0,0 +1,2075 @@
+//! FIX field names by tag, as used by the FIX JSON encoding.
+//!
+//! Names follow the constants in [`tag`](crate::tag): for a tag renamed in
+//! FIX 4.4, the FIX 4.4 name is used.
+
+use crate::tag::Tag;
+
+/// `(tag, name)` sorted by tag.
+const BY_TAG: &[(Tag, &str)] = &[
+    (1, "Account"),
+    (2, "AdvID"),
+    (3, "AdvRefID"),
+    (4, "AdvSide"),
+    (5, "AdvTransType"),
+    (6, "AvgPx"),
+    (7, "BeginSeqNo"),
+    (8, "BeginString"),
+    (9, "BodyLength"),
+    (10, "CheckSum"),
+    (11, "ClOrdID"),
+    (12, "Commission"),
+    (13, "CommType"),
+    (14, "CumQty"),
+    (15, "Currency"),
+    (16, "EndSeqNo"),
+    (17, "ExecID"),
+    (18, "ExecInst"),
+    (19, "ExecRefID"),
+    (20, "ExecTransType"),
+    (21, "HandlInst"),
+    (22, "SecurityIDSource"),
+    (23, "IOIID"),
+    (24, "IOIOthSvc"),
+    (25, "IOIQltyInd"),
+    (26, "IOIRefID"),
+    (27, "IOIQty"),
+    (28, "IOITransType"),
+    (29, "LastCapacity"),
+    (30, "LastMkt"),
+    (31, "LastPx"),
+    (32, "LastQty"),
+    (33, "LinesOfText"),
+    (34, "MsgSeqNum"),
+    (35, "MsgType"),
+    (36, "NewSeqNo"),
+    (37, "OrderID"),
+    (38, "OrderQty"),
+    (39, "OrdStatus"),
+    (40, "OrdType"),
+    (41, "OrigClOrdID"),
+    (42, "OrigTime"),
+    (43, "PossDupFlag"),
+    (44, "Price"),
+    (45, "RefSeqNum"),
+    (46, "RelatdSym"),
+    (47, "Rule80a"),
+    (48, "SecurityID"),
+    (49, "SenderCompID"),
+    (50, "SenderSubID"),
+    (51, "SendingDate"),
+    (52, "SendingTime"),
+    (53, "Quantity"),
+    (54, "Side"),
+    (55, "Symbol"),
+    (56, "TargetCompID"),
+    (57, "TargetSubID"),
+    (58, "Text"),
+    (59, "TimeInForce"),
+    (60, "TransactTime"),
+    (61, "Urgency"),
+    (62, "ValidUntilTime"),
+    (63, "SettlType"),
+    (64, "SettlDate"),
+    (65, "SymbolSfx"),
+    (66, "ListID"),
+    (67, "ListSeqNo"),
+    (68, "TotNoOrders"),
+    (69, "ListExecInst"),
+    (70, "AllocID"),
+    (71, "AllocTransType"),
+    (72, "RefAllocID"),
+    (73, "NoOrders"),
+    (74, "AvgPrxPrecision"),
+    (75, "TradeDate"),
+    (76, "ExecBroker"),
+    (77, "PositionEffect"),
+    (78, "NoAllocs"),
+    (79, "AllocAccount"),
+    (80, "AllocQty"),
+    (81, "ProcessCode"),
+    (82, "NoRpts"),
+    (83, "RptSeq"),
+    (84, "CxlQty"),
+    (85, "NoDlvyInst"),
+    (86, "DlvyInst"),
+    (87, "AllocStatus"),
+    (88, "AllocRejCode"),
+    (89, "Signature"),
+    (90, "SecureDataLen"),
+    (91, "SecureData"),
+    (92, "BrokerOfCredit"),
+    (93, "SignatureLength"),
+    (94, "EmailType"),
+    (95, "RawDataLength"),
+    (96, "RawData"),
+    (97, "PossResend"),
+    (98, "EncryptMethod"),
+    (99, "StopPx"),
+    (100, "ExDestination"),
+    (102, "CxlRejReason"),
+    (103, "OrdRejReason"),
+    (104, "IOIQualifier"),
+    (105, "WaveNo"),
+    (106, "Issuer"),
+    (107, "SecurityDesc"),
+    (108, "HeartBtInt"),
+    (109, "ClientID"),
+    (110, "MinQty"),
+    (111, "MaxFloor"),
+    (112, "TestReqID"),
+    (113, "ReportToExch"),
+    (114, "LocateReqd"),
+    (115, "OnBehalfOfCompID"),
+    (116, "OnBehalfOfSubID"),
+    (117, "QuoteID"),
+    (118, "NetMoney"),
+    (119, "SettlCurrAmt"),
+    (120, "SettlCurrency"),
+    (121, "ForexReq"),
+    (122, "OrigSendingTime"),
+    (123, "GapFillFlag"),
+    (124, "NoExecs"),
+    (125, "CxlType"),
+    (126, "ExpireTime"),
+    (127, "DKReason"),
+    (128, "DeliverToCompID"),
+    (129, "DeliverToSubID"),
+    (130, "IOINaturalFlag"),
+    (131, "QuoteReqID"),
+    (132, "BidPx"),
+    (133, "OfferPx"),
+    (134, "BidSize"),
+    (135, "OfferSize"),
+    (136, "NoMiscFees"),
+    (137, "MiscFeeAmt"),
+    (138, "MiscFeeCurr"),
+    (139, "MiscFeeType"),
+    (140, "PrevClosePx"),
+    (141, "ResetSeqNumFlag"),
+    (142, "SenderLocationID"),
+    (143, "TargetLocationID"),
+    (144, "OnBehalfOfLocationID"),
+    (145, "DeliverToLocationID"),
+    (146, "NoRelatedSym"),
+    (147, "Subject"),
+    (148, "Headline"),
+    (149, "URLLink"),
+    (150, "ExecType"),
+    (151, "LeavesQty"),
+    (152, "CashOrderQty"),
+    (153, "AllocAvgPx"),
+    (154, "AllocNetMoney"),
+    (155, "SettlCurrFxRate"),
+    (156, "SettlCurrFxRateCalc"),
+    (157, "NumDaysInterest"),
+    (158, "AccruedInterestRate"),
+    (159, "AccruedInterestAmt"),
+    (160, "SettlInstMode"),
+    (161, "AllocText"),
+    (162, "SettlInstID"),
+    (163, "SettlInstTransType"),
+    (164, "EmailThreadID"),
+    (165, "SettlInstSource"),
+    (166, "SettlLocation"),
+    (167, "SecurityType"),
+    (168, "EffectiveTime"),
+    (169, "StandInstDbType"),
+    (170, "StandInstDbName"),
+    (171, "StandInstDbID"),
+    (172, "SettlDeliveryType"),
+    (173, "SettlDepositoryCode"),
+    (174, "SettlBrkrCode"),
+    (175, "SettlInstCode"),
+    (176, "SecuritySettlAgentName"),
+    (177, "SecuritySettlAgentCode"),
+    (178, "SecuritySettlAgentAcctNum"),
+    (179, "SecuritySettlAgentAcctName"),
+    (180, "SecuritySettlAgentContactName"),
+    (181, "SecuritySettlAgentContactPhone"),
+    (182, "CashSettlAgentName"),
+    (183, "CashSettlAgentCode"),
+    (184, "CashSettlAgentAcctNum"),
+    (185, "CashSettlAgentAcctName"),
+    (186, "CashSettlAgentContactName"),
+    (187, "CashSettlAgentContactPhone"),
+    (188, "BidSpotRate"),
+    (189, "BidForwardPoints"),
+    (190, "OfferSpotRate"),
+    (191, "OfferForwardPoints"),
+    (192, "OrderQty2"),
+    (193, "FutSettDate2"),
+    (194, "LastSpotRate"),
+    (195, "LastForwardPoints"),
+    (196, "AllocLinkID"),
+    (197, "AllocLinkType"),
+    (198, "SecondaryOrderID"),
+    (199, "NoIOIQualifiers"),
+    (200, "MaturityMonthYear"),
+    (201, "PutOrCall"),
+    (202, "StrikePrice"),
+    (203, "CoveredOrUncovered"),
+    (204, "CustomerOrFirm"),
+    (205, "MaturityDay"),
+    (206, "OptAttribute"),
+    (207, "SecurityExchange"),
+    (208, "NotifyBrokerOfCredit"),
+    (209, "AllocHandlInst"),
+    (210, "MaxShow"),
+    (211, "PegOffsetValue"),
+    (212, "XmlDataLen"),
+    (213, "XmlData"),
+    (214, "SettlInstRefID"),
+    (215, "NoRoutingIDs"),
+    (216, "RoutingType"),
+    (217, "RoutingID"),
+    (218, "Spread"),
+    (219, "Benchmark"),
+    (220, "BenchmarkCurveCurrency"),
+    (221, "BenchmarkCurveName"),
+    (222, "BenchmarkCurvePoint"),
+    (223, "CouponRate"),
+    (224, "CouponPaymentDate"),
+    (225, "IssueDate"),
+    (226, "RepurchaseTerm"),
+    (227, "RepurchaseRate"),
+    (228, "Factor"),
+    (229, "TradeOriginationDate"),
+    (230, "ExDate"),
+    (231, "ContractMultiplier"),
+    (232, "NoStipulations"),
+    (233, "StipulationType"),
+    (234, "StipulationValue"),
+    (235, "YieldType"),
+    (236, "Yield"),
+    (237, "TotalTakedown"),
+    (238, "Concession"),
+    (239, "RepoCollateralSecurityType"),
+    (240, "RedemptionDate"),
+    (241, "UnderlyingCouponPaymentDate"),
+    (242, "UnderlyingIssueDate"),
+    (243, "UnderlyingRepoCollateralSecurityType"),
+    (244, "UnderlyingRepurchaseTerm"),
+    (245, "UnderlyingRepurchaseRate"),
+    (246, "UnderlyingFactor"),
+    (247, "UnderlyingRedemptionDate"),
+    (248, "LegCouponPaymentDate"),
+    (249, "LegIssueDate"),
+    (250, "LegRepoCollateralSecurityType"),
+    (251, "LegRepurchaseTerm"),
+    (252, "LegRepurchaseRate"),
+    (253, "LegFactor"),
+    (254, "LegRedemptionDate"),
+    (255, "CreditRating"),
+    (256, "UnderlyingCreditRating"),
+    (257, "LegCreditRating"),
+    (258, "TradedFlatSwitch"),
+    (259, "BasisFeatureDate"),
+    (260, "BasisFeaturePrice"),
+    (262, "MDReqID"),
+    (263, "SubscriptionRequestType"),
+    (264, "MarketDepth"),
+    (265, "MDUpdateType"),
+    (266, "AggregatedBook"),
+    (267, "NoMDEntryTypes"),
+    (268, "NoMDEntries"),
+    (269, "MDEntryType"),
+    (270, "MDEntryPx"),
+    (271, "MDEntrySize"),
+    (272, "MDEntryDate"),
+    (273, "MDEntryTime"),
+    (274, "TickDirection"),
+    (275, "MDMkt"),
+    (276, "QuoteCondition"),
+    (277, "TradeCondition"),
+    (278, "MDEntryID"),
+    (279, "MDUpdateAction"),
+    (280, "MDEntryRefID"),
+    (281, "MDReqRejReason"),
+    (282, "MDEntryOriginator"),
+    (283, "LocationID"),
+    (284, "DeskID"),
+    (285, "DeleteReason"),
+    (286, "OpenCloseSettleFlag"),
+    (287, "SellerDays"),
+    (288, "MDEntryBuyer"),
+    (289, "MDEntrySeller"),
+    (290, "MDEntryPositionNo"),
+    (291, "FinancialStatus"),
+    (292, "CorporateAction"),
+    (293, "DefBidSize"),
+    (294, "DefOfferSize"),
+    (295, "NoQuoteEntries"),
+    (296, "NoQuoteSets"),
+    (297, "QuoteAckStatus"),
+    (298, "QuoteCancelType"),
+    (299, "QuoteEntryID"),
+    (300, "QuoteRejectReason"),
+    (301, "QuoteResponseLevel"),
+    (302, "QuoteSetID"),
+    (303, "QuoteRequestType"),
+    (304, "TotQuoteEntries"),
+    (305, "UnderlyingIDSource"),
+    (306, "UnderlyingIssuer"),
+    (307, "UnderlyingSecurityDesc"),
+    (308, "UnderlyingSecurityExchange"),
+    (309, "UnderlyingSecurityID"),
+    (310, "UnderlyingSecurityType"),
+    (311, "UnderlyingSymbol"),
+    (312, "UnderlyingSymbolSfx"),
+    (313, "UnderlyingMaturityMonthYear"),
+    (314, "UnderlyingMaturityDay"),
+    (315, "UnderlyingPutOrCall"),
+    (316, "UnderlyingStrikePrice"),
+    (317, "UnderlyingOptAttribute"),
+    (318, "UnderlyingCurrency"),
+    (319, "RatioQty"),
+    (320, "SecurityReqID"),
+    (321, "SecurityRequestType"),
+    (322, "SecurityResponseID"),
+    (323, "SecurityResponseType"),
+    (324, "SecurityStatusReqID"),
+    (325, "UnsolicitedIndicator"),
+    (326, "SecurityTradingStatus"),
+    (327, "HaltReason"),
+    (328, "InViewOfCommon"),
+    (329, "DueToRelated"),
+    (330, "BuyVolume"),
+    (331, "SellVolume"),
+    (332, "HighPx"),
+    (333, "LowPx"),
+    (334, "Adjustment"),
+    (335, "TradSesReqID"),
+    (336, "TradingSessionID"),
+    (337, "ContraTrader"),
+    (338, "TradSesMethod"),
+    (339, "TradSesMode"),
+    (340, "TradSesStatus"),
+    (341, "TradSesStartTime"),
+    (342, "TradSesOpenTime"),
+    (343, "TradSesPreCloseTime"),
+    (344, "TradSesCloseTime"),
+    (345, "TradSesEndTime"),
+    (346, "NumberOfOrders"),
+    (347, "MessageEncoding"),
+    (348, "EncodedIssuerLen"),
+    (349, "EncodedIssuer"),
+    (350, "EncodedSecurityDescLen"),
+    (351, "EncodedSecurityDesc"),
+    (352, "EncodedListExecInstLen"),
+    (353, "EncodedListExecInst"),
+    (354, "EncodedTextLen"),
+    (355, "EncodedText"),
+    (356, "EncodedSubjectLen"),
+    (357, "EncodedSubject"),
+    (358, "EncodedHeadlineLen"),
+    (359, "EncodedHeadline"),
+    (360, "EncodedAllocTextLen"),
+    (361, "EncodedAllocText"),
+    (362, "EncodedUnderlyingIssuerLen"),
+    (363, "EncodedUnderlyingIssuer"),
+    (364, "EncodedUnderlyingSecurityDescLen"),
+    (365, "EncodedUnderlyingSecurityDesc"),
+    (366, "AllocPrice"),
+    (367, "QuoteSetValidUntilTime"),
+    (368, "QuoteEntryRejectReason"),
+    (369, "LastMsgSeqNumProcessed"),
+    (370, "OnBehalfOfSendingTime"),
+    (371, "RefTagID"),
+    (372, "RefMsgType"),
+    (373, "SessionRejectReason"),
+    (374, "BidRequestTransType"),
+    (375, "ContraBroker"),
+    (376, "ComplianceID"),
+    (377, "SolicitedFlag"),
+    (378, "ExecRestatementReason"),
+    (379, "BusinessRejectRefID"),
+    (380, "BusinessRejectReason"),
+    (381, "GrossTradeAmt"),
+    (382, "NoContraBrokers"),
+    (383, "MaxMessageSize"),
+    (384, "NoMsgTypes"),
+    (385, "MsgDirection"),
+    (386, "NoTradingSessions"),
+    (387, "TotalVolumeTraded"),
+    (388, "DiscretionInst"),
+    (389, "DiscretionOffsetValue"),
+    (390, "BidID"),
+    (391, "ClientBidID"),
+    (392, "ListName"),
+    (393, "TotNoRelatedSym"),
+    (394, "BidType"),
+    (395, "NumTickets"),
+    (396, "SideValue1"),
+    (397, "SideValue2"),
+    (398, "NoBidDescriptors"),
+    (399, "BidDescriptorType"),
+    (400, "BidDescriptor"),
+    (401, "SideValueInd"),
+    (402, "LiquidityPctLow"),
+    (403, "LiquidityPctHigh"),
+    (404, "LiquidityValue"),
+    (405, "EFPTrackingError"),
+    (406, "FairValue"),
+    (407, "OutsideIndexPct"),
+    (408, "ValueOfFutures"),
+    (409, "LiquidityIndType"),
+    (410, "WtAverageLiquidity"),
+    (411, "ExchangeForPhysical"),
+    (412, "OutMainCntryUIndex"),
+    (413, "CrossPercent"),
+    (414, "ProgRptReqs"),
+    (415, "ProgPeriodInterval"),
+    (416, "IncTaxInd"),
+    (417, "NumBidders"),
+    (418, "TradeType"),
+    (419, "BasisPxType"),
+    (420, "NoBidComponents"),
+    (421, "Country"),
+    (422, "TotNoStrikes"),
+    (423, "PriceType"),
+    (424, "DayOrderQty"),
+    (425, "DayCumQty"),
+    (426, "DayAvgPx"),
+    (427, "GTBookingInst"),
+    (428, "NoStrikes"),
+    (429, "ListStatusType"),
+    (430, "NetGrossInd"),
+    (431, "ListOrderStatus"),
+    (432, "ExpireDate"),
+    (433, "ListExecInstType"),
+    (434, "CxlRejResponseTo"),
+    (435, "UnderlyingCouponRate"),
+    (436, "UnderlyingContractMultiplier"),
+    (437, "ContraTradeQty"),
+    (438, "ContraTradeTime"),
+    (439, "ClearingFirm"),
+    (440, "ClearingAccount"),
+    (441, "LiquidityNumSecurities"),
+    (442, "MultiLegReportingType"),
+    (443, "StrikeTime"),
+    (444, "ListStatusText"),
+    (445, "EncodedListStatusTextLen"),
+    (446, "EncodedListStatusText"),
+    (447, "PartyIDSource"),
+    (448, "PartyID"),
+    (449, "TotalVolumeTradedDate"),
+    (450, "TotalVolumeTradedTime"),
+    (451, "NetChgPrevDay"),
+    (452, "PartyRole"),
+    (453, "NoPartyIDs"),
+    (454, "NoSecurityAltID"),
+    (455, "SecurityAltID"),
+    (456, "SecurityAltIDSource"),
+    (457, "NoUnderlyingSecurityAltID"),
+    (458, "UnderlyingSecurityAltID"),
+    (459, "UnderlyingSecurityAltIDSource"),
+    (460, "Product"),
+    (461, "CFICode"),
+    (462, "UnderlyingProduct"),
+    (463, "UnderlyingCFICode"),
+    (464, "TestMessageIndicator"),
+    (465, "QuantityType"),
+    (466, "BookingRefID"),
+    (467, "IndividualAllocID"),
+    (468, "RoundingDirection"),
+    (469, "RoundingModulus"),
+    (470, "CountryOfIssue"),
+    (471, "StateOrProvinceOfIssue"),
+    (472, "LocaleOfIssue"),
+    (473, "NoRegistDtls"),
+    (474, "MailingDtls"),
+    (475, "InvestorCountryOfResidence"),
+    (476, "PaymentRef"),
+    (477, "DistribPaymentMethod"),
+    (478, "CashDistribCurr"),
+    (479, "CommCurrency"),
+    (480, "CancellationRights"),
+    (481, "MoneyLaunderingStatus"),
+    (482, "MailingInst"),
+    (483, "TransBkdTime"),
+    (484, "ExecPriceType"),
+    (485, "ExecPriceAdjustment"),
+    (486, "DateOfBirth"),
+    (487, "TradeReportTransType"),
+    (488, "CardHolderName"),
+    (489, "CardNumber"),
+    (490, "CardExpDate"),
+    (491, "CardIssNum"),
+    (492, "PaymentMethod"),
+    (493, "RegistAcctType"),
+    (494, "Designation"),
+    (495, "TaxAdvantageType"),
+    (496, "RegistRejReasonText"),
+    (497, "FundRenewWaiv"),
+    (498, "CashDistribAgentName"),
+    (499, "CashDistribAgentCode"),
+    (500, "CashDistribAgentAcctNumber"),
+    (501, "CashDistribPayRef"),
+    (502, "CashDistribAgentAcctName"),
+    (503, "CardStartDate"),
+    (504, "PaymentDate"),
+    (505, "PaymentRemitterID"),
+    (506, "RegistStatus"),
+    (507, "RegistRejReasonCode"),
+    (508, "RegistRefID"),
+    (509, "RegistDtls"),
+    (510, "NoDistribInsts"),
+    (511, "RegistEmail"),
+    (512, "DistribPercentage"),
+    (513, "RegistID"),
+    (514, "RegistTransType"),
+    (515, "ExecValuationPoint"),
+    (516, "OrderPercent"),
+    (517, "OwnershipType"),
+    (518, "NoContAmts"),
+    (519, "ContAmtType"),
+    (520, "ContAmtValue"),
+    (521, "ContAmtCurr"),
+    (522, "OwnerType"),
+    (523, "PartySubID"),
+    (524, "NestedPartyID"),
+    (525, "NestedPartyIDSource"),
+    (526, "SecondaryClOrdID"),
+    (527, "SecondaryExecID"),
+    (528, "OrderCapacity"),
+    (529, "OrderRestrictions"),
+    (530, "MassCancelRequestType"),
+    (531, "MassCancelResponse"),
+    (532, "MassCancelRejectReason"),
+    (533, "TotalAffectedOrders"),
+    (534, "NoAffectedOrders"),
+    (535, "AffectedOrderID"),
+    (536, "AffectedSecondaryOrderID"),
+    (537, "QuoteType"),
+    (538, "NestedPartyRole"),
+    (539, "NoNestedPartyIDs"),
+    (540, "TotalAccruedInterestAmt"),
+    (541, "MaturityDate"),
+    (542, "UnderlyingMaturityDate"),
+    (543, "InstrRegistry"),
+    (544, "CashMargin"),
+    (545, "NestedPartySubID"),
+    (546, "Scope"),
+    (547, "MDImplicitDelete"),
+    (548, "CrossID"),
+    (549, "CrossType"),
+    (550, "CrossPrioritization"),
+    (551, "OrigCrossID"),
+    (552, "NoSides"),
+    (553, "Username"),
+    (554, "Password"),
+    (555, "NoLegs"),
+    (556, "LegCurrency"),
+    (557, "TotNoSecurityTypes"),
+    (558, "NoSecurityTypes"),
+    (559, "SecurityListRequestType"),
+    (560, "SecurityRequestResult"),
+    (561, "RoundLot"),
+    (562, "MinTradeVol"),
+    (563, "MultiLegRptTypeReq"),
+    (564, "LegPositionEffect"),
+    (565, "LegCoveredOrUncovered"),
+    (566, "LegPrice"),
+    (567, "TradSesStatusRejReason"),
+    (568, "TradeRequestID"),
+    (569, "TradeRequestType"),
+    (570, "PreviouslyReported"),
+    (571, "TradeReportID"),
+    (572, "TradeReportRefID"),
+    (573, "MatchStatus"),
+    (574, "MatchType"),
+    (575, "OddLot"),
+    (576, "NoClearingInstructions"),
+    (577, "ClearingInstruction"),
+    (578, "TradeInputSource"),
+    (579, "TradeInputDevice"),
+    (580, "NoDates"),
+    (581, "AccountType"),
+    (582, "CustOrderCapacity"),
+    (583, "ClOrdLinkID"),
+    (584, "MassStatusReqID"),
+    (585, "MassStatusReqType"),
+    (586, "OrigOrdModTime"),
+    (587, "LegSettlType"),
+    (588, "LegSettlDate"),
+    (589, "DayBookingInst"),
+    (590, "BookingUnit"),
+    (591, "PreallocMethod"),
+    (592, "UnderlyingCountryOfIssue"),
+    (593, "UnderlyingStateOrProvinceOfIssue"),
+    (594, "UnderlyingLocaleOfIssue"),
+    (595, "UnderlyingInstrRegistry"),
+    (596, "LegCountryOfIssue"),
+    (597, "LegStateOrProvinceOfIssue"),
+    (598, "LegLocaleOfIssue"),
+    (599, "LegInstrRegistry"),
+    (600, "LegSymbol"),
+    (601, "LegSymbolSfx"),
+    (602, "LegSecurityID"),
+    (603, "LegSecurityIDSource"),
+    (604, "NoLegSecurityAltID"),
+    (605, "LegSecurityAltID"),
+    (606, "LegSecurityAltIDSource"),
+    (607, "LegProduct"),
+    (608, "LegCFICode"),
+    (609, "LegSecurityType"),
+    (610, "LegMaturityMonthYear"),
+    (611, "LegMaturityDate"),
+    (612, "LegStrikePrice"),
+    (613, "LegOptAttribute"),
+    (614, "LegContractMultiplier"),
+    (615, "LegCouponRate"),
+    (616, "LegSecurityExchange"),
+    (617, "LegIssuer"),
+    (618, "EncodedLegIssuerLen"),
+    (619, "EncodedLegIssuer"),
+    (620, "LegSecurityDesc"),
+    (621, "EncodedLegSecurityDescLen"),
+    (622, "EncodedLegSecurityDesc"),
+    (623, "LegRatioQty"),
+    (624, "LegSide"),
+    (625, "TradingSessionSubID"),
+    (626, "AllocType"),
+    (627, "NoHops"),
+    (628, "HopCompID"),
+    (629, "HopSendingTime"),
+    (630, "HopRefID"),
+    (631, "MidPx"),
+    (632, "BidYield"),
+    (633, "MidYield"),
+    (634, "OfferYield"),
+    (635, "ClearingFeeIndicator"),
+    (637, "LegLastPx"),
+    (638, "PriorityIndicator"),
+    (639, "PriceImprovement"),
+    (640, "Price2"),
+    (641, "LastForwardPoints2"),
+    (642, "BidForwardPoints2"),
+    (643, "OfferForwardPoints2"),
+    (644, "RFQReqID"),
+    (645, "MktBidPx"),
+    (646, "MktOfferPx"),
+    (647, "MinBidSize"),
+    (648, "MinOfferSize"),
+    (649, "QuoteStatusReqID"),
+    (650, "LegalConfirm"),
+    (651, "UnderlyingLastPx"),
+    (652, "UnderlyingLastQty"),
+    (653, "SecDefStatus"),
+    (654, "LegRefID"),
+    (655, "ContraLegRefID"),
+    (656, "SettlCurrBidFxRate"),
+    (657, "SettlCurrOfferFxRate"),
+    (658, "QuoteRequestRejectReason"),
+    (659, "SideComplianceID"),
+    (660, "AcctIDSource"),
+    (661, "AllocAcctIDSource"),
+    (662, "BenchmarkPrice"),
+    (663, "BenchmarkPriceType"),
+    (664, "ConfirmID"),
+    (665, "ConfirmStatus"),
+    (666, "ConfirmTransType"),
+    (667, "ContractSettlMonth"),
+    (668, "DeliveryForm"),
+    (669, "LastParPx"),
+    (670, "NoLegAllocs"),
+    (671, "LegAllocAccount"),
+    (672, "LegIndividualAllocID"),
+    (673, "LegAllocQty"),
+    (674, "LegAllocAcctIDSource"),
+    (675, "LegSettlCurrency"),
+    (676, "LegBenchmarkCurveCurrency"),
+    (677, "LegBenchmarkCurveName"),
+    (678, "LegBenchmarkCurvePoint"),
+    (679, "LegBenchmarkPrice"),
+    (680, "LegBenchmarkPriceType"),
+    (681, "LegBidPx"),
+    (682, "LegIOIQty"),
+    (683, "NoLegStipulations"),
+    (684, "LegOfferPx"),
+    (685, "LegOrderQty"),
+    (686, "LegPriceType"),
+    (687, "LegQty"),
+    (688, "LegStipulationType"),
+    (689, "LegStipulationValue"),
+    (690, "LegSwapType"),
+    (691, "Pool"),
+    (692, "QuotePriceType"),
+    (693, "QuoteRespID"),
+    (694, "QuoteRespType"),
+    (695, "QuoteQualifier"),
+    (696, "YieldRedemptionDate"),
+    (697, "YieldRedemptionPrice"),
+    (698, "YieldRedemptionPriceType"),
+    (699, "BenchmarkSecurityID"),
+    (700, "ReversalIndicator"),
+    (701, "YieldCalcDate"),
+    (702, "NoPositions"),
+    (703, "PosType"),
+    (704, "LongQty"),
+    (705, "ShortQty"),
+    (706, "PosQtyStatus"),
+    (707, "PosAmtType"),
+    (708, "PosAmt"),
+    (709, "PosTransType"),
+    (710, "PosReqID"),
+    (711, "NoUnderlyings"),
+    (712, "PosMaintAction"),
+    (713, "OrigPosReqRefID"),
+    (714, "PosMaintRptRefID"),
+    (715, "ClearingBusinessDate"),
+    (716, "SettlSessID"),
+    (717, "SettlSessSubID"),
+    (718, "AdjustmentType"),
+    (719, "ContraryInstructionIndicator"),
+    (720, "PriorSpreadIndicator"),
+    (721, "PosMaintRptID"),
+    (722, "PosMaintStatus"),
+    (723, "PosMaintResult"),
+    (724, "PosReqType"),
+    (725, "ResponseTransportType"),
+    (726, "ResponseDestination"),
+    (727, "TotalNumPosReports"),
+    (728, "PosReqResult"),
+    (729, "PosReqStatus"),
+    (730, "SettlPrice"),
+    (731, "SettlPriceType"),
+    (732, "UnderlyingSettlPrice"),
+    (733, "UnderlyingSettlPriceType"),
+    (734, "PriorSettlPrice"),
+    (735, "NoQuoteQualifiers"),
+    (736, "AllocSettlCurrency"),
+    (737, "AllocSettlCurrAmt"),
+    (738, "InterestAtMaturity"),
+    (739, "LegDatedDate"),
+    (740, "LegPool"),
+    (741, "AllocInterestAtMaturity"),
+    (742, "AllocAccruedInterestAmt"),
+    (743, "DeliveryDate"),
+    (744, "AssignmentMethod"),
+    (745, "AssignmentUnit"),
+    (746, "OpenInterest"),
+    (747, "ExerciseMethod"),
+    (748, "TotNumTradeReports"),
+    (749, "TradeRequestResult"),
+    (750, "TradeRequestStatus"),
+    (751, "TradeReportRejectReason"),
+    (752, "SideMultiLegReportingType"),
+    (753, "NoPosAmt"),
+    (754, "AutoAcceptIndicator"),
+    (755, "AllocReportID"),
+    (756, "NoNested2PartyIDs"),
+    (757, "Nested2PartyID"),
+    (758, "Nested2PartyIDSource"),
+    (759, "Nested2PartyRole"),
+    (760, "Nested2PartySubID"),
+    (761, "BenchmarkSecurityIDSource"),
+    (762, "SecuritySubType"),
+    (763, "UnderlyingSecuritySubType"),
+    (764, "LegSecuritySubType"),
+    (765, "AllowableOneSidednessPct"),
+    (766, "AllowableOneSidednessValue"),
+    (767, "AllowableOneSidednessCurr"),
+    (768, "NoTrdRegTimestamps"),
+    (769, "TrdRegTimestamp"),
+    (770, "TrdRegTimestampType"),
+    (771, "TrdRegTimestampOrigin"),
+    (772, "ConfirmRefID"),
+    (773, "ConfirmType"),
+    (774, "ConfirmRejReason"),
+    (775, "BookingType"),
+    (776, "IndividualAllocRejCode"),
+    (777, "SettlInstMsgID"),
+    (778, "NoSettlInst"),
+    (779, "LastUpdateTime"),
+    (780, "AllocSettlInstType"),
+    (781, "NoSettlPartyIDs"),
+    (782, "SettlPartyID"),
+    (783, "SettlPartyIDSource"),
+    (784, "SettlPartyRole"),
+    (785, "SettlPartySubID"),
+    (786, "SettlPartySubIDType"),
+    (787, "DlvyInstType"),
+    (788, "TerminationType"),
+    (789, "NextExpectedMsgSeqNum"),
+    (790, "OrdStatusReqID"),
+    (791, "SettlInstReqID"),
+    (792, "SettlInstReqRejCode"),
+    (793, "SecondaryAllocID"),
+    (794, "AllocReportType"),
+    (795, "AllocReportRefID"),
+    (796, "AllocCancReplaceReason"),
+    (797, "CopyMsgIndicator"),
+    (798, "AllocAccountType"),
+    (799, "OrderAvgPx"),
+    (800, "OrderBookingQty"),
+    (801, "NoSettlPartySubIDs"),
+    (802, "NoPartySubIDs"),
+    (803, "PartySubIDType"),
+    (804, "NoNestedPartySubIDs"),
+    (805, "NestedPartySubIDType"),
+    (806, "NoNested2PartySubIDs"),
+    (807, "Nested2PartySubIDType"),
+    (808, "AllocIntermedReqType"),
+    (810, "UnderlyingPx"),
+    (811, "PriceDelta"),
+    (812, "ApplQueueMax"),
+    (813, "ApplQueueDepth"),
+    (814, "ApplQueueResolution"),
+    (815, "ApplQueueAction"),
+    (816, "NoAltMDSource"),
+    (817, "AltMDSourceID"),
+    (818, "SecondaryTradeReportID"),
+    (819, "AvgPxIndicator"),
+    (820, "TradeLinkID"),
+    (821, "OrderInputDevice"),
+    (822, "UnderlyingTradingSessionID"),
+    (823, "UnderlyingTradingSessionSubID"),
+    (824, "TradeLegRefID"),
+    (825, "ExchangeRule"),
+    (826, "TradeAllocIndicator"),
+    (827, "ExpirationCycle"),
+    (828, "TrdType"),
+    (829, "TrdSubType"),
+    (830, "TransferReason"),
+    (831, "AsgnReqID"),
+    (832, "TotNumAssignmentReports"),
+    (833, "AsgnRptID"),
+    (834, "ThresholdAmount"),
+    (835, "PegMoveType"),
+    (836, "PegOffsetType"),
+    (837, "PegLimitType"),
+    (838, "PegRoundDirection"),
+    (839, "PeggedPrice"),
+    (840, "PegScope"),
+    (841, "DiscretionMoveType"),
+    (842, "DiscretionOffsetType"),
+    (843, "DiscretionLimitType"),
+    (844, "DiscretionRoundDirection"),
+    (845, "DiscretionPrice"),
+    (846, "DiscretionScope"),
+    (847, "TargetStrategy"),
+    (848, "TargetStrategyParameters"),
+    (849, "ParticipationRate"),
+    (850, "TargetStrategyPerformance"),
+    (851, "LastLiquidityInd"),
+    (852, "PublishTrdIndicator"),
+    (853, "ShortSaleReason"),
+    (854, "QtyType"),
+    (855, "SecondaryTrdType"),
+    (856, "TradeReportType"),
+    (857, "AllocNoOrdersType"),
+    (858, "SharedCommission"),
+    (859, "ConfirmReqID"),
+    (860, "AvgParPx"),
+    (861, "ReportedPx"),
+    (862, "NoCapacities"),
+    (863, "OrderCapacityQty"),
+    (864, "NoEvents"),
+    (865, "EventType"),
+    (866, "EventDate"),
+    (867, "EventPx"),
+    (868, "EventText"),
+    (869, "PctAtRisk"),
+    (870, "NoInstrAttrib"),
+    (871, "InstrAttribType"),
+    (872, "InstrAttribValue"),
+    (873, "DatedDate"),
+    (874, "InterestAccrualDate"),
+    (875, "CPProgram"),
+    (876, "CPRegType"),
+    (877, "UnderlyingCPProgram"),
+    (878, "UnderlyingCPRegType"),
+    (879, "UnderlyingQty"),
+    (880, "TrdMatchID"),
+    (881, "SecondaryTradeReportRefID"),
+    (882, "UnderlyingDirtyPrice"),
+    (883, "UnderlyingEndPrice"),
+    (884, "UnderlyingStartValue"),
+    (885, "UnderlyingCurrentValue"),
+    (886, "UnderlyingEndValue"),
+    (887, "NoUnderlyingStips"),
+    (888, "UnderlyingStipType"),
+    (889, "UnderlyingStipValue"),
+    (890, "MaturityNetMoney"),
+    (891, "MiscFeeBasis"),
+    (892, "TotNoAllocs"),
+    (893, "LastFragment"),
+    (894, "CollReqID"),
+    (895, "CollAsgnReason"),
+    (896, "CollInquiryQualifier"),
+    (897, "NoTrades"),
+    (898, "MarginRatio"),
+    (899, "MarginExcess"),
+    (900, "TotalNetValue"),
+    (901, "CashOutstanding"),
+    (902, "CollAsgnID"),
+    (903, "CollAsgnTransType"),
+    (904, "CollRespID"),
+    (905, "CollAsgnRespType"),
+    (906, "CollAsgnRejectReason"),
+    (907, "CollAsgnRefID"),
+    (908, "CollRptID"),
+    (909, "CollInquiryID"),
+    (910, "CollStatus"),
+    (911, "TotNumReports"),
+    (912, "LastRptRequested"),
+    (913, "AgreementDesc"),
+    (914, "AgreementID"),
+    (915, "AgreementDate"),
+    (916, "StartDate"),
+    (917, "EndDate"),
+    (918, "AgreementCurrency"),
+    (919, "DeliveryType"),
+    (920, "EndAccruedInterestAmt"),
+    (921, "StartCash"),
+    (922, "EndCash"),
+    (923, "UserRequestID"),
+    (924, "UserRequestType"),
+    (925, "NewPassword"),
+    (926, "UserStatus"),
+    (927, "UserStatusText"),
+    (928, "StatusValue"),
+    (929, "StatusText"),
+    (930, "RefCompID"),
+    (931, "RefSubID"),
+    (932, "NetworkResponseID"),
+    (933, "NetworkRequestID"),
+    (934, "LastNetworkResponseID"),
+    (935, "NetworkRequestType"),
+    (936, "NoCompIDs"),
+    (937, "NetworkStatusResponseType"),
+    (938, "NoCollInquiryQualifier"),
+    (939, "TrdRptStatus"),
+    (940, "AffirmStatus"),
+    (941, "UnderlyingStrikeCurrency"),
+    (942, "LegStrikeCurrency"),
+    (943, "TimeBracket"),
+    (944, "CollAction"),
+    (945, "CollInquiryStatus"),
+    (946, "CollInquiryResult"),
+    (947, "StrikeCurrency"),
+    (948, "NoNested3PartyIDs"),
+    (949, "Nested3PartyID"),
+    (950, "Nested3PartyIDSource"),
+    (951, "Nested3PartyRole"),
+    (952, "NoNested3PartySubIDs"),
+    (953, "Nested3PartySubID"),
+    (954, "Nested3PartySubIDType"),
+    (955, "LegContractSettlMonth"),
+    (956, "LegInterestAccrualDate"),
+    (957, "NoStrategyParameters"),
+    (958, "StrategyParameterName"),
+    (959, "StrategyParameterType"),
+    (960, "StrategyParameterValue"),
+    (1018, "NoInstrumentParties"),
+    (1019, "InstrumentPartyID"),
+    (1021, "MDBookType"),
+    (1022, "MDFeedType"),
+    (1050, "InstrumentPartyIDSource"),
+    (1051, "InstrumentPartyRole"),
+    (1052, "NoInstrumentPartySubIDs"),
+    (1053, "InstrumentPartySubID"),
+    (1054, "InstrumentPartySubIDType"),
+    (1058, "NoUndlyInstrumentParties"),
+    (1059, "UndlyInstrumentPartyID"),
+    (1060, "UndlyInstrumentPartyIDSource"),
+    (1061, "UndlyInstrumentPartyRole"),
+    (1062, "NoUndlyInstrumentPartySubIDs"),
+    (1063, "UndlyInstrumentPartySubID"),
+    (1064, "UndlyInstrumentPartySubIDType"),
+    (1093, "LotType"),
+    (1116, "NoRootPartyIDs"),
+    (1117, "RootPartyID"),
+    (1118, "RootPartyIDSource"),
+    (1119, "RootPartyRole"),
+    (1120, "NoRootPartySubIDs"),
+    (1121, "RootPartySubID"),
+    (1122, "RootPartySubIDType"),
+    (1128, "ApplVerID"),
+    (1137, "DefaultApplVerID"),
+    (1141, "NoMDFeedTypes"),
+    (1142, "MatchAlgorithm"),
+    (1182, "ApplBegSeqNum"),
+    (1183, "ApplEndSeqNum"),
+    (1205, "NoTickRules"),
+    (1206, "StartTickPriceRange"),
+    (1207, "EndTickPriceRange"),
+    (1208, "TickIncrement"),
+    (1209, "TickRuleType"),
+    (1231, "MinLotSize"),
+    (1232, "NoExecInstRules"),
+    (1234, "NoLotTypeRules"),
+    (1235, "NoMatchRules"),
+    (1237, "NoOrdTypeRules"),
+    (1239, "NoTimeInForceRules"),
+    (1308, "ExecInstValue"),
+    (1351, "NoApplIDs"),
+    (1355, "RefApplID"),
+];
+
+/// `(name, tag)` sorted by name.
+const BY_NAME: &[(&str, Tag)] = &[
+    ("Account", 1),
+    ("AccountType", 581),
+    ("AccruedInterestAmt", 159),
+    ("AccruedInterestRate", 158),
+    ("AcctIDSource", 660),
+    ("Adjustment", 334),
+    ("AdjustmentType", 718),
+    ("AdvID", 2),
+    ("AdvRefID", 3),
+    ("AdvSide", 4),
+    ("AdvTransType", 5),
+    ("AffectedOrderID", 535),
+    ("AffectedSecondaryOrderID", 536),
+    ("AffirmStatus", 940),
+    ("AggregatedBook", 266),
+    ("AgreementCurrency", 918),
+    ("AgreementDate", 915),
+    ("AgreementDesc", 913),
+    ("AgreementID", 914),
+    ("AllocAccount", 79),
+    ("AllocAccountType", 798),
+    ("AllocAccruedInterestAmt", 742),
+    ("AllocAcctIDSource", 661),
+    ("AllocAvgPx", 153),
+    ("AllocCancReplaceReason", 796),
+    ("AllocHandlInst", 209),
+    ("AllocID", 70),
+    ("AllocInterestAtMaturity", 741),
+    ("AllocIntermedReqType", 808),
+    ("AllocLinkID", 196),
+    ("AllocLinkType", 197),
+    ("AllocNetMoney", 154),
+    ("AllocNoOrdersType", 857),
+    ("AllocPrice", 366),
+    ("AllocQty", 80),
+    ("AllocRejCode", 88),
+    ("AllocReportID", 755),
+    ("AllocReportRefID", 795),
+    ("AllocReportType", 794),
+    ("AllocSettlCurrAmt", 737),
+    ("AllocSettlCurrency", 736),
+    ("AllocSettlInstType", 780),
+    ("AllocStatus", 87),
+    ("AllocText", 161),
+    ("AllocTransType", 71),
+    ("AllocType", 626),
+    ("AllowableOneSidednessCurr", 767),
+    ("AllowableOneSidednessPct", 765),
+    ("AllowableOneSidednessValue", 766),
+    ("AltMDSourceID", 817),
+    ("ApplBegSeqNum", 1182),
+    ("ApplEndSeqNum", 1183),
+    ("ApplQueueAction", 815),
+    ("ApplQueueDepth", 813),
+    ("ApplQueueMax", 812),
+    ("ApplQueueResolution", 814),
+    ("ApplVerID", 1128),
+    ("AsgnReqID", 831),
+    ("AsgnRptID", 833),
+    ("AssignmentMethod", 744),
+    ("AssignmentUnit", 745),
+    ("AutoAcceptIndicator", 754),
+    ("AvgParPx", 860),
+    ("AvgPrxPrecision", 74),
+    ("AvgPx", 6),
+    ("AvgPxIndicator", 819),
+    ("BasisFeatureDate", 259),
+    ("BasisFeaturePrice", 260),
+    ("BasisPxType", 419),
+    ("BeginSeqNo", 7),
+    ("BeginString", 8),
+    ("Benchmark", 219),
+    ("BenchmarkCurveCurrency", 220),
+    ("BenchmarkCurveName", 221),
+    ("BenchmarkCurvePoint", 222),
+    ("BenchmarkPrice", 662),
+    ("BenchmarkPriceType", 663),
+    ("BenchmarkSecurityID", 699),
+    ("BenchmarkSecurityIDSource", 761),
+    ("BidDescriptor", 400),
+    ("BidDescriptorType", 399),
+    ("BidForwardPoints", 189),
+    ("BidForwardPoints2", 642),
+    ("BidID", 390),
+    ("BidPx", 132),
+    ("BidRequestTransType", 374),
+    ("BidSize", 134),
+    ("BidSpotRate", 188),
+    ("BidType", 394),
+    ("BidYield", 632),
+    ("BodyLength", 9),
+    ("BookingRefID", 466),
+    ("BookingType", 775),
+    ("BookingUnit", 590),
+    ("BrokerOfCredit", 92),
+    ("BusinessRejectReason", 380),
+    ("BusinessRejectRefID", 379),
+    ("BuyVolume", 330),
+    ("CFICode", 461),
+    ("CPProgram", 875),
+    ("CPRegType", 876),
+    ("CancellationRights", 480),
+    ("CardExpDate", 490),
+    ("CardHolderName", 488),
+    ("CardIssNum", 491),
+    ("CardNumber", 489),
+    ("CardStartDate", 503),
+    ("CashDistribAgentAcctName", 502),
+    ("CashDistribAgentAcctNumber", 500),
+    ("CashDistribAgentCode", 499),
+    ("CashDistribAgentName", 498),
+    ("CashDistribCurr", 478),
+    ("CashDistribPayRef", 501),
+    ("CashMargin", 544),
+    ("CashOrderQty", 152),
+    ("CashOutstanding", 901),
+    ("CashSettlAgentAcctName", 185),
+    ("CashSettlAgentAcctNum", 184),
+    ("CashSettlAgentCode", 183),
+    ("CashSettlAgentContactName", 186),
+    ("CashSettlAgentContactPhone", 187),
+    ("CashSettlAgentName", 182),
+    ("CheckSum", 10),
+    ("ClOrdID", 11),
+    ("ClOrdLinkID", 583),
+    ("ClearingAccount", 440),
+    ("ClearingBusinessDate", 715),
+    ("ClearingFeeIndicator", 635),
+    ("ClearingFirm", 439),
+    ("ClearingInstruction", 577),
+    ("ClientBidID", 391),
+    ("ClientID", 109),
+    ("CollAction", 944),
+    ("CollAsgnID", 902),
+    ("CollAsgnReason", 895),
+    ("CollAsgnRefID", 907),
+    ("CollAsgnRejectReason", 906),
+    ("CollAsgnRespType", 905),
+    ("CollAsgnTransType", 903),
+    ("CollInquiryID", 909),
+    ("CollInquiryQualifier", 896),
+    ("CollInquiryResult", 946),
+    ("CollInquiryStatus", 945),
+    ("CollReqID", 894),
+    ("CollRespID", 904),
+    ("CollRptID", 908),
+    ("CollStatus", 910),
+    ("CommCurrency", 479),
+    ("CommType", 13),
+    ("Commission", 12),
+    ("ComplianceID", 376),
+    ("Concession", 238),
+    ("ConfirmID", 664),
+    ("ConfirmRefID", 772),
+    ("ConfirmRejReason", 774),
+    ("ConfirmReqID", 859),
+    ("ConfirmStatus", 665),
+    ("ConfirmTransType", 666),
+    ("ConfirmType", 773),
+    ("ContAmtCurr", 521),
+    ("ContAmtType", 519),
+    ("ContAmtValue", 520),
+    ("ContraBroker", 375),
+    ("ContraLegRefID", 655),
+    ("ContraTradeQty", 437),
+    ("ContraTradeTime", 438),
+    ("ContraTrader", 337),
+    ("ContractMultiplier", 231),
+    ("ContractSettlMonth", 667),
+    ("ContraryInstructionIndicator", 719),
+    ("CopyMsgIndicator", 797),
+    ("CorporateAction", 292),
+    ("Country", 421),
+    ("CountryOfIssue", 470),
+    ("CouponPaymentDate", 224),
+    ("CouponRate", 223),
+    ("CoveredOrUncovered", 203),
+    ("CreditRating", 255),
+    ("CrossID", 548),
+    ("CrossPercent", 413),
+    ("CrossPrioritization", 550),
+    ("CrossType", 549),
+    ("CumQty", 14),
+    ("Currency", 15),
+    ("CustOrderCapacity", 582),
+    ("CustomerOrFirm", 204),
+    ("CxlQty", 84),
+    ("CxlRejReason", 102),
+    ("CxlRejResponseTo", 434),
+    ("CxlType", 125),
+    ("DKReason", 127),
+    ("DateOfBirth", 486),
+    ("DatedDate", 873),
+    ("DayAvgPx", 426),
+    ("DayBookingInst", 589),
+    ("DayCumQty", 425),
+    ("DayOrderQty", 424),
+    ("DefBidSize", 293),
+    ("DefOfferSize", 294),
+    ("DefaultApplVerID", 1137),
+    ("DeleteReason", 285),
+    ("DeliverToCompID", 128),
+    ("DeliverToLocationID", 145),
+    ("DeliverToSubID", 129),
+    ("DeliveryDate", 743),
+    ("DeliveryForm", 668),
+    ("DeliveryType", 919),
+    ("Designation", 494),
+    ("DeskID", 284),
+    ("DiscretionInst", 388),
+    ("DiscretionLimitType", 843),
+    ("DiscretionMoveType", 841),
+    ("DiscretionOffsetType", 842),
+    ("DiscretionOffsetValue", 389),
+    ("DiscretionPrice", 845),
+    ("DiscretionRoundDirection", 844),
+    ("DiscretionScope", 846),
+    ("DistribPaymentMethod", 477),
+    ("DistribPercentage", 512),
+    ("DlvyInst", 86),
+    ("DlvyInstType", 787),
+    ("DueToRelated", 329),
+    ("EFPTrackingError", 405),
+    ("EffectiveTime", 168),
+    ("EmailThreadID", 164),
+    ("EmailType", 94),
+    ("EncodedAllocText", 361),
+    ("EncodedAllocTextLen", 360),
+    ("EncodedHeadline", 359),
+    ("EncodedHeadlineLen", 358),
+    ("EncodedIssuer", 349),
+    ("EncodedIssuerLen", 348),
+    ("EncodedLegIssuer", 619),
+    ("EncodedLegIssuerLen", 618),
+    ("EncodedLegSecurityDesc", 622),
+    ("EncodedLegSecurityDescLen", 621),
+    ("EncodedListExecInst", 353),
+    ("EncodedListExecInstLen", 352),
+    ("EncodedListStatusText", 446),
+    ("EncodedListStatusTextLen", 445),
+    ("EncodedSecurityDesc", 351),
+    ("EncodedSecurityDescLen", 350),
+    ("EncodedSubject", 357),
+    ("EncodedSubjectLen", 356),
+    ("EncodedText", 355),
+    ("EncodedTextLen", 354),
+    ("EncodedUnderlyingIssuer", 363),
+    ("EncodedUnderlyingIssuerLen", 362),
+    ("EncodedUnderlyingSecurityDesc", 365),
+    ("EncodedUnderlyingSecurityDescLen", 364),
+    ("EncryptMethod", 98),
+    ("EndAccruedInterestAmt", 920),
+    ("EndCash", 922),
+    ("EndDate", 917),
+    ("EndSeqNo", 16),
+    ("EndTickPriceRange", 1207),
+    ("EventDate", 866),
+    ("EventPx", 867),
+    ("EventText", 868),
+    ("EventType", 865),
+    ("ExDate", 230),
+    ("ExDestination", 100),
+    ("ExchangeForPhysical", 411),
+    ("ExchangeRule", 825),
+    ("ExecBroker", 76),
+    ("ExecID", 17),
+    ("ExecInst", 18),
+    ("ExecInstValue", 1308),
+    ("ExecPriceAdjustment", 485),
+    ("ExecPriceType", 484),
+    ("ExecRefID", 19),
+    ("ExecRestatementReason", 378),
+    ("ExecTransType", 20),
+    ("ExecType", 150),
+    ("ExecValuationPoint", 515),
+    ("ExerciseMethod", 747),
+    ("ExpirationCycle", 827),
+    ("ExpireDate", 432),
+    ("ExpireTime", 126),
+    ("Factor", 228),
+    ("FairValue", 406),
+    ("FinancialStatus", 291),
+    ("ForexReq", 121),
+    ("FundRenewWaiv", 497),
+    ("FutSettDate2", 193),
+    ("GTBookingInst", 427),
+    ("GapFillFlag", 123),
+    ("GrossTradeAmt", 381),
+    ("HaltReason", 327),
+    ("HandlInst", 21),
+    ("Headline", 148),
+    ("HeartBtInt", 108),
+    ("HighPx", 332),
+    ("HopCompID", 628),
+    ("HopRefID", 630),
+    ("HopSendingTime", 629),
+    ("IOIID", 23),
+    ("IOINaturalFlag", 130),
+    ("IOIOthSvc", 24),
+    ("IOIQltyInd", 25),
+    ("IOIQty", 27),
+    ("IOIQualifier", 104),
+    ("IOIRefID", 26),
+    ("IOITransType", 28),
+    ("InViewOfCommon", 328),
+    ("IncTaxInd", 416),
+    ("IndividualAllocID", 467),
+    ("IndividualAllocRejCode", 776),
+    ("InstrAttribType", 871),
+    ("InstrAttribValue", 872),
+    ("InstrRegistry", 543),
+    ("InstrumentPartyID", 1019),
+    ("InstrumentPartyIDSource", 1050),
+    ("InstrumentPartyRole", 1051),
+    ("InstrumentPartySubID", 1053),
+    ("InstrumentPartySubIDType", 1054),
+    ("InterestAccrualDate", 874),
+    ("InterestAtMaturity", 738),
+    ("InvestorCountryOfResidence", 475),
+    ("IssueDate", 225),
+    ("Issuer", 106),
+    ("LastCapacity", 29),
+    ("LastForwardPoints", 195),
+    ("LastForwardPoints2", 641),
+    ("LastFragment", 893),
+    ("LastLiquidityInd", 851),
+    ("LastMkt", 30),
+    ("LastMsgSeqNumProcessed", 369),
+    ("LastNetworkResponseID", 934),
+    ("LastParPx", 669),
+    ("LastPx", 31),
+    ("LastQty", 32),
+    ("LastRptRequested", 912),
+    ("LastSpotRate", 194),
+    ("LastUpdateTime", 779),
+    ("LeavesQty", 151),
+    ("LegAllocAccount", 671),
+    ("LegAllocAcctIDSource", 674),
+    ("LegAllocQty", 673),
+    ("LegBenchmarkCurveCurrency", 676),
+    ("LegBenchmarkCurveName", 677),
+    ("LegBenchmarkCurvePoint", 678),
+    ("LegBenchmarkPrice", 679),
+    ("LegBenchmarkPriceType", 680),
+    ("LegBidPx", 681),
+    ("LegCFICode", 608),
+    ("LegContractMultiplier", 614),
+    ("LegContractSettlMonth", 955),
+    ("LegCountryOfIssue", 596),
+    ("LegCouponPaymentDate", 248),
+    ("LegCouponRate", 615),
+    ("LegCoveredOrUncovered", 565),
+    ("LegCreditRating", 257),
+    ("LegCurrency", 556),
+    ("LegDatedDate", 739),
+    ("LegFactor", 253),
+    ("LegIOIQty", 682),
+    ("LegIndividualAllocID", 672),
+    ("LegInstrRegistry", 599),
+    ("LegInterestAccrualDate", 956),
+    ("LegIssueDate", 249),
+    ("LegIssuer", 617),
+    ("LegLastPx", 637),
+    ("LegLocaleOfIssue", 598),
+    ("LegMaturityDate", 611),
+    ("LegMaturityMonthYear", 610),
+    ("LegOfferPx", 684),
+    ("LegOptAttribute", 613),
+    ("LegOrderQty", 685),
+    ("LegPool", 740),
+    ("LegPositionEffect", 564),
+    ("LegPrice", 566),
+    ("LegPriceType", 686),
+    ("LegProduct", 607),
+    ("LegQty", 687),
+    ("LegRatioQty", 623),
+    ("LegRedemptionDate", 254),
+    ("LegRefID", 654),
+    ("LegRepoCollateralSecurityType", 250),
+    ("LegRepurchaseRate", 252),
+    ("LegRepurchaseTerm", 251),
+    ("LegSecurityAltID", 605),
+    ("LegSecurityAltIDSource", 606),
+    ("LegSecurityDesc", 620),
+    ("LegSecurityExchange", 616),
+    ("LegSecurityID", 602),
+    ("LegSecurityIDSource", 603),
+    ("LegSecuritySubType", 764),
+    ("LegSecurityType", 609),
+    ("LegSettlCurrency", 675),
+    ("LegSettlDate", 588),
+    ("LegSettlType", 587),
+    ("LegSide", 624),
+    ("LegStateOrProvinceOfIssue", 597),
+    ("LegStipulationType", 688),
+    ("LegStipulationValue", 689),
+    ("LegStrikeCurrency", 942),
+    ("LegStrikePrice", 612),
+    ("LegSwapType", 690),
+    ("LegSymbol", 600),
+    ("LegSymbolSfx", 601),
+    ("LegalConfirm", 650),
+    ("LinesOfText", 33),
+    ("LiquidityIndType", 409),
+    ("LiquidityNumSecurities", 441),
+    ("LiquidityPctHigh", 403),
+    ("LiquidityPctLow", 402),
+    ("LiquidityValue", 404),
+    ("ListExecInst", 69),
+    ("ListExecInstType", 433),
+    ("ListID", 66),
+    ("ListName", 392),
+    ("ListOrderStatus", 431),
+    ("ListSeqNo", 67),
+    ("ListStatusText", 444),
+    ("ListStatusType", 429),
+    ("LocaleOfIssue", 472),
+    ("LocateReqd", 114),
+    ("LocationID", 283),
+    ("LongQty", 704),
+    ("LotType", 1093),
+    ("LowPx", 333),
+    ("MDBookType", 1021),
+    ("MDEntryBuyer", 288),
+    ("MDEntryDate", 272),
+    ("MDEntryID", 278),
+    ("MDEntryOriginator", 282),
+    ("MDEntryPositionNo", 290),
+    ("MDEntryPx", 270),
+    ("MDEntryRefID", 280),
+    ("MDEntrySeller", 289),
+    ("MDEntrySize", 271),
+    ("MDEntryTime", 273),
+    ("MDEntryType", 269),
+    ("MDFeedType", 1022),
+    ("MDImplicitDelete", 547),
+    ("MDMkt", 275),
+    ("MDReqID", 262),
+    ("MDReqRejReason", 281),
+    ("MDUpdateAction", 279),
+    ("MDUpdateType", 265),
+    ("MailingDtls", 474),
+    ("MailingInst", 482),
+    ("MarginExcess", 899),
+    ("MarginRatio", 898),
+    ("MarketDepth", 264),
+    ("MassCancelRejectReason", 532),
+    ("MassCancelRequestType", 530),
+    ("MassCancelResponse", 531),
+    ("MassStatusReqID", 584),
+    ("MassStatusReqType", 585),
+    ("MatchAlgorithm", 1142),
+    ("MatchStatus", 573),
+    ("MatchType", 574),
+    ("MaturityDate", 541),
+    ("MaturityDay", 205),
+    ("MaturityMonthYear", 200),
+    ("MaturityNetMoney", 890),
+    ("MaxFloor", 111),
+    ("MaxMessageSize", 383),
+    ("MaxShow", 210),
+    ("MessageEncoding", 347),
+    ("MidPx", 631),
+    ("MidYield", 633),
+    ("MinBidSize", 647),
+    ("MinLotSize", 1231),
+    ("MinOfferSize", 648),
+    ("MinQty", 110),
+    ("MinTradeVol", 562),
+    ("MiscFeeAmt", 137),
+    ("MiscFeeBasis", 891),
+    ("MiscFeeCurr", 138),
+    ("MiscFeeType", 139),
+    ("MktBidPx", 645),
+    ("MktOfferPx", 646),
+    ("MoneyLaunderingStatus", 481),
+    ("MsgDirection", 385),
+    ("MsgSeqNum", 34),
+    ("MsgType", 35),
+    ("MultiLegReportingType", 442),
+    ("MultiLegRptTypeReq", 563),
+    ("Nested2PartyID", 757),
+    ("Nested2PartyIDSource", 758),
+    ("Nested2PartyRole", 759),
+    ("Nested2PartySubID", 760),
+    ("Nested2PartySubIDType", 807),
+    ("Nested3PartyID", 949),
+    ("Nested3PartyIDSource", 950),
+    ("Nested3PartyRole", 951),
+    ("Nested3PartySubID", 953),
+    ("Nested3PartySubIDType", 954),
+    ("NestedPartyID", 524),
+    ("NestedPartyIDSource", 525),
+    ("NestedPartyRole", 538),
+    ("NestedPartySubID", 545),
+    ("NestedPartySubIDType", 805),
+    ("NetChgPrevDay", 451),
+    ("NetGrossInd", 430),
+    ("NetMoney", 118),
+    ("NetworkRequestID", 933),
+    ("NetworkRequestType", 935),
+    ("NetworkResponseID", 932),
+    ("NetworkStatusResponseType", 937),
+    ("NewPassword", 925),
+    ("NewSeqNo", 36),
+    ("NextExpectedMsgSeqNum", 789),
+    ("NoAffectedOrders", 534),
+    ("NoAllocs", 78),
+    ("NoAltMDSource", 816),
+    ("NoApplIDs", 1351),
+    ("NoBidComponents", 420),
+    ("NoBidDescriptors", 398),
+    ("NoCapacities", 862),
+    ("NoClearingInstructions", 576),
+    ("NoCollInquiryQualifier", 938),
+    ("NoCompIDs", 936),
+    ("NoContAmts", 518),
+    ("NoContraBrokers", 382),
+    ("NoDates", 580),
+    ("NoDistribInsts", 510),
+    ("NoDlvyInst", 85),
+    ("NoEvents", 864),
+    ("NoExecInstRules", 1232),
+    ("NoExecs", 124),
+    ("NoHops", 627),
+    ("NoIOIQualifiers", 199),
+    ("NoInstrAttrib", 870),
+    ("NoInstrumentParties", 1018),
+    ("NoInstrumentPartySubIDs", 1052),
+    ("NoLegAllocs", 670),
+    ("NoLegSecurityAltID", 604),
+    ("NoLegStipulations", 683),
+    ("NoLegs", 555),
+    ("NoLotTypeRules", 1234),
+    ("NoMDEntries", 268),
+    ("NoMDEntryTypes", 267),
+    ("NoMDFeedTypes", 1141),
+    ("NoMatchRules", 1235),
+    ("NoMiscFees", 136),
+    ("NoMsgTypes", 384),
+    ("NoNested2PartyIDs", 756),
+    ("NoNested2PartySubIDs", 806),
+    ("NoNested3PartyIDs", 948),
+    ("NoNested3PartySubIDs", 952),
+    ("NoNestedPartyIDs", 539),
+    ("NoNestedPartySubIDs", 804),
+    ("NoOrdTypeRules", 1237),
+    ("NoOrders", 73),
+    ("NoPartyIDs", 453),
+    ("NoPartySubIDs", 802),
+    ("NoPosAmt", 753),
+    ("NoPositions", 702),
+    ("NoQuoteEntries", 295),
+    ("NoQuoteQualifiers", 735),
+    ("NoQuoteSets", 296),
+    ("NoRegistDtls", 473),
+    ("NoRelatedSym", 146),
+    ("NoRootPartyIDs", 1116),
+    ("NoRootPartySubIDs", 1120),
+    ("NoRoutingIDs", 215),
+    ("NoRpts", 82),
+    ("NoSecurityAltID", 454),
+    ("NoSecurityTypes", 558),
+    ("NoSettlInst", 778),
+    ("NoSettlPartyIDs", 781),
+    ("NoSettlPartySubIDs", 801),
+    ("NoSides", 552),
+    ("NoStipulations", 232),
+    ("NoStrategyParameters", 957),
+    ("NoStrikes", 428),
+    ("NoTickRules", 1205),
+    ("NoTimeInForceRules", 1239),
+    ("NoTrades", 897),
+    ("NoTradingSessions", 386),
+    ("NoTrdRegTimestamps", 768),
+    ("NoUnderlyingSecurityAltID", 457),
+    ("NoUnderlyingStips", 887),
+    ("NoUnderlyings", 711),
+    ("NoUndlyInstrumentParties", 1058),
+    ("NoUndlyInstrumentPartySubIDs", 1062),
+    ("NotifyBrokerOfCredit", 208),
+    ("NumBidders", 417),
+    ("NumDaysInterest", 157),
+    ("NumTickets", 395),
+    ("NumberOfOrders", 346),
+    ("OddLot", 575),
+    ("OfferForwardPoints", 191),
+    ("OfferForwardPoints2", 643),
+    ("OfferPx", 133),
+    ("OfferSize", 135),
+    ("OfferSpotRate", 190),
+    ("OfferYield", 634),
+    ("OnBehalfOfCompID", 115),
+    ("OnBehalfOfLocationID", 144),
+    ("OnBehalfOfSendingTime", 370),
+    ("OnBehalfOfSubID", 116),
+    ("OpenCloseSettleFlag", 286),
+    ("OpenInterest", 746),
+    ("OptAttribute", 206),
+    ("OrdRejReason", 103),
+    ("OrdStatus", 39),
+    ("OrdStatusReqID", 790),
+    ("OrdType", 40),
+    ("OrderAvgPx", 799),
+    ("OrderBookingQty", 800),
+    ("OrderCapacity", 528),
+    ("OrderCapacityQty", 863),
+    ("OrderID", 37),
+    ("OrderInputDevice", 821),
+    ("OrderPercent", 516),
+    ("OrderQty", 38),
+    ("OrderQty2", 192),
+    ("OrderRestrictions", 529),
+    ("OrigClOrdID", 41),
+    ("OrigCrossID", 551),
+    ("OrigOrdModTime", 586),
+    ("OrigPosReqRefID", 713),
+    ("OrigSendingTime", 122),
+    ("OrigTime", 42),
+    ("OutMainCntryUIndex", 412),
+    ("OutsideIndexPct", 407),
+    ("OwnerType", 522),
+    ("OwnershipType", 517),
+    ("ParticipationRate", 849),
+    ("PartyID", 448),
+    ("PartyIDSource", 447),
+    ("PartyRole", 452),
+    ("PartySubID", 523),
+    ("PartySubIDType", 803),
+    ("Password", 554),
+    ("PaymentDate", 504),
+    ("PaymentMethod", 492),
+    ("PaymentRef", 476),
+    ("PaymentRemitterID", 505),
+    ("PctAtRisk", 869),
+    ("PegLimitType", 837),
+    ("PegMoveType", 835),
+    ("PegOffsetType", 836),
+    ("PegOffsetValue", 211),
+    ("PegRoundDirection", 838),
+    ("PegScope", 840),
+    ("PeggedPrice", 839),
+    ("Pool", 691),
+    ("PosAmt", 708),
+    ("PosAmtType", 707),
+    ("PosMaintAction", 712),
+    ("PosMaintResult", 723),
+    ("PosMaintRptID", 721),
+    ("PosMaintRptRefID", 714),
+    ("PosMaintStatus", 722),
+    ("PosQtyStatus", 706),
+    ("PosReqID", 710),
+    ("PosReqResult", 728),
+    ("PosReqStatus", 729),
+    ("PosReqType", 724),
+    ("PosTransType", 709),
+    ("PosType", 703),
+    ("PositionEffect", 77),
+    ("PossDupFlag", 43),
+    ("PossResend", 97),
+    ("PreallocMethod", 591),
+    ("PrevClosePx", 140),
+    ("PreviouslyReported", 570),
+    ("Price", 44),
+    ("Price2", 640),
+    ("PriceDelta", 811),
+    ("PriceImprovement", 639),
+    ("PriceType", 423),
+    ("PriorSettlPrice", 734),
+    ("PriorSpreadIndicator", 720),
+    ("PriorityIndicator", 638),
+    ("ProcessCode", 81),
+    ("Product", 460),
+    ("ProgPeriodInterval", 415),
+    ("ProgRptReqs", 414),
+    ("PublishTrdIndicator", 852),
+    ("PutOrCall", 201),
+    ("QtyType", 854),
+    ("Quantity", 53),
+    ("QuantityType", 465),
+    ("QuoteAckStatus", 297),
+    ("QuoteCancelType", 298),
+    ("QuoteCondition", 276),
+    ("QuoteEntryID", 299),
+    ("QuoteEntryRejectReason", 368),
+    ("QuoteID", 117),
+    ("QuotePriceType", 692),
+    ("QuoteQualifier", 695),
+    ("QuoteRejectReason", 300),
+    ("QuoteReqID", 131),
+    ("QuoteRequestRejectReason", 658),
+    ("QuoteRequestType", 303),
+    ("QuoteRespID", 693),
+    ("QuoteRespType", 694),
+    ("QuoteResponseLevel", 301),
+    ("QuoteSetID", 302),
+    ("QuoteSetValidUntilTime", 367),
+    ("QuoteStatusReqID", 649),
+    ("QuoteType", 537),
+    ("RFQReqID", 644),
+    ("RatioQty", 319),
+    ("RawData", 96),
+    ("RawDataLength", 95),
+    ("RedemptionDate", 240),
+    ("RefAllocID", 72),
+    ("RefApplID", 1355),
+    ("RefCompID", 930),
+    ("RefMsgType", 372),
+    ("RefSeqNum", 45),
+    ("RefSubID", 931),
+    ("RefTagID", 371),
+    ("RegistAcctType", 493),
+    ("RegistDtls", 509),
+    ("RegistEmail", 511),
+    ("RegistID", 513),
+    ("RegistRefID", 508),
+    ("RegistRejReasonCode", 507),
+    ("RegistRejReasonText", 496),
+    ("RegistStatus", 506),
+    ("RegistTransType", 514),
+    ("RelatdSym", 46),
+    ("RepoCollateralSecurityType", 239),
+    ("ReportToExch", 113),
+    ("ReportedPx", 861),
+    ("RepurchaseRate", 227),
+    ("RepurchaseTerm", 226),
+    ("ResetSeqNumFlag", 141),
+    ("ResponseDestination", 726),
+    ("ResponseTransportType", 725),
+    ("ReversalIndicator", 700),
+    ("RootPartyID", 1117),
+    ("RootPartyIDSource", 1118),
+    ("RootPartyRole", 1119),
+    ("RootPartySubID", 1121),
+    ("RootPartySubIDType", 1122),
+    ("RoundLot", 561),
+    ("RoundingDirection", 468),
+    ("RoundingModulus", 469),
+    ("RoutingID", 217),
+    ("RoutingType", 216),
+    ("RptSeq", 83),
+    ("Rule80a", 47),
+    ("Scope", 546),
+    ("SecDefStatus", 653),
+    ("SecondaryAllocID", 793),
+    ("SecondaryClOrdID", 526),
+    ("SecondaryExecID", 527),
+    ("SecondaryOrderID", 198),
+    ("SecondaryTradeReportID", 818),
+    ("SecondaryTradeReportRefID", 881),
+    ("SecondaryTrdType", 855),
+    ("SecureData", 91),
+    ("SecureDataLen", 90),
+    ("SecurityAltID", 455),
+    ("SecurityAltIDSource", 456),
+    ("SecurityDesc", 107),
+    ("SecurityExchange", 207),
+    ("SecurityID", 48),
+    ("SecurityIDSource", 22),
+    ("SecurityListRequestType", 559),
+    ("SecurityReqID", 320),
+    ("SecurityRequestResult", 560),
+    ("SecurityRequestType", 321),
+    ("SecurityResponseID", 322),
+    ("SecurityResponseType", 323),
+    ("SecuritySettlAgentAcctName", 179),
+    ("SecuritySettlAgentAcctNum", 178),
+    ("SecuritySettlAgentCode", 177),
+    ("SecuritySettlAgentContactName", 180),
+    ("SecuritySettlAgentContactPhone", 181),
+    ("SecuritySettlAgentName", 176),
+    ("SecurityStatusReqID", 324),
+    ("SecuritySubType", 762),
+    ("SecurityTradingStatus", 326),
+    ("SecurityType", 167),
+    ("SellVolume", 331),
+    ("SellerDays", 287),
+    ("SenderCompID", 49),
+    ("SenderLocationID", 142),
+    ("SenderSubID", 50),
+    ("SendingDate", 51),
+    ("SendingTime", 52),
+    ("SessionRejectReason", 373),
+    ("SettlBrkrCode", 174),
+    ("SettlCurrAmt", 119),
+    ("SettlCurrBidFxRate", 656),
+    ("SettlCurrFxRate", 155),
+    ("SettlCurrFxRateCalc", 156),
+    ("SettlCurrOfferFxRate", 657),
+    ("SettlCurrency", 120),
+    ("SettlDate", 64),
+    ("SettlDeliveryType", 172),
+    ("SettlDepositoryCode", 173),
+    ("SettlInstCode", 175),
+    ("SettlInstID", 162),
+    ("SettlInstMode", 160),
+    ("SettlInstMsgID", 777),
+    ("SettlInstRefID", 214),
+    ("SettlInstReqID", 791),
+    ("SettlInstReqRejCode", 792),
+    ("SettlInstSource", 165),
+    ("SettlInstTransType", 163),
+    ("SettlLocation", 166),
+    ("SettlPartyID", 782),
+    ("SettlPartyIDSource", 783),
+    ("SettlPartyRole", 784),
+    ("SettlPartySubID", 785),
+    ("SettlPartySubIDType", 786),
+    ("SettlPrice", 730),
+    ("SettlPriceType", 731),
+    ("SettlSessID", 716),
+    ("SettlSessSubID", 717),
+    ("SettlType", 63),
+    ("SharedCommission", 858),
+    ("ShortQty", 705),
+    ("ShortSaleReason", 853),
+    ("Side", 54),
+    ("SideComplianceID", 659),
+    ("SideMultiLegReportingType", 752),
+    ("SideValue1", 396),
+    ("SideValue2", 397),
+    ("SideValueInd", 401),
+    ("Signature", 89),
+    ("SignatureLength", 93),
+    ("SolicitedFlag", 377),
+    ("Spread", 218),
+    ("StandInstDbID", 171),
+    ("StandInstDbName", 170),
+    ("StandInstDbType", 169),
+    ("StartCash", 921),
+    ("StartDate", 916),
+    ("StartTickPriceRange", 1206),
+    ("StateOrProvinceOfIssue", 471),
+    ("StatusText", 929),
+    ("StatusValue", 928),
+    ("StipulationType", 233),
+    ("StipulationValue", 234),
+    ("StopPx", 99),
+    ("StrategyParameterName", 958),
+    ("StrategyParameterType", 959),
+    ("StrategyParameterValue", 960),
+    ("StrikeCurrency", 947),
+    ("StrikePrice", 202),
+    ("StrikeTime", 443),
+    ("Subject", 147),
+    ("SubscriptionRequestType", 263),
+    ("Symbol", 55),
+    ("SymbolSfx", 65),
+    ("TargetCompID", 56),
+    ("TargetLocationID", 143),
+    ("TargetStrategy", 847),
+    ("TargetStrategyParameters", 848),
+    ("TargetStrategyPerformance", 850),
+    ("TargetSubID", 57),
+    ("TaxAdvantageType", 495),
+    ("TerminationType", 788),
+    ("TestMessageIndicator", 464),
+    ("TestReqID", 112),
+    ("Text", 58),
+    ("ThresholdAmount", 834),
+    ("TickDirection", 274),
+    ("TickIncrement", 1208),
+    ("TickRuleType", 1209),
+    ("TimeBracket", 943),
+    ("TimeInForce", 59),
+    ("TotNoAllocs", 892),
+    ("TotNoOrders", 68),
+    ("TotNoRelatedSym", 393),
+    ("TotNoSecurityTypes", 557),
+    ("TotNoStrikes", 422),
+    ("TotNumAssignmentReports", 832),
+    ("TotNumReports", 911),
+    ("TotNumTradeReports", 748),
+    ("TotQuoteEntries", 304),
+    ("TotalAccruedInterestAmt", 540),
+    ("TotalAffectedOrders", 533),
+    ("TotalNetValue", 900),
+    ("TotalNumPosReports", 727),
+    ("TotalTakedown", 237),
+    ("TotalVolumeTraded", 387),
+    ("TotalVolumeTradedDate", 449),
+    ("TotalVolumeTradedTime", 450),
+    ("TradSesCloseTime", 344),
+    ("TradSesEndTime", 345),
+    ("TradSesMethod", 338),
+    ("TradSesMode", 339),
+    ("TradSesOpenTime", 342),
+    ("TradSesPreCloseTime", 343),
+    ("TradSesReqID", 335),
+    ("TradSesStartTime", 341),
+    ("TradSesStatus", 340),
+    ("TradSesStatusRejReason", 567),
+    ("TradeAllocIndicator", 826),
+    ("TradeCondition", 277),
+    ("TradeDate", 75),
+    ("TradeInputDevice", 579),
+    ("TradeInputSource", 578),
+    ("TradeLegRefID", 824),
+    ("TradeLinkID", 820),
+    ("TradeOriginationDate", 229),
+    ("TradeReportID", 571),
+    ("TradeReportRefID", 572),
+    ("TradeReportRejectReason", 751),
+    ("TradeReportTransType", 487),
+    ("TradeReportType", 856),
+    ("TradeRequestID", 568),
+    ("TradeRequestResult", 749),
+    ("TradeRequestStatus", 750),
+    ("TradeRequestType", 569),
+    ("TradeType", 418),
+    ("TradedFlatSwitch", 258),
+    ("TradingSessionID", 336),
+    ("TradingSessionSubID", 625),
+    ("TransBkdTime", 483),
+    ("TransactTime", 60),
+    ("TransferReason", 830),
+    ("TrdMatchID", 880),
+    ("TrdRegTimestamp", 769),
+    ("TrdRegTimestampOrigin", 771),
+    ("TrdRegTimestampType", 770),
+    ("TrdRptStatus", 939),
+    ("TrdSubType", 829),
+    ("TrdType", 828),
+    ("URLLink", 149),
+    ("UnderlyingCFICode", 463),
+    ("UnderlyingCPProgram", 877),
+    ("UnderlyingCPRegType", 878),
+    ("UnderlyingContractMultiplier", 436),
+    ("UnderlyingCountryOfIssue", 592),
+    ("UnderlyingCouponPaymentDate", 241),
+    ("UnderlyingCouponRate", 435),
+    ("UnderlyingCreditRating", 256),
+    ("UnderlyingCurrency", 318),
+    ("UnderlyingCurrentValue", 885),
+    ("UnderlyingDirtyPrice", 882),
+    ("UnderlyingEndPrice", 883),
+    ("UnderlyingEndValue", 886),
+    ("UnderlyingFactor", 246),
+    ("UnderlyingIDSource", 305),
+    ("UnderlyingInstrRegistry", 595),
+    ("UnderlyingIssueDate", 242),
+    ("UnderlyingIssuer", 306),
+    ("UnderlyingLastPx", 651),
+    ("UnderlyingLastQty", 652),
+    ("UnderlyingLocaleOfIssue", 594),
+    ("UnderlyingMaturityDate", 542),
+    ("UnderlyingMaturityDay", 314),
+    ("UnderlyingMaturityMonthYear", 313),
+    ("UnderlyingOptAttribute", 317),
+    ("UnderlyingProduct", 462),
+    ("UnderlyingPutOrCall", 315),
+    ("UnderlyingPx", 810),
+    ("UnderlyingQty", 879),
+    ("UnderlyingRedemptionDate", 247),
+    ("UnderlyingRepoCollateralSecurityType", 243),
+    ("UnderlyingRepurchaseRate", 245),
+    ("UnderlyingRepurchaseTerm", 244),
+    ("UnderlyingSecurityAltID", 458),
+    ("UnderlyingSecurityAltIDSource", 459),
+    ("UnderlyingSecurityDesc", 307),
+    ("UnderlyingSecurityExchange", 308),
+    ("UnderlyingSecurityID", 309),
+    ("UnderlyingSecuritySubType", 763),
+    ("UnderlyingSecurityType", 310),
+    ("UnderlyingSettlPrice", 732),
+    ("UnderlyingSettlPriceType", 733),
+    ("UnderlyingStartValue", 884),
+    ("UnderlyingStateOrProvinceOfIssue", 593),
+    ("UnderlyingStipType", 888),
+    ("UnderlyingStipValue", 889),
+    ("UnderlyingStrikeCurrency", 941),
+    ("UnderlyingStrikePrice", 316),
+    ("UnderlyingSymbol", 311),
+    ("UnderlyingSymbolSfx", 312),
+    ("UnderlyingTradingSessionID", 822),
+    ("UnderlyingTradingSessionSubID", 823),
+    ("UndlyInstrumentPartyID", 1059),
+    ("UndlyInstrumentPartyIDSource", 1060),
+    ("UndlyInstrumentPartyRole", 1061),
+    ("UndlyInstrumentPartySubID", 1063),
+    ("UndlyInstrumentPartySubIDType", 1064),
+    ("UnsolicitedIndicator", 325),
+    ("Urgency", 61),
+    ("UserRequestID", 923),
+    ("UserRequestType", 924),
+    ("UserStatus", 926),
+    ("UserStatusText", 927),
+    ("Username", 553),
+    ("ValidUntilTime", 62),
+    ("ValueOfFutures", 408),
+    ("WaveNo", 105),
+    ("WtAverageLiquidity", 410),
+    ("XmlData", 213),
+    ("XmlDataLen", 212),
+    ("Yield", 236),
+    ("YieldCalcDate", 701),
+    ("YieldRedemptionDate", 696),
+    ("YieldRedemptionPrice", 697),
+    ("YieldRedemptionPriceType", 698),
+    ("YieldType", 235),
+];
+
+/// FIX 4.2 names of tags renamed in FIX 4.4, `(name, tag)` sorted by name.
+const FIX42_NAMES: &[(&str, Tag)] = &[
+    ("AllocShares", 80),
+    ("DiscretionOffset", 389),
+    ("FutSettDate", 64),
+    ("FutSettDate2", 193),
+    ("IDSource", 22),
+    ("IOIShares", 27),
+    ("LastShares", 32),
+    ("OpenClose", 77),
+    ("PegDifference", 211),
+    ("SettlmntTyp", 63),
+    ("Shares", 53),
+    ("SpreadToBenchmark", 218),
+    ("TotalNumSecurities", 393),
+];
+
+/// The FIX name of `tag`, e.g. `"ClOrdID"` for 11.
+pub fn tag_name(tag: Tag) -> Option<&'static str> {
+    BY_TAG
+        .binary_search_by_key(&tag, |&(t, _)| t)
+        .ok()
+        .map(|i| BY_TAG[i].1)
+}
+
+/// The tag named `name`, e.g. 11 for `"ClOrdID"`. FIX 4.2 names of
+/// renamed tags (`"LastShares"`) are accepted too.
+pub fn tag_by_name(name: &str) -> Option<Tag> {
+    [BY_NAME, FIX42_NAMES].into_iter().find_map(|table| {
+        table
+            .binary_search_by_key(&name, |&(n, _)| n)
+            .ok()
+            .map(|i| table[i].1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tag;
+
+    #[test]
+    fn names_round_trip() {
+        assert_eq!(tag_name(tag::CL_ORD_ID), Some("ClOrdID"));
+        assert_eq!(tag_name(tag::NO_MD_ENTRIES), Some("NoMDEntries"));
+        assert_eq!(tag_name(tag::SECURITY_ID_SOURCE), Some("SecurityIDSource"));
+        assert_eq!(tag_name(5001), None);
+        assert_eq!(tag_by_name("HeartBtInt"), Some(tag::HEART_BT_INT));
+        assert_eq!(tag_by_name("heartbtint"), None);
+        assert_eq!(tag_by_name("LastShares"), Some(tag::LAST_QTY));
+        assert_eq!(tag_by_name("LastQty"), Some(tag::LAST_QTY));
+        assert!(BY_TAG.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(BY_NAME.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(FIX42_NAMES.windows(2).all(|w| w[0].0 < w[1].0));
+
+        // Every tag of the FIX 4.2 and 4.4 dictionaries is named.
+        let dict = crate::dictionary::Dictionary::fix44();
+        assert!(dict.tags().all(|t| tag_name(t).is_some()));
+    }
+}
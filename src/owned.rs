@@ -0,0 +1,182 @@
+use crate::error::FixError;
+use crate::field::{Field, Fields, display_fields};
+use crate::group::{GroupIter, GroupSpec};
+use crate::message::Message;
+use crate::tag::{self, Tag};
+use crate::view::FieldsView;
+
+/// A decoded message that owns its bytes, so it can outlive the `Decoder`,
+/// be stored, or be sent to another thread.
+///
+/// Created with [`Message::to_owned`], which copies the raw buffer and the
+/// offset index once. The tag index used by [`find`](Self::find) is built at
+/// the same time, so lookups never allocate afterwards. Field values are
+/// still zero-copy slices into the owned buffer.
+///
+/// # Example
+/// ```ignore
+/// let msg = decoder.decode(buf)?;
+/// let owned = msg.to_owned();
+/// std::thread::spawn(move || {
+///     let px = owned.find(tag::PRICE);
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedMessage {
+    pub(crate) buf: Box<[u8]>,
+    pub(crate) offsets: Box<[(Tag, u32, u32)]>,
+    /// `(tag, offsets_index)` sorted by tag, for binary search in `find`.
+    pub(crate) sorted: Box<[(Tag, u32)]>,
+}
+
+impl OwnedMessage {
+    pub(crate) fn new(buf: &[u8], offsets: &[(Tag, u32, u32)]) -> Self {
+        let mut sorted: Vec<(Tag, u32)> = offsets
+            .iter()
+            .enumerate()
+            .map(|(i, &(t, _, _))| (t, i as u32))
+            .collect();
+        // Stable so the first occurrence of a repeated tag sorts first.
+        sorted.sort_by_key(|&(t, _)| t);
+        Self {
+            buf: buf.into(),
+            offsets: offsets.into(),
+            sorted: sorted.into(),
+        }
+    }
+
+    /// Borrow as a zero-copy [`Message`], for APIs that take one (e.g.
+    /// `Encoder::encode`).
+    #[inline]
+    pub fn as_message(&self) -> Message<'_> {
+        Message::new(&self.buf, &self.offsets)
+    }
+
+    /// The raw message bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Number of fields in the message.
+    #[inline]
+    pub fn len(&self) -> usize {
+        FieldsView::len(&self)
+    }
+
+    /// Returns true if the message contains no fields.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns the field at `index`. Panics if `index >= self.len()`.
+    #[inline]
+    pub fn field(&self, index: usize) -> Field<'_> {
+        FieldsView::field(&self, index)
+    }
+
+    /// Returns an iterator over all fields in wire order.
+    #[inline]
+    pub fn fields(&self) -> Fields<'_> {
+        FieldsView::fields(&self)
+    }
+
+    /// Find the first field with the given tag (O(log n)).
+    #[inline]
+    pub fn find(&self, tag: Tag) -> Option<Field<'_>> {
+        FieldsView::find(&self, tag)
+    }
+
+    /// Return the value of tag 8 (`BEGIN_STRING`), if present.
+    #[inline]
+    pub fn fix_version(&self) -> Option<&[u8]> {
+        self.find(tag::BEGIN_STRING).map(|f| f.value)
+    }
+
+    /// Iterate over the instances of the repeating group described by `spec`.
+    /// See [`Message::groups`].
+    #[inline]
+    pub fn groups(&self, spec: &GroupSpec) -> GroupIter<'_> {
+        FieldsView::groups(&self, spec)
+    }
+
+    /// See [`Message::validate_body_length`].
+    pub fn validate_body_length(&self) -> Result<(), FixError> {
+        self.as_message().validate_body_length()
+    }
+
+    /// See [`Message::validate_checksum`].
+    pub fn validate_checksum(&self) -> Result<(), FixError> {
+        self.as_message().validate_checksum()
+    }
+}
+
+/// Same as [`Message`]'s `Display`: `tag=value|…`, tag names with `{:#}`.
+impl std::fmt::Display for OwnedMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        display_fields(self.fields(), f)
+    }
+}
+
+impl From<&Message<'_>> for OwnedMessage {
+    fn from(msg: &Message<'_>) -> Self {
+        msg.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::group;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn owned_outlives_decoder() {
+        assert_send_sync::<OwnedMessage>();
+
+        let owned = {
+            let raw = b"8=FIX.4.4\x019=5\x0135=0\x0110=163\x01".to_vec();
+            let mut dec = Decoder::new();
+            dec.decode(&raw).unwrap().to_owned()
+        };
+        assert_eq!(owned.len(), 4);
+        assert_eq!(owned.fix_version(), Some(&b"FIX.4.4"[..]));
+        assert_eq!(owned.find(tag::MSG_TYPE).unwrap().value, b"0");
+        assert!(owned.find(tag::SYMBOL).is_none());
+        assert!(owned.validate_body_length().is_ok());
+
+        let handle = std::thread::spawn(move || owned.field(2).value.to_vec());
+        assert_eq!(handle.join().unwrap(), b"0");
+    }
+
+    #[test]
+    fn owned_find_returns_first_occurrence_and_groups_work() {
+        let raw = b"35=W\x01268=2\x01269=0\x01270=1.5\x01269=1\x01270=1.6\x01";
+        let mut dec = Decoder::new();
+        let owned = dec.decode(raw).unwrap().to_owned();
+        assert_eq!(owned.find(tag::MD_ENTRY_PX).unwrap().value, b"1.5");
+        assert_eq!(owned.groups(&group::MD_ENTRIES).count(), 2);
+        assert_eq!(owned.as_message().fields().count(), owned.len());
+    }
+
+    fn prices<'a>(v: &impl FieldsView<'a>) -> Vec<&'a [u8]> {
+        v.groups(&group::MD_ENTRIES)
+            .filter_map(|g| g.find(tag::MD_ENTRY_PX).map(|f| f.value))
+            .collect()
+    }
+
+    #[test]
+    fn owned_is_a_fields_view() {
+        let raw = b"35=W\x01268=2\x01269=0\x01270=1.5\x01269=1\x01270=1.6\x01";
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let owned = msg.to_owned();
+        assert_eq!(prices(&&owned), prices(&msg));
+        assert_eq!(prices(&&owned), [&b"1.5"[..], b"1.6"]);
+        assert_eq!(FieldsView::len(&&owned), 6);
+        assert_eq!(FieldsView::field(&&owned, 1).value, b"2");
+    }
+}
@@ -0,0 +1,459 @@
+use crate::builder::MessageBuilder;
+use crate::dictionary::is_header_tag;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::tag::Tag;
+use crate::timestamp::{TimestampPrecision, UtcTimestamp};
+
+/// One step of the encoder's middleware pipeline.
+///
+/// A stage reads the message produced by the previous stage (or the message
+/// given to [`Encoder::encode`] for the first stage) and writes its own
+/// version of the field stream into `out`, which starts empty. Fields the
+/// stage does not care about must be copied through; fields it omits are
+/// dropped. Stages run in the order they were added with
+/// [`Encoder::push_stage`], and the last stage's output is what gets framed
+/// with 8/9/10.
+///
+/// Any `FnMut(&Message<'_>, &mut MessageBuilder) -> Result<(), FixError>`
+/// closure is a stage, which covers one-off behaviors such as signing or
+/// logging.
+///
+/// [`Encoder::encode`]: crate::encoder::Encoder::encode
+/// [`Encoder::push_stage`]: crate::encoder::Encoder::push_stage
+///
+/// # Example
+/// ```ignore
+/// enc.push_stage(HeaderFields::new().set(tag::SENDER_COMP_ID, b"ME"))
+///     .push_stage(RemapTags::new().remap(tag::EX_DESTINATION, 9100))
+///     .push_stage(|msg: &Message<'_>, out: &mut MessageBuilder| {
+///         log::debug!("outbound {} fields", msg.len());
+///         copy_fields(msg, out);
+///         Ok(())
+///     });
+/// ```
+pub trait EncodeStage {
+    /// Write the transformed fields of `msg` into `out`.
+    ///
+    /// # Errors
+    /// Any error aborts the encode and is returned from `Encoder::encode`.
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError>;
+}
+
+impl<F> EncodeStage for F
+where
+    F: FnMut(&Message<'_>, &mut MessageBuilder) -> Result<(), FixError>,
+{
+    #[inline]
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        self(msg, out)
+    }
+}
+
+/// One step of an inbound [`DecodePipeline`], the mirror of [`EncodeStage`].
+///
+/// A stage reads the message produced by the previous stage (or the decoded
+/// message for the first stage) and writes its own version of the field
+/// stream into `out`, which starts empty.
+///
+/// Any `FnMut(&Message<'_>, &mut MessageBuilder) -> Result<(), FixError>`
+/// closure is a stage, which covers metrics and other per-session hooks.
+pub trait DecodeStage {
+    /// Write the transformed fields of `msg` into `out`.
+    ///
+    /// # Errors
+    /// Any error aborts the pipeline and is returned from
+    /// [`DecodePipeline::run`].
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError>;
+}
+
+impl<F> DecodeStage for F
+where
+    F: FnMut(&Message<'_>, &mut MessageBuilder) -> Result<(), FixError>,
+{
+    #[inline]
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        self(msg, out)
+    }
+}
+
+/// Run `stages` over `msg` through the ping-pong scratch builders `bufs`,
+/// returning the index in `bufs` of the last stage's output.
+///
+/// With no stages, `msg` is copied into `bufs[0]`.
+pub(crate) fn run_stages<S: ?Sized>(
+    stages: &mut [Box<S>],
+    msg: &Message<'_>,
+    bufs: &mut [MessageBuilder; 2],
+    mut apply: impl FnMut(&mut S, &Message<'_>, &mut MessageBuilder) -> Result<(), FixError>,
+) -> Result<usize, FixError> {
+    let [a, b] = bufs;
+    if stages.is_empty() {
+        a.clear();
+        copy_fields(msg, a);
+        return Ok(0);
+    }
+    let (mut src, mut dst) = (a, b);
+    let mut last = 0;
+    for (i, stage) in stages.iter_mut().enumerate() {
+        dst.clear();
+        if i == 0 {
+            apply(stage, msg, dst)?;
+        } else {
+            apply(stage, &src.as_message(), dst)?;
+        }
+        std::mem::swap(&mut src, &mut dst);
+        last ^= 1;
+    }
+    Ok(last)
+}
+
+/// Per-session post-decode transforms: dialect normalization, redaction,
+/// enrichment with receive metadata, metrics.
+///
+/// The decoder itself stays zero-copy; a pipeline is applied to its output
+/// and returns a new [`Message`] borrowing the pipeline's own scratch
+/// buffers, which are reused across calls. Keep one pipeline per session so
+/// each counterparty gets its own stages.
+///
+/// # Example
+/// ```ignore
+/// let mut inbound = DecodePipeline::new();
+/// inbound
+///     .push_stage(RemapTags::new().remap(9100, tag::EX_DESTINATION))
+///     .push_stage(Redact::new(&[tag::PASSWORD]))
+///     .push_stage(ReceiveTime::new(5050, TimestampPrecision::Micros));
+///
+/// let msg = decoder.decode(buf)?;
+/// let msg = inbound.run(&msg)?;
+/// ```
+#[derive(Default)]
+pub struct DecodePipeline {
+    stages: Vec<Box<dyn DecodeStage + Send>>,
+    bufs: [MessageBuilder; 2],
+}
+
+impl DecodePipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage. Stages run in the order they were added.
+    pub fn push_stage<S>(&mut self, stage: S) -> &mut Self
+    where
+        S: DecodeStage + Send + 'static,
+    {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Number of stages.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns true if no stages have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Pass `msg` through every stage and return the last stage's output.
+    ///
+    /// With no stages the result is a copy of `msg`.
+    ///
+    /// # Errors
+    /// Returns the first error raised by a stage.
+    pub fn run(&mut self, msg: &Message<'_>) -> Result<Message<'_>, FixError> {
+        let last = run_stages(&mut self.stages, msg, &mut self.bufs, |s, m, out| {
+            s.apply(m, out)
+        })?;
+        Ok(self.bufs[last].as_message())
+    }
+}
+
+/// Copy every field of `msg` into `out` unchanged — the pass-through most
+/// stages start from.
+#[inline]
+pub fn copy_fields(msg: &Message<'_>, out: &mut MessageBuilder) {
+    for f in msg.fields() {
+        out.push(f.tag, f.value);
+    }
+}
+
+/// Header population: set fixed header fields such as SenderCompID (49),
+/// TargetCompID (56) or OnBehalfOfCompID (115) on every outbound message.
+///
+/// A field the message already carries has its first occurrence replaced;
+/// otherwise it is added at the end of the header fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeaderFields {
+    fields: Vec<(Tag, Vec<u8>)>,
+}
+
+impl HeaderFields {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `tag=value`, replacing any value previously set here for `tag`.
+    pub fn set(mut self, tag: Tag, value: &[u8]) -> Self {
+        match self.fields.iter_mut().find(|(t, _)| *t == tag) {
+            Some((_, v)) => *v = value.to_vec(),
+            None => self.fields.push((tag, value.to_vec())),
+        }
+        self
+    }
+
+    /// The fields set, in the order they were first set.
+    pub(crate) fn fields(&self) -> impl Iterator<Item = (Tag, &[u8])> {
+        self.fields.iter().map(|(t, v)| (*t, v.as_slice()))
+    }
+
+    fn value(&self, tag: Tag) -> Option<&[u8]> {
+        self.fields
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    fn push_missing(&self, msg: &Message<'_>, out: &mut MessageBuilder) {
+        for (tag, value) in &self.fields {
+            if msg.find(*tag).is_none() {
+                out.push(*tag, value);
+            }
+        }
+    }
+}
+
+impl EncodeStage for HeaderFields {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        let mut missing_written = false;
+        let mut replaced: Vec<Tag> = Vec::new();
+        for f in msg.fields() {
+            if !missing_written && !is_header_tag(f.tag) {
+                self.push_missing(msg, out);
+                missing_written = true;
+            }
+            match self.value(f.tag) {
+                Some(v) if !replaced.contains(&f.tag) => {
+                    out.push(f.tag, v);
+                    replaced.push(f.tag);
+                }
+                _ => {
+                    out.push(f.tag, f.value);
+                }
+            }
+        }
+        if !missing_written {
+            self.push_missing(msg, out);
+        }
+        Ok(())
+    }
+}
+
+/// Dialect remapping: rename tags on the way out, e.g. a venue that expects
+/// a standard field under a user-defined tag number.
+///
+/// Values are copied unchanged; only the tag number is rewritten. Each tag
+/// is remapped at most once, so `a -> b` and `b -> a` swap the two tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemapTags {
+    map: Vec<(Tag, Tag)>,
+}
+
+impl RemapTags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write `from` as `to`, replacing any earlier mapping for `from`.
+    pub fn remap(mut self, from: Tag, to: Tag) -> Self {
+        match self.map.iter_mut().find(|(f, _)| *f == from) {
+            Some((_, t)) => *t = to,
+            None => self.map.push((from, to)),
+        }
+        self
+    }
+
+    /// The tag `tag` is written as.
+    #[inline]
+    pub fn target(&self, tag: Tag) -> Tag {
+        self.map
+            .iter()
+            .find(|(f, _)| *f == tag)
+            .map_or(tag, |&(_, t)| t)
+    }
+}
+
+impl RemapTags {
+    fn remap_fields(&self, msg: &Message<'_>, out: &mut MessageBuilder) {
+        for f in msg.fields() {
+            out.push(self.target(f.tag), f.value);
+        }
+    }
+}
+
+impl EncodeStage for RemapTags {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        self.remap_fields(msg, out);
+        Ok(())
+    }
+}
+
+/// Inbound dialect normalization: map a venue's tag numbers back to the
+/// standard ones.
+impl DecodeStage for RemapTags {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        self.remap_fields(msg, out);
+        Ok(())
+    }
+}
+
+/// Redaction: replace the values of sensitive tags (Password 554,
+/// NewPassword 925, RawData 96, …) with a fixed mask before messages are
+/// logged or stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redact {
+    tags: Vec<Tag>,
+    mask: Vec<u8>,
+}
+
+impl Redact {
+    /// Redact `tags` with the default mask `***`.
+    pub fn new(tags: &[Tag]) -> Self {
+        Self {
+            tags: tags.to_vec(),
+            mask: b"***".to_vec(),
+        }
+    }
+
+    /// Use `mask` as the replacement value.
+    pub fn mask(mut self, mask: &[u8]) -> Self {
+        self.mask = mask.to_vec();
+        self
+    }
+}
+
+impl DecodeStage for Redact {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        for f in msg.fields() {
+            if self.tags.contains(&f.tag) {
+                out.push(f.tag, &self.mask);
+            } else {
+                out.push(f.tag, f.value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Receive-metadata enrichment: append the local receive time as a
+/// UTCTimestamp under a user-defined `tag`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiveTime {
+    tag: Tag,
+    precision: TimestampPrecision,
+}
+
+impl ReceiveTime {
+    pub fn new(tag: Tag, precision: TimestampPrecision) -> Self {
+        Self { tag, precision }
+    }
+}
+
+impl DecodeStage for ReceiveTime {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        copy_fields(msg, out);
+        let (buf, len) = UtcTimestamp::now().format(self.precision);
+        out.push(self.tag, &buf[..len]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::tag;
+
+    fn run(stage: &mut dyn EncodeStage, raw: &[u8]) -> Vec<(Tag, Vec<u8>)> {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(raw).unwrap();
+        let mut out = MessageBuilder::new();
+        stage.apply(&msg, &mut out).unwrap();
+        out.as_message()
+            .fields()
+            .map(|f| (f.tag, f.value.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn header_fields_replace_or_insert() {
+        let mut stage = HeaderFields::new()
+            .set(tag::SENDER_COMP_ID, b"ME")
+            .set(tag::ON_BEHALF_OF_COMP_ID, b"DESK");
+        let fields = run(&mut stage, b"35=D\x0149=OLD\x0156=T\x0111=1\x01");
+        assert_eq!(
+            fields,
+            vec![
+                (tag::MSG_TYPE, b"D".to_vec()),
+                (tag::SENDER_COMP_ID, b"ME".to_vec()),
+                (tag::TARGET_COMP_ID, b"T".to_vec()),
+                (tag::ON_BEHALF_OF_COMP_ID, b"DESK".to_vec()),
+                (tag::CL_ORD_ID, b"1".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_pipeline_normalizes_redacts_and_enriches() {
+        let mut count = 0usize;
+        let mut pipeline = DecodePipeline::new();
+        pipeline
+            .push_stage(RemapTags::new().remap(9100, tag::EX_DESTINATION))
+            .push_stage(Redact::new(&[tag::PASSWORD]))
+            .push_stage(ReceiveTime::new(5050, TimestampPrecision::Seconds))
+            .push_stage(move |msg: &Message<'_>, out: &mut MessageBuilder| {
+                count += 1;
+                assert_eq!(count, 1);
+                copy_fields(msg, out);
+                Ok(())
+            });
+        assert_eq!(pipeline.len(), 4);
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=A\x01554=secret\x019100=XNAS\x01").unwrap();
+        let out = pipeline.run(&msg).unwrap();
+        assert_eq!(out.find(tag::PASSWORD).unwrap().value, b"***");
+        assert_eq!(out.find(tag::EX_DESTINATION).unwrap().value, b"XNAS");
+        assert!(out.find(9100).is_none());
+        assert_eq!(out.field(3).tag, 5050);
+        assert_eq!(out.field(3).value.len(), 17);
+    }
+
+    #[test]
+    fn empty_decode_pipeline_copies() {
+        let mut pipeline = DecodePipeline::new();
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=0\x01").unwrap();
+        let out = pipeline.run(&msg).unwrap();
+        assert_eq!(out.len(), 1);
+        assert_eq!(out.field(0).value, b"0");
+    }
+
+    #[test]
+    fn remap_tags_swaps() {
+        let mut stage = RemapTags::new().remap(100, 9100).remap(9100, 100);
+        let fields = run(&mut stage, b"35=D\x01100=X\x019100=Y\x01");
+        assert_eq!(
+            fields,
+            vec![
+                (tag::MSG_TYPE, b"D".to_vec()),
+                (9100, b"X".to_vec()),
+                (100, b"Y".to_vec()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use crate::builder::MessageBuilder;
+use crate::dictionary::is_header_tag;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::pipeline::{EncodeStage, copy_fields};
+use crate::tag::{self, Tag};
+
+/// What a [`FieldPolicy`] rule does with its tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldAction {
+    /// Send the tag with this value when the message does not carry it.
+    AlwaysSend(Vec<u8>),
+    /// Never send the tag, even if the message carries it.
+    Strip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldRule {
+    /// MsgType values the rule applies to; empty means every message.
+    msg_types: Vec<Vec<u8>>,
+    tag: Tag,
+    action: FieldAction,
+}
+
+impl FieldRule {
+    #[inline]
+    fn applies_to(&self, msg_type: &[u8]) -> bool {
+        self.msg_types.is_empty() || self.msg_types.iter().any(|t| t == msg_type)
+    }
+}
+
+/// Optional-field presence rules for one counterparty.
+///
+/// Venues disagree about optional fields: a legacy venue may reject orders
+/// without HandlInst (21), another may reject any TransactTime (60). A
+/// policy records those quirks once so the encoder applies them to every
+/// outbound message (see [`Encoder::field_policies`]).
+///
+/// Rules are scoped to a list of MsgType values; an empty list applies the
+/// rule to every message. When several rules match the same tag, the first
+/// one added wins.
+///
+/// [`Encoder::field_policies`]: crate::encoder::Encoder::field_policies
+///
+/// # Example
+/// ```ignore
+/// let mut legacy = FieldPolicy::new();
+/// legacy
+///     .always_send(tag::HANDL_INST, b"1", &[b"D", b"G"])
+///     .strip(tag::TRANSACT_TIME, &[]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldPolicy {
+    rules: Vec<FieldRule>,
+}
+
+impl FieldPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send `tag=value` on messages of `msg_types` that do not carry `tag`.
+    ///
+    /// Header tags are added at the end of the header, body tags at the end
+    /// of the body.
+    pub fn always_send(&mut self, tag: Tag, value: &[u8], msg_types: &[&[u8]]) -> &mut Self {
+        self.push(tag, FieldAction::AlwaysSend(value.to_vec()), msg_types)
+    }
+
+    /// Drop every occurrence of `tag` from messages of `msg_types`.
+    pub fn strip(&mut self, tag: Tag, msg_types: &[&[u8]]) -> &mut Self {
+        self.push(tag, FieldAction::Strip, msg_types)
+    }
+
+    fn push(&mut self, tag: Tag, action: FieldAction, msg_types: &[&[u8]]) -> &mut Self {
+        self.rules.push(FieldRule {
+            msg_types: msg_types.iter().map(|t| t.to_vec()).collect(),
+            tag,
+            action,
+        });
+        self
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The action for `tag` on a message of `msg_type`, if any rule matches.
+    pub fn action(&self, msg_type: &[u8], tag: Tag) -> Option<&FieldAction> {
+        self.rules
+            .iter()
+            .find(|r| r.tag == tag && r.applies_to(msg_type))
+            .map(|r| &r.action)
+    }
+
+    /// True if `tag` must not be sent on a message of `msg_type`.
+    #[inline]
+    pub fn strips(&self, msg_type: &[u8], tag: Tag) -> bool {
+        matches!(self.action(msg_type, tag), Some(FieldAction::Strip))
+    }
+
+    /// The `(tag, value)` pairs to send on a message of `msg_type` when
+    /// absent, in the order the rules were added.
+    pub fn defaults<'p>(&'p self, msg_type: &'p [u8]) -> impl Iterator<Item = (Tag, &'p [u8])> {
+        self.rules.iter().filter_map(move |r| match &r.action {
+            FieldAction::AlwaysSend(v)
+                if r.applies_to(msg_type)
+                    && matches!(
+                        self.action(msg_type, r.tag),
+                        Some(FieldAction::AlwaysSend(_))
+                    ) =>
+            {
+                Some((r.tag, v.as_slice()))
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Field policies keyed by counterparty TargetCompID (56), with an optional
+/// fallback for counterparties without a profile of their own.
+///
+/// # Example
+/// ```ignore
+/// let mut profiles = PolicyProfiles::new();
+/// profiles.insert(b"LEGACYVENUE", legacy);
+/// profiles.set_default(FieldPolicy::new());
+/// encoder.field_policies(Some(profiles));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PolicyProfiles {
+    default: Option<FieldPolicy>,
+    by_target: HashMap<Vec<u8>, FieldPolicy>,
+}
+
+impl PolicyProfiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `policy` for messages to `target_comp_id`, replacing any previous
+    /// profile for it.
+    pub fn insert(&mut self, target_comp_id: &[u8], policy: FieldPolicy) -> &mut Self {
+        self.by_target.insert(target_comp_id.to_vec(), policy);
+        self
+    }
+
+    /// Use `policy` for counterparties without a profile of their own.
+    pub fn set_default(&mut self, policy: FieldPolicy) -> &mut Self {
+        self.default = Some(policy);
+        self
+    }
+
+    /// The policy for `target_comp_id`, falling back to the default.
+    pub fn for_target(&self, target_comp_id: &[u8]) -> Option<&FieldPolicy> {
+        self.by_target.get(target_comp_id).or(self.default.as_ref())
+    }
+}
+
+/// Profiles also run as an encoder pipeline stage, with the same placement
+/// rules as [`Encoder::field_policies`].
+///
+/// [`Encoder::field_policies`]: crate::encoder::Encoder::field_policies
+impl EncodeStage for PolicyProfiles {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        let target = msg.find(tag::TARGET_COMP_ID).map_or(&[][..], |f| f.value);
+        let Some(policy) = self.for_target(target) else {
+            copy_fields(msg, out);
+            return Ok(());
+        };
+        let msg_type = msg.find(tag::MSG_TYPE).map_or(&[][..], |f| f.value);
+        let push_defaults = |out: &mut MessageBuilder, header: bool| {
+            for (t, value) in policy.defaults(msg_type) {
+                if is_header_tag(t) == header && msg.find(t).is_none() {
+                    out.push(t, value);
+                }
+            }
+        };
+
+        let mut header_defaults_written = false;
+        for f in msg.fields() {
+            if !header_defaults_written && !is_header_tag(f.tag) {
+                push_defaults(out, true);
+                header_defaults_written = true;
+            }
+            if !policy.strips(msg_type, f.tag) {
+                out.push(f.tag, f.value);
+            }
+        }
+        if !header_defaults_written {
+            push_defaults(out, true);
+        }
+        push_defaults(out, false);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tag;
+
+    #[test]
+    fn rules_are_scoped_by_msg_type() {
+        let mut p = FieldPolicy::new();
+        p.always_send(tag::HANDL_INST, b"1", &[b"D", b"G"])
+            .strip(tag::TRANSACT_TIME, &[]);
+        assert!(p.strips(b"D", tag::TRANSACT_TIME));
+        assert!(p.strips(b"8", tag::TRANSACT_TIME));
+        assert!(!p.strips(b"D", tag::HANDL_INST));
+        assert_eq!(
+            p.defaults(b"D").collect::<Vec<_>>(),
+            vec![(tag::HANDL_INST, &b"1"[..])]
+        );
+        assert_eq!(p.defaults(b"F").count(), 0);
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let mut p = FieldPolicy::new();
+        p.strip(tag::HANDL_INST, &[b"F"])
+            .always_send(tag::HANDL_INST, b"1", &[]);
+        assert!(p.strips(b"F", tag::HANDL_INST));
+        assert_eq!(p.defaults(b"F").count(), 0);
+        assert_eq!(p.defaults(b"D").count(), 1);
+    }
+
+    #[test]
+    fn profiles_fall_back_to_default() {
+        let mut legacy = FieldPolicy::new();
+        legacy.always_send(tag::HANDL_INST, b"1", &[]);
+        let mut profiles = PolicyProfiles::new();
+        profiles.insert(b"LEGACY", legacy.clone());
+        assert_eq!(profiles.for_target(b"LEGACY"), Some(&legacy));
+        assert_eq!(profiles.for_target(b"OTHER"), None);
+        profiles.set_default(FieldPolicy::new());
+        assert!(profiles.for_target(b"OTHER").unwrap().is_empty());
+    }
+
+    #[test]
+    fn profiles_as_encode_stage() {
+        let mut legacy = FieldPolicy::new();
+        legacy
+            .always_send(tag::HANDL_INST, b"1", &[])
+            .strip(tag::TRANSACT_TIME, &[]);
+        let mut profiles = PolicyProfiles::new();
+        profiles.insert(b"LEGACY", legacy);
+
+        let mut dec = crate::decoder::Decoder::new();
+        let msg = dec
+            .decode(b"35=D\x0156=LEGACY\x0111=1\x0160=20240229-13:45:06\x01")
+            .unwrap();
+        let mut out = MessageBuilder::new();
+        profiles.apply(&msg, &mut out).unwrap();
+        let tags: Vec<_> = out.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, vec![35, 56, 11, 21]);
+    }
+}
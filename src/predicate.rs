@@ -0,0 +1,187 @@
+use crate::message::Message;
+use crate::tag::Tag;
+
+/// A composable filter evaluated zero-copy against a decoded [`Message`].
+///
+/// Predicates are built once (e.g. from CLI arguments or configuration) and
+/// evaluated against every message of a stream. Evaluation never allocates:
+/// tag lookups go through [`Message::find`] and values are compared as byte
+/// slices directly against the input buffer.
+///
+/// Value predicates look at the *first* occurrence of a tag, matching the
+/// semantics of [`Message::find`].
+///
+/// # Example
+/// ```ignore
+/// // ExecutionReports that are fills or partial fills.
+/// let pred = Pred::tag(tag::MSG_TYPE)
+///     .eq(b"8")
+///     .and(Pred::tag(tag::EXEC_TYPE).one_of([&b"1"[..], b"2", b"F"]));
+///
+/// if pred.matches(&msg) {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pred {
+    /// Matches every message.
+    Any,
+    /// The tag is present (with any value).
+    Exists(Tag),
+    /// The tag is present and its value equals the bytes exactly.
+    Eq(Tag, Vec<u8>),
+    /// The tag is present and its value equals one of the alternatives.
+    OneOf(Tag, Vec<Vec<u8>>),
+    /// The tag is present and its value starts with the bytes.
+    StartsWith(Tag, Vec<u8>),
+    /// Both predicates match.
+    And(Box<Pred>, Box<Pred>),
+    /// At least one of the predicates matches.
+    Or(Box<Pred>, Box<Pred>),
+    /// The inner predicate does not match.
+    Not(Box<Pred>),
+}
+
+/// Intermediate builder returned by [`Pred::tag`]; pick a comparison to turn
+/// it into a [`Pred`].
+#[derive(Debug, Clone, Copy)]
+pub struct TagPred {
+    tag: Tag,
+}
+
+impl TagPred {
+    /// The tag is present, whatever its value.
+    pub fn exists(self) -> Pred {
+        Pred::Exists(self.tag)
+    }
+
+    /// The tag is present and its value equals `value`.
+    pub fn eq(self, value: &[u8]) -> Pred {
+        Pred::Eq(self.tag, value.to_vec())
+    }
+
+    /// The tag is absent or its value differs from `value`.
+    pub fn ne(self, value: &[u8]) -> Pred {
+        Pred::Not(Box::new(self.eq(value)))
+    }
+
+    /// The tag is present and its value equals one of `values`.
+    pub fn one_of<I, V>(self, values: I) -> Pred
+    where
+        I: IntoIterator<Item = V>,
+        V: AsRef<[u8]>,
+    {
+        Pred::OneOf(
+            self.tag,
+            values.into_iter().map(|v| v.as_ref().to_vec()).collect(),
+        )
+    }
+
+    /// The tag is present and its value starts with `prefix`.
+    pub fn starts_with(self, prefix: &[u8]) -> Pred {
+        Pred::StartsWith(self.tag, prefix.to_vec())
+    }
+}
+
+impl Pred {
+    /// Start a predicate on `tag`.
+    #[inline]
+    pub fn tag(tag: Tag) -> TagPred {
+        TagPred { tag }
+    }
+
+    /// Combine with `other`; both must match.
+    pub fn and(self, other: Pred) -> Pred {
+        Pred::And(Box::new(self), Box::new(other))
+    }
+
+    /// Combine with `other`; at least one must match.
+    pub fn or(self, other: Pred) -> Pred {
+        Pred::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Evaluate the predicate against `msg`.
+    pub fn matches(&self, msg: &Message<'_>) -> bool {
+        match self {
+            Pred::Any => true,
+            Pred::Exists(tag) => msg.find(*tag).is_some(),
+            Pred::Eq(tag, value) => msg.find(*tag).is_some_and(|f| f.value == value.as_slice()),
+            Pred::OneOf(tag, values) => msg
+                .find(*tag)
+                .is_some_and(|f| values.iter().any(|v| f.value == v.as_slice())),
+            Pred::StartsWith(tag, prefix) => {
+                msg.find(*tag).is_some_and(|f| f.value.starts_with(prefix))
+            }
+            Pred::And(a, b) => a.matches(msg) && b.matches(msg),
+            Pred::Or(a, b) => a.matches(msg) || b.matches(msg),
+            Pred::Not(p) => !p.matches(msg),
+        }
+    }
+}
+
+impl std::ops::Not for Pred {
+    type Output = Pred;
+
+    /// Negate this predicate.
+    fn not(self) -> Pred {
+        Pred::Not(Box::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::tag;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    #[test]
+    fn eq_and_one_of() {
+        let raw = fix("8=FIX.4.4|35=8|150=F|55=AAPL|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let pred = Pred::tag(tag::MSG_TYPE)
+            .eq(b"8")
+            .and(Pred::tag(tag::EXEC_TYPE).one_of([&b"1"[..], b"2", b"F"]));
+        assert!(pred.matches(&msg));
+
+        let pred = Pred::tag(tag::MSG_TYPE)
+            .eq(b"8")
+            .and(Pred::tag(tag::EXEC_TYPE).one_of([b"0"]));
+        assert!(!pred.matches(&msg));
+    }
+
+    #[test]
+    fn exists_and_absent_tags() {
+        let raw = fix("35=D|55=AAPL|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        assert!(Pred::tag(tag::SYMBOL).exists().matches(&msg));
+        assert!(!Pred::tag(tag::PRICE).exists().matches(&msg));
+        // A value comparison on an absent tag never matches...
+        assert!(!Pred::tag(tag::PRICE).eq(b"1").matches(&msg));
+        // ...but its negation does.
+        assert!(Pred::tag(tag::PRICE).ne(b"1").matches(&msg));
+    }
+
+    #[test]
+    fn or_not_and_starts_with() {
+        let raw = fix("35=D|55=AAPL.O|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let pred = Pred::tag(tag::SYMBOL)
+            .starts_with(b"MSFT")
+            .or(Pred::tag(tag::SYMBOL).starts_with(b"AAPL"));
+        assert!(pred.matches(&msg));
+        assert!(!(!pred.clone()).matches(&msg));
+        assert!(Pred::Any.matches(&msg));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::decoder::Decoder;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::timestamp::UtcTimestamp;
+use crate::validate::{Validator, Violation};
+
+/// Why a message was quarantined.
+#[derive(Debug, Clone, Copy)]
+pub enum QuarantineReason<'a> {
+    /// Decoding or the BodyLength/CheckSum integrity check failed.
+    Decode(&'a FixError),
+    /// The message decoded but failed dictionary validation.
+    Invalid(&'a [Violation<'a>]),
+}
+
+/// One garbled message as handed to a [`QuarantineSink`].
+///
+/// Everything is borrowed: a sink that keeps entries must copy what it
+/// needs (see [`QuarantinedMessage`]).
+#[derive(Debug, Clone, Copy)]
+pub struct QuarantineEntry<'a> {
+    /// Caller-chosen session label, e.g. `b"CLIENT->BROKER"`.
+    pub session: &'a [u8],
+    /// The raw bytes exactly as received.
+    pub raw: &'a [u8],
+    pub reason: QuarantineReason<'a>,
+    /// When the failure was detected.
+    pub at: UtcTimestamp,
+}
+
+/// Destination for garbled messages, so ops can inspect every one a
+/// counterparty sent instead of only seeing the returned error.
+///
+/// Implemented for closures taking a `&QuarantineEntry` and for
+/// `Vec<QuarantinedMessage>`.
+pub trait QuarantineSink {
+    fn quarantine(&mut self, entry: &QuarantineEntry<'_>);
+}
+
+impl<F> QuarantineSink for F
+where
+    F: FnMut(&QuarantineEntry<'_>),
+{
+    #[inline]
+    fn quarantine(&mut self, entry: &QuarantineEntry<'_>) {
+        self(entry)
+    }
+}
+
+/// An owned copy of a [`QuarantineEntry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedMessage {
+    pub session: Vec<u8>,
+    pub raw: Vec<u8>,
+    /// `Debug` rendering of the error, or of the violations.
+    pub detail: String,
+    pub at: UtcTimestamp,
+}
+
+impl From<&QuarantineEntry<'_>> for QuarantinedMessage {
+    fn from(entry: &QuarantineEntry<'_>) -> Self {
+        let detail = match entry.reason {
+            QuarantineReason::Decode(err) => format!("{err:?}"),
+            QuarantineReason::Invalid(violations) => format!("{violations:?}"),
+        };
+        Self {
+            session: entry.session.to_vec(),
+            raw: entry.raw.to_vec(),
+            detail,
+            at: entry.at,
+        }
+    }
+}
+
+impl QuarantineSink for Vec<QuarantinedMessage> {
+    fn quarantine(&mut self, entry: &QuarantineEntry<'_>) {
+        self.push(entry.into());
+    }
+}
+
+/// Routes decode, integrity and validation failures of one session to a
+/// [`QuarantineSink`].
+///
+/// Every method still returns the failure to the caller; the sink only
+/// receives a copy.
+///
+/// # Example
+/// ```ignore
+/// let mut q = Quarantine::new(b"CLIENT->BROKER", Vec::new());
+/// let msg = q.decode(&mut decoder, buf)?;
+/// q.check_integrity(&msg)?;
+/// if !q.validate(&validator, &msg, &mut violations) {
+///     reject(&violations);
+/// }
+/// for bad in q.sink() { ops_log(bad); }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Quarantine<S> {
+    session: Vec<u8>,
+    sink: S,
+}
+
+impl<S: QuarantineSink> Quarantine<S> {
+    pub fn new(session: &[u8], sink: S) -> Self {
+        Self {
+            session: session.to_vec(),
+            sink,
+        }
+    }
+
+    /// The session label given to every entry.
+    #[inline]
+    pub fn session(&self) -> &[u8] {
+        &self.session
+    }
+
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    #[inline]
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+
+    /// Decode `buf`, quarantining it if decoding fails.
+    pub fn decode<'a>(
+        &mut self,
+        decoder: &'a mut Decoder,
+        buf: &'a [u8],
+    ) -> Result<Message<'a>, FixError> {
+        decoder.decode(buf).inspect_err(|err| {
+            self.report(buf, QuarantineReason::Decode(err));
+        })
+    }
+
+    /// Check BodyLength (9) and CheckSum (10), quarantining the message if
+    /// either is wrong.
+    pub fn check_integrity(&mut self, msg: &Message<'_>) -> Result<(), FixError> {
+        msg.validate_body_length()
+            .and_then(|()| msg.validate_checksum())
+            .inspect_err(|err| self.report(msg.buf, QuarantineReason::Decode(err)))
+    }
+
+    /// Run `validator` on `msg`, quarantining the message if any violation
+    /// is found. Violations are appended to `out` as with
+    /// [`Validator::validate`].
+    pub fn validate<'a>(
+        &mut self,
+        validator: &Validator<'_>,
+        msg: &Message<'a>,
+        out: &mut Vec<Violation<'a>>,
+    ) -> bool {
+        let before = out.len();
+        if validator.validate(msg, out) {
+            return true;
+        }
+        self.report(msg.buf, QuarantineReason::Invalid(&out[before..]));
+        false
+    }
+
+    fn report(&mut self, raw: &[u8], reason: QuarantineReason<'_>) {
+        self.sink.quarantine(&QuarantineEntry {
+            session: &self.session,
+            raw,
+            reason,
+            at: UtcTimestamp::now(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::Dictionary;
+    use crate::validate::SessionRejectReason;
+
+    #[test]
+    fn decode_failure_is_quarantined() {
+        let mut q = Quarantine::new(b"S->T", Vec::new());
+        let mut dec = Decoder::new();
+        assert!(q.decode(&mut dec, b"35=D\x0149").is_err());
+        assert!(q.decode(&mut dec, b"35=D\x01").is_ok());
+
+        let sink = q.into_sink();
+        assert_eq!(sink.len(), 1);
+        assert_eq!(sink[0].session, b"S->T");
+        assert_eq!(sink[0].raw, b"35=D\x0149");
+        assert_eq!(sink[0].detail, "IncompleteMessage { offset: 5 }");
+    }
+
+    #[test]
+    fn integrity_failure_is_quarantined() {
+        let mut seen = Vec::new();
+        let mut q = Quarantine::new(b"S->T", |e: &QuarantineEntry<'_>| {
+            seen.push(matches!(
+                e.reason,
+                QuarantineReason::Decode(FixError::InvalidCheckSum { .. })
+            ))
+        });
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"8=FIX.4.2\x019=5\x0135=0\x0110=000\x01")
+            .unwrap();
+        assert!(matches!(
+            q.check_integrity(&msg),
+            Err(FixError::InvalidCheckSum { offset: 19 })
+        ));
+        let msg = dec
+            .decode(b"8=FIX.4.2\x019=5\x0135=0\x0110=161\x01")
+            .unwrap();
+        assert!(q.check_integrity(&msg).is_ok());
+        drop(q);
+        assert_eq!(seen, vec![true]);
+    }
+
+    #[test]
+    fn validation_failure_is_quarantined() {
+        let dict = Dictionary::fix44();
+        let validator = Validator::new(&dict);
+        let mut q = Quarantine::new(b"S->T", Vec::new());
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=D\x0159=Z\x01").unwrap();
+        let mut violations = Vec::new();
+        assert!(!q.validate(&validator, &msg, &mut violations));
+        assert_eq!(violations[0].reason, SessionRejectReason::ValueIsIncorrect);
+        assert_eq!(q.sink().len(), 1);
+        assert!(q.sink()[0].detail.contains("ValueIsIncorrect"));
+    }
+}
@@ -0,0 +1,420 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::group::{self, Group, GroupIter};
+use crate::message::Message;
+use crate::response::mirror_routing;
+use crate::tag::{self, Tag};
+
+/// Instrument fields of a QuoteRequest instance carried over into the
+/// Quote answering it, in Quote field order.
+const INSTRUMENT_ECHO: [Tag; 11] = [
+    tag::TRADING_SESSION_ID,
+    tag::SYMBOL,
+    tag::SYMBOL_SFX,
+    tag::SECURITY_ID,
+    tag::SECURITY_ID_SOURCE,
+    tag::SIDE,
+    tag::ORDER_QTY,
+    tag::SETTL_TYPE,
+    tag::SETTL_DATE,
+    tag::CURRENCY,
+    tag::ACCOUNT,
+];
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+fn expect_type(msg: &Message<'_>, msg_type: &[u8]) -> Result<(), FixError> {
+    match value(msg, tag::MSG_TYPE) {
+        Some(t) if t == msg_type => Ok(()),
+        _ => Err(FixError::InvalidValue),
+    }
+}
+
+/// One `NO_RELATED_SYM` instance of a QuoteRequest: an instrument to be
+/// quoted, with its own side and quantity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuoteRequestInstrument<'a> {
+    pub symbol: &'a [u8],
+    pub security_id: Option<&'a [u8]>,
+    pub security_id_source: Option<&'a [u8]>,
+    /// QuoteRequestType (303): `1` manual, `2` automatic.
+    pub quote_request_type: Option<&'a [u8]>,
+    /// QuoteType (537): `0` indicative, `1` tradeable.
+    pub quote_type: Option<&'a [u8]>,
+    /// Side (54); absent for a two-sided quote.
+    pub side: Option<&'a [u8]>,
+    pub order_qty: Option<&'a [u8]>,
+    pub settl_type: Option<&'a [u8]>,
+    pub settl_date: Option<&'a [u8]>,
+    pub ord_type: Option<&'a [u8]>,
+    pub currency: Option<&'a [u8]>,
+    pub account: Option<&'a [u8]>,
+    pub expire_time: Option<&'a [u8]>,
+    pub transact_time: Option<&'a [u8]>,
+    pub price: Option<&'a [u8]>,
+}
+
+/// Read access to a QuoteRequest (35=R).
+///
+/// # Example
+/// ```ignore
+/// let req = QuoteRequest::new(&msg)?;
+/// for (i, inst) in req.instruments().enumerate() {
+///     let mut b = MessageBuilder::new();
+///     QuoteBuilder::for_request(&req, &inst, &quote_id(i))
+///         .bid(b"99.5", b"100")
+///         .offer(b"100.5", b"100")
+///         .write_to(&mut b);
+///     send(&b);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteRequest<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> QuoteRequest<'a> {
+    /// `InvalidValue` if `msg` is not a QuoteRequest.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"R")?;
+        Ok(Self { msg })
+    }
+
+    /// QuoteReqID (131), echoed on every Quote answering this request.
+    pub fn quote_req_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::QUOTE_REQ_ID)
+    }
+
+    /// RFQReqID (644) of the RFQRequest that triggered this request, if any.
+    pub fn rfq_req_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::RFQ_REQ_ID)
+    }
+
+    /// ClOrdID (11), when the request is tied to an order.
+    pub fn cl_ord_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CL_ORD_ID)
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// The `NO_RELATED_SYM` instances, read with
+    /// [`group::QUOTE_REQUEST_RELATED_SYM`].
+    pub fn instruments(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::QUOTE_REQUEST_RELATED_SYM)
+    }
+
+    /// The first instance requesting `symbol`.
+    pub fn instrument(&self, symbol: &[u8]) -> Option<Group<'a>> {
+        self.instruments()
+            .find(|g| g.find(tag::SYMBOL).is_some_and(|f| f.value == symbol))
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of a QuoteRequest (35=R).
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// QuoteRequestBuilder::new(b"QR1")
+///     .instrument(QuoteRequestInstrument { symbol: b"IBM", side: Some(b"1"), order_qty: Some(b"500"), ..Default::default() })
+///     .instrument(QuoteRequestInstrument { symbol: b"MSFT", order_qty: Some(b"200"), ..Default::default() })
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuoteRequestBuilder<'a> {
+    quote_req_id: &'a [u8],
+    rfq_req_id: Option<&'a [u8]>,
+    cl_ord_id: Option<&'a [u8]>,
+    text: Option<&'a [u8]>,
+    instruments: Vec<QuoteRequestInstrument<'a>>,
+}
+
+impl<'a> QuoteRequestBuilder<'a> {
+    pub fn new(quote_req_id: &'a [u8]) -> Self {
+        Self {
+            quote_req_id,
+            rfq_req_id: None,
+            cl_ord_id: None,
+            text: None,
+            instruments: Vec::new(),
+        }
+    }
+
+    /// RFQReqID (644).
+    pub fn rfq_req_id(mut self, rfq_req_id: &'a [u8]) -> Self {
+        self.rfq_req_id = Some(rfq_req_id);
+        self
+    }
+
+    pub fn cl_ord_id(mut self, cl_ord_id: &'a [u8]) -> Self {
+        self.cl_ord_id = Some(cl_ord_id);
+        self
+    }
+
+    pub fn text(mut self, text: &'a [u8]) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Add a `NO_RELATED_SYM` instance.
+    pub fn instrument(mut self, instrument: QuoteRequestInstrument<'a>) -> Self {
+        self.instruments.push(instrument);
+        self
+    }
+
+    /// Append MsgType `R` and the request fields to `b`. Header fields
+    /// other than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"R")
+            .push(tag::QUOTE_REQ_ID, self.quote_req_id);
+        for (t, v) in [
+            (tag::RFQ_REQ_ID, self.rfq_req_id),
+            (tag::CL_ORD_ID, self.cl_ord_id),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+
+        let mut g = b.begin_group(&group::QUOTE_REQUEST_RELATED_SYM);
+        for i in &self.instruments {
+            g.instance().push(tag::SYMBOL, i.symbol);
+            for (t, v) in [
+                (tag::SECURITY_ID, i.security_id),
+                (tag::SECURITY_ID_SOURCE, i.security_id_source),
+                (tag::QUOTE_REQUEST_TYPE, i.quote_request_type),
+                (tag::QUOTE_TYPE, i.quote_type),
+                (tag::SIDE, i.side),
+                (tag::ORDER_QTY, i.order_qty),
+                (tag::SETTL_TYPE, i.settl_type),
+                (tag::SETTL_DATE, i.settl_date),
+                (tag::ORD_TYPE, i.ord_type),
+                (tag::CURRENCY, i.currency),
+                (tag::ACCOUNT, i.account),
+                (tag::EXPIRE_TIME, i.expire_time),
+                (tag::TRANSACT_TIME, i.transact_time),
+                (tag::PRICE, i.price),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+        }
+        drop(g);
+
+        if let Some(v) = self.text {
+            b.push(tag::TEXT, v);
+        }
+    }
+}
+
+/// Writes a Quote (35=S), either unsolicited or answering one instrument
+/// of a QuoteRequest.
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// QuoteBuilder::new(b"Q1", b"IBM")
+///     .bid(b"99.5", b"100")
+///     .valid_until_time(b"20240229-15:00:30")
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuoteBuilder<'a> {
+    quote_id: &'a [u8],
+    request: Option<&'a Message<'a>>,
+    quote_req_id: Option<&'a [u8]>,
+    instrument: Vec<(Tag, &'a [u8])>,
+    quote_type: Option<&'a [u8]>,
+    bid_px: Option<&'a [u8]>,
+    offer_px: Option<&'a [u8]>,
+    bid_size: Option<&'a [u8]>,
+    offer_size: Option<&'a [u8]>,
+    valid_until_time: Option<&'a [u8]>,
+    transact_time: Option<&'a [u8]>,
+    text: Option<&'a [u8]>,
+}
+
+impl<'a> QuoteBuilder<'a> {
+    /// An unsolicited quote for `symbol`.
+    pub fn new(quote_id: &'a [u8], symbol: &'a [u8]) -> Self {
+        Self {
+            quote_id,
+            request: None,
+            quote_req_id: None,
+            instrument: vec![(tag::SYMBOL, symbol)],
+            quote_type: None,
+            bid_px: None,
+            offer_px: None,
+            bid_size: None,
+            offer_size: None,
+            valid_until_time: None,
+            transact_time: None,
+            text: None,
+        }
+    }
+
+    /// A quote answering `instrument`, one of `request`'s
+    /// [`instruments`](QuoteRequest::instruments): the request's routing
+    /// IDs are mirrored, its QuoteReqID (131) is echoed, and the
+    /// instrument's TradingSessionID, Symbol, SymbolSfx, SecurityID,
+    /// SecurityIDSource, Side, OrderQty, SettlType, SettlDate, Currency and
+    /// Account are carried over.
+    pub fn for_request(
+        request: &QuoteRequest<'a>,
+        instrument: &Group<'a>,
+        quote_id: &'a [u8],
+    ) -> Self {
+        let mut quote = Self::new(quote_id, b"");
+        quote.request = Some(request.msg);
+        quote.quote_req_id = request.quote_req_id();
+        quote.instrument = INSTRUMENT_ECHO
+            .iter()
+            .filter_map(|&t| instrument.find(t).map(|f| (t, f.value)))
+            .collect();
+        quote
+    }
+
+    /// QuoteType (537): `0` indicative, `1` tradeable.
+    pub fn quote_type(mut self, quote_type: &'a [u8]) -> Self {
+        self.quote_type = Some(quote_type);
+        self
+    }
+
+    /// BidPx (132) and BidSize (134); the size is omitted when empty.
+    pub fn bid(mut self, px: &'a [u8], size: &'a [u8]) -> Self {
+        self.bid_px = Some(px);
+        self.bid_size = (!size.is_empty()).then_some(size);
+        self
+    }
+
+    /// OfferPx (133) and OfferSize (135); the size is omitted when empty.
+    pub fn offer(mut self, px: &'a [u8], size: &'a [u8]) -> Self {
+        self.offer_px = Some(px);
+        self.offer_size = (!size.is_empty()).then_some(size);
+        self
+    }
+
+    pub fn valid_until_time(mut self, time: &'a [u8]) -> Self {
+        self.valid_until_time = Some(time);
+        self
+    }
+
+    pub fn transact_time(mut self, time: &'a [u8]) -> Self {
+        self.transact_time = Some(time);
+        self
+    }
+
+    pub fn text(mut self, text: &'a [u8]) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Append MsgType `S`, the mirrored routing IDs of the request (if
+    /// any), QuoteReqID, QuoteID, the instrument and the prices to `b`.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"S");
+        if let Some(req) = self.request {
+            mirror_routing(req, b);
+        }
+        if let Some(v) = self.quote_req_id {
+            b.push(tag::QUOTE_REQ_ID, v);
+        }
+        b.push(tag::QUOTE_ID, self.quote_id);
+        if let Some(v) = self.quote_type {
+            b.push(tag::QUOTE_TYPE, v);
+        }
+        for &(t, v) in &self.instrument {
+            b.push(t, v);
+        }
+        for (t, v) in [
+            (tag::BID_PX, self.bid_px),
+            (tag::OFFER_PX, self.offer_px),
+            (tag::BID_SIZE, self.bid_size),
+            (tag::OFFER_SIZE, self.offer_size),
+            (tag::VALID_UNTIL_TIME, self.valid_until_time),
+            (tag::TRANSACT_TIME, self.transact_time),
+            (tag::TEXT, self.text),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn quote_request_round_trip_and_quotes_per_instrument() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::SENDER_COMP_ID, b"CLIENT")
+            .push(tag::TARGET_COMP_ID, b"DEALER");
+        QuoteRequestBuilder::new(b"QR1")
+            .instrument(QuoteRequestInstrument {
+                symbol: b"IBM",
+                side: Some(b"1"),
+                order_qty: Some(b"500"),
+                currency: Some(b"USD"),
+                ..Default::default()
+            })
+            .instrument(QuoteRequestInstrument {
+                symbol: b"MSFT",
+                order_qty: Some(b"200"),
+                quote_type: Some(b"1"),
+                ..Default::default()
+            })
+            .write_to(&mut b);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let req = QuoteRequest::new(&msg).unwrap();
+        assert_eq!(req.quote_req_id(), Some(&b"QR1"[..]));
+        let qty: Vec<_> = req
+            .instruments()
+            .map(|g| g.find(tag::ORDER_QTY).unwrap().value)
+            .collect();
+        assert_eq!(qty, [&b"500"[..], b"200"]);
+        let msft = req.instrument(b"MSFT").unwrap();
+        assert!(msft.find(tag::SIDE).is_none());
+        assert!(req.instrument(b"AAPL").is_none());
+
+        let ibm = req.instrument(b"IBM").unwrap();
+        let mut b = MessageBuilder::new();
+        QuoteBuilder::for_request(&req, &ibm, b"Q1")
+            .bid(b"99.5", b"500")
+            .write_to(&mut b);
+        let quote = b.as_message();
+        let tags: Vec<Tag> = quote.fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 49, 56, 131, 117, 55, 54, 38, 15, 132, 134]);
+        assert_eq!(quote.find(tag::QUOTE_REQ_ID).unwrap().value, b"QR1");
+        assert_eq!(quote.find(tag::SENDER_COMP_ID).unwrap().value, b"DEALER");
+    }
+
+    #[test]
+    fn unsolicited_quote_and_wrong_type() {
+        let mut b = MessageBuilder::new();
+        QuoteBuilder::new(b"Q9", b"IBM")
+            .offer(b"100.25", b"")
+            .write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 117, 55, 133]);
+        assert!(QuoteRequest::new(&b.as_message()).is_err());
+    }
+}
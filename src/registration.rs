@@ -0,0 +1,515 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::group::{self, GroupIter};
+use crate::message::Message;
+use crate::response::{self, respond_with};
+use crate::tag::{self, Tag};
+
+/// RegistTransType (514) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistTransType {
+    New,
+    Replace,
+    Cancel,
+}
+
+impl RegistTransType {
+    /// The character carried in tag 514.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::New => b'0',
+            Self::Replace => b'1',
+            Self::Cancel => b'2',
+        }
+    }
+
+    /// The type for a tag 514 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"0" => Some(Self::New),
+            b"1" => Some(Self::Replace),
+            b"2" => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
+
+/// RegistStatus (506) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistStatus {
+    Accepted,
+    Rejected,
+    Held,
+    /// Reminder that the instructions are still outstanding.
+    Reminder,
+}
+
+impl RegistStatus {
+    /// The character carried in tag 506.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Accepted => b'A',
+            Self::Rejected => b'R',
+            Self::Held => b'H',
+            Self::Reminder => b'N',
+        }
+    }
+
+    /// The status for a tag 506 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"A" => Some(Self::Accepted),
+            b"R" => Some(Self::Rejected),
+            b"H" => Some(Self::Held),
+            b"N" => Some(Self::Reminder),
+            _ => None,
+        }
+    }
+}
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+fn expect_type(msg: &Message<'_>, msg_type: &[u8]) -> Result<(), FixError> {
+    match value(msg, tag::MSG_TYPE) {
+        Some(t) if t == msg_type => Ok(()),
+        _ => Err(FixError::InvalidValue),
+    }
+}
+
+/// One `NO_REGIST_DTLS` instance: a registered holder of the account.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RegistDetail<'a> {
+    /// RegistDtls (509): the registration name and address line.
+    pub regist_dtls: &'a [u8],
+    pub regist_email: Option<&'a [u8]>,
+    pub mailing_dtls: Option<&'a [u8]>,
+    pub mailing_inst: Option<&'a [u8]>,
+    /// OwnerType (522), e.g. `1` individual investor, `4` company trustee.
+    pub owner_type: Option<&'a [u8]>,
+    /// DateOfBirth (486), `YYYYMMDD`.
+    pub date_of_birth: Option<&'a [u8]>,
+    pub investor_country_of_residence: Option<&'a [u8]>,
+}
+
+/// One `NO_DISTRIB_INSTS` instance: where a share of the cash
+/// distributions is paid.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DistribInstruction<'a> {
+    /// DistribPaymentMethod (477), e.g. `1` CREST, `5` direct credit.
+    pub payment_method: &'a [u8],
+    /// DistribPercentage (512).
+    pub percentage: Option<&'a [u8]>,
+    pub currency: Option<&'a [u8]>,
+    pub agent_name: Option<&'a [u8]>,
+    pub agent_code: Option<&'a [u8]>,
+    pub agent_acct_number: Option<&'a [u8]>,
+    pub pay_ref: Option<&'a [u8]>,
+    pub agent_acct_name: Option<&'a [u8]>,
+}
+
+/// Read access to a RegistrationInstructions (35=o).
+///
+/// # Example
+/// ```ignore
+/// let reg = RegistrationInstructions::new(&msg)?;
+/// for holder in reg.details() {
+///     registry.add(reg.account(), holder.find(tag::REGIST_DTLS));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RegistrationInstructions<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> RegistrationInstructions<'a> {
+    /// `InvalidValue` if `msg` is not a RegistrationInstructions.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"o")?;
+        Ok(Self { msg })
+    }
+
+    /// RegistID (513).
+    pub fn regist_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::REGIST_ID)
+    }
+
+    /// RegistTransType (514), `None` if absent or not a known type.
+    pub fn trans_type(&self) -> Option<RegistTransType> {
+        value(self.msg, tag::REGIST_TRANS_TYPE).and_then(RegistTransType::from_code)
+    }
+
+    /// RegistRefID (508): the instructions replaced or cancelled.
+    pub fn ref_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::REGIST_REF_ID)
+    }
+
+    /// ClOrdID (11) of the order these instructions settle, if any.
+    pub fn cl_ord_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CL_ORD_ID)
+    }
+
+    pub fn account(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ACCOUNT)
+    }
+
+    /// RegistAcctType (493).
+    pub fn acct_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::REGIST_ACCT_TYPE)
+    }
+
+    /// TaxAdvantageType (495).
+    pub fn tax_advantage_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TAX_ADVANTAGE_TYPE)
+    }
+
+    /// OwnershipType (517): `J` joint, `T` tenants in common, `2` joint
+    /// trustees.
+    pub fn ownership_type(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::OWNERSHIP_TYPE)
+    }
+
+    /// The `NO_REGIST_DTLS` instances.
+    pub fn details(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::REGIST_DTLS)
+    }
+
+    /// The `NO_DISTRIB_INSTS` instances.
+    pub fn distrib_insts(&self) -> GroupIter<'a> {
+        self.msg.groups(&group::DISTRIB_INSTS)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes the body of a RegistrationInstructions (35=o).
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// RegistrationInstructionsBuilder::new(b"REG1", RegistTransType::New)
+///     .account(b"ISA-0042")
+///     .detail(RegistDetail {
+///         regist_dtls: b"J SMITH, 1 HIGH ST",
+///         owner_type: Some(b"1"),
+///         ..Default::default()
+///     })
+///     .distrib_inst(DistribInstruction {
+///         payment_method: b"5",
+///         percentage: Some(b"100"),
+///         ..Default::default()
+///     })
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegistrationInstructionsBuilder<'a> {
+    regist_id: &'a [u8],
+    trans_type: RegistTransType,
+    ref_id: Option<&'a [u8]>,
+    cl_ord_id: Option<&'a [u8]>,
+    account: Option<&'a [u8]>,
+    acct_type: Option<&'a [u8]>,
+    tax_advantage_type: Option<&'a [u8]>,
+    ownership_type: Option<&'a [u8]>,
+    details: Vec<RegistDetail<'a>>,
+    distrib_insts: Vec<DistribInstruction<'a>>,
+}
+
+impl<'a> RegistrationInstructionsBuilder<'a> {
+    pub fn new(regist_id: &'a [u8], trans_type: RegistTransType) -> Self {
+        Self {
+            regist_id,
+            trans_type,
+            ref_id: None,
+            cl_ord_id: None,
+            account: None,
+            acct_type: None,
+            tax_advantage_type: None,
+            ownership_type: None,
+            details: Vec::new(),
+            distrib_insts: Vec::new(),
+        }
+    }
+
+    /// RegistRefID (508), for a replace or cancel.
+    pub fn ref_id(mut self, ref_id: &'a [u8]) -> Self {
+        self.ref_id = Some(ref_id);
+        self
+    }
+
+    pub fn cl_ord_id(mut self, cl_ord_id: &'a [u8]) -> Self {
+        self.cl_ord_id = Some(cl_ord_id);
+        self
+    }
+
+    pub fn account(mut self, account: &'a [u8]) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// RegistAcctType (493).
+    pub fn acct_type(mut self, acct_type: &'a [u8]) -> Self {
+        self.acct_type = Some(acct_type);
+        self
+    }
+
+    pub fn tax_advantage_type(mut self, tax_advantage_type: &'a [u8]) -> Self {
+        self.tax_advantage_type = Some(tax_advantage_type);
+        self
+    }
+
+    pub fn ownership_type(mut self, ownership_type: &'a [u8]) -> Self {
+        self.ownership_type = Some(ownership_type);
+        self
+    }
+
+    /// Add a `NO_REGIST_DTLS` instance.
+    pub fn detail(mut self, detail: RegistDetail<'a>) -> Self {
+        self.details.push(detail);
+        self
+    }
+
+    /// Add a `NO_DISTRIB_INSTS` instance.
+    pub fn distrib_inst(mut self, inst: DistribInstruction<'a>) -> Self {
+        self.distrib_insts.push(inst);
+        self
+    }
+
+    /// Append MsgType `o` and the registration fields to `b`. Header
+    /// fields other than MsgType are the caller's responsibility.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"o")
+            .push(tag::REGIST_ID, self.regist_id)
+            .push(tag::REGIST_TRANS_TYPE, &[self.trans_type.code()]);
+        for (t, v) in [
+            (tag::REGIST_REF_ID, self.ref_id),
+            (tag::CL_ORD_ID, self.cl_ord_id),
+            (tag::ACCOUNT, self.account),
+            (tag::REGIST_ACCT_TYPE, self.acct_type),
+            (tag::TAX_ADVANTAGE_TYPE, self.tax_advantage_type),
+            (tag::OWNERSHIP_TYPE, self.ownership_type),
+        ] {
+            if let Some(v) = v {
+                b.push(t, v);
+            }
+        }
+
+        let mut g = b.begin_group(&group::REGIST_DTLS);
+        for d in &self.details {
+            g.instance().push(tag::REGIST_DTLS, d.regist_dtls);
+            for (t, v) in [
+                (tag::REGIST_EMAIL, d.regist_email),
+                (tag::MAILING_DTLS, d.mailing_dtls),
+                (tag::MAILING_INST, d.mailing_inst),
+                (tag::OWNER_TYPE, d.owner_type),
+                (tag::DATE_OF_BIRTH, d.date_of_birth),
+                (
+                    tag::INVESTOR_COUNTRY_OF_RESIDENCE,
+                    d.investor_country_of_residence,
+                ),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+        }
+        drop(g);
+
+        let mut g = b.begin_group(&group::DISTRIB_INSTS);
+        for d in &self.distrib_insts {
+            g.instance()
+                .push(tag::DISTRIB_PAYMENT_METHOD, d.payment_method);
+            for (t, v) in [
+                (tag::DISTRIB_PERCENTAGE, d.percentage),
+                (tag::CASH_DISTRIB_CURR, d.currency),
+                (tag::CASH_DISTRIB_AGENT_NAME, d.agent_name),
+                (tag::CASH_DISTRIB_AGENT_CODE, d.agent_code),
+                (tag::CASH_DISTRIB_AGENT_ACCT_NUMBER, d.agent_acct_number),
+                (tag::CASH_DISTRIB_PAY_REF, d.pay_ref),
+                (tag::CASH_DISTRIB_AGENT_ACCT_NAME, d.agent_acct_name),
+            ] {
+                if let Some(v) = v {
+                    g.push(t, v);
+                }
+            }
+        }
+    }
+}
+
+/// Read access to a RegistrationInstructionsResponse (35=p).
+#[derive(Debug, Clone, Copy)]
+pub struct RegistrationInstructionsResponse<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> RegistrationInstructionsResponse<'a> {
+    /// `InvalidValue` if `msg` is not a RegistrationInstructionsResponse.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"p")?;
+        Ok(Self { msg })
+    }
+
+    /// RegistID (513) of the instructions answered.
+    pub fn regist_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::REGIST_ID)
+    }
+
+    /// RegistTransType (514), `None` if absent or not a known type.
+    pub fn trans_type(&self) -> Option<RegistTransType> {
+        value(self.msg, tag::REGIST_TRANS_TYPE).and_then(RegistTransType::from_code)
+    }
+
+    /// RegistRefID (508).
+    pub fn ref_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::REGIST_REF_ID)
+    }
+
+    pub fn cl_ord_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::CL_ORD_ID)
+    }
+
+    pub fn account(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ACCOUNT)
+    }
+
+    /// RegistStatus (506), `None` if absent or not a known status.
+    pub fn status(&self) -> Option<RegistStatus> {
+        value(self.msg, tag::REGIST_STATUS).and_then(RegistStatus::from_code)
+    }
+
+    /// RegistRejReasonCode (507).
+    pub fn rej_reason_code(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::REGIST_REJ_REASON_CODE)
+    }
+
+    /// RegistRejReasonText (496).
+    pub fn rej_reason_text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::REGIST_REJ_REASON_TEXT)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Writes a RegistrationInstructionsResponse (35=p) answering received
+/// instructions.
+///
+/// # Example
+/// ```ignore
+/// let reg = RegistrationInstructions::new(&msg)?;
+/// let mut b = MessageBuilder::new();
+/// RegistrationResponseBuilder::new(&reg, RegistStatus::Rejected)
+///     .reject_reason(b"2", b"Invalid account type")
+///     .write_to(&mut b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegistrationResponseBuilder<'a> {
+    instructions: &'a Message<'a>,
+    status: RegistStatus,
+    rej_reason_code: Option<&'a [u8]>,
+    rej_reason_text: Option<&'a [u8]>,
+}
+
+impl<'a> RegistrationResponseBuilder<'a> {
+    pub fn new(instructions: &RegistrationInstructions<'a>, status: RegistStatus) -> Self {
+        Self {
+            instructions: instructions.msg,
+            status,
+            rej_reason_code: None,
+            rej_reason_text: None,
+        }
+    }
+
+    /// RegistRejReasonCode (507) and RegistRejReasonText (496); the text
+    /// is omitted when empty.
+    pub fn reject_reason(mut self, code: &'a [u8], text: &'a [u8]) -> Self {
+        self.rej_reason_code = Some(code);
+        self.rej_reason_text = (!text.is_empty()).then_some(text);
+        self
+    }
+
+    /// Append MsgType `p`, the instructions' routing IDs mirrored, the
+    /// echoed RegistID, RegistTransType, RegistRefID, ClOrdID and Account,
+    /// then RegistStatus and the reject reason to `b`.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        respond_with(self.instructions, &response::REGISTRATION_INSTRUCTIONS, b);
+        b.push(tag::REGIST_STATUS, &[self.status.code()]);
+        if let Some(v) = self.rej_reason_code {
+            b.push(tag::REGIST_REJ_REASON_CODE, v);
+        }
+        if let Some(v) = self.rej_reason_text {
+            b.push(tag::REGIST_REJ_REASON_TEXT, v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn instructions_round_trip() {
+        let mut b = MessageBuilder::new();
+        b.push(tag::SENDER_COMP_ID, b"IFA")
+            .push(tag::TARGET_COMP_ID, b"FUND");
+        RegistrationInstructionsBuilder::new(b"REG1", RegistTransType::New)
+            .account(b"ISA-0042")
+            .ownership_type(b"J")
+            .detail(RegistDetail {
+                regist_dtls: b"J SMITH",
+                owner_type: Some(b"1"),
+                date_of_birth: Some(b"19700101"),
+                ..Default::default()
+            })
+            .detail(RegistDetail {
+                regist_dtls: b"A SMITH",
+                ..Default::default()
+            })
+            .distrib_inst(DistribInstruction {
+                payment_method: b"5",
+                percentage: Some(b"100"),
+                currency: Some(b"GBP"),
+                ..Default::default()
+            })
+            .write_to(&mut b);
+        let mut out = Vec::new();
+        Encoder::new().encode(&b.as_message(), &mut out).unwrap();
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&out).unwrap();
+        let reg = RegistrationInstructions::new(&msg).unwrap();
+        assert_eq!(reg.trans_type(), Some(RegistTransType::New));
+        assert_eq!(reg.account(), Some(&b"ISA-0042"[..]));
+        let holders: Vec<_> = reg
+            .details()
+            .map(|g| g.find(tag::REGIST_DTLS).unwrap().value)
+            .collect();
+        assert_eq!(holders, [&b"J SMITH"[..], b"A SMITH"]);
+        let inst = reg.distrib_insts().next().unwrap();
+        assert_eq!(inst.find(tag::CASH_DISTRIB_CURR).unwrap().value, b"GBP");
+        assert!(RegistrationInstructionsResponse::new(&msg).is_err());
+
+        let mut b = MessageBuilder::new();
+        RegistrationResponseBuilder::new(&reg, RegistStatus::Rejected)
+            .reject_reason(b"2", b"")
+            .write_to(&mut b);
+        let tags: Vec<Tag> = b.as_message().fields().map(|f| f.tag).collect();
+        assert_eq!(tags, [35, 49, 56, 513, 514, 1, 506, 507]);
+        let reply = b.as_message();
+        let rsp = RegistrationInstructionsResponse::new(&reply).unwrap();
+        assert_eq!(rsp.regist_id(), Some(&b"REG1"[..]));
+        assert_eq!(rsp.status(), Some(RegistStatus::Rejected));
+        assert_eq!(rsp.rej_reason_code(), Some(&b"2"[..]));
+        assert_eq!(rsp.rej_reason_text(), None);
+    }
+}
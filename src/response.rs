@@ -0,0 +1,500 @@
+use crate::builder::MessageBuilder;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// How a request MsgType is answered: the response MsgType and the
+/// correlating fields echoed back from the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseSpec {
+    /// MsgType (35) of the request.
+    pub request: &'static [u8],
+    /// MsgType (35) of the response.
+    pub response: &'static [u8],
+    /// Fields copied from the request, in this order, when present.
+    pub echo: &'static [Tag],
+}
+
+/// TestRequest (1) → Heartbeat (0) carrying the TestReqID.
+pub const TEST_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"1",
+    response: b"0",
+    echo: &[tag::TEST_REQ_ID],
+};
+
+/// Logon (A) → Logon (A), agreeing on EncryptMethod and HeartBtInt.
+pub const LOGON: ResponseSpec = ResponseSpec {
+    request: b"A",
+    response: b"A",
+    echo: &[tag::ENCRYPT_METHOD, tag::HEART_BT_INT],
+};
+
+/// NewOrderSingle (D) → ExecutionReport (8).
+pub const NEW_ORDER_SINGLE: ResponseSpec = ResponseSpec {
+    request: b"D",
+    response: b"8",
+    echo: &[tag::CL_ORD_ID, tag::SYMBOL, tag::SIDE],
+};
+
+/// OrderCancelRequest (F) → ExecutionReport (8).
+pub const ORDER_CANCEL_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"F",
+    response: b"8",
+    echo: &[
+        tag::CL_ORD_ID,
+        tag::ORIG_CL_ORD_ID,
+        tag::ORDER_ID,
+        tag::SYMBOL,
+        tag::SIDE,
+    ],
+};
+
+/// OrderCancelReplaceRequest (G) → ExecutionReport (8).
+pub const ORDER_CANCEL_REPLACE_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"G",
+    response: b"8",
+    echo: &[
+        tag::CL_ORD_ID,
+        tag::ORIG_CL_ORD_ID,
+        tag::ORDER_ID,
+        tag::SYMBOL,
+        tag::SIDE,
+    ],
+};
+
+/// OrderStatusRequest (H) → ExecutionReport (8).
+pub const ORDER_STATUS_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"H",
+    response: b"8",
+    echo: &[
+        tag::ORD_STATUS_REQ_ID,
+        tag::CL_ORD_ID,
+        tag::ORDER_ID,
+        tag::SYMBOL,
+        tag::SIDE,
+    ],
+};
+
+/// OrderMassStatusRequest (AF) → ExecutionReport (8).
+pub const ORDER_MASS_STATUS_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"AF",
+    response: b"8",
+    echo: &[tag::MASS_STATUS_REQ_ID],
+};
+
+/// QuoteRequest (R) → Quote (S).
+pub const QUOTE_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"R",
+    response: b"S",
+    echo: &[tag::QUOTE_REQ_ID, tag::SYMBOL],
+};
+
+/// QuoteStatusRequest (a) → QuoteStatusReport (AI).
+pub const QUOTE_STATUS_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"a",
+    response: b"AI",
+    echo: &[tag::QUOTE_STATUS_REQ_ID, tag::QUOTE_ID, tag::SYMBOL],
+};
+
+/// MarketDataRequest (V) → MarketDataSnapshotFullRefresh (W).
+pub const MARKET_DATA_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"V",
+    response: b"W",
+    echo: &[tag::MD_REQ_ID],
+};
+
+/// SecurityDefinitionRequest (c) → SecurityDefinition (d).
+pub const SECURITY_DEFINITION_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"c",
+    response: b"d",
+    echo: &[tag::SECURITY_REQ_ID, tag::SYMBOL],
+};
+
+/// SecurityStatusRequest (e) → SecurityStatus (f).
+pub const SECURITY_STATUS_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"e",
+    response: b"f",
+    echo: &[tag::SECURITY_STATUS_REQ_ID, tag::SYMBOL],
+};
+
+/// TradingSessionStatusRequest (g) → TradingSessionStatus (h).
+pub const TRADING_SESSION_STATUS_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"g",
+    response: b"h",
+    echo: &[tag::TRAD_SES_REQ_ID, tag::TRADING_SESSION_ID],
+};
+
+/// SecurityListRequest (x) → SecurityList (y).
+pub const SECURITY_LIST_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"x",
+    response: b"y",
+    echo: &[tag::SECURITY_REQ_ID],
+};
+
+/// RequestForPositions (AN) → PositionReport (AP).
+pub const REQUEST_FOR_POSITIONS: ResponseSpec = ResponseSpec {
+    request: b"AN",
+    response: b"AP",
+    echo: &[tag::POS_REQ_ID],
+};
+
+/// TradeCaptureReportRequest (AD) → TradeCaptureReportRequestAck (AQ).
+pub const TRADE_CAPTURE_REPORT_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"AD",
+    response: b"AQ",
+    echo: &[tag::TRADE_REQUEST_ID],
+};
+
+/// BidRequest (k) → BidResponse (l).
+pub const BID_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"k",
+    response: b"l",
+    echo: &[tag::BID_ID, tag::CLIENT_BID_ID],
+};
+
+/// RegistrationInstructions (o) → RegistrationInstructionsResponse (p).
+pub const REGISTRATION_INSTRUCTIONS: ResponseSpec = ResponseSpec {
+    request: b"o",
+    response: b"p",
+    echo: &[
+        tag::REGIST_ID,
+        tag::REGIST_TRANS_TYPE,
+        tag::REGIST_REF_ID,
+        tag::CL_ORD_ID,
+        tag::ACCOUNT,
+    ],
+};
+
+/// Confirmation (AK) → ConfirmationAck (AU).
+pub const CONFIRMATION: ResponseSpec = ResponseSpec {
+    request: b"AK",
+    response: b"AU",
+    echo: &[tag::CONFIRM_ID, tag::TRADE_DATE, tag::TRANSACT_TIME],
+};
+
+/// UserRequest (BE) → UserResponse (BF).
+pub const USER_REQUEST: ResponseSpec = ResponseSpec {
+    request: b"BE",
+    response: b"BF",
+    echo: &[tag::USER_REQUEST_ID, tag::USERNAME],
+};
+
+/// Every built-in response spec.
+pub const RESPONSES: &[&ResponseSpec] = &[
+    &TEST_REQUEST,
+    &LOGON,
+    &NEW_ORDER_SINGLE,
+    &ORDER_CANCEL_REQUEST,
+    &ORDER_CANCEL_REPLACE_REQUEST,
+    &ORDER_STATUS_REQUEST,
+    &ORDER_MASS_STATUS_REQUEST,
+    &QUOTE_REQUEST,
+    &QUOTE_STATUS_REQUEST,
+    &MARKET_DATA_REQUEST,
+    &SECURITY_DEFINITION_REQUEST,
+    &SECURITY_STATUS_REQUEST,
+    &TRADING_SESSION_STATUS_REQUEST,
+    &SECURITY_LIST_REQUEST,
+    &REQUEST_FOR_POSITIONS,
+    &TRADE_CAPTURE_REPORT_REQUEST,
+    &BID_REQUEST,
+    &REGISTRATION_INSTRUCTIONS,
+    &CONFIRMATION,
+    &USER_REQUEST,
+];
+
+/// `(reply tag, request tag)`: the request's routing IDs with the sender
+/// and target roles swapped, in the order they are written.
+const MIRRORED_ROUTING: [(Tag, Tag); 12] = [
+    (tag::SENDER_COMP_ID, tag::TARGET_COMP_ID),
+    (tag::TARGET_COMP_ID, tag::SENDER_COMP_ID),
+    (tag::ON_BEHALF_OF_COMP_ID, tag::DELIVER_TO_COMP_ID),
+    (tag::DELIVER_TO_COMP_ID, tag::ON_BEHALF_OF_COMP_ID),
+    (tag::SENDER_SUB_ID, tag::TARGET_SUB_ID),
+    (tag::SENDER_LOCATION_ID, tag::TARGET_LOCATION_ID),
+    (tag::TARGET_SUB_ID, tag::SENDER_SUB_ID),
+    (tag::TARGET_LOCATION_ID, tag::SENDER_LOCATION_ID),
+    (tag::ON_BEHALF_OF_SUB_ID, tag::DELIVER_TO_SUB_ID),
+    (tag::ON_BEHALF_OF_LOCATION_ID, tag::DELIVER_TO_LOCATION_ID),
+    (tag::DELIVER_TO_SUB_ID, tag::ON_BEHALF_OF_SUB_ID),
+    (tag::DELIVER_TO_LOCATION_ID, tag::ON_BEHALF_OF_LOCATION_ID),
+];
+
+/// The built-in spec answering requests of `msg_type`, if any.
+pub fn response_spec(msg_type: &[u8]) -> Option<&'static ResponseSpec> {
+    RESPONSES.iter().copied().find(|s| s.request == msg_type)
+}
+
+/// Append the skeleton of the standard response to `request`: the response
+/// MsgType, the routing IDs with sender and target swapped, and the
+/// correlating fields of its [`ResponseSpec`]. Returns the spec used.
+///
+/// BeginString, MsgSeqNum and SendingTime are left to the encoder.
+///
+/// # Errors
+/// Returns `FixError::InvalidValue` if MsgType (35) is absent or has no
+/// built-in spec; nothing is written.
+///
+/// # Example
+/// ```ignore
+/// let mut b = MessageBuilder::new();
+/// respond(&quote_request, &mut b)?; // 35=S, CompIDs swapped, 131 and 55 echoed
+/// b.push(tag::QUOTE_ID, b"Q1").push(tag::BID_PX, b"99.5");
+/// enc.encode(&b.as_message(), &mut out)?;
+/// ```
+pub fn respond(
+    request: &Message<'_>,
+    b: &mut MessageBuilder,
+) -> Result<&'static ResponseSpec, FixError> {
+    let spec = request
+        .find(tag::MSG_TYPE)
+        .and_then(|f| response_spec(f.value))
+        .ok_or(FixError::InvalidValue)?;
+    respond_with(request, spec, b);
+    Ok(spec)
+}
+
+/// Like [`respond`], with an explicit spec, e.g. for a reject
+/// (`MarketDataRequestReject`, `QuoteRequestReject`) or a custom MsgType.
+pub fn respond_with(request: &Message<'_>, spec: &ResponseSpec, b: &mut MessageBuilder) {
+    b.push(tag::MSG_TYPE, spec.response);
+    mirror_routing(request, b);
+    for &t in spec.echo {
+        if let Some(f) = request.find(t) {
+            b.push(t, f.value);
+        }
+    }
+}
+
+/// Append the routing IDs of `request` with the sender and target roles
+/// swapped: SenderCompID (49) becomes TargetCompID (56), OnBehalfOfCompID
+/// (115) becomes DeliverToCompID (128), and likewise for SubIDs and
+/// LocationIDs, and vice versa.
+pub fn mirror_routing(request: &Message<'_>, b: &mut MessageBuilder) {
+    for (reply, from) in MIRRORED_ROUTING {
+        if let Some(f) = request.find(from) {
+            b.push(reply, f.value);
+        }
+    }
+}
+
+/// BusinessRejectReason (380) values, as sent in a BusinessMessageReject
+/// (35=j).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BusinessRejectReason {
+    Other,
+    UnknownId,
+    UnknownSecurity,
+    UnsupportedMessageType,
+    ApplicationNotAvailable,
+    ConditionallyRequiredFieldMissing,
+    NotAuthorized,
+    DeliverToFirmNotAvailableAtThisTime,
+    InvalidPriceIncrement,
+}
+
+impl BusinessRejectReason {
+    /// The numeric value carried in tag 380.
+    pub fn code(self) -> u32 {
+        match self {
+            Self::Other => 0,
+            Self::UnknownId => 1,
+            Self::UnknownSecurity => 2,
+            Self::UnsupportedMessageType => 3,
+            Self::ApplicationNotAvailable => 4,
+            Self::ConditionallyRequiredFieldMissing => 5,
+            Self::NotAuthorized => 6,
+            Self::DeliverToFirmNotAvailableAtThisTime => 7,
+            Self::InvalidPriceIncrement => 18,
+        }
+    }
+
+    /// The reason for a tag 380 value, or `None` if `code` is not defined.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            0 => Self::Other,
+            1 => Self::UnknownId,
+            2 => Self::UnknownSecurity,
+            3 => Self::UnsupportedMessageType,
+            4 => Self::ApplicationNotAvailable,
+            5 => Self::ConditionallyRequiredFieldMissing,
+            6 => Self::NotAuthorized,
+            7 => Self::DeliverToFirmNotAvailableAtThisTime,
+            18 => Self::InvalidPriceIncrement,
+            _ => return None,
+        })
+    }
+}
+
+/// The business-level ID field of a message of type `msg_type`, quoted
+/// back as BusinessRejectRefID (379): ClOrdID for orders, QuoteReqID for
+/// quote requests, ExecID for execution reports, and so on.
+pub fn business_ref_id_tag(msg_type: &[u8]) -> Option<Tag> {
+    match msg_type {
+        b"8" => Some(tag::EXEC_ID),
+        b"6" => Some(tag::IOI_ID),
+        b"S" => Some(tag::QUOTE_ID),
+        b"J" => Some(tag::ALLOC_ID),
+        b"E" => Some(tag::LIST_ID),
+        _ => response_spec(msg_type)?.echo.first().copied(),
+    }
+}
+
+/// A BusinessMessageReject (35=j) answering an application message that
+/// cannot be processed.
+///
+/// # Example
+/// ```ignore
+/// let reject = BusinessMessageReject::from(&msg, BusinessRejectReason::UnknownSecurity, b"no such symbol");
+/// let mut b = MessageBuilder::new();
+/// reject.write_to(&mut b); // 35=j, CompIDs swapped, 45/372/379/380/58
+/// enc.encode(&b.as_message(), &mut out)?;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BusinessMessageReject<'a> {
+    request: &'a Message<'a>,
+    /// RefSeqNum (45): MsgSeqNum (34) of the rejected message.
+    pub ref_seq_num: Option<&'a [u8]>,
+    /// RefMsgType (372): MsgType (35) of the rejected message.
+    pub ref_msg_type: Option<&'a [u8]>,
+    /// BusinessRejectRefID (379), see [`business_ref_id_tag`].
+    pub ref_id: Option<&'a [u8]>,
+    pub reason: BusinessRejectReason,
+    /// Text (58); omitted when empty.
+    pub text: &'a [u8],
+}
+
+impl<'a> BusinessMessageReject<'a> {
+    /// Reject `request`, taking the reference fields from it.
+    pub fn from(request: &'a Message<'a>, reason: BusinessRejectReason, text: &'a [u8]) -> Self {
+        let ref_msg_type = request.find(tag::MSG_TYPE).map(|f| f.value);
+        Self {
+            request,
+            ref_seq_num: request.find(tag::MSG_SEQ_NUM).map(|f| f.value),
+            ref_msg_type,
+            ref_id: ref_msg_type
+                .and_then(business_ref_id_tag)
+                .and_then(|t| request.find(t))
+                .map(|f| f.value),
+            reason,
+            text,
+        }
+    }
+
+    /// Append the reject to `b`: MsgType `j`, the request's routing IDs
+    /// mirrored as with [`mirror_routing`], then the reference fields,
+    /// BusinessRejectReason and Text.
+    pub fn write_to(&self, b: &mut MessageBuilder) {
+        b.push(tag::MSG_TYPE, b"j");
+        mirror_routing(self.request, b);
+        if let Some(v) = self.ref_seq_num {
+            b.push(tag::REF_SEQ_NUM, v);
+        }
+        if let Some(v) = self.ref_msg_type {
+            b.push(tag::REF_MSG_TYPE, v);
+        }
+        if let Some(v) = self.ref_id {
+            b.push(tag::BUSINESS_REJECT_REF_ID, v);
+        }
+        b.push_u32(tag::BUSINESS_REJECT_REASON, self.reason.code());
+        if !self.text.is_empty() {
+            b.push(tag::TEXT, self.text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    fn fields(b: &MessageBuilder) -> Vec<(Tag, Vec<u8>)> {
+        b.as_message()
+            .fields()
+            .map(|f| (f.tag, f.value.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn quote_request_gets_quote_skeleton() {
+        let mut dec = Decoder::new();
+        let req = dec
+            .decode(b"8=FIX.4.4\x0135=R\x0149=CLIENT\x0156=DEALER\x0150=DESK\x01115=FUND\x01131=QR7\x0155=IBM\x0138=100\x01")
+            .unwrap();
+        let mut b = MessageBuilder::new();
+        assert_eq!(respond(&req, &mut b).unwrap(), &QUOTE_REQUEST);
+        assert_eq!(
+            fields(&b),
+            [
+                (tag::MSG_TYPE, b"S".to_vec()),
+                (tag::SENDER_COMP_ID, b"DEALER".to_vec()),
+                (tag::TARGET_COMP_ID, b"CLIENT".to_vec()),
+                (tag::DELIVER_TO_COMP_ID, b"FUND".to_vec()),
+                (tag::TARGET_SUB_ID, b"DESK".to_vec()),
+                (tag::QUOTE_REQ_ID, b"QR7".to_vec()),
+                (tag::SYMBOL, b"IBM".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_or_missing_msg_type_writes_nothing() {
+        let mut dec = Decoder::new();
+        let mut b = MessageBuilder::new();
+        let msg = dec.decode(b"35=8\x0149=A\x0156=B\x01").unwrap();
+        assert!(matches!(respond(&msg, &mut b), Err(FixError::InvalidValue)));
+        let msg = dec.decode(b"49=A\x0156=B\x01").unwrap();
+        assert!(respond(&msg, &mut b).is_err());
+        assert!(b.is_empty());
+
+        let msg = dec.decode(b"35=1\x0149=A\x0156=B\x01112=PING\x01").unwrap();
+        respond(&msg, &mut b).unwrap();
+        assert_eq!(
+            fields(&b),
+            [
+                (tag::MSG_TYPE, b"0".to_vec()),
+                (tag::SENDER_COMP_ID, b"B".to_vec()),
+                (tag::TARGET_COMP_ID, b"A".to_vec()),
+                (tag::TEST_REQ_ID, b"PING".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn business_reject_references_the_order() {
+        let mut dec = Decoder::new();
+        let order = dec
+            .decode(
+                b"8=FIX.4.4\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=12\x0111=ORD9\x0155=ZZZ\x01",
+            )
+            .unwrap();
+        let reject =
+            BusinessMessageReject::from(&order, BusinessRejectReason::UnknownSecurity, b"no ZZZ");
+        let mut b = MessageBuilder::new();
+        reject.write_to(&mut b);
+        assert_eq!(
+            fields(&b),
+            [
+                (tag::MSG_TYPE, b"j".to_vec()),
+                (tag::SENDER_COMP_ID, b"BROKER".to_vec()),
+                (tag::TARGET_COMP_ID, b"CLIENT".to_vec()),
+                (tag::REF_SEQ_NUM, b"12".to_vec()),
+                (tag::REF_MSG_TYPE, b"D".to_vec()),
+                (tag::BUSINESS_REJECT_REF_ID, b"ORD9".to_vec()),
+                (tag::BUSINESS_REJECT_REASON, b"2".to_vec()),
+                (tag::TEXT, b"no ZZZ".to_vec()),
+            ]
+        );
+
+        let report = dec.decode(b"35=8\x0134=3\x0117=EX1\x01").unwrap();
+        let reject = BusinessMessageReject::from(&report, BusinessRejectReason::Other, b"");
+        assert_eq!(reject.ref_id, Some(&b"EX1"[..]));
+        let mut b = MessageBuilder::new();
+        reject.write_to(&mut b);
+        assert!(b.as_message().find(tag::TEXT).is_none());
+        assert_eq!(
+            BusinessRejectReason::from_code(18),
+            Some(BusinessRejectReason::InvalidPriceIncrement)
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// Where a request for quote stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RfqState {
+    /// QuoteRequest (35=R) seen, nothing back yet.
+    Requested,
+    /// At least one Quote (35=S) received for the request.
+    Quoted,
+    /// QuoteRequestReject (35=AG).
+    Rejected,
+    /// QuoteResponse (35=AJ) hitting or lifting a quote; an execution is
+    /// expected.
+    Accepted,
+    /// QuoteResponse countering a quote.
+    Countered,
+    /// QuoteResponse closing the request without a trade (expired, cover,
+    /// done away, pass, end trade, timed out).
+    Closed,
+    /// ExecutionReport (35=8) for the accepted quote with OrdStatus (39) `1`.
+    PartiallyFilled,
+    /// ExecutionReport with OrdStatus `2`.
+    Filled,
+    /// ExecutionReport rejecting or cancelling the resulting order.
+    OrderRejected,
+}
+
+impl RfqState {
+    /// True once nothing further is expected for the request.
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::Rejected | Self::Closed | Self::Filled | Self::OrderRejected
+        )
+    }
+}
+
+/// One tracked request for quote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rfq {
+    quote_req_id: Vec<u8>,
+    state: RfqState,
+    quote_ids: Vec<Vec<u8>>,
+    quote_resp_id: Option<Vec<u8>>,
+    cl_ord_id: Option<Vec<u8>>,
+    reject_reason: Option<Vec<u8>>,
+}
+
+impl Rfq {
+    #[inline]
+    pub fn quote_req_id(&self) -> &[u8] {
+        &self.quote_req_id
+    }
+
+    #[inline]
+    pub fn state(&self) -> RfqState {
+        self.state
+    }
+
+    /// QuoteIDs (117) of every Quote received for the request, oldest first.
+    pub fn quote_ids(&self) -> impl Iterator<Item = &[u8]> {
+        self.quote_ids.iter().map(Vec::as_slice)
+    }
+
+    /// QuoteRespID (693) of the latest QuoteResponse.
+    pub fn quote_resp_id(&self) -> Option<&[u8]> {
+        self.quote_resp_id.as_deref()
+    }
+
+    /// ClOrdID (11) carried on the latest QuoteResponse.
+    pub fn cl_ord_id(&self) -> Option<&[u8]> {
+        self.cl_ord_id.as_deref()
+    }
+
+    /// QuoteRequestRejectReason (658) or OrdRejReason (103), whichever
+    /// rejected the request.
+    pub fn reject_reason(&self) -> Option<&[u8]> {
+        self.reject_reason.as_deref()
+    }
+}
+
+/// Correlates the messages of the RFQ workflow across a session: QuoteRequest
+/// → Quote / QuoteRequestReject → QuoteResponse → ExecutionReport.
+///
+/// Requests are keyed by QuoteReqID (131). Quotes join their request through
+/// QuoteReqID and are remembered by QuoteID (117); a QuoteResponse joins
+/// through QuoteID, and an ExecutionReport through QuoteRespID (693) or the
+/// ClOrdID (11) the response carried. The tracker is direction-agnostic, so
+/// requesters and responders feed it both sides of the session alike.
+///
+/// # Example
+/// ```ignore
+/// let mut rfqs = RfqTracker::new();
+/// while let Some(msg) = next_message()? {
+///     if rfqs.apply(&msg).is_some_and(RfqState::is_terminal) {
+///         report(rfqs.iter().filter(|r| r.state().is_terminal()));
+///         rfqs.retain_open();
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RfqTracker {
+    rfqs: Vec<Rfq>,
+}
+
+impl RfqTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one message. Returns the new state of the request it belongs to,
+    /// or `None` if the message is not part of a tracked RFQ (other message
+    /// types, unsolicited quotes, responses to unknown quotes).
+    ///
+    /// A repeated QuoteRequest for a QuoteReqID already tracked restarts it.
+    pub fn apply(&mut self, msg: &Message<'_>) -> Option<RfqState> {
+        let value = |t: Tag| msg.find(t).map(|f| f.value);
+        let msg_type = value(tag::MSG_TYPE)?;
+        let (idx, state) = match msg_type {
+            b"R" => {
+                let id = value(tag::QUOTE_REQ_ID)?;
+                let rfq = Rfq {
+                    quote_req_id: id.to_vec(),
+                    state: RfqState::Requested,
+                    quote_ids: Vec::new(),
+                    quote_resp_id: None,
+                    cl_ord_id: None,
+                    reject_reason: None,
+                };
+                match self.position(id) {
+                    Some(i) => self.rfqs[i] = rfq,
+                    None => self.rfqs.push(rfq),
+                }
+                return Some(RfqState::Requested);
+            }
+            b"S" => {
+                let i = self.position(value(tag::QUOTE_REQ_ID)?)?;
+                if let Some(quote_id) = value(tag::QUOTE_ID) {
+                    let ids = &mut self.rfqs[i].quote_ids;
+                    if !ids.iter().any(|q| q == quote_id) {
+                        ids.push(quote_id.to_vec());
+                    }
+                }
+                (i, RfqState::Quoted)
+            }
+            b"AG" => {
+                let i = self.position(value(tag::QUOTE_REQ_ID)?)?;
+                self.rfqs[i].reject_reason =
+                    value(tag::QUOTE_REQUEST_REJECT_REASON).map(<[u8]>::to_vec);
+                (i, RfqState::Rejected)
+            }
+            b"AJ" => {
+                let i = match value(tag::QUOTE_REQ_ID).and_then(|id| self.position(id)) {
+                    Some(i) => i,
+                    None => {
+                        let quote_id = value(tag::QUOTE_ID)?;
+                        self.rfqs
+                            .iter()
+                            .position(|r| r.quote_ids.iter().any(|q| q == quote_id))?
+                    }
+                };
+                let state = match value(tag::QUOTE_RESP_TYPE)? {
+                    b"1" => RfqState::Accepted,
+                    b"2" => RfqState::Countered,
+                    _ => RfqState::Closed,
+                };
+                let rfq = &mut self.rfqs[i];
+                rfq.quote_resp_id = value(tag::QUOTE_RESP_ID).map(<[u8]>::to_vec);
+                rfq.cl_ord_id = value(tag::CL_ORD_ID).map(<[u8]>::to_vec);
+                (i, state)
+            }
+            b"8" => {
+                let resp_id = value(tag::QUOTE_RESP_ID);
+                let cl_ord_id = value(tag::CL_ORD_ID);
+                let i = self.rfqs.iter().position(|r| {
+                    resp_id.is_some_and(|id| r.quote_resp_id.as_deref() == Some(id))
+                        || cl_ord_id.is_some_and(|id| r.cl_ord_id.as_deref() == Some(id))
+                })?;
+                let state = match value(tag::ORD_STATUS)? {
+                    b"1" => RfqState::PartiallyFilled,
+                    b"2" => RfqState::Filled,
+                    b"4" | b"8" | b"C" => {
+                        self.rfqs[i].reject_reason = value(tag::ORD_REJ_REASON).map(<[u8]>::to_vec);
+                        RfqState::OrderRejected
+                    }
+                    _ => self.rfqs[i].state,
+                };
+                (i, state)
+            }
+            _ => return None,
+        };
+        self.rfqs[idx].state = state;
+        Some(state)
+    }
+
+    /// The request with QuoteReqID `quote_req_id`.
+    pub fn get(&self, quote_req_id: &[u8]) -> Option<&Rfq> {
+        self.rfqs.iter().find(|r| r.quote_req_id == quote_req_id)
+    }
+
+    /// The request a Quote with QuoteID `quote_id` answered.
+    pub fn by_quote_id(&self, quote_id: &[u8]) -> Option<&Rfq> {
+        self.rfqs
+            .iter()
+            .find(|r| r.quote_ids.iter().any(|q| q == quote_id))
+    }
+
+    /// Stop tracking a request, returning its final record.
+    pub fn remove(&mut self, quote_req_id: &[u8]) -> Option<Rfq> {
+        let i = self.position(quote_req_id)?;
+        Some(self.rfqs.remove(i))
+    }
+
+    /// Drop every request in a terminal state.
+    pub fn retain_open(&mut self) {
+        self.rfqs.retain(|r| !r.state.is_terminal());
+    }
+
+    /// Tracked requests, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Rfq> {
+        self.rfqs.iter()
+    }
+
+    /// Number of tracked requests.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rfqs.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rfqs.is_empty()
+    }
+
+    fn position(&self, quote_req_id: &[u8]) -> Option<usize> {
+        self.rfqs
+            .iter()
+            .position(|r| r.quote_req_id == quote_req_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+
+    fn apply(t: &mut RfqTracker, fields: &[(Tag, &[u8])]) -> Option<RfqState> {
+        let mut b = MessageBuilder::new();
+        for (tag, v) in fields {
+            b.push(*tag, v);
+        }
+        t.apply(&b.as_message())
+    }
+
+    #[test]
+    fn request_quote_accept_fill() {
+        let mut t = RfqTracker::new();
+        assert_eq!(
+            apply(&mut t, &[(tag::MSG_TYPE, b"R"), (tag::QUOTE_REQ_ID, b"Q1")]),
+            Some(RfqState::Requested)
+        );
+        let quote = [
+            (tag::MSG_TYPE, &b"S"[..]),
+            (tag::QUOTE_REQ_ID, b"Q1"),
+            (tag::QUOTE_ID, b"A"),
+        ];
+        assert_eq!(apply(&mut t, &quote), Some(RfqState::Quoted));
+        assert_eq!(apply(&mut t, &quote), Some(RfqState::Quoted));
+        assert_eq!(t.by_quote_id(b"A").unwrap().quote_req_id(), b"Q1");
+        assert_eq!(t.get(b"Q1").unwrap().quote_ids().count(), 1);
+
+        let resp = [
+            (tag::MSG_TYPE, &b"AJ"[..]),
+            (tag::QUOTE_RESP_ID, b"R1"),
+            (tag::QUOTE_ID, b"A"),
+            (tag::QUOTE_RESP_TYPE, b"1"),
+            (tag::CL_ORD_ID, b"C1"),
+        ];
+        assert_eq!(apply(&mut t, &resp), Some(RfqState::Accepted));
+        assert_eq!(
+            apply(
+                &mut t,
+                &[
+                    (tag::MSG_TYPE, b"8"),
+                    (tag::CL_ORD_ID, b"C1"),
+                    (tag::ORD_STATUS, b"1")
+                ]
+            ),
+            Some(RfqState::PartiallyFilled)
+        );
+        assert_eq!(
+            apply(
+                &mut t,
+                &[
+                    (tag::MSG_TYPE, b"8"),
+                    (tag::QUOTE_RESP_ID, b"R1"),
+                    (tag::ORD_STATUS, b"2")
+                ]
+            ),
+            Some(RfqState::Filled)
+        );
+        assert!(t.get(b"Q1").unwrap().state().is_terminal());
+        t.retain_open();
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn reject_and_unrelated() {
+        let mut t = RfqTracker::new();
+        apply(&mut t, &[(tag::MSG_TYPE, b"R"), (tag::QUOTE_REQ_ID, b"Q1")]);
+        // Unsolicited quote and unknown response are ignored.
+        assert_eq!(
+            apply(&mut t, &[(tag::MSG_TYPE, b"S"), (tag::QUOTE_ID, b"X")]),
+            None
+        );
+        assert_eq!(
+            apply(
+                &mut t,
+                &[
+                    (tag::MSG_TYPE, b"AJ"),
+                    (tag::QUOTE_ID, b"X"),
+                    (tag::QUOTE_RESP_TYPE, b"1")
+                ]
+            ),
+            None
+        );
+        assert_eq!(apply(&mut t, &[(tag::MSG_TYPE, b"0")]), None);
+        assert_eq!(
+            apply(
+                &mut t,
+                &[
+                    (tag::MSG_TYPE, b"AG"),
+                    (tag::QUOTE_REQ_ID, b"Q1"),
+                    (tag::QUOTE_REQUEST_REJECT_REASON, b"1")
+                ]
+            ),
+            Some(RfqState::Rejected)
+        );
+        let rfq = t.remove(b"Q1").unwrap();
+        assert_eq!(rfq.reject_reason(), Some(&b"1"[..]));
+        assert!(t.is_empty());
+    }
+}
@@ -0,0 +1,378 @@
+//! Single-producer / single-consumer ring for passing decoded messages
+//! between processes through shared memory.
+//!
+//! Enabled with the `shm-ring` feature. This is the only module in the crate
+//! that uses `unsafe`: a ring shared with another process is, by definition,
+//! memory the compiler cannot see being written. The unsafe surface is kept to
+//! [`attach_writer`] / [`attach_reader`] (the caller vouches for the mapping) and the private `Region`
+//! accessors; everything built on top is safe.
+//!
+//! Messages travel as [`ipc`](crate::ipc) frames, so the consumer gets a
+//! zero-copy [`Message`] over the shared bytes without re-decoding FIX.
+//!
+//! # Layout
+//!
+//! ```text
+//! 0    head  AtomicU64   total bytes ever written (producer-owned)
+//! 64   tail  AtomicU64   total bytes ever consumed (consumer-owned)
+//! 128  data  capacity bytes of records
+//! ```
+//!
+//! Each record is a little-endian `u32` frame length followed by the frame,
+//! padded to 8 bytes. A length of `u32::MAX` marks the unused end of the data
+//! area when a record did not fit before the wrap point.
+
+use std::ptr::NonNull;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::FixError;
+use crate::ipc::{self, IpcReader};
+use crate::message::Message;
+
+/// Bytes reserved in front of the data area (head and tail on separate cache lines).
+pub const RING_HEADER_LEN: usize = 128;
+const TAIL_OFFSET: usize = 64;
+const LEN_PREFIX: usize = 4;
+const WRAP_MARKER: u32 = u32::MAX;
+
+#[inline]
+fn align8(n: usize) -> usize {
+    (n + 7) & !7
+}
+
+/// A mapped ring: raw base pointer plus, for in-process rings, the heap
+/// allocation keeping it alive.
+struct Region {
+    base: NonNull<u8>,
+    capacity: usize,
+    _heap: Option<Arc<[AtomicU64]>>,
+}
+
+// SAFETY: the region is only accessed through atomics (head/tail) and through
+// byte ranges that the SPSC protocol hands to exactly one side at a time.
+unsafe impl Send for Region {}
+
+impl Region {
+    #[inline]
+    fn head(&self) -> &AtomicU64 {
+        // SAFETY: base is 8-aligned and the header is inside the mapping.
+        unsafe { &*(self.base.as_ptr() as *const AtomicU64) }
+    }
+
+    #[inline]
+    fn tail(&self) -> &AtomicU64 {
+        // SAFETY: as for `head`; TAIL_OFFSET is a multiple of 8.
+        unsafe { &*(self.base.as_ptr().add(TAIL_OFFSET) as *const AtomicU64) }
+    }
+
+    #[inline]
+    fn data(&self) -> *mut u8 {
+        // SAFETY: RING_HEADER_LEN is within the mapping.
+        unsafe { self.base.as_ptr().add(RING_HEADER_LEN) }
+    }
+
+    /// Copy `bytes` into the data area at `pos`.
+    #[inline]
+    fn write(&self, pos: usize, bytes: &[u8]) {
+        assert!(pos + bytes.len() <= self.capacity);
+        // SAFETY: in bounds (checked above); the range is owned by the
+        // producer until `head` is published.
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.data().add(pos), bytes.len()) }
+    }
+
+    /// Borrow `len` bytes of the data area at `pos`.
+    #[inline]
+    fn read(&self, pos: usize, len: usize) -> &[u8] {
+        assert!(pos + len <= self.capacity);
+        // SAFETY: in bounds (checked above); the range was published by the
+        // producer and is not reused until the consumer advances `tail`.
+        unsafe { std::slice::from_raw_parts(self.data().add(pos), len) }
+    }
+}
+
+/// Create an in-process ring with `capacity` data bytes (rounded up to a
+/// multiple of 8), returning its two ends. Useful between threads and for
+/// testing code that will later run over shared memory.
+pub fn with_capacity(capacity: usize) -> (RingWriter, RingReader) {
+    let capacity = align8(capacity.max(8));
+    let words = (RING_HEADER_LEN + capacity) / 8;
+    let heap: Arc<[AtomicU64]> = (0..words).map(|_| AtomicU64::new(0)).collect();
+    let base = NonNull::new(heap.as_ptr() as *mut u8).expect("non-null allocation");
+    let region = |heap: &Arc<[AtomicU64]>| Region {
+        base,
+        capacity,
+        _heap: Some(heap.clone()),
+    };
+    (
+        RingWriter::new(region(&heap)),
+        RingReader::new(region(&heap)),
+    )
+}
+
+/// Map the ring header and data area of a caller-provided mapping.
+///
+/// # Safety
+///
+/// See [`attach_writer`].
+unsafe fn attach(base: *mut u8, len: usize) -> Option<Region> {
+    let base = NonNull::new(base)?;
+    if !(base.as_ptr() as usize).is_multiple_of(8) || len < RING_HEADER_LEN + 8 {
+        return None;
+    }
+    Some(Region {
+        base,
+        capacity: (len - RING_HEADER_LEN) & !7,
+        _heap: None,
+    })
+}
+
+/// Attach the producer end to a ring living in caller-mapped shared memory.
+///
+/// `len` is the size of the whole mapping; the data capacity is
+/// `len - RING_HEADER_LEN` rounded down to a multiple of 8. Pass
+/// `init = true` on a fresh mapping to reset head and tail; the consumer
+/// process attaches with [`attach_reader`]. Returns `None` if `base` is null
+/// or misaligned, or the mapping is too small.
+///
+/// # Safety
+///
+/// - `base` must be 8-byte aligned and valid for reads and writes of `len`
+///   bytes for as long as the returned end is alive;
+/// - at most one writer and one reader may be attached to the same mapping;
+/// - nothing else may write to the mapping.
+pub unsafe fn attach_writer(base: *mut u8, len: usize, init: bool) -> Option<RingWriter> {
+    // SAFETY: forwarded to the caller.
+    let region = unsafe { attach(base, len) }?;
+    if init {
+        region.head().store(0, Ordering::Release);
+        region.tail().store(0, Ordering::Release);
+    }
+    Some(RingWriter::new(region))
+}
+
+/// Attach the consumer end to a ring living in caller-mapped shared memory.
+///
+/// # Safety
+///
+/// Same requirements as [`attach_writer`].
+pub unsafe fn attach_reader(base: *mut u8, len: usize) -> Option<RingReader> {
+    // SAFETY: forwarded to the caller.
+    unsafe { attach(base, len) }.map(RingReader::new)
+}
+
+/// Producer end of a ring.
+pub struct RingWriter {
+    region: Region,
+    head: u64,
+    /// Reusable scratch buffer for serializing frames.
+    scratch: Vec<u8>,
+}
+
+impl RingWriter {
+    fn new(region: Region) -> Self {
+        let head = region.head().load(Ordering::Acquire);
+        Self {
+            region,
+            head,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Data capacity of the ring in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.region.capacity
+    }
+
+    /// Publish `msg` to the consumer.
+    ///
+    /// Returns `Ok(false)` without writing if the ring is currently too full,
+    /// and `EncodeError` if the message could never fit.
+    pub fn try_write(&mut self, msg: &Message<'_>) -> Result<bool, FixError> {
+        let cap = self.region.capacity;
+        let record = align8(LEN_PREFIX + ipc::frame_len(msg));
+        if record > cap {
+            return Err(FixError::EncodeError);
+        }
+
+        let tail = self.region.tail().load(Ordering::Acquire);
+        let free = cap - (self.head - tail) as usize;
+        let pos = (self.head % cap as u64) as usize;
+        let until_wrap = cap - pos;
+        let needed = if until_wrap < record {
+            until_wrap + record
+        } else {
+            record
+        };
+        if free < needed {
+            return Ok(false);
+        }
+
+        let mut pos = pos;
+        if until_wrap < record {
+            self.region.write(pos, &WRAP_MARKER.to_le_bytes());
+            self.head += until_wrap as u64;
+            pos = 0;
+        }
+
+        self.scratch.clear();
+        ipc::write_frame(msg, &mut self.scratch);
+        self.region
+            .write(pos, &(self.scratch.len() as u32).to_le_bytes());
+        self.region.write(pos + LEN_PREFIX, &self.scratch);
+        self.head += record as u64;
+        self.region.head().store(self.head, Ordering::Release);
+        Ok(true)
+    }
+}
+
+/// Consumer end of a ring.
+pub struct RingReader {
+    region: Region,
+    tail: u64,
+    ipc: IpcReader,
+}
+
+impl RingReader {
+    fn new(region: Region) -> Self {
+        let tail = region.tail().load(Ordering::Acquire);
+        Self {
+            region,
+            tail,
+            ipc: IpcReader::new(),
+        }
+    }
+
+    /// Data capacity of the ring in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.region.capacity
+    }
+
+    /// Hand the next message to `f`, then release its space to the
+    /// producer. Returns `Ok(None)` if the ring is empty.
+    ///
+    /// The message borrows the shared memory directly and must not outlive
+    /// the call. A corrupt frame is skipped and reported as an error.
+    pub fn try_read<R>(&mut self, f: impl FnOnce(Message<'_>) -> R) -> Result<Option<R>, FixError> {
+        let cap = self.region.capacity;
+        let head = self.region.head().load(Ordering::Acquire);
+        if self.tail == head {
+            return Ok(None);
+        }
+
+        let mut pos = (self.tail % cap as u64) as usize;
+        let len_bytes = self.region.read(pos, LEN_PREFIX);
+        let mut len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]);
+        if len == WRAP_MARKER {
+            self.tail += (cap - pos) as u64;
+            pos = 0;
+            let len_bytes = self.region.read(pos, LEN_PREFIX);
+            len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]);
+        }
+
+        let len = len as usize;
+        let record = align8(LEN_PREFIX + len);
+        let result = if record > cap - pos {
+            Err(FixError::DecodeError)
+        } else {
+            let frame = self.region.read(pos + LEN_PREFIX, len);
+            self.ipc.read(frame).map(f)
+        };
+        self.tail += record.min(cap - pos) as u64;
+        self.region.tail().store(self.tail, Ordering::Release);
+        result.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::tag;
+
+    fn seq_msg(n: u32) -> Vec<u8> {
+        format!("35=X\x0134={n}\x0155=SYM{n}\x01").into_bytes()
+    }
+
+    fn seq_of(msg: &Message<'_>) -> u32 {
+        std::str::from_utf8(msg.find(tag::MSG_SEQ_NUM).unwrap().value)
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn write_read_with_wrap_around() {
+        let (mut w, mut r) = with_capacity(256);
+        let mut dec = Decoder::new();
+        let mut next_read = 0;
+        for n in 0..50 {
+            let raw = seq_msg(n);
+            let msg = dec.decode(&raw).unwrap();
+            while !w.try_write(&msg).unwrap() {
+                let got = r.try_read(|m| seq_of(&m)).unwrap().unwrap();
+                assert_eq!(got, next_read);
+                next_read += 1;
+            }
+        }
+        while let Some(got) = r.try_read(|m| seq_of(&m)).unwrap() {
+            assert_eq!(got, next_read);
+            next_read += 1;
+        }
+        assert_eq!(next_read, 50);
+    }
+
+    #[test]
+    fn oversized_message_is_rejected() {
+        let (mut w, _r) = with_capacity(32);
+        let mut dec = Decoder::new();
+        let raw = seq_msg(1);
+        let msg = dec.decode(&raw).unwrap();
+        assert!(matches!(w.try_write(&msg), Err(FixError::EncodeError)));
+    }
+
+    #[test]
+    fn spsc_across_threads() {
+        let (mut w, mut r) = with_capacity(512);
+        let producer = std::thread::spawn(move || {
+            let mut dec = Decoder::new();
+            for n in 0..1000 {
+                let raw = seq_msg(n);
+                let msg = dec.decode(&raw).unwrap();
+                while !w.try_write(&msg).unwrap() {
+                    std::thread::yield_now();
+                }
+            }
+        });
+        let mut expected = 0;
+        while expected < 1000 {
+            match r.try_read(|m| seq_of(&m)).unwrap() {
+                Some(n) => {
+                    assert_eq!(n, expected);
+                    expected += 1;
+                }
+                None => std::thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+    }
+
+    #[test]
+    fn attach_to_raw_mapping() {
+        let mut mapping = vec![0u64; (RING_HEADER_LEN + 256) / 8];
+        let base = mapping.as_mut_ptr() as *mut u8;
+        let len = mapping.len() * 8;
+        // SAFETY: the Vec outlives both ends and has one writer/one reader.
+        let mut w = unsafe { attach_writer(base, len, true) }.unwrap();
+        let mut r = unsafe { attach_reader(base, len) }.unwrap();
+        assert_eq!(w.capacity(), 256);
+        let mut dec = Decoder::new();
+        let raw = seq_msg(7);
+        let msg = dec.decode(&raw).unwrap();
+        assert!(w.try_write(&msg).unwrap());
+        assert_eq!(r.try_read(|m| seq_of(&m)).unwrap(), Some(7));
+        assert_eq!(r.try_read(|m| seq_of(&m)).unwrap(), None);
+    }
+}
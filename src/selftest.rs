@@ -0,0 +1,162 @@
+//! Runtime throughput check for deployments.
+//!
+//! [`throughput`] decodes and re-encodes a sample of wire messages on the
+//! calling thread and reports rates and per-message latency percentiles, so a
+//! service can verify at startup that the codec performs as expected on its
+//! hardware, pinning and NUMA placement. It is not a substitute for the
+//! criterion benches: each operation is timed individually, which adds the
+//! cost of reading the clock (tens of nanoseconds) to every sample.
+
+use std::time::{Duration, Instant};
+
+use crate::checksum::{self, Backend};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::FixError;
+
+/// Latency distribution of one operation, per message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Latency {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub p999: Duration,
+    pub max: Duration,
+}
+
+impl Latency {
+    /// Percentiles of `samples` (nanoseconds), which are sorted in place.
+    fn from_samples(samples: &mut [u64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let at = |q: f64| {
+            let i = ((samples.len() as f64 * q).ceil() as usize).clamp(1, samples.len()) - 1;
+            Duration::from_nanos(samples[i])
+        };
+        Self {
+            p50: at(0.50),
+            p90: at(0.90),
+            p99: at(0.99),
+            p999: at(0.999),
+            max: Duration::from_nanos(samples[samples.len() - 1]),
+        }
+    }
+}
+
+/// Result of [`throughput`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Report {
+    /// Messages decoded (and encoded) in total.
+    pub messages: usize,
+    /// Bytes of input decoded in total.
+    pub bytes: usize,
+    /// Decoded messages per second.
+    pub decode_per_sec: f64,
+    /// Encoded messages per second.
+    pub encode_per_sec: f64,
+    pub decode_latency: Latency,
+    pub encode_latency: Latency,
+    /// Checksum implementation selected for this CPU.
+    pub checksum_backend: Backend,
+}
+
+impl Report {
+    /// Decode throughput in bytes of input per second.
+    pub fn decode_bytes_per_sec(&self) -> f64 {
+        if self.messages == 0 {
+            return 0.0;
+        }
+        self.decode_per_sec * self.bytes as f64 / self.messages as f64
+    }
+}
+
+/// Decode every message in `msgs`, then encode the result, `iterations`
+/// times over, and report throughput and latency.
+///
+/// `msgs` should be representative wire messages — a slice of a capture
+/// from the deployment's own counterparties is ideal. Each one is decoded
+/// once before timing starts to warm caches and the reusable buffers.
+///
+/// # Errors
+/// The first decode or encode error, before any timing is done.
+///
+/// # Example
+/// ```ignore
+/// let report = selftest::throughput(&sample, 10_000)?;
+/// if report.decode_latency.p99 > Duration::from_micros(2) {
+///     log::warn!("codec slower than expected: {report:?}");
+/// }
+/// ```
+pub fn throughput(msgs: &[&[u8]], iterations: usize) -> Result<Report, FixError> {
+    let mut dec = Decoder::new();
+    let mut enc = Encoder::new();
+    let mut out = Vec::new();
+    for raw in msgs {
+        let msg = dec.decode(raw)?;
+        enc.encode(&msg, &mut out)?;
+    }
+
+    let total = msgs.len() * iterations;
+    let mut decode_ns = Vec::with_capacity(total);
+    let mut encode_ns = Vec::with_capacity(total);
+    let mut decode_time = Duration::ZERO;
+    let mut encode_time = Duration::ZERO;
+    for _ in 0..iterations {
+        for raw in msgs {
+            let t0 = Instant::now();
+            let msg = dec.decode(raw)?;
+            let t1 = Instant::now();
+            enc.encode(&msg, &mut out)?;
+            let t2 = Instant::now();
+            decode_time += t1 - t0;
+            encode_time += t2 - t1;
+            decode_ns.push((t1 - t0).as_nanos() as u64);
+            encode_ns.push((t2 - t1).as_nanos() as u64);
+        }
+    }
+
+    let rate = |elapsed: Duration| {
+        let secs = elapsed.as_secs_f64();
+        if secs > 0.0 { total as f64 / secs } else { 0.0 }
+    };
+    Ok(Report {
+        messages: total,
+        bytes: msgs.iter().map(|m| m.len()).sum::<usize>() * iterations,
+        decode_per_sec: rate(decode_time),
+        encode_per_sec: rate(encode_time),
+        decode_latency: Latency::from_samples(&mut decode_ns),
+        encode_latency: Latency::from_samples(&mut encode_ns),
+        checksum_backend: checksum::backend(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_every_message() {
+        let raw: &[u8] = b"8=FIX.4.4\x019=5\x0135=0\x0110=163\x01";
+        let report = throughput(&[raw, raw], 50).unwrap();
+        assert_eq!(report.messages, 100);
+        assert_eq!(report.bytes, raw.len() * 100);
+        assert!(report.decode_per_sec > 0.0);
+        let l = report.decode_latency;
+        assert!(l.p50 <= l.p90 && l.p90 <= l.p99 && l.p99 <= l.p999 && l.p999 <= l.max);
+
+        assert!(throughput(&[b"garbage"], 1).is_err());
+        assert_eq!(throughput(&[], 10).unwrap().messages, 0);
+    }
+
+    #[test]
+    fn percentiles_pick_nearest_rank() {
+        let mut samples: Vec<u64> = (1..=1000).rev().collect();
+        let l = Latency::from_samples(&mut samples);
+        assert_eq!(l.p50, Duration::from_nanos(500));
+        assert_eq!(l.p99, Duration::from_nanos(990));
+        assert_eq!(l.p999, Duration::from_nanos(999));
+        assert_eq!(l.max, Duration::from_nanos(1000));
+    }
+}
@@ -0,0 +1,247 @@
+//! `serde` support: messages serialize as a map of tag to value in wire
+//! order, with each repeating group of a built-in spec as an array of
+//! instance maps under its `NO_*` count tag.
+//!
+//! ```text
+//! {"8":"FIX.4.4","35":"W","268":[{"269":"0","270":"1.5"},{"269":"1","270":"1.6"}]}
+//! ```
+//!
+//! A group nests inside an instance only if its count tag is a member of
+//! the enclosing spec. Values that are not UTF-8 serialize as bytes.
+//! Deserializing produces an [`OwnedMessage`]: arrays become a count field
+//! followed by the instances, and numbers are written in their display form.
+
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::builder::MessageBuilder;
+use crate::group::{Group, Item, instance_ranges, items};
+use crate::message::Message;
+use crate::owned::OwnedMessage;
+use crate::tag::Tag;
+
+/// A run of fields serialized as a map.
+struct Entries<'a> {
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+}
+
+/// The instances of one group, serialized as an array of maps.
+struct Instances<'a> {
+    buf: &'a [u8],
+    offsets: &'a [(Tag, u32, u32)],
+    item: Item,
+}
+
+struct Value<'a>(&'a [u8]);
+
+impl Serialize for Value<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match std::str::from_utf8(self.0) {
+            Ok(v) => s.serialize_str(v),
+            Err(_) => s.serialize_bytes(self.0),
+        }
+    }
+}
+
+impl Serialize for Entries<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(None)?;
+        for item in items(self.buf, self.offsets) {
+            let (tag, start, end) = self.offsets[item.start];
+            match item.spec {
+                Some(_) => map.serialize_entry(
+                    &tag,
+                    &Instances {
+                        buf: self.buf,
+                        offsets: self.offsets,
+                        item,
+                    },
+                )?,
+                None => {
+                    map.serialize_entry(&tag, &Value(&self.buf[start as usize..end as usize]))?
+                }
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Instances<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(None)?;
+        for r in instance_ranges(self.buf, self.offsets, self.item) {
+            seq.serialize_element(&Entries {
+                buf: self.buf,
+                offsets: &self.offsets[r],
+            })?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for Message<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        Entries {
+            buf: self.buf,
+            offsets: self.offsets,
+        }
+        .serialize(s)
+    }
+}
+
+impl Serialize for Group<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        Entries {
+            buf: self.buf,
+            offsets: self.offsets,
+        }
+        .serialize(s)
+    }
+}
+
+impl Serialize for OwnedMessage {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.as_message().serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedMessage {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let mut b = MessageBuilder::new();
+        d.deserialize_map(EntriesVisitor(&mut b))?;
+        Ok(b.as_message().to_owned())
+    }
+}
+
+/// Appends every entry of a map to the builder.
+struct EntriesVisitor<'b>(&'b mut MessageBuilder);
+
+impl<'de> Visitor<'de> for EntriesVisitor<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map of FIX tag to value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(tag) = map.next_key::<Tag>()? {
+            map.next_value_seed(FieldSeed { tag, b: self.0 })?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for EntriesVisitor<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<(), D::Error> {
+        d.deserialize_map(self)
+    }
+}
+
+/// Appends one field, or a count field and its instances for an array.
+struct FieldSeed<'b> {
+    tag: Tag,
+    b: &'b mut MessageBuilder,
+}
+
+impl<'de> DeserializeSeed<'de> for FieldSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<(), D::Error> {
+        d.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a field value or an array of group instances")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<(), E> {
+        self.b.push(self.tag, v);
+        Ok(())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<(), E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<(), E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<(), E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let count_index = self.b.len();
+        let mut count = 0u32;
+        while seq.next_element_seed(EntriesVisitor(self.b))?.is_some() {
+            count += 1;
+        }
+        self.b
+            .insert_at(count_index, self.tag, count.to_string().as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::Decoder;
+    use crate::group;
+    use crate::owned::OwnedMessage;
+    use crate::tag;
+
+    const SNAPSHOT: &[u8] = b"8=FIX.4.4\x0135=W\x0155=IBM\x01268=2\x01269=0\x01270=1.5\x01269=1\x01270=1.6\x0110=000\x01";
+
+    #[test]
+    fn message_serializes_groups_as_arrays() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(SNAPSHOT).unwrap();
+        assert_eq!(
+            serde_json::to_string(&msg).unwrap(),
+            r#"{"8":"FIX.4.4","35":"W","55":"IBM","268":[{"269":"0","270":"1.5"},{"269":"1","270":"1.6"}],"10":"000"}"#
+        );
+        let first = msg.groups(&group::MD_ENTRIES).next().unwrap();
+        assert!(
+            serde_json::to_string(&first)
+                .unwrap()
+                .starts_with(r#"{"269":"0""#)
+        );
+    }
+
+    #[test]
+    fn json_round_trips_into_owned_message() {
+        let mut dec = Decoder::new();
+        let msg = dec.decode(SNAPSHOT).unwrap();
+        let json = serde_json::to_string(&msg).unwrap();
+        let owned: OwnedMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned, msg.to_owned());
+
+        let owned: OwnedMessage =
+            serde_json::from_str(r#"{"35":"D","38":100,"453":[{"448":"A"}]}"#).unwrap();
+        let tags: Vec<(u32, &[u8])> = owned.fields().map(|f| (f.tag, f.value)).collect();
+        assert_eq!(
+            tags,
+            [
+                (tag::MSG_TYPE, &b"D"[..]),
+                (tag::ORDER_QTY, b"100"),
+                (tag::NO_PARTY_IDS, b"1"),
+                (tag::PARTY_ID, b"A"),
+            ]
+        );
+        assert!(serde_json::from_str::<OwnedMessage>(r#"{"x":"1"}"#).is_err());
+    }
+}
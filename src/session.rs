@@ -0,0 +1,2051 @@
+use std::borrow::Cow;
+use std::io;
+use std::time::Duration;
+
+use crate::builder::MessageBuilder;
+use crate::decoder::Decoder;
+use crate::dictionary::{is_admin_msg_type, is_header_tag};
+use crate::error::FixError;
+use crate::group;
+use crate::message::Message;
+use crate::pipeline::{EncodeStage, HeaderFields, copy_fields};
+use crate::store::MessageStore;
+use crate::tag::{self, Tag};
+use crate::timestamp::{TimestampPrecision, UtcTimestamp};
+
+/// Canonical form of CompID, SubID and LocationID values used when matching
+/// a message against a [`SessionId`].
+///
+/// Counterparties are not always consistent about case or padding of their
+/// routing IDs; a normalizer decides which spellings are the same ID.
+/// `tag` is the header tag the value came from, so an implementation can
+/// treat e.g. SubIDs differently from CompIDs.
+pub trait IdNormalizer {
+    fn normalize<'v>(&self, tag: Tag, value: &'v [u8]) -> Cow<'v, [u8]>;
+}
+
+/// Byte-for-byte comparison.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExactIds;
+
+impl IdNormalizer for ExactIds {
+    #[inline]
+    fn normalize<'v>(&self, _tag: Tag, value: &'v [u8]) -> Cow<'v, [u8]> {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Ignores ASCII case and surrounding ASCII whitespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CaseInsensitiveIds;
+
+impl IdNormalizer for CaseInsensitiveIds {
+    fn normalize<'v>(&self, _tag: Tag, value: &'v [u8]) -> Cow<'v, [u8]> {
+        let value = value.trim_ascii();
+        if value.iter().any(u8::is_ascii_lowercase) {
+            Cow::Owned(value.to_ascii_uppercase())
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+}
+
+/// The CompID, SubID and LocationID header tags of one routing role.
+#[derive(Debug, Clone, Copy)]
+struct RoleTags {
+    comp: Tag,
+    sub: Tag,
+    location: Tag,
+}
+
+const SENDER: RoleTags = RoleTags {
+    comp: tag::SENDER_COMP_ID,
+    sub: tag::SENDER_SUB_ID,
+    location: tag::SENDER_LOCATION_ID,
+};
+const TARGET: RoleTags = RoleTags {
+    comp: tag::TARGET_COMP_ID,
+    sub: tag::TARGET_SUB_ID,
+    location: tag::TARGET_LOCATION_ID,
+};
+const ON_BEHALF_OF: RoleTags = RoleTags {
+    comp: tag::ON_BEHALF_OF_COMP_ID,
+    sub: tag::ON_BEHALF_OF_SUB_ID,
+    location: tag::ON_BEHALF_OF_LOCATION_ID,
+};
+const DELIVER_TO: RoleTags = RoleTags {
+    comp: tag::DELIVER_TO_COMP_ID,
+    sub: tag::DELIVER_TO_SUB_ID,
+    location: tag::DELIVER_TO_LOCATION_ID,
+};
+
+/// A routing party: CompID with its optional SubID and LocationID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CompId {
+    pub comp_id: Vec<u8>,
+    pub sub_id: Option<Vec<u8>>,
+    pub location_id: Option<Vec<u8>>,
+}
+
+impl CompId {
+    pub fn new(comp_id: &[u8]) -> Self {
+        Self {
+            comp_id: comp_id.to_vec(),
+            ..Self::default()
+        }
+    }
+
+    pub fn sub_id(mut self, sub_id: &[u8]) -> Self {
+        self.sub_id = Some(sub_id.to_vec());
+        self
+    }
+
+    pub fn location_id(mut self, location_id: &[u8]) -> Self {
+        self.location_id = Some(location_id.to_vec());
+        self
+    }
+
+    /// Read the party in role `tags` from `msg`, if its CompID is present.
+    fn read(msg: &Message<'_>, tags: RoleTags) -> Option<Self> {
+        let value = |t| msg.find(t).map(|f| f.value.to_vec());
+        Some(Self {
+            comp_id: value(tags.comp)?,
+            sub_id: value(tags.sub),
+            location_id: value(tags.location),
+        })
+    }
+
+    fn normalized(&self, tags: RoleTags, norm: &impl IdNormalizer) -> Self {
+        let n = |t, v: &[u8]| norm.normalize(t, v).into_owned();
+        Self {
+            comp_id: n(tags.comp, &self.comp_id),
+            sub_id: self.sub_id.as_deref().map(|v| n(tags.sub, v)),
+            location_id: self.location_id.as_deref().map(|v| n(tags.location, v)),
+        }
+    }
+
+    fn push(&self, tags: RoleTags, out: &mut MessageBuilder) {
+        out.push(tags.comp, &self.comp_id);
+        if let Some(v) = &self.sub_id {
+            out.push(tags.sub, v);
+        }
+        if let Some(v) = &self.location_id {
+            out.push(tags.location, v);
+        }
+    }
+
+    fn write(&self, tags: RoleTags, mut header: HeaderFields) -> HeaderFields {
+        header = header.set(tags.comp, &self.comp_id);
+        if let Some(v) = &self.sub_id {
+            header = header.set(tags.sub, v);
+        }
+        if let Some(v) = &self.location_id {
+            header = header.set(tags.location, v);
+        }
+        header
+    }
+}
+
+/// Whether the party in role `tags` of `msg` is `expected`. `None` matches
+/// only a message that carries none of the role's tags.
+fn role_matches(
+    expected: Option<&CompId>,
+    msg: &Message<'_>,
+    tags: RoleTags,
+    norm: &impl IdNormalizer,
+) -> bool {
+    let same = |t, want: Option<&[u8]>| match (want, msg.find(t)) {
+        (None, None) => true,
+        (Some(w), Some(f)) => norm.normalize(t, w) == norm.normalize(t, f.value),
+        _ => false,
+    };
+    match expected {
+        Some(id) => {
+            same(tags.comp, Some(&id.comp_id))
+                && same(tags.sub, id.sub_id.as_deref())
+                && same(tags.location, id.location_id.as_deref())
+        }
+        None => same(tags.comp, None) && same(tags.sub, None) && same(tags.location, None),
+    }
+}
+
+/// Identity of one FIX session from our side, including the third-party
+/// routing parties of brokers that carry several clients over one
+/// connection.
+///
+/// Outbound messages carry `sender` as SenderCompID (49/50/142), `target`
+/// as TargetCompID (56/57/143), `on_behalf_of` as OnBehalfOfCompID
+/// (115/116/144) and `deliver_to` as DeliverToCompID (128/129/145).
+/// Inbound messages carry the same parties mirrored: the counterparty's
+/// OnBehalfOf is our DeliverTo and vice versa.
+///
+/// # Example
+/// ```ignore
+/// let id = SessionId::new(b"FIX.4.4", CompId::new(b"BROKER"), CompId::new(b"VENUE"))
+///     .on_behalf_of(CompId::new(b"CLIENT1").sub_id(b"DESK7"));
+/// encoder.push_stage(id.header_fields());
+///
+/// let routes: HashMap<SessionId, Route> = ...;
+/// let key = SessionId::from_inbound(&msg).map(|id| id.normalized(&CaseInsensitiveIds));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SessionId {
+    pub begin_string: Vec<u8>,
+    pub sender: CompId,
+    pub target: CompId,
+    pub on_behalf_of: Option<CompId>,
+    pub deliver_to: Option<CompId>,
+}
+
+impl SessionId {
+    pub fn new(begin_string: &[u8], sender: CompId, target: CompId) -> Self {
+        Self {
+            begin_string: begin_string.to_vec(),
+            sender,
+            target,
+            on_behalf_of: None,
+            deliver_to: None,
+        }
+    }
+
+    pub fn on_behalf_of(mut self, party: CompId) -> Self {
+        self.on_behalf_of = Some(party);
+        self
+    }
+
+    pub fn deliver_to(mut self, party: CompId) -> Self {
+        self.deliver_to = Some(party);
+        self
+    }
+
+    /// The session an inbound `msg` belongs to, seen from our side. Returns
+    /// `None` if BeginString (8), SenderCompID (49) or TargetCompID (56) is
+    /// missing.
+    pub fn from_inbound(msg: &Message<'_>) -> Option<Self> {
+        Some(Self {
+            begin_string: msg.fix_version()?.to_vec(),
+            sender: CompId::read(msg, TARGET)?,
+            target: CompId::read(msg, SENDER)?,
+            on_behalf_of: CompId::read(msg, DELIVER_TO),
+            deliver_to: CompId::read(msg, ON_BEHALF_OF),
+        })
+    }
+
+    /// This ID with every routing value passed through `norm`, for use as a
+    /// map key that agrees with [`matches_inbound`](Self::matches_inbound).
+    pub fn normalized(&self, norm: &impl IdNormalizer) -> Self {
+        Self {
+            begin_string: self.begin_string.clone(),
+            sender: self.sender.normalized(SENDER, norm),
+            target: self.target.normalized(TARGET, norm),
+            on_behalf_of: self
+                .on_behalf_of
+                .as_ref()
+                .map(|p| p.normalized(ON_BEHALF_OF, norm)),
+            deliver_to: self
+                .deliver_to
+                .as_ref()
+                .map(|p| p.normalized(DELIVER_TO, norm)),
+        }
+    }
+
+    /// Whether inbound `msg` belongs to this session, comparing routing IDs
+    /// through `norm`. BeginString must match exactly. A SubID, LocationID
+    /// or routing party this ID leaves unset must be absent from `msg`.
+    pub fn matches_inbound(&self, msg: &Message<'_>, norm: &impl IdNormalizer) -> bool {
+        msg.fix_version() == Some(&self.begin_string[..])
+            && role_matches(Some(&self.sender), msg, TARGET, norm)
+            && role_matches(Some(&self.target), msg, SENDER, norm)
+            && role_matches(self.on_behalf_of.as_ref(), msg, DELIVER_TO, norm)
+            && role_matches(self.deliver_to.as_ref(), msg, ON_BEHALF_OF, norm)
+    }
+
+    /// An encode stage that populates BeginString and every routing header
+    /// field of this session on outbound messages.
+    pub fn header_fields(&self) -> HeaderFields {
+        let mut header = HeaderFields::new().set(tag::BEGIN_STRING, &self.begin_string);
+        header = self.sender.write(SENDER, header);
+        header = self.target.write(TARGET, header);
+        if let Some(p) = &self.on_behalf_of {
+            header = p.write(ON_BEHALF_OF, header);
+        }
+        if let Some(p) = &self.deliver_to {
+            header = p.write(DELIVER_TO, header);
+        }
+        header
+    }
+
+    /// Like [`header_fields`](Self::header_fields), but a message that
+    /// already names a different BeginString or routing ID than this
+    /// session is handled per `on_conflict` instead of silently rewritten.
+    /// Push it as the encoder's last stage so nothing changes the header
+    /// after the check.
+    pub fn header_guard(&self, on_conflict: HeaderConflict) -> HeaderGuard {
+        HeaderGuard {
+            header: self.header_fields(),
+            on_conflict,
+        }
+    }
+}
+
+/// What a [`HeaderGuard`] does with an outbound header field that
+/// disagrees with its session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeaderConflict {
+    /// Fail the encode with `FixError::HeaderMismatch(tag)`.
+    #[default]
+    Reject,
+    /// Write the session's value instead.
+    Override,
+}
+
+/// Encode stage binding outbound messages to one session: BeginString and
+/// the routing IDs missing from a message are filled in, and ones that
+/// conflict — a message built for another session — are rejected or
+/// overridden. Built by [`SessionId::header_guard`].
+///
+/// # Example
+/// ```ignore
+/// enc.push_stage(id.header_guard(HeaderConflict::Reject));
+/// // a message still carrying 56=OTHER_VENUE now fails instead of being
+/// // sent to VENUE:
+/// assert!(matches!(enc.encode(&msg, &mut out), Err(FixError::HeaderMismatch(56))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderGuard {
+    header: HeaderFields,
+    on_conflict: HeaderConflict,
+}
+
+impl EncodeStage for HeaderGuard {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        if self.on_conflict == HeaderConflict::Reject {
+            for (tag, value) in self.header.fields() {
+                if msg.find(tag).is_some_and(|f| f.value != value) {
+                    return Err(FixError::HeaderMismatch(tag));
+                }
+            }
+        }
+        self.header.apply(msg, out)
+    }
+}
+
+/// Intermediary stamping of forwarded messages, per the FIX 4.4
+/// third-party addressing rules.
+///
+/// Push on the encoder of each session a router forwards to, followed by
+/// that session's [`SessionId::header_fields`], and encode the message as
+/// received:
+///
+/// - From the originator (no OnBehalfOfCompID 115): its SenderCompID,
+///   SenderSubID and SenderLocationID move to OnBehalfOfCompID (115/116/144)
+///   and its SendingTime is kept as OnBehalfOfSendingTime (370).
+/// - From another intermediary (115 present): a HOPS instance is appended
+///   with that intermediary's SenderCompID as HopCompID (628), its
+///   SendingTime as HopSendingTime (629) and its MsgSeqNum as HopRefID (630).
+///
+/// Hops already on the message are kept, and NoHops (627) is rewritten to
+/// match. Stamping can be switched off per session with
+/// [`enabled`](Self::enabled), in which case messages pass through.
+///
+/// # Example
+/// ```ignore
+/// enc.push_stage(StampHops::new().enabled(cfg.stamp_hops))
+///     .push_stage(session_id.header_fields());
+/// enc.encode(&inbound, &mut out)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StampHops {
+    enabled: bool,
+}
+
+impl StampHops {
+    pub fn new() -> Self {
+        Self { enabled: true }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Write the routing fields and the HOPS group at the end of the header.
+    fn push_routing(
+        msg: &Message<'_>,
+        relayed: bool,
+        out: &mut MessageBuilder,
+    ) -> Result<(), FixError> {
+        let value = |t| msg.find(t).map(|f| f.value);
+        if !relayed {
+            for (from, to) in [
+                (tag::SENDER_COMP_ID, tag::ON_BEHALF_OF_COMP_ID),
+                (tag::SENDER_SUB_ID, tag::ON_BEHALF_OF_SUB_ID),
+                (tag::SENDER_LOCATION_ID, tag::ON_BEHALF_OF_LOCATION_ID),
+                (tag::SENDING_TIME, tag::ON_BEHALF_OF_SENDING_TIME),
+            ] {
+                if let Some(v) = value(from) {
+                    out.push(to, v);
+                }
+            }
+        }
+
+        let mut hops = out.begin_group(&group::HOPS);
+        for hop in msg.groups(&group::HOPS) {
+            hops.instance();
+            // The last instance runs to the end of the message.
+            for f in hop
+                .fields()
+                .take_while(|f| group::HOPS.member_tags.contains(&f.tag))
+            {
+                hops.push(f.tag, f.value);
+            }
+        }
+        if relayed && let Some(sender) = value(tag::SENDER_COMP_ID) {
+            hops.instance().push(tag::HOP_COMP_ID, sender);
+            if let Some(v) = value(tag::SENDING_TIME) {
+                hops.push(tag::HOP_SENDING_TIME, v);
+            }
+            if let Some(v) = value(tag::MSG_SEQ_NUM) {
+                hops.push(tag::HOP_REF_ID, v);
+            }
+        }
+        hops.end_group()
+    }
+}
+
+impl Default for StampHops {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EncodeStage for StampHops {
+    fn apply(&mut self, msg: &Message<'_>, out: &mut MessageBuilder) -> Result<(), FixError> {
+        if !self.enabled || msg.find(tag::SENDER_COMP_ID).is_none() {
+            copy_fields(msg, out);
+            return Ok(());
+        }
+        let relayed = msg.find(tag::ON_BEHALF_OF_COMP_ID).is_some();
+        let mut in_header = true;
+        for f in msg.fields() {
+            if in_header && !is_header_tag(f.tag) {
+                Self::push_routing(msg, relayed, out)?;
+                in_header = false;
+            }
+            if in_header {
+                let moved = matches!(
+                    f.tag,
+                    tag::NO_HOPS | tag::HOP_COMP_ID | tag::HOP_SENDING_TIME | tag::HOP_REF_ID
+                ) || (!relayed
+                    && matches!(f.tag, tag::SENDER_SUB_ID | tag::SENDER_LOCATION_ID));
+                if moved {
+                    continue;
+                }
+            }
+            out.push(f.tag, f.value);
+        }
+        if in_header {
+            Self::push_routing(msg, relayed, out)?;
+        }
+        Ok(())
+    }
+}
+
+/// What an inbound message's MsgSeqNum (34) means for the session, as
+/// judged by [`SeqNums::check_inbound`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeqCheck {
+    /// The expected number; process the message.
+    InOrder,
+    /// Higher than expected: messages `from..=to` are missing. Send a
+    /// ResendRequest (35=2) for them; the expected number is unchanged.
+    Gap { from: u64, to: u64 },
+    /// Lower than expected with PossDupFlag (43) `Y`: a resent duplicate,
+    /// safe to ignore.
+    PossDup,
+    /// Lower than expected without PossDupFlag: a serious error — send a
+    /// Logout naming `expected` and disconnect.
+    TooLow { expected: u64, received: u64 },
+    /// A SequenceReset (35=4) moved the next expected number to `next`.
+    Reset { next: u64 },
+    /// A SequenceReset-Reset tried to move the expected number backwards;
+    /// reject it (SessionRejectReason 5, value out of range).
+    ResetBackwards { expected: u64, new_seq_no: u64 },
+}
+
+/// Inbound and outbound MsgSeqNum bookkeeping for one session.
+///
+/// Both counters start at 1. [`next_outbound`](Self::next_outbound) hands
+/// out numbers for messages being sent; [`check_inbound`](Self::check_inbound)
+/// classifies every received message and advances the inbound counter when
+/// the message is in order. SequenceReset is handled in both its GapFill
+/// (123=Y) and Reset forms.
+///
+/// # Example
+/// ```ignore
+/// let mut seq = SeqNums::new();
+/// match seq.check_inbound(&msg)? {
+///     SeqCheck::InOrder | SeqCheck::Reset { .. } => handle(&msg),
+///     SeqCheck::Gap { from, to } => request_resend(from, to),
+///     SeqCheck::PossDup => {}
+///     SeqCheck::TooLow { expected, .. } => logout(expected),
+///     SeqCheck::ResetBackwards { .. } => reject(&msg),
+/// }
+/// out.push_u32(tag::MSG_SEQ_NUM, seq.next_outbound() as u32);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeqNums {
+    next_inbound: u64,
+    next_outbound: u64,
+}
+
+impl Default for SeqNums {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeqNums {
+    pub fn new() -> Self {
+        Self {
+            next_inbound: 1,
+            next_outbound: 1,
+        }
+    }
+
+    /// Resume a session from persisted counters.
+    pub fn with_next(next_inbound: u64, next_outbound: u64) -> Self {
+        Self {
+            next_inbound,
+            next_outbound,
+        }
+    }
+
+    /// The MsgSeqNum expected on the next inbound message.
+    #[inline]
+    pub fn expected_inbound(&self) -> u64 {
+        self.next_inbound
+    }
+
+    /// The MsgSeqNum the next outbound message will get, without taking it.
+    #[inline]
+    pub fn peek_outbound(&self) -> u64 {
+        self.next_outbound
+    }
+
+    /// Take the MsgSeqNum for a message about to be sent.
+    #[inline]
+    pub fn next_outbound(&mut self) -> u64 {
+        let n = self.next_outbound;
+        self.next_outbound += 1;
+        n
+    }
+
+    pub fn set_expected_inbound(&mut self, next: u64) {
+        self.next_inbound = next;
+    }
+
+    pub fn set_next_outbound(&mut self, next: u64) {
+        self.next_outbound = next;
+    }
+
+    /// Start both directions again from 1, as for a Logon with
+    /// ResetSeqNumFlag (141) `Y`.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Check a received message's MsgSeqNum against the expected number.
+    ///
+    /// # Errors
+    /// `InvalidValue` if MsgSeqNum (34) is missing or not a number, or a
+    /// SequenceReset has no valid NewSeqNo (36).
+    pub fn check_inbound(&mut self, msg: &Message<'_>) -> Result<SeqCheck, FixError> {
+        let value = |t| msg.find(t).map(|f| f.value);
+        let number = |t| {
+            msg.find(t)
+                .ok_or(FixError::InvalidValue)
+                .and_then(|f| f.as_u64())
+        };
+        let expected = self.next_inbound;
+
+        if value(tag::MSG_TYPE) == Some(b"4") && value(tag::GAP_FILL_FLAG) != Some(b"Y") {
+            // SequenceReset-Reset: MsgSeqNum is ignored.
+            let new_seq_no = number(tag::NEW_SEQ_NO)?;
+            if new_seq_no < expected {
+                return Ok(SeqCheck::ResetBackwards {
+                    expected,
+                    new_seq_no,
+                });
+            }
+            self.next_inbound = new_seq_no;
+            return Ok(SeqCheck::Reset { next: new_seq_no });
+        }
+
+        let received = number(tag::MSG_SEQ_NUM)?;
+        if received > expected {
+            return Ok(SeqCheck::Gap {
+                from: expected,
+                to: received - 1,
+            });
+        }
+        if received < expected {
+            return Ok(if value(tag::POSS_DUP_FLAG) == Some(b"Y") {
+                SeqCheck::PossDup
+            } else {
+                SeqCheck::TooLow { expected, received }
+            });
+        }
+
+        if value(tag::MSG_TYPE) == Some(b"4") {
+            // SequenceReset-GapFill in sequence: jump past the filled range.
+            let new_seq_no = number(tag::NEW_SEQ_NO)?;
+            if new_seq_no <= expected {
+                return Ok(SeqCheck::ResetBackwards {
+                    expected,
+                    new_seq_no,
+                });
+            }
+            self.next_inbound = new_seq_no;
+            return Ok(SeqCheck::Reset { next: new_seq_no });
+        }
+        self.next_inbound += 1;
+        Ok(SeqCheck::InOrder)
+    }
+}
+
+/// Whether a resent message (PossDupFlag `Y`) has an OrigSendingTime (122)
+/// no later than its SendingTime (52). Messages that are not resent, or
+/// that lack either timestamp, pass.
+fn orig_sending_time_ok(msg: &Message<'_>) -> bool {
+    if msg.find(tag::POSS_DUP_FLAG).map(|f| f.value) != Some(b"Y") {
+        return true;
+    }
+    let time = |t| msg.find(t).and_then(|f| UtcTimestamp::parse(f.value).ok());
+    match (time(tag::ORIG_SENDING_TIME), time(tag::SENDING_TIME)) {
+        (Some((orig, _)), Some((sent, _))) => orig <= sent,
+        _ => true,
+    }
+}
+
+/// Where a [`Session`] is in the session lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// No connection; call [`Session::on_connect`] once one is up.
+    Disconnected,
+    /// Acceptor connected and waiting for the counterparty's Logon.
+    AwaitingLogon,
+    /// Initiator sent Logon and is waiting for the reply.
+    LogonSent,
+    /// Logged on; application messages flow.
+    Active,
+    /// Logout sent; waiting for the counterparty's Logout.
+    LogoutSent,
+}
+
+/// Why a [`Session`] wants the connection closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The Logout handshake completed, from either side.
+    LoggedOut,
+    /// The first message on the connection was not a Logon.
+    FirstMessageNotLogon,
+    /// A Logon carried no valid HeartBtInt (108).
+    InvalidLogon,
+    /// No Logon reply within the logon timeout.
+    LogonTimeout,
+    /// No Logout reply within the logout timeout.
+    LogoutTimeout,
+    /// A TestRequest went unanswered for a heartbeat interval.
+    HeartbeatTimeout,
+    /// MsgSeqNum lower than expected without PossDupFlag; a Logout naming
+    /// the expected number was sent.
+    SeqNumTooLow { expected: u64, received: u64 },
+}
+
+/// What the application should do with an inbound message, as decided by
+/// [`Session::on_message`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// A session-level message the session answered itself.
+    Handled,
+    /// The Logon handshake completed; the session is [`Active`](SessionState::Active).
+    LoggedOn,
+    /// An in-sequence application message (or session Reject) to process.
+    Deliver,
+    /// A PossDup resend of a message already processed; ignore it.
+    Duplicate,
+    /// Messages `from..=to` are missing and a ResendRequest was sent; this
+    /// message must not be processed, it will be resent in order.
+    Gap { from: u64, to: u64 },
+    /// The counterparty asked for our messages `begin..=end` again. Answer
+    /// with [`Session::resend`] from the outbound journal, or skip the
+    /// whole range with [`Session::gap_fill`].
+    Resend { begin: u64, end: u64 },
+    /// The message was rejected with a session Reject (35=3).
+    Rejected,
+    /// Close the connection and call [`Session::on_disconnect`].
+    Disconnect(DisconnectReason),
+}
+
+/// The FIX session protocol for one connection: Logon/Logout handshake,
+/// Heartbeat and TestRequest timers, MsgSeqNum validation through
+/// [`SeqNums`], and ResendRequest generation.
+///
+/// The session does no I/O. The caller decodes each inbound message and
+/// passes it to [`on_message`](Self::on_message), calls
+/// [`poll`](Self::poll) from its timer, and supplies a `send` callback that
+/// encodes and writes every message the session produces. Application
+/// messages get their header from [`write_header`](Self::write_header) so
+/// they share the outbound sequence. Sequence numbers survive
+/// [`on_disconnect`](Self::on_disconnect); persist them with
+/// [`seq_nums`](Self::seq_nums) and restore them with
+/// [`seq_nums_mut`](Self::seq_nums_mut).
+///
+/// # Example
+/// ```ignore
+/// let mut session = Session::initiator(id, Duration::from_secs(30));
+/// let mut send = |m: &Message<'_>| {
+///     enc.encode(m, &mut out)?;
+///     stream.write_all(&out).map_err(|_| FixError::EncodeError)
+/// };
+/// session.on_connect(UtcTimestamp::now(), &mut send)?;
+/// loop {
+///     let msg = dec.decode(&read_frame(&mut stream)?)?;
+///     match session.on_message(&msg, UtcTimestamp::now(), &mut send)? {
+///         SessionEvent::Deliver => app.handle(&msg),
+///         SessionEvent::Resend { begin, end } => {
+///             let stored = journal.range(begin..=end).map(|(n, raw)| (*n, &raw[..]));
+///             session.resend(begin, end, stored, UtcTimestamp::now(), &mut send)?
+///         }
+///         SessionEvent::Disconnect(_) => break,
+///         _ => {}
+///     }
+///     session.poll(UtcTimestamp::now(), &mut send)?;
+/// }
+/// session.on_disconnect();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Session {
+    id: SessionId,
+    initiator: bool,
+    state: SessionState,
+    seq: SeqNums,
+    heart_bt_int: Duration,
+    reset_on_logon: bool,
+    logon_timeout: Duration,
+    logout_timeout: Duration,
+    precision: TimestampPrecision,
+    /// When the current LogonSent or LogoutSent state began.
+    state_since: UtcTimestamp,
+    last_sent: UtcTimestamp,
+    last_received: UtcTimestamp,
+    /// TestReqID and send time of the unanswered TestRequest.
+    test_request: Option<(u64, UtcTimestamp)>,
+    test_requests_sent: u64,
+    /// Last missing MsgSeqNum when the outstanding ResendRequest was sent;
+    /// cleared once inbound sequence passes it.
+    resend_until: Option<u64>,
+    out: MessageBuilder,
+}
+
+impl Session {
+    /// A session that logs on: [`on_connect`](Self::on_connect) sends Logon
+    /// with `heart_bt_int`.
+    pub fn initiator(id: SessionId, heart_bt_int: Duration) -> Self {
+        Self::new(id, true, heart_bt_int)
+    }
+
+    /// A session that waits for the counterparty's Logon and adopts its
+    /// HeartBtInt.
+    pub fn acceptor(id: SessionId) -> Self {
+        Self::new(id, false, Duration::ZERO)
+    }
+
+    fn new(id: SessionId, initiator: bool, heart_bt_int: Duration) -> Self {
+        let epoch = UtcTimestamp::new(0, 0);
+        Self {
+            id,
+            initiator,
+            state: SessionState::Disconnected,
+            seq: SeqNums::new(),
+            heart_bt_int,
+            reset_on_logon: false,
+            logon_timeout: Duration::from_secs(10),
+            logout_timeout: Duration::from_secs(10),
+            precision: TimestampPrecision::Millis,
+            state_since: epoch,
+            last_sent: epoch,
+            last_received: epoch,
+            test_request: None,
+            test_requests_sent: 0,
+            resend_until: None,
+            out: MessageBuilder::new(),
+        }
+    }
+
+    /// Initiator only: reset both sequence numbers to 1 at every Logon and
+    /// send ResetSeqNumFlag (141) `Y`. An acceptor follows the flag on the
+    /// counterparty's Logon. Default `false`.
+    pub fn reset_on_logon(mut self, reset: bool) -> Self {
+        self.reset_on_logon = reset;
+        self
+    }
+
+    /// How long to wait for a Logon reply. Default 10 seconds.
+    pub fn logon_timeout(mut self, timeout: Duration) -> Self {
+        self.logon_timeout = timeout;
+        self
+    }
+
+    /// How long to wait for a Logout reply. Default 10 seconds.
+    pub fn logout_timeout(mut self, timeout: Duration) -> Self {
+        self.logout_timeout = timeout;
+        self
+    }
+
+    /// SendingTime (52) precision on outbound messages. Default millis.
+    pub fn precision(mut self, precision: TimestampPrecision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    #[inline]
+    pub fn id(&self) -> &SessionId {
+        &self.id
+    }
+
+    #[inline]
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// The heartbeat interval in force; for an acceptor, zero until Logon.
+    #[inline]
+    pub fn heart_bt_int(&self) -> Duration {
+        self.heart_bt_int
+    }
+
+    #[inline]
+    pub fn seq_nums(&self) -> &SeqNums {
+        &self.seq
+    }
+
+    #[inline]
+    pub fn seq_nums_mut(&mut self) -> &mut SeqNums {
+        &mut self.seq
+    }
+
+    /// A connection is up: an initiator sends Logon, an acceptor starts
+    /// waiting for one.
+    ///
+    /// # Errors
+    /// `InvalidValue` if the session is not disconnected; otherwise any
+    /// error from `send`.
+    pub fn on_connect<S>(&mut self, now: UtcTimestamp, send: &mut S) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        if self.state != SessionState::Disconnected {
+            return Err(FixError::InvalidValue);
+        }
+        self.last_received = now;
+        self.state_since = now;
+        if !self.initiator {
+            self.state = SessionState::AwaitingLogon;
+            return Ok(());
+        }
+        if self.reset_on_logon {
+            self.seq.reset();
+        }
+        self.send_logon(self.reset_on_logon, now, send)?;
+        self.state = SessionState::LogonSent;
+        Ok(())
+    }
+
+    /// The connection is gone. Timers and pending requests are dropped;
+    /// sequence numbers are kept for the next connection.
+    pub fn on_disconnect(&mut self) {
+        self.state = SessionState::Disconnected;
+        self.test_request = None;
+        self.resend_until = None;
+    }
+
+    /// Start the Logout handshake with an optional Text (58).
+    ///
+    /// # Errors
+    /// `InvalidValue` unless the session is active; otherwise any error
+    /// from `send`.
+    pub fn logout<S>(
+        &mut self,
+        text: Option<&[u8]>,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        if self.state != SessionState::Active {
+            return Err(FixError::InvalidValue);
+        }
+        self.send_logout(text, now, send)?;
+        self.state = SessionState::LogoutSent;
+        self.state_since = now;
+        Ok(())
+    }
+
+    /// Write the standard header of an outbound application message into
+    /// `out`: BeginString, MsgType, the session's routing IDs, the next
+    /// MsgSeqNum and SendingTime. Push the body after it and pass the
+    /// result to the same `send` the session uses.
+    pub fn write_header(&mut self, msg_type: &[u8], now: UtcTimestamp, out: &mut MessageBuilder) {
+        let seq = self.seq.next_outbound();
+        self.header(msg_type, seq, now, out);
+        self.last_sent = now;
+    }
+
+    /// Send application message `msg` — MsgType (35) and body fields — under
+    /// this session's header (see [`write_header`](Self::write_header)).
+    /// BeginString, BodyLength, CompIDs, MsgSeqNum, SendingTime and
+    /// CheckSum in `msg` are ignored.
+    ///
+    /// # Errors
+    /// `FixError::InvalidValue` if the session is not Active or `msg` has
+    /// no MsgType; any error from `send`.
+    pub fn send_app<S>(
+        &mut self,
+        msg: &Message<'_>,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let msg_type = msg.find(tag::MSG_TYPE).ok_or(FixError::InvalidValue)?;
+        if self.state != SessionState::Active {
+            return Err(FixError::InvalidValue);
+        }
+        let mut out = std::mem::take(&mut self.out);
+        out.clear();
+        self.write_header(msg_type.value, now, &mut out);
+        for f in msg.fields() {
+            if !matches!(
+                f.tag,
+                tag::BEGIN_STRING
+                    | tag::BODY_LENGTH
+                    | tag::MSG_TYPE
+                    | tag::SENDER_COMP_ID
+                    | tag::TARGET_COMP_ID
+                    | tag::MSG_SEQ_NUM
+                    | tag::SENDING_TIME
+                    | tag::CHECK_SUM
+            ) {
+                out.push(f.tag, f.value);
+            }
+        }
+        let result = send(&out.as_message());
+        self.out = out;
+        result
+    }
+
+    /// Answer a ResendRequest for `begin..=end` with a SequenceReset-GapFill
+    /// (35=4, 123=Y) that skips the whole range.
+    ///
+    /// # Errors
+    /// Any error from `send`.
+    pub fn gap_fill<S>(
+        &mut self,
+        begin: u64,
+        end: u64,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let mut out = std::mem::take(&mut self.out);
+        out.clear();
+        self.header(b"4", begin, now, &mut out);
+        out.push(tag::POSS_DUP_FLAG, b"Y")
+            .push(tag::GAP_FILL_FLAG, b"Y")
+            .push_u64(tag::NEW_SEQ_NO, end.max(begin) + 1);
+        self.last_sent = now;
+        let result = send(&out.as_message());
+        self.out = out;
+        result
+    }
+
+    /// Answer a ResendRequest for `begin..=end` from `journal`, the stored
+    /// outbound messages as `(MsgSeqNum, raw bytes)` in ascending order.
+    ///
+    /// Application messages in the range are sent again with PossDupFlag
+    /// (43) `Y`, their original SendingTime as OrigSendingTime (122) and a
+    /// fresh SendingTime. Session messages (which are never resent) and
+    /// numbers missing from the journal are covered by SequenceReset-GapFill
+    /// messages, one per consecutive run. Entries outside the range are
+    /// skipped.
+    ///
+    /// # Errors
+    /// The error from decoding a journal entry, or any error from `send`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let SessionEvent::Resend { begin, end } = event {
+    ///     let stored = journal.range(begin..=end).map(|(n, raw)| (*n, &raw[..]));
+    ///     session.resend(begin, end, stored, UtcTimestamp::now(), &mut send)?;
+    /// }
+    /// ```
+    pub fn resend<'j, J, S>(
+        &mut self,
+        begin: u64,
+        end: u64,
+        journal: J,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        J: IntoIterator<Item = (u64, &'j [u8])>,
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let mut dec = Decoder::new();
+        // First number of the run not yet resent or gap-filled.
+        let mut next = begin;
+        for (seq, raw) in journal {
+            if seq < next || seq > end {
+                continue;
+            }
+            let msg = dec.decode(raw)?;
+            let msg_type = msg.find(tag::MSG_TYPE).map_or(&b""[..], |f| f.value);
+            if is_admin_msg_type(msg_type) {
+                continue;
+            }
+            if seq > next {
+                self.gap_fill(next, seq - 1, now, send)?;
+            }
+            self.send_poss_dup(&msg, now, send)?;
+            next = seq + 1;
+        }
+        if next <= end {
+            self.gap_fill(next, end, now, send)?;
+        }
+        Ok(())
+    }
+
+    /// [`resend`](Self::resend) with the journal read from `store`.
+    ///
+    /// # Errors
+    /// The store's I/O error, or a decode or `send` error wrapped as
+    /// `io::ErrorKind::InvalidData`.
+    pub fn resend_from<M, S>(
+        &mut self,
+        begin: u64,
+        end: u64,
+        store: &mut M,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> io::Result<()>
+    where
+        M: MessageStore,
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let stored = store.range(begin, end)?;
+        let journal = stored.iter().map(|(seq, raw)| (*seq, raw.as_slice()));
+        self.resend(begin, end, journal, now, send)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Send stored `msg` again with PossDupFlag, OrigSendingTime and a new
+    /// SendingTime, keeping its MsgSeqNum.
+    fn send_poss_dup<S>(
+        &mut self,
+        msg: &Message<'_>,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let mut out = std::mem::take(&mut self.out);
+        out.clear();
+        let original = msg.find(tag::SENDING_TIME).map(|f| f.value);
+        for f in msg.fields() {
+            match f.tag {
+                tag::BODY_LENGTH
+                | tag::CHECK_SUM
+                | tag::POSS_DUP_FLAG
+                | tag::SENDING_TIME
+                | tag::ORIG_SENDING_TIME => {}
+                tag::MSG_SEQ_NUM => {
+                    let (stamp, len) = now.format(self.precision);
+                    out.push(f.tag, f.value)
+                        .push(tag::POSS_DUP_FLAG, b"Y")
+                        .push(tag::SENDING_TIME, &stamp[..len]);
+                    if let Some(original) = original {
+                        out.push(tag::ORIG_SENDING_TIME, original);
+                    }
+                }
+                _ => {
+                    out.push(f.tag, f.value);
+                }
+            }
+        }
+        self.last_sent = now;
+        let result = send(&out.as_message());
+        self.out = out;
+        result
+    }
+
+    /// Apply the session protocol to a decoded inbound message received at
+    /// `now`, sending any replies through `send`.
+    ///
+    /// # Errors
+    /// `InvalidValue` if the session is disconnected, or MsgType (35) or
+    /// MsgSeqNum (34) is missing or malformed; otherwise any error from
+    /// `send`.
+    pub fn on_message<S>(
+        &mut self,
+        msg: &Message<'_>,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<SessionEvent, FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let msg_type = msg.find(tag::MSG_TYPE).ok_or(FixError::InvalidValue)?.value;
+        match self.state {
+            SessionState::Disconnected => return Err(FixError::InvalidValue),
+            SessionState::AwaitingLogon | SessionState::LogonSent => {
+                self.last_received = now;
+                if msg_type == b"A" {
+                    return self.on_logon(msg, now, send);
+                }
+                if msg_type == b"5" && self.state == SessionState::LogonSent {
+                    // Logon refused.
+                    self.state = SessionState::Disconnected;
+                    return Ok(SessionEvent::Disconnect(DisconnectReason::LoggedOut));
+                }
+                return Ok(self.disconnect(DisconnectReason::FirstMessageNotLogon));
+            }
+            SessionState::Active | SessionState::LogoutSent => self.last_received = now,
+        }
+
+        let event = match self.seq.check_inbound(msg)? {
+            SeqCheck::InOrder if !orig_sending_time_ok(msg) => {
+                self.reject(msg, 10, b"OrigSendingTime after SendingTime", now, send)?;
+                SessionEvent::Rejected
+            }
+            SeqCheck::InOrder => self.on_in_order(msg, msg_type, now, send)?,
+            SeqCheck::Gap { from, to } => {
+                self.request_resend(from, to, now, send)?;
+                match msg_type {
+                    b"2" => self.resend_range(msg)?,
+                    b"5" => self.on_logout(now, send)?,
+                    _ => SessionEvent::Gap { from, to },
+                }
+            }
+            SeqCheck::PossDup => SessionEvent::Duplicate,
+            SeqCheck::TooLow { expected, received } => {
+                self.too_low(expected, received, now, send)?
+            }
+            SeqCheck::Reset { .. } => SessionEvent::Handled,
+            SeqCheck::ResetBackwards { .. } => {
+                self.reject(msg, 5, b"NewSeqNo too low", now, send)?;
+                SessionEvent::Rejected
+            }
+        };
+        if self
+            .resend_until
+            .is_some_and(|end| self.seq.expected_inbound() > end)
+        {
+            self.resend_until = None;
+        }
+        Ok(event)
+    }
+
+    /// Drive the timers at `now`: send a Heartbeat after an interval
+    /// without outbound traffic, a TestRequest after an interval (plus 20%)
+    /// without inbound traffic, and time out unanswered TestRequests,
+    /// Logons and Logouts. Call at least once a second.
+    ///
+    /// # Errors
+    /// Any error from `send`.
+    pub fn poll<S>(
+        &mut self,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<Option<DisconnectReason>, FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let since = |t: UtcTimestamp| {
+            if now > t {
+                now.abs_diff(&t)
+            } else {
+                Duration::ZERO
+            }
+        };
+        match self.state {
+            SessionState::Disconnected | SessionState::AwaitingLogon => return Ok(None),
+            SessionState::LogonSent => {
+                if since(self.state_since) >= self.logon_timeout {
+                    return Ok(self.timeout(DisconnectReason::LogonTimeout));
+                }
+                return Ok(None);
+            }
+            SessionState::LogoutSent => {
+                if since(self.state_since) >= self.logout_timeout {
+                    return Ok(self.timeout(DisconnectReason::LogoutTimeout));
+                }
+            }
+            SessionState::Active => {}
+        }
+        let interval = self.heart_bt_int;
+        if interval.is_zero() {
+            return Ok(None);
+        }
+
+        match self.test_request {
+            Some((_, sent)) if since(sent) >= interval => {
+                return Ok(self.timeout(DisconnectReason::HeartbeatTimeout));
+            }
+            None if since(self.last_received) >= interval + interval / 5 => {
+                self.test_requests_sent += 1;
+                let id = self.test_requests_sent;
+                self.send_admin(b"1", now, send, |out| {
+                    out.push_u64(tag::TEST_REQ_ID, id);
+                })?;
+                self.test_request = Some((id, now));
+            }
+            _ => {}
+        }
+        if since(self.last_sent) >= interval {
+            self.send_admin(b"0", now, send, |_| {})?;
+        }
+        Ok(None)
+    }
+
+    fn on_logon<S>(
+        &mut self,
+        msg: &Message<'_>,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<SessionEvent, FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let Some(secs) = msg.find(tag::HEART_BT_INT).and_then(|f| f.as_u64().ok()) else {
+            return Ok(self.disconnect(DisconnectReason::InvalidLogon));
+        };
+        let reset = msg.find(tag::RESET_SEQ_NUM_FLAG).map(|f| f.value) == Some(b"Y");
+        if reset {
+            self.seq.set_expected_inbound(1);
+        }
+        let check = self.seq.check_inbound(msg)?;
+        if let SeqCheck::TooLow { expected, received } = check {
+            return self.too_low(expected, received, now, send);
+        }
+
+        if !self.initiator {
+            self.heart_bt_int = Duration::from_secs(secs);
+            if reset {
+                self.seq.set_next_outbound(1);
+            }
+            self.send_logon(reset, now, send)?;
+        }
+        self.state = SessionState::Active;
+        if let SeqCheck::Gap { from, to } = check {
+            // The Logon is accepted; the counterparty resends everything
+            // from `from`, gap-filling over the Logon itself.
+            self.request_resend(from, to, now, send)?;
+        }
+        Ok(SessionEvent::LoggedOn)
+    }
+
+    fn on_in_order<S>(
+        &mut self,
+        msg: &Message<'_>,
+        msg_type: &[u8],
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<SessionEvent, FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        Ok(match msg_type {
+            b"0" => {
+                let answered = msg.find(tag::TEST_REQ_ID).and_then(|f| f.as_u64().ok());
+                if answered.is_some() && answered == self.test_request.map(|(id, _)| id) {
+                    self.test_request = None;
+                }
+                SessionEvent::Handled
+            }
+            b"1" => {
+                let id = msg.find(tag::TEST_REQ_ID).map_or(&b""[..], |f| f.value);
+                self.send_admin(b"0", now, send, |out| {
+                    out.push(tag::TEST_REQ_ID, id);
+                })?;
+                SessionEvent::Handled
+            }
+            b"2" => self.resend_range(msg)?,
+            b"5" => self.on_logout(now, send)?,
+            b"A" => SessionEvent::Handled,
+            _ => SessionEvent::Deliver,
+        })
+    }
+
+    fn on_logout<S>(&mut self, now: UtcTimestamp, send: &mut S) -> Result<SessionEvent, FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        if self.state != SessionState::LogoutSent {
+            self.send_logout(None, now, send)?;
+        }
+        Ok(self.disconnect(DisconnectReason::LoggedOut))
+    }
+
+    /// Log out naming the expected MsgSeqNum and drop the connection.
+    fn too_low<S>(
+        &mut self,
+        expected: u64,
+        received: u64,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<SessionEvent, FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let text = format!("MsgSeqNum too low, expecting {expected} but received {received}");
+        self.send_logout(Some(text.as_bytes()), now, send)?;
+        Ok(self.disconnect(DisconnectReason::SeqNumTooLow { expected, received }))
+    }
+
+    /// The range asked for by ResendRequest `msg`; EndSeqNo 0 means
+    /// "everything sent so far".
+    fn resend_range(&self, msg: &Message<'_>) -> Result<SessionEvent, FixError> {
+        let number = |t| {
+            msg.find(t)
+                .ok_or(FixError::InvalidValue)
+                .and_then(|f| f.as_u64())
+        };
+        let begin = number(tag::BEGIN_SEQ_NO)?;
+        let last_sent = self.seq.peek_outbound().saturating_sub(1);
+        let end = match number(tag::END_SEQ_NO)? {
+            0 => last_sent,
+            end => end.min(last_sent),
+        };
+        Ok(SessionEvent::Resend { begin, end })
+    }
+
+    /// Send a ResendRequest from `from` onwards unless one is outstanding;
+    /// it asks for everything (EndSeqNo 0), so it covers later gaps too.
+    fn request_resend<S>(
+        &mut self,
+        from: u64,
+        to: u64,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        if self.resend_until.is_some() {
+            return Ok(());
+        }
+        self.send_admin(b"2", now, send, |out| {
+            out.push_u64(tag::BEGIN_SEQ_NO, from)
+                .push_u64(tag::END_SEQ_NO, 0);
+        })?;
+        self.resend_until = Some(to);
+        Ok(())
+    }
+
+    fn reject<S>(
+        &mut self,
+        msg: &Message<'_>,
+        reason: u32,
+        text: &[u8],
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let ref_seq = msg.find(tag::MSG_SEQ_NUM).map_or(&b"0"[..], |f| f.value);
+        self.send_admin(b"3", now, send, |out| {
+            out.push(tag::REF_SEQ_NUM, ref_seq)
+                .push_u32(tag::SESSION_REJECT_REASON, reason)
+                .push(tag::TEXT, text);
+        })
+    }
+
+    fn send_logon<S>(
+        &mut self,
+        reset: bool,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let secs = self.heart_bt_int.as_secs();
+        self.send_admin(b"A", now, send, |out| {
+            out.push(tag::ENCRYPT_METHOD, b"0")
+                .push_u64(tag::HEART_BT_INT, secs);
+            if reset {
+                out.push(tag::RESET_SEQ_NUM_FLAG, b"Y");
+            }
+        })
+    }
+
+    fn send_logout<S>(
+        &mut self,
+        text: Option<&[u8]>,
+        now: UtcTimestamp,
+        send: &mut S,
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        self.send_admin(b"5", now, send, |out| {
+            if let Some(text) = text {
+                out.push(tag::TEXT, text);
+            }
+        })
+    }
+
+    /// Build a session message with the next MsgSeqNum and `body`, and send it.
+    fn send_admin<S>(
+        &mut self,
+        msg_type: &[u8],
+        now: UtcTimestamp,
+        send: &mut S,
+        body: impl FnOnce(&mut MessageBuilder),
+    ) -> Result<(), FixError>
+    where
+        S: FnMut(&Message<'_>) -> Result<(), FixError>,
+    {
+        let mut out = std::mem::take(&mut self.out);
+        out.clear();
+        self.write_header(msg_type, now, &mut out);
+        body(&mut out);
+        let result = send(&out.as_message());
+        self.out = out;
+        result
+    }
+
+    fn header(&self, msg_type: &[u8], seq: u64, now: UtcTimestamp, out: &mut MessageBuilder) {
+        out.push(tag::BEGIN_STRING, &self.id.begin_string)
+            .push(tag::MSG_TYPE, msg_type);
+        self.id.sender.push(SENDER, out);
+        self.id.target.push(TARGET, out);
+        if let Some(p) = &self.id.on_behalf_of {
+            p.push(ON_BEHALF_OF, out);
+        }
+        if let Some(p) = &self.id.deliver_to {
+            p.push(DELIVER_TO, out);
+        }
+        let (stamp, len) = now.format(self.precision);
+        out.push_u64(tag::MSG_SEQ_NUM, seq)
+            .push(tag::SENDING_TIME, &stamp[..len]);
+    }
+
+    fn disconnect(&mut self, reason: DisconnectReason) -> SessionEvent {
+        self.on_disconnect();
+        SessionEvent::Disconnect(reason)
+    }
+
+    fn timeout(&mut self, reason: DisconnectReason) -> Option<DisconnectReason> {
+        self.on_disconnect();
+        Some(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    fn seq_msg(fields: &[(Tag, &[u8])]) -> MessageBuilder {
+        let mut b = MessageBuilder::new();
+        for (t, v) in fields {
+            b.push(*t, v);
+        }
+        b
+    }
+
+    #[test]
+    fn seq_nums_in_order_gap_and_too_low() {
+        let mut seq = SeqNums::new();
+        let check = |seq: &mut SeqNums, fields: &[(Tag, &[u8])]| {
+            seq.check_inbound(&seq_msg(fields).as_message()).unwrap()
+        };
+        assert_eq!(
+            check(&mut seq, &[(tag::MSG_TYPE, b"A"), (tag::MSG_SEQ_NUM, b"1")]),
+            SeqCheck::InOrder
+        );
+        assert_eq!(
+            check(&mut seq, &[(tag::MSG_TYPE, b"0"), (tag::MSG_SEQ_NUM, b"5")]),
+            SeqCheck::Gap { from: 2, to: 4 }
+        );
+        assert_eq!(seq.expected_inbound(), 2);
+        assert_eq!(
+            check(&mut seq, &[(tag::MSG_TYPE, b"0"), (tag::MSG_SEQ_NUM, b"1")]),
+            SeqCheck::TooLow {
+                expected: 2,
+                received: 1
+            }
+        );
+        assert_eq!(
+            check(
+                &mut seq,
+                &[
+                    (tag::MSG_TYPE, b"D"),
+                    (tag::MSG_SEQ_NUM, b"1"),
+                    (tag::POSS_DUP_FLAG, b"Y")
+                ]
+            ),
+            SeqCheck::PossDup
+        );
+        assert!(
+            seq.check_inbound(&seq_msg(&[(tag::MSG_TYPE, b"0")]).as_message())
+                .is_err()
+        );
+
+        assert_eq!((seq.next_outbound(), seq.next_outbound()), (1, 2));
+        assert_eq!(seq.peek_outbound(), 3);
+        seq.reset();
+        assert_eq!((seq.expected_inbound(), seq.peek_outbound()), (1, 1));
+    }
+
+    #[test]
+    fn seq_nums_sequence_reset() {
+        let mut seq = SeqNums::with_next(2, 1);
+        let gap_fill = seq_msg(&[
+            (tag::MSG_TYPE, b"4"),
+            (tag::MSG_SEQ_NUM, b"2"),
+            (tag::GAP_FILL_FLAG, b"Y"),
+            (tag::NEW_SEQ_NO, b"6"),
+        ]);
+        assert_eq!(
+            seq.check_inbound(&gap_fill.as_message()).unwrap(),
+            SeqCheck::Reset { next: 6 }
+        );
+        let reset = |n: &[u8]| {
+            seq_msg(&[
+                (tag::MSG_TYPE, b"4"),
+                (tag::MSG_SEQ_NUM, b"99"),
+                (tag::NEW_SEQ_NO, n),
+            ])
+        };
+        assert_eq!(
+            seq.check_inbound(&reset(b"3").as_message()).unwrap(),
+            SeqCheck::ResetBackwards {
+                expected: 6,
+                new_seq_no: 3
+            }
+        );
+        assert_eq!(
+            seq.check_inbound(&reset(b"20").as_message()).unwrap(),
+            SeqCheck::Reset { next: 20 }
+        );
+        assert_eq!(seq.expected_inbound(), 20);
+    }
+
+    const T0: i64 = 1_709_214_306;
+
+    fn at(secs: i64) -> UtcTimestamp {
+        UtcTimestamp::new(T0 + secs, 0)
+    }
+
+    type Sent = Vec<Vec<(Tag, Vec<u8>)>>;
+
+    fn recorder(log: &mut Sent) -> impl FnMut(&Message<'_>) -> Result<(), FixError> + '_ {
+        |m: &Message<'_>| {
+            log.push(m.fields().map(|f| (f.tag, f.value.to_vec())).collect());
+            Ok(())
+        }
+    }
+
+    fn get(msg: &[(Tag, Vec<u8>)], t: Tag) -> Option<&[u8]> {
+        msg.iter()
+            .find(|(tag, _)| *tag == t)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    fn inbound(msg_type: &[u8], seq: u64, extra: &[(Tag, &[u8])]) -> MessageBuilder {
+        let mut b = MessageBuilder::new();
+        b.push(tag::MSG_TYPE, msg_type)
+            .push_u64(tag::MSG_SEQ_NUM, seq);
+        for (t, v) in extra {
+            b.push(*t, v);
+        }
+        b
+    }
+
+    fn venue() -> SessionId {
+        SessionId::new(b"FIX.4.4", CompId::new(b"BROKER"), CompId::new(b"VENUE"))
+    }
+
+    /// An initiator that has completed Logon at `at(0)`.
+    fn active(log: &mut Sent) -> Session {
+        let mut s = Session::initiator(venue(), Duration::from_secs(30));
+        s.on_connect(at(0), &mut recorder(log)).unwrap();
+        let logon = inbound(b"A", 1, &[(tag::HEART_BT_INT, b"30")]);
+        assert_eq!(
+            s.on_message(&logon.as_message(), at(0), &mut recorder(log))
+                .unwrap(),
+            SessionEvent::LoggedOn
+        );
+        s
+    }
+
+    #[test]
+    fn initiator_logon_and_heartbeat_timers() {
+        let mut log = Sent::new();
+        let mut s = active(&mut log);
+        assert_eq!(s.state(), SessionState::Active);
+        let logon = &log[0];
+        assert_eq!(get(logon, tag::MSG_TYPE), Some(&b"A"[..]));
+        assert_eq!(get(logon, tag::SENDER_COMP_ID), Some(&b"BROKER"[..]));
+        assert_eq!(get(logon, tag::MSG_SEQ_NUM), Some(&b"1"[..]));
+        assert_eq!(get(logon, tag::HEART_BT_INT), Some(&b"30"[..]));
+        assert_eq!(
+            get(logon, tag::SENDING_TIME),
+            Some(&b"20240229-13:45:06.000"[..])
+        );
+
+        // Outbound silence: Heartbeat. Inbound silence past 36s: TestRequest.
+        assert_eq!(s.poll(at(30), &mut recorder(&mut log)).unwrap(), None);
+        assert_eq!(get(&log[1], tag::MSG_TYPE), Some(&b"0"[..]));
+        s.poll(at(37), &mut recorder(&mut log)).unwrap();
+        assert_eq!(get(&log[2], tag::MSG_TYPE), Some(&b"1"[..]));
+        assert_eq!(get(&log[2], tag::TEST_REQ_ID), Some(&b"1"[..]));
+        assert_eq!(log.len(), 3);
+
+        let answer = inbound(b"0", 2, &[(tag::TEST_REQ_ID, b"1")]);
+        let event = s.on_message(&answer.as_message(), at(38), &mut recorder(&mut log));
+        assert_eq!(event.unwrap(), SessionEvent::Handled);
+
+        let test = inbound(b"1", 3, &[(tag::TEST_REQ_ID, b"PING")]);
+        s.on_message(&test.as_message(), at(39), &mut recorder(&mut log))
+            .unwrap();
+        assert_eq!(get(&log[3], tag::MSG_TYPE), Some(&b"0"[..]));
+        assert_eq!(get(&log[3], tag::TEST_REQ_ID), Some(&b"PING"[..]));
+        assert_eq!(get(&log[3], tag::MSG_SEQ_NUM), Some(&b"4"[..]));
+
+        // The answered TestRequest does not time out; a new unanswered one does.
+        assert_eq!(s.poll(at(76), &mut recorder(&mut log)).unwrap(), None);
+        assert_eq!(get(&log[4], tag::TEST_REQ_ID), Some(&b"2"[..]));
+        assert_eq!(
+            s.poll(at(106), &mut recorder(&mut log)).unwrap(),
+            Some(DisconnectReason::HeartbeatTimeout)
+        );
+        assert_eq!(s.state(), SessionState::Disconnected);
+        assert_eq!(s.seq_nums().peek_outbound(), 6);
+    }
+
+    #[test]
+    fn logon_timeout_and_refusal() {
+        let mut log = Sent::new();
+        let mut s = Session::initiator(venue(), Duration::from_secs(30))
+            .reset_on_logon(true)
+            .logon_timeout(Duration::from_secs(5));
+        s.seq_nums_mut().set_next_outbound(40);
+        s.on_connect(at(0), &mut recorder(&mut log)).unwrap();
+        assert_eq!(get(&log[0], tag::MSG_SEQ_NUM), Some(&b"1"[..]));
+        assert_eq!(get(&log[0], tag::RESET_SEQ_NUM_FLAG), Some(&b"Y"[..]));
+        assert!(s.on_connect(at(0), &mut recorder(&mut log)).is_err());
+        assert_eq!(s.poll(at(4), &mut recorder(&mut log)).unwrap(), None);
+        assert_eq!(
+            s.poll(at(5), &mut recorder(&mut log)).unwrap(),
+            Some(DisconnectReason::LogonTimeout)
+        );
+
+        s.on_connect(at(10), &mut recorder(&mut log)).unwrap();
+        let refused = inbound(b"5", 1, &[(tag::TEXT, b"unknown CompID")]);
+        assert_eq!(
+            s.on_message(&refused.as_message(), at(11), &mut recorder(&mut log))
+                .unwrap(),
+            SessionEvent::Disconnect(DisconnectReason::LoggedOut)
+        );
+    }
+
+    #[test]
+    fn gaps_duplicates_and_too_low() {
+        let mut log = Sent::new();
+        let mut s = active(&mut log);
+        let on = |s: &mut Session, b: MessageBuilder, log: &mut Sent| {
+            s.on_message(&b.as_message(), at(1), &mut recorder(log))
+                .unwrap()
+        };
+
+        assert_eq!(
+            on(&mut s, inbound(b"D", 2, &[]), &mut log),
+            SessionEvent::Deliver
+        );
+        assert_eq!(
+            on(&mut s, inbound(b"D", 5, &[]), &mut log),
+            SessionEvent::Gap { from: 3, to: 4 }
+        );
+        let resend = &log[1];
+        assert_eq!(get(resend, tag::MSG_TYPE), Some(&b"2"[..]));
+        assert_eq!(get(resend, tag::BEGIN_SEQ_NO), Some(&b"3"[..]));
+        assert_eq!(get(resend, tag::END_SEQ_NO), Some(&b"0"[..]));
+        // Further messages inside the requested range don't re-request.
+        on(&mut s, inbound(b"D", 6, &[]), &mut log);
+        assert_eq!(log.len(), 2);
+
+        let dup = inbound(b"D", 2, &[(tag::POSS_DUP_FLAG, b"Y")]);
+        assert_eq!(on(&mut s, dup, &mut log), SessionEvent::Duplicate);
+        let fill = inbound(
+            b"4",
+            3,
+            &[(tag::GAP_FILL_FLAG, b"Y"), (tag::NEW_SEQ_NO, b"5")],
+        );
+        assert_eq!(on(&mut s, fill, &mut log), SessionEvent::Handled);
+        assert_eq!(
+            on(&mut s, inbound(b"D", 5, &[]), &mut log),
+            SessionEvent::Deliver
+        );
+
+        let backwards = inbound(b"4", 6, &[(tag::NEW_SEQ_NO, b"2")]);
+        assert_eq!(on(&mut s, backwards, &mut log), SessionEvent::Rejected);
+        assert_eq!(get(&log[2], tag::MSG_TYPE), Some(&b"3"[..]));
+        assert_eq!(get(&log[2], tag::REF_SEQ_NUM), Some(&b"6"[..]));
+        assert_eq!(get(&log[2], tag::SESSION_REJECT_REASON), Some(&b"5"[..]));
+
+        assert_eq!(
+            on(&mut s, inbound(b"D", 3, &[]), &mut log),
+            SessionEvent::Disconnect(DisconnectReason::SeqNumTooLow {
+                expected: 6,
+                received: 3
+            })
+        );
+        assert_eq!(get(&log[3], tag::MSG_TYPE), Some(&b"5"[..]));
+        assert_eq!(
+            get(&log[3], tag::TEXT),
+            Some(&b"MsgSeqNum too low, expecting 6 but received 3"[..])
+        );
+        assert!(
+            s.on_message(
+                &inbound(b"D", 6, &[]).as_message(),
+                at(2),
+                &mut recorder(&mut log)
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn resend_request_is_answered_with_gap_fill() {
+        let mut log = Sent::new();
+        let mut s = active(&mut log);
+        let mut order = MessageBuilder::new();
+        for _ in 0..3 {
+            order.clear();
+            s.write_header(b"D", at(1), &mut order);
+        }
+        assert_eq!(
+            order.as_message().find(tag::MSG_SEQ_NUM).unwrap().value,
+            b"4"
+        );
+
+        let request = inbound(
+            b"2",
+            2,
+            &[(tag::BEGIN_SEQ_NO, b"2"), (tag::END_SEQ_NO, b"0")],
+        );
+        let event = s
+            .on_message(&request.as_message(), at(2), &mut recorder(&mut log))
+            .unwrap();
+        assert_eq!(event, SessionEvent::Resend { begin: 2, end: 4 });
+        s.gap_fill(2, 4, at(2), &mut recorder(&mut log)).unwrap();
+        let fill = &log[1];
+        assert_eq!(get(fill, tag::MSG_TYPE), Some(&b"4"[..]));
+        assert_eq!(get(fill, tag::MSG_SEQ_NUM), Some(&b"2"[..]));
+        assert_eq!(get(fill, tag::POSS_DUP_FLAG), Some(&b"Y"[..]));
+        assert_eq!(get(fill, tag::NEW_SEQ_NO), Some(&b"5"[..]));
+        assert_eq!(s.seq_nums().peek_outbound(), 5);
+    }
+
+    #[test]
+    fn resend_replays_journal_and_gap_fills_the_rest() {
+        let mut log = Sent::new();
+        let mut s = active(&mut log);
+        let journal: Vec<(u64, &[u8])> = vec![
+            (1, b"8=FIX.4.4\x019=5\x0135=A\x0134=1\x0110=000\x01"),
+            (2, b"8=FIX.4.4\x019=5\x0135=D\x0134=2\x0152=20240229-13:45:07.000\x0111=A\x0110=000\x01"),
+            (3, b"8=FIX.4.4\x0135=0\x0134=3\x01"),
+            (5, b"8=FIX.4.4\x0135=F\x0134=5\x0143=N\x0141=A\x01"),
+        ];
+        s.resend(2, 6, journal, at(9), &mut recorder(&mut log))
+            .unwrap();
+        let sent: Vec<_> = log[1..]
+            .iter()
+            .map(|m| {
+                (
+                    get(m, tag::MSG_TYPE).unwrap().to_vec(),
+                    get(m, tag::MSG_SEQ_NUM).unwrap().to_vec(),
+                    get(m, tag::NEW_SEQ_NO).map(<[u8]>::to_vec),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sent,
+            [
+                (b"D".to_vec(), b"2".to_vec(), None),
+                (b"4".to_vec(), b"3".to_vec(), Some(b"5".to_vec())),
+                (b"F".to_vec(), b"5".to_vec(), None),
+                (b"4".to_vec(), b"6".to_vec(), Some(b"7".to_vec())),
+            ]
+        );
+        let order = &log[1];
+        assert_eq!(get(order, tag::POSS_DUP_FLAG), Some(&b"Y"[..]));
+        assert_eq!(
+            get(order, tag::ORIG_SENDING_TIME),
+            Some(&b"20240229-13:45:07.000"[..])
+        );
+        assert_eq!(
+            get(order, tag::SENDING_TIME),
+            Some(&b"20240229-13:45:15.000"[..])
+        );
+        assert_eq!(get(order, tag::CL_ORD_ID), Some(&b"A"[..]));
+        assert_eq!(get(order, tag::CHECK_SUM), None);
+        assert_eq!(get(&log[3], tag::POSS_DUP_FLAG), Some(&b"Y"[..]));
+        assert_eq!(get(&log[3], tag::ORIG_SENDING_TIME), None);
+        // Resending does not consume outbound sequence numbers.
+        assert_eq!(s.seq_nums().peek_outbound(), 2);
+    }
+
+    #[test]
+    fn resend_from_store() {
+        use crate::store::MemoryStore;
+
+        let mut log = Sent::new();
+        let mut s = active(&mut log);
+        let mut store = MemoryStore::new();
+        store.append(2, b"8=FIX.4.4\x0135=D\x0134=2\x01").unwrap();
+        s.resend_from(2, 3, &mut store, at(5), &mut recorder(&mut log))
+            .unwrap();
+        assert_eq!(get(&log[1], tag::MSG_TYPE), Some(&b"D"[..]));
+        assert_eq!(get(&log[2], tag::NEW_SEQ_NO), Some(&b"4"[..]));
+
+        store.append(4, b"garbage").unwrap();
+        let err = s
+            .resend_from(4, 4, &mut store, at(5), &mut recorder(&mut log))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn poss_dup_resend_checks_orig_sending_time() {
+        let mut log = Sent::new();
+        let mut s = active(&mut log);
+        let resent = |seq, orig: &[u8]| {
+            inbound(
+                b"D",
+                seq,
+                &[
+                    (tag::POSS_DUP_FLAG, b"Y"),
+                    (tag::SENDING_TIME, b"20240229-13:45:10"),
+                    (tag::ORIG_SENDING_TIME, orig),
+                ],
+            )
+        };
+        let ok = resent(2, b"20240229-13:45:08");
+        assert_eq!(
+            s.on_message(&ok.as_message(), at(10), &mut recorder(&mut log))
+                .unwrap(),
+            SessionEvent::Deliver
+        );
+        let late = resent(3, b"20240229-13:45:11");
+        assert_eq!(
+            s.on_message(&late.as_message(), at(10), &mut recorder(&mut log))
+                .unwrap(),
+            SessionEvent::Rejected
+        );
+        assert_eq!(get(&log[1], tag::SESSION_REJECT_REASON), Some(&b"10"[..]));
+        assert_eq!(s.seq_nums().expected_inbound(), 4);
+
+        // A resent GapFill below the expected number is a duplicate.
+        let fill = inbound(
+            b"4",
+            2,
+            &[
+                (tag::POSS_DUP_FLAG, b"Y"),
+                (tag::GAP_FILL_FLAG, b"Y"),
+                (tag::NEW_SEQ_NO, b"3"),
+            ],
+        );
+        assert_eq!(
+            s.on_message(&fill.as_message(), at(11), &mut recorder(&mut log))
+                .unwrap(),
+            SessionEvent::Duplicate
+        );
+        assert_eq!(s.seq_nums().expected_inbound(), 4);
+    }
+
+    #[test]
+    fn acceptor_logon_and_logout_handshake() {
+        let mut log = Sent::new();
+        let mut s = Session::acceptor(venue());
+        s.on_connect(at(0), &mut recorder(&mut log)).unwrap();
+        assert_eq!(s.state(), SessionState::AwaitingLogon);
+        let order = inbound(b"D", 1, &[]);
+        assert_eq!(
+            s.on_message(&order.as_message(), at(1), &mut recorder(&mut log))
+                .unwrap(),
+            SessionEvent::Disconnect(DisconnectReason::FirstMessageNotLogon)
+        );
+        assert!(log.is_empty());
+
+        s.on_connect(at(2), &mut recorder(&mut log)).unwrap();
+        let logon = inbound(
+            b"A",
+            1,
+            &[(tag::HEART_BT_INT, b"15"), (tag::RESET_SEQ_NUM_FLAG, b"Y")],
+        );
+        let event = s.on_message(&logon.as_message(), at(2), &mut recorder(&mut log));
+        assert_eq!(event.unwrap(), SessionEvent::LoggedOn);
+        assert_eq!(s.heart_bt_int(), Duration::from_secs(15));
+        assert_eq!(get(&log[0], tag::HEART_BT_INT), Some(&b"15"[..]));
+        assert_eq!(get(&log[0], tag::RESET_SEQ_NUM_FLAG), Some(&b"Y"[..]));
+
+        s.logout(Some(b"end of day"), at(3), &mut recorder(&mut log))
+            .unwrap();
+        assert_eq!(s.state(), SessionState::LogoutSent);
+        assert_eq!(get(&log[1], tag::TEXT), Some(&b"end of day"[..]));
+        let reply = inbound(b"5", 2, &[]);
+        assert_eq!(
+            s.on_message(&reply.as_message(), at(4), &mut recorder(&mut log))
+                .unwrap(),
+            SessionEvent::Disconnect(DisconnectReason::LoggedOut)
+        );
+        assert_eq!(log.len(), 2);
+
+        // A counterparty-initiated Logout is answered.
+        let mut log = Sent::new();
+        let mut s = active(&mut log);
+        let logout = inbound(b"5", 2, &[]);
+        s.on_message(&logout.as_message(), at(5), &mut recorder(&mut log))
+            .unwrap();
+        assert_eq!(get(&log[1], tag::MSG_TYPE), Some(&b"5"[..]));
+        assert_eq!(s.state(), SessionState::Disconnected);
+    }
+
+    fn broker() -> SessionId {
+        SessionId::new(b"FIX.4.4", CompId::new(b"BROKER"), CompId::new(b"VENUE"))
+            .deliver_to(CompId::new(b"CLIENT1").sub_id(b"DESK7"))
+    }
+
+    #[test]
+    fn inbound_matching_mirrors_routing_roles() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"8=FIX.4.4\x0135=8\x0149=venue \x0156=BROKER\x01115=CLIENT1\x01116=desk7\x01")
+            .unwrap();
+        assert!(!broker().matches_inbound(&msg, &ExactIds));
+        assert!(broker().matches_inbound(&msg, &CaseInsensitiveIds));
+
+        let id = SessionId::from_inbound(&msg).unwrap();
+        assert_eq!(id.sender.comp_id, b"BROKER");
+        assert_eq!(id.normalized(&CaseInsensitiveIds), broker());
+
+        let msg = dec
+            .decode(b"8=FIX.4.4\x0135=8\x0149=VENUE\x0156=BROKER\x01115=CLIENT1\x01")
+            .unwrap();
+        assert!(!broker().matches_inbound(&msg, &CaseInsensitiveIds));
+    }
+
+    #[test]
+    fn header_fields_populate_routing_tags() {
+        let id = SessionId::new(b"FIX.4.2", CompId::new(b"BROKER"), CompId::new(b"VENUE"))
+            .on_behalf_of(CompId::new(b"CLIENT1").location_id(b"US"));
+        let mut enc = Encoder::new();
+        enc.push_stage(id.header_fields());
+
+        let mut dec = Decoder::new();
+        let msg = dec.decode(b"35=D\x0149=X\x0111=A\x01").unwrap();
+        let mut out = Vec::new();
+        enc.encode(&msg, &mut out).unwrap();
+        assert!(out.starts_with(
+            b"8=FIX.4.2\x019=48\x0135=D\x0149=BROKER\x0156=VENUE\x01115=CLIENT1\x01144=US\x0111=A\x01"
+        ));
+    }
+    #[test]
+    fn header_guard_rejects_or_overrides_other_sessions() {
+        let id = SessionId::new(b"FIX.4.4", CompId::new(b"BROKER"), CompId::new(b"VENUE"));
+        let mut dec = Decoder::new();
+        let mut out = Vec::new();
+
+        let mut enc = Encoder::new();
+        enc.push_stage(id.header_guard(HeaderConflict::Reject));
+        let ok = dec.decode(b"35=D\x0156=VENUE\x0111=A\x01").unwrap();
+        enc.encode(&ok, &mut out).unwrap();
+        assert!(out.starts_with(b"8=FIX.4.4\x019=29\x0135=D\x0156=VENUE\x0149=BROKER\x01"));
+
+        let misrouted = b"8=FIX.4.4\x0135=D\x0156=OTHER\x0111=A\x01";
+        let err = enc.encode(&dec.decode(misrouted).unwrap(), &mut out);
+        assert!(matches!(
+            err,
+            Err(FixError::HeaderMismatch(tag::TARGET_COMP_ID))
+        ));
+        let fix42 = b"8=FIX.4.2\x0135=D\x0111=A\x01";
+        let err = enc.encode(&dec.decode(fix42).unwrap(), &mut out);
+        assert!(matches!(
+            err,
+            Err(FixError::HeaderMismatch(tag::BEGIN_STRING))
+        ));
+
+        let mut enc = Encoder::new();
+        enc.push_stage(id.header_guard(HeaderConflict::Override));
+        enc.encode(&dec.decode(misrouted).unwrap(), &mut out)
+            .unwrap();
+        assert!(out.starts_with(b"8=FIX.4.4\x019=29\x0135=D\x0156=VENUE\x0149=BROKER\x01"));
+    }
+
+    fn forward(stage: StampHops, raw: &[u8]) -> Vec<u8> {
+        let mut enc = Encoder::new();
+        enc.push_stage(stage).push_stage(
+            SessionId::new(b"FIX.4.4", CompId::new(b"HUB"), CompId::new(b"VENUE")).header_fields(),
+        );
+        let mut dec = Decoder::new();
+        let mut out = Vec::new();
+        enc.encode(&dec.decode(raw).unwrap(), &mut out).unwrap();
+        let body_start = out.iter().position(|&b| b == b'\x01').unwrap() + 1;
+        let body_start = body_start
+            + out[body_start..]
+                .iter()
+                .position(|&b| b == b'\x01')
+                .unwrap()
+            + 1;
+        out.truncate(out.len() - 7);
+        out.split_off(body_start)
+    }
+
+    #[test]
+    fn stamp_hops_promotes_originator() {
+        let out = forward(
+            StampHops::new(),
+            b"8=FIX.4.4\x0135=D\x0149=CLIENT\x0150=DESK7\x0156=HUB\x0134=3\x0152=20240101-12:00:00\x0111=A\x01",
+        );
+        assert_eq!(
+            out,
+            b"35=D\x0149=HUB\x0156=VENUE\x0134=3\x0152=20240101-12:00:00\x01115=CLIENT\x01116=DESK7\x01370=20240101-12:00:00\x0111=A\x01"
+        );
+    }
+
+    #[test]
+    fn stamp_hops_appends_previous_intermediary() {
+        let raw = b"8=FIX.4.4\x0135=D\x0149=HUB1\x0156=HUB\x0134=9\x0152=20240101-12:00:01\x01115=CLIENT\x01627=1\x01628=HUB0\x01630=4\x0111=A\x01";
+        let out = forward(StampHops::new(), raw);
+        assert_eq!(
+            out,
+            b"35=D\x0149=HUB\x0156=VENUE\x0134=9\x0152=20240101-12:00:01\x01115=CLIENT\x01627=2\x01628=HUB0\x01630=4\x01628=HUB1\x01629=20240101-12:00:01\x01630=9\x0111=A\x01"
+        );
+
+        let out = forward(StampHops::new().enabled(false), raw);
+        assert!(out.windows(6).any(|w| w == b"627=1\x01"));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::decimal::FixDecimal;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::tag;
+
+/// SettlCurrFxRateCalc (156): how SettlCurrFxRate (155) converts the trade
+/// currency into the settlement currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FxRateCalc {
+    /// `M` — settlement amount = amount × rate. The FIX default when 156 is
+    /// absent.
+    #[default]
+    Multiply,
+    /// `D` — settlement amount = amount ÷ rate.
+    Divide,
+}
+
+impl FxRateCalc {
+    pub fn parse(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            b"M" => Ok(Self::Multiply),
+            b"D" => Ok(Self::Divide),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Self::Multiply => b"M",
+            Self::Divide => b"D",
+        }
+    }
+}
+
+/// Settlement currency fields of a message: SettlCurrency (120),
+/// SettlCurrAmt (119), SettlCurrFxRate (155) and SettlCurrFxRateCalc (156).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settlement<'a> {
+    pub currency: &'a [u8],
+    pub amount: Option<FixDecimal>,
+    pub fx_rate: Option<FixDecimal>,
+    pub calc: FxRateCalc,
+}
+
+impl<'a> Settlement<'a> {
+    /// Read the settlement fields of `msg`, or `None` if it carries no
+    /// SettlCurrency (it settles in the trade currency).
+    ///
+    /// `InvalidValue` if an amount, rate or calc value is malformed.
+    pub fn from_message(msg: &Message<'a>) -> Result<Option<Self>, FixError> {
+        let Some(currency) = msg.find(tag::SETTL_CURRENCY) else {
+            return Ok(None);
+        };
+        let decimal = |t| msg.find(t).map(|f| FixDecimal::parse(f.value)).transpose();
+        Ok(Some(Self {
+            currency: currency.value,
+            amount: decimal(tag::SETTL_CURR_AMT)?,
+            fx_rate: decimal(tag::SETTL_CURR_FX_RATE)?,
+            calc: msg
+                .find(tag::SETTL_CURR_FX_RATE_CALC)
+                .map_or(Ok(FxRateCalc::Multiply), |f| FxRateCalc::parse(f.value))?,
+        }))
+    }
+
+    /// Convert a trade-currency `amount` into the settlement currency with
+    /// the message's rate and calc convention, rounded to `scale` digits.
+    ///
+    /// `None` without a SettlCurrFxRate, on a zero rate with
+    /// [`FxRateCalc::Divide`], or on overflow.
+    pub fn convert(&self, amount: &FixDecimal, scale: u32) -> Option<FixDecimal> {
+        let rate = self.fx_rate.as_ref()?;
+        match self.calc {
+            FxRateCalc::Multiply => Some(amount.checked_mul(rate)?.round(scale)),
+            FxRateCalc::Divide => amount.checked_div(rate, scale),
+        }
+    }
+}
+
+/// Result of comparing SettlCurrAmt with NetMoney converted at
+/// SettlCurrFxRate. Produced by [`check_settlement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementCheck {
+    /// NetMoney (118) converted into the settlement currency, rounded to the
+    /// precision of the reported SettlCurrAmt.
+    pub expected: FixDecimal,
+    /// SettlCurrAmt (119) as reported by the counterparty.
+    pub reported: FixDecimal,
+    /// `|expected − reported|`.
+    pub difference: FixDecimal,
+    /// Largest difference still considered consistent.
+    pub tolerance: FixDecimal,
+}
+
+impl SettlementCheck {
+    #[inline]
+    pub fn is_consistent(&self) -> bool {
+        self.difference <= self.tolerance
+    }
+}
+
+/// Check that SettlCurrAmt (119) equals NetMoney (118) converted with
+/// SettlCurrFxRate (155) / SettlCurrFxRateCalc (156), within `tolerance`
+/// (in settlement currency units, e.g. `0.01` to absorb one cent of
+/// rounding on the counterparty's side).
+///
+/// `Ok(None)` if the message has no SettlCurrency. `InvalidValue` if
+/// NetMoney, SettlCurrAmt or SettlCurrFxRate is missing or malformed, or the
+/// conversion overflows.
+///
+/// # Example
+/// ```ignore
+/// let tolerance = FixDecimal::parse(b"0.01")?;
+/// if let Some(check) = check_settlement(&report, tolerance)? {
+///     if !check.is_consistent() {
+///         warn!("SettlCurrAmt {} but NetMoney implies {}", check.reported, check.expected);
+///     }
+/// }
+/// ```
+pub fn check_settlement(
+    msg: &Message<'_>,
+    tolerance: FixDecimal,
+) -> Result<Option<SettlementCheck>, FixError> {
+    let Some(settl) = Settlement::from_message(msg)? else {
+        return Ok(None);
+    };
+    let net = msg.find(tag::NET_MONEY).ok_or(FixError::InvalidValue)?;
+    let net = FixDecimal::parse(net.value)?;
+    let reported = settl.amount.ok_or(FixError::InvalidValue)?;
+    let expected = settl
+        .convert(&net, reported.scale())
+        .ok_or(FixError::InvalidValue)?;
+    let difference = expected.abs_diff(&reported).ok_or(FixError::InvalidValue)?;
+    Ok(Some(SettlementCheck {
+        expected,
+        reported,
+        difference,
+        tolerance,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    fn d(s: &str) -> FixDecimal {
+        FixDecimal::parse(s.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn multiply_and_divide_conventions() {
+        let raw = fix("35=8|15=USD|118=1000|120=EUR|155=0.9|156=M|119=900.00|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let settl = Settlement::from_message(&msg).unwrap().unwrap();
+        assert_eq!(settl.currency, b"EUR");
+        assert_eq!(settl.calc, FxRateCalc::Multiply);
+        assert_eq!(settl.convert(&d("1000"), 2), Some(d("900")));
+        let check = check_settlement(&msg, FixDecimal::ZERO).unwrap().unwrap();
+        assert!(check.is_consistent(), "{check:?}");
+
+        let raw = fix("35=8|118=1000|120=JPY|155=0.0064|156=D|119=156250|");
+        let msg = dec.decode(&raw).unwrap();
+        let check = check_settlement(&msg, FixDecimal::ZERO).unwrap().unwrap();
+        assert_eq!(check.expected, d("156250"));
+        assert!(check.is_consistent());
+    }
+
+    #[test]
+    fn tolerance_absorbs_rounding() {
+        let raw = fix("35=8|118=333.33|120=GBP|155=0.79|119=263.34|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        // 333.33 × 0.79 = 263.3307 → 263.33, one penny off.
+        let strict = check_settlement(&msg, FixDecimal::ZERO).unwrap().unwrap();
+        assert_eq!(strict.difference, d("0.01"));
+        assert!(!strict.is_consistent());
+        let loose = check_settlement(&msg, d("0.01")).unwrap().unwrap();
+        assert!(loose.is_consistent());
+    }
+
+    #[test]
+    fn absent_and_malformed() {
+        let mut dec = Decoder::new();
+        let raw = fix("35=8|118=100|");
+        let msg = dec.decode(&raw).unwrap();
+        assert_eq!(check_settlement(&msg, FixDecimal::ZERO).unwrap(), None);
+
+        let raw = fix("35=8|118=100|120=EUR|155=1.1|156=X|119=110|");
+        let msg = dec.decode(&raw).unwrap();
+        assert!(matches!(
+            check_settlement(&msg, FixDecimal::ZERO),
+            Err(FixError::InvalidValue)
+        ));
+    }
+}
@@ -0,0 +1,651 @@
+//! Deterministic simulation of a [`Session`]: a virtual clock, a scripted
+//! counterparty and a transcript of everything the session did.
+//!
+//! A [`Simulator`] drives the session the way the async engine does —
+//! outbound messages go through an [`Outbox`] into a [`MemoryStore`],
+//! ResendRequests are answered from that store, timers are polled once a
+//! virtual second — but time only moves when the script says so. The same
+//! script always produces the same [`Record`]s, byte for byte, so gap,
+//! resend and timeout behaviour can be tested exhaustively and compared
+//! against golden transcripts. A [`FaultInjector`] set with
+//! [`Simulator::peer_faults`] turns the counterparty into a buggy one.
+//!
+//! # Example
+//! ```ignore
+//! let mut sim = Simulator::new(Session::initiator(id, Duration::from_secs(30)), UtcTimestamp::new(0, 0));
+//! sim.run([
+//!     Step::Connect,
+//!     Step::Receive(sim::message(b"A", &[(tag::HEART_BT_INT, b"30")])),
+//!     Step::Advance(Duration::from_secs(31)),
+//! ])?;
+//! print!("{}", sim.transcript());
+//! ```
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use crate::builder::MessageBuilder;
+use crate::decoder::{Decoder, frame_end};
+use crate::encoder::Encoder;
+use crate::error::FixError;
+use crate::fault::FaultInjector;
+use crate::owned::OwnedMessage;
+use crate::session::{Session, SessionEvent, SessionState};
+use crate::store::{MemoryStore, Outbox};
+use crate::tag::{self, Tag};
+use crate::timestamp::{TimestampPrecision, UtcTimestamp};
+
+/// How often the simulator polls the session's timers, as the engine does.
+const TICK: Duration = Duration::from_secs(1);
+
+/// One scripted action.
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// The connection comes up: [`Session::on_connect`].
+    Connect,
+    /// The counterparty sends MsgType (35) and body `msg`. The simulator
+    /// adds the header: BeginString, mirrored CompIDs, SendingTime and —
+    /// unless `msg` carries one — the counterparty's next MsgSeqNum.
+    Receive(MessageBuilder),
+    /// Move the clock forward, polling the timers every second on the way.
+    Advance(Duration),
+    /// The application sends MsgType and body `msg`: [`Session::send_app`].
+    Send(MessageBuilder),
+    /// The application logs out with an optional Text (58).
+    Logout(Option<Vec<u8>>),
+    /// The connection drops: [`Session::on_disconnect`].
+    Disconnect,
+}
+
+/// A message body for [`Step::Receive`] or [`Step::Send`]: MsgType and
+/// `fields`, in order.
+pub fn message(msg_type: &[u8], fields: &[(Tag, &[u8])]) -> MessageBuilder {
+    let mut b = MessageBuilder::new();
+    b.push(tag::MSG_TYPE, msg_type);
+    for (t, v) in fields {
+        b.push(*t, v);
+    }
+    b
+}
+
+/// One line of a [`Simulator`] transcript, stamped with the virtual time
+/// elapsed since the simulation started.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Record {
+    /// The session wrote `msg` to the wire (as encoded and journaled).
+    Sent { at: Duration, msg: OwnedMessage },
+    /// The counterparty's `msg` arrived.
+    Received { at: Duration, msg: OwnedMessage },
+    /// The counterparty's `msg` arrived with a wrong BodyLength or
+    /// CheckSum and was dropped unseen by the session.
+    Garbled { at: Duration, msg: OwnedMessage },
+    /// What the session made of the last message received, or
+    /// [`SessionEvent::Disconnect`] from a timer.
+    Event { at: Duration, event: SessionEvent },
+    /// The session moved from one state to another.
+    State {
+        at: Duration,
+        from: SessionState,
+        to: SessionState,
+    },
+}
+
+impl Record {
+    pub fn at(&self) -> Duration {
+        match self {
+            Self::Sent { at, .. }
+            | Self::Received { at, .. }
+            | Self::Garbled { at, .. }
+            | Self::Event { at, .. }
+            | Self::State { at, .. } => *at,
+        }
+    }
+}
+
+/// `  12.000 > 8=FIX.4.4|9=..|35=0|...` — `>` sent, `<` received, `!`
+/// garbled, `=` event, `:` state change.
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let at = self.at();
+        write!(f, "{:>4}.{:03} ", at.as_secs(), at.subsec_millis())?;
+        match self {
+            Self::Sent { msg, .. } => write!(f, "> {msg}"),
+            Self::Received { msg, .. } => write!(f, "< {msg}"),
+            Self::Garbled { msg, .. } => write!(f, "! {msg}"),
+            Self::Event { event, .. } => write!(f, "= {event:?}"),
+            Self::State { from, to, .. } => write!(f, ": {from:?} -> {to:?}"),
+        }
+    }
+}
+
+/// A [`Session`] on a virtual clock, fed by a script. See the
+/// [module docs](self).
+pub struct Simulator {
+    session: Session,
+    store: MemoryStore,
+    out: Outbox,
+    peer: Encoder,
+    decoder: Decoder,
+    start: UtcTimestamp,
+    elapsed: Duration,
+    next_peer_seq: u64,
+    faults: Option<FaultInjector>,
+    wire: Vec<u8>,
+    /// Counterparty bytes out of `faults`, reused between batches.
+    faulted: Vec<u8>,
+    records: Vec<Record>,
+}
+
+impl Simulator {
+    /// Simulate `session` with the clock starting at `start`.
+    pub fn new(session: Session, start: UtcTimestamp) -> Self {
+        Self {
+            session,
+            store: MemoryStore::new(),
+            out: Outbox::new(),
+            peer: Encoder::new(),
+            decoder: Decoder::new(),
+            start,
+            elapsed: Duration::ZERO,
+            next_peer_seq: 1,
+            faults: None,
+            wire: Vec::new(),
+            faulted: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+
+    /// Pass every counterparty message through `faults` before the session
+    /// sees it; delayed messages arrive as the clock advances.
+    pub fn peer_faults(mut self, faults: FaultInjector) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn session_mut(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    /// The outbound journal, as used to answer ResendRequests.
+    pub fn store(&self) -> &MemoryStore {
+        &self.store
+    }
+
+    /// The virtual time now.
+    pub fn now(&self) -> UtcTimestamp {
+        let nanos = self.start.nanos + self.elapsed.subsec_nanos();
+        UtcTimestamp::new(
+            self.start.secs + self.elapsed.as_secs() as i64 + i64::from(nanos / 1_000_000_000),
+            nanos % 1_000_000_000,
+        )
+    }
+
+    /// Virtual time since the simulation started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The MsgSeqNum the counterparty puts on its next message.
+    pub fn peer_seq(&self) -> u64 {
+        self.next_peer_seq
+    }
+
+    /// Make the counterparty skip ahead (a gap) or fall back (too low).
+    pub fn set_peer_seq(&mut self, next: u64) {
+        self.next_peer_seq = next;
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Every record, one per line.
+    pub fn transcript(&self) -> String {
+        self.records.iter().map(|r| format!("{r}\n")).collect()
+    }
+
+    /// The messages the session sent, in order.
+    pub fn sent(&self) -> impl Iterator<Item = &OwnedMessage> {
+        self.records.iter().filter_map(|r| match r {
+            Record::Sent { msg, .. } => Some(msg),
+            _ => None,
+        })
+    }
+
+    /// The events the session reported, in order.
+    pub fn events(&self) -> impl Iterator<Item = SessionEvent> + '_ {
+        self.records.iter().filter_map(|r| match r {
+            Record::Event { event, .. } => Some(*event),
+            _ => None,
+        })
+    }
+
+    /// Play `script` in order.
+    ///
+    /// # Errors
+    /// The first step's error; the steps after it are not played.
+    pub fn run(&mut self, script: impl IntoIterator<Item = Step>) -> io::Result<()> {
+        script.into_iter().try_for_each(|step| self.step(step))
+    }
+
+    /// Play one step.
+    ///
+    /// # Errors
+    /// A session error (e.g. sending while not logged on) wrapped as
+    /// `io::ErrorKind::InvalidData`.
+    pub fn step(&mut self, step: Step) -> io::Result<()> {
+        let before = self.session.state();
+        let now = self.now();
+        match step {
+            Step::Connect => self
+                .session
+                .on_connect(now, &mut |m| self.out.queue(m))
+                .map_err(invalid_data)?,
+            Step::Receive(msg) => {
+                let raw = self.peer_message(&msg, now).map_err(invalid_data)?;
+                let Some(faults) = &mut self.faults else {
+                    return self.receive(&raw);
+                };
+                let mut wire = std::mem::take(&mut self.faulted);
+                faults.apply(&raw, now, &mut wire).map_err(invalid_data)?;
+                return self.receive_all(wire);
+            }
+            Step::Advance(by) => return self.advance(by),
+            Step::Send(msg) => self
+                .session
+                .send_app(&msg.as_message(), now, &mut |m| self.out.queue(m))
+                .map_err(invalid_data)?,
+            Step::Logout(text) => self
+                .session
+                .logout(text.as_deref(), now, &mut |m| self.out.queue(m))
+                .map_err(invalid_data)?,
+            Step::Disconnect => self.session.on_disconnect(),
+        }
+        self.settle(before)
+    }
+
+    /// Deliver raw counterparty bytes, as [`Step::Receive`] does after
+    /// adding the header. A message whose BodyLength or CheckSum is wrong
+    /// is recorded as [`Record::Garbled`] and otherwise ignored, as the
+    /// FIX session protocol requires.
+    ///
+    /// # Errors
+    /// A decode or session error wrapped as `io::ErrorKind::InvalidData`.
+    pub fn receive(&mut self, raw: &[u8]) -> io::Result<()> {
+        let before = self.session.state();
+        let now = self.now();
+        let at = self.elapsed;
+        let msg = self.decoder.decode(raw).map_err(invalid_data)?;
+        if msg.validate_body_length().is_err() || msg.validate_checksum().is_err() {
+            self.records.push(Record::Garbled {
+                at,
+                msg: OwnedMessage::from(&msg),
+            });
+            return Ok(());
+        }
+        self.records.push(Record::Received {
+            at,
+            msg: OwnedMessage::from(&msg),
+        });
+        let event = self
+            .session
+            .on_message(&msg, now, &mut |m| self.out.queue(m))
+            .map_err(invalid_data)?;
+        self.records.push(Record::Event { at, event });
+        if let SessionEvent::Resend { begin, end } = event {
+            self.session
+                .resend_from(begin, end, &mut self.store, now, &mut |m| self.out.queue(m))?;
+        }
+        self.settle(before)
+    }
+
+    /// Move the clock forward by `by` in one-second ticks, polling the
+    /// session after each (at least once, for a zero `by`).
+    fn advance(&mut self, by: Duration) -> io::Result<()> {
+        let mut left = by;
+        loop {
+            let tick = left.min(TICK);
+            left -= tick;
+            self.elapsed += tick;
+            let now = self.now();
+            if let Some(faults) = &mut self.faults {
+                let mut wire = std::mem::take(&mut self.faulted);
+                faults.release(now, &mut wire);
+                self.receive_all(wire)?;
+            }
+            let before = self.session.state();
+            let ended = self
+                .session
+                .poll(now, &mut |m| self.out.queue(m))
+                .map_err(invalid_data)?;
+            if let Some(reason) = ended {
+                self.records.push(Record::Event {
+                    at: self.elapsed,
+                    event: SessionEvent::Disconnect(reason),
+                });
+            }
+            self.settle(before)?;
+            if left.is_zero() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// [`receive`](Self::receive) each message in `wire`, then keep its
+    /// allocation for the next batch.
+    fn receive_all(&mut self, mut wire: Vec<u8>) -> io::Result<()> {
+        let mut rest = &wire[..];
+        let result = loop {
+            let len = match frame_end(rest) {
+                Ok(Some(len)) => len,
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(invalid_data(e)),
+            };
+            if let Err(e) = self.receive(&rest[..len]) {
+                break Err(e);
+            }
+            rest = &rest[len..];
+        };
+        wire.clear();
+        self.faulted = wire;
+        result
+    }
+
+    /// Encode counterparty message `msg` with its session header.
+    fn peer_message(
+        &mut self,
+        msg: &MessageBuilder,
+        now: UtcTimestamp,
+    ) -> Result<Vec<u8>, FixError> {
+        let body = msg.as_message();
+        let msg_type = body.find(tag::MSG_TYPE).ok_or(FixError::InvalidValue)?;
+        let seq = match body.find(tag::MSG_SEQ_NUM) {
+            Some(f) => f.as_u64()?,
+            None => self.next_peer_seq,
+        };
+        self.next_peer_seq = seq + 1;
+        let id = self.session.id();
+        let (stamp, len) = now.format(TimestampPrecision::Millis);
+        let mut b = MessageBuilder::new();
+        b.push(tag::BEGIN_STRING, &id.begin_string)
+            .push(tag::MSG_TYPE, msg_type.value)
+            .push(tag::SENDER_COMP_ID, &id.target.comp_id)
+            .push(tag::TARGET_COMP_ID, &id.sender.comp_id)
+            .push_u64(tag::MSG_SEQ_NUM, seq)
+            .push(tag::SENDING_TIME, &stamp[..len]);
+        for f in body.fields() {
+            if !matches!(f.tag, tag::MSG_TYPE | tag::MSG_SEQ_NUM) {
+                b.push(f.tag, f.value);
+            }
+        }
+        let mut raw = Vec::new();
+        self.peer.encode(&b.as_message(), &mut raw)?;
+        Ok(raw)
+    }
+
+    /// Journal and record what the session queued, then its state change.
+    fn settle(&mut self, before: SessionState) -> io::Result<()> {
+        let at = self.elapsed;
+        self.wire.clear();
+        self.wire
+            .extend_from_slice(self.out.commit(&mut self.store)?);
+        self.out.clear();
+        for msg in self.decoder.decode_all(&self.wire).map_err(invalid_data)? {
+            self.records.push(Record::Sent {
+                at,
+                msg: OwnedMessage::from(&msg),
+            });
+        }
+        let after = self.session.state();
+        if after != before {
+            self.records.push(Record::State {
+                at,
+                from: before,
+                to: after,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn invalid_data(e: FixError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fault::Fault;
+    use crate::session::{CompId, DisconnectReason, SessionId};
+
+    fn initiator() -> Simulator {
+        let id = SessionId::new(b"FIX.4.4", CompId::new(b"BROKER"), CompId::new(b"VENUE"));
+        let session = Session::initiator(id, Duration::from_secs(30));
+        Simulator::new(session, UtcTimestamp::new(1_709_214_306, 0))
+    }
+
+    fn logged_on() -> Simulator {
+        log_on(initiator())
+    }
+
+    fn log_on(mut sim: Simulator) -> Simulator {
+        sim.run([
+            Step::Connect,
+            Step::Receive(message(b"A", &[(tag::HEART_BT_INT, b"30")])),
+        ])
+        .unwrap();
+        sim
+    }
+
+    fn sent_types(sim: &Simulator) -> Vec<Vec<u8>> {
+        sim.sent()
+            .map(|m| m.find(tag::MSG_TYPE).unwrap().value.to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn transcript_is_deterministic() {
+        let script = || {
+            [
+                Step::Connect,
+                Step::Receive(message(b"A", &[(tag::HEART_BT_INT, b"30")])),
+                Step::Send(message(b"D", &[(tag::CL_ORD_ID, b"1")])),
+                Step::Advance(Duration::from_secs(31)),
+            ]
+        };
+        let mut a = initiator();
+        let mut b = initiator();
+        a.run(script()).unwrap();
+        b.run(script()).unwrap();
+        assert_eq!(a.transcript(), b.transcript());
+
+        let lines: Vec<_> = a.transcript().lines().map(str::to_owned).collect();
+        assert_eq!(
+            lines[0],
+            "   0.000 > 8=FIX.4.4|9=66|35=A|49=BROKER|56=VENUE|34=1|52=20240229-13:45:06.000|98=0|108=30|10=066|"
+        );
+        assert_eq!(lines[1], "   0.000 : Disconnected -> LogonSent");
+        assert_eq!(lines[4], "   0.000 : LogonSent -> Active");
+        assert_eq!(a.events().collect::<Vec<_>>(), [SessionEvent::LoggedOn]);
+        assert_eq!(sent_types(&a), [&b"A"[..], b"D", b"0"]);
+        assert!(lines[lines.len() - 1].starts_with("  30.000 > "));
+    }
+
+    #[test]
+    fn gap_is_filled_by_the_counterparty_resend() {
+        let mut sim = logged_on();
+        sim.set_peer_seq(4);
+        sim.run([
+            Step::Receive(message(b"D", &[(tag::CL_ORD_ID, b"4")])),
+            Step::Receive(message(
+                b"4",
+                &[
+                    (tag::MSG_SEQ_NUM, b"2"),
+                    (tag::POSS_DUP_FLAG, b"Y"),
+                    (tag::GAP_FILL_FLAG, b"Y"),
+                    (tag::NEW_SEQ_NO, b"4"),
+                ],
+            )),
+        ])
+        .unwrap();
+        sim.set_peer_seq(4);
+        sim.step(Step::Receive(message(
+            b"D",
+            &[(tag::CL_ORD_ID, b"4"), (tag::POSS_DUP_FLAG, b"Y")],
+        )))
+        .unwrap();
+        let events: Vec<_> = sim.events().collect();
+        assert_eq!(
+            events[1..],
+            [
+                SessionEvent::Gap { from: 2, to: 3 },
+                SessionEvent::Handled,
+                SessionEvent::Deliver,
+            ]
+        );
+        let resend = sim.sent().last().unwrap();
+        assert_eq!(resend.find(tag::MSG_TYPE).unwrap().value, b"2");
+        assert_eq!(resend.find(tag::BEGIN_SEQ_NO).unwrap().value, b"2");
+        // EndSeqNo 0: everything from 2 on.
+        assert_eq!(resend.find(tag::END_SEQ_NO).unwrap().value, b"0");
+    }
+
+    #[test]
+    fn resend_request_replays_the_journal() {
+        let mut sim = logged_on();
+        sim.run([
+            Step::Send(message(b"D", &[(tag::CL_ORD_ID, b"1")])),
+            Step::Advance(Duration::from_secs(30)),
+            Step::Send(message(b"D", &[(tag::CL_ORD_ID, b"2")])),
+            Step::Receive(message(
+                b"2",
+                &[(tag::BEGIN_SEQ_NO, b"2"), (tag::END_SEQ_NO, b"0")],
+            )),
+        ])
+        .unwrap();
+        // Logon, two orders with a Heartbeat between them, then the resend:
+        // both orders as PossDup and a GapFill over the Heartbeat.
+        assert_eq!(
+            sent_types(&sim),
+            [&b"A"[..], b"D", b"0", b"D", b"D", b"4", b"D"]
+        );
+        let resent: Vec<_> = sim.sent().skip(4).collect();
+        assert_eq!(resent[0].find(tag::POSS_DUP_FLAG).unwrap().value, b"Y");
+        assert_eq!(resent[0].find(tag::CL_ORD_ID).unwrap().value, b"1");
+        assert_eq!(resent[1].find(tag::NEW_SEQ_NO).unwrap().value, b"4");
+        assert_eq!(resent[2].find(tag::MSG_SEQ_NUM).unwrap().value, b"4");
+        assert_eq!(sim.store().len(), 4);
+    }
+
+    #[test]
+    fn silent_counterparty_times_out() {
+        let mut sim = logged_on();
+        sim.step(Step::Advance(Duration::from_secs(120))).unwrap();
+        let test_request = sim
+            .sent()
+            .position(|m| m.find(tag::MSG_TYPE).unwrap().value == b"1")
+            .unwrap();
+        assert!(test_request > 0);
+        assert_eq!(
+            sim.events().last(),
+            Some(SessionEvent::Disconnect(DisconnectReason::HeartbeatTimeout))
+        );
+        assert_eq!(sim.session().state(), SessionState::Disconnected);
+        let disconnected = sim
+            .records()
+            .iter()
+            .find(|r| {
+                matches!(
+                    r,
+                    Record::State {
+                        to: SessionState::Disconnected,
+                        ..
+                    }
+                )
+            })
+            .unwrap();
+        assert!(disconnected.at() < Duration::from_secs(120));
+
+        // Reconnect on the same sequence numbers.
+        sim.step(Step::Connect).unwrap();
+        assert_eq!(sim.session().state(), SessionState::LogonSent);
+    }
+
+    #[test]
+    fn logon_timeout_and_sending_before_logon() {
+        let mut sim = initiator();
+        sim.step(Step::Connect).unwrap();
+        let err = sim
+            .step(Step::Send(message(b"D", &[(tag::CL_ORD_ID, b"1")])))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        sim.step(Step::Advance(Duration::from_secs(60))).unwrap();
+        assert_eq!(
+            sim.events().collect::<Vec<_>>(),
+            [SessionEvent::Disconnect(DisconnectReason::LogonTimeout)]
+        );
+    }
+
+    #[test]
+    fn dropped_and_garbled_peer_messages_open_a_gap() {
+        let faults = FaultInjector::new()
+            .fault(Fault::DropEvery(2))
+            .fault(Fault::CorruptChecksum(3));
+        let mut sim = log_on(initiator().peer_faults(faults));
+        // Logon is the 1st message: 2 and 4 are dropped, 3 is garbled.
+        sim.run([
+            Step::Receive(message(b"D", &[(tag::CL_ORD_ID, b"2")])),
+            Step::Receive(message(b"D", &[(tag::CL_ORD_ID, b"3")])),
+            Step::Receive(message(b"D", &[(tag::CL_ORD_ID, b"4")])),
+            Step::Receive(message(b"D", &[(tag::CL_ORD_ID, b"5")])),
+        ])
+        .unwrap();
+        let garbled: Vec<_> = sim
+            .records()
+            .iter()
+            .filter_map(|r| match r {
+                Record::Garbled { msg, .. } => Some(msg.find(tag::CL_ORD_ID).unwrap().value),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(garbled, [b"3"]);
+        assert_eq!(
+            sim.events().collect::<Vec<_>>(),
+            [SessionEvent::LoggedOn, SessionEvent::Gap { from: 2, to: 4 }]
+        );
+        assert!(sim.transcript().contains("   0.000 ! 8=FIX.4.4|"));
+    }
+
+    #[test]
+    fn delayed_peer_heartbeat_draws_a_test_request() {
+        let faults = FaultInjector::new().fault(Fault::DelayHeartbeat(Duration::from_secs(20)));
+        let mut sim = log_on(initiator().peer_faults(faults));
+        sim.run([
+            Step::Advance(Duration::from_secs(25)),
+            Step::Receive(message(b"0", &[])),
+            Step::Advance(Duration::from_secs(25)),
+        ])
+        .unwrap();
+        let sent_at = |msg_type: &[u8]| {
+            sim.records().iter().find_map(|r| match r {
+                Record::Sent { at, msg } if msg.find(tag::MSG_TYPE).unwrap().value == msg_type => {
+                    Some(*at)
+                }
+                _ => None,
+            })
+        };
+        assert_eq!(sent_at(b"1"), Some(Duration::from_secs(36)));
+        let heartbeat = sim
+            .records()
+            .iter()
+            .rfind(|r| matches!(r, Record::Received { .. }))
+            .unwrap();
+        assert_eq!(heartbeat.at(), Duration::from_secs(45));
+        assert_eq!(sim.session().state(), SessionState::Active);
+    }
+}
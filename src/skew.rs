@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use crate::message::Message;
+use crate::tag;
+use crate::timestamp::UtcTimestamp;
+
+/// Upper bounds (exclusive) of the [`SkewStats::histogram`] buckets, by
+/// absolute drift. The last bucket holds everything from 10 s up.
+pub const SKEW_BUCKET_BOUNDS: [Duration; 5] = [
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+    Duration::from_secs(10),
+];
+
+/// Signed drift in nanoseconds: receive time minus SendingTime (52).
+/// Negative when the counterparty's clock is ahead of ours.
+pub type DriftNanos = i64;
+
+/// Running distribution of SendingTime drift for one session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SkewStats {
+    /// Messages observed with a parseable SendingTime.
+    pub count: u64,
+    pub min: DriftNanos,
+    pub max: DriftNanos,
+    /// Most recent drift.
+    pub last: DriftNanos,
+    /// Sum of every drift, for [`mean`](Self::mean).
+    pub sum: i128,
+    /// Message counts per [`SKEW_BUCKET_BOUNDS`] bucket of absolute drift.
+    pub histogram: [u64; SKEW_BUCKET_BOUNDS.len() + 1],
+}
+
+impl SkewStats {
+    /// Mean drift, or `None` before the first observation.
+    pub fn mean(&self) -> Option<DriftNanos> {
+        (self.count > 0).then(|| (self.sum / self.count as i128) as DriftNanos)
+    }
+
+    fn record(&mut self, drift: DriftNanos) {
+        if self.count == 0 {
+            self.min = drift;
+            self.max = drift;
+        } else {
+            self.min = self.min.min(drift);
+            self.max = self.max.max(drift);
+        }
+        self.count += 1;
+        self.last = drift;
+        self.sum += drift as i128;
+        let abs = Duration::from_nanos(drift.unsigned_abs());
+        let bucket = SKEW_BUCKET_BOUNDS
+            .iter()
+            .position(|&b| abs < b)
+            .unwrap_or(SKEW_BUCKET_BOUNDS.len());
+        self.histogram[bucket] += 1;
+    }
+}
+
+/// Raised by a [`SkewMonitor`] when drift first exceeds its threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkewAlert {
+    pub drift: DriftNanos,
+    pub sending_time: UtcTimestamp,
+    pub received_at: UtcTimestamp,
+    /// MsgSeqNum (34) of the offending message, if present.
+    pub seq_num: Option<u64>,
+}
+
+/// Tracks receive time minus SendingTime (52) per session, catching
+/// counterparty clock problems before they turn into
+/// SendingTimeAccuracyProblem rejects.
+///
+/// `on_alert` is called when the absolute drift goes above the threshold,
+/// once per excursion: it fires again only after a message has come back
+/// within the threshold.
+///
+/// # Example
+/// ```ignore
+/// let mut skew = SkewMonitor::new(Duration::from_millis(500), |a: &SkewAlert| {
+///     warn!("counterparty clock off by {} ns", a.drift);
+/// });
+/// skew.observe(&msg, UtcTimestamp::now());
+/// metrics.gauge("skew_mean_ns", skew.stats().mean().unwrap_or(0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkewMonitor<F> {
+    threshold: Duration,
+    on_alert: F,
+    stats: SkewStats,
+    breached: bool,
+}
+
+impl<F: FnMut(&SkewAlert)> SkewMonitor<F> {
+    pub fn new(threshold: Duration, on_alert: F) -> Self {
+        Self {
+            threshold,
+            on_alert,
+            stats: SkewStats::default(),
+            breached: false,
+        }
+    }
+
+    #[inline]
+    pub fn stats(&self) -> &SkewStats {
+        &self.stats
+    }
+
+    /// True while the latest observation is above the threshold.
+    #[inline]
+    pub fn is_breached(&self) -> bool {
+        self.breached
+    }
+
+    /// Forget every observation.
+    pub fn reset(&mut self) {
+        self.stats = SkewStats::default();
+        self.breached = false;
+    }
+
+    /// Record the drift of `msg` received at `received_at`. Returns the
+    /// drift, or `None` if SendingTime is absent or not a UTCTimestamp.
+    pub fn observe(&mut self, msg: &Message<'_>, received_at: UtcTimestamp) -> Option<DriftNanos> {
+        let (sending_time, _) = UtcTimestamp::parse(msg.find(tag::SENDING_TIME)?.value).ok()?;
+        let drift = drift_nanos(received_at, sending_time);
+        self.stats.record(drift);
+
+        let over = Duration::from_nanos(drift.unsigned_abs()) > self.threshold;
+        if over && !self.breached {
+            (self.on_alert)(&SkewAlert {
+                drift,
+                sending_time,
+                received_at,
+                seq_num: msg.find(tag::MSG_SEQ_NUM).and_then(|f| f.as_u64().ok()),
+            });
+        }
+        self.breached = over;
+        Some(drift)
+    }
+}
+
+pub(crate) fn drift_nanos(a: UtcTimestamp, b: UtcTimestamp) -> DriftNanos {
+    let n = (a.secs as i128 - b.secs as i128) * 1_000_000_000 + a.nanos as i128 - b.nanos as i128;
+    n.clamp(i64::MIN as i128, i64::MAX as i128) as DriftNanos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    const SENT: &[u8] = b"35=0\x0134=7\x0152=20240229-13:45:06.000\x01";
+    const SENT_SECS: i64 = 1_709_214_306;
+
+    #[test]
+    fn stats_track_signed_drift() {
+        let mut m = SkewMonitor::new(Duration::from_secs(1), |_: &SkewAlert| {});
+        let mut dec = Decoder::new();
+        let msg = dec.decode(SENT).unwrap();
+        assert_eq!(
+            m.observe(&msg, UtcTimestamp::new(SENT_SECS, 5_000_000)),
+            Some(5_000_000)
+        );
+        assert_eq!(
+            m.observe(&msg, UtcTimestamp::new(SENT_SECS - 1, 500_000_000)),
+            Some(-500_000_000)
+        );
+        let s = m.stats();
+        assert_eq!(s.count, 2);
+        assert_eq!(
+            (s.min, s.max, s.last),
+            (-500_000_000, 5_000_000, -500_000_000)
+        );
+        assert_eq!(s.mean(), Some(-247_500_000));
+        assert_eq!(s.histogram, [0, 1, 0, 1, 0, 0]);
+
+        let msg = dec.decode(b"35=0\x01").unwrap();
+        assert_eq!(m.observe(&msg, UtcTimestamp::now()), None);
+        assert_eq!(m.stats().count, 2);
+    }
+
+    #[test]
+    fn alert_fires_once_per_excursion() {
+        let mut alerts = Vec::new();
+        let mut m = SkewMonitor::new(Duration::from_millis(100), |a: &SkewAlert| {
+            alerts.push((a.drift, a.seq_num))
+        });
+        let mut dec = Decoder::new();
+        let msg = dec.decode(SENT).unwrap();
+        let late = UtcTimestamp::new(SENT_SECS, 200_000_000);
+        let ok = UtcTimestamp::new(SENT_SECS, 50_000_000);
+        m.observe(&msg, late);
+        m.observe(&msg, late);
+        assert!(m.is_breached());
+        m.observe(&msg, ok);
+        assert!(!m.is_breached());
+        m.observe(&msg, late);
+        assert_eq!(alerts, vec![(200_000_000, Some(7)), (200_000_000, Some(7))]);
+    }
+}
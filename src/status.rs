@@ -0,0 +1,457 @@
+use crate::error::FixError;
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// TradSesStatus (340) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TradSesStatus {
+    Unknown,
+    Halted,
+    Open,
+    Closed,
+    PreOpen,
+    PreClose,
+    /// The TradingSessionStatusRequest (35=g) is rejected; see
+    /// TradSesStatusRejReason (567).
+    RequestRejected,
+}
+
+impl TradSesStatus {
+    /// The character carried in tag 340.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Unknown => b'0',
+            Self::Halted => b'1',
+            Self::Open => b'2',
+            Self::Closed => b'3',
+            Self::PreOpen => b'4',
+            Self::PreClose => b'5',
+            Self::RequestRejected => b'6',
+        }
+    }
+
+    /// The status for a tag 340 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"0" => Some(Self::Unknown),
+            b"1" => Some(Self::Halted),
+            b"2" => Some(Self::Open),
+            b"3" => Some(Self::Closed),
+            b"4" => Some(Self::PreOpen),
+            b"5" => Some(Self::PreClose),
+            b"6" => Some(Self::RequestRejected),
+            _ => None,
+        }
+    }
+}
+
+/// SecurityTradingStatus (326) values. Unlike most enumerated fields the
+/// value is an integer of one or two digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecurityTradingStatus {
+    OpeningDelay,
+    TradingHalt,
+    Resume,
+    NoOpenNoResume,
+    PriceIndication,
+    TradingRangeIndication,
+    MarketImbalanceBuy,
+    MarketImbalanceSell,
+    MarketOnCloseImbalanceBuy,
+    MarketOnCloseImbalanceSell,
+    NoMarketImbalance,
+    NoMarketOnCloseImbalance,
+    ItsPreOpening,
+    NewPriceIndication,
+    TradeDisseminationTime,
+    ReadyToTrade,
+    NotAvailableForTrading,
+    NotTradedOnThisMarket,
+    UnknownOrInvalid,
+    PreOpen,
+    OpeningRotation,
+    FastMarket,
+}
+
+impl SecurityTradingStatus {
+    /// The tag 326 value.
+    pub fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::OpeningDelay => b"1",
+            Self::TradingHalt => b"2",
+            Self::Resume => b"3",
+            Self::NoOpenNoResume => b"4",
+            Self::PriceIndication => b"5",
+            Self::TradingRangeIndication => b"6",
+            Self::MarketImbalanceBuy => b"7",
+            Self::MarketImbalanceSell => b"8",
+            Self::MarketOnCloseImbalanceBuy => b"9",
+            Self::MarketOnCloseImbalanceSell => b"10",
+            Self::NoMarketImbalance => b"12",
+            Self::NoMarketOnCloseImbalance => b"13",
+            Self::ItsPreOpening => b"14",
+            Self::NewPriceIndication => b"15",
+            Self::TradeDisseminationTime => b"16",
+            Self::ReadyToTrade => b"17",
+            Self::NotAvailableForTrading => b"18",
+            Self::NotTradedOnThisMarket => b"19",
+            Self::UnknownOrInvalid => b"20",
+            Self::PreOpen => b"21",
+            Self::OpeningRotation => b"22",
+            Self::FastMarket => b"23",
+        }
+    }
+
+    /// The status for a tag 326 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"1" => Some(Self::OpeningDelay),
+            b"2" => Some(Self::TradingHalt),
+            b"3" => Some(Self::Resume),
+            b"4" => Some(Self::NoOpenNoResume),
+            b"5" => Some(Self::PriceIndication),
+            b"6" => Some(Self::TradingRangeIndication),
+            b"7" => Some(Self::MarketImbalanceBuy),
+            b"8" => Some(Self::MarketImbalanceSell),
+            b"9" => Some(Self::MarketOnCloseImbalanceBuy),
+            b"10" => Some(Self::MarketOnCloseImbalanceSell),
+            b"12" => Some(Self::NoMarketImbalance),
+            b"13" => Some(Self::NoMarketOnCloseImbalance),
+            b"14" => Some(Self::ItsPreOpening),
+            b"15" => Some(Self::NewPriceIndication),
+            b"16" => Some(Self::TradeDisseminationTime),
+            b"17" => Some(Self::ReadyToTrade),
+            b"18" => Some(Self::NotAvailableForTrading),
+            b"19" => Some(Self::NotTradedOnThisMarket),
+            b"20" => Some(Self::UnknownOrInvalid),
+            b"21" => Some(Self::PreOpen),
+            b"22" => Some(Self::OpeningRotation),
+            b"23" => Some(Self::FastMarket),
+            _ => None,
+        }
+    }
+
+    /// Whether the security cannot currently be traded: an opening delay,
+    /// a halt, no open/no resume, or not available for trading.
+    pub fn is_halted(self) -> bool {
+        matches!(
+            self,
+            Self::OpeningDelay
+                | Self::TradingHalt
+                | Self::NoOpenNoResume
+                | Self::NotAvailableForTrading
+        )
+    }
+}
+
+/// HaltReason (327) values, as defined up to FIX 4.4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HaltReason {
+    NewsDissemination,
+    OrderInflux,
+    OrderImbalance,
+    AdditionalInformation,
+    NewsPending,
+    EquipmentChangeover,
+}
+
+impl HaltReason {
+    /// The character carried in tag 327.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::NewsDissemination => b'D',
+            Self::OrderInflux => b'E',
+            Self::OrderImbalance => b'I',
+            Self::AdditionalInformation => b'M',
+            Self::NewsPending => b'P',
+            Self::EquipmentChangeover => b'X',
+        }
+    }
+
+    /// The reason for a tag 327 value, or `None` if `code` is not defined.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"D" => Some(Self::NewsDissemination),
+            b"E" => Some(Self::OrderInflux),
+            b"I" => Some(Self::OrderImbalance),
+            b"M" => Some(Self::AdditionalInformation),
+            b"P" => Some(Self::NewsPending),
+            b"X" => Some(Self::EquipmentChangeover),
+            _ => None,
+        }
+    }
+}
+
+fn value<'a>(msg: &Message<'a>, tag: Tag) -> Option<&'a [u8]> {
+    msg.find(tag).map(|f| f.value)
+}
+
+fn flag(msg: &Message<'_>, tag: Tag) -> bool {
+    value(msg, tag) == Some(b"Y")
+}
+
+fn expect_type(msg: &Message<'_>, msg_type: &[u8]) -> Result<(), FixError> {
+    match value(msg, tag::MSG_TYPE) {
+        Some(t) if t == msg_type => Ok(()),
+        _ => Err(FixError::InvalidValue),
+    }
+}
+
+/// Read access to a TradingSessionStatus (35=h).
+///
+/// # Example
+/// ```ignore
+/// let status = TradingSessionStatus::new(&msg)?;
+/// if status.status() == Some(TradSesStatus::Halted) {
+///     risk.halt_session(status.trading_session_id());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TradingSessionStatus<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> TradingSessionStatus<'a> {
+    /// `InvalidValue` if `msg` is not a TradingSessionStatus.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"h")?;
+        Ok(Self { msg })
+    }
+
+    /// TradSesReqID (335) of the request answered; absent on unsolicited
+    /// updates.
+    pub fn trad_ses_req_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_REQ_ID)
+    }
+
+    pub fn trading_session_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRADING_SESSION_ID)
+    }
+
+    pub fn trading_session_sub_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRADING_SESSION_SUB_ID)
+    }
+
+    /// TradSesStatus (340), `None` if absent or not a known status.
+    pub fn status(&self) -> Option<TradSesStatus> {
+        value(self.msg, tag::TRAD_SES_STATUS).and_then(TradSesStatus::from_code)
+    }
+
+    /// TradSesStatusRejReason (567), when the status is
+    /// [`TradSesStatus::RequestRejected`].
+    pub fn rej_reason(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_STATUS_REJ_REASON)
+    }
+
+    /// TradSesMethod (338): `1` electronic, `2` open outcry, `3` two party.
+    pub fn method(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_METHOD)
+    }
+
+    /// TradSesMode (339): `1` testing, `2` simulated, `3` production.
+    pub fn mode(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_MODE)
+    }
+
+    /// UnsolicitedIndicator (325): `true` if not sent in answer to a
+    /// request.
+    pub fn is_unsolicited(&self) -> bool {
+        flag(self.msg, tag::UNSOLICITED_INDICATOR)
+    }
+
+    pub fn start_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_START_TIME)
+    }
+
+    pub fn open_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_OPEN_TIME)
+    }
+
+    pub fn pre_close_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_PRE_CLOSE_TIME)
+    }
+
+    pub fn close_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_CLOSE_TIME)
+    }
+
+    pub fn end_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRAD_SES_END_TIME)
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+/// Read access to a SecurityStatus (35=f).
+///
+/// # Example
+/// ```ignore
+/// let status = SecurityStatus::new(&msg)?;
+/// if status.is_halted() {
+///     risk.block(status.symbol(), status.halt_reason());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SecurityStatus<'a> {
+    msg: &'a Message<'a>,
+}
+
+impl<'a> SecurityStatus<'a> {
+    /// `InvalidValue` if `msg` is not a SecurityStatus.
+    pub fn new(msg: &'a Message<'a>) -> Result<Self, FixError> {
+        expect_type(msg, b"f")?;
+        Ok(Self { msg })
+    }
+
+    /// SecurityStatusReqID (324) of the request answered; absent on
+    /// unsolicited updates.
+    pub fn req_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SECURITY_STATUS_REQ_ID)
+    }
+
+    pub fn symbol(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SYMBOL)
+    }
+
+    pub fn security_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SECURITY_ID)
+    }
+
+    pub fn trading_session_id(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRADING_SESSION_ID)
+    }
+
+    /// SecurityTradingStatus (326), `None` if absent or not a known status.
+    pub fn trading_status(&self) -> Option<SecurityTradingStatus> {
+        value(self.msg, tag::SECURITY_TRADING_STATUS).and_then(SecurityTradingStatus::from_code)
+    }
+
+    /// Whether the security cannot currently be traded; see
+    /// [`SecurityTradingStatus::is_halted`]. A message without a known
+    /// SecurityTradingStatus is treated as halted only if it carries a
+    /// HaltReason (327).
+    pub fn is_halted(&self) -> bool {
+        match self.trading_status() {
+            Some(s) => s.is_halted(),
+            None => self.msg.find(tag::HALT_REASON).is_some(),
+        }
+    }
+
+    /// HaltReason (327), `None` if absent or not a known reason.
+    pub fn halt_reason(&self) -> Option<HaltReason> {
+        value(self.msg, tag::HALT_REASON).and_then(HaltReason::from_code)
+    }
+
+    /// InViewOfCommon (328): the halt is due to a halt in the common stock.
+    pub fn in_view_of_common(&self) -> bool {
+        flag(self.msg, tag::IN_VIEW_OF_COMMON)
+    }
+
+    /// DueToRelated (329): the halt is due to a related security.
+    pub fn due_to_related(&self) -> bool {
+        flag(self.msg, tag::DUE_TO_RELATED)
+    }
+
+    /// UnsolicitedIndicator (325): `true` if not sent in answer to a
+    /// request.
+    pub fn is_unsolicited(&self) -> bool {
+        flag(self.msg, tag::UNSOLICITED_INDICATOR)
+    }
+
+    pub fn buy_volume(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::BUY_VOLUME)
+    }
+
+    pub fn sell_volume(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::SELL_VOLUME)
+    }
+
+    pub fn high_px(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::HIGH_PX)
+    }
+
+    pub fn low_px(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::LOW_PX)
+    }
+
+    pub fn last_px(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::LAST_PX)
+    }
+
+    pub fn transact_time(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TRANSACT_TIME)
+    }
+
+    /// Adjustment (334): `1` cancel, `2` error, `3` correction.
+    pub fn adjustment(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::ADJUSTMENT)
+    }
+
+    pub fn text(&self) -> Option<&'a [u8]> {
+        value(self.msg, tag::TEXT)
+    }
+
+    /// The underlying message.
+    #[inline]
+    pub fn message(&self) -> &'a Message<'a> {
+        self.msg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    #[test]
+    fn trading_session_status_fields() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"35=h\x01336=XNAS\x01340=1\x01325=Y\x01341=20240229-14:30:00\x01")
+            .unwrap();
+        let status = TradingSessionStatus::new(&msg).unwrap();
+        assert_eq!(status.trading_session_id(), Some(&b"XNAS"[..]));
+        assert_eq!(status.status(), Some(TradSesStatus::Halted));
+        assert!(status.is_unsolicited());
+        assert_eq!(status.trad_ses_req_id(), None);
+        assert!(SecurityStatus::new(&msg).is_err());
+    }
+
+    #[test]
+    fn security_status_halt_handling() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"35=f\x0155=IBM\x01326=2\x01327=I\x01328=N\x01329=Y\x01")
+            .unwrap();
+        let status = SecurityStatus::new(&msg).unwrap();
+        assert_eq!(
+            status.trading_status(),
+            Some(SecurityTradingStatus::TradingHalt)
+        );
+        assert!(status.is_halted());
+        assert_eq!(status.halt_reason(), Some(HaltReason::OrderImbalance));
+        assert!(!status.in_view_of_common());
+        assert!(status.due_to_related());
+
+        let msg = dec.decode(b"35=f\x0155=IBM\x01326=17\x01").unwrap();
+        let status = SecurityStatus::new(&msg).unwrap();
+        assert_eq!(
+            status.trading_status(),
+            Some(SecurityTradingStatus::ReadyToTrade)
+        );
+        assert!(!status.is_halted());
+        assert_eq!(SecurityTradingStatus::ReadyToTrade.as_bytes(), b"17");
+        assert_eq!(SecurityTradingStatus::from_code(b"11"), None);
+
+        let msg = dec.decode(b"35=f\x0155=IBM\x01327=X\x01").unwrap();
+        assert!(SecurityStatus::new(&msg).unwrap().is_halted());
+    }
+}
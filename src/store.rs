@@ -0,0 +1,540 @@
+//! Durable journal of a session's outbound messages and sequence numbers.
+//!
+//! [`MessageStore`] is what a [`Session`](crate::session::Session) needs to
+//! survive a restart and answer ResendRequests: every outbound message by
+//! MsgSeqNum, and the next inbound and outbound numbers.
+//! [`MemoryStore`] keeps both in memory; [`FileStore`] appends them to a
+//! single file of records, all integers little-endian:
+//!
+//! ```text
+//! M <seq u64> <len u32> <len raw bytes>    outbound message
+//! S <next inbound u64> <next outbound u64> sequence numbers
+//! R                                        reset: forget everything before
+//! ```
+//!
+//! The file is replayed on open; the latest `S` record wins and a torn
+//! record left by a crash mid-write is cut off.
+//!
+//! [`Outbox`] puts the store in front of the socket: messages a session
+//! sends are encoded into it, and [`Outbox::commit`] journals them — synced
+//! per the store's [`SyncPolicy`] — before handing back the bytes to write,
+//! so nothing the counterparty may have received is missing after a crash.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::encoder::Encoder;
+use crate::error::FixError;
+use crate::message::Message;
+use crate::session::SeqNums;
+use crate::tag;
+
+/// Storage for one session's outbound messages and sequence numbers.
+pub trait MessageStore {
+    /// Persist outbound message `raw`, sent with MsgSeqNum `seq`. Storing
+    /// the same `seq` again replaces the earlier message.
+    fn append(&mut self, seq: u64, raw: &[u8]) -> io::Result<()>;
+
+    /// Stored messages with `begin <= seq <= end`, in ascending order —
+    /// the journal [`Session::resend`](crate::session::Session::resend)
+    /// replays.
+    fn range(&mut self, begin: u64, end: u64) -> io::Result<Vec<(u64, Vec<u8>)>>;
+
+    /// The sequence numbers last saved, or 1/1 for a new store.
+    fn seq_nums(&self) -> SeqNums;
+
+    fn set_seq_nums(&mut self, seq: SeqNums) -> io::Result<()>;
+
+    /// Drop every message and start both sequence numbers again from 1,
+    /// as for a Logon with ResetSeqNumFlag.
+    fn reset(&mut self) -> io::Result<()>;
+
+    /// Make everything stored so far durable, as far as the store's policy
+    /// asks; called by [`Outbox::commit`] before messages go on the wire.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Encodes the messages a [`Session`](crate::session::Session) sends into
+/// one write buffer, journaling each in a [`MessageStore`] before it is
+/// written — a write-ahead journal for the session's send path.
+///
+/// Pass [`queue`](Self::queue) as the session's `send` callback, then
+/// [`commit`](Self::commit) and write what it returns. PossDup resends are
+/// written but not journaled again, since the store already holds them.
+///
+/// # Example
+/// ```ignore
+/// let mut outbox = Outbox::new();
+/// session.on_message(&msg, UtcTimestamp::now(), &mut |m| outbox.queue(m))?;
+/// stream.write_all(outbox.commit(&mut store)?)?;
+/// outbox.clear();
+/// ```
+#[derive(Default)]
+pub struct Outbox {
+    encoder: Encoder,
+    scratch: Vec<u8>,
+    wire: Vec<u8>,
+    /// MsgSeqNum and byte range in `wire` of each message to journal.
+    pending: Vec<(u64, Range<usize>)>,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a preconfigured `encoder` (timestamp policy, stages, ...).
+    pub fn with_encoder(encoder: Encoder) -> Self {
+        Self {
+            encoder,
+            ..Self::default()
+        }
+    }
+
+    /// Encode `msg` after the messages already queued.
+    ///
+    /// # Errors
+    /// The encode error, or `InvalidValue` if MsgSeqNum (34) is not a number.
+    pub fn queue(&mut self, msg: &Message<'_>) -> Result<(), FixError> {
+        self.encoder.encode(msg, &mut self.scratch)?;
+        let start = self.wire.len();
+        self.wire.extend_from_slice(&self.scratch);
+        let resent = msg.find(tag::POSS_DUP_FLAG).map(|f| f.value) == Some(b"Y");
+        if let (false, Some(seq)) = (resent, msg.find(tag::MSG_SEQ_NUM)) {
+            self.pending.push((seq.as_u64()?, start..self.wire.len()));
+        }
+        Ok(())
+    }
+
+    /// Journal every message queued since the last commit in `store` and
+    /// [`flush`](MessageStore::flush) it, then return all queued bytes,
+    /// ready to write.
+    ///
+    /// # Errors
+    /// The store's error; the messages must not be written then.
+    pub fn commit(&mut self, store: &mut impl MessageStore) -> io::Result<&[u8]> {
+        if !self.pending.is_empty() {
+            for (seq, range) in self.pending.drain(..) {
+                store.append(seq, &self.wire[range])?;
+            }
+            store.flush()?;
+        }
+        Ok(&self.wire)
+    }
+
+    /// Forget the queued bytes once written.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.wire.clear();
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.wire.is_empty()
+    }
+}
+
+/// When a [`FileStore`] forces its records to the storage device
+/// (`File::sync_data`). Unsynced records survive a process crash but may be
+/// lost with the machine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncPolicy {
+    /// Leave it to the OS.
+    #[default]
+    Never,
+    /// After every record: the safest and slowest.
+    EveryRecord,
+    /// On [`MessageStore::flush`], once per [`Outbox::commit`]: a burst of
+    /// messages costs one sync, still before any of them is written.
+    OnFlush,
+}
+
+/// A [`MessageStore`] that lives as long as the process.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    messages: BTreeMap<u64, Vec<u8>>,
+    seq: SeqNums,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored messages.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl MessageStore for MemoryStore {
+    fn append(&mut self, seq: u64, raw: &[u8]) -> io::Result<()> {
+        self.messages.insert(seq, raw.to_vec());
+        Ok(())
+    }
+
+    fn range(&mut self, begin: u64, end: u64) -> io::Result<Vec<(u64, Vec<u8>)>> {
+        if begin > end {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .messages
+            .range(begin..=end)
+            .map(|(&seq, raw)| (seq, raw.clone()))
+            .collect())
+    }
+
+    fn seq_nums(&self) -> SeqNums {
+        self.seq
+    }
+
+    fn set_seq_nums(&mut self, seq: SeqNums) -> io::Result<()> {
+        self.seq = seq;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        self.messages.clear();
+        self.seq.reset();
+        Ok(())
+    }
+}
+
+const MESSAGE: u8 = b'M';
+const SEQ_NUMS: u8 = b'S';
+const RESET: u8 = b'R';
+
+/// A [`MessageStore`] backed by one append-only file; see the
+/// [module documentation](self) for the format.
+///
+/// Messages stay on disk: the store keeps only an index of where each one
+/// is, and [`range`](MessageStore::range) reads them back. Records are
+/// written with one `write` call each; choose when they are also synced to
+/// the device with [`sync`](Self::sync).
+///
+/// # Example
+/// ```ignore
+/// let mut store = FileStore::open("sessions/BROKER-VENUE.store")?.sync(SyncPolicy::OnFlush);
+/// *session.seq_nums_mut() = store.seq_nums();
+/// ```
+#[derive(Debug)]
+pub struct FileStore {
+    file: File,
+    path: PathBuf,
+    /// MsgSeqNum → (offset of the raw bytes, length).
+    index: BTreeMap<u64, (u64, u32)>,
+    seq: SeqNums,
+    /// Length of the valid part of the file.
+    len: u64,
+    sync: SyncPolicy,
+    record: Vec<u8>,
+}
+
+impl FileStore {
+    /// Open the store at `path`, creating the file if it does not exist,
+    /// and rebuild the index from its records.
+    ///
+    /// # Errors
+    /// Any I/O error, or `io::ErrorKind::InvalidData` if the file contains
+    /// something other than store records.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+        let mut store = Self {
+            file,
+            path,
+            index: BTreeMap::new(),
+            seq: SeqNums::new(),
+            len: 0,
+            sync: SyncPolicy::Never,
+            record: Vec::new(),
+        };
+        store.replay()?;
+        Ok(store)
+    }
+
+    /// When to sync records to the storage device. Default
+    /// [`SyncPolicy::Never`].
+    pub fn sync(mut self, policy: SyncPolicy) -> Self {
+        self.sync = policy;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of stored messages.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    fn replay(&mut self) -> io::Result<()> {
+        let mut r = BufReader::new(&self.file);
+        let mut pos = 0u64;
+        let mut kind = [0u8; 1];
+        loop {
+            if r.read(&mut kind)? == 0 {
+                break;
+            }
+            let complete = match kind[0] {
+                MESSAGE => {
+                    let mut head = [0u8; 12];
+                    if read_full(&mut r, &mut head)? {
+                        let seq = u64::from_le_bytes(head[..8].try_into().unwrap());
+                        let len = u32::from_le_bytes(head[8..].try_into().unwrap());
+                        let skipped = io::copy(&mut (&mut r).take(len.into()), &mut io::sink())?;
+                        if skipped == u64::from(len) {
+                            self.index.insert(seq, (pos + 13, len));
+                            pos += 13 + skipped;
+                            true
+                        } else {
+                            false
+                        }
+                    } else {
+                        false
+                    }
+                }
+                SEQ_NUMS => {
+                    let mut body = [0u8; 16];
+                    let complete = read_full(&mut r, &mut body)?;
+                    if complete {
+                        self.seq = SeqNums::with_next(
+                            u64::from_le_bytes(body[..8].try_into().unwrap()),
+                            u64::from_le_bytes(body[8..].try_into().unwrap()),
+                        );
+                        pos += 17;
+                    }
+                    complete
+                }
+                RESET => {
+                    self.index.clear();
+                    self.seq.reset();
+                    pos += 1;
+                    true
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "not a message store record",
+                    ));
+                }
+            };
+            if !complete {
+                break;
+            }
+        }
+        self.len = pos;
+        if self.file.metadata()?.len() > pos {
+            self.file.set_len(pos)?;
+        }
+        Ok(())
+    }
+
+    /// Append the record in `self.record`.
+    fn write_record(&mut self) -> io::Result<()> {
+        self.file.write_all(&self.record)?;
+        if self.sync == SyncPolicy::EveryRecord {
+            self.file.sync_data()?;
+        }
+        self.len += self.record.len() as u64;
+        Ok(())
+    }
+}
+
+/// Fill `buf`, or return `false` if the input ends first.
+fn read_full(r: &mut impl Read, buf: &mut [u8]) -> io::Result<bool> {
+    match r.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+impl MessageStore for FileStore {
+    fn append(&mut self, seq: u64, raw: &[u8]) -> io::Result<()> {
+        let len = u32::try_from(raw.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "message too large"))?;
+        self.record.clear();
+        self.record.push(MESSAGE);
+        self.record.extend_from_slice(&seq.to_le_bytes());
+        self.record.extend_from_slice(&len.to_le_bytes());
+        self.record.extend_from_slice(raw);
+        let offset = self.len + 13;
+        self.write_record()?;
+        self.index.insert(seq, (offset, len));
+        Ok(())
+    }
+
+    fn range(&mut self, begin: u64, end: u64) -> io::Result<Vec<(u64, Vec<u8>)>> {
+        if begin > end {
+            return Ok(Vec::new());
+        }
+        let mut out = Vec::new();
+        for (&seq, &(offset, len)) in self.index.range(begin..=end) {
+            let mut raw = vec![0u8; len as usize];
+            self.file.seek(SeekFrom::Start(offset))?;
+            self.file.read_exact(&mut raw)?;
+            out.push((seq, raw));
+        }
+        Ok(out)
+    }
+
+    fn seq_nums(&self) -> SeqNums {
+        self.seq
+    }
+
+    fn set_seq_nums(&mut self, seq: SeqNums) -> io::Result<()> {
+        self.record.clear();
+        self.record.push(SEQ_NUMS);
+        self.record
+            .extend_from_slice(&seq.expected_inbound().to_le_bytes());
+        self.record
+            .extend_from_slice(&seq.peek_outbound().to_le_bytes());
+        self.write_record()?;
+        self.seq = seq;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        self.record.clear();
+        self.record.push(RESET);
+        self.write_record()?;
+        self.index.clear();
+        self.seq.reset();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.sync == SyncPolicy::OnFlush {
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("fix-codec-store-{name}-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn exercise(store: &mut impl MessageStore) {
+        store.append(1, b"35=A\x01").unwrap();
+        store.append(2, b"35=D\x0111=A\x01").unwrap();
+        store.append(4, b"35=D\x0111=B\x01").unwrap();
+        store.set_seq_nums(SeqNums::with_next(7, 5)).unwrap();
+        assert_eq!(
+            store.range(2, 9).unwrap(),
+            [
+                (2, b"35=D\x0111=A\x01".to_vec()),
+                (4, b"35=D\x0111=B\x01".to_vec())
+            ]
+        );
+        assert!(store.range(5, 4).unwrap().is_empty());
+        assert_eq!(store.seq_nums(), SeqNums::with_next(7, 5));
+    }
+
+    #[test]
+    fn memory_store_round_trip() {
+        let mut store = MemoryStore::new();
+        exercise(&mut store);
+        assert_eq!(store.len(), 3);
+        store.reset().unwrap();
+        assert!(store.is_empty());
+        assert_eq!(store.seq_nums(), SeqNums::new());
+    }
+
+    #[test]
+    fn file_store_survives_reopen_and_torn_tail() {
+        let path = temp_file("reopen");
+        let mut store = FileStore::open(&path).unwrap();
+        exercise(&mut store);
+        drop(store);
+
+        let mut store = FileStore::open(&path).unwrap();
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.seq_nums(), SeqNums::with_next(7, 5));
+        assert_eq!(store.range(4, 4).unwrap()[0].1, b"35=D\x0111=B\x01");
+        store.append(5, b"35=F\x01").unwrap();
+        drop(store);
+
+        // A crash mid-record leaves a partial tail, which is discarded.
+        let full = fs::metadata(&path).unwrap().len();
+        let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+        f.write_all(&[MESSAGE, 6, 0, 0]).unwrap();
+        drop(f);
+        let mut store = FileStore::open(&path).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), full);
+        assert_eq!(store.range(5, 6).unwrap(), [(5, b"35=F\x01".to_vec())]);
+
+        store.reset().unwrap();
+        store.append(1, b"35=A\x01").unwrap();
+        drop(store);
+        let mut store = FileStore::open(&path).unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.seq_nums(), SeqNums::new());
+        assert_eq!(store.range(1, 10).unwrap().len(), 1);
+        fs::remove_file(&path).unwrap();
+
+        fs::write(&path, b"garbage").unwrap();
+        let err = FileStore::open(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn outbox_journals_before_returning_bytes() {
+        use crate::builder::MessageBuilder;
+
+        let mut msg = MessageBuilder::new();
+        msg.push(tag::BEGIN_STRING, b"FIX.4.4")
+            .push(tag::MSG_TYPE, b"D")
+            .push(tag::MSG_SEQ_NUM, b"7");
+        let mut resend = msg.clone();
+        resend.push(tag::POSS_DUP_FLAG, b"Y");
+
+        let path = temp_file("outbox");
+        let mut store = FileStore::open(&path).unwrap().sync(SyncPolicy::OnFlush);
+        let mut outbox = Outbox::new();
+        outbox.queue(&msg.as_message()).unwrap();
+        outbox.queue(&resend.as_message()).unwrap();
+        let wire = outbox.commit(&mut store).unwrap().to_vec();
+        assert_eq!(store.len(), 1);
+        let journaled = &store.range(7, 7).unwrap()[0].1;
+        assert!(wire.starts_with(journaled));
+        assert!(wire.len() > journaled.len());
+
+        outbox.clear();
+        assert!(outbox.is_empty());
+        assert!(outbox.commit(&mut store).unwrap().is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+}
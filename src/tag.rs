@@ -958,6 +958,56 @@ pub const NESTED3_PARTY_SUB_ID_TYPE: Tag = 954;
 pub const LEG_CONTRACT_SETTL_MONTH: Tag = 955;
 pub const LEG_INTEREST_ACCRUAL_DATE: Tag = 956;
 
+// FIX 5.0 SP2 Tag constants (source: https://www.onixs.biz/fix-dictionary/5.0.sp2/fields_by_tag.html)
+pub const NO_STRATEGY_PARAMETERS: Tag = 957;
+pub const STRATEGY_PARAMETER_NAME: Tag = 958;
+pub const STRATEGY_PARAMETER_TYPE: Tag = 959;
+pub const STRATEGY_PARAMETER_VALUE: Tag = 960;
+pub const NO_INSTRUMENT_PARTIES: Tag = 1018;
+pub const INSTRUMENT_PARTY_ID: Tag = 1019;
+pub const MD_BOOK_TYPE: Tag = 1021;
+pub const MD_FEED_TYPE: Tag = 1022;
+pub const INSTRUMENT_PARTY_ID_SOURCE: Tag = 1050;
+pub const INSTRUMENT_PARTY_ROLE: Tag = 1051;
+pub const NO_INSTRUMENT_PARTY_SUB_IDS: Tag = 1052;
+pub const INSTRUMENT_PARTY_SUB_ID: Tag = 1053;
+pub const INSTRUMENT_PARTY_SUB_ID_TYPE: Tag = 1054;
+pub const NO_UNDLY_INSTRUMENT_PARTIES: Tag = 1058;
+pub const UNDLY_INSTRUMENT_PARTY_ID: Tag = 1059;
+pub const UNDLY_INSTRUMENT_PARTY_ID_SOURCE: Tag = 1060;
+pub const UNDLY_INSTRUMENT_PARTY_ROLE: Tag = 1061;
+pub const NO_UNDLY_INSTRUMENT_PARTY_SUB_IDS: Tag = 1062;
+pub const UNDLY_INSTRUMENT_PARTY_SUB_ID: Tag = 1063;
+pub const UNDLY_INSTRUMENT_PARTY_SUB_ID_TYPE: Tag = 1064;
+pub const LOT_TYPE: Tag = 1093;
+pub const NO_ROOT_PARTY_IDS: Tag = 1116;
+pub const ROOT_PARTY_ID: Tag = 1117;
+pub const ROOT_PARTY_ID_SOURCE: Tag = 1118;
+pub const ROOT_PARTY_ROLE: Tag = 1119;
+pub const NO_ROOT_PARTY_SUB_IDS: Tag = 1120;
+pub const ROOT_PARTY_SUB_ID: Tag = 1121;
+pub const ROOT_PARTY_SUB_ID_TYPE: Tag = 1122;
+pub const APPL_VER_ID: Tag = 1128;
+pub const DEFAULT_APPL_VER_ID: Tag = 1137;
+pub const NO_MD_FEED_TYPES: Tag = 1141;
+pub const MATCH_ALGORITHM: Tag = 1142;
+pub const APPL_BEG_SEQ_NUM: Tag = 1182;
+pub const APPL_END_SEQ_NUM: Tag = 1183;
+pub const NO_TICK_RULES: Tag = 1205;
+pub const START_TICK_PRICE_RANGE: Tag = 1206;
+pub const END_TICK_PRICE_RANGE: Tag = 1207;
+pub const TICK_INCREMENT: Tag = 1208;
+pub const TICK_RULE_TYPE: Tag = 1209;
+pub const MIN_LOT_SIZE: Tag = 1231;
+pub const NO_EXEC_INST_RULES: Tag = 1232;
+pub const NO_LOT_TYPE_RULES: Tag = 1234;
+pub const NO_MATCH_RULES: Tag = 1235;
+pub const NO_ORD_TYPE_RULES: Tag = 1237;
+pub const NO_TIME_IN_FORCE_RULES: Tag = 1239;
+pub const EXEC_INST_VALUE: Tag = 1308;
+pub const NO_APPL_IDS: Tag = 1351;
+pub const REF_APPL_ID: Tag = 1355;
+
 // FIX 4.4 aliases for FIX 4.2 renamed tags (backward-compatible)
 pub const SECURITY_ID_SOURCE: Tag = 22; // was ID_SOURCE
 pub const IOI_QTY: Tag = 27; // was IOI_SHARES
@@ -967,24 +1017,45 @@ pub const POSITION_EFFECT: Tag = 77; // was OPEN_CLOSE
 pub const ALLOC_QTY: Tag = 80; // was ALLOC_SHARES
 pub const SETTL_TYPE: Tag = 63; // was SETTLMNT_TYP
 pub const SETTL_DATE: Tag = 64; // was FUT_SETT_DATE
+pub const SETTL_DATE2: Tag = 193; // was FUT_SETT_DATE2
 pub const PEG_OFFSET_VALUE: Tag = 211; // was PEG_DIFFERENCE
 pub const DISCRETION_OFFSET_VALUE: Tag = 389; // was DISCRETION_OFFSET
 pub const SPREAD: Tag = 218; // was SPREAD_TO_BENCHMARK
+pub const TOT_NO_RELATED_SYM: Tag = 393; // was TOTAL_NUM_SECURITIES
+
+/// The FIX name of `tag` (`"Symbol"` for 55), for logs and config files;
+/// the FIX 4.4 name for tags renamed since 4.2. Covers every tag of the
+/// FIX 4.2 and 4.4 dictionaries.
+#[inline]
+pub fn name(tag: Tag) -> Option<&'static str> {
+    crate::names::tag_name(tag)
+}
+
+/// The tag named `name` (55 for `"Symbol"`), the inverse of [`name`].
+/// Exact, case-sensitive match; FIX 4.2 names of renamed tags are accepted.
+#[inline]
+pub fn from_name(name: &str) -> Option<Tag> {
+    crate::names::tag_by_name(name)
+}
 
+/// Parse ASCII decimal tag bytes.
+///
+/// `InvalidTag` carries the index in `bytes` of the first non-digit or of
+/// the digit that overflows `u32` (0 if `bytes` is empty).
 #[inline]
 pub fn parse_tag(bytes: &[u8]) -> Result<Tag, FixError> {
     if bytes.is_empty() {
-        return Err(FixError::InvalidTag);
+        return Err(FixError::InvalidTag { offset: 0 });
     }
     let mut value: u32 = 0;
-    for &b in bytes {
+    for (i, &b) in bytes.iter().enumerate() {
         if !b.is_ascii_digit() {
-            return Err(FixError::InvalidTag);
+            return Err(FixError::InvalidTag { offset: i });
         }
         value = value
             .checked_mul(10)
             .and_then(|v| v.checked_add((b - b'0') as u32))
-            .ok_or(FixError::InvalidTag)?;
+            .ok_or(FixError::InvalidTag { offset: i })?;
     }
     Ok(value)
 }
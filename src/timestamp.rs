@@ -0,0 +1,659 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::FixError;
+
+/// Longest UTCTimestamp rendering: `YYYYMMDD-HH:MM:SS.sssssssss`.
+pub const MAX_TIMESTAMP_LEN: usize = 27;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Number of fractional-second digits in a UTCTimestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TimestampPrecision {
+    /// `YYYYMMDD-HH:MM:SS`
+    Seconds,
+    /// `YYYYMMDD-HH:MM:SS.sss`
+    #[default]
+    Millis,
+    /// `YYYYMMDD-HH:MM:SS.ssssss`
+    Micros,
+    /// `YYYYMMDD-HH:MM:SS.sssssssss`
+    Nanos,
+}
+
+impl TimestampPrecision {
+    /// Number of fractional digits written for this precision.
+    #[inline]
+    pub fn digits(self) -> usize {
+        match self {
+            Self::Seconds => 0,
+            Self::Millis => 3,
+            Self::Micros => 6,
+            Self::Nanos => 9,
+        }
+    }
+
+    fn from_digits(digits: usize) -> Option<Self> {
+        match digits {
+            0 => Some(Self::Seconds),
+            3 => Some(Self::Millis),
+            6 => Some(Self::Micros),
+            9 => Some(Self::Nanos),
+            _ => None,
+        }
+    }
+}
+
+/// A point in time as carried by FIX UTCTimestamp fields (SendingTime 52,
+/// TransactTime 60, OrigSendingTime 122, …).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcTimestamp {
+    /// Seconds since the Unix epoch.
+    pub secs: i64,
+    /// Sub-second part, `0..1_000_000_000`.
+    pub nanos: u32,
+}
+
+impl UtcTimestamp {
+    pub fn new(secs: i64, nanos: u32) -> Self {
+        Self { secs, nanos }
+    }
+
+    /// The current wall-clock time.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// Render as `YYYYMMDD-HH:MM:SS[.fff…]` with `precision` fractional
+    /// digits (truncated, not rounded). Returns a stack buffer and the
+    /// number of bytes used — no heap allocation.
+    pub fn format(&self, precision: TimestampPrecision) -> ([u8; MAX_TIMESTAMP_LEN], usize) {
+        let mut buf = [b'0'; MAX_TIMESTAMP_LEN];
+        let days = self.secs.div_euclid(SECS_PER_DAY);
+        let sod = self.secs.rem_euclid(SECS_PER_DAY) as u32;
+        let (y, m, d) = civil_from_days(days);
+
+        write_digits(&mut buf[0..4], y.clamp(0, 9999) as u32);
+        write_digits(&mut buf[4..6], m);
+        write_digits(&mut buf[6..8], d);
+        buf[8] = b'-';
+        write_digits(&mut buf[9..11], sod / 3600);
+        buf[11] = b':';
+        write_digits(&mut buf[12..14], sod / 60 % 60);
+        buf[14] = b':';
+        write_digits(&mut buf[15..17], sod % 60);
+
+        let len = 17 + write_fraction(&mut buf[17..], self.nanos, precision);
+        (buf, len)
+    }
+
+    /// Parse a UTCTimestamp with 0, 3, 6 or 9 fractional digits, returning
+    /// the value and the precision it was written with.
+    pub fn parse(value: &[u8]) -> Result<(Self, TimestampPrecision), FixError> {
+        if value.len() < 17 || value[8] != b'-' || value[11] != b':' || value[14] != b':' {
+            return Err(FixError::InvalidValue);
+        }
+        let y = read_digits(&value[0..4])? as i64;
+        let m = read_digits(&value[4..6])?;
+        let d = read_digits(&value[6..8])?;
+        let hh = read_digits(&value[9..11])?;
+        let mm = read_digits(&value[12..14])?;
+        // 60 is allowed for leap seconds.
+        let ss = read_digits(&value[15..17])?;
+        if !(1..=12).contains(&m) || !(1..=31).contains(&d) || hh > 23 || mm > 59 || ss > 60 {
+            return Err(FixError::InvalidValue);
+        }
+
+        let (nanos, precision) = parse_fraction(&value[17..])?;
+
+        let secs = days_from_civil(y, m, d) * SECS_PER_DAY + (hh * 3600 + mm * 60 + ss) as i64;
+        Ok((Self { secs, nanos }, precision))
+    }
+
+    /// Absolute distance between two timestamps.
+    pub fn abs_diff(&self, other: &Self) -> Duration {
+        let a = self.secs as i128 * 1_000_000_000 + self.nanos as i128;
+        let b = other.secs as i128 * 1_000_000_000 + other.nanos as i128;
+        let n = (a - b).unsigned_abs();
+        Duration::new((n / 1_000_000_000) as u64, (n % 1_000_000_000) as u32)
+    }
+}
+
+impl From<SystemTime> for UtcTimestamp {
+    fn from(t: SystemTime) -> Self {
+        match t.duration_since(UNIX_EPOCH) {
+            Ok(d) => Self::new(d.as_secs() as i64, d.subsec_nanos()),
+            Err(e) => {
+                let d = e.duration();
+                let mut secs = -(d.as_secs() as i64);
+                let mut nanos = d.subsec_nanos();
+                if nanos > 0 {
+                    secs -= 1;
+                    nanos = 1_000_000_000 - nanos;
+                }
+                Self::new(secs, nanos)
+            }
+        }
+    }
+}
+
+/// Longest UTCTimeOnly rendering: `HH:MM:SS.sssssssss`.
+pub const MAX_TIME_ONLY_LEN: usize = 18;
+
+/// A FIX UTCDateOnly value (`YYYYMMDD`), e.g. TradeDate (75) or
+/// MaturityDate (541).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl UtcDate {
+    /// Parse `YYYYMMDD`, rejecting days that do not exist (e.g. `20230229`).
+    pub fn parse(value: &[u8]) -> Result<Self, FixError> {
+        if value.len() != 8 {
+            return Err(FixError::InvalidValue);
+        }
+        let y = read_digits(&value[0..4])?;
+        let m = read_digits(&value[4..6])?;
+        let d = read_digits(&value[6..8])?;
+        if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+            return Err(FixError::InvalidValue);
+        }
+        if civil_from_days(days_from_civil(y as i64, m, d)) != (y as i64, m, d) {
+            return Err(FixError::InvalidValue);
+        }
+        Ok(Self {
+            year: y as u16,
+            month: m as u8,
+            day: d as u8,
+        })
+    }
+
+    /// Render as `YYYYMMDD` — no heap allocation.
+    pub fn format(&self) -> [u8; 8] {
+        let mut buf = [b'0'; 8];
+        write_digits(&mut buf[0..4], self.year as u32);
+        write_digits(&mut buf[4..6], self.month as u32);
+        write_digits(&mut buf[6..8], self.day as u32);
+        buf
+    }
+
+    /// Midnight UTC at the start of this date.
+    pub fn at_midnight(&self) -> UtcTimestamp {
+        let days = days_from_civil(self.year as i64, self.month as u32, self.day as u32);
+        UtcTimestamp::new(days * SECS_PER_DAY, 0)
+    }
+}
+
+impl From<UtcTimestamp> for UtcDate {
+    fn from(ts: UtcTimestamp) -> Self {
+        let (y, m, d) = civil_from_days(ts.secs.div_euclid(SECS_PER_DAY));
+        Self {
+            year: y.clamp(0, 9999) as u16,
+            month: m as u8,
+            day: d as u8,
+        }
+    }
+}
+
+/// A FIX UTCTimeOnly value (`HH:MM:SS[.fff…]`), e.g. MDEntryTime (273).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcTimeOnly {
+    /// Seconds since midnight; 86400 is a leap second.
+    pub secs: u32,
+    /// Sub-second part, `0..1_000_000_000`.
+    pub nanos: u32,
+}
+
+impl UtcTimeOnly {
+    pub fn new(secs: u32, nanos: u32) -> Self {
+        Self { secs, nanos }
+    }
+
+    /// Parse with 0, 3, 6 or 9 fractional digits, returning the value and
+    /// the precision it was written with.
+    pub fn parse(value: &[u8]) -> Result<(Self, TimestampPrecision), FixError> {
+        if value.len() < 8 || value[2] != b':' || value[5] != b':' {
+            return Err(FixError::InvalidValue);
+        }
+        let hh = read_digits(&value[0..2])?;
+        let mm = read_digits(&value[3..5])?;
+        // 60 is allowed for leap seconds.
+        let ss = read_digits(&value[6..8])?;
+        if hh > 23 || mm > 59 || ss > 60 {
+            return Err(FixError::InvalidValue);
+        }
+        let (nanos, precision) = parse_fraction(&value[8..])?;
+        Ok((Self::new(hh * 3600 + mm * 60 + ss, nanos), precision))
+    }
+
+    /// Render as `HH:MM:SS[.fff…]` with `precision` fractional digits
+    /// (truncated). Returns a stack buffer and the number of bytes used.
+    pub fn format(&self, precision: TimestampPrecision) -> ([u8; MAX_TIME_ONLY_LEN], usize) {
+        let mut buf = [b'0'; MAX_TIME_ONLY_LEN];
+        // A leap second renders as 23:59:60.
+        let (hh, mm, ss) = if self.secs >= 86_400 {
+            (23, 59, 60)
+        } else {
+            (self.secs / 3600, self.secs / 60 % 60, self.secs % 60)
+        };
+        write_digits(&mut buf[0..2], hh);
+        buf[2] = b':';
+        write_digits(&mut buf[3..5], mm);
+        buf[5] = b':';
+        write_digits(&mut buf[6..8], ss);
+        let len = 8 + write_fraction(&mut buf[8..], self.nanos, precision);
+        (buf, len)
+    }
+}
+
+impl From<UtcTimestamp> for UtcTimeOnly {
+    fn from(ts: UtcTimestamp) -> Self {
+        Self::new(ts.secs.rem_euclid(SECS_PER_DAY) as u32, ts.nanos)
+    }
+}
+
+/// Hands out strictly increasing timestamps at a fixed precision.
+///
+/// Wall-clock readings are truncated to `precision`; when a reading is not
+/// later than the previous one — a burst within the same millisecond, or the
+/// clock stepping backwards after an NTP correction — the previous value plus
+/// one unit of `precision` is returned instead. Once the wall clock catches
+/// up, readings are used as-is again. A value is never at or below the one
+/// handed out before it, so a burst faster than `precision` runs ahead of
+/// the wall clock until the burst ends.
+///
+/// # Example
+/// ```ignore
+/// let mut clock = MonotonicTimestamper::new(TimestampPrecision::Micros);
+/// let (buf, len) = clock.stamp().format(TimestampPrecision::Micros);
+/// b.push(tag::TRANSACT_TIME, &buf[..len]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MonotonicTimestamper {
+    precision: TimestampPrecision,
+    last: Option<UtcTimestamp>,
+}
+
+impl MonotonicTimestamper {
+    pub fn new(precision: TimestampPrecision) -> Self {
+        Self {
+            precision,
+            last: None,
+        }
+    }
+
+    /// Precision the timestamps are truncated to.
+    #[inline]
+    pub fn precision(&self) -> TimestampPrecision {
+        self.precision
+    }
+
+    /// Last value handed out, if any.
+    #[inline]
+    pub fn last(&self) -> Option<UtcTimestamp> {
+        self.last
+    }
+
+    /// Next timestamp based on the current wall clock.
+    pub fn stamp(&mut self) -> UtcTimestamp {
+        self.stamp_at(UtcTimestamp::now())
+    }
+
+    /// Next timestamp based on the clock reading `now`.
+    pub fn stamp_at(&mut self, now: UtcTimestamp) -> UtcTimestamp {
+        let unit = 10u32.pow(9 - self.precision.digits() as u32);
+        let now = UtcTimestamp::new(now.secs, now.nanos - now.nanos % unit);
+        let ts = match self.last {
+            Some(last) if now <= last => {
+                let nanos = last.nanos + unit;
+                if nanos >= 1_000_000_000 {
+                    UtcTimestamp::new(last.secs + 1, nanos - 1_000_000_000)
+                } else {
+                    UtcTimestamp::new(last.secs, nanos)
+                }
+            }
+            _ => now,
+        };
+        self.last = Some(ts);
+        ts
+    }
+}
+
+/// How a session writes and accepts UTCTimestamp fields.
+///
+/// - `outbound`: precision the encoder stamps SendingTime (52) with and
+///   normalizes TransactTime (60) to.
+/// - `max_inbound`: finest precision accepted on inbound timestamps; finer
+///   values are rejected as incorrect data format.
+/// - `max_skew`: maximum allowed distance between an inbound SendingTime and
+///   the local clock, or `None` to skip the check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampPolicy {
+    pub outbound: TimestampPrecision,
+    pub max_inbound: TimestampPrecision,
+    pub max_skew: Option<Duration>,
+}
+
+impl Default for TimestampPolicy {
+    /// Millisecond output, any precision accepted, 2 minute SendingTime skew.
+    fn default() -> Self {
+        Self {
+            outbound: TimestampPrecision::Millis,
+            max_inbound: TimestampPrecision::Nanos,
+            max_skew: Some(Duration::from_secs(120)),
+        }
+    }
+}
+
+impl TimestampPolicy {
+    /// Policy writing and accepting exactly up to `precision`, with the
+    /// default skew tolerance.
+    pub fn with_precision(precision: TimestampPrecision) -> Self {
+        Self {
+            outbound: precision,
+            max_inbound: precision,
+            ..Self::default()
+        }
+    }
+}
+
+/// Write `n` zero-padded into `out` (most significant digit first).
+#[inline]
+fn write_digits(out: &mut [u8], mut n: u32) {
+    for b in out.iter_mut().rev() {
+        *b = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+}
+
+/// Write `.fff…` for `precision` into `out`, returning the bytes written
+/// (0 for whole seconds).
+#[inline]
+fn write_fraction(out: &mut [u8], nanos: u32, precision: TimestampPrecision) -> usize {
+    let digits = precision.digits();
+    if digits == 0 {
+        return 0;
+    }
+    out[0] = b'.';
+    write_digits(
+        &mut out[1..1 + digits],
+        nanos / 10u32.pow(9 - digits as u32),
+    );
+    1 + digits
+}
+
+/// Parse an optional `.fff…` suffix of 3, 6 or 9 digits into nanoseconds.
+#[inline]
+fn parse_fraction(value: &[u8]) -> Result<(u32, TimestampPrecision), FixError> {
+    match value {
+        [] => Ok((0, TimestampPrecision::Seconds)),
+        [b'.', frac @ ..] => {
+            let precision =
+                TimestampPrecision::from_digits(frac.len()).ok_or(FixError::InvalidValue)?;
+            Ok((
+                read_digits(frac)? * 10u32.pow(9 - frac.len() as u32),
+                precision,
+            ))
+        }
+        _ => Err(FixError::InvalidValue),
+    }
+}
+
+#[inline]
+fn read_digits(bytes: &[u8]) -> Result<u32, FixError> {
+    bytes.iter().try_fold(0u32, |acc, &b| {
+        if b.is_ascii_digit() {
+            Ok(acc * 10 + (b - b'0') as u32)
+        } else {
+            Err(FixError::InvalidValue)
+        }
+    })
+}
+
+/// Days since 1970-01-01 to proleptic Gregorian (year, month, day).
+/// Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+/// Inverse of [`civil_from_days`].
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = if m > 2 { m - 3 } else { m + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Conversions to and from `chrono` types (`chrono` feature).
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Timelike, Utc};
+
+    use super::{UtcDate, UtcTimeOnly, UtcTimestamp};
+    use crate::error::FixError;
+
+    impl TryFrom<UtcTimestamp> for DateTime<Utc> {
+        type Error = FixError;
+
+        fn try_from(ts: UtcTimestamp) -> Result<Self, FixError> {
+            DateTime::from_timestamp(ts.secs, ts.nanos).ok_or(FixError::InvalidValue)
+        }
+    }
+
+    impl From<DateTime<Utc>> for UtcTimestamp {
+        fn from(dt: DateTime<Utc>) -> Self {
+            // chrono carries a leap second as nanos >= 1e9; fold it into secs.
+            let nanos = dt.timestamp_subsec_nanos();
+            Self::new(
+                dt.timestamp() + i64::from(nanos / 1_000_000_000),
+                nanos % 1_000_000_000,
+            )
+        }
+    }
+
+    impl TryFrom<UtcDate> for NaiveDate {
+        type Error = FixError;
+
+        fn try_from(d: UtcDate) -> Result<Self, FixError> {
+            NaiveDate::from_ymd_opt(d.year.into(), d.month.into(), d.day.into())
+                .ok_or(FixError::InvalidValue)
+        }
+    }
+
+    impl TryFrom<NaiveDate> for UtcDate {
+        type Error = FixError;
+
+        fn try_from(d: NaiveDate) -> Result<Self, FixError> {
+            Ok(Self {
+                year: u16::try_from(d.year())
+                    .ok()
+                    .filter(|&y| y <= 9999)
+                    .ok_or(FixError::InvalidValue)?,
+                month: d.month() as u8,
+                day: d.day() as u8,
+            })
+        }
+    }
+
+    impl TryFrom<UtcTimeOnly> for NaiveTime {
+        type Error = FixError;
+
+        fn try_from(t: UtcTimeOnly) -> Result<Self, FixError> {
+            // A leap second is 23:59:59 with nanos >= 1e9 in chrono.
+            let (secs, nanos) = if t.secs >= 86_400 {
+                (86_399, t.nanos + 1_000_000_000)
+            } else {
+                (t.secs, t.nanos)
+            };
+            NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).ok_or(FixError::InvalidValue)
+        }
+    }
+
+    impl From<NaiveTime> for UtcTimeOnly {
+        fn from(t: NaiveTime) -> Self {
+            let nanos = t.nanosecond();
+            Self::new(
+                t.num_seconds_from_midnight() + nanos / 1_000_000_000,
+                nanos % 1_000_000_000,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fmt(ts: UtcTimestamp, p: TimestampPrecision) -> String {
+        let (buf, len) = ts.format(p);
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn format_each_precision() {
+        // 2024-02-29 13:45:06.123456789 UTC
+        let ts = UtcTimestamp::new(1_709_214_306, 123_456_789);
+        assert_eq!(fmt(ts, TimestampPrecision::Seconds), "20240229-13:45:06");
+        assert_eq!(fmt(ts, TimestampPrecision::Millis), "20240229-13:45:06.123");
+        assert_eq!(
+            fmt(ts, TimestampPrecision::Micros),
+            "20240229-13:45:06.123456"
+        );
+        assert_eq!(
+            fmt(ts, TimestampPrecision::Nanos),
+            "20240229-13:45:06.123456789"
+        );
+        assert_eq!(
+            fmt(UtcTimestamp::new(0, 0), TimestampPrecision::Seconds),
+            "19700101-00:00:00"
+        );
+    }
+
+    #[test]
+    fn monotonic_timestamper_never_repeats() {
+        let mut clock = MonotonicTimestamper::new(TimestampPrecision::Millis);
+        let t0 = UtcTimestamp::new(100, 999_400_000);
+
+        assert_eq!(clock.stamp_at(t0), UtcTimestamp::new(100, 999_000_000));
+        // Same millisecond: bumped by one unit, rolling into the next second.
+        assert_eq!(clock.stamp_at(t0), UtcTimestamp::new(101, 0));
+        // Clock stepped backwards: keep counting from the last value.
+        assert_eq!(
+            clock.stamp_at(UtcTimestamp::new(99, 0)),
+            UtcTimestamp::new(101, 1_000_000)
+        );
+        // Clock caught up: readings are used again.
+        assert_eq!(
+            clock.stamp_at(UtcTimestamp::new(105, 123_456_789)),
+            UtcTimestamp::new(105, 123_000_000)
+        );
+        assert_eq!(clock.last(), Some(UtcTimestamp::new(105, 123_000_000)));
+    }
+
+    #[test]
+    fn monotonic_timestamper_burst_never_goes_backwards() {
+        let mut clock = MonotonicTimestamper::new(TimestampPrecision::Seconds);
+        let now = UtcTimestamp::new(100, 500_000_000);
+        let stamps: Vec<_> = (0..5).map(|_| clock.stamp_at(now).secs).collect();
+        assert_eq!(stamps, [100, 101, 102, 103, 104]);
+
+        // An NTP step back of well over a second still counts up.
+        assert_eq!(clock.stamp_at(UtcTimestamp::new(50, 0)).secs, 105);
+        assert_eq!(clock.stamp_at(UtcTimestamp::new(50, 0)).secs, 106);
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let ts = UtcTimestamp::new(1_709_214_306, 123_456_000);
+        let (parsed, p) = UtcTimestamp::parse(b"20240229-13:45:06.123456").unwrap();
+        assert_eq!(parsed, ts);
+        assert_eq!(p, TimestampPrecision::Micros);
+        assert_eq!(
+            UtcTimestamp::parse(b"20240229-13:45:06").unwrap().1,
+            TimestampPrecision::Seconds
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed() {
+        for bad in [
+            &b"20240229-13:45"[..],
+            b"20240229 13:45:06",
+            b"20241329-13:45:06",
+            b"20240229-13:45:06.12",
+            b"20240229-13:45:06.abc",
+            b"20240229-13:45:06Z",
+        ] {
+            assert!(UtcTimestamp::parse(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn date_only_round_trip() {
+        let d = UtcDate::parse(b"20240229").unwrap();
+        assert_eq!((d.year, d.month, d.day), (2024, 2, 29));
+        assert_eq!(&d.format(), b"20240229");
+        assert_eq!(d.at_midnight(), UtcTimestamp::new(1_709_164_800, 0));
+        assert_eq!(UtcDate::from(UtcTimestamp::new(1_709_214_306, 0)), d);
+        for bad in [&b"20230229"[..], b"20240431", b"2024022", b"2024-02-29"] {
+            assert!(UtcDate::parse(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn time_only_round_trip() {
+        let (t, p) = UtcTimeOnly::parse(b"13:45:06.123").unwrap();
+        assert_eq!(t, UtcTimeOnly::new(49_506, 123_000_000));
+        assert_eq!(p, TimestampPrecision::Millis);
+        let (buf, len) = t.format(TimestampPrecision::Micros);
+        assert_eq!(&buf[..len], b"13:45:06.123000");
+        let (buf, len) = UtcTimeOnly::parse(b"23:59:60")
+            .unwrap()
+            .0
+            .format(TimestampPrecision::Seconds);
+        assert_eq!(&buf[..len], b"23:59:60");
+        assert_eq!(
+            UtcTimeOnly::from(UtcTimestamp::new(1_709_214_306, 5)),
+            UtcTimeOnly::new(49_506, 5)
+        );
+        for bad in [&b"24:00:00"[..], b"13:45", b"13:45:06.1", b"13-45-06"] {
+            assert!(UtcTimeOnly::parse(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_conversions() {
+        use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+        let (ts, _) = UtcTimestamp::parse(b"20240229-13:45:06.123456789").unwrap();
+        let dt = DateTime::<Utc>::try_from(ts).unwrap();
+        assert_eq!(dt.timestamp(), 1_709_214_306);
+        assert_eq!(dt.timestamp_subsec_nanos(), 123_456_789);
+        assert_eq!(UtcTimestamp::from(dt), ts);
+
+        let d = UtcDate::parse(b"20240229").unwrap();
+        let nd = NaiveDate::try_from(d).unwrap();
+        assert_eq!(nd, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+        assert_eq!(UtcDate::try_from(nd).unwrap(), d);
+
+        let (t, _) = UtcTimeOnly::parse(b"23:59:60.500").unwrap();
+        let nt = NaiveTime::try_from(t).unwrap();
+        assert_eq!(UtcTimeOnly::from(nt), t);
+    }
+}
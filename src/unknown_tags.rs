@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::dictionary::Dictionary;
+use crate::message::Message;
+use crate::tag::{self, Tag};
+
+/// Default number of distinct sample values kept per unknown tag.
+const DEFAULT_MAX_SAMPLES: usize = 4;
+
+/// What has been observed about one tag missing from the dictionary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownTag {
+    /// Number of occurrences across all observed messages.
+    pub count: u64,
+    /// Up to `max_samples` distinct values, in first-seen order.
+    pub samples: Vec<Vec<u8>>,
+    /// MsgType (35) values of the messages the tag appeared in.
+    pub msg_types: BTreeSet<Vec<u8>>,
+}
+
+/// Records tags that are not defined in the active [`Dictionary`].
+///
+/// Keep one collector per session and feed it every inbound message during
+/// certification; [`report`](Self::report) then lists each undocumented venue
+/// tag with its occurrence count, a few sample values and the message types
+/// it appeared in. Known tags cost one set lookup and no allocation.
+///
+/// # Example
+/// ```ignore
+/// let dict = Dictionary::fix44();
+/// let mut unknown = UnknownTagCollector::new();
+/// loop {
+///     let msg = decoder.decode(buf)?;
+///     unknown.observe(&dict, &msg);
+/// }
+/// for (tag, seen) in unknown.report() {
+///     println!("{tag}: {} times in {:?}", seen.count, seen.msg_types);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnknownTagCollector {
+    max_samples: usize,
+    tags: BTreeMap<Tag, UnknownTag>,
+}
+
+impl Default for UnknownTagCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UnknownTagCollector {
+    /// Create a collector keeping up to 4 sample values per tag.
+    pub fn new() -> Self {
+        Self::with_max_samples(DEFAULT_MAX_SAMPLES)
+    }
+
+    /// Create a collector keeping up to `max_samples` distinct sample values
+    /// per tag.
+    pub fn with_max_samples(max_samples: usize) -> Self {
+        Self {
+            max_samples,
+            tags: BTreeMap::new(),
+        }
+    }
+
+    /// Record every field of `msg` whose tag is not in `dict`.
+    pub fn observe(&mut self, dict: &Dictionary, msg: &Message<'_>) {
+        let mut msg_type: Option<&[u8]> = None;
+        for field in msg.fields() {
+            if field.tag == tag::MSG_TYPE {
+                msg_type = Some(field.value);
+            }
+            if dict.contains_tag(field.tag) {
+                continue;
+            }
+            let entry = self.tags.entry(field.tag).or_default();
+            entry.count += 1;
+            if entry.samples.len() < self.max_samples
+                && !entry.samples.iter().any(|s| s.as_slice() == field.value)
+            {
+                entry.samples.push(field.value.to_vec());
+            }
+            let ty = msg_type.or_else(|| msg.find(tag::MSG_TYPE).map(|f| f.value));
+            if let Some(ty) = ty
+                && !entry.msg_types.contains(ty)
+            {
+                entry.msg_types.insert(ty.to_vec());
+            }
+        }
+    }
+
+    /// Returns true if no unknown tag has been seen.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Observations for `tag`, if it was seen.
+    #[inline]
+    pub fn get(&self, tag: Tag) -> Option<&UnknownTag> {
+        self.tags.get(&tag)
+    }
+
+    /// Every unknown tag seen so far, in ascending tag order.
+    pub fn report(&self) -> impl Iterator<Item = (Tag, &UnknownTag)> + '_ {
+        self.tags.iter().map(|(&t, u)| (t, u))
+    }
+
+    /// Forget everything observed so far.
+    pub fn clear(&mut self) {
+        self.tags.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    #[test]
+    fn records_unknown_tags_with_counts_samples_and_types() {
+        let dict = Dictionary::fix44();
+        let mut c = UnknownTagCollector::with_max_samples(2);
+        let mut dec = Decoder::new();
+
+        for raw in [
+            "35=8|55=AAPL|9001=X|",
+            "35=8|9001=Y|9001=Y|",
+            "35=D|9001=Z|7777=1|",
+        ] {
+            let raw = fix(raw);
+            let msg = dec.decode(&raw).unwrap();
+            c.observe(&dict, &msg);
+        }
+
+        let seen = c.get(9001).unwrap();
+        assert_eq!(seen.count, 4);
+        assert_eq!(seen.samples, vec![b"X".to_vec(), b"Y".to_vec()]);
+        assert_eq!(
+            seen.msg_types.iter().cloned().collect::<Vec<_>>(),
+            vec![b"8".to_vec(), b"D".to_vec()]
+        );
+        assert!(c.get(tag::SYMBOL).is_none());
+        assert_eq!(
+            c.report().map(|(t, _)| t).collect::<Vec<_>>(),
+            vec![7777, 9001]
+        );
+    }
+
+    #[test]
+    fn registered_custom_tags_are_not_reported() {
+        let mut dict = Dictionary::fix44();
+        dict.add_tag(9001);
+        let mut c = UnknownTagCollector::new();
+        let raw = fix("35=8|9001=X|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        c.observe(&dict, &msg);
+        assert!(c.is_empty());
+    }
+}
@@ -0,0 +1,583 @@
+use std::collections::BTreeSet;
+
+use crate::dictionary::{Dictionary, MessageDef, is_header_tag, is_trailer_tag};
+use crate::message::Message;
+use crate::tag::{self, Tag};
+use crate::timestamp::{TimestampPolicy, UtcTimestamp};
+
+/// UTCTimestamp fields checked by [`Validator::check_timestamps`].
+const TIMESTAMP_TAGS: &[Tag] = &[
+    tag::SENDING_TIME,
+    tag::ORIG_SENDING_TIME,
+    tag::TRANSACT_TIME,
+];
+
+/// Header fields that must open every message, in this order.
+const LEADING_TAGS: [Tag; 3] = [tag::BEGIN_STRING, tag::BODY_LENGTH, tag::MSG_TYPE];
+
+/// SessionRejectReason (373) values, as sent back in a session-level Reject
+/// (35=3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionRejectReason {
+    InvalidTagNumber,
+    RequiredTagMissing,
+    TagNotDefinedForMessageType,
+    UndefinedTag,
+    TagSpecifiedWithoutValue,
+    ValueIsIncorrect,
+    IncorrectDataFormat,
+    DecryptionProblem,
+    SignatureProblem,
+    CompIdProblem,
+    SendingTimeAccuracyProblem,
+    InvalidMsgType,
+    XmlValidationError,
+    TagAppearsMoreThanOnce,
+    TagSpecifiedOutOfRequiredOrder,
+    RepeatingGroupFieldsOutOfOrder,
+    IncorrectNumInGroupCount,
+    NonDataValueIncludesFieldDelimiter,
+    Other,
+}
+
+impl SessionRejectReason {
+    /// The numeric value carried in tag 373.
+    pub fn code(self) -> u32 {
+        match self {
+            Self::InvalidTagNumber => 0,
+            Self::RequiredTagMissing => 1,
+            Self::TagNotDefinedForMessageType => 2,
+            Self::UndefinedTag => 3,
+            Self::TagSpecifiedWithoutValue => 4,
+            Self::ValueIsIncorrect => 5,
+            Self::IncorrectDataFormat => 6,
+            Self::DecryptionProblem => 7,
+            Self::SignatureProblem => 8,
+            Self::CompIdProblem => 9,
+            Self::SendingTimeAccuracyProblem => 10,
+            Self::InvalidMsgType => 11,
+            Self::XmlValidationError => 12,
+            Self::TagAppearsMoreThanOnce => 13,
+            Self::TagSpecifiedOutOfRequiredOrder => 14,
+            Self::RepeatingGroupFieldsOutOfOrder => 15,
+            Self::IncorrectNumInGroupCount => 16,
+            Self::NonDataValueIncludesFieldDelimiter => 17,
+            Self::Other => 99,
+        }
+    }
+
+    /// The reason for a tag 373 value, or `None` if `code` is not defined.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            0 => Self::InvalidTagNumber,
+            1 => Self::RequiredTagMissing,
+            2 => Self::TagNotDefinedForMessageType,
+            3 => Self::UndefinedTag,
+            4 => Self::TagSpecifiedWithoutValue,
+            5 => Self::ValueIsIncorrect,
+            6 => Self::IncorrectDataFormat,
+            7 => Self::DecryptionProblem,
+            8 => Self::SignatureProblem,
+            9 => Self::CompIdProblem,
+            10 => Self::SendingTimeAccuracyProblem,
+            11 => Self::InvalidMsgType,
+            12 => Self::XmlValidationError,
+            13 => Self::TagAppearsMoreThanOnce,
+            14 => Self::TagSpecifiedOutOfRequiredOrder,
+            15 => Self::RepeatingGroupFieldsOutOfOrder,
+            16 => Self::IncorrectNumInGroupCount,
+            17 => Self::NonDataValueIncludesFieldDelimiter,
+            99 => Self::Other,
+            _ => return None,
+        })
+    }
+}
+
+/// What to do with a field sent without a value (`58=` followed by SOH),
+/// which FIX forbids. Applied by [`Decoder::empty_values`] and
+/// [`Validator::empty_values`].
+///
+/// [`Decoder::empty_values`]: crate::decoder::Decoder::empty_values
+#[derive(Debug, Clone, Copy, Default)]
+pub enum EmptyValuePolicy {
+    /// Let the field through unchanged.
+    #[default]
+    Accept,
+    /// Fail: the decoder returns `FixError::EmptyValue`, the validator
+    /// reports [`SessionRejectReason::TagSpecifiedWithoutValue`] (4).
+    Reject,
+    /// Let the field through, calling the function with its tag first —
+    /// typically to log it.
+    Warn(fn(Tag)),
+}
+
+impl EmptyValuePolicy {
+    /// Apply the policy to the tags of the empty fields of one message;
+    /// `Err` carries the first tag rejected.
+    pub(crate) fn apply(self, empty: impl Iterator<Item = Tag>) -> Result<(), Tag> {
+        match self {
+            Self::Accept => Ok(()),
+            Self::Reject => empty.take(1).try_for_each(Err),
+            Self::Warn(warn) => {
+                empty.for_each(warn);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// One validation failure, pointing at the offending field.
+///
+/// `value` borrows the message buffer, so it is exactly the bytes that were
+/// received — suitable for logging or for building the Reject's Text (58).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation<'a> {
+    /// RefTagID (371) of the offending field.
+    pub tag: Tag,
+    pub reason: SessionRejectReason,
+    /// Offending value bytes (empty when the field is absent).
+    pub value: &'a [u8],
+}
+
+/// Checks decoded messages against a [`Dictionary`].
+///
+/// Violations are appended to a caller-owned `Vec`, so a session can reuse
+/// one buffer and a clean message costs no allocation.
+///
+/// # Example
+/// ```ignore
+/// let mut dict = Dictionary::fix44();
+/// dict.add_enum_values(tag::TIME_IN_FORCE, &[b"X"]); // venue extension
+/// let validator = Validator::new(&dict);
+/// let mut violations = Vec::new();
+/// if !validator.validate(&msg, &mut violations) {
+///     let v = violations[0];
+///     reject(v.tag, v.reason.code());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Validator<'d> {
+    dict: &'d Dictionary,
+    timestamps: Option<TimestampPolicy>,
+    empty_values: EmptyValuePolicy,
+    charsets: bool,
+}
+
+impl<'d> Validator<'d> {
+    pub fn new(dict: &'d Dictionary) -> Self {
+        Self {
+            dict,
+            timestamps: None,
+            empty_values: EmptyValuePolicy::Accept,
+            charsets: false,
+        }
+    }
+
+    /// Also check UTCTimestamp fields against `policy`; see
+    /// [`check_timestamps`](Self::check_timestamps).
+    pub fn timestamp_policy(mut self, policy: TimestampPolicy) -> Self {
+        self.timestamps = Some(policy);
+        self
+    }
+
+    /// Also check for fields without a value; see
+    /// [`check_empty_values`](Self::check_empty_values).
+    pub fn empty_values(mut self, policy: EmptyValuePolicy) -> Self {
+        self.empty_values = policy;
+        self
+    }
+
+    /// Also check value character sets; see
+    /// [`check_charsets`](Self::check_charsets).
+    pub fn charsets(mut self, check: bool) -> Self {
+        self.charsets = check;
+        self
+    }
+
+    /// The dictionary this validator checks against.
+    #[inline]
+    pub fn dictionary(&self) -> &'d Dictionary {
+        self.dict
+    }
+
+    /// Run every check on `msg`, appending violations to `out` in field
+    /// order. Returns true if no violation was found.
+    pub fn validate<'a>(&self, msg: &Message<'a>, out: &mut Vec<Violation<'a>>) -> bool {
+        let before = out.len();
+        self.check_empty_values(msg, out);
+        if self.charsets {
+            self.check_charsets(msg, out);
+        }
+        self.check_enums(msg, out);
+        if let Some(policy) = self.timestamps {
+            Self::check_timestamps(&policy, msg, UtcTimestamp::now(), out);
+        }
+        out.len() == before
+    }
+
+    /// Apply the [`EmptyValuePolicy`] to fields sent without a value. Under
+    /// `Reject` each is reported with
+    /// [`SessionRejectReason::TagSpecifiedWithoutValue`] (4); `Warn` calls
+    /// its function and reports nothing.
+    pub fn check_empty_values<'a>(&self, msg: &Message<'a>, out: &mut Vec<Violation<'a>>) {
+        let empty = msg.fields().filter(|f| f.value.is_empty()).map(|f| f.tag);
+        if let EmptyValuePolicy::Reject = self.empty_values {
+            out.extend(empty.map(|tag| Violation {
+                tag,
+                reason: SessionRejectReason::TagSpecifiedWithoutValue,
+                value: &[],
+            }));
+        } else {
+            let _ = self.empty_values.apply(empty);
+        }
+    }
+
+    /// Check each typed field's value against its
+    /// [`FieldType`](crate::dictionary::FieldType): digits for Int fields,
+    /// printable ASCII for String fields, and so on. Catches whitespace,
+    /// control characters and non-ASCII text from upstream systems.
+    /// Failures are reported with
+    /// [`SessionRejectReason::IncorrectDataFormat`] (6); `FieldType::invalid_byte`
+    /// on the reported value gives the offending position.
+    pub fn check_charsets<'a>(&self, msg: &Message<'a>, out: &mut Vec<Violation<'a>>) {
+        for field in msg.fields() {
+            let bad = self
+                .dict
+                .field_type(field.tag)
+                .and_then(|ty| ty.invalid_byte(field.value));
+            if bad.is_some() {
+                out.push(Violation {
+                    tag: field.tag,
+                    reason: SessionRejectReason::IncorrectDataFormat,
+                    value: field.value,
+                });
+            }
+        }
+    }
+
+    /// Check every enumerated field's value against the dictionary,
+    /// including user-registered custom values. Failures are reported with
+    /// [`SessionRejectReason::ValueIsIncorrect`] (5).
+    pub fn check_enums<'a>(&self, msg: &Message<'a>, out: &mut Vec<Violation<'a>>) {
+        for field in msg.fields() {
+            if !self.dict.is_valid_enum(field.tag, field.value) {
+                out.push(Violation {
+                    tag: field.tag,
+                    reason: SessionRejectReason::ValueIsIncorrect,
+                    value: field.value,
+                });
+            }
+        }
+    }
+
+    /// Check the structure of `msg` against the dictionary:
+    ///
+    /// - BeginString (8), BodyLength (9) and MsgType (35) open the message
+    ///   and CheckSum (10) closes it; a missing one is reported with
+    ///   [`SessionRejectReason::RequiredTagMissing`] (1), a misplaced one
+    ///   with [`SessionRejectReason::TagSpecifiedOutOfRequiredOrder`] (14);
+    /// - header fields after the body, and body fields after the trailer,
+    ///   are reported with reason 14;
+    /// - a tag the dictionary does not know is reported with
+    ///   [`SessionRejectReason::UndefinedTag`] (3).
+    ///
+    /// When the dictionary defines the message type, a body field that is
+    /// neither in its layout nor a member of one of its groups is reported
+    /// with [`SessionRejectReason::TagNotDefinedForMessageType`] (2), and
+    /// every absent required field with reason 1.
+    pub fn check_layout<'a>(&self, msg: &Message<'a>, out: &mut Vec<Violation<'a>>) {
+        let n = msg.len();
+        let missing = |tag| Violation {
+            tag,
+            reason: SessionRejectReason::RequiredTagMissing,
+            value: &[],
+        };
+        for (i, t) in LEADING_TAGS.into_iter().enumerate() {
+            match msg.find(t) {
+                None => out.push(missing(t)),
+                Some(f) if i >= n || msg.field(i).tag != t => out.push(Violation {
+                    tag: t,
+                    reason: SessionRejectReason::TagSpecifiedOutOfRequiredOrder,
+                    value: f.value,
+                }),
+                Some(_) => {}
+            }
+        }
+
+        let def = msg
+            .find(tag::MSG_TYPE)
+            .and_then(|f| self.dict.message(f.value));
+        let body = def.map(|def| self.body_tags(def));
+        let mut in_body = false;
+        let mut in_trailer = false;
+        for (i, field) in msg.fields().enumerate() {
+            let t = field.tag;
+            let reason = if LEADING_TAGS.contains(&t) {
+                continue;
+            } else if !self.dict.contains_tag(t) {
+                SessionRejectReason::UndefinedTag
+            } else if is_header_tag(t) {
+                if !(in_body || in_trailer) {
+                    continue;
+                }
+                SessionRejectReason::TagSpecifiedOutOfRequiredOrder
+            } else if is_trailer_tag(t) {
+                in_trailer = true;
+                if t != tag::CHECK_SUM || i == n - 1 {
+                    continue;
+                }
+                SessionRejectReason::TagSpecifiedOutOfRequiredOrder
+            } else if in_trailer {
+                SessionRejectReason::TagSpecifiedOutOfRequiredOrder
+            } else {
+                in_body = true;
+                if body.as_ref().is_none_or(|b| b.contains(&t)) {
+                    continue;
+                }
+                SessionRejectReason::TagNotDefinedForMessageType
+            };
+            out.push(Violation {
+                tag: t,
+                reason,
+                value: field.value,
+            });
+        }
+        if msg.find(tag::CHECK_SUM).is_none() {
+            out.push(missing(tag::CHECK_SUM));
+        }
+
+        if let Some(def) = def {
+            for &t in &def.required {
+                if msg.find(t).is_none() {
+                    out.push(missing(t));
+                }
+            }
+        }
+    }
+
+    /// Every tag legal in the body of `def`: its fields and the members of
+    /// its groups, nested groups included.
+    fn body_tags(&self, def: &MessageDef) -> BTreeSet<Tag> {
+        let mut tags: BTreeSet<Tag> = def.fields.iter().copied().collect();
+        let mut pending: Vec<Tag> = def.fields.clone();
+        while let Some(t) = pending.pop() {
+            if let Some(group) = self.dict.group(t) {
+                for &m in &group.member_tags {
+                    if tags.insert(m) {
+                        pending.push(m);
+                    }
+                }
+            }
+        }
+        tags
+    }
+
+    /// Check SendingTime (52), OrigSendingTime (122) and TransactTime (60)
+    /// against `policy`, with `now` as the local clock:
+    ///
+    /// - a value that is not a UTCTimestamp, or carries more fractional
+    ///   digits than `policy.max_inbound`, is reported with
+    ///   [`SessionRejectReason::IncorrectDataFormat`] (6);
+    /// - a SendingTime further than `policy.max_skew` from `now` is reported
+    ///   with [`SessionRejectReason::SendingTimeAccuracyProblem`] (10).
+    pub fn check_timestamps<'a>(
+        policy: &TimestampPolicy,
+        msg: &Message<'a>,
+        now: UtcTimestamp,
+        out: &mut Vec<Violation<'a>>,
+    ) {
+        for field in msg.fields() {
+            if !TIMESTAMP_TAGS.contains(&field.tag) {
+                continue;
+            }
+            let reason = match UtcTimestamp::parse(field.value) {
+                Ok((_, precision)) if precision > policy.max_inbound => {
+                    SessionRejectReason::IncorrectDataFormat
+                }
+                Ok((ts, _)) => match policy.max_skew {
+                    Some(max) if field.tag == tag::SENDING_TIME && ts.abs_diff(&now) > max => {
+                        SessionRejectReason::SendingTimeAccuracyProblem
+                    }
+                    _ => continue,
+                },
+                Err(_) => SessionRejectReason::IncorrectDataFormat,
+            };
+            out.push(Violation {
+                tag: field.tag,
+                reason,
+                value: field.value,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::tag;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    #[test]
+    fn enum_violation_reports_offending_bytes() {
+        let dict = Dictionary::fix44();
+        let raw = fix("35=D|21=1|59=X|43=N|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut out = Vec::new();
+        assert!(!Validator::new(&dict).validate(&msg, &mut out));
+        assert_eq!(
+            out,
+            vec![Violation {
+                tag: tag::TIME_IN_FORCE,
+                reason: SessionRejectReason::ValueIsIncorrect,
+                value: b"X",
+            }]
+        );
+        assert_eq!(out[0].reason.code(), 5);
+    }
+
+    #[test]
+    fn custom_enum_values_are_accepted() {
+        let mut dict = Dictionary::fix44();
+        dict.add_enum_values(tag::TIME_IN_FORCE, &[b"X"]);
+        let raw = fix("35=D|59=X|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut out = Vec::new();
+        assert!(Validator::new(&dict).validate(&msg, &mut out));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn layout_violations() {
+        let mut dict = Dictionary::fix44();
+        dict.define_message(
+            b"D",
+            &[
+                tag::CL_ORD_ID,
+                tag::NO_PARTY_IDS,
+                tag::SYMBOL,
+                tag::SIDE,
+                tag::ORD_TYPE,
+            ],
+            &[tag::CL_ORD_ID, tag::SIDE, tag::ORD_TYPE],
+        );
+        let validator = Validator::new(&dict);
+        let mut dec = Decoder::new();
+
+        let raw = fix("8=FIX.4.4|9=5|35=D|49=A|11=1|453=1|448=P|55=X|54=1|40=1|10=000|");
+        let msg = dec.decode(&raw).unwrap();
+        let mut out = Vec::new();
+        validator.check_layout(&msg, &mut out);
+        assert!(out.is_empty(), "{out:?}");
+
+        let raw = fix("9=5|8=FIX.4.4|35=D|11=1|52=X|44=1|9999=1|10=000|54=1|");
+        let msg = dec.decode(&raw).unwrap();
+        let mut out = Vec::new();
+        validator.check_layout(&msg, &mut out);
+        let found: Vec<_> = out.iter().map(|v| (v.tag, v.reason.code())).collect();
+        assert_eq!(
+            found,
+            [
+                (tag::BEGIN_STRING, 14),
+                (tag::BODY_LENGTH, 14),
+                (tag::SENDING_TIME, 14),
+                (tag::PRICE, 2),
+                (9999, 3),
+                (tag::CHECK_SUM, 14),
+                (tag::SIDE, 14),
+                (tag::ORD_TYPE, 1),
+            ]
+        );
+        assert_eq!(out[4].value, b"1");
+    }
+
+    #[test]
+    fn empty_value_policies() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        static WARNED: AtomicU32 = AtomicU32::new(0);
+
+        let dict = Dictionary::fix44();
+        let raw = fix("35=D|58=|55=X|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut out = Vec::new();
+        assert!(Validator::new(&dict).validate(&msg, &mut out));
+        let validator = Validator::new(&dict).empty_values(EmptyValuePolicy::Reject);
+        assert!(!validator.validate(&msg, &mut out));
+        assert_eq!(out[0].tag, tag::TEXT);
+        assert_eq!(out[0].reason.code(), 4);
+
+        let mut out = Vec::new();
+        let warn = EmptyValuePolicy::Warn(|t| WARNED.store(t, Ordering::Relaxed));
+        assert!(
+            Validator::new(&dict)
+                .empty_values(warn)
+                .validate(&msg, &mut out)
+        );
+        assert_eq!(WARNED.load(Ordering::Relaxed), tag::TEXT);
+    }
+
+    #[test]
+    fn charset_violations() {
+        let dict = Dictionary::fix44();
+        let raw = fix("34= 7|35=D|55=AAPL\t|38=100|44=1.5|9001=\u{e9}|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let mut out = Vec::new();
+        assert!(Validator::new(&dict).validate(&msg, &mut out));
+        assert!(
+            !Validator::new(&dict)
+                .charsets(true)
+                .validate(&msg, &mut out)
+        );
+        let found: Vec<_> = out.iter().map(|v| (v.tag, v.reason.code())).collect();
+        assert_eq!(found, [(tag::MSG_SEQ_NUM, 6), (tag::SYMBOL, 6)]);
+        assert_eq!(out[1].value, b"AAPL\t");
+    }
+
+    #[test]
+    fn timestamp_precision_and_skew() {
+        use crate::timestamp::TimestampPrecision;
+        use std::time::Duration;
+
+        let policy = TimestampPolicy {
+            outbound: TimestampPrecision::Millis,
+            max_inbound: TimestampPrecision::Millis,
+            max_skew: Some(Duration::from_secs(30)),
+        };
+        let now = UtcTimestamp::parse(b"20240229-13:45:00.000").unwrap().0;
+        let raw =
+            fix("35=D|52=20240229-13:45:10.5|122=20240229-13:40:00|60=20240229-13:45:06.123456|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let mut out = Vec::new();
+        Validator::check_timestamps(&policy, &msg, now, &mut out);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].tag, tag::SENDING_TIME);
+        assert_eq!(out[0].reason.code(), 6);
+        assert_eq!(out[1].tag, tag::TRANSACT_TIME);
+        assert_eq!(out[1].value, b"20240229-13:45:06.123456");
+
+        let raw = fix("35=D|52=20240229-13:46:00.000|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let mut out = Vec::new();
+        Validator::check_timestamps(&policy, &msg, now, &mut out);
+        assert_eq!(
+            out[0].reason,
+            SessionRejectReason::SendingTimeAccuracyProblem
+        );
+        assert_eq!(out[0].reason.code(), 10);
+    }
+}
@@ -0,0 +1,683 @@
+//! Typed values for common enumerated fields.
+//!
+//! Each enum converts to and from the single character carried on the
+//! wire: [`code`](Side::code) / [`from_code`](Side::from_code) for the
+//! byte, [`as_bytes`](Side::as_bytes) and `TryFrom<&[u8]>` for field
+//! values.
+//!
+//! ```ignore
+//! let side = Side::try_from(msg.find(tag::SIDE)?.value)?;
+//! b.push(tag::ORD_TYPE, OrdType::Limit.as_bytes());
+//! ```
+
+use crate::error::FixError;
+
+/// Side (54) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Buy,
+    Sell,
+    BuyMinus,
+    SellPlus,
+    SellShort,
+    SellShortExempt,
+    Undisclosed,
+    Cross,
+    CrossShort,
+    CrossShortExempt,
+    AsDefined,
+    Opposite,
+    Subscribe,
+    Redeem,
+    Lend,
+    Borrow,
+}
+
+impl Side {
+    /// The character carried in tag 54.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Buy => b'1',
+            Self::Sell => b'2',
+            Self::BuyMinus => b'3',
+            Self::SellPlus => b'4',
+            Self::SellShort => b'5',
+            Self::SellShortExempt => b'6',
+            Self::Undisclosed => b'7',
+            Self::Cross => b'8',
+            Self::CrossShort => b'9',
+            Self::CrossShortExempt => b'A',
+            Self::AsDefined => b'B',
+            Self::Opposite => b'C',
+            Self::Subscribe => b'D',
+            Self::Redeem => b'E',
+            Self::Lend => b'F',
+            Self::Borrow => b'G',
+        }
+    }
+
+    /// The tag 54 value.
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Buy => b"1",
+            Self::Sell => b"2",
+            Self::BuyMinus => b"3",
+            Self::SellPlus => b"4",
+            Self::SellShort => b"5",
+            Self::SellShortExempt => b"6",
+            Self::Undisclosed => b"7",
+            Self::Cross => b"8",
+            Self::CrossShort => b"9",
+            Self::CrossShortExempt => b"A",
+            Self::AsDefined => b"B",
+            Self::Opposite => b"C",
+            Self::Subscribe => b"D",
+            Self::Redeem => b"E",
+            Self::Lend => b"F",
+            Self::Borrow => b"G",
+        }
+    }
+
+    /// The value for a tag 54 character, or `None` if `code` is not
+    /// defined.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            b'1' => Self::Buy,
+            b'2' => Self::Sell,
+            b'3' => Self::BuyMinus,
+            b'4' => Self::SellPlus,
+            b'5' => Self::SellShort,
+            b'6' => Self::SellShortExempt,
+            b'7' => Self::Undisclosed,
+            b'8' => Self::Cross,
+            b'9' => Self::CrossShort,
+            b'A' => Self::CrossShortExempt,
+            b'B' => Self::AsDefined,
+            b'C' => Self::Opposite,
+            b'D' => Self::Subscribe,
+            b'E' => Self::Redeem,
+            b'F' => Self::Lend,
+            b'G' => Self::Borrow,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for Side {
+    type Error = FixError;
+
+    fn try_from(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            &[c] => Self::from_code(c).ok_or(FixError::InvalidValue),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+/// OrdType (40) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrdType {
+    Market,
+    Limit,
+    Stop,
+    StopLimit,
+    MarketOnClose,
+    WithOrWithout,
+    LimitOrBetter,
+    LimitWithOrWithout,
+    OnBasis,
+    OnClose,
+    LimitOnClose,
+    ForexMarket,
+    PreviouslyQuoted,
+    PreviouslyIndicated,
+    ForexLimit,
+    ForexSwap,
+    ForexPreviouslyQuoted,
+    Funari,
+    MarketIfTouched,
+    MarketWithLeftOverAsLimit,
+    PreviousFundValuationPoint,
+    NextFundValuationPoint,
+    Pegged,
+}
+
+impl OrdType {
+    /// The character carried in tag 40.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Market => b'1',
+            Self::Limit => b'2',
+            Self::Stop => b'3',
+            Self::StopLimit => b'4',
+            Self::MarketOnClose => b'5',
+            Self::WithOrWithout => b'6',
+            Self::LimitOrBetter => b'7',
+            Self::LimitWithOrWithout => b'8',
+            Self::OnBasis => b'9',
+            Self::OnClose => b'A',
+            Self::LimitOnClose => b'B',
+            Self::ForexMarket => b'C',
+            Self::PreviouslyQuoted => b'D',
+            Self::PreviouslyIndicated => b'E',
+            Self::ForexLimit => b'F',
+            Self::ForexSwap => b'G',
+            Self::ForexPreviouslyQuoted => b'H',
+            Self::Funari => b'I',
+            Self::MarketIfTouched => b'J',
+            Self::MarketWithLeftOverAsLimit => b'K',
+            Self::PreviousFundValuationPoint => b'L',
+            Self::NextFundValuationPoint => b'M',
+            Self::Pegged => b'P',
+        }
+    }
+
+    /// The tag 40 value.
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Market => b"1",
+            Self::Limit => b"2",
+            Self::Stop => b"3",
+            Self::StopLimit => b"4",
+            Self::MarketOnClose => b"5",
+            Self::WithOrWithout => b"6",
+            Self::LimitOrBetter => b"7",
+            Self::LimitWithOrWithout => b"8",
+            Self::OnBasis => b"9",
+            Self::OnClose => b"A",
+            Self::LimitOnClose => b"B",
+            Self::ForexMarket => b"C",
+            Self::PreviouslyQuoted => b"D",
+            Self::PreviouslyIndicated => b"E",
+            Self::ForexLimit => b"F",
+            Self::ForexSwap => b"G",
+            Self::ForexPreviouslyQuoted => b"H",
+            Self::Funari => b"I",
+            Self::MarketIfTouched => b"J",
+            Self::MarketWithLeftOverAsLimit => b"K",
+            Self::PreviousFundValuationPoint => b"L",
+            Self::NextFundValuationPoint => b"M",
+            Self::Pegged => b"P",
+        }
+    }
+
+    /// The value for a tag 40 character, or `None` if `code` is not
+    /// defined.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            b'1' => Self::Market,
+            b'2' => Self::Limit,
+            b'3' => Self::Stop,
+            b'4' => Self::StopLimit,
+            b'5' => Self::MarketOnClose,
+            b'6' => Self::WithOrWithout,
+            b'7' => Self::LimitOrBetter,
+            b'8' => Self::LimitWithOrWithout,
+            b'9' => Self::OnBasis,
+            b'A' => Self::OnClose,
+            b'B' => Self::LimitOnClose,
+            b'C' => Self::ForexMarket,
+            b'D' => Self::PreviouslyQuoted,
+            b'E' => Self::PreviouslyIndicated,
+            b'F' => Self::ForexLimit,
+            b'G' => Self::ForexSwap,
+            b'H' => Self::ForexPreviouslyQuoted,
+            b'I' => Self::Funari,
+            b'J' => Self::MarketIfTouched,
+            b'K' => Self::MarketWithLeftOverAsLimit,
+            b'L' => Self::PreviousFundValuationPoint,
+            b'M' => Self::NextFundValuationPoint,
+            b'P' => Self::Pegged,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for OrdType {
+    type Error = FixError;
+
+    fn try_from(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            &[c] => Self::from_code(c).ok_or(FixError::InvalidValue),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+/// TimeInForce (59) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeInForce {
+    Day,
+    GoodTillCancel,
+    AtTheOpening,
+    ImmediateOrCancel,
+    FillOrKill,
+    GoodTillCrossing,
+    GoodTillDate,
+    AtTheClose,
+}
+
+impl TimeInForce {
+    /// The character carried in tag 59.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Day => b'0',
+            Self::GoodTillCancel => b'1',
+            Self::AtTheOpening => b'2',
+            Self::ImmediateOrCancel => b'3',
+            Self::FillOrKill => b'4',
+            Self::GoodTillCrossing => b'5',
+            Self::GoodTillDate => b'6',
+            Self::AtTheClose => b'7',
+        }
+    }
+
+    /// The tag 59 value.
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Day => b"0",
+            Self::GoodTillCancel => b"1",
+            Self::AtTheOpening => b"2",
+            Self::ImmediateOrCancel => b"3",
+            Self::FillOrKill => b"4",
+            Self::GoodTillCrossing => b"5",
+            Self::GoodTillDate => b"6",
+            Self::AtTheClose => b"7",
+        }
+    }
+
+    /// The value for a tag 59 character, or `None` if `code` is not
+    /// defined.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            b'0' => Self::Day,
+            b'1' => Self::GoodTillCancel,
+            b'2' => Self::AtTheOpening,
+            b'3' => Self::ImmediateOrCancel,
+            b'4' => Self::FillOrKill,
+            b'5' => Self::GoodTillCrossing,
+            b'6' => Self::GoodTillDate,
+            b'7' => Self::AtTheClose,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for TimeInForce {
+    type Error = FixError;
+
+    fn try_from(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            &[c] => Self::from_code(c).ok_or(FixError::InvalidValue),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+/// ExecType (150) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExecType {
+    New,
+    PartialFill,
+    Fill,
+    DoneForDay,
+    Canceled,
+    Replaced,
+    PendingCancel,
+    Stopped,
+    Rejected,
+    Suspended,
+    PendingNew,
+    Calculated,
+    Expired,
+    Restated,
+    PendingReplace,
+    Trade,
+    TradeCorrect,
+    TradeCancel,
+    OrderStatus,
+}
+
+impl ExecType {
+    /// The character carried in tag 150.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::New => b'0',
+            Self::PartialFill => b'1',
+            Self::Fill => b'2',
+            Self::DoneForDay => b'3',
+            Self::Canceled => b'4',
+            Self::Replaced => b'5',
+            Self::PendingCancel => b'6',
+            Self::Stopped => b'7',
+            Self::Rejected => b'8',
+            Self::Suspended => b'9',
+            Self::PendingNew => b'A',
+            Self::Calculated => b'B',
+            Self::Expired => b'C',
+            Self::Restated => b'D',
+            Self::PendingReplace => b'E',
+            Self::Trade => b'F',
+            Self::TradeCorrect => b'G',
+            Self::TradeCancel => b'H',
+            Self::OrderStatus => b'I',
+        }
+    }
+
+    /// The tag 150 value.
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::New => b"0",
+            Self::PartialFill => b"1",
+            Self::Fill => b"2",
+            Self::DoneForDay => b"3",
+            Self::Canceled => b"4",
+            Self::Replaced => b"5",
+            Self::PendingCancel => b"6",
+            Self::Stopped => b"7",
+            Self::Rejected => b"8",
+            Self::Suspended => b"9",
+            Self::PendingNew => b"A",
+            Self::Calculated => b"B",
+            Self::Expired => b"C",
+            Self::Restated => b"D",
+            Self::PendingReplace => b"E",
+            Self::Trade => b"F",
+            Self::TradeCorrect => b"G",
+            Self::TradeCancel => b"H",
+            Self::OrderStatus => b"I",
+        }
+    }
+
+    /// The value for a tag 150 character, or `None` if `code` is not
+    /// defined.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            b'0' => Self::New,
+            b'1' => Self::PartialFill,
+            b'2' => Self::Fill,
+            b'3' => Self::DoneForDay,
+            b'4' => Self::Canceled,
+            b'5' => Self::Replaced,
+            b'6' => Self::PendingCancel,
+            b'7' => Self::Stopped,
+            b'8' => Self::Rejected,
+            b'9' => Self::Suspended,
+            b'A' => Self::PendingNew,
+            b'B' => Self::Calculated,
+            b'C' => Self::Expired,
+            b'D' => Self::Restated,
+            b'E' => Self::PendingReplace,
+            b'F' => Self::Trade,
+            b'G' => Self::TradeCorrect,
+            b'H' => Self::TradeCancel,
+            b'I' => Self::OrderStatus,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for ExecType {
+    type Error = FixError;
+
+    fn try_from(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            &[c] => Self::from_code(c).ok_or(FixError::InvalidValue),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+/// OrdStatus (39) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrdStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    DoneForDay,
+    Canceled,
+    Replaced,
+    PendingCancel,
+    Stopped,
+    Rejected,
+    Suspended,
+    PendingNew,
+    Calculated,
+    Expired,
+    AcceptedForBidding,
+    PendingReplace,
+}
+
+impl OrdStatus {
+    /// The character carried in tag 39.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::New => b'0',
+            Self::PartiallyFilled => b'1',
+            Self::Filled => b'2',
+            Self::DoneForDay => b'3',
+            Self::Canceled => b'4',
+            Self::Replaced => b'5',
+            Self::PendingCancel => b'6',
+            Self::Stopped => b'7',
+            Self::Rejected => b'8',
+            Self::Suspended => b'9',
+            Self::PendingNew => b'A',
+            Self::Calculated => b'B',
+            Self::Expired => b'C',
+            Self::AcceptedForBidding => b'D',
+            Self::PendingReplace => b'E',
+        }
+    }
+
+    /// The tag 39 value.
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::New => b"0",
+            Self::PartiallyFilled => b"1",
+            Self::Filled => b"2",
+            Self::DoneForDay => b"3",
+            Self::Canceled => b"4",
+            Self::Replaced => b"5",
+            Self::PendingCancel => b"6",
+            Self::Stopped => b"7",
+            Self::Rejected => b"8",
+            Self::Suspended => b"9",
+            Self::PendingNew => b"A",
+            Self::Calculated => b"B",
+            Self::Expired => b"C",
+            Self::AcceptedForBidding => b"D",
+            Self::PendingReplace => b"E",
+        }
+    }
+
+    /// The value for a tag 39 character, or `None` if `code` is not
+    /// defined.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            b'0' => Self::New,
+            b'1' => Self::PartiallyFilled,
+            b'2' => Self::Filled,
+            b'3' => Self::DoneForDay,
+            b'4' => Self::Canceled,
+            b'5' => Self::Replaced,
+            b'6' => Self::PendingCancel,
+            b'7' => Self::Stopped,
+            b'8' => Self::Rejected,
+            b'9' => Self::Suspended,
+            b'A' => Self::PendingNew,
+            b'B' => Self::Calculated,
+            b'C' => Self::Expired,
+            b'D' => Self::AcceptedForBidding,
+            b'E' => Self::PendingReplace,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for OrdStatus {
+    type Error = FixError;
+
+    fn try_from(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            &[c] => Self::from_code(c).ok_or(FixError::InvalidValue),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+/// MDEntryType (269) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MdEntryType {
+    Bid,
+    Offer,
+    Trade,
+    IndexValue,
+    OpeningPrice,
+    ClosingPrice,
+    SettlementPrice,
+    TradingSessionHighPrice,
+    TradingSessionLowPrice,
+    TradingSessionVwapPrice,
+    Imbalance,
+    TradeVolume,
+    OpenInterest,
+}
+
+impl MdEntryType {
+    /// The character carried in tag 269.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Bid => b'0',
+            Self::Offer => b'1',
+            Self::Trade => b'2',
+            Self::IndexValue => b'3',
+            Self::OpeningPrice => b'4',
+            Self::ClosingPrice => b'5',
+            Self::SettlementPrice => b'6',
+            Self::TradingSessionHighPrice => b'7',
+            Self::TradingSessionLowPrice => b'8',
+            Self::TradingSessionVwapPrice => b'9',
+            Self::Imbalance => b'A',
+            Self::TradeVolume => b'B',
+            Self::OpenInterest => b'C',
+        }
+    }
+
+    /// The tag 269 value.
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Bid => b"0",
+            Self::Offer => b"1",
+            Self::Trade => b"2",
+            Self::IndexValue => b"3",
+            Self::OpeningPrice => b"4",
+            Self::ClosingPrice => b"5",
+            Self::SettlementPrice => b"6",
+            Self::TradingSessionHighPrice => b"7",
+            Self::TradingSessionLowPrice => b"8",
+            Self::TradingSessionVwapPrice => b"9",
+            Self::Imbalance => b"A",
+            Self::TradeVolume => b"B",
+            Self::OpenInterest => b"C",
+        }
+    }
+
+    /// The value for a tag 269 character, or `None` if `code` is not
+    /// defined.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            b'0' => Self::Bid,
+            b'1' => Self::Offer,
+            b'2' => Self::Trade,
+            b'3' => Self::IndexValue,
+            b'4' => Self::OpeningPrice,
+            b'5' => Self::ClosingPrice,
+            b'6' => Self::SettlementPrice,
+            b'7' => Self::TradingSessionHighPrice,
+            b'8' => Self::TradingSessionLowPrice,
+            b'9' => Self::TradingSessionVwapPrice,
+            b'A' => Self::Imbalance,
+            b'B' => Self::TradeVolume,
+            b'C' => Self::OpenInterest,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for MdEntryType {
+    type Error = FixError;
+
+    fn try_from(value: &[u8]) -> Result<Self, FixError> {
+        match value {
+            &[c] => Self::from_code(c).ok_or(FixError::InvalidValue),
+            _ => Err(FixError::InvalidValue),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::tag;
+
+    #[test]
+    fn codes_round_trip() {
+        for c in 0..=u8::MAX {
+            if let Some(v) = Side::from_code(c) {
+                assert_eq!(v.code(), c);
+                assert_eq!(Side::try_from(v.as_bytes()).unwrap(), v);
+            }
+            if let Some(v) = OrdType::from_code(c) {
+                assert_eq!(v.code(), c);
+                assert_eq!(OrdType::try_from(v.as_bytes()).unwrap(), v);
+            }
+            if let Some(v) = TimeInForce::from_code(c) {
+                assert_eq!(v.code(), c);
+                assert_eq!(TimeInForce::try_from(v.as_bytes()).unwrap(), v);
+            }
+            if let Some(v) = ExecType::from_code(c) {
+                assert_eq!(v.code(), c);
+                assert_eq!(ExecType::try_from(v.as_bytes()).unwrap(), v);
+            }
+            if let Some(v) = OrdStatus::from_code(c) {
+                assert_eq!(v.code(), c);
+                assert_eq!(OrdStatus::try_from(v.as_bytes()).unwrap(), v);
+            }
+            if let Some(v) = MdEntryType::from_code(c) {
+                assert_eq!(v.code(), c);
+                assert_eq!(MdEntryType::try_from(v.as_bytes()).unwrap(), v);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_field_values() {
+        let mut dec = Decoder::new();
+        let msg = dec
+            .decode(b"35=8\x0154=2\x0140=2\x0159=3\x01150=F\x0139=1\x01")
+            .unwrap();
+        let field = |t| msg.find(t).unwrap().value;
+        assert_eq!(Side::try_from(field(tag::SIDE)).unwrap(), Side::Sell);
+        assert_eq!(
+            OrdType::try_from(field(tag::ORD_TYPE)).unwrap(),
+            OrdType::Limit
+        );
+        assert_eq!(
+            TimeInForce::try_from(field(tag::TIME_IN_FORCE)).unwrap(),
+            TimeInForce::ImmediateOrCancel
+        );
+        assert_eq!(
+            ExecType::try_from(field(tag::EXEC_TYPE)).unwrap(),
+            ExecType::Trade
+        );
+        assert_eq!(
+            OrdStatus::try_from(field(tag::ORD_STATUS)).unwrap(),
+            OrdStatus::PartiallyFilled
+        );
+        assert!(Side::try_from(&b"12"[..]).is_err());
+        assert!(Side::try_from(&b""[..]).is_err());
+        assert!(TimeInForce::try_from(&b"9"[..]).is_err());
+    }
+}
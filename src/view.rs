@@ -0,0 +1,283 @@
+use crate::field::{Field, Fields};
+use crate::group::{Group, GroupIter, GroupSpec};
+use crate::message::Message;
+use crate::owned::OwnedMessage;
+use crate::tag::Tag;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Read access shared by every zero-copy field container in this crate.
+///
+/// Implemented by [`Message`], [`&OwnedMessage`](OwnedMessage), [`Group`]
+/// and [`Section`], so helpers such as validators,
+/// pretty-printers and converters can be written once:
+///
+/// ```ignore
+/// fn symbol<'a>(v: &impl FieldsView<'a>) -> Option<&'a [u8]> {
+///     v.find(tag::SYMBOL).map(|f| f.value)
+/// }
+/// ```
+///
+/// The trait is sealed: the set of views is defined by this crate, which
+/// keeps the door open for adding methods without breaking downstream code.
+pub trait FieldsView<'a>: private::Sealed {
+    /// Number of fields.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no fields.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The field at `index`. Panics if `index >= self.len()`.
+    fn field(&self, index: usize) -> Field<'a>;
+
+    /// All fields in wire order.
+    fn fields(&self) -> Fields<'a>;
+
+    /// The first field with `tag`, or `None`.
+    fn find(&self, tag: Tag) -> Option<Field<'a>>;
+
+    /// Instances of the repeating group described by `spec`.
+    fn groups(&self, spec: &GroupSpec) -> GroupIter<'a>;
+}
+
+/// A contiguous run of a message's fields: the standard header, the body or
+/// the trailer, as returned by [`Message::header`], [`Message::body`] and
+/// [`Message::trailer`].
+#[derive(Debug, Clone, Copy)]
+pub struct Section<'a> {
+    pub(crate) buf: &'a [u8],
+    pub(crate) offsets: &'a [(Tag, u32, u32)],
+}
+
+impl<'a> Section<'a> {
+    #[inline]
+    fn as_group(&self) -> Group<'a> {
+        Group {
+            buf: self.buf,
+            offsets: self.offsets,
+        }
+    }
+
+    /// Number of fields in the section.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if the section has no fields.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// The field at `index` within the section. Panics if out of range.
+    #[inline]
+    pub fn field(&self, index: usize) -> Field<'a> {
+        self.as_group().field(index)
+    }
+
+    /// The section's fields in wire order.
+    #[inline]
+    pub fn fields(&self) -> Fields<'a> {
+        Fields::new(self.buf, self.offsets)
+    }
+
+    /// The first field with `tag` in the section, or `None`. A linear scan:
+    /// sections are short, and the message's sorted index covers every
+    /// section at once.
+    #[inline]
+    pub fn find(&self, tag: Tag) -> Option<Field<'a>> {
+        self.as_group().find(tag)
+    }
+
+    /// Instances of the repeating group described by `spec` within the
+    /// section — e.g. NO_HOPS (627) in the header.
+    #[inline]
+    pub fn groups(&self, spec: &GroupSpec) -> GroupIter<'a> {
+        self.as_group().groups(spec)
+    }
+}
+
+impl private::Sealed for Section<'_> {}
+
+impl<'a> FieldsView<'a> for Section<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        Section::len(self)
+    }
+
+    #[inline]
+    fn field(&self, index: usize) -> Field<'a> {
+        Section::field(self, index)
+    }
+
+    #[inline]
+    fn fields(&self) -> Fields<'a> {
+        Section::fields(self)
+    }
+
+    #[inline]
+    fn find(&self, tag: Tag) -> Option<Field<'a>> {
+        Section::find(self, tag)
+    }
+
+    #[inline]
+    fn groups(&self, spec: &GroupSpec) -> GroupIter<'a> {
+        Section::groups(self, spec)
+    }
+}
+
+impl private::Sealed for Message<'_> {}
+
+impl<'a> FieldsView<'a> for Message<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        Message::len(self)
+    }
+
+    #[inline]
+    fn field(&self, index: usize) -> Field<'a> {
+        Message::field(self, index)
+    }
+
+    #[inline]
+    fn fields(&self) -> Fields<'a> {
+        Message::fields(self)
+    }
+
+    #[inline]
+    fn find(&self, tag: Tag) -> Option<Field<'a>> {
+        Message::find(self, tag)
+    }
+
+    #[inline]
+    fn groups(&self, spec: &GroupSpec) -> GroupIter<'a> {
+        Message::groups(self, spec)
+    }
+}
+
+impl private::Sealed for Group<'_> {}
+
+impl<'a> FieldsView<'a> for Group<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        Group::len(self)
+    }
+
+    #[inline]
+    fn field(&self, index: usize) -> Field<'a> {
+        Group::field(self, index)
+    }
+
+    #[inline]
+    fn fields(&self) -> Fields<'a> {
+        Group::fields(self)
+    }
+
+    #[inline]
+    fn find(&self, tag: Tag) -> Option<Field<'a>> {
+        Group::find(self, tag)
+    }
+
+    #[inline]
+    fn groups(&self, spec: &GroupSpec) -> GroupIter<'a> {
+        Group::groups(self, spec)
+    }
+}
+
+impl private::Sealed for &OwnedMessage {}
+
+impl<'a> FieldsView<'a> for &'a OwnedMessage {
+    #[inline]
+    fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    #[inline]
+    fn field(&self, index: usize) -> Field<'a> {
+        let (tag, start, end) = self.offsets[index];
+        Field {
+            tag,
+            value: &self.buf[start as usize..end as usize],
+        }
+    }
+
+    #[inline]
+    fn fields(&self) -> Fields<'a> {
+        Fields::new(&self.buf, &self.offsets)
+    }
+
+    /// Binary search of the tag index built by [`Message::to_owned`].
+    #[inline]
+    fn find(&self, tag: Tag) -> Option<Field<'a>> {
+        let idx = self.sorted.partition_point(|&(t, _)| t < tag);
+        let &(found, i) = self.sorted.get(idx)?;
+        (found == tag).then(|| FieldsView::field(self, i as usize))
+    }
+
+    #[inline]
+    fn groups(&self, spec: &GroupSpec) -> GroupIter<'a> {
+        self.as_message().groups(spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::group::SIDES;
+    use crate::tag;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    fn order_id<'a>(v: &impl FieldsView<'a>) -> Option<&'a [u8]> {
+        v.find(tag::ORDER_ID).map(|f| f.value)
+    }
+
+    fn tags<'a>(v: &impl FieldsView<'a>) -> Vec<Tag> {
+        v.fields().map(|f| f.tag).collect()
+    }
+
+    #[test]
+    fn same_helper_over_message_and_group() {
+        let raw = fix("35=8|37=TOP|552=1|54=1|37=ORD1|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+        let side = msg.groups(&SIDES).next().unwrap();
+
+        assert_eq!(order_id(&msg), Some(&b"TOP"[..]));
+        assert_eq!(order_id(&side), Some(&b"ORD1"[..]));
+        assert_eq!(tags(&side), vec![tag::SIDE, tag::ORDER_ID]);
+        assert_eq!(FieldsView::len(&msg), 5);
+        assert!(!FieldsView::is_empty(&side));
+    }
+
+    #[test]
+    fn header_body_trailer_split() {
+        let raw = fix("8=FIX.4.4|9=40|35=D|49=A|56=B|627=1|628=H1|11=C1|55=X|93=2|89=ab|10=000|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let header = msg.header();
+        assert_eq!(tags(&header), vec![8, 9, 35, 49, 56, 627, 628]);
+        assert_eq!(header.groups(&crate::group::HOPS).count(), 1);
+        assert_eq!(tags(&msg.body()), vec![tag::CL_ORD_ID, tag::SYMBOL]);
+        assert_eq!(order_id(&msg.body()), None);
+        assert_eq!(tags(&msg.trailer()), vec![93, 89, 10]);
+        assert!(msg.body().find(tag::SENDER_COMP_ID).is_none());
+
+        let raw = fix("35=0|");
+        let msg = dec.decode(&raw).unwrap();
+        assert_eq!(msg.header().len(), 1);
+        assert!(msg.body().is_empty() && msg.trailer().is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+use crate::field::Field;
+use crate::message::Message;
+use crate::tag::Tag;
+
+/// Extracts a fixed set of tags from every message in a single pass.
+///
+/// Configure once with the tags of interest, then call [`fill`] or [`watch`]
+/// per decoded message. The watcher walks the message's fields once and stops
+/// as soon as every slot is filled — no sorted index is built and no `find()`
+/// calls are made, which is cheaper than `N` lookups when a strategy loop
+/// reads the same handful of tags from every message.
+///
+/// Slot `i` receives the first occurrence of `tags[i]`, matching the
+/// semantics of [`Message::find`]. Slots for absent tags are empty.
+///
+/// # Example
+/// ```ignore
+/// const ER: FieldWatcher<6> = FieldWatcher::new([
+///     tag::CL_ORD_ID, tag::EXEC_TYPE, tag::ORD_STATUS,
+///     tag::LAST_PX, tag::LAST_QTY, tag::CUM_QTY,
+/// ]);
+///
+/// let mut slots = WatchSlots::new();
+/// loop {
+///     let msg = decoder.decode(buf)?;
+///     ER.fill(&msg, &mut slots);
+///     let last_px = slots.get(&msg, 3);
+/// }
+/// ```
+///
+/// [`fill`]: FieldWatcher::fill
+/// [`watch`]: FieldWatcher::watch
+#[derive(Debug, Clone, Copy)]
+pub struct FieldWatcher<const N: usize> {
+    tags: [Tag; N],
+}
+
+impl<const N: usize> FieldWatcher<N> {
+    /// Create a watcher for `tags`. Slot order follows the order given here.
+    pub const fn new(tags: [Tag; N]) -> Self {
+        Self { tags }
+    }
+
+    /// The watched tags, in slot order.
+    #[inline]
+    pub fn tags(&self) -> &[Tag; N] {
+        &self.tags
+    }
+
+    /// Record the positions of the watched fields of `msg` into `slots`.
+    ///
+    /// Every slot is overwritten, so the same `WatchSlots` can be reused
+    /// across messages without stale values leaking through. Slots store field
+    /// indices rather than borrowed values, which lets them outlive each
+    /// decoded message.
+    pub fn fill(&self, msg: &Message<'_>, slots: &mut WatchSlots<N>) {
+        slots.indices = [ABSENT; N];
+
+        let mut remaining = N;
+        for (pos, &(t, _, _)) in msg.offsets.iter().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+            for (i, &watched) in self.tags.iter().enumerate() {
+                if watched == t && slots.indices[i] == ABSENT {
+                    slots.indices[i] = pos as u32;
+                    remaining -= 1;
+                }
+            }
+        }
+    }
+
+    /// Return the watched fields of `msg` as an array of zero-copy fields,
+    /// one per slot.
+    pub fn watch<'a>(&self, msg: &Message<'a>) -> [Option<Field<'a>>; N] {
+        let mut slots = WatchSlots::new();
+        self.fill(msg, &mut slots);
+        std::array::from_fn(|i| slots.get(msg, i))
+    }
+}
+
+/// Sentinel for a slot whose tag was not found.
+const ABSENT: u32 = u32::MAX;
+
+/// Reusable per-message result of [`FieldWatcher::fill`].
+///
+/// Holds the field index of each watched tag. Values are resolved zero-copy
+/// against the message they were filled from via [`get`](Self::get).
+#[derive(Debug, Clone, Copy)]
+pub struct WatchSlots<const N: usize> {
+    indices: [u32; N],
+}
+
+impl<const N: usize> Default for WatchSlots<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> WatchSlots<N> {
+    /// Create an empty set of slots.
+    pub const fn new() -> Self {
+        Self {
+            indices: [ABSENT; N],
+        }
+    }
+
+    /// Returns true if the tag watched by `slot` was present.
+    #[inline]
+    pub fn is_present(&self, slot: usize) -> bool {
+        self.indices[slot] != ABSENT
+    }
+
+    /// Index of the field recorded in `slot`, if the tag was present.
+    #[inline]
+    pub fn index(&self, slot: usize) -> Option<usize> {
+        match self.indices[slot] {
+            ABSENT => None,
+            i => Some(i as usize),
+        }
+    }
+
+    /// Resolve `slot` against `msg`, which must be the message these slots
+    /// were last filled from.
+    #[inline]
+    pub fn get<'a>(&self, msg: &Message<'a>, slot: usize) -> Option<Field<'a>> {
+        self.index(slot).map(|i| msg.field(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::tag;
+
+    fn fix(s: &str) -> Vec<u8> {
+        s.bytes()
+            .map(|b| if b == b'|' { 0x01 } else { b })
+            .collect()
+    }
+
+    const ER: FieldWatcher<4> =
+        FieldWatcher::new([tag::CL_ORD_ID, tag::EXEC_TYPE, tag::LAST_PX, tag::CUM_QTY]);
+
+    #[test]
+    fn watch_fills_slots_in_configured_order() {
+        let raw = fix("35=8|14=100|11=ORD1|150=F|31=150.25|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let [cl_ord_id, exec_type, last_px, cum_qty] = ER.watch(&msg);
+        assert_eq!(cl_ord_id.unwrap().value, b"ORD1");
+        assert_eq!(exec_type.unwrap().value, b"F");
+        assert_eq!(last_px.unwrap().value, b"150.25");
+        assert_eq!(cum_qty.unwrap().value, b"100");
+    }
+
+    #[test]
+    fn fill_resets_slots_between_messages() {
+        let mut dec = Decoder::new();
+        let mut slots = WatchSlots::new();
+
+        let raw1 = fix("35=8|11=ORD1|31=1.5|");
+        let msg = dec.decode(&raw1).unwrap();
+        ER.fill(&msg, &mut slots);
+        assert_eq!(slots.get(&msg, 2).unwrap().value, b"1.5");
+
+        let raw2 = fix("35=8|11=ORD2|");
+        let msg = dec.decode(&raw2).unwrap();
+        ER.fill(&msg, &mut slots);
+        assert_eq!(slots.get(&msg, 0).unwrap().value, b"ORD2");
+        assert_eq!(slots.index(0), Some(1));
+        assert!(!slots.is_present(1));
+        assert!(!slots.is_present(2));
+        assert!(slots.get(&msg, 3).is_none());
+    }
+
+    #[test]
+    fn first_occurrence_wins() {
+        let raw = fix("35=8|11=FIRST|11=SECOND|");
+        let mut dec = Decoder::new();
+        let msg = dec.decode(&raw).unwrap();
+
+        let [cl_ord_id, ..] = ER.watch(&msg);
+        assert_eq!(cl_ord_id.unwrap().value, b"FIRST");
+    }
+}